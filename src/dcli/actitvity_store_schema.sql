@@ -1,3 +1,6 @@
+-- base schema for the activity store (version 6). Later schema changes are
+-- applied to it as migrations, which are defined in storeschema.rs
+
 BEGIN TRANSACTION;
 
 DROP TABLE IF EXISTS "modes";
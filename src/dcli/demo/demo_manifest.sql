@@ -0,0 +1,44 @@
+--generated manifest extract used by the dcli --demo flag. All players,
+--members and activities are fictional.
+
+BEGIN TRANSACTION;
+
+CREATE TABLE "DestinyActivityDefinition" ("id" INTEGER PRIMARY KEY NOT NULL, "json" TEXT);
+CREATE TABLE "DestinyInventoryItemDefinition" ("id" INTEGER PRIMARY KEY NOT NULL, "json" TEXT);
+CREATE TABLE "DestinyHistoricalStatsDefinition" ("key" TEXT PRIMARY KEY NOT NULL, "json" TEXT);
+CREATE TABLE "DestinyActivityModeDefinition" ("id" INTEGER PRIMARY KEY NOT NULL, "json" TEXT);
+
+INSERT INTO "DestinyActivityDefinition" VALUES (1003889713, '{"hash": 1003889713, "displayProperties": {"name": "Javelin-4", "description": "Warsat Impact Site, Io", "hasIcon": false}, "destinationHash": 0, "placeHash": 0, "activityTypeHash": 0}');
+INSERT INTO "DestinyActivityDefinition" VALUES (-1130052039, '{"hash": 3164915257, "displayProperties": {"name": "Midtown", "description": "The Last City, Earth", "hasIcon": false}, "destinationHash": 0, "placeHash": 0, "activityTypeHash": 0}');
+INSERT INTO "DestinyActivityDefinition" VALUES (1489679220, '{"hash": 1489679220, "displayProperties": {"name": "Endless Vale", "description": "Arcadian Valley, Nessus", "hasIcon": false}, "destinationHash": 0, "placeHash": 0, "activityTypeHash": 0}');
+INSERT INTO "DestinyActivityDefinition" VALUES (-445170432, '{"hash": 3849796864, "displayProperties": {"name": "Wormhaven", "description": "Grand Stair, Savathûn''s Throne World", "hasIcon": false}, "destinationHash": 0, "placeHash": 0, "activityTypeHash": 0}');
+INSERT INTO "DestinyActivityDefinition" VALUES (-1628206074, '{"hash": 2666761222, "displayProperties": {"name": "The Dead Cliffs", "description": "Mars", "hasIcon": false}, "destinationHash": 0, "placeHash": 0, "activityTypeHash": 0}');
+INSERT INTO "DestinyActivityDefinition" VALUES (532383918, '{"hash": 532383918, "displayProperties": {"name": "Radiant Cliffs", "description": "Mercury", "hasIcon": false}, "destinationHash": 0, "placeHash": 0, "activityTypeHash": 0}');
+
+INSERT INTO "DestinyInventoryItemDefinition" VALUES (347366834, '{"hash": 347366834, "displayProperties": {"name": "Ace of Spades", "description": "", "hasIcon": false}, "itemTypeDisplayName": "Hand Cannon", "itemTypeAndTierDisplayName": "Hand Cannon", "itemType": 3, "itemSubType": 9}');
+INSERT INTO "DestinyInventoryItemDefinition" VALUES (-169982848, '{"hash": 4124984448, "displayProperties": {"name": "Not Forgotten", "description": "", "hasIcon": false}, "itemTypeDisplayName": "Hand Cannon", "itemTypeAndTierDisplayName": "Hand Cannon", "itemType": 3, "itemSubType": 9}');
+INSERT INTO "DestinyInventoryItemDefinition" VALUES (-51962905, '{"hash": 4243004391, "displayProperties": {"name": "Felwinter''s Lie", "description": "", "hasIcon": false}, "itemTypeDisplayName": "Shotgun", "itemTypeAndTierDisplayName": "Shotgun", "itemType": 3, "itemSubType": 7}');
+INSERT INTO "DestinyInventoryItemDefinition" VALUES (1119734784, '{"hash": 1119734784, "displayProperties": {"name": "Chroma Rush", "description": "", "hasIcon": false}, "itemTypeDisplayName": "Auto Rifle", "itemTypeAndTierDisplayName": "Auto Rifle", "itemType": 3, "itemSubType": 6}');
+INSERT INTO "DestinyInventoryItemDefinition" VALUES (1234150730, '{"hash": 1234150730, "displayProperties": {"name": "Bygones", "description": "", "hasIcon": false}, "itemTypeDisplayName": "Pulse Rifle", "itemTypeAndTierDisplayName": "Pulse Rifle", "itemType": 3, "itemSubType": 13}');
+INSERT INTO "DestinyInventoryItemDefinition" VALUES (2069224589, '{"hash": 2069224589, "displayProperties": {"name": "Beloved", "description": "", "hasIcon": false}, "itemTypeDisplayName": "Sniper Rifle", "itemTypeAndTierDisplayName": "Sniper Rifle", "itemType": 3, "itemSubType": 12}');
+INSERT INTO "DestinyInventoryItemDefinition" VALUES (-759224337, '{"hash": 3535742959, "displayProperties": {"name": "Succession", "description": "", "hasIcon": false}, "itemTypeDisplayName": "Sniper Rifle", "itemTypeAndTierDisplayName": "Sniper Rifle", "itemType": 3, "itemSubType": 12}');
+INSERT INTO "DestinyInventoryItemDefinition" VALUES (1725420063, '{"hash": 1725420063, "displayProperties": {"name": "Igneous Hammer", "description": "", "hasIcon": false}, "itemTypeDisplayName": "Hand Cannon", "itemTypeAndTierDisplayName": "Hand Cannon", "itemType": 3, "itemSubType": 9}');
+INSERT INTO "DestinyInventoryItemDefinition" VALUES (-640292735, '{"hash": 3654674561, "displayProperties": {"name": "Dead Man''s Tale", "description": "", "hasIcon": false}, "itemTypeDisplayName": "Scout Rifle", "itemTypeAndTierDisplayName": "Scout Rifle", "itemType": 3, "itemSubType": 14}');
+INSERT INTO "DestinyInventoryItemDefinition" VALUES (1321506184, '{"hash": 1321506184, "displayProperties": {"name": "Eyasluna", "description": "", "hasIcon": false}, "itemTypeDisplayName": "Hand Cannon", "itemTypeAndTierDisplayName": "Hand Cannon", "itemType": 3, "itemSubType": 9}');
+INSERT INTO "DestinyInventoryItemDefinition" VALUES (1366394399, '{"hash": 1366394399, "displayProperties": {"name": "The Messenger", "description": "", "hasIcon": false}, "itemTypeDisplayName": "Pulse Rifle", "itemTypeAndTierDisplayName": "Pulse Rifle", "itemType": 3, "itemSubType": 13}');
+INSERT INTO "DestinyInventoryItemDefinition" VALUES (-1918485746, '{"hash": 2376481550, "displayProperties": {"name": "Riskrunner", "description": "", "hasIcon": false}, "itemTypeDisplayName": "Submachine Gun", "itemTypeAndTierDisplayName": "Submachine Gun", "itemType": 3, "itemSubType": 24}');
+
+INSERT INTO "DestinyHistoricalStatsDefinition" VALUES ('medalFirstBlood', '{"statId": "medalFirstBlood", "statName": "First Blood", "statDescription": "Get the first kill of the match.", "weight": 1, "medalTierHash": 802673297}');
+INSERT INTO "DestinyHistoricalStatsDefinition" VALUES ('medalMulti2x', '{"statId": "medalMulti2x", "statName": "Double Down", "statDescription": "Defeat two opponents in rapid succession.", "weight": 1, "medalTierHash": 802673298}');
+INSERT INTO "DestinyHistoricalStatsDefinition" VALUES ('medalMulti3x', '{"statId": "medalMulti3x", "statName": "Triple Down", "statDescription": "Defeat three opponents in rapid succession.", "weight": 1, "medalTierHash": 802673297}');
+INSERT INTO "DestinyHistoricalStatsDefinition" VALUES ('medalStreak5x', '{"statId": "medalStreak5x", "statName": "Rampage", "statDescription": "Defeat five opponents without dying.", "weight": 1, "medalTierHash": 802673296}');
+INSERT INTO "DestinyHistoricalStatsDefinition" VALUES ('medalPayback', '{"statId": "medalPayback", "statName": "Payback", "statDescription": "Defeat the opponent who last defeated you.", "weight": 1, "medalTierHash": 802673298}');
+INSERT INTO "DestinyHistoricalStatsDefinition" VALUES ('medalAvenger', '{"statId": "medalAvenger", "statName": "Avenger", "statDescription": "Defeat an opponent who recently defeated a teammate.", "weight": 1, "medalTierHash": 802673298}');
+INSERT INTO "DestinyHistoricalStatsDefinition" VALUES ('medalMatchUndefeated', '{"statId": "medalMatchUndefeated", "statName": "We Ran Out of Medals", "statDescription": "Complete a match without dying.", "weight": 1, "medalTierHash": 802673300}');
+
+INSERT INTO "DestinyActivityModeDefinition" VALUES (1164760493, '{"hash": 1164760493, "displayProperties": {"name": "Crucible", "hasIcon": false}, "modeType": 5, "friendlyName": "crucible", "display": true}');
+INSERT INTO "DestinyActivityModeDefinition" VALUES (1164760504, '{"hash": 1164760504, "displayProperties": {"name": "Control", "hasIcon": false}, "modeType": 10, "friendlyName": "control", "display": true}');
+INSERT INTO "DestinyActivityModeDefinition" VALUES (2096553452, '{"hash": 2096553452, "displayProperties": {"name": "Clash", "hasIcon": false}, "modeType": 12, "friendlyName": "clash", "display": true}');
+INSERT INTO "DestinyActivityModeDefinition" VALUES (-2056662307, '{"hash": 2238304989, "displayProperties": {"name": "Elimination", "hasIcon": false}, "modeType": 80, "friendlyName": "elimination", "display": true}');
+
+COMMIT;
@@ -0,0 +1,718 @@
+--generated sample data used by the dcli --demo flag. All players,
+--members and activities are fictional.
+
+BEGIN TRANSACTION;
+
+INSERT INTO "member" ("id", "member_id", "platform_id", "display_name") VALUES (1, '4611686018400000001', 3, 'DemoGuardian');
+INSERT INTO "member" ("id", "member_id", "platform_id", "display_name") VALUES (2, '4611686018400000002', 3, 'Ashen Veil');
+INSERT INTO "member" ("id", "member_id", "platform_id", "display_name") VALUES (3, '4611686018400000003', 2, 'Moth Lantern');
+INSERT INTO "member" ("id", "member_id", "platform_id", "display_name") VALUES (4, '4611686018400000004', 1, 'Orbit Sparrow');
+INSERT INTO "member" ("id", "member_id", "platform_id", "display_name") VALUES (5, '4611686018400000005', 3, 'Quiet Anvil');
+INSERT INTO "member" ("id", "member_id", "platform_id", "display_name") VALUES (6, '4611686018400000006', 2, 'Hollow Ember');
+INSERT INTO "member" ("id", "member_id", "platform_id", "display_name") VALUES (7, '4611686018400000007', 1, 'Tin Heron');
+INSERT INTO "member" ("id", "member_id", "platform_id", "display_name") VALUES (8, '4611686018400000008', 3, 'Velvet Comet');
+INSERT INTO "member" ("id", "member_id", "platform_id", "display_name") VALUES (9, '4611686018400000009', 2, 'Rust Siren');
+INSERT INTO "member" ("id", "member_id", "platform_id", "display_name") VALUES (10, '4611686018400000010', 3, 'Paper Titan');
+INSERT INTO "member" ("id", "member_id", "platform_id", "display_name") VALUES (11, '4611686018400000011', 1, 'Glass Otter');
+INSERT INTO "member" ("id", "member_id", "platform_id", "display_name") VALUES (12, '4611686018400000012', 3, 'Nine Lanterns');
+INSERT INTO "member" ("id", "member_id", "platform_id", "display_name") VALUES (13, '4611686018400000013', 2, 'Cobalt Finch');
+INSERT INTO "member" ("id", "member_id", "platform_id", "display_name") VALUES (14, '4611686018400000014', 3, 'Static Bloom');
+
+INSERT INTO "character" ("id", "character_id", "member", "class") VALUES (1, '2305843000000000001', 1, 1);
+INSERT INTO "character" ("id", "character_id", "member", "class") VALUES (2, '2305843000000000002', 1, 2);
+INSERT INTO "character" ("id", "character_id", "member", "class") VALUES (3, '2305843000000000003', 2, 1);
+INSERT INTO "character" ("id", "character_id", "member", "class") VALUES (4, '2305843000000000004', 3, 0);
+INSERT INTO "character" ("id", "character_id", "member", "class") VALUES (5, '2305843000000000005', 4, 2);
+INSERT INTO "character" ("id", "character_id", "member", "class") VALUES (6, '2305843000000000006', 5, 2);
+INSERT INTO "character" ("id", "character_id", "member", "class") VALUES (7, '2305843000000000007', 6, 1);
+INSERT INTO "character" ("id", "character_id", "member", "class") VALUES (8, '2305843000000000008', 7, 1);
+INSERT INTO "character" ("id", "character_id", "member", "class") VALUES (9, '2305843000000000009', 8, 2);
+INSERT INTO "character" ("id", "character_id", "member", "class") VALUES (10, '2305843000000000010', 9, 2);
+INSERT INTO "character" ("id", "character_id", "member", "class") VALUES (11, '2305843000000000011', 10, 2);
+INSERT INTO "character" ("id", "character_id", "member", "class") VALUES (12, '2305843000000000012', 11, 1);
+INSERT INTO "character" ("id", "character_id", "member", "class") VALUES (13, '2305843000000000013', 12, 1);
+INSERT INTO "character" ("id", "character_id", "member", "class") VALUES (14, '2305843000000000014', 13, 2);
+INSERT INTO "character" ("id", "character_id", "member", "class") VALUES (15, '2305843000000000015', 14, 1);
+
+--Clash on Radiant Cliffs
+INSERT INTO "activity" ("id", "activity_id", "period", "mode", "platform", "director_activity_hash", "reference_id") VALUES (1, 9100000001, strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now', '-9 hours'), 12, 3, 2259621242, 532383918);
+INSERT INTO "modes" ("mode", "activity") VALUES (5, 1);
+INSERT INTO "modes" ("mode", "activity") VALUES (12, 1);
+INSERT INTO "modes" ("mode", "activity") VALUES (70, 1);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (17, 1, 48, 1);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (18, 1, 91, 0);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (1, 1, 1, 3, 1750, 16, 17, 109.38, 102.94, 1, 19, 600, 1, 17, 1, 0, 600, 12, 48, 6, 0, 0, 1, 2, 2, 1820);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2069224589, 10, 5, 0.50, 1);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 3, 1, 0.33, 1);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 1, 1);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 1, 1);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (2, 5, 1, 9, 2850, 24, 9, 118.75, 316.67, 1, 33, 600, 1, 17, 1, 0, 600, 12, 48, 8, 1, 2, 0, 0, 0, 1808);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 8, 5, 0.62, 2);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1119734784, 11, 0, 0.00, 2);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 3, 3, 1.00, 2);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (3, 6, 1, 10, 1900, 14, 9, 135.71, 211.11, 1, 24, 600, 1, 17, 1, 0, 600, 12, 48, 4, 1, 0, 1, 1, 0, 1819);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 5, 2, 0.40, 3);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2069224589, 3, 0, 0.00, 3);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 4, 2, 0.50, 3);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (4, 13, 1, 6, 1200, 9, 9, 133.33, 133.33, 1, 15, 600, 1, 17, 1, 0, 600, 12, 48, 1, 0, 1, 2, 1, 0, 1824);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 5, 1, 0.20, 4);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (5, 3, 1, 6, 2100, 18, 10, 116.67, 210.00, 1, 24, 600, 1, 17, 1, 0, 600, 12, 48, 3, 0, 1, 1, 2, 0, 1802);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 14, 3, 0.21, 5);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (6, 10, 1, 8, 2800, 24, 16, 116.67, 175.00, 1, 32, 600, 1, 17, 1, 0, 600, 12, 48, 8, 0, 0, 2, 1, 3, 1805);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 20, 7, 0.35, 6);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 1, 1, 1.00, 6);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 1, 6);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 2, 6);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (7, 15, 1, 11, 3150, 26, 12, 121.15, 262.50, 1, 37, 600, 0, 18, 1, 0, 600, 12, 91, 10, 1, 2, 0, 1, 1, 1814);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 16, 6, 0.38, 7);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1119734784, 5, 2, 0.40, 7);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 2, 2, 1.00, 7);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 1, 7);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (8, 8, 1, 11, 2650, 21, 9, 126.19, 294.44, 1, 32, 600, 0, 18, 1, 0, 600, 12, 91, 9, 0, 1, 1, 1, 2, 1802);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 17, 8, 0.47, 8);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 1, 1, 1.00, 8);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 8);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (9, 14, 1, 10, 1800, 13, 15, 138.46, 120.00, 1, 23, 600, 0, 18, 1, 0, 600, 12, 91, 6, 0, 2, 1, 1, 0, 1821);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 9, 6, 0.67, 9);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (10, 9, 1, 8, 2000, 16, 14, 125.00, 142.86, 1, 24, 600, 0, 18, 1, 0, 600, 12, 91, 2, 0, 0, 1, 2, 3, 1829);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 13, 2, 0.15, 10);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 1, 10);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 2, 10);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (11, 12, 1, 9, 1750, 13, 17, 134.62, 102.94, 1, 22, 600, 0, 18, 1, 0, 600, 12, 91, 4, 0, 1, 0, 2, 0, 1820);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1119734784, 9, 4, 0.44, 11);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 1, 0, 0.00, 11);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (12, 7, 1, 2, 1000, 9, 17, 111.11, 58.82, 1, 11, 600, 0, 18, 1, 0, 600, 12, 91, 4, 1, 0, 0, 2, 2, 1801);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 6, 3, 0.50, 12);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 1, 1, 1.00, 12);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 1, 12);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 1, 12);
+
+--Elimination on Wormhaven
+INSERT INTO "activity" ("id", "activity_id", "period", "mode", "platform", "director_activity_hash", "reference_id") VALUES (2, 9100000002, strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now', '-15 hours'), 80, 3, 2259621310, 3849796864);
+INSERT INTO "modes" ("mode", "activity") VALUES (5, 2);
+INSERT INTO "modes" ("mode", "activity") VALUES (80, 2);
+INSERT INTO "modes" ("mode", "activity") VALUES (69, 2);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (17, 2, 6, 0);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (18, 2, 4, 1);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (13, 1, 2, 3, 1550, 14, 3, 110.71, 516.67, 1, 17, 447, 0, 17, 0, 0, 447, 6, 6, 5, 0, 0, 2, 1, 0, 1807);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 6, 5, 0.83, 13);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 5, 0, 0.00, 13);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (14, 14, 2, 1, 950, 9, 10, 105.56, 95.00, 1, 10, 447, 0, 17, 0, 0, 447, 6, 6, 2, 0, 0, 1, 1, 5, 1825);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 7, 2, 0.29, 14);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 2, 14);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 14);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 14);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (15, 9, 2, 3, 850, 7, 7, 121.43, 121.43, 1, 10, 447, 0, 17, 0, 0, 447, 6, 6, 1, 0, 2, 0, 2, 0, 1812);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 3, 1, 0.33, 15);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (16, 5, 2, 6, 900, 6, 10, 150.00, 90.00, 1, 12, 447, 1, 18, 0, 0, 447, 6, 4, 3, 1, 2, 0, 1, 2, 1830);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 1, 1, 1.00, 16);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2069224589, 2, 2, 1.00, 16);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 16);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (17, 3, 2, 6, 1300, 10, 8, 130.00, 162.50, 1, 16, 447, 1, 18, 0, 0, 447, 6, 4, 0, 0, 2, 1, 1, 1, 1830);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 5, 0, 0.00, 17);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 1, 0, 0.00, 17);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 1, 17);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (18, 13, 2, 5, 450, 2, 4, 225.00, 112.50, 1, 7, 447, 1, 18, 0, 0, 447, 6, 4, 0, 0, 1, 0, 1, 2, 1801);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 2, 18);
+
+--Control on Javelin-4
+INSERT INTO "activity" ("id", "activity_id", "period", "mode", "platform", "director_activity_hash", "reference_id") VALUES (3, 9100000003, strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now', '-16 hours'), 10, 3, 2259621240, 1003889713);
+INSERT INTO "modes" ("mode", "activity") VALUES (5, 3);
+INSERT INTO "modes" ("mode", "activity") VALUES (10, 3);
+INSERT INTO "modes" ("mode", "activity") VALUES (70, 3);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (17, 3, 54, 1);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (18, 3, 122, 0);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (19, 1, 3, 5, 2850, 26, 7, 109.62, 407.14, 1, 31, 600, 1, 17, 1, 0, 600, 12, 54, 19, 1, 1, 0, 0, 3, 1822);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 25, 19, 0.76, 19);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 2, 19);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 19);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (20, 8, 3, 10, 2600, 21, 12, 123.81, 216.67, 1, 31, 600, 1, 17, 1, 0, 600, 12, 54, 8, 0, 0, 2, 2, 0, 1817);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 12, 4, 0.33, 20);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 4, 3, 0.75, 20);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2069224589, 1, 1, 1.00, 20);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (21, 15, 3, 6, 2300, 20, 17, 115.00, 135.29, 1, 26, 600, 1, 17, 1, 0, 600, 12, 54, 2, 1, 0, 1, 2, 3, 1803);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1119734784, 17, 2, 0.12, 21);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 21);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 1, 21);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (22, 4, 3, 4, 1000, 8, 14, 125.00, 71.43, 1, 12, 600, 1, 17, 1, 0, 600, 12, 54, 0, 0, 0, 1, 0, 3, 1828);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1119734784, 4, 0, 0.00, 22);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 3, 0, 0.00, 22);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 1, 22);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 22);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (23, 7, 3, 11, 2150, 16, 13, 134.38, 165.38, 1, 27, 600, 1, 17, 1, 0, 600, 12, 54, 4, 0, 1, 0, 0, 4, 1820);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 14, 3, 0.21, 23);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 1, 1, 1.00, 23);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 23);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 23);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (24, 9, 3, 7, 2050, 17, 9, 120.59, 227.78, 1, 24, 600, 1, 17, 1, 0, 600, 12, 54, 3, 1, 2, 2, 1, 6, 1808);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 12, 3, 0.25, 24);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 2, 24);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 24);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 24);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (25, 12, 3, 3, 950, 8, 6, 118.75, 158.33, 1, 11, 600, 0, 18, 1, 0, 600, 12, 122, 1, 1, 2, 2, 1, 0, 1824);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 2, 0, 0.00, 25);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 1, 1, 1.00, 25);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (26, 13, 3, 10, 1000, 5, 8, 200.00, 125.00, 1, 15, 600, 0, 18, 1, 0, 600, 12, 122, 2, 1, 1, 1, 1, 1, 1801);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 1, 1, 1.00, 26);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 1, 1, 1.00, 26);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 26);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (27, 5, 3, 4, 2800, 26, 13, 107.69, 215.38, 1, 30, 600, 0, 18, 1, 0, 600, 12, 122, 1, 1, 1, 0, 0, 2, 1800);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 25, 1, 0.04, 27);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 2, 27);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (28, 10, 3, 8, 2900, 25, 19, 116.00, 152.63, 1, 33, 600, 0, 18, 1, 0, 600, 12, 122, 18, 1, 1, 0, 1, 2, 1801);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 16, 14, 0.88, 28);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 3, 1, 0.33, 28);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 4, 3, 0.75, 28);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 1, 28);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 1, 28);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (29, 6, 3, 10, 2700, 22, 15, 122.73, 180.00, 1, 32, 600, 0, 18, 1, 0, 600, 12, 122, 11, 0, 0, 1, 1, 1, 1814);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 9, 0, 0.00, 29);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 7, 7, 1.00, 29);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 4, 4, 1.00, 29);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 1, 29);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (30, 14, 3, 8, 2500, 21, 19, 119.05, 131.58, 1, 29, 600, 0, 18, 1, 0, 600, 12, 122, 3, 0, 1, 2, 2, 1, 1802);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 14, 1, 0.07, 30);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2069224589, 2, 2, 1.00, 30);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 1, 30);
+
+--Elimination on Midtown
+INSERT INTO "activity" ("id", "activity_id", "period", "mode", "platform", "director_activity_hash", "reference_id") VALUES (4, 9100000004, strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now', '-24 hours'), 80, 3, 2259621310, 3164915257);
+INSERT INTO "modes" ("mode", "activity") VALUES (5, 4);
+INSERT INTO "modes" ("mode", "activity") VALUES (80, 4);
+INSERT INTO "modes" ("mode", "activity") VALUES (69, 4);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (17, 4, 6, 0);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (18, 4, 3, 1);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (31, 1, 4, 0, 600, 6, 9, 100.00, 66.67, 1, 6, 490, 0, 17, 0, 0, 490, 6, 6, 1, 1, 2, 1, 1, 0, 1830);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 2, 1, 0.50, 31);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (32, 15, 4, 5, 1050, 8, 5, 131.25, 210.00, 1, 13, 490, 0, 17, 0, 0, 490, 6, 6, 0, 1, 2, 1, 1, 5, 1821);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 4, 0, 0.00, 32);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 32);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 32);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 32);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (33, 11, 4, 3, 1150, 10, 3, 115.00, 383.33, 1, 13, 490, 0, 17, 0, 0, 490, 6, 6, 5, 1, 2, 1, 1, 2, 1801);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 4, 4, 1.00, 33);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 1, 1, 1.00, 33);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 1, 0, 0.00, 33);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 2, 33);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (34, 13, 4, 0, 600, 6, 4, 100.00, 150.00, 1, 6, 490, 1, 18, 0, 0, 490, 6, 3, 2, 0, 1, 0, 2, 0, 1813);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 1, 1, 1.00, 34);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 2, 1, 0.50, 34);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (35, 3, 4, 2, 1000, 9, 10, 111.11, 100.00, 1, 11, 490, 1, 18, 0, 0, 490, 6, 3, 1, 1, 2, 2, 0, 2, 1829);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 3, 0, 0.00, 35);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 2, 1, 0.50, 35);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 35);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (36, 7, 4, 6, 1400, 11, 10, 127.27, 140.00, 1, 17, 490, 1, 18, 0, 0, 490, 6, 3, 6, 1, 0, 2, 1, 6, 1824);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 8, 6, 0.75, 36);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 2, 36);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 2, 36);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 36);
+
+--Elimination on Endless Vale
+INSERT INTO "activity" ("id", "activity_id", "period", "mode", "platform", "director_activity_hash", "reference_id") VALUES (5, 9100000005, strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now', '-26 hours'), 80, 3, 2259621310, 1489679220);
+INSERT INTO "modes" ("mode", "activity") VALUES (5, 5);
+INSERT INTO "modes" ("mode", "activity") VALUES (80, 5);
+INSERT INTO "modes" ("mode", "activity") VALUES (69, 5);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (17, 5, 1, 1);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (18, 5, 6, 0);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (37, 2, 5, 2, 900, 8, 6, 112.50, 150.00, 1, 10, 568, 1, 17, 0, 0, 568, 6, 1, 1, 0, 2, 1, 2, 3, 1807);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 3, 1, 0.33, 37);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 1, 37);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 2, 37);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (38, 9, 5, 0, 1100, 11, 3, 100.00, 366.67, 1, 11, 568, 1, 17, 0, 0, 568, 6, 1, 1, 0, 2, 2, 0, 2, 1806);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 4, 0, 0.00, 38);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 3, 1, 0.33, 38);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 38);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (39, 11, 5, 0, 900, 9, 6, 100.00, 150.00, 1, 9, 568, 1, 17, 0, 0, 568, 6, 1, 3, 0, 2, 1, 2, 5, 1812);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1119734784, 1, 0, 0.00, 39);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 1, 1, 1.00, 39);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 2, 2, 1.00, 39);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 39);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 2, 39);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 1, 39);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (40, 14, 5, 1, 650, 6, 3, 108.33, 216.67, 1, 7, 568, 0, 18, 0, 0, 568, 6, 6, 2, 0, 2, 0, 2, 1, 1805);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 2, 2, 1.00, 40);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 1, 40);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (41, 6, 5, 2, 700, 6, 8, 116.67, 87.50, 1, 8, 568, 0, 18, 0, 0, 568, 6, 6, 0, 0, 0, 1, 2, 4, 1807);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 3, 0, 0.00, 41);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 41);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 41);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (42, 13, 5, 5, 1250, 10, 4, 125.00, 312.50, 1, 15, 568, 0, 18, 0, 0, 568, 6, 6, 4, 0, 1, 0, 1, 6, 1811);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 1, 0, 0.00, 42);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 5, 2, 0.40, 42);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 2, 2, 1.00, 42);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 42);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 2, 42);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 2, 42);
+
+--Clash on Wormhaven
+INSERT INTO "activity" ("id", "activity_id", "period", "mode", "platform", "director_activity_hash", "reference_id") VALUES (6, 9100000006, strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now', '-35 hours'), 12, 3, 2259621242, 3849796864);
+INSERT INTO "modes" ("mode", "activity") VALUES (5, 6);
+INSERT INTO "modes" ("mode", "activity") VALUES (12, 6);
+INSERT INTO "modes" ("mode", "activity") VALUES (70, 6);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (17, 6, 71, 1);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (18, 6, 91, 0);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (43, 2, 6, 8, 3100, 27, 12, 114.81, 258.33, 1, 35, 600, 1, 17, 1, 0, 600, 12, 71, 19, 1, 2, 2, 1, 0, 1822);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 22, 19, 0.86, 43);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (44, 5, 6, 4, 2000, 18, 13, 111.11, 153.85, 1, 22, 600, 1, 17, 1, 0, 600, 12, 71, 7, 1, 1, 1, 2, 2, 1814);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 14, 7, 0.50, 44);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 2, 44);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (45, 7, 6, 10, 3100, 26, 18, 119.23, 172.22, 1, 36, 600, 1, 17, 1, 0, 600, 12, 71, 12, 0, 2, 2, 2, 1, 1800);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 5, 3, 0.60, 45);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 5, 4, 0.80, 45);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1119734784, 10, 5, 0.50, 45);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 1, 45);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (46, 6, 6, 9, 2950, 25, 8, 118.00, 368.75, 1, 34, 600, 1, 17, 1, 0, 600, 12, 71, 14, 1, 2, 2, 0, 3, 1829);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 18, 13, 0.72, 46);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 2, 1, 0.50, 46);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1119734784, 1, 0, 0.00, 46);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 2, 46);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 1, 46);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (47, 3, 6, 2, 1300, 12, 6, 108.33, 216.67, 1, 14, 600, 1, 17, 1, 0, 600, 12, 71, 8, 0, 1, 1, 0, 5, 1805);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 10, 8, 0.80, 47);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 2, 47);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 1, 47);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 47);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (48, 13, 6, 2, 1600, 15, 12, 106.67, 133.33, 1, 17, 600, 1, 17, 1, 0, 600, 12, 71, 1, 1, 2, 0, 2, 2, 1823);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 11, 1, 0.09, 48);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 1, 48);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 1, 48);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (49, 12, 6, 4, 2300, 21, 12, 109.52, 191.67, 1, 25, 600, 0, 18, 1, 0, 600, 12, 91, 15, 1, 2, 0, 1, 3, 1829);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 5, 4, 0.80, 49);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 6, 4, 0.67, 49);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 7, 7, 1.00, 49);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 1, 49);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 49);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (50, 11, 6, 4, 2100, 19, 20, 110.53, 105.00, 1, 23, 600, 0, 18, 1, 0, 600, 12, 91, 4, 1, 2, 0, 1, 2, 1800);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2069224589, 9, 2, 0.22, 50);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 2, 2, 1.00, 50);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 5, 0, 0.00, 50);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 50);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 1, 50);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (51, 8, 6, 6, 2200, 19, 18, 115.79, 122.22, 1, 25, 600, 0, 18, 1, 0, 600, 12, 91, 6, 0, 0, 0, 1, 1, 1819);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 18, 6, 0.33, 51);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 1, 51);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (52, 14, 6, 9, 2150, 17, 7, 126.47, 307.14, 1, 26, 600, 0, 18, 1, 0, 600, 12, 91, 3, 1, 2, 0, 2, 0, 1801);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 5, 0, 0.00, 52);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 8, 3, 0.38, 52);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (53, 10, 6, 5, 850, 6, 8, 141.67, 106.25, 1, 11, 600, 0, 18, 1, 0, 600, 12, 91, 1, 0, 2, 2, 1, 0, 1824);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 1, 1, 1.00, 53);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (54, 15, 6, 6, 2400, 21, 13, 114.29, 184.62, 1, 27, 600, 0, 18, 1, 0, 600, 12, 91, 17, 1, 1, 0, 0, 0, 1817);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 19, 16, 0.84, 54);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 1, 1, 1.00, 54);
+
+--Clash on Radiant Cliffs
+INSERT INTO "activity" ("id", "activity_id", "period", "mode", "platform", "director_activity_hash", "reference_id") VALUES (7, 9100000007, strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now', '-37 hours'), 12, 3, 2259621242, 532383918);
+INSERT INTO "modes" ("mode", "activity") VALUES (5, 7);
+INSERT INTO "modes" ("mode", "activity") VALUES (12, 7);
+INSERT INTO "modes" ("mode", "activity") VALUES (70, 7);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (17, 7, 71, 1);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (18, 7, 94, 0);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (55, 2, 7, 10, 2300, 18, 7, 127.78, 328.57, 1, 28, 600, 1, 17, 1, 0, 600, 12, 71, 5, 0, 2, 1, 2, 3, 1825);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 4, 2, 0.50, 55);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1119734784, 3, 1, 0.33, 55);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 6, 2, 0.33, 55);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 1, 55);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 55);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (56, 5, 7, 3, 2050, 19, 18, 107.89, 113.89, 1, 22, 600, 1, 17, 1, 0, 600, 12, 71, 1, 1, 2, 2, 1, 3, 1803);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 13, 1, 0.08, 56);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 1, 0, 0.00, 56);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 1, 56);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 2, 56);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (57, 15, 7, 11, 2350, 18, 12, 130.56, 195.83, 1, 29, 600, 1, 17, 1, 0, 600, 12, 71, 4, 1, 0, 1, 2, 2, 1800);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 15, 4, 0.27, 57);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 57);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (58, 3, 7, 9, 2550, 21, 8, 121.43, 318.75, 1, 30, 600, 1, 17, 1, 0, 600, 12, 71, 3, 1, 0, 1, 2, 2, 1807);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2069224589, 18, 3, 0.17, 58);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 2, 58);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (59, 10, 7, 9, 950, 5, 8, 190.00, 118.75, 1, 14, 600, 1, 17, 1, 0, 600, 12, 71, 2, 0, 0, 1, 0, 0, 1828);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 2, 0, 0.00, 59);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 2, 2, 1.00, 59);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (60, 7, 7, 4, 900, 7, 17, 128.57, 52.94, 1, 11, 600, 1, 17, 1, 0, 600, 12, 71, 2, 1, 1, 1, 0, 0, 1800);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 2, 1, 0.50, 60);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 3, 1, 0.33, 60);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (61, 14, 7, 6, 2500, 22, 19, 113.64, 131.58, 1, 28, 600, 0, 18, 1, 0, 600, 12, 94, 14, 1, 2, 1, 2, 6, 1808);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2069224589, 13, 12, 0.92, 61);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 1, 1, 1.00, 61);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 3, 1, 0.33, 61);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 2, 61);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 61);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 61);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (62, 11, 7, 3, 2250, 21, 9, 107.14, 250.00, 1, 24, 600, 0, 18, 1, 0, 600, 12, 94, 0, 1, 0, 2, 1, 5, 1812);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 18, 0, 0.00, 62);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 62);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 1, 62);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 2, 62);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (63, 6, 7, 9, 1850, 14, 16, 132.14, 115.62, 1, 23, 600, 0, 18, 1, 0, 600, 12, 94, 3, 1, 2, 0, 0, 0, 1805);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1119734784, 12, 3, 0.25, 63);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (64, 8, 7, 5, 1050, 8, 9, 131.25, 116.67, 1, 13, 600, 0, 18, 1, 0, 600, 12, 94, 3, 0, 1, 1, 0, 0, 1801);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 6, 3, 0.50, 64);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (65, 13, 7, 5, 1450, 12, 8, 120.83, 181.25, 1, 17, 600, 0, 18, 1, 0, 600, 12, 94, 5, 0, 0, 0, 1, 3, 1813);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 4, 1, 0.25, 65);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 7, 4, 0.57, 65);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 1, 65);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 65);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (66, 9, 7, 3, 2450, 23, 18, 106.52, 136.11, 1, 26, 600, 0, 18, 1, 0, 600, 12, 94, 13, 1, 2, 1, 1, 3, 1821);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 13, 9, 0.69, 66);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1119734784, 6, 4, 0.67, 66);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 66);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 66);
+
+--Elimination on Endless Vale
+INSERT INTO "activity" ("id", "activity_id", "period", "mode", "platform", "director_activity_hash", "reference_id") VALUES (8, 9100000008, strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now', '-40 hours'), 80, 3, 2259621310, 1489679220);
+INSERT INTO "modes" ("mode", "activity") VALUES (5, 8);
+INSERT INTO "modes" ("mode", "activity") VALUES (80, 8);
+INSERT INTO "modes" ("mode", "activity") VALUES (69, 8);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (17, 8, 2, 1);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (18, 8, 6, 0);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (67, 1, 8, 0, 600, 6, 3, 100.00, 200.00, 1, 6, 533, 1, 17, 0, 0, 533, 6, 2, 1, 1, 0, 2, 1, 4, 1814);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 1, 0, 0.00, 67);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 1, 1, 1.00, 67);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 1, 0, 0.00, 67);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 1, 67);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 67);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 1, 67);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (68, 3, 8, 3, 1250, 11, 9, 113.64, 138.89, 1, 14, 533, 1, 17, 0, 0, 533, 6, 2, 1, 1, 1, 2, 1, 0, 1803);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 7, 1, 0.14, 68);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (69, 7, 8, 1, 350, 3, 5, 116.67, 70.00, 1, 4, 533, 1, 17, 0, 0, 533, 6, 2, 0, 0, 2, 1, 0, 2, 1800);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 1, 69);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 69);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (70, 6, 8, 2, 900, 8, 9, 112.50, 100.00, 1, 10, 533, 0, 18, 0, 0, 533, 6, 6, 1, 1, 0, 0, 2, 1, 1821);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2069224589, 6, 1, 0.17, 70);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 1, 70);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (71, 15, 8, 4, 1300, 11, 3, 118.18, 433.33, 1, 15, 533, 0, 18, 0, 0, 533, 6, 6, 0, 1, 2, 1, 0, 4, 1802);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 8, 0, 0.00, 71);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 71);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 1, 71);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 71);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (72, 5, 8, 5, 1150, 9, 7, 127.78, 164.29, 1, 14, 533, 0, 18, 0, 0, 533, 6, 6, 2, 0, 1, 2, 0, 3, 1813);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 5, 1, 0.20, 72);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 1, 1, 1.00, 72);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 72);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 1, 72);
+
+--Control on Midtown
+INSERT INTO "activity" ("id", "activity_id", "period", "mode", "platform", "director_activity_hash", "reference_id") VALUES (9, 9100000009, strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now', '-46 hours'), 10, 3, 2259621240, 3164915257);
+INSERT INTO "modes" ("mode", "activity") VALUES (5, 9);
+INSERT INTO "modes" ("mode", "activity") VALUES (10, 9);
+INSERT INTO "modes" ("mode", "activity") VALUES (70, 9);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (17, 9, 138, 0);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (18, 9, 82, 1);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (73, 1, 9, 12, 2800, 22, 10, 127.27, 280.00, 1, 34, 600, 0, 17, 1, 0, 600, 12, 138, 6, 0, 2, 0, 1, 1, 1821);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 14, 6, 0.43, 73);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 5, 0, 0.00, 73);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 1, 73);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (74, 11, 9, 12, 2200, 16, 19, 137.50, 115.79, 1, 28, 600, 0, 17, 1, 0, 600, 12, 138, 10, 0, 0, 2, 0, 3, 1816);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 14, 10, 0.71, 74);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 74);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 1, 74);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (75, 14, 9, 8, 1600, 12, 10, 133.33, 160.00, 1, 20, 600, 0, 17, 1, 0, 600, 12, 138, 6, 0, 1, 1, 2, 2, 1802);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 1, 0, 0.00, 75);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 1, 0, 0.00, 75);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 6, 6, 1.00, 75);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 75);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (76, 3, 9, 6, 1900, 16, 10, 118.75, 190.00, 1, 22, 600, 0, 17, 1, 0, 600, 12, 138, 4, 0, 0, 0, 2, 2, 1807);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 12, 3, 0.25, 76);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 2, 1, 0.50, 76);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 1, 76);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 76);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (77, 12, 9, 5, 2650, 24, 14, 110.42, 189.29, 1, 29, 600, 0, 17, 1, 0, 600, 12, 138, 3, 1, 2, 2, 0, 3, 1807);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 17, 2, 0.12, 77);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 3, 1, 0.33, 77);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 77);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 1, 77);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (78, 7, 9, 9, 2150, 17, 7, 126.47, 307.14, 1, 26, 600, 0, 17, 1, 0, 600, 12, 138, 12, 0, 2, 1, 0, 0, 1811);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 10, 8, 0.80, 78);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 1, 1, 1.00, 78);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 3, 3, 1.00, 78);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (79, 10, 9, 2, 700, 6, 7, 116.67, 100.00, 1, 8, 600, 1, 18, 1, 0, 600, 12, 82, 0, 1, 1, 1, 2, 2, 1809);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1119734784, 2, 0, 0.00, 79);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 79);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (80, 15, 9, 9, 2950, 25, 19, 118.00, 155.26, 1, 34, 600, 1, 18, 1, 0, 600, 12, 82, 7, 0, 1, 0, 2, 4, 1815);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 4, 2, 0.50, 80);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 13, 4, 0.31, 80);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 5, 1, 0.20, 80);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 80);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 80);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (81, 8, 9, 6, 2500, 22, 8, 113.64, 312.50, 1, 28, 600, 1, 18, 1, 0, 600, 12, 82, 0, 1, 2, 2, 1, 0, 1820);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 17, 0, 0.00, 81);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (82, 9, 9, 8, 2000, 16, 13, 125.00, 153.85, 1, 24, 600, 1, 18, 1, 0, 600, 12, 82, 4, 0, 0, 1, 1, 0, 1828);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 7, 3, 0.43, 82);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 5, 0, 0.00, 82);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 2, 1, 0.50, 82);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (83, 13, 9, 9, 2150, 17, 8, 126.47, 268.75, 1, 26, 600, 1, 18, 1, 0, 600, 12, 82, 3, 1, 2, 2, 2, 2, 1823);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 11, 3, 0.27, 83);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 2, 83);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (84, 6, 9, 7, 1750, 14, 12, 125.00, 145.83, 1, 21, 600, 1, 18, 1, 0, 600, 12, 82, 0, 0, 2, 0, 2, 2, 1824);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2069224589, 8, 0, 0.00, 84);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 2, 0, 0.00, 84);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 1, 84);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 84);
+
+--Clash on Javelin-4
+INSERT INTO "activity" ("id", "activity_id", "period", "mode", "platform", "director_activity_hash", "reference_id") VALUES (10, 9100000010, strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now', '-50 hours'), 12, 3, 2259621242, 1003889713);
+INSERT INTO "modes" ("mode", "activity") VALUES (5, 10);
+INSERT INTO "modes" ("mode", "activity") VALUES (12, 10);
+INSERT INTO "modes" ("mode", "activity") VALUES (70, 10);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (17, 10, 100, 0);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (18, 10, 84, 1);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (85, 1, 10, 9, 3450, 30, 6, 115.00, 575.00, 1, 39, 536, 0, 17, 0, 0, 536, 12, 100, 8, 0, 2, 2, 2, 4, 1818);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 16, 5, 0.31, 85);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 5, 0, 0.00, 85);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 3, 3, 1.00, 85);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 85);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 2, 85);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (86, 8, 10, 2, 1800, 17, 16, 105.88, 112.50, 1, 19, 536, 0, 17, 0, 0, 536, 12, 100, 9, 0, 2, 0, 1, 0, 1821);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 14, 9, 0.64, 86);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (87, 12, 10, 7, 2550, 22, 13, 115.91, 196.15, 1, 29, 536, 0, 17, 0, 0, 536, 12, 100, 1, 1, 1, 2, 1, 0, 1828);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 18, 1, 0.06, 87);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (88, 3, 10, 6, 2900, 26, 17, 111.54, 170.59, 1, 32, 536, 0, 17, 0, 0, 536, 12, 100, 11, 0, 1, 0, 1, 2, 1822);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 10, 10, 1.00, 88);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 13, 1, 0.08, 88);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 1, 0, 0.00, 88);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 2, 88);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (89, 15, 10, 9, 1350, 9, 17, 150.00, 79.41, 1, 18, 536, 0, 17, 0, 0, 536, 12, 100, 3, 1, 1, 2, 2, 3, 1814);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 2, 1, 0.50, 89);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 2, 2, 1.00, 89);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 89);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 89);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (90, 7, 10, 4, 1100, 9, 11, 122.22, 100.00, 1, 13, 536, 0, 17, 0, 0, 536, 12, 100, 3, 0, 1, 1, 2, 2, 1824);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 5, 3, 0.60, 90);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 90);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 1, 90);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (91, 11, 10, 4, 2000, 18, 15, 111.11, 133.33, 1, 22, 536, 1, 18, 0, 0, 536, 12, 84, 7, 0, 0, 0, 1, 3, 1814);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 16, 7, 0.44, 91);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 1, 0, 0.00, 91);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 2, 91);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 91);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (92, 6, 10, 7, 2150, 18, 17, 119.44, 126.47, 1, 25, 536, 1, 18, 0, 0, 536, 12, 84, 4, 0, 0, 2, 1, 0, 1816);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 15, 4, 0.27, 92);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (93, 5, 10, 3, 1750, 16, 9, 109.38, 194.44, 1, 19, 536, 1, 18, 0, 0, 536, 12, 84, 6, 0, 2, 2, 2, 3, 1818);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 10, 6, 0.60, 93);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 2, 93);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 1, 93);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (94, 14, 10, 7, 2050, 17, 13, 120.59, 157.69, 1, 24, 536, 1, 18, 0, 0, 536, 12, 84, 3, 0, 2, 1, 0, 5, 1816);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 9, 0, 0.00, 94);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 5, 3, 0.60, 94);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 94);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 1, 94);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 94);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (95, 13, 10, 4, 800, 6, 17, 133.33, 47.06, 1, 10, 536, 1, 18, 0, 0, 536, 12, 84, 3, 1, 1, 1, 0, 0, 1809);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 3, 2, 0.67, 95);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 1, 1, 1.00, 95);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (96, 9, 10, 11, 2150, 16, 16, 134.38, 134.38, 1, 27, 536, 1, 18, 0, 0, 536, 12, 84, 0, 1, 0, 0, 2, 0, 1802);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 14, 0, 0.00, 96);
+
+--Control on Radiant Cliffs
+INSERT INTO "activity" ("id", "activity_id", "period", "mode", "platform", "director_activity_hash", "reference_id") VALUES (11, 9100000011, strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now', '-51 hours'), 10, 3, 2259621240, 532383918);
+INSERT INTO "modes" ("mode", "activity") VALUES (5, 11);
+INSERT INTO "modes" ("mode", "activity") VALUES (10, 11);
+INSERT INTO "modes" ("mode", "activity") VALUES (70, 11);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (17, 11, 131, 0);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (18, 11, 104, 1);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (97, 1, 11, 12, 2600, 20, 16, 130.00, 162.50, 1, 32, 600, 0, 17, 1, 0, 600, 12, 131, 9, 1, 0, 0, 0, 2, 1823);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 5, 1, 0.20, 97);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 15, 8, 0.53, 97);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 2, 97);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (98, 5, 11, 3, 2150, 20, 13, 107.50, 165.38, 1, 23, 600, 0, 17, 1, 0, 600, 12, 131, 5, 1, 1, 0, 0, 2, 1805);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 11, 1, 0.09, 98);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 8, 4, 0.50, 98);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 98);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (99, 9, 11, 11, 1050, 5, 15, 210.00, 70.00, 1, 16, 600, 0, 17, 1, 0, 600, 12, 131, 2, 1, 0, 2, 0, 3, 1814);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 2, 1, 0.50, 99);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 1, 1, 1.00, 99);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 99);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 1, 99);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (100, 11, 11, 11, 1150, 6, 19, 191.67, 60.53, 1, 17, 600, 0, 17, 1, 0, 600, 12, 131, 0, 0, 2, 0, 2, 1, 1801);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 2, 0, 0.00, 100);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 1, 100);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (101, 8, 11, 12, 1400, 8, 20, 175.00, 70.00, 1, 20, 600, 0, 17, 1, 0, 600, 12, 131, 1, 0, 0, 2, 2, 1, 1802);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 3, 0, 0.00, 101);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 1, 1, 1.00, 101);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 101);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (102, 7, 11, 9, 2850, 24, 14, 118.75, 203.57, 1, 33, 600, 0, 17, 1, 0, 600, 12, 131, 6, 0, 2, 0, 0, 0, 1823);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 15, 4, 0.27, 102);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 7, 2, 0.29, 102);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (103, 4, 11, 10, 3000, 25, 7, 120.00, 428.57, 1, 35, 600, 1, 18, 1, 0, 600, 12, 104, 12, 0, 1, 0, 2, 0, 1825);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 11, 9, 0.82, 103);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 4, 1, 0.25, 103);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 7, 2, 0.29, 103);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (104, 13, 11, 2, 2000, 19, 10, 105.26, 200.00, 1, 21, 600, 1, 18, 1, 0, 600, 12, 104, 6, 1, 1, 2, 2, 1, 1821);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 14, 6, 0.43, 104);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 1, 104);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (105, 15, 11, 5, 2450, 22, 17, 111.36, 144.12, 1, 27, 600, 1, 18, 1, 0, 600, 12, 104, 9, 0, 0, 1, 1, 0, 1814);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 13, 2, 0.15, 105);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 7, 7, 1.00, 105);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (106, 12, 11, 9, 1350, 9, 8, 150.00, 168.75, 1, 18, 600, 1, 18, 1, 0, 600, 12, 104, 0, 1, 2, 0, 0, 0, 1803);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 3, 0, 0.00, 106);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 3, 0, 0.00, 106);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 1, 0, 0.00, 106);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (107, 3, 11, 8, 2700, 23, 15, 117.39, 180.00, 1, 31, 600, 1, 18, 1, 0, 600, 12, 104, 19, 0, 0, 0, 2, 0, 1806);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 21, 19, 0.90, 107);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (108, 10, 11, 5, 2750, 25, 15, 110.00, 183.33, 1, 30, 600, 1, 18, 1, 0, 600, 12, 104, 9, 0, 1, 2, 1, 0, 1824);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 8, 5, 0.62, 108);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 1, 1, 1.00, 108);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 12, 3, 0.25, 108);
+
+--Clash on The Dead Cliffs
+INSERT INTO "activity" ("id", "activity_id", "period", "mode", "platform", "director_activity_hash", "reference_id") VALUES (12, 9100000012, strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now', '-60 hours'), 12, 3, 2259621242, 2666761222);
+INSERT INTO "modes" ("mode", "activity") VALUES (5, 12);
+INSERT INTO "modes" ("mode", "activity") VALUES (12, 12);
+INSERT INTO "modes" ("mode", "activity") VALUES (70, 12);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (17, 12, 94, 0);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (18, 12, 76, 1);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (109, 2, 12, 5, 2250, 20, 11, 112.50, 204.55, 1, 25, 600, 0, 17, 1, 0, 600, 12, 94, 7, 1, 2, 1, 2, 3, 1800);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 15, 7, 0.47, 109);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 109);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 109);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (110, 12, 12, 5, 2650, 24, 13, 110.42, 203.85, 1, 29, 600, 0, 17, 1, 0, 600, 12, 94, 7, 1, 1, 1, 0, 0, 1809);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 21, 6, 0.29, 110);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 1, 1, 1.00, 110);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (111, 8, 12, 9, 1850, 14, 9, 132.14, 205.56, 1, 23, 600, 0, 17, 1, 0, 600, 12, 94, 7, 0, 2, 2, 2, 4, 1801);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 8, 7, 0.88, 111);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 111);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 2, 111);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (112, 4, 12, 5, 1050, 8, 18, 131.25, 58.33, 1, 13, 600, 0, 17, 1, 0, 600, 12, 94, 2, 0, 0, 1, 0, 2, 1830);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 3, 0, 0.00, 112);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 2, 0, 0.00, 112);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 2, 2, 1.00, 112);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 2, 112);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (113, 3, 12, 4, 1100, 9, 8, 122.22, 137.50, 1, 13, 600, 0, 17, 1, 0, 600, 12, 94, 7, 0, 0, 0, 0, 3, 1823);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 7, 6, 0.86, 113);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 1, 1, 1.00, 113);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 1, 0, 0.00, 113);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 1, 113);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 113);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (114, 15, 12, 11, 2550, 20, 14, 127.50, 182.14, 1, 31, 600, 0, 17, 1, 0, 600, 12, 94, 7, 1, 2, 2, 0, 1, 1803);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2069224589, 10, 6, 0.60, 114);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 3, 0, 0.00, 114);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 3, 1, 0.33, 114);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 1, 114);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (115, 10, 12, 3, 850, 7, 13, 121.43, 65.38, 1, 10, 600, 1, 18, 1, 0, 600, 12, 76, 1, 0, 2, 0, 2, 2, 1811);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1119734784, 2, 0, 0.00, 115);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 1, 1, 1.00, 115);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 115);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (116, 14, 12, 6, 1300, 10, 13, 130.00, 100.00, 1, 16, 600, 1, 18, 1, 0, 600, 12, 76, 6, 1, 1, 0, 2, 3, 1811);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 4, 3, 0.75, 116);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 1, 1, 1.00, 116);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 2, 2, 1.00, 116);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 2, 116);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 1, 116);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (117, 13, 12, 8, 2600, 22, 15, 118.18, 173.33, 1, 30, 600, 1, 18, 1, 0, 600, 12, 76, 10, 1, 2, 0, 0, 2, 1805);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2069224589, 17, 9, 0.53, 117);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 3, 1, 0.33, 117);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 2, 117);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (118, 11, 12, 11, 2150, 16, 8, 134.38, 268.75, 1, 27, 600, 1, 18, 1, 0, 600, 12, 76, 1, 0, 2, 1, 1, 4, 1815);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 12, 1, 0.08, 118);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 118);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 118);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (119, 7, 12, 11, 2650, 21, 12, 126.19, 220.83, 1, 32, 600, 1, 18, 1, 0, 600, 12, 76, 0, 1, 1, 2, 2, 1, 1811);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 16, 0, 0.00, 119);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 119);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (120, 6, 12, 11, 2050, 15, 12, 136.67, 170.83, 1, 26, 600, 1, 18, 1, 0, 600, 12, 76, 3, 0, 0, 1, 1, 4, 1827);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 13, 3, 0.23, 120);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 120);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 1, 120);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 120);
+
+--Control on The Dead Cliffs
+INSERT INTO "activity" ("id", "activity_id", "period", "mode", "platform", "director_activity_hash", "reference_id") VALUES (13, 9100000013, strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now', '-66 hours'), 10, 3, 2259621240, 2666761222);
+INSERT INTO "modes" ("mode", "activity") VALUES (5, 13);
+INSERT INTO "modes" ("mode", "activity") VALUES (10, 13);
+INSERT INTO "modes" ("mode", "activity") VALUES (70, 13);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (17, 13, 105, 1);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (18, 13, 129, 0);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (121, 1, 13, 7, 3350, 30, 13, 111.67, 257.69, 1, 37, 600, 1, 17, 1, 0, 600, 12, 105, 9, 1, 0, 1, 1, 0, 1811);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 28, 9, 0.32, 121);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (122, 3, 13, 5, 1250, 10, 6, 125.00, 208.33, 1, 15, 600, 1, 17, 1, 0, 600, 12, 105, 2, 1, 2, 1, 1, 4, 1821);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 4, 0, 0.00, 122);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 2, 2, 1.00, 122);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 1, 122);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 1, 122);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 2, 122);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (123, 10, 13, 8, 1300, 9, 9, 144.44, 144.44, 1, 17, 600, 1, 17, 1, 0, 600, 12, 105, 0, 1, 2, 2, 2, 3, 1828);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 2, 0, 0.00, 123);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 1, 0, 0.00, 123);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 123);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 1, 123);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (124, 6, 13, 5, 2750, 25, 11, 110.00, 250.00, 1, 30, 600, 1, 17, 1, 0, 600, 12, 105, 1, 1, 2, 2, 1, 4, 1808);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 20, 1, 0.05, 124);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 2, 124);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 124);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (125, 14, 13, 11, 1850, 13, 15, 142.31, 123.33, 1, 24, 600, 1, 17, 1, 0, 600, 12, 105, 3, 0, 0, 2, 0, 0, 1816);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 10, 2, 0.20, 125);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 1, 1, 1.00, 125);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (126, 11, 13, 7, 1550, 12, 20, 129.17, 77.50, 1, 19, 600, 1, 17, 1, 0, 600, 12, 105, 8, 1, 1, 1, 1, 0, 1829);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1119734784, 9, 8, 0.89, 126);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (127, 4, 13, 5, 1950, 17, 19, 114.71, 102.63, 1, 22, 600, 0, 18, 1, 0, 600, 12, 129, 2, 0, 0, 1, 0, 0, 1800);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 16, 2, 0.12, 127);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (128, 9, 13, 11, 1550, 10, 6, 155.00, 258.33, 1, 21, 600, 0, 18, 1, 0, 600, 12, 129, 1, 0, 2, 2, 1, 0, 1825);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3654674561, 2, 0, 0.00, 128);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 3, 1, 0.33, 128);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (129, 15, 13, 9, 1850, 14, 14, 132.14, 132.14, 1, 23, 600, 0, 18, 1, 0, 600, 12, 129, 1, 1, 2, 2, 2, 3, 1820);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1366394399, 8, 1, 0.12, 129);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 1, 129);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 2, 129);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (130, 7, 13, 9, 2350, 19, 20, 123.68, 117.50, 1, 28, 600, 0, 18, 1, 0, 600, 12, 129, 0, 0, 0, 2, 0, 0, 1811);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 17, 0, 0.00, 130);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (131, 12, 13, 5, 750, 5, 8, 150.00, 93.75, 1, 10, 600, 0, 18, 1, 0, 600, 12, 129, 0, 0, 1, 0, 0, 3, 1804);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 2, 0, 0.00, 131);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 2, 0, 0.00, 131);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 131);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 1, 131);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (132, 13, 13, 6, 2100, 18, 12, 116.67, 175.00, 1, 24, 600, 0, 18, 1, 0, 600, 12, 129, 5, 1, 1, 1, 0, 3, 1806);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 12, 4, 0.33, 132);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4243004391, 2, 1, 0.50, 132);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 2, 0, 0.00, 132);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 1, 132);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 1, 132);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 1, 132);
+
+--Control on Javelin-4
+INSERT INTO "activity" ("id", "activity_id", "period", "mode", "platform", "director_activity_hash", "reference_id") VALUES (14, 9100000014, strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now', '-70 hours'), 10, 3, 2259621240, 1003889713);
+INSERT INTO "modes" ("mode", "activity") VALUES (5, 14);
+INSERT INTO "modes" ("mode", "activity") VALUES (10, 14);
+INSERT INTO "modes" ("mode", "activity") VALUES (70, 14);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (17, 14, 140, 1);
+INSERT INTO "team_result" ("team_id", "activity", "score", "standing") VALUES (18, 14, 150, 0);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (133, 1, 14, 7, 1250, 9, 11, 138.89, 113.64, 1, 16, 456, 1, 17, 0, 0, 456, 12, 140, 8, 0, 0, 0, 1, 3, 1821);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 8, 8, 1.00, 133);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 2, 133);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 1, 133);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (134, 6, 14, 6, 2100, 18, 6, 116.67, 350.00, 1, 24, 456, 1, 17, 0, 0, 456, 12, 140, 9, 1, 2, 1, 0, 2, 1802);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 15, 9, 0.60, 134);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 134);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (135, 14, 14, 9, 1650, 12, 20, 137.50, 82.50, 1, 21, 456, 1, 17, 0, 0, 456, 12, 140, 2, 1, 2, 1, 1, 0, 1812);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 8, 2, 0.25, 135);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (136, 8, 14, 3, 1650, 15, 12, 110.00, 137.50, 1, 18, 456, 1, 17, 0, 0, 456, 12, 140, 2, 1, 0, 1, 0, 2, 1818);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1119734784, 14, 2, 0.14, 136);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 136);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (137, 9, 14, 6, 2200, 19, 20, 115.79, 110.00, 1, 25, 456, 1, 17, 0, 0, 456, 12, 140, 15, 0, 1, 0, 2, 2, 1821);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 10, 10, 1.00, 137);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 6, 5, 0.83, 137);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalFirstBlood', 2, 137);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (138, 13, 14, 11, 1550, 10, 12, 155.00, 129.17, 1, 21, 456, 1, 17, 0, 0, 456, 12, 140, 3, 1, 1, 0, 1, 0, 1814);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 8, 3, 0.38, 138);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (139, 4, 14, 10, 1500, 10, 18, 150.00, 83.33, 1, 20, 456, 0, 18, 0, 0, 456, 12, 150, 4, 1, 1, 0, 1, 2, 1826);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1321506184, 7, 3, 0.43, 139);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 1, 1, 1.00, 139);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalStreak5x', 1, 139);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 1, 139);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (140, 11, 14, 5, 2150, 19, 14, 113.16, 153.57, 1, 24, 456, 0, 18, 0, 0, 456, 12, 150, 4, 1, 2, 1, 1, 0, 1818);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2069224589, 15, 4, 0.27, 140);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (141, 12, 14, 3, 1950, 18, 6, 108.33, 325.00, 1, 21, 456, 0, 18, 0, 0, 456, 12, 150, 4, 0, 2, 0, 1, 3, 1801);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (3535742959, 15, 4, 0.27, 141);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalAvenger', 2, 141);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalPayback', 1, 141);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (142, 3, 14, 12, 3100, 25, 7, 124.00, 442.86, 1, 37, 456, 0, 18, 0, 0, 456, 12, 150, 0, 1, 0, 2, 2, 2, 1821);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (4124984448, 21, 0, 0.00, 142);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti3x', 2, 142);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (143, 7, 14, 12, 1400, 8, 8, 175.00, 175.00, 1, 20, 456, 0, 18, 0, 0, 456, 12, 150, 3, 0, 0, 1, 1, 2, 1824);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 2, 1, 0.50, 143);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (347366834, 2, 1, 0.50, 143);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1725420063, 2, 1, 0.50, 143);
+INSERT INTO "medal_result" ("reference_id", "count", "character_activity_stats") VALUES ('medalMulti2x', 2, 143);
+INSERT INTO "character_activity_stats" ("id", "character", "activity", "assists", "score", "kills", "deaths", "average_score_per_kill", "average_score_per_life", "completed", "opponents_defeated", "activity_duration_seconds", "standing", "team", "completion_reason", "start_seconds", "time_played_seconds", "player_count", "team_score", "precision_kills", "weapon_kills_ability", "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", "all_medals_earned", "light_level") VALUES (144, 15, 14, 7, 2950, 26, 10, 113.46, 295.00, 1, 33, 456, 0, 18, 0, 0, 456, 12, 150, 5, 0, 0, 1, 1, 0, 1811);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (2376481550, 20, 5, 0.25, 144);
+INSERT INTO "weapon_result" ("reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats") VALUES (1234150730, 4, 0, 0.00, 144);
+
+COMMIT;
@@ -0,0 +1,107 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Account level context for players, used to give a sense of a player's
+//experience beyond a single combat rating. Results are cached in the data
+//directory, since they change slowly and require several API calls per player.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::datadir;
+use crate::error::Error;
+
+const CACHE_FILE_NAME: &str = "account_context.json";
+
+//how long cached data is used before it is retrieved again
+const CACHE_TTL_SECONDS: i64 = 60 * 60 * 24;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AccountContext {
+    pub member_id: String,
+
+    //unix timestamp of when the bungie.net account was first accessed
+    pub first_access: Option<i64>,
+
+    pub crucible_matches: u32,
+    pub crucible_seconds_played: u32,
+
+    //unix timestamp of when the data was retrieved
+    pub retrieved: i64,
+}
+
+impl AccountContext {
+    pub fn get_first_access(&self) -> Option<DateTime<Utc>> {
+        self.first_access.map(|e| Utc.timestamp(e, 0))
+    }
+
+    fn is_expired(&self) -> bool {
+        Utc::now().timestamp() - self.retrieved > CACHE_TTL_SECONDS
+    }
+}
+
+pub struct AccountContextCache {
+    path: PathBuf,
+    entries: HashMap<String, AccountContext>,
+}
+
+impl AccountContextCache {
+    /// loads the cache from the data directory. If the cache can't be read, an
+    /// empty cache is returned
+    pub fn load(data_dir: &Path) -> AccountContextCache {
+        let path = datadir::cache_dir(data_dir).join(CACHE_FILE_NAME);
+
+        let entries: HashMap<String, AccountContext> =
+            match fs::read_to_string(&path) {
+                Ok(e) => serde_json::from_str(&e).unwrap_or_default(),
+                Err(_e) => HashMap::new(),
+            };
+
+        AccountContextCache { path, entries }
+    }
+
+    /// returns the cached context for the member, if it hasnt expired
+    pub fn get(&self, member_id: &str) -> Option<&AccountContext> {
+        match self.entries.get(member_id) {
+            Some(e) if !e.is_expired() => Some(e),
+            _ => None,
+        }
+    }
+
+    pub fn insert(&mut self, context: AccountContext) {
+        self.entries.insert(context.member_id.clone(), context);
+    }
+
+    pub fn save(&mut self) -> Result<(), Error> {
+        //dont keep expired entries around forever
+        self.entries.retain(|_k, v| !v.is_expired());
+
+        let json = serde_json::to_string(&self.entries)?;
+        fs::write(&self.path, json)?;
+
+        Ok(())
+    }
+}
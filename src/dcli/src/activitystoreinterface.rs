@@ -21,10 +21,12 @@
 */
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 
 use crate::{
     crucible::{CrucibleActivity, Team},
@@ -34,22 +36,54 @@ use crate::{
         moment::DateTimePeriod,
         standing::Standing,
     },
+    response::character::CharacterData,
     response::pgcr::DestinyPostGameCarnageReportEntry,
 };
 use futures::TryStreamExt;
-use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode};
-use sqlx::Row;
-use sqlx::{ConnectOptions, SqliteConnection};
-
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions,
+};
+use sqlx::{Column, FromRow, Row, TypeInfo, ValueRef};
+use sqlx::{ConnectOptions, Connection, SqliteConnection};
+
+use crate::apiclient::ApiKeyUsage;
+use crate::cancellation::CancellationToken;
+use crate::character::CharacterMetadata;
+use crate::competitive::{
+    infer_progress, CompetitiveMatch, CompetitiveResult, COMPETITIVE_MODES,
+    GLORY_PROGRESSION_HASH,
+};
 use crate::crucible::{
-    ActivityDetail, CruciblePlayerActivityPerformance,
-    CruciblePlayerPerformance, CrucibleStats, ExtendedCrucibleStats, Item,
-    Medal, MedalStat, Player, WeaponStat,
+    ActivityDetail, ActivityOpponentComposition,
+    CruciblePlayerActivityPerformance, CruciblePlayerPerformance,
+    CrucibleStats, ExtendedCrucibleStats, Item, LobbyRatingPeriod, MapStats,
+    Medal, MedalStat, OpponentComposition, OpponentPlatformStats, Player,
+    PlayerRelationship, SharedActivity, WeaponStat,
 };
+use crate::datadir;
+use crate::derivedstats;
 use crate::enums::character::{CharacterClass, CharacterClassSelection};
 use crate::enums::medaltier::MedalTier;
 use crate::enums::mode::Mode;
 use crate::enums::platform::Platform;
+use crate::export::{ExportTable, PlayerPerformanceRow};
+use crate::manifest::definitions::SeasonDefinitionData;
+use crate::parquet::{ParquetType, ParquetValue};
+use crate::pve::PveActivityStats;
+use crate::response::activities::MAX_ACTIVITIES_REQUEST_COUNT;
+use crate::storeexport::{
+    StoreExportActivity, StoreExportCharacter, StoreExportMember,
+    StoreExportPlayer, STORE_EXPORT_FORMAT, STORE_EXPORT_FORMAT_VERSION,
+};
+use crate::storequery::{QueryResult, QueryValue, QUERY_VIEWS};
+use crate::storerows::{
+    ActivityRow, CharacterActivityStatsRow, CharacterMetadataRow,
+    MedalResultRow, PlayerRow, TeamResultRow, WeaponResultRow,
+};
+use crate::storeschema;
+use crate::syncevent::SyncEvent;
+use crate::synclock::{self, SyncLock};
+use crate::timing::{self, TimedFutureExt, TimingCategory};
 use crate::{apiinterface::ApiInterface, manifestinterface::ManifestInterface};
 use crate::{
     error::Error,
@@ -58,1187 +92,6201 @@ use crate::{
     },
     utils::{
         calculate_efficiency, calculate_kills_deaths_assists,
-        calculate_kills_deaths_ratio,
+        calculate_kills_deaths_ratio, calculate_percent, print_verbose,
     },
 };
 
-const STORE_FILE_NAME: &str = "dcli.sqlite3";
+pub const STORE_FILE_NAME: &str = "dcli.sqlite3";
 const STORE_DB_SCHEMA: &str = include_str!("../actitvity_store_schema.sql");
 
 //numer of simultaneous requests we make to server when retrieving activity history
-const PGCR_REQUEST_CHUNK_AMOUNT: usize = 24;
+pub const DEFAULT_PGCR_CONCURRENCY: usize = 24;
+
+//upper bound for the number of simultaneous requests (see set_pgcr_concurrency)
+//so we dont flood the api, and get throttled
+pub const MAX_PGCR_CONCURRENCY: usize = 64;
+
+//number of times throttled PGCR requests are retried while syncing, before
+//they are left in the queue for the next sync
+const MAX_THROTTLE_RETRIES: u32 = 5;
+
+//wait before the first retry of throttled requests. Doubled for each retry,
+//unless the api asks us to wait longer.
+const THROTTLE_BACKOFF_SECONDS: u64 = 2;
+
+//activity data retrieved for an activity in the queue, along with the json
+//it was parsed from, if it is being stored
+type RetrievedPgcr =
+    Option<(DestinyPostGameCarnageReportData, Option<serde_json::Value>)>;
+
+const DB_SCHEMA_VERSION: i32 = 15;
+
+//version of the store created by STORE_DB_SCHEMA. Stores are upgraded from
+//it to DB_SCHEMA_VERSION with the migrations in storeschema
+const BASE_SCHEMA_VERSION: i32 = 6;
+
+//member ids of the members known to have been synced into the store. Every
+//sync run is recorded in sync_history, and characters with queued activities
+//or an unfinished history walk are part way through a sync.
+const SYNCED_MEMBER_IDS_SQL: &str = r#"
+    SELECT member_id FROM "main"."sync_history"
+    UNION SELECT member.member_id FROM "main"."activity_queue"
+        INNER JOIN "main"."character"
+            ON activity_queue.character = character.id
+        INNER JOIN "main"."member" ON character.member = member.id
+    UNION SELECT member.member_id FROM "main"."history_cursor"
+        INNER JOIN "main"."character"
+            ON history_cursor.character = character.id
+        INNER JOIN "main"."member" ON character.member = member.id
+"#;
+
+//prefix of the file names of store backups made before schema upgrades
+const STORE_BACKUP_PREFIX: &str = "dcli_store";
+
+//extension of encrypted stores and backups. Matches storeencryption, which
+//is only compiled with the encryption feature.
+const ENCRYPTED_STORE_EXTENSION: &str = "enc";
 
-const DB_SCHEMA_VERSION: i32 = 6;
 const NO_TEAMS_INDEX: i32 = 253;
 
+//number of recent activities checked for each mode when looking for
+//activities which have not been synced yet
+const UNSYNCED_ACTIVITY_COUNT: i32 = 25;
+
+//sanity limit on the number of player entries we will store for a single
+//activity. Normal crucible matches have at most 12 players (plus anyone who
+//joined in progress) but glitched activities can report many more.
+const MAX_PGCR_ENTRIES: usize = 48;
+
+//number of activities loaded at a time when streaming activities
+const STREAM_PAGE_SIZE: i64 = 100;
+
+//max number of connections held open to the store. Readers can run
+//concurrently, while sqlite serializes writes.
+const MAX_STORE_CONNECTIONS: u32 = 4;
+
+//largest store (in bytes) init_overlay_with_path will copy. The overlay is a
+//full copy of the store, so larger stores take too long (and too much disk
+//space) to copy for a single report.
+pub const MAX_OVERLAY_STORE_SIZE: u64 = 512 * 1024 * 1024;
+
+//modes synced when pve syncing is enabled. Nightfalls are also included in
+//AllStrikes, but are synced seperately in case they are not. Activities
+//returned for more than one mode are only queued once.
+const PVE_SYNC_MODES: [Mode; 4] = [
+    Mode::AllStrikes,
+    Mode::ScoredNightfall,
+    Mode::Raid,
+    Mode::Dungeon,
+];
+
+/// Interface to the local activity store.
+///
+/// Queries run against a connection pool, so a single instance can be
+/// shared (i.e. via an Arc) across tasks and serve concurrent readers.
 pub struct ActivityStoreInterface {
     verbose: bool,
-    db: SqliteConnection,
+    db: SqlitePool,
     path: String,
+    data_dir: PathBuf,
+    emit_events: bool,
+    sync_pve: bool,
+    store_raw_pgcr: bool,
+    max_activities: Option<u32>,
+    pgcr_concurrency: usize,
+
+    //set if we are reading from a snapshot of the store
+    snapshot_path: Option<PathBuf>,
+
+    //unencrypted copy of the store the connection is using, if the store
+    //is encrypted
+    #[cfg(feature = "encryption")]
+    working_copy: Option<crate::storeencryption::WorkingCopy>,
 }
 
 impl ActivityStoreInterface {
     pub fn get_storage_path(&self) -> String {
+        //the working copy is temporary, so we return the encrypted store
+        #[cfg(feature = "encryption")]
+        if self.working_copy.is_some() && self.snapshot_path.is_none() {
+            let path = datadir::store_dir(&self.data_dir).join(STORE_FILE_NAME);
+            return crate::storeencryption::encrypted_path(&path)
+                .display()
+                .to_string();
+        }
+
         self.path.clone()
     }
 
+    /// whether the store is a snapshot, opened because another process is
+    /// syncing the store. Snapshots should not be synced.
+    pub fn is_snapshot(&self) -> bool {
+        self.snapshot_path.is_some()
+    }
+
+    /// when set, sync events are written to stdout as JSON Lines
+    /// (one json object per event)
+    pub fn set_emit_events(&mut self, emit_events: bool) {
+        self.emit_events = emit_events;
+    }
+
+    /// when set, pve activities (strikes, nightfalls, raids and dungeons)
+    /// are synced along with crucible and gambit activities
+    pub fn set_sync_pve(&mut self, sync_pve: bool) {
+        self.sync_pve = sync_pve;
+    }
+
+    /// sets whether the original PGCR json is stored for each activity that
+    /// is synced or re-ingested. This significantly increases the size of
+    /// the store, but lets activities be re-ingested without downloading
+    /// them again.
+    pub fn set_store_raw_pgcr(&mut self, store_raw_pgcr: bool) {
+        self.store_raw_pgcr = store_raw_pgcr;
+    }
+
+    /// sets the maximum number of activities retrieved each time a member is
+    /// synced. Activities over the limit are left in the queue, and synced
+    /// the next time the member is synced. Lets long first syncs be spread
+    /// across several runs.
+    pub fn set_max_activities(&mut self, max_activities: Option<u32>) {
+        self.max_activities = max_activities;
+    }
+
+    /// sets the number of activities retrieved from the api at the same time
+    /// when syncing. Lower values can help on slow connections, and higher
+    /// values speed up large syncs. Clamped to between 1 and
+    /// MAX_PGCR_CONCURRENCY. Defaults to DEFAULT_PGCR_CONCURRENCY.
+    pub fn set_pgcr_concurrency(&mut self, concurrency: usize) {
+        self.pgcr_concurrency = concurrency.clamp(1, MAX_PGCR_CONCURRENCY);
+    }
+
+    fn emit(&self, event: SyncEvent) {
+        if self.emit_events {
+            event.emit();
+        }
+    }
+
     pub async fn init_with_path(
-        store_dir: &PathBuf,
+        data_dir: &Path,
         verbose: bool,
     ) -> Result<ActivityStoreInterface, Error> {
-        let path = store_dir.join(STORE_FILE_NAME).display().to_string();
+        let store_path = datadir::store_dir(data_dir).join(STORE_FILE_NAME);
+
+        #[cfg(feature = "encryption")]
+        let passphrase = crate::storeencryption::passphrase_from_env();
+
+        #[cfg(feature = "encryption")]
+        let working_copy = match &passphrase {
+            Some(p) => {
+                Some(crate::storeencryption::WorkingCopy::open(&store_path, p)?)
+            }
+            None => None,
+        };
+
+        #[cfg(feature = "encryption")]
+        let path = match &working_copy {
+            Some(e) => e.path().display().to_string(),
+            None => store_path.display().to_string(),
+        };
+
+        #[cfg(not(feature = "encryption"))]
+        let path = store_path.display().to_string();
 
         let read_only = false;
         let connection_string: &str = &path;
 
         //TODO: Is this still the correct / best journal mode for us?
-        let mut db = SqliteConnectOptions::from_str(&connection_string)?
+        let options = SqliteConnectOptions::from_str(connection_string)?
             .journal_mode(SqliteJournalMode::Wal)
             .create_if_missing(true)
-            .read_only(read_only)
-            .connect()
+            .read_only(read_only);
+
+        let db = SqlitePoolOptions::new()
+            .max_connections(MAX_STORE_CONNECTIONS)
+            .connect_with(options)
             .await?;
 
+        let mut conn = db.acquire().await?;
+
         //is this an existing db, or a completly new one / first time?
 
-        let should_update_schema = match sqlx::query(
+        let current_version: Option<i32> = match sqlx::query(
             r#"
             SELECT max(version) as max_version FROM version
         "#,
         )
-        .fetch_one(&mut db)
+        .fetch_one(&mut conn)
+        .timed(TimingCategory::Database)
         .await
         {
-            Ok(e) => {
-                let version: i32 = e.try_get("max_version").unwrap_or(-1);
-                version != DB_SCHEMA_VERSION
-            }
-            Err(_e) => true,
+            Ok(e) => e.try_get("max_version").ok(),
+            Err(_e) => None,
         };
 
-        if should_update_schema {
-            eprintln!("Data store needs to be updated.");
-            sqlx::query(STORE_DB_SCHEMA).execute(&mut db).await?;
+        if let Some(v) = current_version {
+            if v > DB_SCHEMA_VERSION {
+                return Err(Error::Database {
+                    description: format!(
+                        "Activity store schema version {} is newer than supported version {}. Please update dcli.",
+                        v, DB_SCHEMA_VERSION
+                    ),
+                });
+            }
+        }
+
+        if current_version != Some(DB_SCHEMA_VERSION) {
+            let summary = storeschema::describe_upgrade(
+                current_version,
+                DB_SCHEMA_VERSION,
+            );
+            eprintln!("{}", summary);
+
+            //upgrades can rebuild the store, so we keep a copy of the
+            //existing data, and dont upgrade if we couldnt make one
+            if let Some(v) = current_version {
+                let backup = ActivityStoreInterface::backup_store(
+                    &mut conn,
+                    data_dir,
+                    v,
+                    #[cfg(feature = "encryption")]
+                    passphrase.as_deref(),
+                )
+                .await?;
+                eprintln!("Activity store backed up to {}", backup.display());
+            }
+
+            //only log actual upgrades, not creation of a new store
+            if current_version.is_some() {
+                if let Err(e) = storeschema::log_upgrade(
+                    &datadir::store_dir(data_dir),
+                    &summary,
+                ) {
+                    print_verbose(
+                        &format!("Could not log schema change : {}", e),
+                        verbose,
+                    );
+                }
+            }
+
+            ActivityStoreInterface::upgrade_schema(&mut conn, current_version)
+                .await?;
         }
 
-        Ok(ActivityStoreInterface { db, verbose, path })
+        drop(conn);
+
+        Ok(ActivityStoreInterface {
+            db,
+            verbose,
+            path,
+            data_dir: data_dir.to_path_buf(),
+            emit_events: false,
+            sync_pve: false,
+            store_raw_pgcr: false,
+            max_activities: None,
+            pgcr_concurrency: DEFAULT_PGCR_CONCURRENCY,
+            snapshot_path: None,
+
+            #[cfg(feature = "encryption")]
+            working_copy,
+        })
     }
 
-    /// TODO currently no way to sync old / delete characters. would be easy to
-    /// add by just moving the character sync into its own api sync_character(id, class_type)
-    /// but not going to worry about it unless someone requests it
-    /// retrieves and stores activity details for ids in activity queue
-    pub async fn sync(
-        &mut self,
-        member_id: &str,
-        platform: &Platform,
-    ) -> Result<SyncResult, Error> {
-        let api = ApiInterface::new(self.verbose)?;
+    //copies the store into the backups directory, named with the schema
+    //version and time, and returns the path of the copy. If the store is
+    //encrypted, so is the copy.
+    async fn backup_store(
+        conn: &mut SqliteConnection,
+        data_dir: &Path,
+        version: i32,
+        #[cfg(feature = "encryption")] passphrase: Option<&str>,
+    ) -> Result<PathBuf, Error> {
+        let dir = datadir::backups_dir(data_dir);
+        std::fs::create_dir_all(&dir)?;
+
+        let path = dir.join(format!(
+            "{}_v{}_{}.sqlite3",
+            STORE_BACKUP_PREFIX,
+            version,
+            Utc::now().format("%Y%m%dT%H%M%SZ")
+        ));
+
+        //VACUUM INTO gives us a consistent copy, including any data still
+        //in the wal
+        sqlx::query(&format!(
+            "VACUUM INTO '{}'",
+            path.display().to_string().replace('\'', "''")
+        ))
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
 
-        //TODO: call API to get display name
-        //https://www.bungie.net/Platform/Destiny2/1/Profile/4611686018429783292/?components=100,200
-        let player_info = api.get_player_info(member_id, platform).await?;
+        #[cfg(feature = "encryption")]
+        if let Some(p) = passphrase {
+            let enc = crate::storeencryption::encrypted_path(&path);
+            crate::storeencryption::encrypt_file(&path, &enc, p)?;
+            std::fs::remove_file(&path)?;
+            return Ok(enc);
+        }
 
-        let characters = player_info.characters;
+        Ok(path)
+    }
 
-        let display_name = player_info.user_info.display_name;
+    /// returns the paths of the store backups made before schema upgrades,
+    /// most recent first
+    pub fn list_backups(data_dir: &Path) -> Result<Vec<PathBuf>, Error> {
+        let dir = datadir::backups_dir(data_dir);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
 
-        let member_row_id = self
-            .insert_member_id(&member_id, &platform, &display_name)
-            .await?;
+        let mut out: Vec<(std::time::SystemTime, PathBuf)> = Vec::new();
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            //wal files kept alongside replaced stores are not backups
+            let is_backup = entry
+                .file_name()
+                .to_str()
+                .map(|n| {
+                    n.starts_with(STORE_BACKUP_PREFIX)
+                        && !n.ends_with("-wal")
+                        && !n.ends_with("-shm")
+                })
+                .unwrap_or(false);
+
+            if is_backup {
+                out.push((entry.metadata()?.modified()?, entry.path()));
+            }
+        }
 
-        let mut total_synced = 0;
-        let mut total_in_queue = 0;
+        out.sort_by_key(|b| std::cmp::Reverse(b.0));
+        Ok(out.into_iter().map(|(_, p)| p).collect())
+    }
 
-        eprintln!();
+    /// replaces the activity store with a backup (see list_backups). The
+    /// current store is moved into the backups directory rather than being
+    /// removed. Encrypted backups (.enc) replace the encrypted store. The
+    /// store must not be open, and can't be restored while it is being
+    /// synced.
+    pub fn restore_backup(data_dir: &Path, backup: &Path) -> Result<(), Error> {
+        if synclock::is_sync_in_progress(data_dir) {
+            return Err(Error::SyncInProgress {
+                description: "Cannot restore a backup while the activity store is being synced.".to_string(),
+            });
+        }
 
-        eprintln!(
-            "{}",
-            "Checking for new activities (public and private)".to_uppercase()
+        if !backup.is_file() {
+            return Err(Error::IoFileDoesNotExist {
+                description: format!("Backup not found : {}", backup.display()),
+            });
+        }
+
+        let store_path = datadir::store_dir(data_dir).join(STORE_FILE_NAME);
+        let encrypted = backup
+            .extension()
+            .map(|e| e == ENCRYPTED_STORE_EXTENSION)
+            .unwrap_or(false);
+
+        let dest = if encrypted {
+            let mut p = store_path.as_os_str().to_owned();
+            p.push(".");
+            p.push(ENCRYPTED_STORE_EXTENSION);
+            PathBuf::from(p)
+        } else {
+            store_path.clone()
+        };
+
+        //keep the store we are replacing (listed along with the other
+        //backups), in case the wrong backup was restored
+        let replaced = format!(
+            "{}_replaced_{}",
+            STORE_BACKUP_PREFIX,
+            Utc::now().format("%Y%m%dT%H%M%SZ")
         );
-        eprintln!("This may take a few minutes depending on the number of activities.");
-        for c in characters.characters {
-            let character_id = &c.id;
-            let character_row_id = self
-                .insert_character_id(&c.id, &c.class_type, member_row_id)
+        let dir = datadir::backups_dir(data_dir);
+        std::fs::create_dir_all(&dir)?;
+
+        //the wal may have changes which haven't been checkpointed into the
+        //store yet, so it is kept with it (sqlite finds it by name when the
+        //kept store is opened)
+        for ext in ["sqlite3", "sqlite3-wal", "sqlite3-shm"].iter() {
+            let p = store_path.with_extension(ext);
+            if p.exists() {
+                std::fs::rename(&p, dir.join(format!("{}.{}", replaced, ext)))?;
+            }
+        }
+
+        if dest.exists() {
+            std::fs::rename(
+                &dest,
+                dir.join(format!(
+                    "{}.sqlite3.{}",
+                    replaced, ENCRYPTED_STORE_EXTENSION
+                )),
+            )?;
+        }
+
+        std::fs::copy(backup, &dest)?;
+
+        Ok(())
+    }
+
+    //upgrades the store to DB_SCHEMA_VERSION. Stores which can't be migrated
+    //in place (new stores, and stores older than the base schema) are rebuilt
+    //from the base schema first.
+    pub(crate) async fn upgrade_schema(
+        db: &mut SqliteConnection,
+        current_version: Option<i32>,
+    ) -> Result<(), Error> {
+        let version = match current_version {
+            Some(v) if storeschema::can_migrate(v, DB_SCHEMA_VERSION) => v,
+            _ => {
+                sqlx::query(STORE_DB_SCHEMA)
+                    .execute(&mut *db)
+                    .timed(TimingCategory::Database)
+                    .await?;
+                BASE_SCHEMA_VERSION
+            }
+        };
+
+        for m in
+            storeschema::migrations_between(Some(version), DB_SCHEMA_VERSION)
+        {
+            sqlx::query("BEGIN TRANSACTION;")
+                .execute(&mut *db)
+                .timed(TimingCategory::Database)
                 .await?;
-            eprintln!("{}", format!("{}", c.class_type).to_uppercase());
 
-            //these calls could be a little more general purpose by taking api ids and not db ids.
-            //however, passing the db ids, lets us optimize a lot of the sql, and avoid
-            //some extra calls to the DB
+            match ActivityStoreInterface::_apply_migration(db, m).await {
+                Ok(_e) => {
+                    sqlx::query("COMMIT;")
+                        .execute(&mut *db)
+                        .timed(TimingCategory::Database)
+                        .await?;
+                }
+                Err(e) => {
+                    sqlx::query("ROLLBACK;")
+                        .execute(&mut *db)
+                        .timed(TimingCategory::Database)
+                        .await?;
+                    return Err(e);
+                }
+            }
+        }
 
-            let a = self.sync_activities(character_row_id, &api).await?;
+        Ok(())
+    }
 
-            let _b = self
-                .update_activity_queue(
-                    character_row_id,
-                    member_id,
-                    character_id,
-                    platform,
-                    &api,
-                )
+    async fn _apply_migration(
+        db: &mut SqliteConnection,
+        migration: &storeschema::SchemaMigration,
+    ) -> Result<(), Error> {
+        //can_migrate ensures all of the migrations we run have sql
+        if let Some(sql) = migration.sql {
+            sqlx::query(sql)
+                .execute(&mut *db)
+                .timed(TimingCategory::Database)
                 .await?;
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO "main"."version"("version") VALUES (?)
+            "#,
+        )
+        .bind(migration.version)
+        .execute(&mut *db)
+        .timed(TimingCategory::Database)
+        .await?;
 
-            let c = self.sync_activities(character_row_id, &api).await?;
+        Ok(())
+    }
 
-            total_synced += a.total_synced + c.total_synced;
-            total_in_queue += (a.total_available + c.total_available)
-                - (a.total_synced + c.total_synced);
+    /// opens the store for reading. If another process is currently syncing
+    /// the store, a snapshot of the store is made and opened instead, so
+    /// reports don't block or error while a long sync is running.
+    pub async fn init_read_only_with_path(
+        data_dir: &Path,
+        verbose: bool,
+    ) -> Result<ActivityStoreInterface, Error> {
+        if !synclock::is_sync_in_progress(data_dir) {
+            return ActivityStoreInterface::init_with_path(data_dir, verbose)
+                .await;
         }
 
-        Ok(SyncResult {
-            total_synced,
-            total_available: total_in_queue,
-        })
+        print_verbose(
+            "Sync in progress. Reading from a snapshot of the data store.",
+            verbose,
+        );
+
+        //if the store is encrypted, the syncing process's working copy is
+        //private to it, so we snapshot the encrypted store
+        let path = datadir::store_dir(data_dir).join(STORE_FILE_NAME);
+
+        ActivityStoreInterface::init_snapshot(data_dir, &path, verbose, true)
+            .await
     }
 
-    /// download results from ids in queue, and return number of items synced
-    async fn sync_activities(
-        &mut self,
-        character_row_id: i32,
-        api: &ApiInterface,
-    ) -> Result<SyncResult, Error> {
-        let mut ids: Vec<i64> = Vec::new();
+    /// opens a private, writable snapshot of the store. Activities can be
+    /// added to the snapshot (see add_unsynced_activities) for display,
+    /// without changing the store itself, or needing the sync lock. The
+    /// snapshot is removed when the store is closed.
+    ///
+    /// The snapshot is a full copy of the store, made each time this is
+    /// called, so it takes time and disk space proportional to the size of
+    /// the store. Returns Error::StoreTooLarge, without copying the store, if
+    /// it is larger than MAX_OVERLAY_STORE_SIZE.
+    pub async fn init_overlay_with_path(
+        data_dir: &Path,
+        verbose: bool,
+    ) -> Result<ActivityStoreInterface, Error> {
+        let size = ActivityStoreInterface::store_size(data_dir)?;
+        if size > MAX_OVERLAY_STORE_SIZE {
+            return Err(Error::StoreTooLarge {
+                description: format!(
+                    "Store is {} MB. Stores larger than {} MB are not copied.",
+                    size / (1024 * 1024),
+                    MAX_OVERLAY_STORE_SIZE / (1024 * 1024)
+                ),
+            });
+        }
 
-        //This is to scope rows, so the mutable borrow of self goes out of scope
-        {
-            let mut rows = sqlx::query(
-                r#"
-                    SELECT "activity_id" from "activity_queue" where character = ?
-                "#,
+        //if no one else is syncing, open the store first so it is created /
+        //upgraded (and decrypted) before we snapshot it
+        let source = if synclock::is_sync_in_progress(data_dir) {
+            None
+        } else {
+            Some(
+                ActivityStoreInterface::init_with_path(data_dir, verbose)
+                    .await?,
             )
-            .bind(format!("{}", character_row_id))
-            .fetch(&mut self.db);
+        };
 
-            while let Some(row) = rows.try_next().await? {
-                let activity_id: i64 = row.try_get("activity_id")?;
-                ids.push(activity_id);
-            }
+        let path = match &source {
+            Some(e) => PathBuf::from(&e.path),
+            None => datadir::store_dir(data_dir).join(STORE_FILE_NAME),
         };
 
-        if ids.is_empty() {
-            return Ok(SyncResult {
-                total_available: 0,
-                total_synced: 0,
-            });
+        let out = ActivityStoreInterface::init_snapshot(
+            data_dir, &path, verbose, false,
+        )
+        .await;
+
+        if let Some(e) = source {
+            e.close().await?;
         }
 
-        let total_available = ids.len() as u32;
-        let mut total_synced = 0;
+        out
+    }
 
-        let s = if ids.len() == 1 { "y" } else { "ies" };
-        eprintln!(
-            "{}",
-            format!("Retrieving details for {} activit{}", ids.len(), s)
-        );
+    //size on disk of the store in the data dir (including its wal file, and
+    //the encrypted store, if there is one)
+    fn store_size(data_dir: &Path) -> Result<u64, Error> {
+        let path = datadir::store_dir(data_dir).join(STORE_FILE_NAME);
 
-        eprintln!(
-            "Each dot represents {} activities",
-            PGCR_REQUEST_CHUNK_AMOUNT
-        );
-        eprint!("[");
-        for id_chunks in ids.chunks(PGCR_REQUEST_CHUNK_AMOUNT) {
-            let mut f = Vec::new();
+        let mut paths = vec![path.clone()];
+        let mut wal = path.as_os_str().to_owned();
+        wal.push("-wal");
+        paths.push(PathBuf::from(wal));
 
-            for c in id_chunks {
-                //this is saving the future, call hasnt been made yet
-                f.push(api.retrieve_post_game_carnage_report(*c));
+        #[cfg(feature = "encryption")]
+        paths.push(crate::storeencryption::encrypted_path(&path));
+
+        let mut size = 0;
+        for p in paths {
+            if p.exists() {
+                size += std::fs::metadata(p)?.len();
             }
+        }
 
-            eprint!(".");
+        Ok(size)
+    }
 
-            //TODO: look into using threading for this
-            let results = futures::future::join_all(f).await;
+    //copies the store at path into the cache dir and opens the copy
+    async fn init_snapshot(
+        data_dir: &Path,
+        path: &Path,
+        verbose: bool,
+        read_only: bool,
+    ) -> Result<ActivityStoreInterface, Error> {
+        let cache_dir = datadir::cache_dir(data_dir);
+        std::fs::create_dir_all(&cache_dir)?;
 
-            //loop through. if we get results. grab those, otherwise, we ignore
-            //any errors, as that will keep the IDs in the queue to try next time
-            //TODO: this is a mess. can we simpify and not nest so deeply?
-            for r in results {
-                match r {
-                    Ok(e) => {
-                        match e {
-                            Some(e) => match self
-                                .insert_activity(&e, character_row_id)
-                                .await
-                            {
-                                Ok(_e) => {
-                                    total_synced += 1;
-                                }
-                                Err(e) => {
-                                    eprintln!();
-                                    eprintln!(
-                                        "Error inserting data into character activity stats table. Skipping. : {}",
-                                        e,
-                                    );
-                                }
-                            },
-                            None => {
-                                eprintln!();
-                                eprintln!(
-                                    "PGCR returned empty response. Ignoring."
-                                );
-                                //TODO: should not get here, as none means either an API error
-                                //occured or there is no data associated with the ID (which is
-                                //an api data error).
-                                //we will just ignore it here, with the assumption that any error
-                                //is temporary, and will be fixed next time we sync
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!();
-                        eprintln!(
-                            "Error retrieving activity details from api. Skipping : {}",
-                            e
-                        );
-                    }
-                }
+        let snapshot_path = cache_dir
+            .join(format!("store_snapshot_{}.sqlite3", std::process::id()));
+
+        if snapshot_path.exists() {
+            std::fs::remove_file(&snapshot_path)?;
+        }
+
+        #[cfg(feature = "encryption")]
+        let passphrase = crate::storeencryption::passphrase_from_env();
+
+        //encrypted stores which are not open in this process are decrypted
+        //straight into the snapshot
+        #[cfg(feature = "encryption")]
+        let working_copy = match &passphrase {
+            Some(p) if !path.exists() => {
+                Some(crate::storeencryption::WorkingCopy::snapshot(
+                    path,
+                    &snapshot_path,
+                    p,
+                )?)
+            }
+            Some(_) => {
+                Some(crate::storeencryption::WorkingCopy::guard(&snapshot_path))
             }
+            None => None,
+        };
+
+        #[cfg(feature = "encryption")]
+        let copied = snapshot_path.exists();
+
+        #[cfg(not(feature = "encryption"))]
+        let copied = false;
+
+        //VACUUM INTO runs inside a read transaction, so we get a consistent
+        //copy of the store without blocking the syncing process
+        if !copied {
+            let mut live =
+                SqliteConnectOptions::from_str(&path.display().to_string())?
+                    .journal_mode(SqliteJournalMode::Wal)
+                    .read_only(true)
+                    .connect()
+                    .await?;
+
+            sqlx::query(&format!(
+                "VACUUM INTO '{}'",
+                snapshot_path.display().to_string().replace('\'', "''")
+            ))
+            .execute(&mut live)
+            .timed(TimingCategory::Database)
+            .await?;
+            live.close().await?;
+        } else {
+            //decrypted stores are still in wal mode, which can't be changed
+            //from a read only connection
+            SqliteConnectOptions::from_str(
+                &snapshot_path.display().to_string(),
+            )?
+            .journal_mode(SqliteJournalMode::Delete)
+            .connect()
+            .await?
+            .close()
+            .await?;
         }
 
-        eprintln!("]");
-        eprintln!(
-            "{} of {} synced ({}%)",
-            total_synced,
-            total_available,
-            ((total_synced as f32 / total_available as f32) * 100.0).floor()
-        );
+        let options = SqliteConnectOptions::from_str(
+            &snapshot_path.display().to_string(),
+        )?
+        .journal_mode(SqliteJournalMode::Delete)
+        .read_only(read_only);
 
-        Ok(SyncResult {
-            total_synced,
-            total_available,
+        let db = SqlitePoolOptions::new()
+            .max_connections(MAX_STORE_CONNECTIONS)
+            .connect_with(options)
+            .await?;
+
+        Ok(ActivityStoreInterface {
+            db,
+            verbose,
+            path: snapshot_path.display().to_string(),
+            data_dir: data_dir.to_path_buf(),
+            emit_events: false,
+            sync_pve: false,
+            store_raw_pgcr: false,
+            max_activities: None,
+            pgcr_concurrency: DEFAULT_PGCR_CONCURRENCY,
+            snapshot_path: Some(snapshot_path),
+
+            #[cfg(feature = "encryption")]
+            working_copy,
         })
     }
 
-    async fn update_activity_queue(
-        &mut self,
-        character_row_id: i32,
-        member_id: &str,
-        character_id: &str,
-        platform: &Platform,
-        api: &ApiInterface,
-    ) -> Result<SyncResult, Error> {
-        //TODO catch errors so we can continue?
-        let prv_result = self
-            ._update_activity_queue(
-                character_row_id,
-                member_id,
-                character_id,
-                platform,
-                &Mode::PrivateMatchesAll,
-                &api,
-            )
-            .await?;
+    /// closes the connection to the store. If the store is encrypted, the
+    /// working copy is encrypted and removed.
+    pub async fn close(self) -> Result<(), Error> {
+        self.db.close().await;
 
-        let pub_result = self
-            ._update_activity_queue(
-                character_row_id,
+        if let Some(p) = &self.snapshot_path {
+            std::fs::remove_file(p)?;
+        }
+
+        #[cfg(feature = "encryption")]
+        if let Some(e) = self.working_copy {
+            e.close()?;
+        }
+
+        Ok(())
+    }
+
+    /// syncs all members registered with add_sync_member, in the order they
+    /// were added. An error syncing one member is returned in that member's
+    /// result, and does not stop the other members from being synced, unless
+    /// the sync was cancelled, in which case Error::Cancelled is returned.
+    pub async fn sync_all(
+        &self,
+        cancel: &CancellationToken,
+    ) -> Result<Vec<MemberSyncResult>, Error> {
+        let members = self.retrieve_sync_members().await?;
+
+        let mut out: Vec<MemberSyncResult> = Vec::new();
+        for (member_id, platform) in members {
+            let result = match self.sync(&member_id, &platform, cancel).await {
+                Err(Error::Cancelled) => return Err(Error::Cancelled),
+                e => e,
+            };
+
+            out.push(MemberSyncResult {
                 member_id,
-                character_id,
                 platform,
-                &Mode::AllPvP,
-                &api,
-            )
-            .await?;
+                result,
+            });
+        }
 
-        Ok(pub_result + prv_result)
+        Ok(out)
     }
 
-    //updates activity id queue with ids which have not been synced
-    async fn _update_activity_queue(
-        &mut self,
-        character_row_id: i32,
+    /// registers a member to be synced by sync_all. If the member is already
+    /// registered, their platform is updated.
+    pub async fn add_sync_member(
+        &self,
         member_id: &str,
-        character_id: &str,
         platform: &Platform,
-        mode: &Mode,
-        api: &ApiInterface,
-    ) -> Result<SyncResult, Error> {
-        let max_id: i64 =
-            self.get_max_activity_id(character_row_id, mode).await?;
+    ) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO "main"."sync_member"("member_id", "platform_id", "created_at")
+            VALUES (?, ?, ?)
+            ON CONFLICT(member_id) DO UPDATE SET platform_id=excluded.platform_id
+            "#,
+        )
+        .bind(member_id)
+        .bind(platform.to_id())
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
 
-        let result = api
-            .retrieve_activities_since_id(
-                member_id,
-                character_id,
-                platform,
-                mode,
-                max_id,
-            )
-            .await?;
+        Ok(())
+    }
 
-        if result.is_none() {
-            return Ok(SyncResult {
-                total_available: 0,
-                total_synced: 0,
-            });
-        }
+    /// removes the member from the members synced by sync_all. Returns false
+    /// if the member was not registered. Synced activities are not removed.
+    pub async fn remove_sync_member(
+        &self,
+        member_id: &str,
+    ) -> Result<bool, Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM "main"."sync_member" WHERE member_id = ?
+            "#,
+        )
+        .bind(member_id)
+        .execute(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
 
-        let mut activities = result.unwrap();
-        eprintln!("{} new activities found", activities.len());
+        Ok(result.rows_affected() > 0)
+    }
 
-        //reverse them so we add the oldest first
-        activities.reverse();
+    /// returns the members synced by sync_all, in the order they were added
+    pub async fn retrieve_sync_members(
+        &self,
+    ) -> Result<Vec<(String, Platform)>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT member_id, platform_id FROM "main"."sync_member"
+            ORDER BY rowid
+            "#,
+        )
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
 
-        // TODO: think through this
-        // Right now, we do all inserts in one transaction. This gives a significant performance
-        // increse when inserting large number of activities at one time (i.e. on first sync).
-        // however, it means if something goes wrong, nothing will be inserted, and if we
-        // come across some data that causes a bug inserting, then nothing would ever be inserted
-        // (until we fixed the bug). Probably shouldnt be an issue, since any weird stuff with
-        // api data should be caught by the json deserializer in apiinterface
-        sqlx::query("BEGIN TRANSACTION;")
-            .execute(&mut self.db)
-            .await?;
+        let mut out = Vec::new();
+        for row in &rows {
+            let platform_id: u32 = row.try_get("platform_id")?;
+            out.push((
+                row.try_get("member_id")?,
+                Platform::from_id(platform_id),
+            ));
+        }
 
-        let mut total = 0;
+        Ok(out)
+    }
 
-        for activity in activities {
-            let director_activity_hash =
-                activity.details.director_activity_hash;
-
-            //these are DestinyActivityDefinition manifest hashes for gambit private
-            //matches
-            //TODO: can rewrite this to short circuit when first result found
-            //if !(director_activity_hash != 2526740498 && director_activity_hash != 248695599)
-            if director_activity_hash == 2526740498
-                || director_activity_hash == 248695599
-                || director_activity_hash == 248695599
-            {
-                //gambit private matches. ignoring
+    /// removes a member's synced data from the store, i.e. a member that was
+    /// synced by mistake. Activities are removed unless another synced
+    /// member (one with sync history, or part way through a sync) played in
+    /// them, in which case the member is kept as a player in those
+    /// activities. Players left without any activities are removed.
+    ///
+    /// Returns None if the member has not been synced into the store. Every
+    /// stored activity was synced for a member who played in it, so
+    /// Error::SyncedMembersUnknown is returned if any activity has no known
+    /// synced member (i.e. members synced before sync history was recorded),
+    /// since their activities could otherwise be removed. Syncing those
+    /// members records their history.
+    pub async fn purge_member(
+        &self,
+        member_id: &str,
+    ) -> Result<Option<MemberPurgeResult>, Error> {
+        if self.snapshot_path.is_some() {
+            return Err(Error::SyncInProgress {
+                description:
+                    "Cannot purge a member from a snapshot of the data store."
+                        .to_string(),
+            });
+        }
 
-                continue;
-            }
+        //only synced members can be purged, so we never remove the history
+        //of a player who just appears in other members' activities
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT id FROM "main"."member" WHERE member_id = ? AND
+                member_id IN ({})
+            "#,
+            SYNCED_MEMBER_IDS_SQL
+        ))
+        .bind(member_id)
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
 
-            total += 1;
+        let member_row_id: i32 = match rows.first() {
+            Some(e) => e.try_get("id")?,
+            None => return Ok(None),
+        };
 
-            let instance_id = activity.details.instance_id;
+        //we dont want to remove activities while a sync is writing them
+        let _lock = SyncLock::acquire(&self.data_dir)?;
 
-            match sqlx::query(
-                "INSERT into activity_queue ('activity_id', 'character') VALUES (?, ?)",
-            )
-            .bind(instance_id)
-            .bind(character_row_id)
-            .execute(&mut self.db)
-            .await
-            {
-                Ok(_e) => (),
-                Err(e) => {
-                    sqlx::query("ROLLBACK;").execute(&mut self.db).await?;
-                    return Err(Error::from(e));
-                }
-            };
+        //activities are only synced for members who played in them, so an
+        //activity without a known synced member was synced for a member we
+        //don't know about, who we could remove activities from
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT count(*) as count FROM "main"."activity" WHERE
+                not exists (
+                    SELECT 1 FROM character_activity_stats
+                    INNER JOIN
+                        character ON character_activity_stats.character = character.id,
+                        member ON character.member = member.id
+                    WHERE
+                        character_activity_stats.activity = activity.id AND
+                        member.member_id IN ({})
+                )
+            "#,
+            SYNCED_MEMBER_IDS_SQL
+        ))
+        .fetch_one(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let unknown: i64 = row.try_get("count")?;
+        if unknown > 0 {
+            return Err(Error::SyncedMembersUnknown {
+                description: format!(
+                    "{} activities were synced for members without sync history. Sync those members, and then try again.",
+                    unknown
+                ),
+            });
         }
-        sqlx::query("COMMIT;").execute(&mut self.db).await?;
 
-        Ok(SyncResult {
-            total_available: total,
-            total_synced: total,
-        })
-    }
+        let mut conn = self.db.acquire().await?;
 
-    async fn insert_activity(
-        &mut self,
-        data: &DestinyPostGameCarnageReportData,
-        character_row_id: i32,
-    ) -> Result<(), Error> {
         sqlx::query("BEGIN TRANSACTION;")
-            .execute(&mut self.db)
+            .execute(&mut conn)
+            .timed(TimingCategory::Database)
             .await?;
 
-        match self._insert_activity(data, character_row_id).await {
-            Ok(_e) => {
-                sqlx::query("COMMIT;").execute(&mut self.db).await?;
-                sqlx::query("PRAGMA OPTIMIZE;")
-                    .execute(&mut self.db)
+        match Self::_purge_member(&mut conn, member_id, member_row_id).await {
+            Ok(e) => {
+                sqlx::query("COMMIT;")
+                    .execute(&mut conn)
+                    .timed(TimingCategory::Database)
                     .await?;
-
-                Ok(())
+                Ok(Some(e))
             }
             Err(e) => {
-                sqlx::query("ROLLBACK;").execute(&mut self.db).await?;
+                sqlx::query("ROLLBACK;")
+                    .execute(&mut conn)
+                    .timed(TimingCategory::Database)
+                    .await?;
                 Err(e)
             }
         }
     }
 
-    //todo: this doesnt need to be an instance fn, not sure if it matters
-    fn get_medal_hash_value(
-        &self,
-        property: &str,
-        medal_hash: &HashMap<String, DestinyHistoricalStatsValue>,
-    ) -> u32 {
-        match medal_hash.get(property) {
-            Some(e) => e.basic.value as u32,
-            None => 0,
-        }
-    }
-
-    async fn _insert_activity(
-        &mut self,
-        data: &DestinyPostGameCarnageReportData,
-        character_row_id: i32,
-    ) -> Result<(), Error> {
-        //see if we already have this activity
-        match self
-            .get_activity_row_id(data.activity_details.instance_id)
-            .await
-        {
-            Ok(_e) => {
-                return Ok(());
-            }
-            Err(_e) => (),
-        };
-
-        //todo:if it already exists, what should we do? we have the data? do we need to remove
-        //from queue?
-        sqlx::query(
+    async fn _purge_member(
+        conn: &mut SqliteConnection,
+        member_id: &str,
+        member_row_id: i32,
+    ) -> Result<MemberPurgeResult, Error> {
+        //activities the member played in, which no other synced member
+        //played in
+        let rows = sqlx::query(&format!(
             r#"
-            INSERT OR IGNORE INTO "main"."activity"
-                ("activity_id","period","mode","platform","director_activity_hash", "reference_id") 
-            VALUES (?,?,?,?,?, ?)
-        "#,
-        )
-        .bind(data.activity_details.instance_id) //activity_id
-        .bind(data.period.to_rfc3339()) //period
-        .bind(data.activity_details.mode.to_id().to_string()) //mode
-        .bind(data.activity_details.membership_type.to_id().to_string()) //platform
-        .bind(data.activity_details.director_activity_hash.to_string()) //director_activity_hash
-        .bind(data.activity_details.reference_id.to_string()) //reference_id
-        .execute(&mut self.db)
+            SELECT DISTINCT
+                activity.id as id,
+                activity.activity_id as activity_id
+            FROM
+                character_activity_stats
+            INNER JOIN
+                character ON character_activity_stats.character = character.id,
+                activity ON character_activity_stats.activity = activity.id
+            WHERE
+                character.member = ? AND
+                not exists (
+                    SELECT 1 FROM character_activity_stats as other
+                    INNER JOIN
+                        character as other_character ON other.character = other_character.id,
+                        member as other_member ON other_character.member = other_member.id
+                    WHERE
+                        other.activity = activity.id AND
+                        other_member.id != ? AND
+                        other_member.member_id IN ({})
+                )
+            "#,
+            SYNCED_MEMBER_IDS_SQL
+        ))
+        .bind(member_row_id)
+        .bind(member_row_id)
+        .fetch_all(&mut *conn)
+        .timed(TimingCategory::Database)
         .await?;
 
-        let activity_row_id = self
-            .get_activity_row_id(data.activity_details.instance_id)
-            .await?;
+        //modes, teams, player stats, weapons and medals are removed along
+        //with the activity (on delete cascade)
+        for row in &rows {
+            let activity_row_id: i64 = row.try_get("id")?;
+            let activity_id: i64 = row.try_get("activity_id")?;
 
-        for team in &data.teams {
             sqlx::query(
                 r#"
-                INSERT INTO "main"."team_result"
-                (
-                    "team_id", "score", "standing", "activity"
-                )
-                VALUES(?,?, ?, ?)
+                DELETE FROM "main"."combat_rating" WHERE activity_id = ?
                 "#,
             )
-            .bind(team.team)
-            .bind(team.score as i32)
-            .bind(team.standing as i32)
-            .bind(activity_row_id)
-            .execute(&mut self.db)
+            .bind(activity_id)
+            .execute(&mut *conn)
+            .timed(TimingCategory::Database)
             .await?;
-        }
-
-        //TODO: Rumble will have no teams. Need to create one
 
-        for mode in &data.activity_details.modes {
             sqlx::query(
                 r#"
-                INSERT INTO "main"."modes"
-                (
-                    "mode", "activity"
-                )
-                VALUES(?,?)
+                DELETE FROM "main"."activity" WHERE id = ?
                 "#,
             )
-            .bind(mode.to_id().to_string())
             .bind(activity_row_id)
-            .execute(&mut self.db)
+            .execute(&mut *conn)
+            .timed(TimingCategory::Database)
             .await?;
         }
 
-        for entry in &data.entries {
-            //todo: not sure if we should use membership type of crosssave orveride
-            let member_row_id = self
-                .insert_member_id(
-                    &entry.player.user_info.membership_id,
-                    &entry.player.user_info.membership_type,
-                    &entry.player.user_info.display_name,
-                )
-                .await?;
-
-            let class_type = CharacterClass::from_hash(entry.player.class_hash);
+        sqlx::query(
+            r#"
+            DELETE FROM "main"."activity_queue" WHERE character IN
+                (SELECT id FROM "main"."character" WHERE member = ?)
+            "#,
+        )
+        .bind(member_row_id)
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
 
-            let character_row_id = self
-                .insert_character_id(
-                    &entry.character_id,
-                    &class_type,
-                    member_row_id,
-                )
-                .await?;
+        sqlx::query(
+            r#"
+            DELETE FROM "main"."history_cursor" WHERE character IN
+                (SELECT id FROM "main"."character" WHERE member = ?)
+            "#,
+        )
+        .bind(member_row_id)
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
 
-            self._insert_character_activity_stats(
-                &entry,
-                character_row_id,
-                activity_row_id,
-            )
+        //sync_log rows are removed along with their sync_history runs
+        for table in &[
+            "sync_member",
+            "sync_history",
+            "character_metadata",
+            "competitive_progress",
+        ] {
+            sqlx::query(&format!(
+                r#"DELETE FROM "main"."{}" WHERE member_id = ?"#,
+                table
+            ))
+            .bind(member_id)
+            .execute(&mut *conn)
+            .timed(TimingCategory::Database)
             .await?;
         }
 
-        self.remove_from_activity_queue(
-            &character_row_id,
-            &data.activity_details.instance_id,
+        //this includes the purged member, unless they played with another
+        //synced member, along with the other players from the removed
+        //activities
+        Self::_remove_orphaned_players(&mut *conn).await?;
+
+        let remaining = sqlx::query(
+            r#"
+            SELECT id FROM "main"."member" WHERE id = ?
+            "#,
+        )
+        .bind(member_row_id)
+        .fetch_all(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        Ok(MemberPurgeResult {
+            activities_removed: rows.len() as u32,
+            member_removed: remaining.is_empty(),
+        })
+    }
+
+    //removes characters, and then members, which are no longer in any
+    //activities and are not synced members. Returns the number of members
+    //removed.
+    async fn _remove_orphaned_players(
+        conn: &mut SqliteConnection,
+    ) -> Result<u64, Error> {
+        sqlx::query(
+            r#"
+            DELETE FROM "main"."character" WHERE
+                not exists (
+                    SELECT 1 FROM character_activity_stats
+                    WHERE character_activity_stats.character = character.id
+                ) AND
+                member NOT IN (
+                    SELECT id FROM "main"."member" WHERE member_id IN (
+                        SELECT member_id FROM "main"."sync_history"
+                        UNION SELECT member_id FROM "main"."sync_member"
+                        UNION SELECT member_id FROM "main"."character_metadata"
+                    )
+                )
+            "#,
         )
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
         .await?;
 
-        Ok(())
+        let result = sqlx::query(
+            r#"
+            DELETE FROM "main"."member" WHERE
+                not exists (
+                    SELECT 1 FROM "main"."character"
+                    WHERE character.member = member.id
+                ) AND
+                member_id NOT IN (
+                    SELECT member_id FROM "main"."sync_history"
+                    UNION SELECT member_id FROM "main"."sync_member"
+                    UNION SELECT member_id FROM "main"."character_metadata"
+                )
+            "#,
+        )
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        Ok(result.rows_affected())
     }
+    /// removes all activities (along with their teams, player stats, weapons,
+    /// medals and combat ratings) which started before the cutoff, and any
+    /// players which are no longer in any activities. The cutoff is kept in
+    /// the store, and activities older than it are not synced again. Space
+    /// is not reclaimed until the store is compacted (see run_maintenance).
+    pub async fn prune_before(
+        &self,
+        cutoff: &DateTime<Utc>,
+    ) -> Result<PruneResult, Error> {
+        if self.snapshot_path.is_some() {
+            return Err(Error::SyncInProgress {
+                description:
+                    "Cannot prune activities from a snapshot of the data store."
+                        .to_string(),
+            });
+        }
 
-    async fn _insert_character_activity_stats(
-        &mut self,
-        entry: &DestinyPostGameCarnageReportEntry,
-        character_row_id: i32,
-        activity_row_id: i32,
-    ) -> Result<(), Error> {
-        let char_data = entry;
+        //we dont want to remove activities while a sync is writing them
+        let _lock = SyncLock::acquire(&self.data_dir)?;
 
-        let medal_hash: &HashMap<String, DestinyHistoricalStatsValue> =
-            &entry.extended.values;
+        let mut conn = self.db.acquire().await?;
 
-        let precision_kills: u32 =
-            self.get_medal_hash_value("precisionKills", medal_hash);
-        let weapon_kills_ability: u32 =
-            self.get_medal_hash_value("weaponKillsAbility", medal_hash);
-        let weapon_kills_grenade: u32 =
-            self.get_medal_hash_value("weaponKillsGrenade", medal_hash);
-        let weapon_kills_melee: u32 =
-            self.get_medal_hash_value("weaponKillsMelee", medal_hash);
-        let weapon_kills_super: u32 =
-            self.get_medal_hash_value("weaponKillsSuper", medal_hash);
-        let all_medals_earned: u32 =
-            self.get_medal_hash_value("allMedalsEarned", medal_hash);
+        sqlx::query("BEGIN TRANSACTION;")
+            .execute(&mut conn)
+            .timed(TimingCategory::Database)
+            .await?;
+
+        match Self::_prune_before(&mut conn, cutoff).await {
+            Ok(e) => {
+                sqlx::query("COMMIT;")
+                    .execute(&mut conn)
+                    .timed(TimingCategory::Database)
+                    .await?;
+                Ok(e)
+            }
+            Err(e) => {
+                sqlx::query("ROLLBACK;")
+                    .execute(&mut conn)
+                    .timed(TimingCategory::Database)
+                    .await?;
+                Err(e)
+            }
+        }
+    }
 
+    async fn _prune_before(
+        conn: &mut SqliteConnection,
+        cutoff: &DateTime<Utc>,
+    ) -> Result<PruneResult, Error> {
+        let cutoff_str = cutoff.to_rfc3339();
+
+        //combat ratings are keyed by activity id, so are not removed along
+        //with the activity
         sqlx::query(
             r#"
-            INSERT INTO "main"."character_activity_stats"
-            (
-                "character", "assists", "score", "kills", "deaths", 
-                "average_score_per_kill", "average_score_per_life", "completed", 
-                "opponents_defeated", "activity_duration_seconds", "standing", 
-                "team", "completion_reason", "start_seconds", "time_played_seconds", 
-                "player_count", "team_score", "precision_kills", "weapon_kills_ability", 
-                "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", 
-                "all_medals_earned", "light_level", "activity"
-            )
-            VALUES (
-                ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?,
-                ?, ? )
+            DELETE FROM "main"."combat_rating" WHERE activity_id IN
+                (SELECT activity_id FROM "main"."activity" WHERE period < ?)
             "#,
         )
-        //we for through format, as otherwise we have to cast to i32, and while
-        //shouldnt be an issue, there is a chance we could lose precision when
-        //converting some of the IDS. so we just do this to be consistent.
-        //TODO: should think about losing data when pulling out of DB
-        .bind(character_row_id as i32) //character
-        .bind(char_data.values.assists as i32) //assists
-        .bind(char_data.values.score as i32) //score
-        .bind(char_data.values.kills as i32) //kiis
-        .bind(char_data.values.deaths as i32) //deaths
-        .bind(char_data.values.average_score_per_kill) //average_score_per_kill
-        .bind(char_data.values.average_score_per_life) //average_score_per_life
-        .bind(char_data.values.completed as i32) //completed
-        .bind(char_data.values.opponents_defeated as i32) //opponents_defeated
-        .bind(format!(
-            "{}",
-            char_data.values.activity_duration_seconds as u32
-        )) //activity_duration_seconds
-        .bind(char_data.values.standing as i32) //standing
-        .bind(char_data.values.team as i32) //team
-        .bind(char_data.values.completion_reason as i32) //completion_reason
-        .bind(char_data.values.start_seconds as i32) //start_seconds
-        .bind(char_data.values.time_played_seconds as i32) //time_played_seconds
-        .bind(char_data.values.player_count as i32) //player_count
-        .bind(char_data.values.team_score as i32) //team_score
-        .bind(precision_kills as i32) //precision_kills
-        .bind(weapon_kills_ability as i32) //weapon_kills_ability
-        .bind(weapon_kills_grenade as i32) //weapon_kills_grenade
-        .bind(weapon_kills_melee as i32) //weapon_kills_melee
-        .bind(weapon_kills_super as i32) //weapon_kills_super
-        .bind(all_medals_earned as i32) //weapon_kills_super
-        .bind(char_data.player.light_level) //activity
-        .bind(activity_row_id) //activity
-        .execute(&mut self.db)
+        .bind(&cutoff_str)
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
         .await?;
 
-        //character_activity_stats
-
-        let row = sqlx::query(
+        //modes, teams, player stats, weapons and medals are removed along
+        //with the activity (on delete cascade)
+        let activities = sqlx::query(
             r#"
-            SELECT "id" FROM "character_activity_stats" WHERE activity = ? and character = ?
-        "#,
+            DELETE FROM "main"."activity" WHERE period < ?
+            "#,
         )
-        .bind(activity_row_id)
-        .bind(character_row_id)
-        .fetch_one(&mut self.db)
+        .bind(&cutoff_str)
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
         .await?;
 
-        let character_activity_stats_id: i32 = row.try_get("id")?;
-
-        for (key, value) in medal_hash {
-            sqlx::query(
-                r#"
-                INSERT INTO "main"."medal_result"
-                (
-                    "reference_id", "count", "character_activity_stats"
-                )
-                VALUES  (
-                    ?,?,?
-                )
-                "#,
-            )
-            .bind(key) //reference_id
-            .bind(format!("{}", value.basic.value as u32)) //unique_weapon_kills
-            .bind(character_activity_stats_id)
-            .execute(&mut self.db)
-            .await?;
-        }
-
-        //ran into a case once where weapons was missing, so have to check here
-        if char_data.extended.weapons.is_some() {
-            let weapons = entry.extended.weapons.as_ref().unwrap();
-            for w in weapons {
-                sqlx::query(
-                    r#"
-                    INSERT INTO "main"."weapon_result"
-                    (
-                        "reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats"
-                    )
-                    VALUES (?, ?, ?, ?, ?)
-                    "#,
-                )
-                .bind(format!("{}", w.reference_id)) //reference_id
-                .bind(format!("{}", w.values.unique_weapon_kills as u32)) //unique_weapon_kills
-                .bind(format!("{}", w.values.unique_weapon_precision_kills as u32)) //unique_weapon_precision_kills
-                .bind(format!("{}", w.values.unique_weapon_kills_precision_kills)) //unique_weapon_kills_precision_kills
-                .bind(character_activity_stats_id)
-                .execute(&mut self.db)
-                .await?;
-            }
-        }
-
-        Ok(())
-    }
+        let players_removed =
+            Self::_remove_orphaned_players(&mut *conn).await?;
 
-    async fn remove_from_activity_queue(
-        &mut self,
-        character_row_id: &i32,
-        instance_id: &i64,
-    ) -> Result<(), Error> {
+        //only move the cutoff forward, so pruning with an earlier date doesnt
+        //let activities we have already removed be synced again
         sqlx::query(
             r#"
-            DELETE FROM "main"."activity_queue" WHERE character = ? and activity_id = ?
-        "#,
+            INSERT INTO "main"."prune_cutoff" (id, cutoff, pruned_at)
+            VALUES (1, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                cutoff = max(cutoff, excluded.cutoff),
+                pruned_at = excluded.pruned_at
+            "#,
         )
-        .bind(character_row_id.to_string())
-        .bind(instance_id)
-        .execute(&mut self.db)
+        .bind(&cutoff_str)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
         .await?;
 
-        Ok(())
+        Ok(PruneResult {
+            activities_removed: activities.rows_affected(),
+            players_removed,
+        })
     }
 
-    async fn get_activity_row_id(
-        &mut self,
-        instance_id: i64,
-    ) -> Result<i32, Error> {
+    /// returns the date before which activities have been pruned from the
+    /// store, if they have been (see prune_before)
+    pub async fn retrieve_prune_cutoff(
+        &self,
+    ) -> Result<Option<DateTime<Utc>>, Error> {
         let row = sqlx::query(
             r#"
-            SELECT "id" FROM "activity" WHERE activity_id = ?
-        "#,
+            SELECT cutoff FROM "main"."prune_cutoff" WHERE id = 1
+            "#,
         )
-        .bind(instance_id.to_string())
-        .fetch_one(&mut self.db)
+        .fetch_optional(&self.db)
+        .timed(TimingCategory::Database)
         .await?;
 
-        let id: i32 = row.try_get("id")?;
+        let row = match row {
+            Some(e) => e,
+            None => return Ok(None),
+        };
 
-        Ok(id)
+        let cutoff: String = row.try_get("cutoff")?;
+        match DateTime::parse_from_rfc3339(&cutoff) {
+            Ok(e) => Ok(Some(e.with_timezone(&Utc))),
+            Err(_e) => Err(Error::Database {
+                description: format!("Invalid prune cutoff : {}", cutoff),
+            }),
+        }
     }
 
-    async fn get_character_row_id(
-        &mut self,
-        member_id: &str,
-        character_id: &str,
-    ) -> Result<i32, Error> {
-        let row = sqlx::query(
-            r#"
-            SELECT
-                character.id as id 
-            FROM
-                "character"
-            JOIN
-                member on character.member = member.id and member.member_id = ?
-            WHERE
-                character_id = ?
-        "#,
-        )
-        .bind(member_id.to_string())
-        .bind(character_id.to_string())
-        .fetch_one(&mut self.db)
-        .await?;
+    /// updates the seasons stored in the data store from the season
+    /// definitions in the manifest, and tags any activities which do not have
+    /// a season yet. Activities are tagged with their season as they are
+    /// synced, so this only needs to be called when the manifest has been
+    /// updated. Returns the number of activities which were tagged.
+    pub async fn update_seasons(
+        &self,
+        manifest: &mut ManifestInterface,
+    ) -> Result<u64, Error> {
+        if self.snapshot_path.is_some() {
+            return Err(Error::SyncInProgress {
+                description:
+                    "Cannot update seasons in a snapshot of the data store."
+                        .to_string(),
+            });
+        }
 
-        let character_rowid: i32 = row.try_get("id")?;
+        let definitions = manifest.get_season_definitions().await?;
 
-        Ok(character_rowid)
-    }
+        let _lock = SyncLock::acquire(&self.data_dir)?;
+        let mut conn = self.db.acquire().await?;
 
-    async fn insert_member_id(
-        &mut self,
-        member_id: &str,
-        platform: &Platform,
-        display_name: &str,
-    ) -> Result<i32, Error> {
-        //we will use whatever the last display name that we find (since you can
-        //change it on PC)
-        sqlx::query(
-            r#"
-            INSERT into "member" ("member_id", "platform_id", "display_name") VALUES (?, ?, ?)
-            ON CONFLICT(member_id) DO UPDATE
-            set display_name = ?
-        "#,
-        )
-        .bind(member_id.to_string())
-        .bind(platform.to_id().to_string())
-        .bind(display_name.to_string())
-        .bind(display_name.to_string())
-        .execute(&mut self.db)
-        .await?;
+        sqlx::query("BEGIN TRANSACTION;")
+            .execute(&mut conn)
+            .timed(TimingCategory::Database)
+            .await?;
 
-        let row = sqlx::query(
-            r#"
-            SELECT id from "member" where member_id=?
-        "#,
-        )
-        .bind(member_id.to_string())
-        .bind(format!("{}", platform.to_id()))
-        .fetch_one(&mut self.db)
-        .await?;
+        match Self::_update_seasons(&mut conn, &definitions).await {
+            Ok(e) => {
+                sqlx::query("COMMIT;")
+                    .execute(&mut conn)
+                    .timed(TimingCategory::Database)
+                    .await?;
+                Ok(e)
+            }
+            Err(e) => {
+                sqlx::query("ROLLBACK;")
+                    .execute(&mut conn)
+                    .timed(TimingCategory::Database)
+                    .await?;
+                Err(e)
+            }
+        }
+    }
 
-        let rowid: i32 = row.try_get("id")?;
+    async fn _update_seasons(
+        conn: &mut SqliteConnection,
+        definitions: &[SeasonDefinitionData],
+    ) -> Result<u64, Error> {
+        for d in definitions {
+            //seasons which have not been scheduled yet dont have any
+            //activities
+            let start_date = match d.start_date {
+                Some(e) => e,
+                None => continue,
+            };
 
-        Ok(rowid)
-    }
+            sqlx::query(
+                r#"
+                INSERT INTO "main"."season"
+                    ("season_number", "season_hash", "name", "start_date", "end_date")
+                VALUES (?, ?, ?, ?, ?)
+                ON CONFLICT(season_number) DO UPDATE SET
+                    season_hash = excluded.season_hash,
+                    name = excluded.name,
+                    start_date = excluded.start_date,
+                    end_date = excluded.end_date
+                "#,
+            )
+            .bind(d.season_number)
+            .bind(d.id.to_string())
+            .bind(d.display_properties.name.trim())
+            .bind(start_date.to_rfc3339())
+            .bind(d.end_date.map(|e| e.to_rfc3339()))
+            .execute(&mut *conn)
+            .timed(TimingCategory::Database)
+            .await?;
+        }
 
-    async fn insert_character_id(
-        &mut self,
-        character_id: &str,
-        class_type: &CharacterClass,
-        member_rowid: i32,
-    ) -> Result<i32, Error> {
-        sqlx::query(
+        let result = sqlx::query(
             r#"
-            INSERT OR IGNORE into "character" ("character_id", "member", "class") VALUES (?, ?, ?)
-        "#,
+            UPDATE "main"."activity" SET season = (
+                SELECT season_number FROM season
+                WHERE
+                    activity.period >= season.start_date AND
+                    (season.end_date IS NULL OR activity.period < season.end_date)
+                ORDER BY season_number DESC LIMIT 1
+            )
+            WHERE season IS NULL
+            "#,
         )
-        .bind(character_id.to_string())
-        .bind(member_rowid)
-        .bind(class_type.to_id().to_string())
-        .execute(&mut self.db)
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
         .await?;
 
-        let row = sqlx::query(
+        //rows_affected includes activities which still dont have a season
+        let untagged: i64 = sqlx::query(
             r#"
-            SELECT id from "character" where character_id=? and member=?
-        "#,
+            SELECT count(*) as count FROM "main"."activity" WHERE season IS NULL
+            "#,
         )
-        .bind(character_id.to_string())
-        .bind(format!("{}", member_rowid))
-        .fetch_one(&mut self.db)
-        .await?;
-
-        let rowid: i32 = row.try_get("id")?;
+        .fetch_one(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?
+        .try_get("count")?;
 
-        Ok(rowid)
+        Ok(result.rows_affected().saturating_sub(untagged as u64))
     }
 
-    async fn get_max_activity_id(
-        &mut self,
-        character_row_id: i32,
-        mode: &Mode,
-    ) -> Result<i64, Error> {
+    /// returns the seasons in the data store, ordered by season number.
+    /// Seasons are loaded from the manifest by update_seasons.
+    pub async fn retrieve_seasons(&self) -> Result<Vec<Season>, Error> {
         let rows = sqlx::query(
             r#"
             SELECT
-                activity_id as max_activity_id
+                season_number, name, start_date, end_date
             FROM
-                "activity"
-            INNER JOIN
-                character_activity_stats ON character_activity_stats.activity = activity.id,
-                character on character_activity_stats.character = character.id,
-                modes ON modes.activity = activity.id and modes.mode in (select mode from modes where mode = ?)
-            WHERE
-                character_activity_stats.character = ?
-            ORDER BY period DESC LIMIT 1
-        "#,
+                "main"."season"
+            ORDER BY
+                season_number ASC
+            "#,
         )
-        .bind(mode.to_id().to_string())
-        .bind(character_row_id.to_string())
-        .fetch_all(&mut self.db)
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
         .await?;
 
-        if rows.is_empty() {
-            return Ok(0);
+        let mut out: Vec<Season> = Vec::with_capacity(rows.len());
+        for r in &rows {
+            out.push(Season::from_row(r)?);
         }
 
-        let row = &rows[0];
-        let activity_id: i64 = row.try_get("max_activity_id")?;
-        Ok(activity_id)
+        Ok(out)
     }
 
-    pub async fn retrieve_activity_by_index(
-        &mut self,
-        activity_index: u32,
-        manifest: &mut ManifestInterface,
-    ) -> Result<CrucibleActivity, Error> {
-        let activity_row = match sqlx::query(
+    /// returns the season with the specified season number, or None if the
+    /// season is not in the data store
+    pub async fn retrieve_season(
+        &self,
+        season_number: u32,
+    ) -> Result<Option<Season>, Error> {
+        let row = sqlx::query(
             r#"
             SELECT
-                activity.id as activity_index_id,
-                activity.activity_id,
-                activity.period,
-                activity.mode as activity_mode,
-                activity.director_activity_hash,
-                activity.reference_id,
-                activity.platform
+                season_number, name, start_date, end_date
             FROM
-                activity
-            INNER JOIN
-                character_activity_stats on character_activity_stats.activity = activity.id,
-                character on character_activity_stats.character = character.id,
-                member on character.member = member.id
+                "main"."season"
             WHERE
-                activity.id = ?
-            ORDER BY
-                period DESC LIMIT 1
+                season_number = ?
             "#,
         )
-        .bind(activity_index.to_string())
-        .fetch_one(&mut self.db)
-        .await
-        {
-            Ok(e) => e,
-            Err(e) => match e {
-                sqlx::Error::RowNotFound => {
-                    return Err(Error::ActivityNotFound);
-                }
-                _ => {
-                    return Err(Error::from(e));
-                }
-            },
-        };
+        .bind(season_number)
+        .fetch_optional(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
 
-        let crucible_activity =
-            self.populate_activity_data(&activity_row, manifest).await?;
-        Ok(crucible_activity)
+        match row {
+            Some(e) => Ok(Some(Season::from_row(&e)?)),
+            None => Ok(None),
+        }
     }
 
-    pub async fn retrieve_last_activity(
-        &mut self,
-        member_id: &str,
-        platform: &Platform,
-        character_selection: &CharacterClassSelection,
-        mode: &Mode,
-        manifest: &mut ManifestInterface,
-    ) -> Result<CrucibleActivity, Error> {
-        let activity_row = if character_selection
-            == &CharacterClassSelection::All
-        {
-            match sqlx::query(
-                r#"
-                SELECT
-                    activity.id as activity_index_id,
-                    activity.activity_id,
-                    activity.period,
-                    activity.mode as activity_mode,
-                    activity.director_activity_hash,
-                    activity.reference_id,
-                    activity.platform
-                FROM
-                    activity
-                INNER JOIN
-                    character_activity_stats on character_activity_stats.activity = activity.id,
-                    character on character_activity_stats.character = character.id,
-                    member on character.member = member.id AND member.member_id = ?
-                WHERE
-                    exists (select 1 from modes where activity = activity.id and mode = ?)
-                ORDER BY
-                    period DESC LIMIT 1
-                "#,
-            )
-            .bind(member_id.to_string())
-            .bind(mode.to_id().to_string())
-            .fetch_one(&mut self.db)
-            .await
-            {
-                Ok(e) => e,
-                Err(e) => match e {
-                    sqlx::Error::RowNotFound => {
-                        return Err(Error::ActivityNotFound);
-                    }
-                    _ => {
-                        return Err(Error::from(e));
-                    }
-                },
+    /// compacts and checks the store. Runs an integrity check, and if the
+    /// store is ok, ANALYZE, VACUUM and PRAGMA optimize. VACUUM rewrites the
+    /// whole store, so can take a while for large stores, and needs free
+    /// disk space of up to twice the size of the store.
+    pub async fn run_maintenance(&self) -> Result<MaintenanceResult, Error> {
+        if self.snapshot_path.is_some() {
+            return Err(Error::SyncInProgress {
+                description:
+                    "Cannot run maintenance on a snapshot of the data store."
+                        .to_string(),
+            });
+        }
+
+        //VACUUM cant run while another connection is writing to the store
+        let _lock = SyncLock::acquire(&self.data_dir)?;
+
+        let size_before = self.storage_size()?;
+
+        let rows = sqlx::query("PRAGMA integrity_check;")
+            .fetch_all(&self.db)
+            .timed(TimingCategory::Database)
+            .await?;
+
+        let mut integrity_errors: Vec<String> = Vec::new();
+        for row in &rows {
+            let r: String = row.try_get(0)?;
+            if r != "ok" {
+                integrity_errors.push(r);
             }
-        } else {
-            let character_id = self
-                .retrieve_character_selection_id(
-                    member_id,
-                    platform,
-                    character_selection,
-                )
+        }
+
+        //vacuuming a corrupt store can lose data, so we leave it as is
+        if !integrity_errors.is_empty() {
+            return Ok(MaintenanceResult {
+                size_before,
+                size_after: size_before,
+                integrity_errors,
+            });
+        }
+
+        for q in &[
+            "ANALYZE;",
+            "VACUUM;",
+            "PRAGMA optimize;",
+            "PRAGMA wal_checkpoint(TRUNCATE);",
+        ] {
+            sqlx::query(q)
+                .execute(&self.db)
+                .timed(TimingCategory::Database)
                 .await?;
+        }
 
-            match sqlx::query(
-                    r#"
-                    SELECT
-                        activity.id as activity_index_id,
-                        activity.activity_id,
-                        activity.period,
-                        activity.mode as activity_mode,
-                        activity.director_activity_hash,
-                        activity.reference_id,
-                        activity.platform
-                    FROM
-                        activity
-                    INNER JOIN
-                        character_activity_stats on character_activity_stats.activity = activity.id,
-                        character on character_activity_stats.character = character.id AND character.character_id = ?
-                    WHERE
-                        exists (select 1 from modes where activity = activity.id and mode = ?)
-                    ORDER BY
-                        period DESC LIMIT 1
-                    "#
-                ).bind(character_id.to_string())
-                .bind(mode.to_id().to_string())
-                .fetch_one(&mut self.db)
-                .await
-                {
-                    Ok(e) => e,
-                    Err(e) => match e {
-                        sqlx::Error::RowNotFound => {
-                            return Err(Error::ActivityNotFound);
-                        }
-                        _ => {
-                            return Err(Error::from(e));
-                        }
-                    },
-                }
-        };
+        let size_after = self.storage_size()?;
 
-        let crucible_activity =
-            self.populate_activity_data(&activity_row, manifest).await?;
-        Ok(crucible_activity)
+        Ok(MaintenanceResult {
+            size_before,
+            size_after,
+            integrity_errors,
+        })
     }
 
-    async fn populate_activity_data(
-        &mut self,
-        activity_row: &sqlx::sqlite::SqliteRow,
-        manifest: &mut ManifestInterface,
-    ) -> Result<CrucibleActivity, Error> {
-        let activity_row_id: i32 = activity_row.try_get("activity_index_id")?;
+    //size of the store on disk, including the write ahead log
+    fn storage_size(&self) -> Result<u64, Error> {
+        let mut size = std::fs::metadata(&self.path)?.len();
 
-        let team_rows = sqlx::query(
+        let wal_path = format!("{}-wal", self.path);
+        if let Ok(e) = std::fs::metadata(&wal_path) {
+            size += e.len();
+        }
+
+        Ok(size)
+    }
+
+    /// re-fetches the PGCRs for a random sample of stored activities from the
+    /// api, and compares their key values (mode, teams and each player's
+    /// kills, deaths, assists, score, standing and weapon kills) with what is
+    /// stored, to find activities which were not ingested correctly. The
+    /// store is not modified.
+    pub async fn verify_activities(
+        &self,
+        sample_size: u32,
+    ) -> Result<VerifyResult, Error> {
+        let rows = sqlx::query(
             r#"
-            SELECT
-                *
-            FROM
-                team_result
-            WHERE
-                activity = ?
+            SELECT id, activity_id FROM activity ORDER BY RANDOM() LIMIT ?
             "#,
         )
-        .bind(activity_row_id)
-        .fetch_all(&mut self.db)
+        .bind(sample_size)
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
         .await?;
 
-        let mut teams: HashMap<i32, Team> = HashMap::new();
+        let api = ApiInterface::new(self.verbose)?;
 
-        let mut team_names = vec![
-            "Alpha".to_string(),
-            "Bravo".to_string(),
-            "Charlie".to_string(),
-            "Delta".to_string(),
-            "Echo".to_string(),
-            "Foxtrot".to_string(),
-        ];
-        team_names.reverse();
+        let mut result = VerifyResult {
+            activities_checked: 0,
+            mismatches: Vec::new(),
+            failed: Vec::new(),
+        };
 
-        for t in team_rows {
-            let standing: i32 = t.try_get("standing")?;
-            let standing = Standing::from_value(standing as u32);
+        for (i, row) in rows.iter().enumerate() {
+            let activity_index: i64 = row.try_get("id")?;
+            let activity_id: i64 = row.try_get("activity_id")?;
+
+            if self.verbose {
+                eprintln!(
+                    "Verifying activity {} ({} of {})",
+                    activity_id,
+                    i + 1,
+                    rows.len()
+                );
+            }
 
-            let id: i32 = t.try_get("team_id")?;
-            let score: u32 = t.try_get("score")?;
+            let data = match api
+                .retrieve_post_game_carnage_report(activity_id)
+                .await
+            {
+                Ok(Some(e)) => e,
+                Ok(None) => {
+                    result.failed.push((activity_id, Error::ActivityNotFound));
+                    continue;
+                }
+                Err(e) => {
+                    result.failed.push((activity_id, e));
+                    continue;
+                }
+            };
 
-            let player_performances: Vec<CruciblePlayerPerformance> =
-                Vec::new();
+            let mismatches = self
+                .compare_activity(activity_index, activity_id, &data)
+                .await?;
 
-            let display_name =
-                team_names.pop().unwrap_or_else(|| "".to_string());
+            result.mismatches.extend(mismatches);
+            result.activities_checked += 1;
+        }
 
-            let team = Team {
-                standing,
-                id,
-                score,
-                player_performances,
-                display_name,
-            };
+        Ok(result)
+    }
 
-            teams.insert(id, team);
-        }
+    async fn compare_activity(
+        &self,
+        activity_index: i64,
+        activity_id: i64,
+        data: &DestinyPostGameCarnageReportData,
+    ) -> Result<Vec<ActivityMismatch>, Error> {
+        let mut out: Vec<ActivityMismatch> = Vec::new();
 
-        //Rumble wont have any teams, so we put all items in one team
-        //this also covered any bugs where no teams are specified
-        let mut no_teams = false;
-        if teams.is_empty() {
-            let display_name =
-                team_names.pop().unwrap_or_else(|| "".to_string());
+        let row = sqlx::query(
+            r#"
+            SELECT mode, director_activity_hash FROM activity WHERE id = ?
+            "#,
+        )
+        .bind(activity_index)
+        .fetch_one(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
 
-            let team = Team {
-                standing: Standing::Unknown,
-                id: NO_TEAMS_INDEX,
-                score: 0,
-                player_performances: Vec::new(),
-                display_name,
-            };
+        let mode: u32 = row.try_get("mode")?;
+        let director_activity_hash: i64 =
+            row.try_get("director_activity_hash")?;
+
+        let mut compare = |character_id: Option<&str>,
+                           field: &str,
+                           stored: String,
+                           remote: String| {
+            if stored != remote {
+                out.push(ActivityMismatch {
+                    activity_id,
+                    character_id: character_id.map(|e| e.to_string()),
+                    field: field.to_string(),
+                    stored,
+                    remote,
+                });
+            }
+        };
 
-            teams.insert(NO_TEAMS_INDEX, team);
-            no_teams = true;
+        compare(
+            None,
+            "mode",
+            mode.to_string(),
+            data.activity_details.mode.to_id().to_string(),
+        );
+        compare(
+            None,
+            "director_activity_hash",
+            director_activity_hash.to_string(),
+            data.activity_details.director_activity_hash.to_string(),
+        );
+
+        let team_rows = sqlx::query(
+            r#"
+            SELECT team_id, score, standing FROM team_result WHERE activity = ?
+            "#,
+        )
+        .bind(activity_index)
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut stored_teams: HashMap<i32, (i32, i32)> = HashMap::new();
+        for r in &team_rows {
+            stored_teams.insert(
+                r.try_get("team_id")?,
+                (r.try_get("score")?, r.try_get("standing")?),
+            );
         }
 
-        //TODO: need to account for character and member, need to join both
-        let character_rows = sqlx::query(
+        compare(
+            None,
+            "teams",
+            stored_teams.len().to_string(),
+            data.teams.len().to_string(),
+        );
+
+        for team in &data.teams {
+            if let Some((score, standing)) = stored_teams.get(&team.team) {
+                let field = format!("team {} score", team.team);
+                compare(
+                    None,
+                    &field,
+                    score.to_string(),
+                    (team.score as i32).to_string(),
+                );
+
+                let field = format!("team {} standing", team.team);
+                compare(
+                    None,
+                    &field,
+                    standing.to_string(),
+                    (team.standing as i32).to_string(),
+                );
+            }
+        }
+
+        let player_rows = sqlx::query(
             r#"
             SELECT
-                *,
-                character_activity_stats.id as character_activity_stats_index
+                character.character_id,
+                character_activity_stats.kills,
+                character_activity_stats.deaths,
+                character_activity_stats.assists,
+                character_activity_stats.score,
+                character_activity_stats.standing,
+                character_activity_stats.team,
+                (
+                    SELECT
+                        COALESCE(SUM(kills), 0)
+                    FROM
+                        weapon_result
+                    WHERE
+                        character_activity_stats = character_activity_stats.id
+                ) as weapon_kills
             FROM
                 character_activity_stats
             INNER JOIN
-                character on character_activity_stats.character = character.id,
-                member on character.member = member.id
+                character on character_activity_stats.character = character.id
             WHERE
-                activity = ?
+                character_activity_stats.activity = ?
             "#,
         )
-        .bind(activity_row_id)
-        .fetch_all(&mut self.db)
+        .bind(activity_index)
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
         .await?;
 
-        for c_row in character_rows {
-            let stats = self.parse_crucible_stats(manifest, &c_row).await?;
+        let mut stored_players: HashMap<String, [i64; 7]> = HashMap::new();
+        for r in &player_rows {
+            stored_players.insert(
+                r.try_get("character_id")?,
+                [
+                    r.try_get("kills")?,
+                    r.try_get("deaths")?,
+                    r.try_get("assists")?,
+                    r.try_get("score")?,
+                    r.try_get("standing")?,
+                    r.try_get("team")?,
+                    r.try_get("weapon_kills")?,
+                ],
+            );
+        }
 
-            let player = self.parse_player(&c_row).await?;
+        //only MAX_PGCR_ENTRIES players are stored. Synced characters are
+        //always kept, so any stored player is treated as one when working
+        //out which entries the store would have kept
+        let entries = capped_pgcr_entries(&data.entries, |e| {
+            stored_players.contains_key(e)
+        });
+
+        compare(
+            None,
+            "players",
+            stored_players.len().to_string(),
+            entries.len().to_string(),
+        );
 
-            let cpp = CruciblePlayerPerformance { stats, player };
+        let fields = [
+            "kills",
+            "deaths",
+            "assists",
+            "score",
+            "standing",
+            "team",
+            "weapon_kills",
+        ];
 
-            let index = if no_teams {
-                NO_TEAMS_INDEX
-            } else {
-                cpp.stats.team
+        for entry in entries {
+            let stored = match stored_players.get(&entry.character_id) {
+                Some(e) => e,
+                None => {
+                    compare(
+                        Some(&entry.character_id),
+                        "player",
+                        "missing".to_string(),
+                        "present".to_string(),
+                    );
+                    continue;
+                }
             };
 
-            match teams.get_mut(&index) {
-                Some(e) => e.player_performances.push(cpp),
-                None => eprintln!("Invalid Team ID ({}) : Skipping", &index),
+            let weapon_kills: u32 = match &entry.extended.weapons {
+                Some(weapons) => weapons
+                    .iter()
+                    .map(|w| w.values.unique_weapon_kills as u32)
+                    .sum(),
+                None => 0,
+            };
+
+            let remote: [i64; 7] = [
+                entry.values.kills as i64,
+                entry.values.deaths as i64,
+                entry.values.assists as i64,
+                entry.values.score as i64,
+                entry.values.standing as i64,
+                entry.values.team as i64,
+                weapon_kills as i64,
+            ];
+
+            for (i, field) in fields.iter().enumerate() {
+                compare(
+                    Some(&entry.character_id),
+                    field,
+                    stored[i].to_string(),
+                    remote[i].to_string(),
+                );
             }
         }
 
-        let details = self.parse_activity(manifest, &activity_row).await?;
+        Ok(out)
+    }
 
-        Ok(CrucibleActivity { details, teams })
+    /// returns the ids of the stored activities which started within the
+    /// time period
+    pub async fn retrieve_activity_ids_in_period(
+        &self,
+        time_period: &DateTimePeriod,
+    ) -> Result<Vec<i64>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                activity_id
+            FROM
+                activity
+            WHERE
+                period > ? AND
+                period < ?
+            ORDER BY
+                period ASC
+            "#,
+        )
+        .bind(time_period.get_start().to_rfc3339())
+        .bind(time_period.get_end().to_rfc3339())
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut out: Vec<i64> = Vec::with_capacity(rows.len());
+        for r in &rows {
+            out.push(r.try_get("activity_id")?);
+        }
+
+        Ok(out)
     }
 
-    //returns character_id for specified character class selection
-    //returns member_id if selection is ALL
-    async fn retrieve_character_selection_id(
+    /// deletes the specified activities (along with their teams, player
+    /// stats, weapons and medals) and downloads and stores them again from
+    /// the api. Used to repair activities stored with bad data. Each activity
+    /// is downloaded before it is deleted, so activities which can't be
+    /// retrieved are left as they are. Activities which are not in the store
+    /// are skipped. Combat ratings are kept, since they can't be retrieved
+    /// again.
+    pub async fn reingest_activities(
+        &self,
+        activity_ids: &[i64],
+    ) -> Result<ReingestResult, Error> {
+        if self.snapshot_path.is_some() {
+            return Err(Error::SyncInProgress {
+                description:
+                    "Cannot re-ingest activities into a snapshot of the data store."
+                        .to_string(),
+            });
+        }
+
+        let _lock = SyncLock::acquire(&self.data_dir)?;
+
+        let api = ApiInterface::new(self.verbose)?;
+
+        let mut conn = self.db.acquire().await?;
+
+        let mut result = ReingestResult {
+            reingested: Vec::new(),
+            not_found: Vec::new(),
+            failed: Vec::new(),
+        };
+
+        for (i, activity_id) in activity_ids.iter().enumerate() {
+            if Self::get_activity_row_id(&mut conn, *activity_id)
+                .await
+                .is_err()
+            {
+                result.not_found.push(*activity_id);
+                continue;
+            }
+
+            if self.verbose {
+                eprintln!(
+                    "Re-ingesting activity {} ({} of {})",
+                    activity_id,
+                    i + 1,
+                    activity_ids.len()
+                );
+            }
+
+            //activities stored with their original json are re-parsed from
+            //it, rather than being downloaded again
+            let retrieved = match self
+                .retrieve_stored_raw_pgcr(*activity_id)
+                .await
+            {
+                Ok(Some(raw)) => serde_json::from_value(raw.clone())
+                    .map(|data| Some((data, Some(raw))))
+                    .map_err(Error::from),
+                Ok(None) => {
+                    self.retrieve_post_game_carnage_report(&api, *activity_id)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+
+            let (data, raw) = match retrieved {
+                Ok(Some(e)) => e,
+                Ok(None) => {
+                    result.failed.push((*activity_id, Error::ActivityNotFound));
+                    continue;
+                }
+                Err(e) => {
+                    result.failed.push((*activity_id, e));
+                    continue;
+                }
+            };
+
+            sqlx::query("BEGIN TRANSACTION;")
+                .execute(&mut conn)
+                .timed(TimingCategory::Database)
+                .await?;
+
+            match Self::_reingest_activity(&mut conn, &data, raw.as_ref()).await
+            {
+                Ok(_e) => {
+                    sqlx::query("COMMIT;")
+                        .execute(&mut conn)
+                        .timed(TimingCategory::Database)
+                        .await?;
+                    result.reingested.push(*activity_id);
+                }
+                Err(e) => {
+                    sqlx::query("ROLLBACK;")
+                        .execute(&mut conn)
+                        .timed(TimingCategory::Database)
+                        .await?;
+                    result.failed.push((*activity_id, e));
+                }
+            }
+        }
+
+        sqlx::query("PRAGMA OPTIMIZE;")
+            .execute(&mut conn)
+            .timed(TimingCategory::Database)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// re-parses all stored activities into the current schema, so data
+    /// added in newer versions of the store (new columns and tables) is
+    /// backfilled for activities synced before it existed. Activities are
+    /// re-parsed from their stored PGCR json (see set_store_raw_pgcr).
+    /// Activities without stored json are skipped, unless refetch is set, in
+    /// which case they are downloaded from the api again.
+    pub async fn reprocess_activities(
+        &self,
+        refetch: bool,
+    ) -> Result<ReprocessResult, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                activity.activity_id,
+                raw_pgcr.activity IS NOT NULL AS has_raw
+            FROM
+                activity
+            LEFT JOIN raw_pgcr ON raw_pgcr.activity = activity.id
+            ORDER BY
+                activity.period ASC
+            "#,
+        )
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut activity_ids: Vec<i64> = Vec::with_capacity(rows.len());
+        let mut skipped: Vec<i64> = Vec::new();
+        for r in &rows {
+            let activity_id: i64 = r.try_get("activity_id")?;
+            let has_raw: bool = r.try_get("has_raw")?;
+
+            if has_raw || refetch {
+                activity_ids.push(activity_id);
+            } else {
+                skipped.push(activity_id);
+            }
+        }
+
+        let result = self.reingest_activities(&activity_ids).await?;
+
+        Ok(ReprocessResult {
+            reprocessed: result.reingested,
+            skipped,
+            failed: result.failed,
+        })
+    }
+
+    async fn _reingest_activity(
+        conn: &mut SqliteConnection,
+        data: &DestinyPostGameCarnageReportData,
+        raw: Option<&serde_json::Value>,
+    ) -> Result<(), Error> {
+        //teams, modes and player stats (and their weapons and medals) are
+        //removed by the foreign key cascades
+        sqlx::query(
+            r#"
+            DELETE FROM "main"."activity" WHERE activity_id = ?
+            "#,
+        )
+        .bind(data.activity_details.instance_id)
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        Self::_insert_activity(conn, data, None).await?;
+
+        if let Some(r) = raw {
+            Self::_insert_raw_pgcr(conn, data.activity_details.instance_id, r)
+                .await?;
+        }
+
+        //we have the activity now, so it doesnt need to be synced for anyone
+        sqlx::query(
+            r#"
+            DELETE FROM "main"."activity_queue" WHERE activity_id = ?
+            "#,
+        )
+        .bind(data.activity_details.instance_id)
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn _insert_synced_activity(
+        conn: &mut SqliteConnection,
+        data: &DestinyPostGameCarnageReportData,
+        character_row_id: i32,
+        raw: Option<&serde_json::Value>,
+    ) -> Result<(), Error> {
+        Self::_insert_activity(conn, data, Some(character_row_id)).await?;
+
+        if let Some(r) = raw {
+            Self::_insert_raw_pgcr(conn, data.activity_details.instance_id, r)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn _insert_raw_pgcr(
+        conn: &mut SqliteConnection,
+        instance_id: i64,
+        raw: &serde_json::Value,
+    ) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO "main"."raw_pgcr"
+                ("activity", "data", "stored_at")
+            SELECT id, ?, ? FROM "main"."activity" WHERE activity_id = ?
+            "#,
+        )
+        .bind(raw.to_string())
+        .bind(Utc::now().to_rfc3339())
+        .bind(instance_id)
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        Ok(())
+    }
+
+    /// retrieves the original PGCR json stored for the activity. Returns
+    /// None if the json was not stored when the activity was synced (see
+    /// set_store_raw_pgcr).
+    pub async fn retrieve_stored_raw_pgcr(
+        &self,
+        activity_id: i64,
+    ) -> Result<Option<serde_json::Value>, Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT raw_pgcr.data FROM "main"."raw_pgcr"
+            INNER JOIN activity ON raw_pgcr.activity = activity.id
+            WHERE activity.activity_id = ?
+            "#,
+        )
+        .bind(activity_id)
+        .fetch_optional(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        match row {
+            Some(e) => {
+                let data: String = e.try_get("data")?;
+                Ok(Some(serde_json::from_str(&data)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// retrieves and stores activity details for ids in activity queue
+    ///
+    /// the sync can be stopped by cancelling the token, in which case
+    /// Error::Cancelled is returned. Activities synced up to that point are
+    /// kept, and any remaining will be synced the next time sync is called.
+    pub async fn sync(
+        &self,
+        member_id: &str,
+        platform: &Platform,
+        cancel: &CancellationToken,
+    ) -> Result<SyncResult, Error> {
+        self._sync(member_id, platform, None, cancel).await
+    }
+
+    /// syncs only the specified characters of the member, rather than all of
+    /// them. Useful for accounts with large histories where only one
+    /// character is played. Returns Error::CharacterDoesNotExist if any of
+    /// the ids are not current characters of the member (use sync_character
+    /// for deleted characters).
+    pub async fn sync_characters(
+        &self,
+        member_id: &str,
+        platform: &Platform,
+        character_ids: &[String],
+        cancel: &CancellationToken,
+    ) -> Result<SyncResult, Error> {
+        self._sync(member_id, platform, Some(character_ids), cancel)
+            .await
+    }
+
+    //syncs all characters if character_ids is None
+    async fn _sync(
+        &self,
+        member_id: &str,
+        platform: &Platform,
+        character_ids: Option<&[String]>,
+        cancel: &CancellationToken,
+    ) -> Result<SyncResult, Error> {
+        if self.snapshot_path.is_some() {
+            return Err(Error::SyncInProgress {
+                description: "Cannot sync a snapshot of the data store."
+                    .to_string(),
+            });
+        }
+
+        //held until the sync completes, and lets other processes know that
+        //the store is being written to
+        let _lock = SyncLock::acquire(&self.data_dir)?;
+
+        let api = ApiInterface::new(self.verbose)?;
+
+        let started_at = Utc::now();
+        let result = self
+            ._sync_member(&api, member_id, platform, character_ids, cancel)
+            .await;
+        self.record_sync_history(
+            member_id,
+            &started_at,
+            &result,
+            &api.get_key_usage(),
+        )
+        .await;
+
+        result
+    }
+
+    async fn _sync_member(
+        &self,
+        api: &ApiInterface,
+        member_id: &str,
+        platform: &Platform,
+        character_ids: Option<&[String]>,
+        cancel: &CancellationToken,
+    ) -> Result<SyncResult, Error> {
+        self.emit(SyncEvent::sync_started(member_id, platform));
+
+        //TODO: call API to get display name
+        //https://www.bungie.net/Platform/Destiny2/1/Profile/4611686018429783292/?components=100,200
+        let player_info = api.get_player_info(member_id, platform).await?;
+
+        let characters = player_info.characters;
+
+        //check ids before we start, so we dont partially sync
+        if let Some(ids) = character_ids {
+            if !ids
+                .iter()
+                .all(|id| characters.characters.iter().any(|c| &c.id == id))
+            {
+                return Err(Error::CharacterDoesNotExist);
+            }
+        }
+
+        let display_name = player_info.user_info.display_name;
+
+        let member_row_id = Self::insert_member_id(
+            &mut *self.db.acquire().await?,
+            member_id,
+            platform,
+            &display_name,
+        )
+        .await?;
+
+        self.save_character_metadata(member_id, &characters.characters)
+            .await?;
+
+        let mut total_synced = 0;
+        let mut total_in_queue = 0;
+        let mut character_results: Vec<CharacterSyncResult> = Vec::new();
+
+        //max activities is for the whole member, not each character
+        let mut limit = self.max_activities;
+
+        eprintln!();
+
+        eprintln!(
+            "{}",
+            "Checking for new activities (public, private and gambit)"
+                .to_uppercase()
+        );
+        eprintln!("This may take a few minutes depending on the number of activities.");
+        for c in characters
+            .characters
+            .iter()
+            .filter(|c| match character_ids {
+                Some(ids) => ids.contains(&c.id),
+                None => true,
+            })
+        {
+            let r = self
+                ._sync_character(
+                    member_id,
+                    platform,
+                    member_row_id,
+                    &c.id,
+                    &c.class_type,
+                    api,
+                    limit,
+                    cancel,
+                )
+                .await?;
+
+            limit = limit.map(|l| l.saturating_sub(r.total_synced));
+            total_synced += r.total_synced;
+            total_in_queue += r.total_available;
+            character_results.push(r);
+        }
+
+        //progress is matched to the last competitive activity, so we only
+        //record it once all activities have been synced. Not fatal, since it
+        //is only used to annotate competitive history.
+        if total_in_queue == 0 {
+            if let Err(e) = self
+                .record_competitive_progress(member_id, platform, api)
+                .await
+            {
+                print_verbose(
+                    &format!("Could not record competitive progress : {}", e),
+                    self.verbose,
+                );
+            }
+        }
+
+        self.emit(SyncEvent::sync_finished(
+            total_synced,
+            total_in_queue,
+            &self.path,
+        ));
+
+        Ok(SyncResult {
+            total_synced,
+            total_available: total_in_queue,
+            characters: character_results,
+        })
+    }
+
+    /// syncs activities for a single character of the member. Used to sync
+    /// characters which have been deleted, since they are not returned with
+    /// the member's profile (and so are not synced by sync). The class can't
+    /// be retrieved for deleted characters, so must be specified.
+    pub async fn sync_character(
+        &self,
+        member_id: &str,
+        platform: &Platform,
+        character_id: &str,
+        class_type: &CharacterClass,
+        cancel: &CancellationToken,
+    ) -> Result<SyncResult, Error> {
+        if self.snapshot_path.is_some() {
+            return Err(Error::SyncInProgress {
+                description: "Cannot sync a snapshot of the data store."
+                    .to_string(),
+            });
+        }
+
+        let _lock = SyncLock::acquire(&self.data_dir)?;
+
+        let api = ApiInterface::new(self.verbose)?;
+
+        let started_at = Utc::now();
+        let result = self
+            ._sync_deleted_character(
+                &api,
+                member_id,
+                platform,
+                character_id,
+                class_type,
+                cancel,
+            )
+            .await;
+        self.record_sync_history(
+            member_id,
+            &started_at,
+            &result,
+            &api.get_key_usage(),
+        )
+        .await;
+
+        result
+    }
+
+    async fn _sync_deleted_character(
+        &self,
+        api: &ApiInterface,
+        member_id: &str,
+        platform: &Platform,
+        character_id: &str,
+        class_type: &CharacterClass,
+        cancel: &CancellationToken,
+    ) -> Result<SyncResult, Error> {
+        self.emit(SyncEvent::sync_started(member_id, platform));
+
+        let player_info = api.get_player_info(member_id, platform).await?;
+        let member_row_id = Self::insert_member_id(
+            &mut *self.db.acquire().await?,
+            member_id,
+            platform,
+            &player_info.user_info.display_name,
+        )
+        .await?;
+
+        eprintln!();
+        eprintln!(
+            "{}",
+            "Checking for new activities (public, private and gambit)"
+                .to_uppercase()
+        );
+
+        let r = self
+            ._sync_character(
+                member_id,
+                platform,
+                member_row_id,
+                character_id,
+                class_type,
+                api,
+                self.max_activities,
+                cancel,
+            )
+            .await?;
+
+        self.emit(SyncEvent::sync_finished(
+            r.total_synced,
+            r.total_available,
+            &self.path,
+        ));
+
+        Ok(SyncResult {
+            total_synced: r.total_synced,
+            total_available: r.total_available,
+            characters: vec![r],
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn _sync_character(
+        &self,
+        member_id: &str,
+        platform: &Platform,
+        member_row_id: i32,
+        character_id: &str,
+        class_type: &CharacterClass,
+        api: &ApiInterface,
+        limit: Option<u32>,
+        cancel: &CancellationToken,
+    ) -> Result<CharacterSyncResult, Error> {
+        cancel.check()?;
+
+        let character_row_id = Self::insert_character_id(
+            &mut *self.db.acquire().await?,
+            character_id,
+            class_type,
+            member_row_id,
+        )
+        .await?;
+        eprintln!("{}", format!("{}", class_type).to_uppercase());
+
+        //these calls could be a little more general purpose by taking api ids and not db ids.
+        //however, passing the db ids, lets us optimize a lot of the sql, and avoid
+        //some extra calls to the DB
+
+        let a = self
+            .sync_activities(character_row_id, api, limit, cancel)
+            .await?;
+
+        cancel.check()?;
+        let _b = self
+            .update_activity_queue(
+                character_row_id,
+                member_id,
+                character_id,
+                platform,
+                api,
+                cancel,
+            )
+            .await?;
+
+        let limit = limit.map(|l| l.saturating_sub(a.total_synced));
+        let c = self
+            .sync_activities(character_row_id, api, limit, cancel)
+            .await?;
+
+        //the second sync covers the whole queue, including anything left
+        //from the first
+        let total_synced = a.total_synced + c.total_synced;
+        let total_in_queue = c.total_available - c.total_synced;
+
+        Ok(CharacterSyncResult {
+            character_id: character_id.to_string(),
+            class_type: *class_type,
+            total_synced,
+            total_available: total_in_queue,
+        })
+    }
+
+    /// retrieves the member's most recent activities within the time period
+    /// which are not in the store yet, and adds them to the snapshot, so
+    /// they can be displayed when another process is responsible for
+    /// syncing. Only the most recent activities for each character are
+    /// checked, and the sync queue and cursors are not changed. The store
+    /// must have been opened with init_overlay_with_path, so the activities
+    /// are not added to the actual store. Returns the number of activities
+    /// added.
+    pub async fn add_unsynced_activities(
+        &self,
+        member_id: &str,
+        platform: &Platform,
+        character_selection: &CharacterClassSelection,
+        time_period: &DateTimePeriod,
+    ) -> Result<u32, Error> {
+        if self.snapshot_path.is_none() {
+            return Err(Error::InvalidParameters);
+        }
+
+        let api = ApiInterface::new(self.verbose)?;
+        let player_info = api.get_player_info(member_id, platform).await?;
+
+        let mut conn = self.db.acquire().await?;
+        let member_row_id = Self::insert_member_id(
+            &mut conn,
+            member_id,
+            platform,
+            &player_info.user_info.display_name,
+        )
+        .await?;
+
+        let mut total = 0;
+        for c in player_info
+            .characters
+            .get_by_selection_ref(character_selection)
+        {
+            let character_row_id = Self::insert_character_id(
+                &mut conn,
+                &c.id,
+                &c.class_type,
+                member_row_id,
+            )
+            .await?;
+
+            //activities are returned most recent first
+            let mut ids: Vec<i64> = Vec::new();
+            for mode in &[
+                Mode::AllPvP,
+                Mode::PrivateMatchesAll,
+                Mode::AllPvECompetitive,
+            ] {
+                let activities = api
+                    .retrieve_activities(
+                        member_id,
+                        &c.id,
+                        platform,
+                        mode,
+                        UNSYNCED_ACTIVITY_COUNT,
+                        0,
+                    )
+                    .await?
+                    .unwrap_or_default();
+
+                for a in activities {
+                    if a.period < time_period.get_start()
+                        || a.period > time_period.get_end()
+                    {
+                        continue;
+                    }
+
+                    let id = a.details.instance_id;
+
+                    //anything older has already been synced
+                    if Self::get_activity_row_id(&mut conn, id).await.is_ok() {
+                        break;
+                    }
+
+                    if !ids.contains(&id) {
+                        ids.push(id);
+                    }
+                }
+            }
+
+            for id in ids {
+                print_verbose(
+                    &format!("Retrieving unsynced activity {}", id),
+                    self.verbose,
+                );
+
+                if let Some(data) =
+                    api.retrieve_post_game_carnage_report(id).await?
+                {
+                    self.insert_activity(&data, character_row_id, None).await?;
+                    total += 1;
+                }
+            }
+        }
+
+        Ok(total)
+    }
+
+    async fn record_competitive_progress(
+        &self,
+        member_id: &str,
+        platform: &Platform,
+        api: &ApiInterface,
+    ) -> Result<(), Error> {
+        let progressions = api
+            .retrieve_character_progression(
+                member_id,
+                platform,
+                GLORY_PROGRESSION_HASH,
+            )
+            .await?;
+
+        //progress is account wide, so every character has the same value
+        let progress = match progressions.values().next() {
+            Some(e) => e,
+            None => return Ok(()),
+        };
+
+        let row = sqlx::query(
+            r#"
+            SELECT
+                activity.activity_id
+            FROM
+                character_activity_stats
+            INNER JOIN
+                activity ON character_activity_stats.activity = activity.id,
+                character ON character_activity_stats.character = character.id,
+                member ON member.id = character.member
+            WHERE
+                member.member_id = ? AND
+                exists (select 1 from modes where activity = activity.id and mode IN (?, ?))
+            ORDER BY
+                activity.period DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(member_id)
+        .bind(COMPETITIVE_MODES[0].to_id().to_string())
+        .bind(COMPETITIVE_MODES[1].to_id().to_string())
+        .fetch_optional(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        //no competitive activities to attach the progress to
+        let activity_id: i64 = match row {
+            Some(e) => e.try_get_unchecked("activity_id")?,
+            None => return Ok(()),
+        };
+
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO "main"."competitive_progress"("member_id", "progression_hash", "activity_id", "current_progress", "step_index", "recorded_at")
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(member_id)
+        .bind(GLORY_PROGRESSION_HASH as i64)
+        .bind(activity_id)
+        .bind(progress.current_progress)
+        .bind(progress.step_index)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        Ok(())
+    }
+
+    //not fatal, since the sync itself has already completed (or failed)
+    async fn record_sync_history(
+        &self,
+        member_id: &str,
+        started_at: &DateTime<Utc>,
+        result: &Result<SyncResult, Error>,
+        usage: &[ApiKeyUsage],
+    ) {
+        if let Err(e) = self
+            ._record_sync_history(member_id, started_at, result, usage)
+            .await
+        {
+            print_verbose(
+                &format!("Could not record sync history : {}", e),
+                self.verbose,
+            );
+        }
+    }
+
+    async fn _record_sync_history(
+        &self,
+        member_id: &str,
+        started_at: &DateTime<Utc>,
+        result: &Result<SyncResult, Error>,
+        usage: &[ApiKeyUsage],
+    ) -> Result<(), Error> {
+        let mut conn = self.db.acquire().await?;
+
+        sqlx::query("BEGIN TRANSACTION;")
+            .execute(&mut conn)
+            .timed(TimingCategory::Database)
+            .await?;
+
+        match Self::_insert_sync_history(
+            &mut conn, member_id, started_at, result, usage,
+        )
+        .await
+        {
+            Ok(_e) => {
+                sqlx::query("COMMIT;")
+                    .execute(&mut conn)
+                    .timed(TimingCategory::Database)
+                    .await?;
+                Ok(())
+            }
+            Err(e) => {
+                sqlx::query("ROLLBACK;")
+                    .execute(&mut conn)
+                    .timed(TimingCategory::Database)
+                    .await?;
+                Err(e)
+            }
+        }
+    }
+
+    //adds the run to sync_history, along with the api key usage for the run
+    //to sync_log
+    async fn _insert_sync_history(
+        conn: &mut SqliteConnection,
+        member_id: &str,
+        started_at: &DateTime<Utc>,
+        result: &Result<SyncResult, Error>,
+        usage: &[ApiKeyUsage],
+    ) -> Result<(), Error> {
+        let finished_at = Utc::now();
+        let duration_ms = (finished_at - *started_at).num_milliseconds();
+
+        let (added, remaining, error) = match result {
+            Ok(r) => (r.total_synced, r.total_available, None),
+            Err(e) => (0, 0, Some(e.to_string())),
+        };
+
+        let sync_history_id = sqlx::query(
+            r#"
+            INSERT INTO "main"."sync_history"("member_id", "started_at", "finished_at",
+                "duration_ms", "activities_added", "activities_remaining", "error")
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(member_id)
+        .bind(started_at.to_rfc3339())
+        .bind(finished_at.to_rfc3339())
+        .bind(duration_ms)
+        .bind(added)
+        .bind(remaining)
+        .bind(error)
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?
+        .last_insert_rowid();
+
+        for u in usage {
+            sqlx::query(
+                r#"
+                INSERT INTO "main"."sync_log"("sync_history", "api_key", "requests", "throttled")
+                VALUES (?, ?, ?, ?)
+                "#,
+            )
+            .bind(sync_history_id)
+            .bind(&u.key_hint)
+            .bind(u.requests)
+            .bind(u.throttled)
+            .execute(&mut *conn)
+            .timed(TimingCategory::Database)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// returns the most recent sync run for the member, including runs which
+    /// failed or were cancelled (check SyncHistoryEntry::error), or None if
+    /// the member has never been synced since sync history was recorded.
+    pub async fn last_sync(
+        &self,
+        member_id: &str,
+    ) -> Result<Option<SyncHistoryEntry>, Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT started_at, finished_at, duration_ms, activities_added,
+                activities_remaining, error
+            FROM "main"."sync_history"
+            WHERE member_id = ?
+            ORDER BY finished_at DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(member_id)
+        .fetch_optional(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let row = match row {
+            Some(e) => e,
+            None => return Ok(None),
+        };
+
+        let started_at: String = row.try_get("started_at")?;
+        let finished_at: String = row.try_get("finished_at")?;
+        let duration_ms: i64 = row.try_get("duration_ms")?;
+        let activities_added: i64 = row.try_get("activities_added")?;
+        let activities_remaining: i64 = row.try_get("activities_remaining")?;
+
+        Ok(Some(SyncHistoryEntry {
+            member_id: member_id.to_string(),
+            started_at: DateTime::parse_from_rfc3339(&started_at)?
+                .with_timezone(&Utc),
+            finished_at: DateTime::parse_from_rfc3339(&finished_at)?
+                .with_timezone(&Utc),
+            duration_ms: duration_ms.max(0) as u64,
+            activities_added: activities_added as u32,
+            activities_remaining: activities_remaining as u32,
+            error: row.try_get("error")?,
+        }))
+    }
+
+    /// download results from ids in queue, and return number of items synced.
+    /// At most limit activities are retrieved, if set.
+    async fn sync_activities(
+        &self,
+        character_row_id: i32,
+        api: &ApiInterface,
+        limit: Option<u32>,
+        cancel: &CancellationToken,
+    ) -> Result<SyncResult, Error> {
+        let mut ids: Vec<i64> = Vec::new();
+
+        //This is to scope rows, so the mutable borrow of self goes out of scope
+        {
+            let _timer = timing::start(TimingCategory::Database);
+            let mut rows = sqlx::query(
+                r#"
+                    SELECT "activity_id" from "activity_queue" where character = ?
+                    ORDER BY "activity_id" ASC
+                "#,
+            )
+            .bind(format!("{}", character_row_id))
+            .fetch(&self.db);
+
+            while let Some(row) = rows.try_next().await? {
+                let activity_id: i64 = row.try_get("activity_id")?;
+                ids.push(activity_id);
+            }
+        };
+
+        if ids.is_empty() {
+            return Ok(SyncResult {
+                total_available: 0,
+                total_synced: 0,
+                characters: Vec::new(),
+            });
+        }
+
+        let total_available = ids.len() as u32;
+        let mut total_synced = 0;
+
+        //the rest are left in the queue for the next sync
+        if let Some(l) = limit {
+            if ids.len() > l as usize {
+                ids.truncate(l as usize);
+                eprintln!(
+                    "Limited to {} of {} activities in queue",
+                    ids.len(),
+                    total_available
+                );
+            }
+        }
+
+        if ids.is_empty() {
+            return Ok(SyncResult {
+                total_available,
+                total_synced,
+                characters: Vec::new(),
+            });
+        }
+
+        let s = if ids.len() == 1 { "y" } else { "ies" };
+        eprintln!("Retrieving details for {} activit{}", ids.len(), s);
+
+        eprintln!("Each dot represents {} activities", self.pgcr_concurrency);
+        eprint!("[");
+        for id_chunks in ids.chunks(self.pgcr_concurrency) {
+            //each activity is inserted in its own transaction, so stopping
+            //between chunks leaves the store consistent
+            if cancel.is_cancelled() {
+                eprintln!("]");
+                return Err(Error::Cancelled);
+            }
+
+            eprint!(".");
+
+            let results = self
+                .retrieve_post_game_carnage_reports(api, id_chunks, cancel)
+                .await;
+
+            //loop through. if we get results. grab those, otherwise, we ignore
+            //any errors, as that will keep the IDs in the queue to try next time
+            //TODO: this is a mess. can we simpify and not nest so deeply?
+            for r in results {
+                match r {
+                    Ok(e) => {
+                        match e {
+                            Some((e, raw)) => match self
+                                .insert_activity(
+                                    &e,
+                                    character_row_id,
+                                    raw.as_ref(),
+                                )
+                                .await
+                            {
+                                Ok(_e) => {
+                                    total_synced += 1;
+                                    self.emit(SyncEvent::activity_synced(
+                                        e.activity_details.instance_id,
+                                        &e.activity_details.mode,
+                                        &e.period,
+                                    ));
+                                }
+                                Err(err) => {
+                                    eprintln!();
+                                    eprintln!(
+                                        "Error inserting data into character activity stats table. Skipping. : {}",
+                                        err,
+                                    );
+                                    self.emit(SyncEvent::error(&format!(
+                                        "Error inserting activity {} : {}",
+                                        e.activity_details.instance_id, err
+                                    )));
+                                }
+                            },
+                            None => {
+                                eprintln!();
+                                eprintln!(
+                                    "PGCR returned empty response. Ignoring."
+                                );
+                                //TODO: should not get here, as none means either an API error
+                                //occured or there is no data associated with the ID (which is
+                                //an api data error).
+                                //we will just ignore it here, with the assumption that any error
+                                //is temporary, and will be fixed next time we sync
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!();
+                        eprintln!(
+                            "Error retrieving activity details from api. Skipping : {}",
+                            e
+                        );
+                        self.emit(SyncEvent::error(&format!(
+                            "Error retrieving activity details from api : {}",
+                            e
+                        )));
+                    }
+                }
+            }
+        }
+
+        eprintln!("]");
+        eprintln!(
+            "{} of {} synced ({}%)",
+            total_synced,
+            ids.len(),
+            ((total_synced as f32 / ids.len() as f32) * 100.0).floor()
+        );
+
+        Ok(SyncResult {
+            total_synced,
+            total_available,
+            characters: Vec::new(),
+        })
+    }
+
+    async fn update_activity_queue(
+        &self,
+        character_row_id: i32,
+        member_id: &str,
+        character_id: &str,
+        platform: &Platform,
+        api: &ApiInterface,
+        cancel: &CancellationToken,
+    ) -> Result<SyncResult, Error> {
+        //TODO catch errors so we can continue?
+        let prv_result = self
+            ._update_activity_queue(
+                character_row_id,
+                member_id,
+                character_id,
+                platform,
+                &Mode::PrivateMatchesAll,
+                api,
+                cancel,
+            )
+            .await?;
+
+        let pub_result = self
+            ._update_activity_queue(
+                character_row_id,
+                member_id,
+                character_id,
+                platform,
+                &Mode::AllPvP,
+                api,
+                cancel,
+            )
+            .await?;
+
+        //gambit (including gambit prime) is not included in AllPvP
+        let gambit_result = self
+            ._update_activity_queue(
+                character_row_id,
+                member_id,
+                character_id,
+                platform,
+                &Mode::AllPvECompetitive,
+                api,
+                cancel,
+            )
+            .await?;
+
+        let mut out = pub_result + prv_result + gambit_result;
+
+        if self.sync_pve {
+            for mode in &PVE_SYNC_MODES {
+                let result = self
+                    ._update_activity_queue(
+                        character_row_id,
+                        member_id,
+                        character_id,
+                        platform,
+                        mode,
+                        api,
+                        cancel,
+                    )
+                    .await?;
+                out = out + result;
+            }
+        }
+
+        Ok(out)
+    }
+
+    //updates activity id queue with ids which have not been synced. History
+    //is retrieved a page at a time (newest first), and each page is queued
+    //along with a cursor for the next page. If retrieving the history fails
+    //part way (i.e. the first sync of a long history), the next sync resumes
+    //from the last page retrieved rather than starting over.
+    #[allow(clippy::too_many_arguments)]
+    async fn _update_activity_queue(
+        &self,
+        character_row_id: i32,
+        member_id: &str,
+        character_id: &str,
+        platform: &Platform,
+        mode: &Mode,
+        api: &ApiInterface,
+        cancel: &CancellationToken,
+    ) -> Result<SyncResult, Error> {
+        let (mut page, since_activity_id) =
+            match self.retrieve_history_cursor(character_row_id, mode).await? {
+                Some((page, since_activity_id)) => {
+                    eprintln!(
+                        "Resuming {} activity history from page {}",
+                        mode,
+                        page + 1
+                    );
+                    (page, since_activity_id)
+                }
+                None => (
+                    0,
+                    self.get_max_activity_id(character_row_id, mode).await?,
+                ),
+            };
+
+        //history before the prune cutoff was removed on purpose, so we dont
+        //sync it again
+        let prune_cutoff = self.retrieve_prune_cutoff().await?;
+
+        let count = MAX_ACTIVITIES_REQUEST_COUNT;
+        let mut total = 0;
+
+        eprint!("[");
+        loop {
+            eprint!(".");
+
+            let activities = match api
+                .retrieve_activities(
+                    member_id,
+                    character_id,
+                    platform,
+                    mode,
+                    count,
+                    page,
+                )
+                .await
+            {
+                Ok(e) => e,
+                Err(e) => {
+                    eprintln!("]");
+                    return Err(e);
+                }
+            };
+
+            //if we page past the end of the history, the api returns no data
+            let activities = activities.unwrap_or_default();
+            let len = activities.len() as i32;
+
+            let mut ids: Vec<i64> = Vec::with_capacity(activities.len());
+            let mut found_since = false;
+            for activity in &activities {
+                if activity.details.instance_id == since_activity_id
+                    || prune_cutoff.is_some_and(|c| activity.period < c)
+                {
+                    found_since = true;
+                    break;
+                }
+
+                ids.push(activity.details.instance_id);
+            }
+
+            let done = found_since || len < count;
+            let next = if done {
+                None
+            } else {
+                Some((page + 1, since_activity_id))
+            };
+
+            self.queue_history_page(character_row_id, mode, &ids, next)
+                .await?;
+
+            total += ids.len() as u32;
+            self.emit(SyncEvent::history_page(mode, page, ids.len() as u32));
+
+            if done {
+                break;
+            }
+
+            //the cursor for the next page has been saved, so the walk
+            //resumes from here on the next sync
+            if cancel.is_cancelled() {
+                eprintln!("]");
+                return Err(Error::Cancelled);
+            }
+
+            page += 1;
+        }
+        eprintln!("]");
+
+        eprintln!("{} new activities found", total);
+
+        Ok(SyncResult {
+            total_available: total,
+            total_synced: total,
+            characters: Vec::new(),
+        })
+    }
+
+    //returns the page and since activity id of an unfinished history walk
+    //for the character and mode
+    async fn retrieve_history_cursor(
+        &self,
+        character_row_id: i32,
+        mode: &Mode,
+    ) -> Result<Option<(i32, i64)>, Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                page, since_activity_id
+            FROM
+                history_cursor
+            WHERE
+                character = ? AND mode = ?
+            "#,
+        )
+        .bind(character_row_id)
+        .bind(mode.to_id())
+        .fetch_optional(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        match row {
+            Some(r) => {
+                Ok(Some((r.try_get("page")?, r.try_get("since_activity_id")?)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    //queues the activity ids from a page of history, and saves the cursor
+    //for the next page (or removes it if the walk is finished) in the same
+    //transaction, so the queue and cursor always agree
+    async fn queue_history_page(
+        &self,
+        character_row_id: i32,
+        mode: &Mode,
+        activity_ids: &[i64],
+        next: Option<(i32, i64)>,
+    ) -> Result<(), Error> {
+        let mut conn = self.db.acquire().await?;
+
+        sqlx::query("BEGIN TRANSACTION;")
+            .execute(&mut conn)
+            .timed(TimingCategory::Database)
+            .await?;
+
+        match Self::_queue_history_page(
+            &mut conn,
+            character_row_id,
+            mode,
+            activity_ids,
+            next,
+        )
+        .await
+        {
+            Ok(_e) => {
+                sqlx::query("COMMIT;")
+                    .execute(&mut conn)
+                    .timed(TimingCategory::Database)
+                    .await?;
+                Ok(())
+            }
+            Err(e) => {
+                sqlx::query("ROLLBACK;")
+                    .execute(&mut conn)
+                    .timed(TimingCategory::Database)
+                    .await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn _queue_history_page(
+        conn: &mut SqliteConnection,
+        character_row_id: i32,
+        mode: &Mode,
+        activity_ids: &[i64],
+        next: Option<(i32, i64)>,
+    ) -> Result<(), Error> {
+        for activity_id in activity_ids {
+            sqlx::query(
+                //ignore activities already in the queue, since private gambit
+                //matches are returned for both private and gambit modes
+                "INSERT OR IGNORE into activity_queue ('activity_id', 'character') VALUES (?, ?)",
+            )
+            .bind(activity_id)
+            .bind(character_row_id)
+            .execute(&mut *conn)
+            .timed(TimingCategory::Database)
+            .await?;
+        }
+
+        match next {
+            Some((page, since_activity_id)) => {
+                sqlx::query(
+                    r#"
+                    INSERT OR REPLACE INTO "main"."history_cursor"
+                        ("character", "mode", "page", "since_activity_id", "updated_at")
+                    VALUES (?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(character_row_id)
+                .bind(mode.to_id())
+                .bind(page)
+                .bind(since_activity_id)
+                .bind(Utc::now().to_rfc3339())
+                .execute(&mut *conn)
+                .timed(TimingCategory::Database)
+                .await?;
+            }
+            None => {
+                sqlx::query(
+                    r#"
+                    DELETE FROM "main"."history_cursor" WHERE character = ? AND mode = ?
+                    "#,
+                )
+                .bind(character_row_id)
+                .bind(mode.to_id())
+                .execute(&mut *conn)
+                .timed(TimingCategory::Database)
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    //retrieves the PGCRs for the activities at the same time, returning the
+    //results in the same order as the ids. Throttled requests are retried,
+    //waiting for the throttle seconds returned by the api, or backing off
+    //exponentially, whichever is longer. If they are still throttled after
+    //MAX_THROTTLE_RETRIES (or the sync is cancelled), the throttle error is
+    //returned for them.
+    async fn retrieve_post_game_carnage_reports(
+        &self,
+        api: &ApiInterface,
+        instance_ids: &[i64],
+        cancel: &CancellationToken,
+    ) -> Vec<Result<RetrievedPgcr, Error>> {
+        let mut results: Vec<Option<Result<RetrievedPgcr, Error>>> =
+            instance_ids.iter().map(|_| None).collect();
+        let mut pending: Vec<usize> = (0..instance_ids.len()).collect();
+        let mut retry = 0;
+
+        loop {
+            //TODO: look into using threading for this
+            let f = pending.iter().map(|i| {
+                self.retrieve_post_game_carnage_report(api, instance_ids[*i])
+            });
+            let r = futures::future::join_all(f).await;
+
+            let mut throttled: Vec<usize> = Vec::new();
+            let mut throttle_seconds = 0;
+            for (i, r) in pending.iter().zip(r) {
+                if let Err(Error::ApiThrottled { seconds: t, .. }) = &r {
+                    if retry < MAX_THROTTLE_RETRIES && !cancel.is_cancelled() {
+                        throttle_seconds = throttle_seconds.max(*t as u64);
+                        throttled.push(*i);
+                        continue;
+                    }
+                }
+
+                results[*i] = Some(r);
+            }
+
+            if throttled.is_empty() {
+                break;
+            }
+
+            let wait = throttle_seconds.max(THROTTLE_BACKOFF_SECONDS << retry);
+            eprintln!();
+            eprintln!(
+                "API requests were throttled. Retrying {} activities in {} seconds.",
+                throttled.len(),
+                wait
+            );
+
+            //wait a second at a time, so a cancel isnt held up by the wait
+            for _ in 0..wait {
+                if cancel.is_cancelled() {
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            }
+
+            pending = throttled;
+            retry += 1;
+        }
+
+        //every id has a result once nothing is left to retry
+        results.into_iter().flatten().collect()
+    }
+
+    //retrieves the PGCR from the api, along with its original json if raw
+    //PGCRs are being stored
+    async fn retrieve_post_game_carnage_report(
+        &self,
+        api: &ApiInterface,
+        instance_id: i64,
+    ) -> Result<RetrievedPgcr, Error> {
+        if self.store_raw_pgcr {
+            let out = api
+                .retrieve_post_game_carnage_report_with_raw(instance_id)
+                .await?;
+            return Ok(out.map(|(data, raw)| (data, Some(raw))));
+        }
+
+        let out = api.retrieve_post_game_carnage_report(instance_id).await?;
+        Ok(out.map(|data| (data, None)))
+    }
+
+    async fn insert_activity(
+        &self,
+        data: &DestinyPostGameCarnageReportData,
+        character_row_id: i32,
+        raw: Option<&serde_json::Value>,
+    ) -> Result<(), Error> {
+        let mut conn = self.db.acquire().await?;
+
+        sqlx::query("BEGIN TRANSACTION;")
+            .execute(&mut conn)
+            .timed(TimingCategory::Database)
+            .await?;
+
+        match Self::_insert_synced_activity(
+            &mut conn,
+            data,
+            character_row_id,
+            raw,
+        )
+        .await
+        {
+            Ok(_e) => {
+                sqlx::query("COMMIT;")
+                    .execute(&mut conn)
+                    .timed(TimingCategory::Database)
+                    .await?;
+                sqlx::query("PRAGMA OPTIMIZE;")
+                    .execute(&mut conn)
+                    .timed(TimingCategory::Database)
+                    .await?;
+
+                Ok(())
+            }
+            Err(e) => {
+                sqlx::query("ROLLBACK;")
+                    .execute(&mut conn)
+                    .timed(TimingCategory::Database)
+                    .await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn _insert_entry(
+        conn: &mut SqliteConnection,
+        entry: &DestinyPostGameCarnageReportEntry,
+        activity_row_id: i32,
+    ) -> Result<(), Error> {
+        //todo: not sure if we should use membership type of crosssave orveride
+        let member_row_id = Self::insert_member_id(
+            conn,
+            &entry.player.user_info.membership_id,
+            &entry.player.user_info.membership_type,
+            &entry.player.user_info.display_name,
+        )
+        .await?;
+
+        let class_type = CharacterClass::from_hash(entry.player.class_hash);
+
+        let character_row_id = Self::insert_character_id(
+            conn,
+            &entry.character_id,
+            &class_type,
+            member_row_id,
+        )
+        .await?;
+
+        Self::_insert_character_activity_stats(
+            conn,
+            entry,
+            character_row_id,
+            activity_row_id,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    //todo: this doesnt need to be an instance fn, not sure if it matters
+    fn get_medal_hash_value(
+        property: &str,
+        medal_hash: &HashMap<String, DestinyHistoricalStatsValue>,
+    ) -> u32 {
+        match medal_hash.get(property) {
+            Some(e) => e.basic.value as u32,
+            None => 0,
+        }
+    }
+
+    //if character_row_id is set, the activity is removed from the character's
+    //activity queue
+    async fn _insert_activity(
+        conn: &mut SqliteConnection,
+        data: &DestinyPostGameCarnageReportData,
+        character_row_id: Option<i32>,
+    ) -> Result<(), Error> {
+        //see if we already have this activity
+        match Self::get_activity_row_id(conn, data.activity_details.instance_id)
+            .await
+        {
+            Ok(_e) => {
+                return Ok(());
+            }
+            Err(_e) => (),
+        };
+
+        //todo:if it already exists, what should we do? we have the data? do we need to remove
+        //from queue?
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO "main"."activity"
+                ("activity_id","period","mode","platform","director_activity_hash", "reference_id", "season") 
+            VALUES (?,?,?,?,?, ?, (
+                SELECT season_number FROM season
+                WHERE ? >= start_date AND (end_date IS NULL OR ? < end_date)
+                ORDER BY season_number DESC LIMIT 1
+            ))
+        "#,
+        )
+        .bind(data.activity_details.instance_id) //activity_id
+        .bind(data.period.to_rfc3339()) //period
+        .bind(data.activity_details.mode.to_id().to_string()) //mode
+        .bind(data.activity_details.membership_type.to_id().to_string()) //platform
+        .bind(data.activity_details.director_activity_hash.to_string()) //director_activity_hash
+        .bind(data.activity_details.reference_id.to_string()) //reference_id
+        .bind(data.period.to_rfc3339()) //season
+        .bind(data.period.to_rfc3339()) //season
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let activity_row_id =
+            Self::get_activity_row_id(conn, data.activity_details.instance_id)
+                .await?;
+
+        for team in &data.teams {
+            sqlx::query(
+                r#"
+                INSERT INTO "main"."team_result"
+                (
+                    "team_id", "score", "standing", "activity", "team_name"
+                )
+                VALUES(?,?, ?, ?, ?)
+                "#,
+            )
+            .bind(team.team)
+            .bind(team.score as i32)
+            .bind(team.standing as i32)
+            .bind(activity_row_id)
+            .bind(
+                team.team_name
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|n| !n.is_empty()),
+            )
+            .execute(&mut *conn)
+            .timed(TimingCategory::Database)
+            .await?;
+        }
+
+        //TODO: Rumble will have no teams. Need to create one
+
+        for mode in &data.activity_details.modes {
+            sqlx::query(
+                r#"
+                INSERT INTO "main"."modes"
+                (
+                    "mode", "activity"
+                )
+                VALUES(?,?)
+                "#,
+            )
+            .bind(mode.to_id().to_string())
+            .bind(activity_row_id)
+            .execute(&mut *conn)
+            .timed(TimingCategory::Database)
+            .await?;
+        }
+
+        //the entry for the character being synced is always stored, so the
+        //activity shows up for them, even if the report is over the limit
+        let synced_character_id: Option<String> = match character_row_id {
+            Some(e) => Some(
+                sqlx::query(
+                    r#"
+                    SELECT character_id FROM "main"."character" WHERE id = ?
+                    "#,
+                )
+                .bind(e)
+                .fetch_one(&mut *conn)
+                .timed(TimingCategory::Database)
+                .await?
+                .try_get("character_id")?,
+            ),
+            None => None,
+        };
+        let is_synced = |character_id: &str| {
+            synced_character_id.as_deref() == Some(character_id)
+        };
+
+        if let Some(character_id) = &synced_character_id {
+            if !data.entries.iter().any(|e| &e.character_id == character_id) {
+                return Err(Error::Unknown {
+                    description: format!(
+                        "Activity {} does not include an entry for character {}",
+                        data.activity_details.instance_id, character_id
+                    ),
+                });
+            }
+        }
+
+        if data.entries.len() > MAX_PGCR_ENTRIES {
+            eprintln!();
+            eprintln!(
+                "Activity {} has {} entries. Only storing {}.",
+                data.activity_details.instance_id,
+                data.entries.len(),
+                MAX_PGCR_ENTRIES
+            );
+        }
+
+        for entry in capped_pgcr_entries(&data.entries, is_synced) {
+            //each entry is inserted within its own savepoint, so if there is
+            //bad data for one player, we can skip them and still store the rest
+            //of the activity
+            sqlx::query("SAVEPOINT entry;")
+                .execute(&mut *conn)
+                .timed(TimingCategory::Database)
+                .await?;
+
+            match Self::_insert_entry(conn, entry, activity_row_id).await {
+                Ok(_e) => {
+                    sqlx::query("RELEASE entry;")
+                        .execute(&mut *conn)
+                        .timed(TimingCategory::Database)
+                        .await?;
+                }
+                Err(e) => {
+                    sqlx::query("ROLLBACK TO entry;")
+                        .execute(&mut *conn)
+                        .timed(TimingCategory::Database)
+                        .await?;
+                    sqlx::query("RELEASE entry;")
+                        .execute(&mut *conn)
+                        .timed(TimingCategory::Database)
+                        .await?;
+
+                    //without the synced character's entry the activity isnt
+                    //stored, and is left in the queue for the next sync
+                    if is_synced(&entry.character_id) {
+                        return Err(e);
+                    }
+
+                    eprintln!();
+                    eprintln!(
+                        "Error inserting entry for character {} in activity {}. Skipping. : {}",
+                        entry.character_id,
+                        data.activity_details.instance_id,
+                        e
+                    );
+                }
+            }
+        }
+
+        if let Some(character_row_id) = character_row_id {
+            Self::remove_from_activity_queue(
+                conn,
+                &character_row_id,
+                &data.activity_details.instance_id,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn _insert_character_activity_stats(
+        conn: &mut SqliteConnection,
+        entry: &DestinyPostGameCarnageReportEntry,
+        character_row_id: i32,
+        activity_row_id: i32,
+    ) -> Result<(), Error> {
+        let char_data = entry;
+
+        let medal_hash: &HashMap<String, DestinyHistoricalStatsValue> =
+            &entry.extended.values;
+
+        let precision_kills: u32 =
+            Self::get_medal_hash_value("precisionKills", medal_hash);
+        let weapon_kills_ability: u32 =
+            Self::get_medal_hash_value("weaponKillsAbility", medal_hash);
+        let weapon_kills_grenade: u32 =
+            Self::get_medal_hash_value("weaponKillsGrenade", medal_hash);
+        let weapon_kills_melee: u32 =
+            Self::get_medal_hash_value("weaponKillsMelee", medal_hash);
+        let weapon_kills_super: u32 =
+            Self::get_medal_hash_value("weaponKillsSuper", medal_hash);
+        let all_medals_earned: u32 =
+            Self::get_medal_hash_value("allMedalsEarned", medal_hash);
+
+        sqlx::query(
+            r#"
+            INSERT INTO "main"."character_activity_stats"
+            (
+                "character", "assists", "score", "kills", "deaths", 
+                "average_score_per_kill", "average_score_per_life", "completed", 
+                "opponents_defeated", "activity_duration_seconds", "standing", 
+                "team", "completion_reason", "start_seconds", "time_played_seconds", 
+                "player_count", "team_score", "precision_kills", "weapon_kills_ability", 
+                "weapon_kills_grenade", "weapon_kills_melee", "weapon_kills_super", 
+                "all_medals_earned", "light_level", "activity", "fireteam_id"
+            )
+            VALUES (
+                ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?,
+                ?, ?, ? )
+            "#,
+        )
+        //we for through format, as otherwise we have to cast to i32, and while
+        //shouldnt be an issue, there is a chance we could lose precision when
+        //converting some of the IDS. so we just do this to be consistent.
+        //TODO: should think about losing data when pulling out of DB
+        .bind(character_row_id) //character
+        .bind(char_data.values.assists as i32) //assists
+        .bind(char_data.values.score as i32) //score
+        .bind(char_data.values.kills as i32) //kiis
+        .bind(char_data.values.deaths as i32) //deaths
+        .bind(char_data.values.average_score_per_kill) //average_score_per_kill
+        .bind(char_data.values.average_score_per_life) //average_score_per_life
+        .bind(char_data.values.completed as i32) //completed
+        .bind(char_data.values.opponents_defeated as i32) //opponents_defeated
+        .bind(format!(
+            "{}",
+            char_data.values.activity_duration_seconds as u32
+        )) //activity_duration_seconds
+        .bind(char_data.values.standing as i32) //standing
+        .bind(char_data.values.team as i32) //team
+        .bind(char_data.values.completion_reason as i32) //completion_reason
+        .bind(char_data.values.start_seconds as i32) //start_seconds
+        .bind(char_data.values.time_played_seconds as i32) //time_played_seconds
+        .bind(char_data.values.player_count as i32) //player_count
+        .bind(char_data.values.team_score as i32) //team_score
+        .bind(precision_kills as i32) //precision_kills
+        .bind(weapon_kills_ability as i32) //weapon_kills_ability
+        .bind(weapon_kills_grenade as i32) //weapon_kills_grenade
+        .bind(weapon_kills_melee as i32) //weapon_kills_melee
+        .bind(weapon_kills_super as i32) //weapon_kills_super
+        .bind(all_medals_earned as i32) //weapon_kills_super
+        .bind(char_data.player.light_level) //activity
+        .bind(activity_row_id) //activity
+        .bind(char_data.values.get_fireteam_id()) //fireteam_id
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        //character_activity_stats
+
+        let row = sqlx::query(
+            r#"
+            SELECT "id" FROM "character_activity_stats" WHERE activity = ? and character = ?
+        "#,
+        )
+        .bind(activity_row_id)
+        .bind(character_row_id)
+        .fetch_one(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let character_activity_stats_id: i32 = row.try_get("id")?;
+
+        for (key, value) in medal_hash {
+            sqlx::query(
+                r#"
+                INSERT INTO "main"."medal_result"
+                (
+                    "reference_id", "count", "character_activity_stats"
+                )
+                VALUES  (
+                    ?,?,?
+                )
+                "#,
+            )
+            .bind(key) //reference_id
+            .bind(format!("{}", value.basic.value as u32)) //unique_weapon_kills
+            .bind(character_activity_stats_id)
+            .execute(&mut *conn)
+            .timed(TimingCategory::Database)
+            .await?;
+        }
+
+        //ran into a case once where weapons was missing, so have to check here
+        if char_data.extended.weapons.is_some() {
+            let weapons = entry.extended.weapons.as_ref().unwrap();
+            for w in weapons {
+                sqlx::query(
+                    r#"
+                    INSERT INTO "main"."weapon_result"
+                    (
+                        "reference_id", "kills", "precision_kills", "kills_precision_kills_ratio", "character_activity_stats"
+                    )
+                    VALUES (?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(format!("{}", w.reference_id)) //reference_id
+                .bind(format!("{}", w.values.unique_weapon_kills as u32)) //unique_weapon_kills
+                .bind(format!("{}", w.values.unique_weapon_precision_kills as u32)) //unique_weapon_precision_kills
+                .bind(format!("{}", w.values.unique_weapon_kills_precision_kills)) //unique_weapon_kills_precision_kills
+                .bind(character_activity_stats_id)
+                .execute(&mut *conn)
+                .timed(TimingCategory::Database)
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn remove_from_activity_queue(
+        conn: &mut SqliteConnection,
+        character_row_id: &i32,
+        instance_id: &i64,
+    ) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            DELETE FROM "main"."activity_queue" WHERE character = ? and activity_id = ?
+        "#,
+        )
+        .bind(character_row_id.to_string())
+        .bind(instance_id)
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_activity_row_id(
+        conn: &mut SqliteConnection,
+        instance_id: i64,
+    ) -> Result<i32, Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT "id" FROM "activity" WHERE activity_id = ?
+        "#,
+        )
+        .bind(instance_id.to_string())
+        .fetch_one(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let id: i32 = row.try_get("id")?;
+
+        Ok(id)
+    }
+
+    async fn get_character_row_id(
+        &self,
+        member_id: &str,
+        character_id: &str,
+    ) -> Result<i32, Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                character.id as id 
+            FROM
+                "character"
+            JOIN
+                member on character.member = member.id and member.member_id = ?
+            WHERE
+                character_id = ?
+        "#,
+        )
+        .bind(member_id.to_string())
+        .bind(character_id.to_string())
+        .fetch_one(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let character_rowid: i32 = row.try_get("id")?;
+
+        Ok(character_rowid)
+    }
+
+    async fn insert_member_id(
+        conn: &mut SqliteConnection,
+        member_id: &str,
+        platform: &Platform,
+        display_name: &str,
+    ) -> Result<i32, Error> {
+        //we will use whatever the last display name that we find (since you can
+        //change it on PC)
+        sqlx::query(
+            r#"
+            INSERT into "member" ("member_id", "platform_id", "display_name") VALUES (?, ?, ?)
+            ON CONFLICT(member_id) DO UPDATE
+            set display_name = ?
+        "#,
+        )
+        .bind(member_id.to_string())
+        .bind(platform.to_id().to_string())
+        .bind(display_name.to_string())
+        .bind(display_name.to_string())
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT id from "member" where member_id=?
+        "#,
+        )
+        .bind(member_id.to_string())
+        .bind(format!("{}", platform.to_id()))
+        .fetch_one(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let rowid: i32 = row.try_get("id")?;
+
+        Ok(rowid)
+    }
+
+    async fn insert_character_id(
+        conn: &mut SqliteConnection,
+        character_id: &str,
+        class_type: &CharacterClass,
+        member_rowid: i32,
+    ) -> Result<i32, Error> {
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE into "character" ("character_id", "member", "class") VALUES (?, ?, ?)
+        "#,
+        )
+        .bind(character_id.to_string())
+        .bind(member_rowid)
+        .bind(class_type.to_id().to_string())
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT id from "character" where character_id=? and member=?
+        "#,
+        )
+        .bind(character_id.to_string())
+        .bind(format!("{}", member_rowid))
+        .fetch_one(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let rowid: i32 = row.try_get("id")?;
+
+        Ok(rowid)
+    }
+
+    //replaces the stored details for the member's characters, so characters
+    //which have been deleted are removed
+    async fn save_character_metadata(
+        &self,
+        member_id: &str,
+        characters: &[CharacterData],
+    ) -> Result<(), Error> {
+        let mut conn = self.db.acquire().await?;
+
+        sqlx::query("BEGIN TRANSACTION;")
+            .execute(&mut conn)
+            .timed(TimingCategory::Database)
+            .await?;
+
+        match Self::_save_character_metadata(&mut conn, member_id, characters)
+            .await
+        {
+            Ok(_e) => {
+                sqlx::query("COMMIT;")
+                    .execute(&mut conn)
+                    .timed(TimingCategory::Database)
+                    .await?;
+                Ok(())
+            }
+            Err(e) => {
+                sqlx::query("ROLLBACK;")
+                    .execute(&mut conn)
+                    .timed(TimingCategory::Database)
+                    .await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn _save_character_metadata(
+        conn: &mut SqliteConnection,
+        member_id: &str,
+        characters: &[CharacterData],
+    ) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            DELETE FROM "main"."character_metadata" WHERE member_id = ?
+            "#,
+        )
+        .bind(member_id)
+        .execute(&mut *conn)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let updated_at = Utc::now().to_rfc3339();
+        for c in characters {
+            sqlx::query(
+                r#"
+                INSERT OR REPLACE INTO "main"."character_metadata"
+                    ("character_id", "member_id", "class", "emblem_hash",
+                    "light", "minutes_played_total", "date_last_played",
+                    "updated_at")
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&c.id)
+            .bind(member_id)
+            .bind(c.class_type.to_id())
+            .bind(c.emblem_hash)
+            .bind(c.stats.power)
+            .bind(c.minutes_played_total)
+            .bind(c.date_last_played.to_rfc3339())
+            .bind(&updated_at)
+            .execute(&mut *conn)
+            .timed(TimingCategory::Database)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// returns the stored details for the member's characters, most recently
+    /// played first. Details are refreshed each time the member is synced, so
+    /// this is empty if the member has not been synced.
+    pub async fn retrieve_character_metadata(
+        &self,
+        member_id: &str,
+    ) -> Result<Vec<CharacterMetadata>, Error> {
+        let rows = sqlx::query_as::<_, CharacterMetadataRow>(
+            r#"
+            SELECT * FROM "main"."character_metadata"
+            WHERE member_id = ?
+            ORDER BY date_last_played DESC
+            "#,
+        )
+        .bind(member_id)
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(CharacterMetadata {
+                class_type: CharacterClass::from_id(row.class),
+                emblem_hash: row.emblem_hash,
+                light: row.light,
+                minutes_played_total: row.minutes_played_total,
+                date_last_played: DateTime::parse_from_rfc3339(
+                    &row.date_last_played,
+                )?
+                .with_timezone(&Utc),
+                updated_at: DateTime::parse_from_rfc3339(&row.updated_at)?
+                    .with_timezone(&Utc),
+                character_id: row.character_id,
+                member_id: row.member_id,
+            });
+        }
+
+        Ok(out)
+    }
+
+    async fn get_max_activity_id(
+        &self,
+        character_row_id: i32,
+        mode: &Mode,
+    ) -> Result<i64, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                activity_id as max_activity_id
+            FROM
+                "activity"
+            INNER JOIN
+                character_activity_stats ON character_activity_stats.activity = activity.id,
+                character on character_activity_stats.character = character.id,
+                modes ON modes.activity = activity.id and modes.mode in (select mode from modes where mode = ?)
+            WHERE
+                character_activity_stats.character = ?
+            ORDER BY period DESC LIMIT 1
+        "#,
+        )
+        .bind(mode.to_id().to_string())
+        .bind(character_row_id.to_string())
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        if rows.is_empty() {
+            return Ok(0);
+        }
+
+        let row = &rows[0];
+        let activity_id: i64 = row.try_get("max_activity_id")?;
+        Ok(activity_id)
+    }
+
+    /// retrieves the original PGCR json for a stored activity. The json
+    /// stored with the activity is used if available (see
+    /// set_store_raw_pgcr), otherwise it is retrieved from the api.
+    /// Useful for debugging, and for looking at data which is not stored.
+    pub async fn retrieve_raw_post_game_carnage_report(
+        &self,
+        activity_id: i64,
+    ) -> Result<serde_json::Value, Error> {
+        if let Some(e) = self.retrieve_stored_raw_pgcr(activity_id).await? {
+            return Ok(e);
+        }
+
+        let api = ApiInterface::new(self.verbose)?;
+
+        match api
+            .retrieve_raw_post_game_carnage_report(activity_id)
+            .await?
+        {
+            Some(e) => Ok(e),
+            None => Err(Error::ActivityNotFound),
+        }
+    }
+
+    pub async fn retrieve_activity_by_index(
+        &self,
+        activity_index: u32,
+        manifest: &mut ManifestInterface,
+    ) -> Result<CrucibleActivity, Error> {
+        self.retrieve_activity_where(
+            "activity.id",
+            activity_index as i64,
+            manifest,
+        )
+        .await
+    }
+
+    /// retrieves a stored activity by its Bungie instance id (the id in
+    /// activity urls on bungie.net and third party sites). Returns
+    /// Error::ActivityNotFound if the activity has not been synced.
+    pub async fn retrieve_activity_by_instance_id(
+        &self,
+        instance_id: i64,
+        manifest: &mut ManifestInterface,
+    ) -> Result<CrucibleActivity, Error> {
+        self.retrieve_activity_where(
+            "activity.activity_id",
+            instance_id,
+            manifest,
+        )
+        .await
+    }
+
+    //column is one of the unique activity columns (id or activity_id)
+    async fn retrieve_activity_where(
+        &self,
+        column: &str,
+        value: i64,
+        manifest: &mut ManifestInterface,
+    ) -> Result<CrucibleActivity, Error> {
+        let activity_row = match sqlx::query(&format!(
+            r#"
+            SELECT
+                activity.id as activity_index_id,
+                activity.activity_id,
+                activity.period,
+                activity.mode as activity_mode,
+                activity.director_activity_hash,
+                activity.reference_id,
+                activity.platform
+            FROM
+                activity
+            INNER JOIN
+                character_activity_stats on character_activity_stats.activity = activity.id,
+                character on character_activity_stats.character = character.id,
+                member on character.member = member.id
+            WHERE
+                {} = ?
+            ORDER BY
+                period DESC LIMIT 1
+            "#,
+            column
+        ))
+        .bind(value)
+        .fetch_one(&self.db)
+        .timed(TimingCategory::Database)
+        .await
+        {
+            Ok(e) => e,
+            Err(e) => match e {
+                sqlx::Error::RowNotFound => {
+                    return Err(Error::ActivityNotFound);
+                }
+                _ => {
+                    return Err(Error::from(e));
+                }
+            },
+        };
+
+        let crucible_activity =
+            self.populate_activity_data(&activity_row, manifest).await?;
+        Ok(crucible_activity)
+    }
+
+    pub async fn retrieve_last_activity(
+        &self,
+        member_id: &str,
+        platform: &Platform,
+        character_selection: &CharacterClassSelection,
+        mode: &Mode,
+        manifest: &mut ManifestInterface,
+    ) -> Result<CrucibleActivity, Error> {
+        let activity_row = if character_selection
+            == &CharacterClassSelection::All
+        {
+            match sqlx::query(
+                r#"
+                SELECT
+                    activity.id as activity_index_id,
+                    activity.activity_id,
+                    activity.period,
+                    activity.mode as activity_mode,
+                    activity.director_activity_hash,
+                    activity.reference_id,
+                    activity.platform
+                FROM
+                    activity
+                INNER JOIN
+                    character_activity_stats on character_activity_stats.activity = activity.id,
+                    character on character_activity_stats.character = character.id,
+                    member on character.member = member.id AND member.member_id = ?
+                WHERE
+                    exists (select 1 from modes where activity = activity.id and mode = ?)
+                ORDER BY
+                    period DESC LIMIT 1
+                "#,
+            )
+            .bind(member_id.to_string())
+            .bind(mode.to_id().to_string())
+            .fetch_one(&self.db)
+            .timed(TimingCategory::Database)
+            .await
+            {
+                Ok(e) => e,
+                Err(e) => match e {
+                    sqlx::Error::RowNotFound => {
+                        return Err(Error::ActivityNotFound);
+                    }
+                    _ => {
+                        return Err(Error::from(e));
+                    }
+                },
+            }
+        } else {
+            let character_id = self
+                .retrieve_character_selection_id(
+                    member_id,
+                    platform,
+                    character_selection,
+                )
+                .await?;
+
+            match sqlx::query(
+                    r#"
+                    SELECT
+                        activity.id as activity_index_id,
+                        activity.activity_id,
+                        activity.period,
+                        activity.mode as activity_mode,
+                        activity.director_activity_hash,
+                        activity.reference_id,
+                        activity.platform
+                    FROM
+                        activity
+                    INNER JOIN
+                        character_activity_stats on character_activity_stats.activity = activity.id,
+                        character on character_activity_stats.character = character.id AND character.character_id = ?
+                    WHERE
+                        exists (select 1 from modes where activity = activity.id and mode = ?)
+                    ORDER BY
+                        period DESC LIMIT 1
+                    "#
+                ).bind(character_id.to_string())
+                .bind(mode.to_id().to_string())
+                .fetch_one(&self.db)
+                .timed(TimingCategory::Database)
+                .await
+                {
+                    Ok(e) => e,
+                    Err(e) => match e {
+                        sqlx::Error::RowNotFound => {
+                            return Err(Error::ActivityNotFound);
+                        }
+                        _ => {
+                            return Err(Error::from(e));
+                        }
+                    },
+                }
+        };
+
+        let crucible_activity =
+            self.populate_activity_data(&activity_row, manifest).await?;
+        Ok(crucible_activity)
+    }
+
+    async fn populate_activity_data(
+        &self,
+        activity_row: &sqlx::sqlite::SqliteRow,
+        manifest: &mut ManifestInterface,
+    ) -> Result<CrucibleActivity, Error> {
+        let activity_row_id: i64 = activity_row.try_get("activity_index_id")?;
+
+        let team_rows = sqlx::query_as::<_, TeamResultRow>(
+            r#"
+            SELECT
+                *
+            FROM
+                team_result
+            WHERE
+                activity = ?
+            "#,
+        )
+        .bind(activity_row_id)
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut teams: HashMap<i32, Team> = HashMap::new();
+
+        let mut team_names = vec![
+            "Alpha".to_string(),
+            "Bravo".to_string(),
+            "Charlie".to_string(),
+            "Delta".to_string(),
+            "Echo".to_string(),
+            "Foxtrot".to_string(),
+        ];
+        team_names.reverse();
+
+        for t in team_rows {
+            let standing = Standing::from_value(t.standing as u32);
+            let id = t.team_id;
+            let score = t.score;
+
+            let player_performances: Vec<CruciblePlayerPerformance> =
+                Vec::new();
+
+            //use the name the api returned for the team if we have one, and
+            //fall back to the generated names for older activities
+            let generated_name =
+                team_names.pop().unwrap_or_else(|| "".to_string());
+            let display_name = match t.team_name {
+                Some(e) if !e.is_empty() => e,
+                _ => generated_name,
+            };
+
+            let team = Team {
+                standing,
+                id,
+                score,
+                player_performances,
+                display_name,
+            };
+
+            teams.insert(id, team);
+        }
+
+        //Rumble wont have any teams, so we put all items in one team
+        //this also covered any bugs where no teams are specified
+        let mut no_teams = false;
+        if teams.is_empty() {
+            let display_name =
+                team_names.pop().unwrap_or_else(|| "".to_string());
+
+            let team = Team {
+                standing: Standing::Unknown,
+                id: NO_TEAMS_INDEX,
+                score: 0,
+                player_performances: Vec::new(),
+                display_name,
+            };
+
+            teams.insert(NO_TEAMS_INDEX, team);
+            no_teams = true;
+        }
+
+        //TODO: need to account for character and member, need to join both
+        let character_rows = sqlx::query(
+            r#"
+            SELECT
+                *,
+                character_activity_stats.id as character_activity_stats_index
+            FROM
+                character_activity_stats
+            INNER JOIN
+                character on character_activity_stats.character = character.id,
+                member on character.member = member.id
+            WHERE
+                activity = ?
+            "#,
+        )
+        .bind(activity_row_id)
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        for c_row in character_rows {
+            let stats_row = CharacterActivityStatsRow::from_row(&c_row)?;
+            let player_row = PlayerRow::from_row(&c_row)?;
+
+            let stats = self
+                .parse_crucible_stats(manifest, activity_row_id, &stats_row)
+                .await?;
+            let player = Self::parse_player(&player_row, &stats_row);
+
+            let cpp = CruciblePlayerPerformance { stats, player };
+
+            let index = if no_teams {
+                NO_TEAMS_INDEX
+            } else {
+                cpp.stats.team
+            };
+
+            match teams.get_mut(&index) {
+                Some(e) => e.player_performances.push(cpp),
+                None => eprintln!("Invalid Team ID ({}) : Skipping", &index),
+            }
+        }
+
+        let activity = ActivityRow::from_row(activity_row)?;
+        let details = self.parse_activity(manifest, &activity).await?;
+
+        Ok(CrucibleActivity { details, teams })
+    }
+
+    //returns character_id for specified character class selection
+    //returns member_id if selection is ALL
+    async fn retrieve_character_selection_id(
+        &self,
+        member_id: &str,
+        platform: &Platform,
+        character_selection: &CharacterClassSelection,
+    ) -> Result<String, Error> {
+        if character_selection == &CharacterClassSelection::All {
+            return Ok(member_id.to_string());
+        }
+
+        //resolve from the character details stored when syncing if we can,
+        //and only call the api for members that have not been synced
+        let stored = self.retrieve_character_metadata(member_id).await?;
+        if !stored.is_empty() {
+            let found = match character_selection {
+                CharacterClassSelection::LastActive => stored.first(),
+                CharacterClassSelection::Hunter => stored
+                    .iter()
+                    .find(|c| c.class_type == CharacterClass::Hunter),
+                CharacterClassSelection::Titan => stored
+                    .iter()
+                    .find(|c| c.class_type == CharacterClass::Titan),
+                CharacterClassSelection::Warlock => stored
+                    .iter()
+                    .find(|c| c.class_type == CharacterClass::Warlock),
+                CharacterClassSelection::All => None,
+            };
+
+            return match found {
+                Some(e) => Ok(e.character_id.to_string()),
+                None => Err(Error::CharacterDoesNotExist),
+            };
+        }
+
+        let api = ApiInterface::new(self.verbose)?;
+        //first, lets get all of the current characters for the member
+        let characters = api
+            .retrieve_characters(member_id, platform)
+            .await?
+            .ok_or(Error::NoCharacters)?;
+
+        let out = match character_selection {
+            CharacterClassSelection::All => member_id.to_string(),
+            CharacterClassSelection::Hunter => {
+                match characters.get_by_class_ref(CharacterClass::Hunter) {
+                    Some(e) => e.id.to_string(),
+                    None => return Err(Error::CharacterDoesNotExist),
+                }
+            }
+            CharacterClassSelection::Titan => {
+                match characters.get_by_class_ref(CharacterClass::Titan) {
+                    Some(e) => e.id.to_string(),
+                    None => return Err(Error::CharacterDoesNotExist),
+                }
+            }
+            CharacterClassSelection::Warlock => {
+                match characters.get_by_class_ref(CharacterClass::Warlock) {
+                    Some(e) => e.id.to_string(),
+                    None => return Err(Error::CharacterDoesNotExist),
+                }
+            }
+            CharacterClassSelection::LastActive => {
+                match characters.get_last_active_ref() {
+                    Some(e) => e.id.to_string(),
+                    None => return Err(Error::CharacterDoesNotExist),
+                }
+            }
+        };
+
+        Ok(out)
+    }
+
+    /// streams every player performance (for all players, not just synced
+    /// members) for activities in the specified mode, calling f for each
+    /// row. Returns the number of rows exported.
+    pub async fn export_player_performances<F>(
+        &self,
+        mode: &Mode,
+        mut f: F,
+    ) -> Result<u64, Error>
+    where
+        F: FnMut(PlayerPerformanceRow) -> Result<(), Error>,
+    {
+        //if mode if private, we dont restrict results
+        let restrict_mode_id = if mode.is_private() {
+            -1
+        } else {
+            Mode::PrivateMatchesAll.to_id() as i32
+        };
+
+        let mut rows = sqlx::query(
+            r#"
+            SELECT
+                activity.activity_id,
+                activity.period,
+                activity.mode as activity_mode,
+                activity.platform,
+                activity.reference_id,
+                activity.director_activity_hash,
+                activity.id as activity_index_id,
+                member.member_id,
+                member.display_name,
+                member.platform_id,
+                character.character_id,
+                character.class,
+                character_activity_stats.*,
+                character_activity_stats.id as character_activity_stats_index
+            FROM
+                character_activity_stats
+            INNER JOIN
+                activity ON character_activity_stats.activity = activity.id,
+                character on character_activity_stats.character = character.id,
+                member on member.id = character.member
+            WHERE
+                exists (select 1 from modes where activity = activity.id and mode = ?) AND
+                not exists (select 1 from modes where activity = activity.id and mode = ?)
+            ORDER BY
+                activity.period ASC
+            "#,
+        )
+        .bind(mode.to_id().to_string())
+        .bind(restrict_mode_id.to_string())
+        .fetch(&self.db);
+
+        let mut count: u64 = 0;
+        while let Some(row) = rows.try_next().await? {
+            let activity = ActivityRow::from_row(&row)?;
+            let stats = CharacterActivityStatsRow::from_row(&row)?;
+            let player = PlayerRow::from_row(&row)?;
+
+            f(PlayerPerformanceRow {
+                activity_id: activity.activity_id,
+                period: activity.period,
+                mode: Mode::from_id(activity.activity_mode)?,
+                platform: Platform::from_id(activity.platform),
+                reference_id: activity.reference_id,
+                director_activity_hash: activity.director_activity_hash as u32,
+                member_id: player.member_id,
+                display_name: player.display_name,
+                member_platform: Platform::from_id(player.platform_id),
+                character_id: player.character_id,
+                class_type: CharacterClass::from_id(player.class),
+                light_level: stats.light_level,
+                team: stats.team,
+                standing: Standing::from_value(stats.standing),
+                completed: stats.completed == 1,
+                start_seconds: stats.start_seconds,
+                time_played_seconds: stats.time_played_seconds,
+                activity_duration_seconds: stats.activity_duration_seconds,
+                player_count: stats.player_count,
+                team_score: stats.team_score,
+                score: stats.score,
+                kills: stats.kills,
+                deaths: stats.deaths,
+                assists: stats.assists,
+                opponents_defeated: stats.opponents_defeated,
+                precision_kills: stats.precision_kills,
+                weapon_kills_ability: stats.weapon_kills_ability,
+                weapon_kills_grenade: stats.weapon_kills_grenade,
+                weapon_kills_melee: stats.weapon_kills_melee,
+                weapon_kills_super: stats.weapon_kills_super,
+                all_medals_earned: stats.all_medals_earned,
+            })?;
+
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// streams the rows of a store table for export, calling f with the
+    /// values for each of the table's export columns. Rows for tables tied
+    /// to an activity are restricted to activities in the specified mode.
+    /// Returns the number of rows streamed.
+    pub async fn export_table_rows<F>(
+        &self,
+        table: &ExportTable,
+        mode: &Mode,
+        mut f: F,
+    ) -> Result<u64, Error>
+    where
+        F: FnMut(Vec<ParquetValue>) -> Result<(), Error>,
+    {
+        //if mode is private, we dont restrict results
+        let restrict_mode_id = if mode.is_private() {
+            -1
+        } else {
+            Mode::PrivateMatchesAll.to_id() as i32
+        };
+
+        let columns: Vec<String> = table
+            .columns
+            .iter()
+            .map(|c| format!("\"{}\"", c.name))
+            .collect();
+
+        let filter = match table.activity {
+            Some(activity) => format!(
+                "WHERE
+                    exists (select 1 from modes where activity = {0} and mode = ?) AND
+                    not exists (select 1 from modes where activity = {0} and mode = ?)",
+                activity
+            ),
+            None => String::new(),
+        };
+
+        let query = format!(
+            "SELECT {} FROM \"{}\" {} ORDER BY \"{}\".id ASC",
+            columns.join(", "),
+            table.name,
+            filter,
+            table.name
+        );
+
+        let mut q = sqlx::query(&query);
+        if table.activity.is_some() {
+            q = q
+                .bind(mode.to_id().to_string())
+                .bind(restrict_mode_id.to_string());
+        }
+
+        let mut rows = q.fetch(&self.db);
+
+        let mut count: u64 = 0;
+        while let Some(row) = rows.try_next().await? {
+            let mut values: Vec<ParquetValue> =
+                Vec::with_capacity(table.columns.len());
+            for c in table.columns.iter() {
+                let value = match c.kind {
+                    ParquetType::Int64 => row
+                        .try_get_unchecked::<Option<i64>, _>(c.name)?
+                        .map(ParquetValue::Int64),
+                    ParquetType::Double => row
+                        .try_get_unchecked::<Option<f64>, _>(c.name)?
+                        .map(ParquetValue::Double),
+                    ParquetType::Utf8 => row
+                        .try_get_unchecked::<Option<String>, _>(c.name)?
+                        .map(ParquetValue::Utf8),
+                    ParquetType::TimestampMillis => {
+                        match row
+                            .try_get_unchecked::<Option<String>, _>(c.name)?
+                        {
+                            Some(period) => {
+                                let period =
+                                    DateTime::parse_from_rfc3339(&period)?;
+                                Some(ParquetValue::Int64(
+                                    period.timestamp_millis(),
+                                ))
+                            }
+                            None => None,
+                        }
+                    }
+                };
+
+                //the writer returns an error for nulls in required columns
+                values.push(value.unwrap_or(ParquetValue::Null));
+            }
+
+            f(values)?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// streams every stored activity in the specified mode, in the order
+    /// they were stored, calling f with each activity (including teams,
+    /// player performances, weapons and medals). Activities are loaded a
+    /// page at a time so the entire store is never held in memory. Returns
+    /// the number of activities streamed.
+    pub async fn stream_activities<F>(
+        &self,
+        mode: &Mode,
+        manifest: &mut ManifestInterface,
+        mut f: F,
+    ) -> Result<u64, Error>
+    where
+        F: FnMut(CrucibleActivity) -> Result<(), Error>,
+    {
+        //if mode if private, we dont restrict results
+        let restrict_mode_id = if mode.is_private() {
+            -1
+        } else {
+            Mode::PrivateMatchesAll.to_id() as i32
+        };
+
+        let mut count: u64 = 0;
+        let mut last_index: i64 = 0;
+        loop {
+            //page through by row id, so each page is a quick index lookup
+            //regardless of how far into the store we are
+            let rows = sqlx::query(
+                r#"
+                SELECT
+                    activity.id as activity_index_id,
+                    activity.activity_id,
+                    activity.period,
+                    activity.mode as activity_mode,
+                    activity.director_activity_hash,
+                    activity.reference_id,
+                    activity.platform
+                FROM
+                    activity
+                WHERE
+                    activity.id > ? AND
+                    exists (select 1 from modes where activity = activity.id and mode = ?) AND
+                    not exists (select 1 from modes where activity = activity.id and mode = ?)
+                ORDER BY
+                    activity.id ASC
+                LIMIT ?
+                "#,
+            )
+            .bind(last_index)
+            .bind(mode.to_id().to_string())
+            .bind(restrict_mode_id.to_string())
+            .bind(STREAM_PAGE_SIZE)
+            .fetch_all(&self.db)
+            .timed(TimingCategory::Database)
+            .await?;
+
+            if rows.is_empty() {
+                break;
+            }
+
+            for row in &rows {
+                last_index = row.try_get("activity_index_id")?;
+
+                let activity =
+                    self.populate_activity_data(row, manifest).await?;
+                f(activity)?;
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// runs a read only sql query against the store, and returns all of the
+    /// rows it returns. Queries can use the views in storequery::QUERY_VIEWS
+    /// as well as the store tables. Queries which try to modify the store
+    /// return an error.
+    pub async fn query(&self, sql: &str) -> Result<QueryResult, Error> {
+        //run on a seperate read only connection, so the query can never
+        //modify the store, regardless of what it contains
+        let mut conn = SqliteConnectOptions::from_str(&self.path)?
+            .read_only(true)
+            .connect()
+            .await?;
+
+        let result = ActivityStoreInterface::_query(&mut conn, sql).await;
+        conn.close().await?;
+
+        result
+    }
+
+    async fn _query(
+        conn: &mut SqliteConnection,
+        sql: &str,
+    ) -> Result<QueryResult, Error> {
+        //views are temporary, so they are only created for this connection
+        for v in QUERY_VIEWS {
+            sqlx::query(v.sql)
+                .execute(&mut *conn)
+                .timed(TimingCategory::Database)
+                .await?;
+        }
+
+        sqlx::query("PRAGMA query_only = ON;")
+            .execute(&mut *conn)
+            .timed(TimingCategory::Database)
+            .await?;
+
+        let rows = sqlx::query(sql)
+            .fetch_all(&mut *conn)
+            .timed(TimingCategory::Database)
+            .await?;
+
+        let mut out = QueryResult::default();
+        if let Some(row) = rows.first() {
+            out.columns =
+                row.columns().iter().map(|c| c.name().to_string()).collect();
+        }
+
+        for row in &rows {
+            let mut values = Vec::with_capacity(row.len());
+            for i in 0..row.len() {
+                values.push(Self::parse_query_value(row, i)?);
+            }
+            out.rows.push(values);
+        }
+
+        Ok(out)
+    }
+
+    //converts a column to a value based on the type of the value stored in
+    //it, since sqlite columns can hold values of any type
+    fn parse_query_value(
+        row: &sqlx::sqlite::SqliteRow,
+        index: usize,
+    ) -> Result<QueryValue, Error> {
+        let type_name = {
+            let raw = row.try_get_raw(index)?;
+            if raw.is_null() {
+                return Ok(QueryValue::Null);
+            }
+
+            raw.type_info().name().to_string()
+        };
+
+        let value = match &type_name[..] {
+            "INTEGER" => QueryValue::Integer(row.try_get_unchecked(index)?),
+            "REAL" => QueryValue::Real(row.try_get_unchecked(index)?),
+            "BLOB" => QueryValue::Blob(row.try_get_unchecked(index)?),
+            _ => QueryValue::Text(row.try_get_unchecked(index)?),
+        };
+
+        Ok(value)
+    }
+
+    /// exports all of the members, characters and activities (with their
+    /// teams, player stats, weapons and medals) in the store to a json file
+    /// at path. See storeexport for the structure of the file. Activities are
+    /// read and written a page at a time, so large stores can be exported
+    /// without holding them in memory. Returns the number of activities
+    /// exported.
+    pub async fn export_json(&self, path: &Path) -> Result<u64, Error> {
+        let mut out = BufWriter::new(File::create(path)?);
+
+        write!(
+            out,
+            "{{\"format\":{},\"format_version\":{},\"schema_version\":{},\"exported_at\":{},\"members\":[",
+            serde_json::to_string(STORE_EXPORT_FORMAT)?,
+            STORE_EXPORT_FORMAT_VERSION,
+            DB_SCHEMA_VERSION,
+            serde_json::to_string(&Utc::now().to_rfc3339())?,
+        )?;
+
+        let members = self.retrieve_export_members().await?;
+        for (i, m) in members.iter().enumerate() {
+            if i > 0 {
+                write!(out, ",")?;
+            }
+            serde_json::to_writer(&mut out, m)?;
+        }
+
+        write!(out, "],\"activities\":[")?;
+
+        let mut count: u64 = 0;
+        let mut last_index: i64 = 0;
+        loop {
+            let rows = sqlx::query_as::<_, ActivityRow>(
+                r#"
+                SELECT
+                    id as activity_index_id,
+                    activity_id,
+                    period,
+                    mode as activity_mode,
+                    platform,
+                    director_activity_hash,
+                    reference_id
+                FROM
+                    activity
+                WHERE
+                    id > ?
+                ORDER BY
+                    id ASC
+                LIMIT ?
+                "#,
+            )
+            .bind(last_index)
+            .bind(STREAM_PAGE_SIZE)
+            .fetch_all(&self.db)
+            .timed(TimingCategory::Database)
+            .await?;
+
+            if rows.is_empty() {
+                break;
+            }
+
+            for row in rows {
+                last_index = row.activity_index_id;
+
+                let activity = self.retrieve_export_activity(row).await?;
+
+                if count > 0 {
+                    write!(out, ",")?;
+                }
+                serde_json::to_writer(&mut out, &activity)?;
+                count += 1;
+            }
+        }
+
+        write!(out, "]}}")?;
+        out.flush()?;
+
+        Ok(count)
+    }
+
+    async fn retrieve_export_members(
+        &self,
+    ) -> Result<Vec<StoreExportMember>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                member.id as member_index_id,
+                member.member_id,
+                member.platform_id,
+                member.display_name,
+                character.character_id,
+                character.class
+            FROM
+                member
+            LEFT JOIN
+                character ON character.member = member.id
+            ORDER BY
+                member.id ASC, character.id ASC
+            "#,
+        )
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut out: Vec<StoreExportMember> = Vec::new();
+        let mut last_index: i64 = -1;
+        for row in &rows {
+            let index: i64 = row.try_get("member_index_id")?;
+            if index != last_index {
+                out.push(StoreExportMember {
+                    member_id: row.try_get("member_id")?,
+                    platform_id: row.try_get("platform_id")?,
+                    display_name: row.try_get("display_name")?,
+                    characters: Vec::new(),
+                });
+                last_index = index;
+            }
+
+            let character_id: Option<String> = row.try_get("character_id")?;
+            if let (Some(character_id), Some(m)) =
+                (character_id, out.last_mut())
+            {
+                m.characters.push(StoreExportCharacter {
+                    character_id,
+                    class: row.try_get("class")?,
+                });
+            }
+        }
+
+        Ok(out)
+    }
+
+    async fn retrieve_export_activity(
+        &self,
+        activity: ActivityRow,
+    ) -> Result<StoreExportActivity, Error> {
+        let mode_rows = sqlx::query(
+            r#"
+            SELECT mode FROM modes WHERE activity = ? ORDER BY mode ASC
+            "#,
+        )
+        .bind(activity.activity_index_id)
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut modes: Vec<u32> = Vec::with_capacity(mode_rows.len());
+        for row in &mode_rows {
+            modes.push(row.try_get("mode")?);
+        }
+
+        let teams = sqlx::query_as::<_, TeamResultRow>(
+            r#"
+            SELECT * FROM team_result WHERE activity = ? ORDER BY team_id ASC
+            "#,
+        )
+        .bind(activity.activity_index_id)
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let player_rows = sqlx::query(
+            r#"
+            SELECT
+                *,
+                character_activity_stats.id as character_activity_stats_index
+            FROM
+                character_activity_stats
+            INNER JOIN
+                character on character_activity_stats.character = character.id,
+                member on character.member = member.id
+            WHERE
+                activity = ?
+            ORDER BY
+                character_activity_stats.id ASC
+            "#,
+        )
+        .bind(activity.activity_index_id)
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut players: Vec<StoreExportPlayer> =
+            Vec::with_capacity(player_rows.len());
+        for row in &player_rows {
+            let stats = CharacterActivityStatsRow::from_row(row)?;
+            let player = PlayerRow::from_row(row)?;
+
+            let weapons = sqlx::query_as::<_, WeaponResultRow>(
+                r#"
+                SELECT * FROM weapon_result WHERE character_activity_stats = ?
+                ORDER BY id ASC
+                "#,
+            )
+            .bind(stats.character_activity_stats_index)
+            .fetch_all(&self.db)
+            .timed(TimingCategory::Database)
+            .await?;
+
+            let medals = sqlx::query_as::<_, MedalResultRow>(
+                r#"
+                SELECT * FROM medal_result WHERE character_activity_stats = ?
+                ORDER BY id ASC
+                "#,
+            )
+            .bind(stats.character_activity_stats_index)
+            .fetch_all(&self.db)
+            .timed(TimingCategory::Database)
+            .await?;
+
+            players.push(StoreExportPlayer {
+                member_id: player.member_id,
+                character_id: player.character_id,
+                stats,
+                weapons,
+                medals,
+            });
+        }
+
+        Ok(StoreExportActivity {
+            activity_id: activity.activity_id,
+            period: activity.period,
+            mode: activity.activity_mode,
+            modes,
+            platform: activity.platform,
+            director_activity_hash: activity.director_activity_hash,
+            reference_id: activity.reference_id,
+            teams,
+            players,
+        })
+    }
+
+    /// returns the modes stored in the activity store which are not known
+    /// by this version of dcli, along with the number of activities for each.
+    /// These are normally new modes added by Bungie.
+    pub async fn retrieve_unknown_modes(
+        &self,
+    ) -> Result<Vec<ModeActivityCount>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                mode_id,
+                count(DISTINCT activity_id) as activities
+            FROM
+            (
+                SELECT mode as mode_id, activity as activity_id FROM modes
+                UNION
+                SELECT mode as mode_id, id as activity_id FROM activity
+            )
+            GROUP BY
+                mode_id
+            ORDER BY
+                mode_id ASC
+            "#,
+        )
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            let mode_id: u32 = row.try_get_unchecked("mode_id")?;
+            let mode = Mode::from_id(mode_id)?;
+
+            if !mode.is_unknown() {
+                continue;
+            }
+
+            out.push(ModeActivityCount {
+                mode,
+                activities: row.try_get_unchecked("activities")?,
+            });
+        }
+
+        Ok(out)
+    }
+
+    /// saves the combat ratings (keyed by character id) of players in the
+    /// specified activity. Existing ratings for the activity are replaced.
+    /// Does nothing if reading from a snapshot of the store.
+    pub async fn save_combat_ratings(
+        &self,
+        activity_id: i64,
+        ratings: &HashMap<String, f32>,
+    ) -> Result<(), Error> {
+        if self.snapshot_path.is_some() {
+            print_verbose(
+                "Reading from store snapshot. Not saving combat ratings.",
+                self.verbose,
+            );
+            return Ok(());
+        }
+
+        let recorded_at = Utc::now().to_rfc3339();
+        for (character_id, rating) in ratings {
+            sqlx::query(
+                r#"
+                INSERT OR REPLACE INTO "main"."combat_rating"("activity_id", "character_id", "rating", "recorded_at")
+                VALUES (?, ?, ?, ?)
+                "#,
+            )
+            .bind(activity_id)
+            .bind(character_id)
+            .bind(rating)
+            .bind(&recorded_at)
+            .execute(&self.db)
+            .timed(TimingCategory::Database)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// sets a local alias for the member, replacing any existing alias. Aliases
+    /// are displayed alongside the player's name in reports, and can be used in
+    /// place of a member id. If another member has the alias, it is moved to
+    /// this member.
+    pub async fn set_player_alias(
+        &self,
+        member_id: &str,
+        alias: &str,
+    ) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO "main"."player_alias"("member_id", "alias", "created_at")
+            VALUES (?, ?, ?)
+            "#,
+        )
+        .bind(member_id)
+        .bind(alias)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        Ok(())
+    }
+
+    /// removes the alias for the member. Returns false if the member did not
+    /// have an alias.
+    pub async fn remove_player_alias(
+        &self,
+        member_id: &str,
+    ) -> Result<bool, Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM "main"."player_alias" WHERE member_id = ?
+            "#,
+        )
+        .bind(member_id)
+        .execute(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// returns all aliases, keyed by member id
+    pub async fn retrieve_player_aliases(
+        &self,
+    ) -> Result<HashMap<String, String>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT member_id, alias FROM "main"."player_alias"
+            "#,
+        )
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut out = HashMap::new();
+        for row in &rows {
+            out.insert(
+                row.try_get_unchecked("member_id")?,
+                row.try_get_unchecked("alias")?,
+            );
+        }
+
+        Ok(out)
+    }
+
+    /// resolves a member id from a member id, player alias or display name.
+    /// Aliases are checked first (case insensitive), then display names of
+    /// players in stored activities. Since display names are not unique, the
+    /// name can include the platform (i.e. name:steam), and an
+    /// Error::AmbiguousPlayerName listing the matching players is returned if
+    /// more than one player has the name. If nothing matches, the value is
+    /// returned unchanged, so this can be called with a member id.
+    pub async fn resolve_member_id(
+        &self,
+        member_id_or_alias: &str,
+    ) -> Result<String, Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT member_id FROM "main"."player_alias"
+            WHERE alias = ? COLLATE NOCASE
+            "#,
+        )
+        .bind(member_id_or_alias)
+        .fetch_optional(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        if let Some(e) = row {
+            return Ok(e.try_get_unchecked("member_id")?);
+        }
+
+        //already a member id
+        if member_id_or_alias.chars().all(|c| c.is_ascii_digit()) {
+            return Ok(member_id_or_alias.to_string());
+        }
+
+        //name:platform
+        let (name, platform) = match member_id_or_alias.rsplit_once(':') {
+            Some((n, p)) => match Platform::from_str(p) {
+                Ok(e) => (n, Some(e)),
+                Err(_e) => (member_id_or_alias, None),
+            },
+            None => (member_id_or_alias, None),
+        };
+
+        let candidates =
+            self.find_members_by_name(name, platform.as_ref()).await?;
+
+        match candidates.len() {
+            0 => Ok(member_id_or_alias.to_string()),
+            1 => Ok(candidates[0].member_id.clone()),
+            _ => {
+                let list: Vec<String> =
+                    candidates.iter().map(|c| c.to_string()).collect();
+                Err(Error::AmbiguousPlayerName {
+                    description: format!(
+                        "Matching players:\n{}",
+                        list.join("\n")
+                    ),
+                })
+            }
+        }
+    }
+
+    /// returns players in stored activities with the display name (case
+    /// insensitive), optionally restricted to a platform. Sorted by the
+    /// number of stored activities the player was in, most first.
+    pub async fn find_members_by_name(
+        &self,
+        display_name: &str,
+        platform: Option<&Platform>,
+    ) -> Result<Vec<MemberCandidate>, Error> {
+        let platform_id: i64 = match platform {
+            Some(p) => p.to_id() as i64,
+            None => -1,
+        };
+
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                member.member_id,
+                member.platform_id,
+                member.display_name,
+                count(character_activity_stats.id) as activities
+            FROM
+                member
+            LEFT JOIN
+                character ON character.member = member.id
+            LEFT JOIN
+                character_activity_stats
+                    ON character_activity_stats.character = character.id
+            WHERE
+                member.display_name = ? COLLATE NOCASE AND
+                (? = -1 OR member.platform_id = ?)
+            GROUP BY
+                member.id
+            ORDER BY
+                activities DESC
+            "#,
+        )
+        .bind(display_name)
+        .bind(platform_id)
+        .bind(platform_id)
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut out: Vec<MemberCandidate> = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let platform_id: u32 = row.try_get_unchecked("platform_id")?;
+            out.push(MemberCandidate {
+                member_id: row.try_get("member_id")?,
+                platform: Platform::from_id(platform_id),
+                display_name: row.try_get("display_name")?,
+                activities: row.try_get_unchecked("activities")?,
+            });
+        }
+
+        Ok(out)
+    }
+
+    /// returns the average recorded combat rating of the other players in
+    /// the member's activities, for each day in the time period that has
+    /// recorded ratings. Sorted by day, oldest first.
+    pub async fn retrieve_lobby_rating_trend(
+        &self,
+        member_id: &str,
+        mode: &Mode,
+        time_period: &DateTimePeriod,
+    ) -> Result<Vec<LobbyRatingPeriod>, Error> {
+        let restrict_mode_id = if mode.is_private() {
+            -1
+        } else {
+            Mode::PrivateMatchesAll.to_id() as i32
+        };
+
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                date(activity.period) as day,
+                count(DISTINCT activity.id) as activities,
+                count(*) as rated_players,
+                avg(combat_rating.rating) as average_rating
+            FROM
+                character_activity_stats as mine
+            INNER JOIN
+                character as my_character ON mine.character = my_character.id,
+                member ON my_character.member = member.id,
+                activity ON mine.activity = activity.id,
+                character_activity_stats as other ON other.activity = activity.id,
+                character as other_character ON other.character = other_character.id,
+                combat_rating ON combat_rating.activity_id = activity.activity_id
+                    AND combat_rating.character_id = other_character.character_id
+            WHERE
+                member.member_id = ? AND
+                other.id != mine.id AND
+                activity.period > ? AND
+                activity.period < ? AND
+                exists (select 1 from modes where activity = activity.id and mode = ?) AND
+                not exists (select 1 from modes where activity = activity.id and mode = ?)
+            GROUP BY
+                day
+            ORDER BY
+                day ASC
+            "#,
+        )
+        .bind(member_id.to_string())
+        .bind(time_period.get_start().to_rfc3339())
+        .bind(time_period.get_end().to_rfc3339())
+        .bind(mode.to_id().to_string())
+        .bind(restrict_mode_id.to_string())
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            let day: String = row.try_get_unchecked("day")?;
+            let day = NaiveDate::parse_from_str(&day, "%Y-%m-%d")?;
+
+            out.push(LobbyRatingPeriod {
+                day,
+                activities: row.try_get_unchecked("activities")?,
+                rated_players: row.try_get_unchecked("rated_players")?,
+                average_rating: row.try_get_unchecked("average_rating")?,
+            });
+        }
+
+        Ok(out)
+    }
+
+    /// returns the member's competitive activities (across all characters)
+    /// for the time period in chronological order, annotated with their
+    /// glory and rank after the activity. Glory is recorded when syncing, and
+    /// inferred from the win / loss sequence for other activities (see
+    /// competitive::infer_progress).
+    pub async fn retrieve_competitive_history(
+        &self,
+        member_id: &str,
+        time_period: &DateTimePeriod,
+        manifest: &mut ManifestInterface,
+    ) -> Result<Vec<CompetitiveMatch>, Error> {
+        //all activities are loaded, so values before the time period can be
+        //used to infer values within it
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                activity.activity_id,
+                activity.period,
+                activity.mode as activity_mode,
+                activity.platform,
+                activity.director_activity_hash,
+                activity.reference_id,
+                activity.id as activity_index_id,
+                character_activity_stats.standing,
+                competitive_progress.current_progress
+            FROM
+                character_activity_stats
+            INNER JOIN
+                activity ON character_activity_stats.activity = activity.id,
+                character ON character_activity_stats.character = character.id,
+                member ON member.id = character.member
+            LEFT JOIN
+                competitive_progress ON competitive_progress.activity_id = activity.activity_id
+                    AND competitive_progress.member_id = member.member_id
+                    AND competitive_progress.progression_hash = ?
+            WHERE
+                member.member_id = ? AND
+                period < ? AND
+                exists (select 1 from modes where activity = activity.id and mode IN (?, ?))
+            ORDER BY
+                activity.period ASC
+            "#,
+        )
+        .bind(GLORY_PROGRESSION_HASH as i64)
+        .bind(member_id)
+        .bind(time_period.get_end().to_rfc3339())
+        .bind(COMPETITIVE_MODES[0].to_id().to_string())
+        .bind(COMPETITIVE_MODES[1].to_id().to_string())
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut results: Vec<CompetitiveResult> =
+            Vec::with_capacity(rows.len());
+        for row in &rows {
+            let standing: u32 = row.try_get_unchecked("standing")?;
+            let progress: Option<i64> =
+                row.try_get_unchecked("current_progress")?;
+
+            results.push(CompetitiveResult {
+                standing: Standing::from_value(standing),
+                recorded_progress: progress.map(|e| e as u32),
+            });
+        }
+
+        let estimates = infer_progress(&results);
+
+        //older manifests may not include progression definitions, in which
+        //case we just dont display rank names
+        let definition = manifest
+            .get_progression_definition(GLORY_PROGRESSION_HASH)
+            .await
+            .unwrap_or(None);
+
+        let start = time_period.get_start().to_rfc3339();
+        let mut out: Vec<CompetitiveMatch> = Vec::new();
+        for ((row, result), estimate) in
+            rows.iter().zip(results.iter()).zip(estimates.iter())
+        {
+            let activity = ActivityRow::from_row(row)?;
+            if activity.period <= start {
+                continue;
+            }
+
+            let rank = match (&definition, estimate.progress) {
+                (Some(d), Some(p)) => {
+                    d.step_for_progress(p).map(|e| e.step_name.to_string())
+                }
+                _ => None,
+            };
+
+            out.push(CompetitiveMatch {
+                activity_detail: self
+                    .parse_activity(manifest, &activity)
+                    .await?,
+                standing: result.standing,
+                streak: estimate.streak,
+                progress: estimate.progress,
+                progress_change: estimate.progress_change,
+                rank,
+                inferred: estimate.inferred,
+            });
+        }
+
+        Ok(out)
+    }
+
+    /// returns the platforms of opponents the member faced in the specified
+    /// mode and time period (across all characters), sorted by number of
+    /// opponents. In modes without teams, all other players are opponents.
+    /// returns clears, deaths, durations and fireteam sizes for each pve
+    /// activity the member has played in the mode and time period, with the
+    /// most cleared activities first. PvE activities are only stored when
+    /// syncing with pve enabled (see set_sync_pve).
+    pub async fn retrieve_pve_activity_stats(
+        &self,
+        member_id: &str,
+        character_selection: &CharacterClassSelection,
+        platform: &Platform,
+        mode: &Mode,
+        time_period: &DateTimePeriod,
+        manifest: &mut ManifestInterface,
+    ) -> Result<Vec<PveActivityStats>, Error> {
+        //empty matches all of the member's characters
+        let character_id =
+            if character_selection == &CharacterClassSelection::All {
+                "".to_string()
+            } else {
+                self.retrieve_character_selection_id(
+                    member_id,
+                    platform,
+                    character_selection,
+                )
+                .await?
+            };
+
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                activity.reference_id as reference_id,
+                count(DISTINCT activity.id) as activities,
+                sum(CASE WHEN character_activity_stats.completed = 1 AND
+                    character_activity_stats.completion_reason = ? THEN 1 ELSE 0 END) as clears,
+                sum(character_activity_stats.kills) as kills,
+                sum(character_activity_stats.deaths) as deaths,
+                sum(character_activity_stats.time_played_seconds) as time_played_seconds,
+                min(CASE WHEN character_activity_stats.completed = 1 AND
+                    character_activity_stats.completion_reason = ?
+                    THEN character_activity_stats.activity_duration_seconds END) as fastest_clear_seconds,
+                sum((SELECT count(*) FROM character_activity_stats as fireteam
+                    WHERE fireteam.activity = activity.id)) as fireteam_members
+            FROM
+                character_activity_stats
+            INNER JOIN
+                character ON character_activity_stats.character = character.id,
+                member ON character.member = member.id,
+                activity ON character_activity_stats.activity = activity.id
+            WHERE
+                member.member_id = ? AND
+                (? = '' OR character.character_id = ?) AND
+                activity.period > ? AND
+                activity.period < ? AND
+                exists (select 1 from modes where activity = activity.id and mode = ?)
+            GROUP BY
+                activity.reference_id
+            ORDER BY
+                clears DESC, activities DESC
+            "#,
+        )
+        .bind(CompletionReason::ObjectiveComplete.to_id())
+        .bind(CompletionReason::ObjectiveComplete.to_id())
+        .bind(member_id.to_string())
+        .bind(&character_id)
+        .bind(&character_id)
+        .bind(time_period.get_start().to_rfc3339())
+        .bind(time_period.get_end().to_rfc3339())
+        .bind(mode.to_id().to_string())
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            let reference_id: u32 = row.try_get_unchecked("reference_id")?;
+            let activity_name =
+                match manifest.get_activity_definition(reference_id).await? {
+                    Some(e) => e.display_properties.name,
+                    None => "Unknown".to_string(),
+                };
+
+            out.push(PveActivityStats {
+                reference_id,
+                activity_name,
+                activities: row.try_get_unchecked("activities")?,
+                clears: row.try_get_unchecked("clears")?,
+                kills: row.try_get_unchecked("kills")?,
+                deaths: row.try_get_unchecked("deaths")?,
+                time_played_seconds: row
+                    .try_get_unchecked("time_played_seconds")?,
+                fastest_clear_seconds: row
+                    .try_get_unchecked("fastest_clear_seconds")?,
+                fireteam_members: row.try_get_unchecked("fireteam_members")?,
+            });
+        }
+
+        Ok(out)
+    }
+
+    pub async fn retrieve_opponent_platform_stats(
+        &self,
+        member_id: &str,
+        mode: &Mode,
+        time_period: &DateTimePeriod,
+    ) -> Result<Vec<OpponentPlatformStats>, Error> {
+        let restrict_mode_id = if mode.is_private() {
+            -1
+        } else {
+            Mode::PrivateMatchesAll.to_id() as i32
+        };
+
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                opponent_member.platform_id as platform_id,
+                count(*) as opponents,
+                count(DISTINCT activity.id) as activities,
+                count(DISTINCT CASE WHEN mine.standing = ? THEN activity.id END) as wins
+            FROM
+                character_activity_stats as mine
+            INNER JOIN
+                character as my_character ON mine.character = my_character.id,
+                member as my_member ON my_character.member = my_member.id,
+                activity ON mine.activity = activity.id,
+                character_activity_stats as opponent ON opponent.activity = activity.id,
+                character as opponent_character ON opponent.character = opponent_character.id,
+                member as opponent_member ON opponent_character.member = opponent_member.id
+            WHERE
+                my_member.member_id = ? AND
+                opponent.id != mine.id AND
+                (
+                    opponent.team != mine.team OR
+                    not exists (select 1 from team_result where team_result.activity = activity.id)
+                ) AND
+                activity.period > ? AND
+                activity.period < ? AND
+                exists (select 1 from modes where activity = activity.id and mode = ?) AND
+                not exists (select 1 from modes where activity = activity.id and mode = ?)
+            GROUP BY
+                opponent_member.platform_id
+            ORDER BY
+                opponents DESC
+            "#,
+        )
+        .bind(Standing::Victory as i32)
+        .bind(member_id.to_string())
+        .bind(time_period.get_start().to_rfc3339())
+        .bind(time_period.get_end().to_rfc3339())
+        .bind(mode.to_id().to_string())
+        .bind(restrict_mode_id.to_string())
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            let platform_id: u32 = row.try_get_unchecked("platform_id")?;
+            out.push(OpponentPlatformStats {
+                platform: Platform::from_id(platform_id),
+                opponents: row.try_get_unchecked("opponents")?,
+                activities: row.try_get_unchecked("activities")?,
+                wins: row.try_get_unchecked("wins")?,
+            });
+        }
+
+        Ok(out)
+    }
+
+    /// retrieves the member's games, wins, kills, deaths and assists on each
+    /// map, most played first. Includes all of the member's characters. Map
+    /// names are resolved with the manifest, and maps with more than one
+    /// activity definition (i.e. the same map in different playlists) are
+    /// combined.
+    pub async fn retrieve_map_stats(
+        &self,
+        member_id: &str,
+        mode: &Mode,
+        time_period: &DateTimePeriod,
+        manifest: &mut ManifestInterface,
+    ) -> Result<Vec<MapStats>, Error> {
+        let restrict_mode_id = if mode.is_private() {
+            -1
+        } else {
+            Mode::PrivateMatchesAll.to_id() as i32
+        };
+
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                activity.reference_id as reference_id,
+                count(*) as activities,
+                sum(CASE WHEN character_activity_stats.standing = ? THEN 1 ELSE 0 END) as wins,
+                sum(character_activity_stats.kills) as kills,
+                sum(character_activity_stats.deaths) as deaths,
+                sum(character_activity_stats.assists) as assists
+            FROM
+                character_activity_stats
+            INNER JOIN
+                character ON character_activity_stats.character = character.id,
+                member ON character.member = member.id,
+                activity ON character_activity_stats.activity = activity.id
+            WHERE
+                member.member_id = ? AND
+                activity.period > ? AND
+                activity.period < ? AND
+                exists (select 1 from modes where activity = activity.id and mode = ?) AND
+                not exists (select 1 from modes where activity = activity.id and mode = ?)
+            GROUP BY
+                activity.reference_id
+            "#,
+        )
+        .bind(Standing::Victory as i32)
+        .bind(member_id.to_string())
+        .bind(time_period.get_start().to_rfc3339())
+        .bind(time_period.get_end().to_rfc3339())
+        .bind(mode.to_id().to_string())
+        .bind(restrict_mode_id.to_string())
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut out: Vec<MapStats> = Vec::new();
+        for row in rows {
+            let reference_id: i64 = row.try_get("reference_id")?;
+            let map_name = match manifest
+                .get_activity_definition(reference_id as u32)
+                .await?
+            {
+                Some(e) => e.display_properties.name,
+                None => "Unknown".to_string(),
+            };
+
+            let index = match out.iter().position(|m| m.map_name == map_name) {
+                Some(e) => e,
+                None => {
+                    out.push(MapStats {
+                        map_name,
+                        ..Default::default()
+                    });
+                    out.len() - 1
+                }
+            };
+
+            let m = &mut out[index];
+            m.activities += row.try_get_unchecked::<u32, _>("activities")?;
+            m.wins += row.try_get_unchecked::<u32, _>("wins")?;
+            m.kills += row.try_get_unchecked::<u32, _>("kills")?;
+            m.deaths += row.try_get_unchecked::<u32, _>("deaths")?;
+            m.assists += row.try_get_unchecked::<u32, _>("assists")?;
+        }
+
+        out.sort_by(|a, b| {
+            b.activities
+                .cmp(&a.activities)
+                .then_with(|| a.map_name.cmp(&b.map_name))
+        });
+
+        Ok(out)
+    }
+
+    /// retrieves the number of opponents of each class the member faced in
+    /// each activity. Includes all of the member's characters. Subclasses
+    /// are not stored, so only classes are available.
+    pub async fn retrieve_opponent_compositions(
+        &self,
+        member_id: &str,
+        mode: &Mode,
+        time_period: &DateTimePeriod,
+    ) -> Result<Vec<ActivityOpponentComposition>, Error> {
+        let restrict_mode_id = if mode.is_private() {
+            -1
+        } else {
+            Mode::PrivateMatchesAll.to_id() as i32
+        };
+
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                activity.activity_id as activity_id,
+                max(CASE WHEN mine.standing = ? THEN 1 ELSE 0 END) as won,
+                sum(CASE WHEN opponent_character.class = ? THEN 1 ELSE 0 END) as titans,
+                sum(CASE WHEN opponent_character.class = ? THEN 1 ELSE 0 END) as hunters,
+                sum(CASE WHEN opponent_character.class = ? THEN 1 ELSE 0 END) as warlocks
+            FROM
+                character_activity_stats as mine
+            INNER JOIN
+                character as my_character ON mine.character = my_character.id,
+                member as my_member ON my_character.member = my_member.id,
+                activity ON mine.activity = activity.id,
+                character_activity_stats as opponent ON opponent.activity = activity.id,
+                character as opponent_character ON opponent.character = opponent_character.id
+            WHERE
+                my_member.member_id = ? AND
+                opponent.id != mine.id AND
+                (
+                    opponent.team != mine.team OR
+                    not exists (select 1 from team_result where team_result.activity = activity.id)
+                ) AND
+                activity.period > ? AND
+                activity.period < ? AND
+                exists (select 1 from modes where activity = activity.id and mode = ?) AND
+                not exists (select 1 from modes where activity = activity.id and mode = ?)
+            GROUP BY
+                activity.id
+            ORDER BY
+                activity.period ASC
+            "#,
+        )
+        .bind(Standing::Victory as i32)
+        .bind(CharacterClass::Titan.to_id())
+        .bind(CharacterClass::Hunter.to_id())
+        .bind(CharacterClass::Warlock.to_id())
+        .bind(member_id.to_string())
+        .bind(time_period.get_start().to_rfc3339())
+        .bind(time_period.get_end().to_rfc3339())
+        .bind(mode.to_id().to_string())
+        .bind(restrict_mode_id.to_string())
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            let won: i32 = row.try_get_unchecked("won")?;
+            out.push(ActivityOpponentComposition {
+                activity_id: row.try_get_unchecked("activity_id")?,
+                won: won == 1,
+                composition: OpponentComposition {
+                    titans: row.try_get_unchecked("titans")?,
+                    hunters: row.try_get_unchecked("hunters")?,
+                    warlocks: row.try_get_unchecked("warlocks")?,
+                },
+            });
+        }
+
+        Ok(out)
+    }
+
+    /// retrieves the activities the member played in along with another
+    /// player, and whether they were teammates or opponents. Includes all
+    /// of both players' characters. Use HeadToHeadStats to summarize them.
+    pub async fn retrieve_games_with_player(
+        &self,
+        member_id: &str,
+        other_member_id: &str,
+        mode: &Mode,
+        time_period: &DateTimePeriod,
+    ) -> Result<Vec<SharedActivity>, Error> {
+        let restrict_mode_id = if mode.is_private() {
+            -1
+        } else {
+            Mode::PrivateMatchesAll.to_id() as i32
+        };
+
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                activity.activity_id as activity_id,
+                activity.period as period,
+                activity.mode as activity_mode,
+                mine.standing as standing,
+                mine.kills as kills,
+                mine.deaths as deaths,
+                other.kills as other_kills,
+                other.deaths as other_deaths,
+                CASE WHEN other.team = mine.team AND
+                    exists (select 1 from team_result where team_result.activity = activity.id)
+                    THEN 1 ELSE 0 END as teammate
+            FROM
+                character_activity_stats as mine
+            INNER JOIN
+                character as my_character ON mine.character = my_character.id,
+                member as my_member ON my_character.member = my_member.id,
+                activity ON mine.activity = activity.id,
+                character_activity_stats as other ON other.activity = activity.id,
+                character as other_character ON other.character = other_character.id,
+                member as other_member ON other_character.member = other_member.id
+            WHERE
+                my_member.member_id = ? AND
+                other_member.member_id = ? AND
+                other.id != mine.id AND
+                activity.period > ? AND
+                activity.period < ? AND
+                exists (select 1 from modes where activity = activity.id and mode = ?) AND
+                not exists (select 1 from modes where activity = activity.id and mode = ?)
+            ORDER BY
+                activity.period ASC
+            "#,
+        )
+        .bind(member_id.to_string())
+        .bind(other_member_id.to_string())
+        .bind(time_period.get_start().to_rfc3339())
+        .bind(time_period.get_end().to_rfc3339())
+        .bind(mode.to_id().to_string())
+        .bind(restrict_mode_id.to_string())
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            let period: String = row.try_get_unchecked("period")?;
+            let standing: u32 = row.try_get_unchecked("standing")?;
+            let teammate: i32 = row.try_get_unchecked("teammate")?;
+
+            out.push(SharedActivity {
+                activity_id: row.try_get_unchecked("activity_id")?,
+                period: DateTime::parse_from_rfc3339(&period)?
+                    .with_timezone(&Utc),
+                mode: Mode::from_id(row.try_get_unchecked("activity_mode")?)?,
+                relationship: if teammate == 1 {
+                    PlayerRelationship::Teammate
+                } else {
+                    PlayerRelationship::Opponent
+                },
+                won: Standing::from_value(standing) == Standing::Victory,
+                kills: row.try_get_unchecked("kills")?,
+                deaths: row.try_get_unchecked("deaths")?,
+                other_kills: row.try_get_unchecked("other_kills")?,
+                other_deaths: row.try_get_unchecked("other_deaths")?,
+            });
+        }
+
+        Ok(out)
+    }
+
+    pub async fn retrieve_activities_since(
         &self,
         member_id: &str,
-        platform: &Platform,
         character_selection: &CharacterClassSelection,
-    ) -> Result<String, Error> {
-        let api = ApiInterface::new(self.verbose)?;
-        //first, lets get all of the current characters for the member
-        let characters = api
-            .retrieve_characters(member_id, platform)
+        platform: &Platform,
+        mode: &Mode,
+        time_period: &DateTimePeriod,
+        manifest: &mut ManifestInterface,
+    ) -> Result<Option<Vec<CruciblePlayerActivityPerformance>>, Error> {
+        let out = if character_selection == &CharacterClassSelection::All {
+            self.retrieve_activities_for_member_since(
+                member_id,
+                mode,
+                time_period,
+                manifest,
+            )
             .await?
-            .ok_or(Error::NoCharacters)?;
+        } else {
+            let character_id = self
+                .retrieve_character_selection_id(
+                    member_id,
+                    platform,
+                    character_selection,
+                )
+                .await?;
 
-        let out = match character_selection {
-            CharacterClassSelection::All => member_id.to_string(),
-            CharacterClassSelection::Hunter => {
-                match characters.get_by_class_ref(CharacterClass::Hunter) {
-                    Some(e) => e.id.to_string(),
-                    None => return Err(Error::CharacterDoesNotExist),
-                }
-            }
-            CharacterClassSelection::Titan => {
-                match characters.get_by_class_ref(CharacterClass::Titan) {
-                    Some(e) => e.id.to_string(),
-                    None => return Err(Error::CharacterDoesNotExist),
-                }
-            }
-            CharacterClassSelection::Warlock => {
-                match characters.get_by_class_ref(CharacterClass::Warlock) {
-                    Some(e) => e.id.to_string(),
-                    None => return Err(Error::CharacterDoesNotExist),
-                }
+            self.retrieve_activities_for_character(
+                member_id,
+                &character_id,
+                mode,
+                time_period,
+                manifest,
+            )
+            .await?
+        };
+
+        Ok(out)
+    }
+
+    /// streams the member's performances for the mode and time period, most
+    /// recent first, calling f with each one. Rows are loaded a page at a
+    /// time so reports over thousands of activities do not need to hold
+    /// every performance in memory. Returns the number of performances
+    /// streamed.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn stream_activities_since<F>(
+        &self,
+        member_id: &str,
+        character_selection: &CharacterClassSelection,
+        platform: &Platform,
+        mode: &Mode,
+        time_period: &DateTimePeriod,
+        manifest: &mut ManifestInterface,
+        mut f: F,
+    ) -> Result<u64, Error>
+    where
+        F: FnMut(CruciblePlayerActivityPerformance) -> Result<(), Error>,
+    {
+        let character_index = self
+            .retrieve_character_index(member_id, character_selection, platform)
+            .await?;
+
+        //if mode if private, we dont restrict results
+        let restrict_mode_id = if mode.is_private() {
+            -1
+        } else {
+            Mode::PrivateMatchesAll.to_id() as i32
+        };
+
+        let end = time_period.get_end().to_rfc3339();
+
+        let mut count: u64 = 0;
+        let mut last_period = end.clone();
+        let mut last_index: i64 = i64::MAX;
+        loop {
+            //page backwards through (period, row id) so ordering matches
+            //retrieve_activities_since, and each page picks up where the
+            //last one left off
+            let rows = sqlx::query(
+                r#"
+                SELECT
+                    *,
+                    activity.mode as activity_mode,
+                    activity.id as activity_index_id,
+                    character_activity_stats.id as character_activity_stats_index
+                FROM
+                    character_activity_stats
+                INNER JOIN
+                    activity ON character_activity_stats.activity = activity.id,
+                    character on character_activity_stats.character = character.id,
+                    member on member.id = character.member
+                WHERE
+                    member.id = (select id from member where member_id = ?) AND
+                    (? < 0 OR character_activity_stats.character = ?) AND
+                    activity.period > ? AND
+                    activity.period < ? AND
+                    (activity.period < ? OR
+                        (activity.period = ? AND character_activity_stats.id < ?)) AND
+                    exists (select 1 from modes where activity = activity.id and mode = ?) AND
+                    not exists (select 1 from modes where activity = activity.id and mode = ?)
+                ORDER BY
+                    activity.period DESC,
+                    character_activity_stats.id DESC
+                LIMIT ?
+                "#,
+            )
+            .bind(member_id.to_string())
+            .bind(character_index)
+            .bind(character_index)
+            .bind(time_period.get_start().to_rfc3339())
+            .bind(&end)
+            .bind(&last_period)
+            .bind(&last_period)
+            .bind(last_index)
+            .bind(mode.to_id().to_string())
+            .bind(restrict_mode_id.to_string())
+            .bind(STREAM_PAGE_SIZE)
+            .fetch_all(&self.db)
+            .timed(TimingCategory::Database)
+            .await?;
+
+            if rows.is_empty() {
+                break;
             }
-            CharacterClassSelection::LastActive => {
-                match characters.get_last_active_ref() {
-                    Some(e) => e.id.to_string(),
-                    None => return Err(Error::CharacterDoesNotExist),
-                }
+
+            for row in &rows {
+                last_period = row.try_get_unchecked("period")?;
+                last_index = row.try_get("character_activity_stats_index")?;
+
+                let performance = self
+                    .parse_individual_performance_row(manifest, row)
+                    .await?;
+                f(performance)?;
+                count += 1;
             }
+        }
+
+        Ok(count)
+    }
+
+    /// retrieves a single page of the member's performances for the mode and
+    /// time period, most recent first. Skips the first offset performances,
+    /// and returns at most limit. Use count_activities_since to get the total
+    /// number of performances available.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn retrieve_activities_page(
+        &self,
+        member_id: &str,
+        character_selection: &CharacterClassSelection,
+        platform: &Platform,
+        mode: &Mode,
+        time_period: &DateTimePeriod,
+        manifest: &mut ManifestInterface,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<CruciblePlayerActivityPerformance>, Error> {
+        let character_index = self
+            .retrieve_character_index(member_id, character_selection, platform)
+            .await?;
+
+        //if mode if private, we dont restrict results
+        let restrict_mode_id = if mode.is_private() {
+            -1
+        } else {
+            Mode::PrivateMatchesAll.to_id() as i32
+        };
+
+        //order by row id as well as period, so pages are stable when
+        //performances share a period
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                *,
+                activity.mode as activity_mode,
+                activity.id as activity_index_id,
+                character_activity_stats.id as character_activity_stats_index
+            FROM
+                character_activity_stats
+            INNER JOIN
+                activity ON character_activity_stats.activity = activity.id,
+                character on character_activity_stats.character = character.id,
+                member on member.id = character.member
+            WHERE
+                member.id = (select id from member where member_id = ?) AND
+                (? < 0 OR character_activity_stats.character = ?) AND
+                activity.period > ? AND
+                activity.period < ? AND
+                exists (select 1 from modes where activity = activity.id and mode = ?) AND
+                not exists (select 1 from modes where activity = activity.id and mode = ?)
+            ORDER BY
+                activity.period DESC,
+                character_activity_stats.id DESC
+            LIMIT ? OFFSET ?
+            "#,
+        )
+        .bind(member_id.to_string())
+        .bind(character_index)
+        .bind(character_index)
+        .bind(time_period.get_start().to_rfc3339())
+        .bind(time_period.get_end().to_rfc3339())
+        .bind(mode.to_id().to_string())
+        .bind(restrict_mode_id.to_string())
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        self.parse_individual_performance_rows(manifest, &rows)
+            .await
+    }
+
+    /// returns the number of the member's performances for the mode and time
+    /// period, i.e. the total that can be paged through with
+    /// retrieve_activities_page.
+    pub async fn count_activities_since(
+        &self,
+        member_id: &str,
+        character_selection: &CharacterClassSelection,
+        platform: &Platform,
+        mode: &Mode,
+        time_period: &DateTimePeriod,
+    ) -> Result<u64, Error> {
+        let character_index = self
+            .retrieve_character_index(member_id, character_selection, platform)
+            .await?;
+
+        //if mode if private, we dont restrict results
+        let restrict_mode_id = if mode.is_private() {
+            -1
+        } else {
+            Mode::PrivateMatchesAll.to_id() as i32
         };
 
-        Ok(out)
+        let row = sqlx::query(
+            r#"
+            SELECT
+                count(*) as total
+            FROM
+                character_activity_stats
+            INNER JOIN
+                activity ON character_activity_stats.activity = activity.id,
+                character on character_activity_stats.character = character.id,
+                member on member.id = character.member
+            WHERE
+                member.id = (select id from member where member_id = ?) AND
+                (? < 0 OR character_activity_stats.character = ?) AND
+                activity.period > ? AND
+                activity.period < ? AND
+                exists (select 1 from modes where activity = activity.id and mode = ?) AND
+                not exists (select 1 from modes where activity = activity.id and mode = ?)
+            "#,
+        )
+        .bind(member_id.to_string())
+        .bind(character_index)
+        .bind(character_index)
+        .bind(time_period.get_start().to_rfc3339())
+        .bind(time_period.get_end().to_rfc3339())
+        .bind(mode.to_id().to_string())
+        .bind(restrict_mode_id.to_string())
+        .fetch_one(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let total: i64 = row.try_get("total")?;
+        Ok(total as u64)
     }
 
-    pub async fn retrieve_activities_since(
-        &mut self,
+    //returns the row id of the selected character, or -1 if all of the
+    //member's characters are selected
+    /// retrieves the member's kills and precision kills with each weapon,
+    /// summed in the store rather than by loading every activity, sorted by
+    /// kills. activity_count is the number of activities the weapon got a
+    /// kill in. Much faster than aggregating performances for large stores,
+    /// when only weapon stats are needed.
+    pub async fn retrieve_weapon_stats(
+        &self,
         member_id: &str,
         character_selection: &CharacterClassSelection,
         platform: &Platform,
         mode: &Mode,
         time_period: &DateTimePeriod,
         manifest: &mut ManifestInterface,
-    ) -> Result<Option<Vec<CruciblePlayerActivityPerformance>>, Error> {
-        let out = if character_selection == &CharacterClassSelection::All {
-            self.retrieve_activities_for_member_since(
-                member_id,
-                mode,
-                time_period,
-                manifest,
-            )
-            .await?
+    ) -> Result<Vec<WeaponStat>, Error> {
+        let character_index = self
+            .retrieve_character_index(member_id, character_selection, platform)
+            .await?;
+
+        //if mode if private, we dont restrict results
+        let restrict_mode_id = if mode.is_private() {
+            -1
         } else {
-            let character_id = self
-                .retrieve_character_selection_id(
-                    member_id,
-                    platform,
-                    character_selection,
-                )
-                .await?;
+            Mode::PrivateMatchesAll.to_id() as i32
+        };
 
-            self.retrieve_activities_for_character(
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                weapon_result.reference_id as reference_id,
+                sum(weapon_result.kills) as kills,
+                sum(weapon_result.precision_kills) as precision_kills,
+                count(DISTINCT activity.id) as activity_count,
+                group_concat(DISTINCT activity.id) as activity_ids
+            FROM
+                weapon_result
+            INNER JOIN
+                character_activity_stats ON weapon_result.character_activity_stats = character_activity_stats.id,
+                activity ON character_activity_stats.activity = activity.id,
+                character on character_activity_stats.character = character.id,
+                member on member.id = character.member
+            WHERE
+                member.member_id = ? AND
+                (? < 0 OR character_activity_stats.character = ?) AND
+                activity.period > ? AND
+                activity.period < ? AND
+                exists (select 1 from modes where activity = activity.id and mode = ?) AND
+                not exists (select 1 from modes where activity = activity.id and mode = ?)
+            GROUP BY
+                weapon_result.reference_id
+            ORDER BY
+                kills DESC
+            "#,
+        )
+        .bind(member_id.to_string())
+        .bind(character_index)
+        .bind(character_index)
+        .bind(time_period.get_start().to_rfc3339())
+        .bind(time_period.get_end().to_rfc3339())
+        .bind(mode.to_id().to_string())
+        .bind(restrict_mode_id.to_string())
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
+        .await?;
+
+        let mut out: Vec<WeaponStat> = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let reference_id: i64 = row.try_get("reference_id")?;
+            let kills: u32 = row.try_get_unchecked("kills")?;
+            let precision_kills: u32 =
+                row.try_get_unchecked("precision_kills")?;
+            let activity_ids: String = row.try_get("activity_ids")?;
+
+            out.push(WeaponStat {
+                weapon: Self::parse_weapon_item(manifest, reference_id as u32)
+                    .await?,
+                kills,
+                precision_kills,
+                precision_kills_percent: calculate_percent(
+                    precision_kills,
+                    kills,
+                ),
+                activity_count: row.try_get_unchecked("activity_count")?,
+                activity_ids: activity_ids
+                    .split(',')
+                    .filter_map(|e| e.parse().ok())
+                    .collect(),
+            });
+        }
+
+        Ok(out)
+    }
+
+    async fn retrieve_character_index(
+        &self,
+        member_id: &str,
+        character_selection: &CharacterClassSelection,
+        platform: &Platform,
+    ) -> Result<i64, Error> {
+        if character_selection == &CharacterClassSelection::All {
+            return Ok(-1);
+        }
+
+        let character_id = self
+            .retrieve_character_selection_id(
                 member_id,
-                &character_id,
-                mode,
-                time_period,
-                manifest,
+                platform,
+                character_selection,
             )
-            .await?
-        };
+            .await?;
 
-        Ok(out)
+        Ok(self.get_character_row_id(member_id, &character_id).await? as i64)
     }
 
     pub async fn retrieve_activities_for_member_since(
-        &mut self,
+        &self,
         member_id: &str,
         mode: &Mode,
         time_period: &DateTimePeriod,
@@ -1287,7 +6335,8 @@ impl ActivityStoreInterface {
         .bind(time_period.get_end().to_rfc3339())
         .bind(mode.to_id().to_string())
         .bind(restrict_mode_id.to_string())
-        .fetch_all(&mut self.db)
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
         .await?;
 
         if activity_rows.is_empty() {
@@ -1302,7 +6351,7 @@ impl ActivityStoreInterface {
     }
 
     pub async fn retrieve_activities_for_character(
-        &mut self,
+        &self,
         member_id: &str,
         character_id: &str,
         mode: &Mode,
@@ -1351,7 +6400,8 @@ impl ActivityStoreInterface {
         .bind(mode.to_id().to_string())
         .bind(restrict_mode_id.to_string())
         .bind(character_index.to_string())
-        .fetch_all(&mut self.db)
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
         .await?;
 
         if activity_rows.is_empty() {
@@ -1366,7 +6416,7 @@ impl ActivityStoreInterface {
     }
 
     async fn parse_individual_performance_rows(
-        &mut self,
+        &self,
         manifest: &mut ManifestInterface,
         activity_rows: &[sqlx::sqlite::SqliteRow],
     ) -> Result<Vec<CruciblePlayerActivityPerformance>, Error> {
@@ -1375,7 +6425,7 @@ impl ActivityStoreInterface {
 
         for activity_row in activity_rows {
             let player_performance = self
-                .parse_individual_performance_row(manifest, &activity_row)
+                .parse_individual_performance_row(manifest, activity_row)
                 .await?;
 
             performances.push(player_performance);
@@ -1387,198 +6437,127 @@ impl ActivityStoreInterface {
     }
 
     async fn parse_activity(
-        &mut self,
+        &self,
         manifest: &mut ManifestInterface,
-        activity_row: &sqlx::sqlite::SqliteRow,
+        row: &ActivityRow,
     ) -> Result<ActivityDetail, Error> {
-        let activity_id: i64 = activity_row.try_get("activity_id")?;
-
-        let mode_id: u32 = activity_row.try_get_unchecked("activity_mode")?;
-        let platform_id: u32 = activity_row.try_get_unchecked("platform")?;
-
-        let period: String = activity_row.try_get_unchecked("period")?;
-        let period = DateTime::parse_from_rfc3339(&period)?;
+        let period = DateTime::parse_from_rfc3339(&row.period)?;
         let period = period.with_timezone(&Utc);
 
-        let director_activity_hash: i64 =
-            activity_row.try_get_unchecked("director_activity_hash")?;
-        let director_activity_hash: u32 = director_activity_hash as u32;
-
-        let reference_id: u32 =
-            activity_row.try_get_unchecked("reference_id")?;
-
-        let index_id: u32 =
-            activity_row.try_get_unchecked("activity_index_id")?;
+        let reference_id = row.reference_id;
         let activity_definition =
             manifest.get_activity_definition(reference_id).await?;
 
-        let map_name = match activity_definition {
-            Some(e) => e.display_properties.name,
-            None => "Unknown".to_string(),
+        let (map_name, map_image_url) = match activity_definition {
+            Some(e) => (e.display_properties.name, e.pgcr_image),
+            None => ("Unknown".to_string(), None),
         };
 
         let activity_detail = ActivityDetail {
-            index_id,
-            id: activity_id,
+            index_id: row.activity_index_id as u32,
+            id: row.activity_id,
             period,
             map_name,
-            mode: Mode::from_id(mode_id)?,
-            platform: Platform::from_id(platform_id),
-            director_activity_hash,
+            map_image_url,
+            mode: Mode::from_id(row.activity_mode)?,
+            platform: Platform::from_id(row.platform),
+            director_activity_hash: row.director_activity_hash as u32,
             reference_id,
         };
 
         Ok(activity_detail)
     }
 
-    async fn parse_crucible_stats(
-        &mut self,
+    async fn parse_weapon_item(
         manifest: &mut ManifestInterface,
-        activity_row: &sqlx::sqlite::SqliteRow,
-    ) -> Result<CrucibleStats, Error> {
-        let assists: u32 = activity_row.try_get_unchecked("assists")?;
-        let score: u32 = activity_row.try_get_unchecked("score")?;
-        let kills: u32 = activity_row.try_get_unchecked("kills")?;
-        let deaths: u32 = activity_row.try_get_unchecked("deaths")?;
-
-        let average_score_per_kill: f32 =
-            activity_row.try_get_unchecked("average_score_per_kill")?;
-        let average_score_per_life: f32 =
-            activity_row.try_get_unchecked("average_score_per_life")?;
-        let completed: i32 = activity_row.try_get_unchecked("completed")?;
-        let completed: bool = completed == 1;
-
-        let opponents_defeated: u32 =
-            activity_row.try_get_unchecked("opponents_defeated")?;
-
-        let activity_duration_seconds: u32 =
-            activity_row.try_get_unchecked("activity_duration_seconds")?;
-
-        let standing: u32 = activity_row.try_get_unchecked("standing")?;
-        let standing: Standing = Standing::from_value(standing);
-
-        let team: i32 = activity_row.try_get_unchecked("team")?;
-
-        let completion_reason: u32 =
-            activity_row.try_get_unchecked("completion_reason")?;
-        let completion_reason = CompletionReason::from_id(completion_reason);
-
-        let start_seconds: u32 =
-            activity_row.try_get_unchecked("start_seconds")?;
-
-        let time_played_seconds: u32 =
-            activity_row.try_get_unchecked("time_played_seconds")?;
-
-        let player_count: u32 =
-            activity_row.try_get_unchecked("player_count")?;
-
-        let team_score: u32 = activity_row.try_get_unchecked("team_score")?;
-
-        let precision_kills: u32 =
-            activity_row.try_get_unchecked("precision_kills")?;
-
-        let weapon_kills_ability: u32 =
-            activity_row.try_get_unchecked("weapon_kills_ability")?;
-
-        let weapon_kills_grenade: u32 =
-            activity_row.try_get_unchecked("weapon_kills_grenade")?;
-
-        let weapon_kills_melee: u32 =
-            activity_row.try_get_unchecked("weapon_kills_melee")?;
-
-        let weapon_kills_super: u32 =
-            activity_row.try_get_unchecked("weapon_kills_super")?;
-
-        let all_medals_earned: u32 =
-            activity_row.try_get_unchecked("all_medals_earned")?;
+        reference_id: u32,
+    ) -> Result<Item, Error> {
+        let item_definition =
+            manifest.get_iventory_item_definition(reference_id).await?;
+
+        //TODO: catch error here if not found
+
+        let description: String;
+        let name: String;
+        let item_type: ItemType;
+        let item_sub_type: ItemSubType;
+
+        match item_definition {
+            Some(e) => {
+                description = e
+                    .display_properties
+                    .description
+                    .unwrap_or_else(|| "".to_string());
+                name = e.display_properties.name;
+                item_type = e.item_type;
+                item_sub_type = e.item_sub_type;
+            }
+            None => {
+                name = "Unknown".to_string();
+                description = "".to_string();
+                item_type = ItemType::Unknown;
+                item_sub_type = ItemSubType::Unknown;
+            }
+        };
 
-        let character_activity_stats_index: i64 =
-            activity_row.try_get("character_activity_stats_index")?;
+        Ok(Item {
+            id: reference_id,
+            name,
+            description,
+            item_type,
+            item_sub_type,
+        })
+    }
 
-        let weapon_rows = sqlx::query(
+    async fn parse_crucible_stats(
+        &self,
+        manifest: &mut ManifestInterface,
+        activity_row_id: i64,
+        row: &CharacterActivityStatsRow,
+    ) -> Result<CrucibleStats, Error> {
+        let weapon_rows = sqlx::query_as::<_, WeaponResultRow>(
             r#"
            select * from weapon_result where character_activity_stats = ?
        "#,
         )
-        .bind(character_activity_stats_index)
-        .fetch_all(&mut self.db)
+        .bind(row.character_activity_stats_index)
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
         .await?;
 
         let mut weapon_stats: Vec<WeaponStat> =
             Vec::with_capacity(weapon_rows.len());
         for weapon_row in &weapon_rows {
-            let reference_id: u32 =
-                weapon_row.try_get_unchecked("reference_id")?;
-
-            let kills: u32 = weapon_row.try_get_unchecked("kills")?;
-            let precision_kills: u32 =
-                weapon_row.try_get_unchecked("precision_kills")?;
-            let precision_kills_percent: f32 =
-                weapon_row.try_get("kills_precision_kills_ratio")?;
-
-            let item_definition =
-                manifest.get_iventory_item_definition(reference_id).await?;
-
-            //TODO: catch error here if not found
-
-            let description: String;
-            let name: String;
-            let item_type: ItemType;
-            let item_sub_type: ItemSubType;
-
-            match item_definition {
-                Some(e) => {
-                    description = e
-                        .display_properties
-                        .description
-                        .unwrap_or_else(|| "".to_string());
-                    name = e.display_properties.name;
-                    item_type = e.item_type;
-                    item_sub_type = e.item_sub_type;
-                }
-                None => {
-                    name = "Unknown".to_string();
-                    description = "".to_string();
-                    item_type = ItemType::Unknown;
-                    item_sub_type = ItemSubType::Unknown;
-                }
-            };
-
-            let item: Item = Item {
-                id: reference_id,
-                name,
-                description,
-                item_type,
-                item_sub_type,
-            };
+            let item =
+                Self::parse_weapon_item(manifest, weapon_row.reference_id)
+                    .await?;
 
             let ws = WeaponStat {
                 weapon: item,
-                kills,
-                precision_kills,
-                precision_kills_percent,
+                kills: weapon_row.kills,
+                precision_kills: weapon_row.precision_kills,
+                precision_kills_percent: weapon_row.kills_precision_kills_ratio,
                 activity_count: 1,
+                activity_ids: std::iter::once(activity_row_id).collect(),
             };
 
             weapon_stats.push(ws);
         }
 
-        let medal_rows = sqlx::query(
+        let medal_rows = sqlx::query_as::<_, MedalResultRow>(
             r#"
            select * from medal_result where character_activity_stats = ?
        "#,
         )
-        .bind(character_activity_stats_index)
-        .fetch_all(&mut self.db)
+        .bind(row.character_activity_stats_index)
+        .fetch_all(&self.db)
+        .timed(TimingCategory::Database)
         .await?;
 
         let mut medal_stats: Vec<MedalStat> =
             Vec::with_capacity(medal_rows.len());
         for medal_row in &medal_rows {
-            let reference_id: String =
-                medal_row.try_get_unchecked("reference_id")?;
-
-            let count: u32 = medal_row.try_get_unchecked("count")?;
+            let reference_id = medal_row.reference_id.to_string();
 
             let medal_definition = manifest
                 .get_historical_stats_definition(&reference_id)
@@ -1615,86 +6594,90 @@ impl ActivityStoreInterface {
                 description,
             };
 
-            let medal_stat = MedalStat { medal, count };
+            let medal_stat = MedalStat {
+                medal,
+                count: medal_row.count,
+            };
             medal_stats.push(medal_stat);
         }
 
         let extended = ExtendedCrucibleStats {
-            precision_kills,
-            weapon_kills_ability,
-            weapon_kills_grenade,
-            weapon_kills_melee,
-            weapon_kills_super,
-            all_medals_earned,
+            precision_kills: row.precision_kills,
+            weapon_kills_ability: row.weapon_kills_ability,
+            weapon_kills_grenade: row.weapon_kills_grenade,
+            weapon_kills_melee: row.weapon_kills_melee,
+            weapon_kills_super: row.weapon_kills_super,
+            all_medals_earned: row.all_medals_earned,
 
             weapons: weapon_stats,
             medals: medal_stats,
         };
 
-        let stats = CrucibleStats {
+        let (kills, deaths, assists) = (row.kills, row.deaths, row.assists);
+        let mut stats = CrucibleStats {
             assists,
-            score,
+            score: row.score,
             kills,
             deaths,
-            average_score_per_kill,
-            average_score_per_life,
-            completed,
-            opponents_defeated,
+            average_score_per_kill: row.average_score_per_kill,
+            average_score_per_life: row.average_score_per_life,
+            completed: row.completed == 1,
+            opponents_defeated: row.opponents_defeated,
             efficiency: calculate_efficiency(kills, deaths, assists),
             kills_deaths_ratio: calculate_kills_deaths_ratio(kills, deaths),
             kills_deaths_assists: calculate_kills_deaths_assists(
                 kills, deaths, assists,
             ),
-            activity_duration_seconds,
-            standing,
-            team,
-            completion_reason,
-            start_seconds,
-            time_played_seconds,
-            player_count,
-            team_score,
+            activity_duration_seconds: row.activity_duration_seconds,
+            standing: Standing::from_value(row.standing),
+            team: row.team,
+            fireteam_id: row.fireteam_id,
+            completion_reason: CompletionReason::from_id(row.completion_reason),
+            start_seconds: row.start_seconds,
+            time_played_seconds: row.time_played_seconds,
+            player_count: row.player_count,
+            team_score: row.team_score,
             extended: Some(extended),
+            derived: Vec::new(),
         };
 
+        stats.derived = derivedstats::calculate(&stats);
+
         Ok(stats)
     }
 
-    async fn parse_player(
-        &mut self,
-        activity_row: &sqlx::sqlite::SqliteRow,
-    ) -> Result<Player, Error> {
-        let member_id: String = activity_row.try_get_unchecked("member_id")?;
-        let character_id = activity_row.try_get_unchecked("character_id")?;
-        let platform_id: u32 = activity_row.try_get_unchecked("platform_id")?;
-        let display_name: String =
-            activity_row.try_get_unchecked("display_name")?;
-        let light_level: i32 = activity_row.try_get_unchecked("light_level")?;
-        let class_type: u32 = activity_row.try_get_unchecked("class")?;
-        let class_type: CharacterClass = CharacterClass::from_id(class_type);
-
-        let platform = Platform::from_id(platform_id);
-
-        let player = Player {
-            member_id,
-            character_id,
-            platform,
-            display_name,
-            light_level,
-            class_type,
-        };
-
-        Ok(player)
+    fn parse_player(
+        player_row: &PlayerRow,
+        stats_row: &CharacterActivityStatsRow,
+    ) -> Player {
+        Player {
+            member_id: player_row.member_id.to_string(),
+            character_id: player_row.character_id.to_string(),
+            platform: Platform::from_id(player_row.platform_id),
+            display_name: player_row.display_name.to_string(),
+            light_level: stats_row.light_level,
+            class_type: CharacterClass::from_id(player_row.class),
+        }
     }
 
     async fn parse_individual_performance_row(
-        &mut self,
+        &self,
         manifest: &mut ManifestInterface,
         activity_row: &sqlx::sqlite::SqliteRow,
     ) -> Result<CruciblePlayerActivityPerformance, Error> {
-        let activity_detail =
-            self.parse_activity(manifest, activity_row).await?;
-        let stats = self.parse_crucible_stats(manifest, activity_row).await?;
-        let player = self.parse_player(activity_row).await?;
+        let activity = ActivityRow::from_row(activity_row)?;
+        let stats_row = CharacterActivityStatsRow::from_row(activity_row)?;
+        let player_row = PlayerRow::from_row(activity_row)?;
+
+        let activity_detail = self.parse_activity(manifest, &activity).await?;
+        let stats = self
+            .parse_crucible_stats(
+                manifest,
+                activity.activity_index_id,
+                &stats_row,
+            )
+            .await?;
+        let player = Self::parse_player(&player_row, &stats_row);
 
         let performance = CruciblePlayerPerformance { player, stats };
 
@@ -1711,15 +6694,240 @@ impl ActivityStoreInterface {
 pub struct SyncResult {
     pub total_available: u32,
     pub total_synced: u32,
+
+    /// results for each character synced. Only set for results returned
+    /// from sync.
+    pub characters: Vec<CharacterSyncResult>,
+}
+
+/// a single recorded sync run for a member
+#[derive(Debug, Clone)]
+pub struct SyncHistoryEntry {
+    pub member_id: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub activities_added: u32,
+
+    /// activities found but not yet synced when the run finished
+    pub activities_remaining: u32,
+
+    /// set if the run failed or was cancelled
+    pub error: Option<String>,
+}
+
+impl SyncHistoryEntry {
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+#[derive(Debug)]
+pub struct MemberSyncResult {
+    pub member_id: String,
+    pub platform: Platform,
+    pub result: Result<SyncResult, Error>,
+}
+
+#[derive(Debug)]
+pub struct MaintenanceResult {
+    /// size of the store on disk in bytes
+    pub size_before: u64,
+    pub size_after: u64,
+
+    /// problems found by the integrity check. If any are found, the store is
+    /// not compacted.
+    pub integrity_errors: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct VerifyResult {
+    pub activities_checked: u32,
+    pub mismatches: Vec<ActivityMismatch>,
+
+    /// activities which could not be retrieved from the api
+    pub failed: Vec<(i64, Error)>,
+}
+
+/// a value which is stored differently than it was returned from the api
+#[derive(Debug)]
+pub struct ActivityMismatch {
+    pub activity_id: i64,
+
+    /// character the value is for, or None for activity and team values
+    pub character_id: Option<String>,
+    pub field: String,
+    pub stored: String,
+    pub remote: String,
+}
+
+#[derive(Debug)]
+pub struct ReingestResult {
+    pub reingested: Vec<i64>,
+
+    /// activities which are not in the store
+    pub not_found: Vec<i64>,
+
+    /// activities which could not be retrieved or stored. These are left
+    /// as they were.
+    pub failed: Vec<(i64, Error)>,
+}
+
+#[derive(Debug)]
+pub struct ReprocessResult {
+    pub reprocessed: Vec<i64>,
+
+    /// activities without stored PGCR json, which were not reprocessed
+    pub skipped: Vec<i64>,
+
+    /// activities which could not be re-parsed or retrieved. These are left
+    /// as they were.
+    pub failed: Vec<(i64, Error)>,
+}
+
+/// a stored player matching a display name
+#[derive(Debug, Clone)]
+pub struct MemberCandidate {
+    pub member_id: String,
+    pub platform: Platform,
+    pub display_name: String,
+
+    /// number of stored activities the player was in
+    pub activities: u32,
+}
+
+impl std::fmt::Display for MemberCandidate {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}) member id : {} ({} activities)",
+            self.display_name, self.platform, self.member_id, self.activities
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct MemberPurgeResult {
+    pub activities_removed: u32,
+
+    /// false if the member was kept as a player in activities with other
+    /// synced members
+    pub member_removed: bool,
+}
+
+/// parses the number of activities to retrieve at the same time when
+/// syncing. Must be between 1 and MAX_PGCR_CONCURRENCY.
+pub fn parse_pgcr_concurrency(src: &str) -> Result<usize, String> {
+    match src.trim().parse::<usize>() {
+        Ok(e) if (1..=MAX_PGCR_CONCURRENCY).contains(&e) => Ok(e),
+        _ => Err(format!(
+            "Concurrency must be a number between 1 and {} : {}",
+            MAX_PGCR_CONCURRENCY, src
+        )),
+    }
+}
+
+//returns the entries from a PGCR which are stored, capped at MAX_PGCR_ENTRIES.
+//Entries for which keep returns true (i.e. the character being synced) are
+//always included, and the rest are filled with the other entries in order.
+fn capped_pgcr_entries<F>(
+    entries: &[DestinyPostGameCarnageReportEntry],
+    keep: F,
+) -> Vec<&DestinyPostGameCarnageReportEntry>
+where
+    F: Fn(&str) -> bool,
+{
+    let kept = entries.iter().filter(|e| keep(&e.character_id)).count();
+    let mut others = MAX_PGCR_ENTRIES.saturating_sub(kept);
+
+    entries
+        .iter()
+        .filter(|e| {
+            if keep(&e.character_id) {
+                return true;
+            }
+
+            if others == 0 {
+                return false;
+            }
+
+            others -= 1;
+            true
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct Season {
+    pub season_number: u32,
+    pub name: String,
+    pub start: DateTime<Utc>,
+
+    /// None if the season has not ended, and does not have a scheduled end
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl Season {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> Result<Season, Error> {
+        let parse = |date: &str| -> Result<DateTime<Utc>, Error> {
+            match DateTime::parse_from_rfc3339(date) {
+                Ok(e) => Ok(e.with_timezone(&Utc)),
+                Err(_e) => Err(Error::Database {
+                    description: format!("Invalid season date : {}", date),
+                }),
+            }
+        };
+
+        let start: String = row.try_get("start_date")?;
+        let end: Option<String> = row.try_get("end_date")?;
+
+        let end = match end {
+            Some(e) => Some(parse(&e)?),
+            None => None,
+        };
+
+        Ok(Season {
+            season_number: row.try_get("season_number")?,
+            name: row.try_get("name")?,
+            start: parse(&start)?,
+            end,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct PruneResult {
+    pub activities_removed: u64,
+
+    /// players who were only in the removed activities
+    pub players_removed: u64,
+}
+
+#[derive(Debug)]
+pub struct ModeActivityCount {
+    pub mode: Mode,
+    pub activities: u32,
+}
+
+#[derive(Debug)]
+pub struct CharacterSyncResult {
+    pub character_id: String,
+    pub class_type: CharacterClass,
+    pub total_available: u32,
+    pub total_synced: u32,
 }
 
 impl std::ops::Add<SyncResult> for SyncResult {
     type Output = SyncResult;
 
     fn add(self, sr: SyncResult) -> SyncResult {
+        let mut characters = self.characters;
+        characters.extend(sr.characters);
+
         SyncResult {
             total_available: self.total_available + sr.total_available,
             total_synced: self.total_synced + sr.total_synced,
+            characters,
         }
     }
 }
@@ -20,24 +20,70 @@
 * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 */
 
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+
 use reqwest::header::{HeaderMap, HeaderValue, CONNECTION};
-use reqwest::{Client, Url};
+use reqwest::{Client, StatusCode, Url};
 
 use crate::error::Error;
+use crate::ratelimit;
 use crate::response::drs::{
     check_destiny_response_status, IsDestinyAPIResponse,
 };
+use crate::timing::{self, TimingCategory};
 use crate::utils::print_verbose;
 
 const DESTINY_API_KEY: &str = env!("DESTINY_API_KEY");
 const API_TIMEOUT: u64 = 10; //seconds
 
+//comma seperated list of additional api keys to use at runtime. When a request
+//is throttled, we rotate to the next key and try again.
+pub const API_KEYS_ENV_VAR: &str = "DCLI_API_KEYS";
+
 //this makes sure that the env variable isnt set, but empty
 static_assertions::const_assert!(!DESTINY_API_KEY.is_empty());
 
+#[derive(Debug, Clone)]
+pub struct ApiKeyUsage {
+    /// last few characters of the key, so it can be identified without
+    /// storing the entire key
+    pub key_hint: String,
+    pub requests: u32,
+    pub throttled: u32,
+}
+
+struct ApiKey {
+    value: HeaderValue,
+    hint: String,
+    requests: AtomicU32,
+    throttled: AtomicU32,
+}
+
+impl ApiKey {
+    fn new(key: &str) -> Result<ApiKey, Error> {
+        let value =
+            HeaderValue::from_str(key).map_err(|_e| Error::ApiRequest {
+                description: "Invalid API key.".to_string(),
+            })?;
+
+        let chars: Vec<char> = key.chars().collect();
+        let hint: String =
+            chars[chars.len().saturating_sub(4)..].iter().collect();
+
+        Ok(ApiKey {
+            value,
+            hint,
+            requests: AtomicU32::new(0),
+            throttled: AtomicU32::new(0),
+        })
+    }
+}
+
 pub struct ApiClient {
     pub verbose: bool,
     client: Client,
+    keys: Vec<ApiKey>,
+    current_key: AtomicUsize,
 }
 
 impl ApiClient {
@@ -48,29 +94,105 @@ impl ApiClient {
             "Keep-Alive",
             HeaderValue::from_static("timeout=10, max=1000"),
         );
-        headers.insert("X-API-Key", HeaderValue::from_static(DESTINY_API_KEY));
+
+        let mut keys = vec![ApiKey::new(DESTINY_API_KEY)?];
+        if let Ok(e) = std::env::var(API_KEYS_ENV_VAR) {
+            for k in e.split(',').map(|k| k.trim()).filter(|k| !k.is_empty()) {
+                if k != DESTINY_API_KEY {
+                    keys.push(ApiKey::new(k)?);
+                }
+            }
+        }
 
         let client = Client::builder()
             .default_headers(headers)
             .timeout(std::time::Duration::from_secs(API_TIMEOUT))
             .build()?;
 
-        Ok(ApiClient { client, verbose })
+        Ok(ApiClient {
+            client,
+            verbose,
+            keys,
+            current_key: AtomicUsize::new(0),
+        })
+    }
+
+    /// number of requests made and throttled for each api key
+    pub fn get_key_usage(&self) -> Vec<ApiKeyUsage> {
+        self.keys
+            .iter()
+            .map(|k| ApiKeyUsage {
+                key_hint: k.hint.clone(),
+                requests: k.requests.load(Ordering::Relaxed),
+                throttled: k.throttled.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+
+    //moves to the next key, unless another request already rotated away
+    //from the throttled key
+    fn rotate_key(&self, throttled_index: usize) {
+        self.keys[throttled_index]
+            .throttled
+            .fetch_add(1, Ordering::Relaxed);
+
+        let next = (throttled_index + 1) % self.keys.len();
+        let _ = self.current_key.compare_exchange(
+            throttled_index,
+            next,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        );
+
+        print_verbose(
+            &format!(
+                "API key ...{} throttled. Rotating to key ...{}",
+                self.keys[throttled_index].hint, self.keys[next].hint
+            ),
+            self.verbose,
+        );
     }
 
     pub async fn call(&self, url: &str) -> Result<reqwest::Response, Error> {
-        let url = Url::parse(&url).unwrap();
+        let _timer = timing::start(TimingCategory::Api);
+        Ok(self.call_with_key(url).await?.0)
+    }
+
+    //makes the request, rotating keys if the request is throttled. Returns
+    //the response along with the index of the key used for it
+    async fn call_with_key(
+        &self,
+        url: &str,
+    ) -> Result<(reqwest::Response, usize), Error> {
+        let url = Url::parse(url).unwrap();
 
         print_verbose(&format!("{}", url), self.verbose);
 
-        let response = self
-            .client
-            .get(url)
-            //.header("X-API-Key", DESTINY_API_KEY)
-            .send()
-            .await?; //this either returns a reqwest::Response for an Error which is returned
+        let mut attempt = 0;
+        loop {
+            let index = self.current_key.load(Ordering::SeqCst);
+            let key = &self.keys[index];
+            key.requests.fetch_add(1, Ordering::Relaxed);
+
+            ratelimit::wait().await;
+
+            let response = self
+                .client
+                .get(url.clone())
+                .header("X-API-Key", key.value.clone())
+                .send()
+                .await?; //this either returns a reqwest::Response for an Error which is returned
+
+            attempt += 1;
+            if response.status() == StatusCode::TOO_MANY_REQUESTS
+                && attempt < self.keys.len()
+            {
+                self.rotate_key(index);
+                continue;
+            }
 
-        Ok(response)
+            return Ok((response, index));
+        }
     }
 
     pub async fn call_and_parse<
@@ -79,30 +201,103 @@ impl ApiClient {
         &self,
         url: &str,
     ) -> Result<T, Error> {
-        let body = match self.call(url).await {
-            Ok(e) => {
-                //println!("{:?}", e.headers());
-                e.text().await?
+        let _timer = timing::start(TimingCategory::Api);
+        let mut attempt = 0;
+        loop {
+            let (response, index) = self.call_with_key(url).await?;
+            let body = response.text().await?;
+
+            attempt += 1;
+            match self.parse(&body) {
+                Err(Error::ApiThrottled { .. })
+                    if attempt < self.keys.len() =>
+                {
+                    self.rotate_key(index);
+                }
+                r => return r,
             }
-            Err(e) => return Err(e),
-        };
+        }
+    }
+
+    /// calls the api, reading the response in chunks and aborting if it
+    /// is larger than max_bytes, before parsing it.
+    pub async fn call_and_parse_with_max_size<
+        T: serde::de::DeserializeOwned + IsDestinyAPIResponse,
+    >(
+        &self,
+        url: &str,
+        max_bytes: usize,
+    ) -> Result<T, Error> {
+        let _timer = timing::start(TimingCategory::Api);
+        let mut attempt = 0;
+        loop {
+            let (response, index) = self.call_with_key(url).await?;
+
+            attempt += 1;
+            match self.read_and_parse(response, max_bytes).await {
+                Err(Error::ApiThrottled { .. })
+                    if attempt < self.keys.len() =>
+                {
+                    self.rotate_key(index);
+                }
+                r => return r,
+            }
+        }
+    }
 
+    async fn read_and_parse<
+        T: serde::de::DeserializeOwned + IsDestinyAPIResponse,
+    >(
+        &self,
+        mut response: reqwest::Response,
+        max_bytes: usize,
+    ) -> Result<T, Error> {
+        //bail early if the server tells us how large the response is
+        if let Some(len) = response.content_length() {
+            if len as usize > max_bytes {
+                return Err(Error::ResponseTooLarge {
+                    description: format!(
+                        "{} bytes (max {} bytes)",
+                        len, max_bytes
+                    ),
+                });
+            }
+        }
+
+        let mut out: Vec<u8> = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if out.len() + chunk.len() > max_bytes {
+                return Err(Error::ResponseTooLarge {
+                    description: format!("more than {} bytes", max_bytes),
+                });
+            }
+            out.extend_from_slice(&chunk);
+        }
+
+        let body = String::from_utf8_lossy(&out);
+        self.parse(&body)
+    }
+
+    fn parse<T: serde::de::DeserializeOwned + IsDestinyAPIResponse>(
+        &self,
+        body: &str,
+    ) -> Result<T, Error> {
         if self.verbose {
             let len = body.chars().count();
             const MAX: usize = 200;
             let limit = std::cmp::min(len, MAX);
 
-            println!(
+            eprintln!(
                 "---------Begin API response : First {}  chars---------",
                 limit
             );
-            println!("{}", &body[..limit]);
-            println!("---------End API response---------");
+            eprintln!("{}", &body[..limit]);
+            eprintln!("---------End API response---------");
         }
 
         //we split the parsing from the request so we can capture the body and
         //print it out if we need to
-        let r = serde_json::from_str::<T>(&body)?;
+        let r = serde_json::from_str::<T>(body)?;
 
         check_destiny_response_status(r.get_status())?;
 
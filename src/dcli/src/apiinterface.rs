@@ -28,6 +28,8 @@ use std::{
 use chrono::{DateTime, Utc};
 use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 
+use crate::accountcontext::AccountContext;
+use crate::enums::character::CharacterGender;
 use crate::enums::mode::Mode;
 use crate::enums::platform::Platform;
 use crate::error::Error;
@@ -35,14 +37,22 @@ use crate::response::activities::{
     ActivitiesResponse, Activity, MAX_ACTIVITIES_REQUEST_COUNT,
 };
 use crate::response::drs::API_RESPONSE_STATUS_SUCCESS;
-use crate::response::gpr::{CharacterActivitiesData, GetProfileResponse};
-use crate::response::pgcr::{DestinyPostGameCarnageReportData, PGCRResponse};
+use crate::response::gpr::{
+    CharacterActivitiesData, GetProfileResponse, ProgressionData,
+};
+use crate::response::pgcr::{
+    DestinyPostGameCarnageReportData, PGCRResponse, RawPGCRResponse,
+};
 use crate::response::stats::{
     AllTimePvPStatsResponse, DailyPvPStatsResponse, DailyPvPStatsValuesData,
     PvpStatsData,
 };
+use crate::response::user::GetMembershipsResponse;
 use crate::utils::Period;
-use crate::{apiclient::ApiClient, crucible::Player};
+use crate::{
+    apiclient::{ApiClient, ApiKeyUsage},
+    crucible::Player,
+};
 use crate::{
     apiutils::{API_BASE_URL, PGCR_BASE_URL},
     character::PlayerInfo,
@@ -50,6 +60,9 @@ use crate::{
 
 use crate::character::Characters;
 
+//normal PGCRs are well under 1MB
+const MAX_PGCR_RESPONSE_BYTES: usize = 10 * 1024 * 1024;
+
 pub struct ApiInterface {
     client: ApiClient,
 }
@@ -63,6 +76,49 @@ impl ApiInterface {
         //some methods may require it and will throw errors if its not set
     }
 
+    /// number of requests made and throttled for each configured api key
+    pub fn get_key_usage(&self) -> Vec<ApiKeyUsage> {
+        self.client.get_key_usage()
+    }
+
+    /// retrieves the member's progress for the specified progression (i.e.
+    /// glory) keyed by character id. Characters without the progression are
+    /// not included.
+    pub async fn retrieve_character_progression(
+        &self,
+        member_id: &str,
+        platform: &Platform,
+        progression_hash: u32,
+    ) -> Result<HashMap<String, ProgressionData>, Error> {
+        let url = format!(
+            "{base}/Platform/Destiny2/{platform_id}/Profile/{member_id}/?components=202",
+            base = API_BASE_URL,
+            platform_id = platform.to_id(),
+            member_id = utf8_percent_encode(member_id, NON_ALPHANUMERIC)
+        );
+
+        let profile: GetProfileResponse = self
+            .client
+            .call_and_parse::<GetProfileResponse>(&url)
+            .await?;
+
+        let progressions = profile
+            .response
+            .and_then(|e| e.character_progressions)
+            .map(|e| e.data)
+            .unwrap_or_default();
+
+        let key = progression_hash.to_string();
+        let mut out: HashMap<String, ProgressionData> = HashMap::new();
+        for (character_id, mut data) in progressions {
+            if let Some(e) = data.progressions.remove(&key) {
+                out.insert(character_id, e);
+            }
+        }
+
+        Ok(out)
+    }
+
     /// Retrieves characters for specified member_id and platform
     pub async fn retrieve_current_activity(
         &self,
@@ -129,7 +185,7 @@ impl ApiInterface {
             "{base}/Platform/Destiny2/{platform_id}/Profile/{member_id}/?components=100,200",
             base = API_BASE_URL,
             platform_id = platform.to_id(),
-            member_id = utf8_percent_encode(&member_id, NON_ALPHANUMERIC)
+            member_id = utf8_percent_encode(member_id, NON_ALPHANUMERIC)
         );
 
         let profile: GetProfileResponse = self
@@ -150,13 +206,7 @@ impl ApiInterface {
 
         //characters should never be empty
         //todo: test with player with no chars created
-        let c = response
-            .characters
-            .unwrap()
-            .data
-            .into_iter()
-            .map(|(_id, m)| m)
-            .collect();
+        let c = response.characters.unwrap().data.into_values().collect();
 
         let characters = Characters::with_characters(c);
 
@@ -214,6 +264,133 @@ impl ApiInterface {
         Ok(hash)
     }
 
+    /// retrieves the equipped title record hash and gender for the characters
+    /// of the specified players. Players who do not have a title equipped, or
+    /// whose profile could not be loaded, are not included.
+    pub async fn retrieve_title_records(
+        &self,
+        players: &[&Player],
+    ) -> HashMap<u64, (u32, CharacterGender)> {
+        let mut futures = Vec::new();
+        for p in players {
+            futures.push(self.get_player_info(&p.member_id, &p.platform));
+        }
+
+        let results = futures::future::join_all(futures).await;
+
+        let mut hash: HashMap<u64, (u32, CharacterGender)> = HashMap::new();
+        for (i, r) in results.iter().enumerate() {
+            let info = match r {
+                Ok(e) => e,
+                Err(_e) => continue,
+            };
+
+            let player = players[i];
+            let character = info
+                .characters
+                .characters
+                .iter()
+                .find(|c| c.id == player.character_id);
+
+            if let Some(c) = character {
+                if let Some(title_hash) = c.title_record_hash {
+                    hash.insert(
+                        player.calculate_hash(),
+                        (title_hash, c.gender),
+                    );
+                }
+            }
+        }
+
+        hash
+    }
+
+    /// retrieves the date the bungie.net account was first accessed. Returns
+    /// None if the account does not have a linked bungie.net user.
+    pub async fn retrieve_account_first_access(
+        &self,
+        member_id: &str,
+        platform: &Platform,
+    ) -> Result<Option<DateTime<Utc>>, Error> {
+        let url = format!(
+            "{base}/Platform/User/GetMembershipsById/{member_id}/{platform_id}/",
+            base = API_BASE_URL,
+            member_id = utf8_percent_encode(member_id, NON_ALPHANUMERIC),
+            platform_id = platform.to_id(),
+        );
+
+        let response: GetMembershipsResponse = self
+            .client
+            .call_and_parse::<GetMembershipsResponse>(&url)
+            .await?;
+
+        let first_access = response
+            .response
+            .and_then(|e| e.bungie_net_user)
+            .and_then(|e| e.first_access);
+
+        let out = match first_access {
+            Some(e) => {
+                Some(DateTime::parse_from_rfc3339(&e)?.with_timezone(&Utc))
+            }
+            None => None,
+        };
+
+        Ok(out)
+    }
+
+    /// retrieves account level context (account age and total crucible
+    /// matches) for the specified player
+    pub async fn retrieve_account_context(
+        &self,
+        member_id: &str,
+        platform: &Platform,
+    ) -> Result<AccountContext, Error> {
+        //character id of 0 returns stats merged across all characters
+        let (first_access, stats) = futures::future::join(
+            self.retrieve_account_first_access(member_id, platform),
+            self.retrieve_alltime_crucible_stats(
+                member_id,
+                "0",
+                platform,
+                &Mode::AllPvP,
+            ),
+        )
+        .await;
+
+        //not all accounts have a bungie.net user, so we dont fail if we
+        //cant get the date
+        let first_access = first_access.unwrap_or(None).map(|e| e.timestamp());
+
+        let (crucible_matches, crucible_seconds_played) = match stats? {
+            Some(e) => (e.activities_entered as u32, e.seconds_played as u32),
+            None => (0, 0),
+        };
+
+        Ok(AccountContext {
+            member_id: member_id.to_string(),
+            first_access,
+            crucible_matches,
+            crucible_seconds_played,
+            retrieved: Utc::now().timestamp(),
+        })
+    }
+
+    /// retrieves account context for multiple players concurrently. Results
+    /// are returned in the same order as players.
+    pub async fn retrieve_account_contexts(
+        &self,
+        players: &[&Player],
+    ) -> Vec<Result<AccountContext, Error>> {
+        let mut futures = Vec::new();
+        for p in players {
+            futures
+                .push(self.retrieve_account_context(&p.member_id, &p.platform));
+        }
+
+        futures::future::join_all(futures).await
+    }
+
     pub async fn retrieve_alltime_crucible_stats(
         &self,
         member_id: &str,
@@ -226,8 +403,8 @@ impl ApiInterface {
         format!("{base}/Platform/Destiny2/{platform_id}/Account/{member_id}/Character/{character_id}/Stats/?modes={mode_id}&periodType=2&groups=1,2,3",
             base=API_BASE_URL,
             platform_id = platform.to_id(),
-            member_id=utf8_percent_encode(&member_id, NON_ALPHANUMERIC),
-            character_id=utf8_percent_encode(&character_id, NON_ALPHANUMERIC),
+            member_id=utf8_percent_encode(member_id, NON_ALPHANUMERIC),
+            character_id=utf8_percent_encode(character_id, NON_ALPHANUMERIC),
             mode_id = mode.to_id(),
         );
 
@@ -266,8 +443,8 @@ impl ApiInterface {
         format!("{base}/Platform/Destiny2/{platform_id}/Account/{member_id}/Character/{character_id}/Stats/?modes={mode_id}&periodType=1&groups=1,2,3&daystart={day_start}&dayend={day_end}",
             base=API_BASE_URL,
             platform_id = platform.to_id(),
-            member_id=utf8_percent_encode(&member_id, NON_ALPHANUMERIC),
-            character_id=utf8_percent_encode(&character_id, NON_ALPHANUMERIC),
+            member_id=utf8_percent_encode(member_id, NON_ALPHANUMERIC),
+            character_id=utf8_percent_encode(character_id, NON_ALPHANUMERIC),
             mode_id = mode.to_id(),
             day_start = utf8_percent_encode(&day_start, NON_ALPHANUMERIC),
             day_end = utf8_percent_encode(&day_end, NON_ALPHANUMERIC),
@@ -484,8 +661,8 @@ impl ApiInterface {
         format!("{base}/Platform/Destiny2/{platform_id}/Account/{member_id}/Character/{character_id}/Stats/Activities/?mode={mode_id}&count={count}&page={page}",
             base=API_BASE_URL,
             platform_id = platform.to_id(),
-            member_id=utf8_percent_encode(&member_id, NON_ALPHANUMERIC),
-            character_id=utf8_percent_encode(&character_id, NON_ALPHANUMERIC),
+            member_id=utf8_percent_encode(member_id, NON_ALPHANUMERIC),
+            character_id=utf8_percent_encode(character_id, NON_ALPHANUMERIC),
             mode_id = mode.to_id(),
             count=count,
             page=page,
@@ -526,6 +703,65 @@ impl ApiInterface {
         Ok(activities)
     }
 
+    /// retrieves the PGCR for the activity as untyped json, including any
+    /// fields which are not parsed by retrieve_post_game_carnage_report
+    pub async fn retrieve_raw_post_game_carnage_report(
+        &self,
+        instance_id: i64,
+    ) -> Result<Option<serde_json::Value>, Error> {
+        let url = format!(
+            "{base}/Platform/Destiny2/Stats/PostGameCarnageReport/{instance_id}/",
+            base = PGCR_BASE_URL,
+            instance_id = instance_id,
+        );
+
+        let response: RawPGCRResponse = self
+            .client
+            .call_and_parse_with_max_size::<RawPGCRResponse>(
+                &url,
+                MAX_PGCR_RESPONSE_BYTES,
+            )
+            .await?;
+
+        match response.response {
+            Some(e) => Ok(Some(e)),
+            None => {
+                if response.status.error_code == API_RESPONSE_STATUS_SUCCESS {
+                    Ok(None)
+                } else {
+                    Err(Error::ApiRequest {
+                        description: String::from(
+                            "No response data from API Call.",
+                        ),
+                    })
+                }
+            }
+        }
+    }
+
+    /// retrieves the PGCR for the activity, along with the untyped json it
+    /// was parsed from, so the original data can be stored
+    pub async fn retrieve_post_game_carnage_report_with_raw(
+        &self,
+        instance_id: i64,
+    ) -> Result<
+        Option<(DestinyPostGameCarnageReportData, serde_json::Value)>,
+        Error,
+    > {
+        let raw = match self
+            .retrieve_raw_post_game_carnage_report(instance_id)
+            .await?
+        {
+            Some(e) => e,
+            None => return Ok(None),
+        };
+
+        let data: DestinyPostGameCarnageReportData =
+            serde_json::from_value(raw.clone())?;
+
+        Ok(Some((data, raw)))
+    }
+
     pub async fn retrieve_post_game_carnage_report(
         &self,
         instance_id: i64,
@@ -537,8 +773,15 @@ impl ApiInterface {
             instance_id = instance_id,
         );
 
-        let response: PGCRResponse =
-            self.client.call_and_parse::<PGCRResponse>(&url).await?;
+        //some PGCRs (glitched activities, large private matches) can be much
+        //larger than normal, so we cap how much we will read
+        let response: PGCRResponse = self
+            .client
+            .call_and_parse_with_max_size::<PGCRResponse>(
+                &url,
+                MAX_PGCR_RESPONSE_BYTES,
+            )
+            .await?;
 
         let data: DestinyPostGameCarnageReportData = match response.response {
             Some(e) => e,
@@ -0,0 +1,107 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+
+use crate::crucible::CruciblePlayerActivityPerformance;
+
+/// number of intensity levels in a calendar heatmap, including the level for
+/// days without any activities
+pub const HEATMAP_LEVELS: usize = 5;
+
+/// max number of days displayed in a calendar heatmap (a year, the same as
+/// github)
+pub const MAX_HEATMAP_DAYS: i64 = 365;
+
+/// number of activities started on a single (local) day
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DayActivityCount {
+    pub day: NaiveDate,
+    pub activities: u32,
+}
+
+/// buckets activities by the local day they started on. Returns an entry
+/// for every day from start to end (inclusive) in order, including days
+/// without any activities.
+pub fn count_activities_by_day(
+    data: &[CruciblePlayerActivityPerformance],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Vec<DayActivityCount> {
+    let mut counts: HashMap<NaiveDate, u32> = HashMap::new();
+    for p in data {
+        let day = p
+            .activity_detail
+            .period
+            .with_timezone(&Local)
+            .date()
+            .naive_local();
+        *counts.entry(day).or_insert(0) += 1;
+    }
+
+    let mut out: Vec<DayActivityCount> = Vec::new();
+    let mut day = start;
+    while day <= end {
+        out.push(DayActivityCount {
+            day,
+            activities: counts.get(&day).copied().unwrap_or(0),
+        });
+        day += Duration::days(1);
+    }
+
+    out
+}
+
+/// intensity level for a day, from 0 (no activities) to HEATMAP_LEVELS - 1
+/// (the busiest day), relative to the max activities played in a day
+pub fn heatmap_level(activities: u32, max: u32) -> usize {
+    if activities == 0 || max == 0 {
+        return 0;
+    }
+
+    let steps = (HEATMAP_LEVELS - 1) as f32;
+    let level = ((activities as f32 / max as f32) * steps).ceil() as usize;
+    level.clamp(1, HEATMAP_LEVELS - 1)
+}
+
+/// splits days into weeks (starting on monday) for display as columns in a
+/// heatmap. Days before the first day and after the last day in the first
+/// and last weeks are None.
+pub fn group_weeks(
+    days: &[DayActivityCount],
+) -> Vec<[Option<DayActivityCount>; 7]> {
+    let mut weeks: Vec<[Option<DayActivityCount>; 7]> = Vec::new();
+
+    for d in days {
+        let weekday = d.day.weekday().num_days_from_monday() as usize;
+        if weeks.is_empty() || d.day.weekday() == Weekday::Mon {
+            weeks.push([None; 7]);
+        }
+
+        //unwrap is safe since we just pushed if empty
+        weeks.last_mut().unwrap()[weekday] = Some(*d);
+    }
+
+    weeks
+}
@@ -20,8 +20,11 @@
 * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 */
 
+use chrono::{DateTime, Utc};
+
+use crate::enums::character::{CharacterClass, CharacterClassSelection};
 use crate::response::character::CharacterData;
-use crate::{enums::character::CharacterClass, response::pgcr::UserInfoCard};
+use crate::response::pgcr::UserInfoCard;
 
 pub struct PlayerInfo {
     pub characters: Characters,
@@ -36,7 +39,7 @@ impl Characters {
     pub fn with_characters(characters: Vec<CharacterData>) -> Characters {
         let mut out = Characters { characters };
         out.characters
-            .sort_by(|a, b| b.date_last_played.cmp(&a.date_last_played));
+            .sort_by_key(|b| std::cmp::Reverse(b.date_last_played));
 
         out
     }
@@ -49,13 +52,10 @@ impl Characters {
             return None;
         }
 
-        for c in &self.characters {
-            if c.class_type == class_type {
-                return Some(c);
-            }
-        }
-
-        None
+        self.characters
+            .iter()
+            .find(|&c| c.class_type == class_type)
+            .map(|v| v as _)
     }
 
     pub fn get_last_active_ref(&self) -> Option<&CharacterData> {
@@ -65,4 +65,47 @@ impl Characters {
 
         Some(&self.characters[0])
     }
+
+    /// returns the characters for the selection. Empty if the member does
+    /// not have a character of the selected class.
+    pub fn get_by_selection_ref(
+        &self,
+        selection: &CharacterClassSelection,
+    ) -> Vec<&CharacterData> {
+        match selection {
+            CharacterClassSelection::All => self.characters.iter().collect(),
+            CharacterClassSelection::LastActive => {
+                self.get_last_active_ref().into_iter().collect()
+            }
+            CharacterClassSelection::Titan => self
+                .get_by_class_ref(CharacterClass::Titan)
+                .into_iter()
+                .collect(),
+            CharacterClassSelection::Hunter => self
+                .get_by_class_ref(CharacterClass::Hunter)
+                .into_iter()
+                .collect(),
+            CharacterClassSelection::Warlock => self
+                .get_by_class_ref(CharacterClass::Warlock)
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+/// character details stored in the activity store, and refreshed each time
+/// the member is synced. Lets character information be displayed, and the
+/// last active character be found, without calling the API.
+#[derive(Debug, Clone)]
+pub struct CharacterMetadata {
+    pub character_id: String,
+    pub member_id: String,
+    pub class_type: CharacterClass,
+    pub emblem_hash: u32,
+    pub light: u32,
+    pub minutes_played_total: u32,
+    pub date_last_played: DateTime<Utc>,
+
+    /// when the details were last refreshed
+    pub updated_at: DateTime<Utc>,
 }
@@ -0,0 +1,161 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use serde_derive::Serialize;
+
+use crate::crucible::ActivityDetail;
+use crate::enums::mode::Mode;
+use crate::enums::standing::Standing;
+
+/// Glory, the progression for competitive crucible playlists
+pub const GLORY_PROGRESSION_HASH: u32 = 2000925172;
+
+/// modes which count towards competitive progression
+pub const COMPETITIVE_MODES: [Mode; 2] = [Mode::PvPCompetitive, Mode::Survival];
+
+/// a competitive activity, along with the member's progress (i.e. glory)
+/// after it. Progress is recorded when syncing, so is only known exactly
+/// for activities which were the last competitive activity at the time of
+/// a sync. Other values are inferred from the win / loss sequence.
+#[derive(Debug, Clone, Serialize)]
+pub struct CompetitiveMatch {
+    pub activity_detail: ActivityDetail,
+    pub standing: Standing,
+
+    /// positive for a win streak, negative for a loss streak
+    pub streak: i32,
+
+    pub progress: Option<u32>,
+    pub progress_change: Option<i32>,
+    pub rank: Option<String>,
+
+    /// whether progress was inferred, rather than recorded
+    pub inferred: bool,
+}
+
+/// result and recorded progress of a competitive activity, used to infer
+/// progress for activities where it was not recorded
+#[derive(Debug, Clone, Copy)]
+pub struct CompetitiveResult {
+    pub standing: Standing,
+    pub recorded_progress: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ProgressEstimate {
+    pub streak: i32,
+    pub progress: Option<u32>,
+    pub progress_change: Option<i32>,
+    pub inferred: bool,
+}
+
+fn average(values: &[i64]) -> Option<i64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    Some(values.iter().sum::<i64>() / values.len() as i64)
+}
+
+/// estimates progress for each result (in chronological order). Changes
+/// between consecutive recorded results are exact, and their average for
+/// wins and losses is used to walk forward (or backward) from recorded
+/// values to fill in the others. If no consecutive results were recorded,
+/// only the recorded values are returned.
+pub fn infer_progress(results: &[CompetitiveResult]) -> Vec<ProgressEstimate> {
+    let mut win_changes: Vec<i64> = Vec::new();
+    let mut loss_changes: Vec<i64> = Vec::new();
+    for w in results.windows(2) {
+        if let (Some(a), Some(b)) =
+            (w[0].recorded_progress, w[1].recorded_progress)
+        {
+            let change = b as i64 - a as i64;
+            match w[1].standing {
+                Standing::Victory => win_changes.push(change),
+                Standing::Defeat => loss_changes.push(change),
+                Standing::Unknown => (),
+            }
+        }
+    }
+
+    let win_change = average(&win_changes);
+    let loss_change = average(&loss_changes);
+    let estimate_change = |standing: Standing| match standing {
+        Standing::Victory => win_change,
+        Standing::Defeat => loss_change,
+        Standing::Unknown => Some(0),
+    };
+
+    let mut out = vec![ProgressEstimate::default(); results.len()];
+
+    //forward from recorded values
+    let mut current: Option<i64> = None;
+    for (i, r) in results.iter().enumerate() {
+        current = match r.recorded_progress {
+            Some(e) => Some(e as i64),
+            None => {
+                out[i].inferred = true;
+                current
+                    .zip(estimate_change(r.standing))
+                    .map(|(c, e)| (c + e).max(0))
+            }
+        };
+        out[i].progress = current.map(|e| e as u32);
+    }
+
+    //backward for results before the first recorded value
+    for i in (0..results.len().saturating_sub(1)).rev() {
+        if out[i].progress.is_some() {
+            continue;
+        }
+
+        out[i].progress = out[i + 1]
+            .progress
+            .zip(estimate_change(results[i + 1].standing))
+            .map(|(p, e)| (p as i64 - e).max(0) as u32);
+    }
+
+    let mut streak = 0;
+    for i in 0..results.len() {
+        streak = match results[i].standing {
+            Standing::Victory if streak > 0 => streak + 1,
+            Standing::Victory => 1,
+            Standing::Defeat if streak < 0 => streak - 1,
+            Standing::Defeat => -1,
+            Standing::Unknown => 0,
+        };
+        out[i].streak = streak;
+
+        if i > 0 {
+            out[i].progress_change = out[i]
+                .progress
+                .zip(out[i - 1].progress)
+                .map(|(a, b)| a as i32 - b as i32);
+        }
+
+        if out[i].progress.is_none() {
+            out[i].inferred = false;
+        }
+    }
+
+    out
+}
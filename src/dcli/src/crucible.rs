@@ -20,6 +20,7 @@
 * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 */
 
+use crate::derivedstats::{DerivedStatAggregate, DerivedStatValue};
 use crate::enums::mode::Mode;
 use crate::enums::platform::Platform;
 use crate::enums::standing::Standing;
@@ -28,19 +29,29 @@ use crate::enums::{
     itemtype::{ItemSubType, ItemType},
 };
 use crate::enums::{completionreason::CompletionReason, medaltier::MedalTier};
-use chrono::{DateTime, Utc};
+use crate::gambit::GambitStats;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_derive::Serialize;
 
 use std::{cmp::max, collections::hash_map::DefaultHasher, hash::Hasher};
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
 
 use crate::utils::{
     calculate_efficiency, calculate_kills_deaths_assists,
-    calculate_kills_deaths_ratio,
+    calculate_kills_deaths_ratio, calculate_percent,
 };
 
 const PLAYER_START_BUFFER: u32 = 30;
 
-#[derive(Debug, Clone)]
+//suffixes the manifest adds to the names of weapon variants which are
+//otherwise the same as the base weapon
+const WEAPON_VARIANT_SUFFIXES: [&str; 3] =
+    ["(Adept)", "(Timelost)", "(Harrowed)"];
+
+#[derive(Debug, Clone, Serialize)]
 pub struct Team {
     pub id: i32,
     pub standing: Standing,
@@ -49,13 +60,34 @@ pub struct Team {
     pub display_name: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CrucibleActivity {
     pub details: ActivityDetail,
     pub teams: HashMap<i32, Team>,
 }
 
 impl CrucibleActivity {
+    /// number of players in the performance's fireteam, including the player.
+    /// 1 for players who queued alone, or if the fireteam is not known.
+    pub fn get_fireteam_size(
+        &self,
+        performance: &CruciblePlayerPerformance,
+    ) -> u32 {
+        let fireteam_id = match performance.stats.fireteam_id {
+            Some(e) => e,
+            None => return 1,
+        };
+
+        let size = self
+            .teams
+            .values()
+            .flat_map(|t| t.player_performances.iter())
+            .filter(|p| p.stats.fireteam_id == Some(fireteam_id))
+            .count() as u32;
+
+        size.max(1)
+    }
+
     pub fn get_member_performance(
         &self,
         member_id: &str,
@@ -70,21 +102,37 @@ impl CrucibleActivity {
 
         None
     }
+
+    /// place (starting at 1) of the player in the activity when all players
+    /// are ranked by score. Players with the same score share a place.
+    pub fn get_placement(
+        &self,
+        performance: &CruciblePlayerPerformance,
+    ) -> u32 {
+        let higher = self
+            .teams
+            .values()
+            .flat_map(|t| t.player_performances.iter())
+            .filter(|p| p.stats.score > performance.stats.score)
+            .count();
+
+        higher as u32 + 1
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CruciblePlayerPerformance {
     pub player: Player,
     pub stats: CrucibleStats,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CruciblePlayerActivityPerformance {
     pub performance: CruciblePlayerPerformance,
     pub activity_detail: ActivityDetail,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CrucibleStats {
     pub assists: u32,
     pub score: u32,
@@ -100,6 +148,10 @@ pub struct CrucibleStats {
     pub activity_duration_seconds: u32,
     pub standing: Standing,
     pub team: i32,
+
+    /// players with the same id queued together. None for activities
+    /// synced before fireteams were stored.
+    pub fireteam_id: Option<i64>,
     pub completion_reason: CompletionReason,
     pub start_seconds: u32,
     pub time_played_seconds: u32,
@@ -107,6 +159,9 @@ pub struct CrucibleStats {
     pub team_score: u32,
 
     pub extended: Option<ExtendedCrucibleStats>,
+
+    //values of any registered derived stats
+    pub derived: Vec<DerivedStatValue>,
 }
 
 impl CrucibleStats {
@@ -125,7 +180,7 @@ impl CrucibleStats {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct ExtendedCrucibleStats {
     pub precision_kills: u32,
     pub weapon_kills_ability: u32,
@@ -138,7 +193,15 @@ pub struct ExtendedCrucibleStats {
     pub medals: Vec<MedalStat>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+impl ExtendedCrucibleStats {
+    /// gambit stats, which are stored along with the medals. Zero for other
+    /// modes.
+    pub fn gambit_stats(&self) -> GambitStats {
+        GambitStats::with_medals(&self.medals)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct Player {
     pub member_id: String,
     pub character_id: String,
@@ -156,16 +219,91 @@ impl Player {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct WeaponStat {
     pub weapon: Item,
     pub kills: u32,
     pub precision_kills: u32,
     pub precision_kills_percent: f32,
     pub activity_count: u32,
+
+    /// ids (in the activity store) of the activities the weapon was used in,
+    /// so activities aren't counted twice when stats are merged
+    #[serde(skip)]
+    pub activity_ids: HashSet<i64>,
 }
 
-#[derive(Debug, Clone)]
+impl WeaponStat {
+    /// merges adept / timelost / harrowed variants of weapons into their base
+    /// weapon. Weapons are matched on their manifest name (without the variant
+    /// suffix) and item sub type.
+    pub fn merge_variants(weapons: &[WeaponStat]) -> Vec<WeaponStat> {
+        let mut merged: HashMap<(String, ItemSubType), WeaponStat> =
+            HashMap::new();
+
+        for w in weapons {
+            let base_name = normalize_weapon_name(&w.weapon.name);
+            let is_variant = base_name != w.weapon.name;
+            let key = (base_name.clone(), w.weapon.item_sub_type);
+
+            match merged.get_mut(&key) {
+                Some(e) => {
+                    e.kills += w.kills;
+                    e.precision_kills += w.precision_kills;
+
+                    //both variants may have been used in the same game, so
+                    //the activities are merged, rather than the counts
+                    //summed. Stats without activity ids can only be summed.
+                    e.activity_ids.extend(&w.activity_ids);
+                    e.activity_count = if e.activity_ids.is_empty() {
+                        e.activity_count + w.activity_count
+                    } else {
+                        e.activity_ids.len() as u32
+                    };
+
+                    //prefer the base weapon's manifest data
+                    if !is_variant {
+                        e.weapon = w.weapon.clone();
+                    }
+                }
+                None => {
+                    let mut ws = w.clone();
+                    ws.weapon.name = base_name;
+                    merged.insert(key, ws);
+                }
+            }
+        }
+
+        let mut out: Vec<WeaponStat> = merged
+            .into_values()
+            .map(|mut w| {
+                w.precision_kills_percent = if w.kills == 0 {
+                    0.0
+                } else {
+                    (w.precision_kills as f32 / w.kills as f32) * 100.0
+                };
+                w
+            })
+            .collect();
+
+        out.sort_by_key(|b| std::cmp::Reverse(b.kills));
+        out
+    }
+}
+
+/// removes variant suffixes such as (Adept) from a weapon name
+pub fn normalize_weapon_name(name: &str) -> String {
+    let mut out = name.trim();
+    for suffix in WEAPON_VARIANT_SUFFIXES.iter() {
+        if let Some(e) = out.strip_suffix(suffix) {
+            out = e.trim_end();
+        }
+    }
+
+    out.to_string()
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct Item {
     pub id: u32,
     pub name: String,
@@ -174,13 +312,13 @@ pub struct Item {
     pub item_sub_type: ItemSubType,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MedalStat {
     pub medal: Medal,
     pub count: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Medal {
     pub id: String,
     pub icon_image_path: Option<String>,
@@ -189,6 +327,297 @@ pub struct Medal {
     pub description: String,
 }
 
+/// average combat rating of the other players in the lobbies played on a
+/// single day. Only includes players whose rating has been recorded.
+#[derive(Debug, Clone, Serialize)]
+pub struct LobbyRatingPeriod {
+    pub day: NaiveDate,
+    pub activities: u32,
+    pub rated_players: u32,
+    pub average_rating: f32,
+}
+
+/// opponents faced from a single platform, and the player's results in
+/// activities where they faced opponents from that platform
+#[derive(Debug, Clone, Serialize)]
+pub struct OpponentPlatformStats {
+    pub platform: Platform,
+
+    /// total number of opponents faced from the platform
+    pub opponents: u32,
+
+    /// number of activities with at least one opponent from the platform
+    pub activities: u32,
+    pub wins: u32,
+}
+
+impl OpponentPlatformStats {
+    pub fn win_rate(&self) -> f32 {
+        if self.activities == 0 {
+            return 0.0;
+        }
+
+        (self.wins as f32 / self.activities as f32) * 100.0
+    }
+}
+
+/// number of opponents of each class faced in a single activity
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize)]
+pub struct OpponentComposition {
+    pub titans: u32,
+    pub hunters: u32,
+    pub warlocks: u32,
+}
+
+impl OpponentComposition {
+    pub fn total(&self) -> u32 {
+        self.titans + self.hunters + self.warlocks
+    }
+
+    pub fn class_count(&self, class_type: &CharacterClass) -> u32 {
+        match class_type {
+            CharacterClass::Titan => self.titans,
+            CharacterClass::Hunter => self.hunters,
+            CharacterClass::Warlock => self.warlocks,
+            CharacterClass::Unknown => 0,
+        }
+    }
+}
+
+impl std::fmt::Display for OpponentComposition {
+    //i.e. 3 Titan, 1 Hunter
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut classes = [
+            CharacterClass::Titan,
+            CharacterClass::Hunter,
+            CharacterClass::Warlock,
+        ];
+        classes.sort_by_key(|c| std::cmp::Reverse(self.class_count(c)));
+
+        let out: Vec<String> = classes
+            .iter()
+            .filter(|c| self.class_count(c) > 0)
+            .map(|c| format!("{} {}", self.class_count(c), c))
+            .collect();
+
+        if out.is_empty() {
+            return write!(f, "Unknown");
+        }
+
+        write!(f, "{}", out.join(", "))
+    }
+}
+
+/// the classes of the opponents faced in a single activity, and whether the
+/// player won it
+#[derive(Debug, Clone, Serialize)]
+pub struct ActivityOpponentComposition {
+    pub activity_id: i64,
+    pub won: bool,
+    pub composition: OpponentComposition,
+}
+
+/// the player's results in activities against a specific opponent
+/// composition
+#[derive(Debug, Clone, Serialize)]
+pub struct OpponentCompositionStats {
+    pub composition: OpponentComposition,
+    pub activities: u32,
+    pub wins: u32,
+}
+
+impl OpponentCompositionStats {
+    /// groups activities by their opponent composition. The most frequently
+    /// faced compositions are returned first.
+    pub fn with_activities(
+        activities: &[ActivityOpponentComposition],
+    ) -> Vec<OpponentCompositionStats> {
+        let mut stats: HashMap<OpponentComposition, OpponentCompositionStats> =
+            HashMap::new();
+
+        for a in activities {
+            let s = stats.entry(a.composition).or_insert_with(|| {
+                OpponentCompositionStats {
+                    composition: a.composition,
+                    activities: 0,
+                    wins: 0,
+                }
+            });
+
+            s.activities += 1;
+            if a.won {
+                s.wins += 1;
+            }
+        }
+
+        let mut out: Vec<OpponentCompositionStats> =
+            stats.into_values().collect();
+        out.sort_by(|a, b| {
+            b.activities
+                .cmp(&a.activities)
+                .then_with(|| b.wins.cmp(&a.wins))
+                .then_with(|| {
+                    a.composition.to_string().cmp(&b.composition.to_string())
+                })
+        });
+
+        out
+    }
+
+    pub fn win_rate(&self) -> f32 {
+        calculate_percent(self.wins, self.activities)
+    }
+}
+
+/// whether another player was on the player's team in an activity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum PlayerRelationship {
+    Teammate,
+
+    /// includes activities without teams (i.e. rumble)
+    Opponent,
+}
+
+impl std::fmt::Display for PlayerRelationship {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let out = match self {
+            PlayerRelationship::Teammate => "Teammate",
+            PlayerRelationship::Opponent => "Opponent",
+        };
+
+        write!(f, "{}", out)
+    }
+}
+
+/// an activity the player played in along with another specific player
+#[derive(Debug, Clone, Serialize)]
+pub struct SharedActivity {
+    pub activity_id: i64,
+    pub period: DateTime<Utc>,
+    pub mode: Mode,
+    pub relationship: PlayerRelationship,
+
+    /// whether the player (not the other player) won
+    pub won: bool,
+
+    pub kills: u32,
+    pub deaths: u32,
+    pub other_kills: u32,
+    pub other_deaths: u32,
+}
+
+/// the player's results in activities with, or against, another player
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PlayerRecord {
+    pub activities: u32,
+    pub wins: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    pub other_kills: u32,
+    pub other_deaths: u32,
+}
+
+impl PlayerRecord {
+    pub fn losses(&self) -> u32 {
+        self.activities - self.wins
+    }
+
+    pub fn win_rate(&self) -> f32 {
+        calculate_percent(self.wins, self.activities)
+    }
+
+    pub fn kills_deaths_ratio(&self) -> f32 {
+        calculate_kills_deaths_ratio(self.kills, self.deaths)
+    }
+
+    pub fn other_kills_deaths_ratio(&self) -> f32 {
+        calculate_kills_deaths_ratio(self.other_kills, self.other_deaths)
+    }
+}
+
+/// the player's record in activities with another player, split by whether
+/// they were teammates or opponents
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HeadToHeadStats {
+    pub teammate: PlayerRecord,
+    pub opponent: PlayerRecord,
+}
+
+impl HeadToHeadStats {
+    pub fn with_activities(activities: &[SharedActivity]) -> HeadToHeadStats {
+        let mut out = HeadToHeadStats::default();
+
+        for a in activities {
+            let r = match a.relationship {
+                PlayerRelationship::Teammate => &mut out.teammate,
+                PlayerRelationship::Opponent => &mut out.opponent,
+            };
+
+            r.activities += 1;
+            if a.won {
+                r.wins += 1;
+            }
+            r.kills += a.kills;
+            r.deaths += a.deaths;
+            r.other_kills += a.other_kills;
+            r.other_deaths += a.other_deaths;
+        }
+
+        out
+    }
+
+    pub fn total_activities(&self) -> u32 {
+        self.teammate.activities + self.opponent.activities
+    }
+}
+
+/// composite key for grouping activities by the map and mode they were
+/// played on (i.e. Control on Burnout vs Clash on Burnout)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MapModeKey {
+    pub map_name: String,
+    pub mode: Mode,
+}
+
+impl MapModeKey {
+    pub fn with_performance(p: &CruciblePlayerActivityPerformance) -> Self {
+        MapModeKey {
+            map_name: p.activity_detail.map_name.to_string(),
+            mode: p.activity_detail.mode,
+        }
+    }
+}
+
+/// the player's results on a single map, aggregated in the activity store
+/// (see ActivityStoreInterface::retrieve_map_stats)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MapStats {
+    pub map_name: String,
+    pub activities: u32,
+    pub wins: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+}
+
+impl MapStats {
+    pub fn losses(&self) -> u32 {
+        self.activities - self.wins
+    }
+
+    pub fn win_rate(&self) -> f32 {
+        calculate_percent(self.wins, self.activities)
+    }
+
+    pub fn kills_deaths_ratio(&self) -> f32 {
+        calculate_kills_deaths_ratio(self.kills, self.deaths)
+    }
+
+    pub fn efficiency(&self) -> f32 {
+        calculate_efficiency(self.kills, self.deaths, self.assists)
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct AggregateCruciblePerformances {
     pub total_activities: u32,
@@ -222,180 +651,291 @@ pub struct AggregateCruciblePerformances {
     pub total_mercy: u32,
 
     pub extended: Option<ExtendedCruciblePlayerActivityPerformances>,
+
+    pub derived: Vec<DerivedStatAggregate>,
 }
 
 impl AggregateCruciblePerformances {
+    /// groups performances by the key returned from key_fn, and aggregates
+    /// each group. Groups are returned in the order they are first found.
+    pub fn group_by<K, F>(
+        performances: &[CruciblePlayerActivityPerformance],
+        key_fn: F,
+    ) -> Vec<(K, AggregateCruciblePerformances)>
+    where
+        K: Eq + Hash + Clone,
+        F: Fn(&CruciblePlayerActivityPerformance) -> K,
+    {
+        let mut keys: Vec<K> = Vec::new();
+        let mut groups: HashMap<K, Vec<&CruciblePlayerPerformance>> =
+            HashMap::new();
+
+        for p in performances {
+            let key = key_fn(p);
+            if !groups.contains_key(&key) {
+                keys.push(key.clone());
+            }
+
+            groups.entry(key).or_default().push(&p.performance);
+        }
+
+        keys.into_iter()
+            .map(|k| {
+                let aggregate =
+                    AggregateCruciblePerformances::with_performances(
+                        &groups[&k],
+                    );
+                (k, aggregate)
+            })
+            .collect()
+    }
+
+    /// aggregates the performances of each team in the activity separately,
+    /// so teams (and the weapons they used) can be compared. Winning teams
+    /// are returned first, then teams are ordered by id.
+    pub fn by_team(
+        activity: &CrucibleActivity,
+    ) -> Vec<(&Team, AggregateCruciblePerformances)> {
+        let mut teams: Vec<&Team> = activity.teams.values().collect();
+        teams.sort_by_key(|t| (t.standing != Standing::Victory, t.id));
+
+        teams
+            .into_iter()
+            .map(|t| {
+                let performances: Vec<&CruciblePlayerPerformance> =
+                    t.player_performances.iter().collect();
+                (
+                    t,
+                    AggregateCruciblePerformances::with_performances(
+                        &performances,
+                    ),
+                )
+            })
+            .collect()
+    }
+
     pub fn with_performances(
         performances: &[&CruciblePlayerPerformance],
     ) -> AggregateCruciblePerformances {
-        let mut out = AggregateCruciblePerformances::default();
-        let mut extended =
-            ExtendedCruciblePlayerActivityPerformances::default();
+        let mut accumulator = CruciblePerformanceAccumulator::default();
+        for p in performances {
+            accumulator.add_performance(p);
+        }
 
-        out.total_activities = performances.len() as u32;
+        accumulator.finish()
+    }
 
-        let mut medal_hash: HashMap<String, MedalStat> = HashMap::new();
-        let mut weapon_hash: HashMap<u32, WeaponStat> = HashMap::new();
+    /// percent of all kills which were made with the weapon
+    pub fn weapon_kill_share(&self, weapon: &WeaponStat) -> f32 {
+        calculate_percent(weapon.kills, self.kills)
+    }
 
-        let mut streak: i32 = 0;
-        let mut longest_win_streak: u32 = 0;
-        let mut longest_loss_streak: u32 = 0;
-        let mut last_standing = Standing::Unknown;
+    pub fn stat_per_game(&self, value: u32) -> f32 {
+        if self.total_activities == 0 {
+            return 0.0;
+        }
 
-        let mut has_extended = false;
-        for p in performances {
-            if p.stats.completion_reason == CompletionReason::Mercy {
-                out.total_mercy += 1;
-            };
+        value as f32 / self.total_activities as f32
+    }
+}
 
-            out.assists += p.stats.assists;
-            out.score += p.stats.score;
-            out.kills += p.stats.kills;
-            out.deaths += p.stats.deaths;
-            out.opponents_defeated += p.stats.opponents_defeated;
-
-            out.activity_duration_seconds += p.stats.activity_duration_seconds;
-            out.time_played_seconds += p.stats.time_played_seconds;
-
-            out.highest_assists = max(p.stats.assists, out.highest_assists);
-            out.highest_score = max(p.stats.score, out.highest_score);
-            out.highest_kills = max(p.stats.kills, out.highest_kills);
-            out.highest_deaths = max(p.stats.deaths, out.highest_deaths);
-            out.highest_opponents_defeated =
-                max(p.stats.opponents_defeated, out.highest_opponents_defeated);
-            out.highest_efficiency =
-                out.highest_efficiency.max(p.stats.efficiency);
-            out.highest_kills_deaths_ratio = out
-                .highest_kills_deaths_ratio
-                .max(p.stats.kills_deaths_ratio);
-            out.highest_kills_deaths_assists = out
-                .highest_kills_deaths_assists
-                .max(p.stats.kills_deaths_assists);
-
-            match p.stats.standing {
-                Standing::Victory => {
-                    out.wins += 1;
-
-                    if last_standing == Standing::Victory {
-                        streak += 1;
-                    } else {
-                        streak = 1;
-                    }
+/// builds an AggregateCruciblePerformances one performance at a time, so
+/// callers reading performances from a stream never need to hold them all
+#[derive(Debug, Default)]
+pub struct CruciblePerformanceAccumulator {
+    out: AggregateCruciblePerformances,
+    extended: ExtendedCruciblePlayerActivityPerformances,
+    medal_hash: HashMap<String, MedalStat>,
+    weapon_hash: HashMap<u32, WeaponStat>,
+    streak: i32,
+    longest_win_streak: u32,
+    longest_loss_streak: u32,
+    last_standing: Standing,
+    has_extended: bool,
+}
+
+impl CruciblePerformanceAccumulator {
+    pub fn add_performance(&mut self, p: &CruciblePlayerPerformance) {
+        self.out.total_activities += 1;
+
+        if p.stats.completion_reason == CompletionReason::Mercy {
+            self.out.total_mercy += 1;
+        };
+
+        self.out.assists += p.stats.assists;
+        self.out.score += p.stats.score;
+        self.out.kills += p.stats.kills;
+        self.out.deaths += p.stats.deaths;
+        self.out.opponents_defeated += p.stats.opponents_defeated;
+
+        self.out.activity_duration_seconds += p.stats.activity_duration_seconds;
+        self.out.time_played_seconds += p.stats.time_played_seconds;
+
+        self.out.highest_assists =
+            max(p.stats.assists, self.out.highest_assists);
+        self.out.highest_score = max(p.stats.score, self.out.highest_score);
+        self.out.highest_kills = max(p.stats.kills, self.out.highest_kills);
+        self.out.highest_deaths = max(p.stats.deaths, self.out.highest_deaths);
+        self.out.highest_opponents_defeated = max(
+            p.stats.opponents_defeated,
+            self.out.highest_opponents_defeated,
+        );
+        self.out.highest_efficiency =
+            self.out.highest_efficiency.max(p.stats.efficiency);
+        self.out.highest_kills_deaths_ratio = self
+            .out
+            .highest_kills_deaths_ratio
+            .max(p.stats.kills_deaths_ratio);
+        self.out.highest_kills_deaths_assists = self
+            .out
+            .highest_kills_deaths_assists
+            .max(p.stats.kills_deaths_assists);
+
+        match p.stats.standing {
+            Standing::Victory => {
+                self.out.wins += 1;
+
+                if self.last_standing == Standing::Victory {
+                    self.streak += 1;
+                } else {
+                    self.streak = 1;
                 }
-                Standing::Defeat => {
-                    out.losses += 1;
-                    if last_standing == Standing::Defeat {
-                        streak -= 1;
-                    } else {
-                        streak = -1;
-                    }
+            }
+            Standing::Defeat => {
+                self.out.losses += 1;
+                if self.last_standing == Standing::Defeat {
+                    self.streak -= 1;
+                } else {
+                    self.streak = -1;
                 }
-                Standing::Unknown => (),
-            };
+            }
+            Standing::Unknown => (),
+        };
+
+        #[allow(clippy::comparison_chain)]
+        if self.streak > 0 {
+            self.longest_win_streak =
+                std::cmp::max(self.longest_win_streak, self.streak as u32);
+        } else if self.streak < 0 {
+            self.longest_loss_streak = std::cmp::max(
+                self.longest_loss_streak,
+                self.streak.unsigned_abs(),
+            );
+        }
 
-            #[allow(clippy::comparison_chain)]
-            if streak > 0 {
-                longest_win_streak =
-                    std::cmp::max(longest_win_streak, streak as u32);
-            } else if streak < 0 {
-                longest_loss_streak =
-                    std::cmp::max(longest_loss_streak, streak.abs() as u32);
+        self.last_standing = p.stats.standing;
+
+        for d in &p.stats.derived {
+            match self.out.derived.iter_mut().find(|a| a.name == d.name) {
+                Some(a) => {
+                    a.total += d.value;
+                    a.highest = a.highest.max(d.value);
+                }
+                None => self.out.derived.push(DerivedStatAggregate {
+                    name: d.name.to_string(),
+                    total: d.value,
+                    average: 0.0,
+                    highest: d.value,
+                }),
             }
+        }
 
-            last_standing = p.stats.standing;
-
-            if p.stats.extended.is_some() {
-                has_extended = true;
-                let e = p.stats.extended.as_ref().unwrap();
-                extended.weapon_kills_ability += e.weapon_kills_ability;
-                extended.weapon_kills_grenade += e.weapon_kills_grenade;
-                extended.weapon_kills_melee += e.weapon_kills_melee;
-                extended.weapon_kills_super += e.weapon_kills_super;
-                extended.all_medals_earned += e.all_medals_earned;
-                extended.precision_kills += e.precision_kills;
-
-                extended.highest_precision_kills =
-                    max(extended.highest_precision_kills, e.precision_kills);
-                extended.highest_weapon_kills_ability = max(
-                    extended.highest_weapon_kills_ability,
-                    e.weapon_kills_ability,
-                );
-                extended.highest_weapon_kills_grenade = max(
-                    extended.highest_weapon_kills_grenade,
-                    e.weapon_kills_grenade,
-                );
-
-                extended.highest_weapon_kills_melee = max(
-                    extended.highest_weapon_kills_melee,
-                    e.weapon_kills_melee,
-                );
-                extended.highest_weapon_kills_super = max(
-                    extended.highest_weapon_kills_super,
-                    e.weapon_kills_super,
-                );
-                extended.highest_all_medals_earned = max(
-                    extended.highest_all_medals_earned,
-                    e.all_medals_earned,
-                );
-
-                for m in &e.medals {
-                    let key = &m.medal.id;
-
-                    match medal_hash.get_mut(key) {
-                        Some(e) => {
-                            e.count += m.count;
+        if let Some(e) = &p.stats.extended {
+            self.has_extended = true;
+            self.extended.weapon_kills_ability += e.weapon_kills_ability;
+            self.extended.weapon_kills_grenade += e.weapon_kills_grenade;
+            self.extended.weapon_kills_melee += e.weapon_kills_melee;
+            self.extended.weapon_kills_super += e.weapon_kills_super;
+            self.extended.all_medals_earned += e.all_medals_earned;
+            self.extended.precision_kills += e.precision_kills;
+            self.extended.gambit.add(&e.gambit_stats());
+
+            self.extended.highest_precision_kills =
+                max(self.extended.highest_precision_kills, e.precision_kills);
+            self.extended.highest_weapon_kills_ability = max(
+                self.extended.highest_weapon_kills_ability,
+                e.weapon_kills_ability,
+            );
+            self.extended.highest_weapon_kills_grenade = max(
+                self.extended.highest_weapon_kills_grenade,
+                e.weapon_kills_grenade,
+            );
+
+            self.extended.highest_weapon_kills_melee = max(
+                self.extended.highest_weapon_kills_melee,
+                e.weapon_kills_melee,
+            );
+            self.extended.highest_weapon_kills_super = max(
+                self.extended.highest_weapon_kills_super,
+                e.weapon_kills_super,
+            );
+            self.extended.highest_all_medals_earned = max(
+                self.extended.highest_all_medals_earned,
+                e.all_medals_earned,
+            );
+
+            for m in &e.medals {
+                let key = &m.medal.id;
+
+                match self.medal_hash.get_mut(key) {
+                    Some(e) => {
+                        e.count += m.count;
+                    }
+                    None => {
+                        let mut c = m.clone();
+                        if c.count == 0 {
+                            c.count = 1;
                         }
-                        None => {
-                            let mut c = m.clone();
-                            if c.count == 0 {
-                                c.count = 1;
-                            }
 
-                            medal_hash.insert(key.clone(), c);
-                        }
+                        self.medal_hash.insert(key.clone(), c);
                     }
                 }
+            }
 
-                for w in &e.weapons {
-                    let key = &w.weapon.id;
+            for w in &e.weapons {
+                let key = &w.weapon.id;
 
-                    let mut ws = match weapon_hash.get_mut(key) {
-                        Some(e) => {
-                            e.activity_count += 1;
-                            e.kills += w.kills;
-                            e.precision_kills += w.precision_kills;
-                            e
-                        }
-                        None => {
-                            weapon_hash.insert(*key, w.clone());
-                            weapon_hash.get_mut(key).unwrap()
-                        }
-                    };
+                let ws = match self.weapon_hash.get_mut(key) {
+                    Some(e) => {
+                        e.activity_count += 1;
+                        e.activity_ids.extend(&w.activity_ids);
+                        e.kills += w.kills;
+                        e.precision_kills += w.precision_kills;
+                        e
+                    }
+                    None => {
+                        self.weapon_hash.insert(*key, w.clone());
+                        self.weapon_hash.get_mut(key).unwrap()
+                    }
+                };
 
-                    ws.precision_kills_percent = {
-                        if ws.kills == 0 {
-                            0.0
-                        } else {
-                            (ws.precision_kills as f32 / ws.kills as f32)
-                                * 100.0
-                        }
-                    };
-                }
+                ws.precision_kills_percent = {
+                    if ws.kills == 0 {
+                        0.0
+                    } else {
+                        (ws.precision_kills as f32 / ws.kills as f32) * 100.0
+                    }
+                };
             }
         }
+    }
+
+    pub fn finish(self) -> AggregateCruciblePerformances {
+        let mut out = self.out;
+        let mut extended = self.extended;
 
-        out.longest_win_streak = longest_win_streak;
-        out.longest_loss_streak = longest_loss_streak;
+        out.longest_win_streak = self.longest_win_streak;
+        out.longest_loss_streak = self.longest_loss_streak;
 
-        if has_extended {
+        if self.has_extended {
             let mut medals: Vec<MedalStat> =
-                medal_hash.into_iter().map(|(_id, m)| m).collect();
+                self.medal_hash.into_values().collect();
 
-            medals.sort_by(|a, b| b.count.cmp(&a.count));
+            medals.sort_by_key(|b| std::cmp::Reverse(b.count));
 
             let mut weapons: Vec<WeaponStat> =
-                weapon_hash.into_iter().map(|(_id, w)| w).collect();
-            weapons.sort_by(|a, b| b.kills.cmp(&a.kills));
+                self.weapon_hash.into_values().collect();
+            weapons.sort_by_key(|b| std::cmp::Reverse(b.kills));
 
             extended.medals = medals;
             extended.weapons = weapons;
@@ -408,6 +948,10 @@ impl AggregateCruciblePerformances {
         if out.total_activities > 0 {
             out.win_rate =
                 (out.wins as f32 / out.total_activities as f32) * 100.0;
+
+            for d in out.derived.iter_mut() {
+                d.average = d.total / out.total_activities as f32;
+            }
         }
 
         out.efficiency =
@@ -419,13 +963,120 @@ impl AggregateCruciblePerformances {
 
         out
     }
+}
 
-    pub fn stat_per_game(&self, value: u32) -> f32 {
-        if self.total_activities == 0 {
+/// builds a separate AggregateCruciblePerformances for each partition (i.e.
+/// each character) one performance at a time. This is the streaming
+/// equivalent of AggregateCruciblePerformances::group_by. Partitions are
+/// returned in the order they are first found.
+#[derive(Debug)]
+pub struct PartitionedPerformanceAccumulator<K> {
+    keys: Vec<K>,
+    accumulators: HashMap<K, CruciblePerformanceAccumulator>,
+}
+
+impl<K> PartitionedPerformanceAccumulator<K>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn new() -> Self {
+        PartitionedPerformanceAccumulator {
+            keys: Vec::new(),
+            accumulators: HashMap::new(),
+        }
+    }
+
+    pub fn add_performance(&mut self, key: K, p: &CruciblePlayerPerformance) {
+        if !self.accumulators.contains_key(&key) {
+            self.keys.push(key.clone());
+        }
+
+        self.accumulators.entry(key).or_default().add_performance(p);
+    }
+
+    pub fn finish(mut self) -> Vec<(K, AggregateCruciblePerformances)> {
+        let mut out = Vec::with_capacity(self.keys.len());
+        for k in self.keys {
+            if let Some(a) = self.accumulators.remove(&k) {
+                out.push((k, a.finish()));
+            }
+        }
+
+        out
+    }
+}
+
+impl<K> Default for PartitionedPerformanceAccumulator<K>
+where
+    K: Eq + Hash + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//medal reference ids (historical stats definition ids) which mean the
+//player got the first kill of the match. The PGCR only includes them if the
+//medal was earned, so this is only derivable for modes which award them.
+const FIRST_BLOOD_MEDAL_IDS: [&str; 2] =
+    ["medalFirstBlood", "medalFirstStrike"];
+
+/// opening duel tendency for a single mode, derived from first blood medals
+#[derive(Debug, Clone)]
+pub struct OpeningDuelStats {
+    pub mode: Mode,
+    pub activities: u32,
+    pub first_bloods: u32,
+}
+
+impl OpeningDuelStats {
+    /// percent of activities where the player got the first kill
+    pub fn first_blood_rate(&self) -> f32 {
+        if self.activities == 0 {
             return 0.0;
         }
 
-        value as f32 / self.total_activities as f32
+        (self.first_bloods as f32 / self.activities as f32) * 100.0
+    }
+
+    /// aggregates first blood medals into per mode stats. Activities without
+    /// extended data are ignored.
+    pub fn with_performances(
+        performances: &[CruciblePlayerActivityPerformance],
+    ) -> Vec<OpeningDuelStats> {
+        let mut out: Vec<OpeningDuelStats> = Vec::new();
+
+        for p in performances {
+            let extended = match &p.performance.stats.extended {
+                Some(e) => e,
+                None => continue,
+            };
+
+            let first_bloods: u32 = extended
+                .medals
+                .iter()
+                .filter(|m| {
+                    FIRST_BLOOD_MEDAL_IDS.contains(&m.medal.id.as_str())
+                })
+                .map(|m| m.count)
+                .sum();
+
+            let mode = p.activity_detail.mode;
+            match out.iter_mut().find(|e| e.mode == mode) {
+                Some(e) => {
+                    e.activities += 1;
+                    e.first_bloods += first_bloods;
+                }
+                None => out.push(OpeningDuelStats {
+                    mode,
+                    activities: 1,
+                    first_bloods,
+                }),
+            }
+        }
+
+        out.sort_by_key(|b| std::cmp::Reverse(b.activities));
+        out
     }
 }
 
@@ -445,16 +1096,22 @@ pub struct ExtendedCruciblePlayerActivityPerformances {
     pub highest_weapon_kills_super: u32,
     pub highest_all_medals_earned: u32,
 
+    /// totals for gambit activities. Zero for other modes.
+    pub gambit: GambitStats,
+
     pub weapons: Vec<WeaponStat>,
     pub medals: Vec<MedalStat>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ActivityDetail {
     pub index_id: u32,
     pub id: i64,
     pub period: DateTime<Utc>,
     pub map_name: String,
+
+    /// url to the pgcr image for the map (from the manifest)
+    pub map_image_url: Option<String>,
     pub mode: Mode,
     pub platform: Platform,
     pub director_activity_hash: u32,
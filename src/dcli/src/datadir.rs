@@ -0,0 +1,223 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Layout of the dcli data directory. Files are grouped into sub directories
+//by type. The layout is versioned so we can move files around in the future
+//and automatically migrate existing data directories.
+//
+//Version 0 (unversioned) stored all files in the root of the data directory.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::activitystoreinterface::STORE_FILE_NAME;
+use crate::error::Error;
+use crate::manifestinterface::{MANIFEST_FILE_NAME, MANIFEST_INFO_FILE_NAME};
+
+pub const DATA_DIR_LAYOUT_VERSION: u32 = 1;
+
+//name of the current manifest, which is stored in the root of the manifest dir
+pub const LIVE_MANIFEST_NAME: &str = "live";
+
+//dclim keeps the last manifest under this name when it downloads a new one
+pub const PREVIOUS_MANIFEST_NAME: &str = "previous";
+
+const LAYOUT_VERSION_FILE_NAME: &str = "layout_version";
+
+const MANIFEST_DIR_NAME: &str = "manifest";
+const STORE_DIR_NAME: &str = "store";
+const CACHE_DIR_NAME: &str = "cache";
+const CONFIG_DIR_NAME: &str = "config";
+const BACKUPS_DIR_NAME: &str = "backups";
+
+pub fn manifest_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(MANIFEST_DIR_NAME)
+}
+
+/// directory for the manifest with the specified name. Named manifests (other
+/// than live) are stored in their own sub directory of the manifest directory
+pub fn named_manifest_dir(data_dir: &Path, name: &str) -> PathBuf {
+    if name == LIVE_MANIFEST_NAME {
+        manifest_dir(data_dir)
+    } else {
+        manifest_dir(data_dir).join(name)
+    }
+}
+
+/// parses a manifest name from the command line. Names are used as directory
+/// names, so may only contain letters, numbers, _ and -.
+pub fn parse_manifest_name(src: &str) -> Result<String, String> {
+    let valid = !src.is_empty()
+        && src
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+    if !valid {
+        return Err(format!(
+            "Manifest names may only contain letters, numbers, _ and - : {}",
+            src
+        ));
+    }
+
+    Ok(src.to_string())
+}
+
+pub fn store_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(STORE_DIR_NAME)
+}
+
+pub fn cache_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(CACHE_DIR_NAME)
+}
+
+pub fn config_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(CONFIG_DIR_NAME)
+}
+
+pub fn backups_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(BACKUPS_DIR_NAME)
+}
+
+/// returns the layout version of the data directory. Directories created
+/// before the layout was versioned will return 0
+pub fn read_layout_version(data_dir: &Path) -> Result<u32, Error> {
+    let path = data_dir.join(LAYOUT_VERSION_FILE_NAME);
+
+    if !path.exists() {
+        return Ok(0);
+    }
+
+    let s = fs::read_to_string(&path)?;
+    s.trim().parse::<u32>().map_err(|_e| Error::IoError {
+        description: format!(
+            "Could not parse data directory layout version : {}",
+            path.display()
+        ),
+    })
+}
+
+/// creates the directory layout, migrating files from older layouts if
+/// necessary
+pub fn init_layout(data_dir: &Path) -> Result<(), Error> {
+    let version = read_layout_version(data_dir)?;
+
+    if version > DATA_DIR_LAYOUT_VERSION {
+        return Err(Error::DataDirLayoutVersion {
+            description: format!(
+                "Data directory layout version {} is newer than supported version {}. Please update dcli.",
+                version, DATA_DIR_LAYOUT_VERSION
+            ),
+        });
+    }
+
+    for dir in [
+        manifest_dir(data_dir),
+        store_dir(data_dir),
+        cache_dir(data_dir),
+        config_dir(data_dir),
+        backups_dir(data_dir),
+    ]
+    .iter()
+    {
+        if !dir.exists() {
+            fs::create_dir_all(dir)?;
+        }
+    }
+
+    if version == DATA_DIR_LAYOUT_VERSION {
+        return Ok(());
+    }
+
+    if version < 1 {
+        migrate_v0_to_v1(data_dir)?;
+    }
+
+    fs::write(
+        data_dir.join(LAYOUT_VERSION_FILE_NAME),
+        format!("{}", DATA_DIR_LAYOUT_VERSION),
+    )?;
+
+    Ok(())
+}
+
+//moves manifest and store files from the root of the data dir into their
+//own sub directories
+fn migrate_v0_to_v1(data_dir: &Path) -> Result<(), Error> {
+    let manifest_files = [MANIFEST_FILE_NAME, MANIFEST_INFO_FILE_NAME];
+    for f in manifest_files.iter() {
+        move_file(data_dir, &manifest_dir(data_dir), f)?;
+    }
+
+    //include the sqlite journal files, and an encrypted store if it exists
+    let store_files = [
+        STORE_FILE_NAME.to_string(),
+        format!("{}-wal", STORE_FILE_NAME),
+        format!("{}-shm", STORE_FILE_NAME),
+        format!("{}.enc", STORE_FILE_NAME),
+    ];
+    for f in store_files.iter() {
+        move_file(data_dir, &store_dir(data_dir), f)?;
+    }
+
+    Ok(())
+}
+
+fn move_file(from_dir: &Path, to_dir: &Path, name: &str) -> Result<(), Error> {
+    let src = from_dir.join(name);
+
+    if !src.exists() {
+        return Ok(());
+    }
+
+    let dest = to_dir.join(name);
+    eprintln!("Moving {} to {}", src.display(), dest.display());
+    fs::rename(src, dest)?;
+
+    Ok(())
+}
+
+/// prints out the paths of all of the files and directories used by dcli
+pub fn print_paths(data_dir: &Path) {
+    let col_w = 20;
+    let paths: Vec<(&str, PathBuf)> = vec![
+        ("Data directory", data_dir.to_path_buf()),
+        ("Manifest", manifest_dir(data_dir).join(MANIFEST_FILE_NAME)),
+        (
+            "Manifest info",
+            manifest_dir(data_dir).join(MANIFEST_INFO_FILE_NAME),
+        ),
+        ("Activity store", store_dir(data_dir).join(STORE_FILE_NAME)),
+        ("Cache", cache_dir(data_dir)),
+        ("Config", config_dir(data_dir)),
+        ("Backups", backups_dir(data_dir)),
+    ];
+
+    println!(
+        "{:<0col_w$}{}",
+        "Layout version",
+        DATA_DIR_LAYOUT_VERSION,
+        col_w = col_w
+    );
+    for (name, path) in paths {
+        println!("{:<0col_w$}{}", name, path.display(), col_w = col_w);
+    }
+}
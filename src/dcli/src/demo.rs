@@ -0,0 +1,110 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Sample data used by the --demo flag of the report tools. The data
+//directory is rebuilt from bundled sql fixtures (a small activity store and
+//a manifest extract) each time, so the tools can show real output without
+//an API key, member id or an initial sync. All of the players and
+//activities in the fixtures are fictional.
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode};
+use sqlx::{ConnectOptions, Connection};
+
+use crate::activitystoreinterface::{ActivityStoreInterface, STORE_FILE_NAME};
+use crate::datadir;
+use crate::enums::platform::Platform;
+use crate::error::Error;
+use crate::manifestinterface::MANIFEST_FILE_NAME;
+
+const DEMO_STORE_DATA: &str = include_str!("../demo/demo_store.sql");
+const DEMO_MANIFEST_DATA: &str = include_str!("../demo/demo_manifest.sql");
+
+const DEMO_DIR_NAME: &str = "dcli_demo";
+
+/// member id of the player the demo data is for
+pub const DEMO_MEMBER_ID: &str = "4611686018400000001";
+
+/// platform of the player the demo data is for
+pub const DEMO_PLATFORM: Platform = Platform::Steam;
+
+/// creates a data directory containing the demo activity store and
+/// manifest, and returns its path.
+///
+/// Any existing demo directory is replaced, so activity dates are always
+/// relative to when the tool was run.
+pub async fn create_demo_data_dir() -> Result<PathBuf, Error> {
+    let data_dir = std::env::temp_dir().join(DEMO_DIR_NAME);
+
+    if data_dir.exists() {
+        std::fs::remove_dir_all(&data_dir)?;
+    }
+
+    std::fs::create_dir_all(&data_dir)?;
+    datadir::init_layout(&data_dir)?;
+
+    let store_path = datadir::store_dir(&data_dir).join(STORE_FILE_NAME);
+    load_fixture(&store_path, true, &[DEMO_STORE_DATA]).await?;
+
+    let manifest_dir =
+        datadir::named_manifest_dir(&data_dir, datadir::LIVE_MANIFEST_NAME);
+    std::fs::create_dir_all(&manifest_dir)?;
+    load_fixture(
+        &manifest_dir.join(MANIFEST_FILE_NAME),
+        false,
+        &[DEMO_MANIFEST_DATA],
+    )
+    .await?;
+
+    Ok(data_dir)
+}
+
+//creates a new sqlite db at the path and runs the sql against it. If
+//store_schema is true, the current activity store schema is created first,
+//so the store doesn't need to be upgraded when it is opened.
+async fn load_fixture(
+    path: &Path,
+    store_schema: bool,
+    sql: &[&str],
+) -> Result<(), Error> {
+    let connection_string = format!("{}", path.display());
+
+    let mut db = SqliteConnectOptions::from_str(&connection_string)?
+        .journal_mode(SqliteJournalMode::Memory)
+        .create_if_missing(true)
+        .connect()
+        .await?;
+
+    if store_schema {
+        ActivityStoreInterface::upgrade_schema(&mut db, None).await?;
+    }
+
+    for s in sql {
+        sqlx::query(s).execute(&mut db).await?;
+    }
+
+    db.close().await?;
+
+    Ok(())
+}
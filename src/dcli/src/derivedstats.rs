@@ -0,0 +1,151 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Registry of derived stats defined by library consumers. A derived stat is a
+//named value calculated from a player's stats for a single activity. Once
+//registered, derived stats are calculated for every performance loaded from
+//the activity store, and are included in aggregates, csv exports and json
+//output.
+
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use serde_derive::Serialize;
+
+use crate::crucible::CrucibleStats;
+use crate::error::Error;
+use crate::export::ACTIVITY_PERFORMANCE_COLUMNS;
+
+type DerivedStatFn = dyn Fn(&CrucibleStats) -> f32 + Send + Sync;
+
+static REGISTRY: RwLock<Vec<DerivedStat>> = RwLock::new(Vec::new());
+
+#[derive(Clone)]
+pub struct DerivedStat {
+    pub name: String,
+    calculate: Arc<DerivedStatFn>,
+}
+
+impl DerivedStat {
+    pub fn value(&self, stats: &CrucibleStats) -> f32 {
+        (self.calculate)(stats)
+    }
+}
+
+impl fmt::Debug for DerivedStat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DerivedStat")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+/// the value of a derived stat for a single activity
+#[derive(Debug, Clone, Serialize)]
+pub struct DerivedStatValue {
+    pub name: String,
+    pub value: f32,
+}
+
+/// a derived stat aggregated across activities
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DerivedStatAggregate {
+    pub name: String,
+    pub total: f32,
+    pub average: f32,
+    pub highest: f32,
+}
+
+/// registers a derived stat. The name is used as the column name in exports
+/// and as the key in json output, so it must be unique, and must not be the
+/// same as one of the built in export columns.
+///
+/// Stats should be registered before performances are loaded from the store,
+/// as values are calculated when the performances are loaded.
+pub fn register<F>(name: &str, calculate: F) -> Result<(), Error>
+where
+    F: Fn(&CrucibleStats) -> f32 + Send + Sync + 'static,
+{
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(Error::DerivedStat {
+            description: "Derived stat name cannot be empty.".to_string(),
+        });
+    }
+
+    if ACTIVITY_PERFORMANCE_COLUMNS.contains(&name) {
+        return Err(Error::DerivedStat {
+            description: format!("{} is a built in stat.", name),
+        });
+    }
+
+    let mut registry = REGISTRY.write().unwrap_or_else(|e| e.into_inner());
+    if registry.iter().any(|s| s.name == name) {
+        return Err(Error::DerivedStat {
+            description: format!("{} has already been registered.", name),
+        });
+    }
+
+    registry.push(DerivedStat {
+        name: name.to_string(),
+        calculate: Arc::new(calculate),
+    });
+
+    Ok(())
+}
+
+/// removes the derived stat with the name. Returns whether it was registered.
+pub fn unregister(name: &str) -> bool {
+    let mut registry = REGISTRY.write().unwrap_or_else(|e| e.into_inner());
+    let len = registry.len();
+    registry.retain(|s| s.name != name);
+
+    registry.len() != len
+}
+
+/// all registered derived stats, in the order they were registered
+pub fn registered() -> Vec<DerivedStat> {
+    REGISTRY.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// names of all registered derived stats, in the order they were registered
+pub fn registered_names() -> Vec<String> {
+    REGISTRY
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|s| s.name.to_string())
+        .collect()
+}
+
+/// calculates the value of each registered derived stat for the stats
+pub fn calculate(stats: &CrucibleStats) -> Vec<DerivedStatValue> {
+    REGISTRY
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|s| DerivedStatValue {
+            name: s.name.to_string(),
+            value: s.value(stats),
+        })
+        .collect()
+}
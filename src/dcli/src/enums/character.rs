@@ -53,6 +53,45 @@ impl FromStr for CharacterClassSelection {
     }
 }
 
+/****************CharacterReport *******************/
+/// how stats are reported when more than one character is selected (i.e.
+/// with CharacterClassSelection::All)
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum CharacterReport {
+    /// stats for all selected characters are merged into account stats
+    Combined,
+
+    /// stats are also broken down for each character
+    PerCharacter,
+}
+
+impl FromStr for CharacterReport {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        //wrap in String so we can convert to lower case
+        let s = String::from(s).to_lowercase();
+
+        //get a slice to get a &str for the match
+        match &s[..] {
+            "combined" => Ok(CharacterReport::Combined),
+            "per_character" => Ok(CharacterReport::PerCharacter),
+            _ => Err("Unknown CharacterReport type"),
+        }
+    }
+}
+
+impl fmt::Display for CharacterReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let out = match self {
+            CharacterReport::Combined => "combined",
+            CharacterReport::PerCharacter => "per_character",
+        };
+
+        check_width(out, f)
+    }
+}
+
 /****************CharacterGender *******************/
 #[derive(
     PartialEq, Eq, Clone, Copy, Serialize_repr, Deserialize_repr, Debug,
@@ -122,6 +161,24 @@ impl CharacterClass {
         }
     }
 }
+
+impl FromStr for CharacterClass {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        //wrap in String so we can convert to lower case
+        let s = String::from(s).to_lowercase();
+
+        //get a slice to get a &str for the match
+        match &s[..] {
+            "titan" => Ok(CharacterClass::Titan),
+            "hunter" => Ok(CharacterClass::Hunter),
+            "warlock" => Ok(CharacterClass::Warlock),
+            _ => Err("Unknown CharacterClass type"),
+        }
+    }
+}
+
 fn check_width(s: &str, f: &mut fmt::Formatter) -> fmt::Result {
     if let Some(width) = f.width() {
         write!(f, "{:width$}", s.to_string(), width = width)
@@ -22,8 +22,10 @@
 
 use std::fmt;
 
+use serde_derive::Serialize;
+
 ///Destiny 2 Platforms
-#[derive(PartialEq, Clone, Copy, Debug)]
+#[derive(PartialEq, Clone, Copy, Debug, Serialize)]
 #[repr(i32)]
 pub enum CompletionReason {
     ObjectiveComplete = 0,
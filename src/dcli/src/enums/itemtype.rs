@@ -20,9 +20,13 @@
 * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 */
 
-use serde_repr::{Deserialize_repr, Serialize_repr};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(PartialEq, Debug, Clone, Deserialize_repr, Serialize_repr)]
+//item types are serialized as their ids. Ids added by bungie after this version
+//of dcli was released deserialize to Unrecognized (carrying the id), so new
+//weapon types don't break syncing or reports.
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 #[repr(i32)]
 pub enum ItemType {
     Unknown = -1,
@@ -53,9 +57,102 @@ pub enum ItemType {
     Wrapper = 27,
     SeasonalArtifact = 28,
     Finisher = 29,
+
+    //id not known by this version of dcli
+    Unrecognized(i32),
+}
+
+impl ItemType {
+    /// returns the type for the id. Ids which aren't known return
+    /// ItemType::Unrecognized.
+    pub fn from_id(id: i32) -> ItemType {
+        match id {
+            -1 => ItemType::Unknown,
+            0 => ItemType::None,
+            1 => ItemType::Currency,
+            2 => ItemType::Armor,
+            3 => ItemType::Weapon,
+            7 => ItemType::Message,
+            8 => ItemType::Engram,
+            9 => ItemType::Consumable,
+            10 => ItemType::ExchangeMaterial,
+            11 => ItemType::MissionReward,
+            12 => ItemType::QuestStep,
+            13 => ItemType::QuestStepComplete,
+            14 => ItemType::Emblem,
+            15 => ItemType::Quest,
+            16 => ItemType::Subclass,
+            17 => ItemType::ClanBanner,
+            18 => ItemType::Aura,
+            19 => ItemType::Mod,
+            20 => ItemType::Dummy,
+            21 => ItemType::Ship,
+            22 => ItemType::Vehicle,
+            23 => ItemType::Emote,
+            24 => ItemType::Ghost,
+            25 => ItemType::Package,
+            26 => ItemType::Bounty,
+            27 => ItemType::Wrapper,
+            28 => ItemType::SeasonalArtifact,
+            29 => ItemType::Finisher,
+            _ => ItemType::Unrecognized(id),
+        }
+    }
+
+    pub fn to_id(&self) -> i32 {
+        match self {
+            ItemType::Unknown => -1,
+            ItemType::None => 0,
+            ItemType::Currency => 1,
+            ItemType::Armor => 2,
+            ItemType::Weapon => 3,
+            ItemType::Message => 7,
+            ItemType::Engram => 8,
+            ItemType::Consumable => 9,
+            ItemType::ExchangeMaterial => 10,
+            ItemType::MissionReward => 11,
+            ItemType::QuestStep => 12,
+            ItemType::QuestStepComplete => 13,
+            ItemType::Emblem => 14,
+            ItemType::Quest => 15,
+            ItemType::Subclass => 16,
+            ItemType::ClanBanner => 17,
+            ItemType::Aura => 18,
+            ItemType::Mod => 19,
+            ItemType::Dummy => 20,
+            ItemType::Ship => 21,
+            ItemType::Vehicle => 22,
+            ItemType::Emote => 23,
+            ItemType::Ghost => 24,
+            ItemType::Package => 25,
+            ItemType::Bounty => 26,
+            ItemType::Wrapper => 27,
+            ItemType::SeasonalArtifact => 28,
+            ItemType::Finisher => 29,
+            ItemType::Unrecognized(id) => *id,
+        }
+    }
+}
+
+impl Serialize for ItemType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_i32(self.to_id())
+    }
+}
+
+impl<'de> Deserialize<'de> for ItemType {
+    fn deserialize<D>(deserializer: D) -> Result<ItemType, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(ItemType::from_id(i32::deserialize(deserializer)?))
+    }
 }
 
-#[derive(PartialEq, Debug, Clone, Deserialize_repr, Serialize_repr)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 #[repr(i32)]
 pub enum ItemSubType {
     Unknown = -1,
@@ -89,6 +186,105 @@ pub enum ItemSubType {
     ClassArmor = 30,
     Bow = 31,
     DummyRepeatableBounty = 32,
+
+    //id not known by this version of dcli
+    Unrecognized(i32),
+}
+
+impl ItemSubType {
+    /// returns the type for the id. Ids which aren't known return
+    /// ItemSubType::Unrecognized.
+    pub fn from_id(id: i32) -> ItemSubType {
+        match id {
+            -1 => ItemSubType::Unknown,
+            0 => ItemSubType::None,
+            1 => ItemSubType::Crucible,
+            2 => ItemSubType::Vanguard,
+            5 => ItemSubType::Exotic,
+            6 => ItemSubType::AutoRifle,
+            7 => ItemSubType::Shotgun,
+            8 => ItemSubType::Machinegun,
+            9 => ItemSubType::HandCannon,
+            10 => ItemSubType::RocketLauncher,
+            11 => ItemSubType::FusionRifle,
+            12 => ItemSubType::SniperRifle,
+            13 => ItemSubType::PulseRifle,
+            14 => ItemSubType::ScoutRifle,
+            16 => ItemSubType::Crm,
+            17 => ItemSubType::Sidearm,
+            18 => ItemSubType::Sword,
+            19 => ItemSubType::Mask,
+            20 => ItemSubType::Shader,
+            21 => ItemSubType::Ornament,
+            22 => ItemSubType::FusionRifleLine,
+            23 => ItemSubType::GrenadeLauncher,
+            24 => ItemSubType::SubmachineGun,
+            25 => ItemSubType::TraceRifle,
+            26 => ItemSubType::HelmetArmor,
+            27 => ItemSubType::GauntletsArmor,
+            28 => ItemSubType::ChestArmor,
+            29 => ItemSubType::LegArmor,
+            30 => ItemSubType::ClassArmor,
+            31 => ItemSubType::Bow,
+            32 => ItemSubType::DummyRepeatableBounty,
+            _ => ItemSubType::Unrecognized(id),
+        }
+    }
+
+    pub fn to_id(&self) -> i32 {
+        match self {
+            ItemSubType::Unknown => -1,
+            ItemSubType::None => 0,
+            ItemSubType::Crucible => 1,
+            ItemSubType::Vanguard => 2,
+            ItemSubType::Exotic => 5,
+            ItemSubType::AutoRifle => 6,
+            ItemSubType::Shotgun => 7,
+            ItemSubType::Machinegun => 8,
+            ItemSubType::HandCannon => 9,
+            ItemSubType::RocketLauncher => 10,
+            ItemSubType::FusionRifle => 11,
+            ItemSubType::SniperRifle => 12,
+            ItemSubType::PulseRifle => 13,
+            ItemSubType::ScoutRifle => 14,
+            ItemSubType::Crm => 16,
+            ItemSubType::Sidearm => 17,
+            ItemSubType::Sword => 18,
+            ItemSubType::Mask => 19,
+            ItemSubType::Shader => 20,
+            ItemSubType::Ornament => 21,
+            ItemSubType::FusionRifleLine => 22,
+            ItemSubType::GrenadeLauncher => 23,
+            ItemSubType::SubmachineGun => 24,
+            ItemSubType::TraceRifle => 25,
+            ItemSubType::HelmetArmor => 26,
+            ItemSubType::GauntletsArmor => 27,
+            ItemSubType::ChestArmor => 28,
+            ItemSubType::LegArmor => 29,
+            ItemSubType::ClassArmor => 30,
+            ItemSubType::Bow => 31,
+            ItemSubType::DummyRepeatableBounty => 32,
+            ItemSubType::Unrecognized(id) => *id,
+        }
+    }
+}
+
+impl Serialize for ItemSubType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_i32(self.to_id())
+    }
+}
+
+impl<'de> Deserialize<'de> for ItemSubType {
+    fn deserialize<D>(deserializer: D) -> Result<ItemSubType, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(ItemSubType::from_id(i32::deserialize(deserializer)?))
+    }
 }
 
 impl std::fmt::Display for ItemSubType {
@@ -96,6 +292,7 @@ impl std::fmt::Display for ItemSubType {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let out = match self {
             ItemSubType::Unknown => "Unknown".to_string(),
+            ItemSubType::Unrecognized(id) => format!("Unknown ({})", id),
             ItemSubType::AutoRifle => "Auto Rifle".to_string(),
             ItemSubType::Machinegun => "Machine Gun".to_string(),
             ItemSubType::HandCannon => "Hand Cannon".to_string(),
@@ -23,9 +23,11 @@
 pub mod character;
 pub mod completionreason;
 pub mod itemtype;
+pub mod language;
 pub mod medaltier;
 pub mod mode;
 pub mod moment;
 pub mod platform;
 pub mod standing;
+pub mod statprofile;
 pub mod weaponsort;
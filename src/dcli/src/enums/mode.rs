@@ -20,14 +20,17 @@
 * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 */
 
+use std::collections::HashMap;
 use std::fmt;
 use std::str::FromStr;
 
 use crate::error::Error;
-use serde_repr::{Deserialize_repr, Serialize_repr};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 //https://bungie-net.github.io/multi/schema_Destiny-HistoricalStats-Definitions-DestinyActivityModeType.html#schema_Destiny-HistoricalStats-Definitions-DestinyActivityModeType
-#[derive(PartialEq, Clone, Copy, Debug, Serialize_repr, Deserialize_repr)]
+//serialized as the mode id. Unknown ids deserialize to Mode::Unknown so new
+//modes don't break parsing api responses.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
 #[repr(u32)]
 pub enum Mode {
     None = 0,
@@ -107,9 +110,24 @@ pub enum Mode {
     Dungeon = 82,
     Sundial = 83,
     TrialsOfOsiris = 84,
+
+    //mode id not known by this version of dcli
+    Unknown(u32),
 }
 
 impl Mode {
+    /// name of the mode for displaying to users, from the mode names loaded
+    /// from the manifest (see ManifestInterface::get_mode_display_names),
+    /// keyed by mode id. Modes without a name use their built in name.
+    /// Display always uses the built in name, so machine readable output
+    /// doesnt change with the manifest or its language.
+    pub fn display_name(&self, names: &HashMap<u32, String>) -> String {
+        match names.get(&self.to_id()) {
+            Some(e) => e.to_string(),
+            None => self.to_string(),
+        }
+    }
+
     pub fn from_id(id: u32) -> Result<Mode, Error> {
         match id {
             0 => Ok(Mode::None),
@@ -189,16 +207,122 @@ impl Mode {
             82 => Ok(Mode::Dungeon),
             83 => Ok(Mode::Sundial),
             84 => Ok(Mode::TrialsOfOsiris),
-            _ => Err(Error::UnknownEnumValue),
+            //new modes added by bungie which we dont know about yet
+            _ => Ok(Mode::Unknown(id)),
         }
     }
 
     pub fn to_id(&self) -> u32 {
-        *self as u32
+        match self {
+            Mode::None => 0,
+            Mode::Story => 2,
+            Mode::Strike => 3,
+            Mode::Raid => 4,
+            Mode::AllPvP => 5,
+            Mode::Patrol => 6,
+            Mode::AllPvE => 7,
+            Mode::Reserved9 => 9,
+            Mode::Control => 10,
+            Mode::Reserved11 => 11,
+            Mode::Clash => 12,
+            Mode::Reserved13 => 13,
+            Mode::CrimsonDoubles => 15,
+            Mode::Nightfall => 16,
+            Mode::HeroicNightfall => 17,
+            Mode::AllStrikes => 18,
+            Mode::IronBanner => 19,
+            Mode::Reserved20 => 20,
+            Mode::Reserved21 => 21,
+            Mode::Reserved22 => 22,
+            Mode::Reserved24 => 24,
+            Mode::AllMayhem => 25,
+            Mode::Reserved26 => 26,
+            Mode::Reserved27 => 27,
+            Mode::Reserved28 => 28,
+            Mode::Reserved29 => 29,
+            Mode::Reserved30 => 30,
+            Mode::Supremacy => 31,
+            Mode::PrivateMatchesAll => 32,
+            Mode::Survival => 37,
+            Mode::Countdown => 38,
+            Mode::TrialsOfTheNine => 39,
+            Mode::Social => 40,
+            Mode::TrialsCountdown => 41,
+            Mode::TrialsSurvival => 42,
+            Mode::IronBannerControl => 43,
+            Mode::IronBannerClash => 44,
+            Mode::IronBannerSupremacy => 45,
+            Mode::ScoredNightfall => 46,
+            Mode::ScoredHeroicNightfall => 47,
+            Mode::Rumble => 48,
+            Mode::AllDoubles => 49,
+            Mode::Doubles => 50,
+            Mode::PrivateMatchesClash => 51,
+            Mode::PrivateMatchesControl => 52,
+            Mode::PrivateMatchesSupremacy => 53,
+            Mode::PrivateMatchesCountdown => 54,
+            Mode::PrivateMatchesSurvival => 55,
+            Mode::PrivateMatchesMayhem => 56,
+            Mode::PrivateMatchesRumble => 57,
+            Mode::HeroicAdventure => 58,
+            Mode::Showdown => 59,
+            Mode::Lockdown => 60,
+            Mode::Scorched => 61,
+            Mode::ScorchedTeam => 62,
+            Mode::Gambit => 63,
+            Mode::AllPvECompetitive => 64,
+            Mode::Breakthrough => 65,
+            Mode::BlackArmoryRun => 66,
+            Mode::Salvage => 67,
+            Mode::IronBannerSalvage => 68,
+            Mode::PvPCompetitive => 69,
+            Mode::PvPQuickplay => 70,
+            Mode::ClashQuickplay => 71,
+            Mode::ClashCompetitive => 72,
+            Mode::ControlQuickplay => 73,
+            Mode::ControlCompetitive => 74,
+            Mode::GambitPrime => 75,
+            Mode::Reckoning => 76,
+            Mode::Menagerie => 77,
+            Mode::VexOffensive => 78,
+            Mode::NightmareHunt => 79,
+            Mode::Elimination => 80,
+            Mode::Momentum => 81,
+            Mode::Dungeon => 82,
+            Mode::Sundial => 83,
+            Mode::TrialsOfOsiris => 84,
+            Mode::Unknown(id) => *id,
+        }
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, Mode::Unknown(_))
     }
 
     pub fn is_gambit(&self) -> bool {
-        *self == Mode::Gambit || *self == Mode::GambitPrime
+        *self == Mode::Gambit
+            || *self == Mode::GambitPrime
+            || *self == Mode::AllPvECompetitive
+    }
+
+    pub fn is_pve(&self) -> bool {
+        *self == Mode::Story
+            || *self == Mode::Strike
+            || *self == Mode::Raid
+            || *self == Mode::AllPvE
+            || *self == Mode::Nightfall
+            || *self == Mode::HeroicNightfall
+            || *self == Mode::AllStrikes
+            || *self == Mode::ScoredNightfall
+            || *self == Mode::ScoredHeroicNightfall
+            || *self == Mode::HeroicAdventure
+            || *self == Mode::BlackArmoryRun
+            || *self == Mode::Reckoning
+            || *self == Mode::Menagerie
+            || *self == Mode::VexOffensive
+            || *self == Mode::NightmareHunt
+            || *self == Mode::Dungeon
+            || *self == Mode::Sundial
     }
 
     pub fn is_rumble(&self) -> bool {
@@ -333,8 +457,8 @@ impl FromStr for Mode {
             "scorched" => Ok(Mode::Scorched),
             "scorched_team" => Ok(Mode::ScorchedTeam),
             "gambit" => Ok(Mode::Gambit),
-            //TODO: is this just all gambit?
-            "pve_competitive" => Ok(Mode::AllPvECompetitive),
+            //all gambit modes
+            "pve_competitive" | "all_gambit" => Ok(Mode::AllPvECompetitive),
             "breakthrough" => Ok(Mode::Breakthrough),
             "black_armory_run" => Ok(Mode::BlackArmoryRun),
             "salvage" => Ok(Mode::Salvage),
@@ -361,6 +485,27 @@ impl FromStr for Mode {
     }
 }
 
+impl Serialize for Mode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u32(self.to_id())
+    }
+}
+
+impl<'de> Deserialize<'de> for Mode {
+    fn deserialize<D>(deserializer: D) -> Result<Mode, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let id = u32::deserialize(deserializer)?;
+
+        //from_id doesnt fail for unknown ids
+        Mode::from_id(id).map_err(serde::de::Error::custom)
+    }
+}
+
 impl fmt::Display for Mode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let out = match self {
@@ -441,6 +586,7 @@ impl fmt::Display for Mode {
             Mode::Dungeon => "Dungeon",
             Mode::Sundial => "Sundial",
             Mode::TrialsOfOsiris => "Trials Of Osiris",
+            Mode::Unknown(id) => return write!(f, "Unknown ({})", id),
         };
 
         write!(f, "{}", out)
@@ -22,24 +22,22 @@
 
 use std::fmt;
 
+use serde_derive::Serialize;
+
 use crate::enums::mode::Mode;
 
 pub const STANDING_UNKNOWN_MAGIC_NUMBER: u32 = 2325;
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize)]
 #[repr(u32)]
+#[derive(Default)]
 pub enum Standing {
     Victory = 0,
     Defeat = 1,
+    #[default]
     Unknown = STANDING_UNKNOWN_MAGIC_NUMBER,
 }
 
-impl Default for Standing {
-    fn default() -> Self {
-        Standing::Unknown
-    }
-}
-
 impl Standing {
     //todo: any value except 1 is probably defeat
     pub fn from_value(value: u32) -> Standing {
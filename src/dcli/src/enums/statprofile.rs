@@ -0,0 +1,86 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use crate::enums::mode::Mode;
+
+/// the set of stats which are most relevant for a mode, used by tools to
+/// choose which columns to display for an activity
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum StatProfile {
+    //team based elimination of opponents (clash, mayhem, etc)
+    Standard,
+
+    //round based modes, where the team score is the number of rounds won
+    Rounds,
+
+    //zone and objective modes, where score reflects objective play
+    Objective,
+
+    //free for all modes, where players are ranked by score
+    FreeForAll,
+
+    //gambit modes, where motes and invasions matter more than kills
+    Gambit,
+}
+
+impl StatProfile {
+    pub fn from_mode(mode: &Mode) -> StatProfile {
+        match mode {
+            Mode::Survival
+            | Mode::Countdown
+            | Mode::TrialsOfTheNine
+            | Mode::TrialsCountdown
+            | Mode::TrialsSurvival
+            | Mode::PrivateMatchesCountdown
+            | Mode::PrivateMatchesSurvival
+            | Mode::Showdown
+            | Mode::Breakthrough
+            | Mode::PvPCompetitive
+            | Mode::Elimination
+            | Mode::TrialsOfOsiris => StatProfile::Rounds,
+
+            Mode::Control
+            | Mode::IronBanner
+            | Mode::Supremacy
+            | Mode::IronBannerControl
+            | Mode::IronBannerSupremacy
+            | Mode::PrivateMatchesControl
+            | Mode::PrivateMatchesSupremacy
+            | Mode::Lockdown
+            | Mode::Salvage
+            | Mode::IronBannerSalvage
+            | Mode::ControlQuickplay
+            | Mode::ControlCompetitive
+            | Mode::Momentum => StatProfile::Objective,
+
+            Mode::Rumble | Mode::PrivateMatchesRumble => {
+                StatProfile::FreeForAll
+            }
+
+            Mode::Gambit | Mode::GambitPrime | Mode::AllPvECompetitive => {
+                StatProfile::Gambit
+            }
+
+            _ => StatProfile::Standard,
+        }
+    }
+}
@@ -65,6 +65,19 @@ pub enum Error {
     CharacterDoesNotExist,
     ActivityNotFound,
     DateTimePeriodOrder,
+    UnsupportedOutputFormat { description: String },
+    Encryption { description: String },
+    DataDirLayoutVersion { description: String },
+    ResponseTooLarge { description: String },
+    SyncInProgress { description: String },
+    ApiThrottled { description: String, seconds: u32 },
+    Cancelled,
+    Template { description: String },
+    DerivedStat { description: String },
+    Discord { description: String },
+    AmbiguousPlayerName { description: String },
+    SyncedMembersUnknown { description: String },
+    StoreTooLarge { description: String },
 }
 
 impl Display for Error {
@@ -162,6 +175,43 @@ impl Display for Error {
             Error::DateTimePeriodOrder  => {
                 write!(f, "Start date must be before end date.")
             },
+            Error::UnsupportedOutputFormat { description } => {
+                write!(f, "Unsupported output format : {}", description)
+            },
+            Error::Encryption { description } => {
+                write!(f, "Error encrypting / decrypting data store : {}", description)
+            },
+            Error::DataDirLayoutVersion { description } => {
+                write!(f, "Unsupported data directory layout : {}", description)
+            },
+            Error::ResponseTooLarge { description } => {
+                write!(f, "API response was too large : {}", description)
+            },
+            Error::SyncInProgress { description } => {
+                write!(f, "Sync in progress : {}", description)
+            },
+            Error::ApiThrottled { description, .. } => {
+                write!(f, "API request was throttled : {}", description)
+            },
+            Error::Cancelled => write!(f, "Operation was cancelled."),
+            Error::Template { description } => {
+                write!(f, "Error with output template. {}", description)
+            },
+            Error::DerivedStat { description } => {
+                write!(f, "Invalid derived stat : {}", description)
+            },
+            Error::Discord { description } => {
+                write!(f, "Error communicating with Discord. {}", description)
+            },
+            Error::AmbiguousPlayerName { description } => {
+                write!(f, "More than one player has the specified name. Use the member id, or add the platform to the name (i.e. name:steam). {}", description)
+            },
+            Error::SyncedMembersUnknown { description } => {
+                write!(f, "Could not determine which members have been synced into the activity store. {}", description)
+            },
+            Error::StoreTooLarge { description } => {
+                write!(f, "Activity store is too large to copy. {}", description)
+            },
         }
     }
 }
@@ -229,3 +279,19 @@ impl From<chrono::format::ParseError> for Error {
         }
     }
 }
+
+impl From<handlebars::TemplateError> for Error {
+    fn from(err: handlebars::TemplateError) -> Error {
+        Error::Template {
+            description: format!("{}", err),
+        }
+    }
+}
+
+impl From<handlebars::TemplateRenderError> for Error {
+    fn from(err: handlebars::TemplateRenderError) -> Error {
+        Error::Template {
+            description: format!("{}", err),
+        }
+    }
+}
@@ -0,0 +1,616 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Support for bulk exporting data from the activity store. Exports include
+//every player in every stored activity (not just the synced member), and can
+//optionally be anonymized for building shared datasets.
+//
+//Also includes a csv writer for exporting a single player's activity history,
+//and the store tables which can be exported to parquet files for analytics.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::crucible::{CrucibleActivity, CruciblePlayerActivityPerformance};
+use crate::derivedstats;
+use crate::enums::character::CharacterClass;
+use crate::enums::mode::Mode;
+use crate::enums::platform::Platform;
+use crate::enums::standing::Standing;
+use crate::error::Error;
+use crate::parquet::{ParquetColumn, ParquetType};
+
+/// a single player's performance in a single activity, flattened for export
+#[derive(Debug, Clone)]
+pub struct PlayerPerformanceRow {
+    pub activity_id: i64,
+    pub period: String,
+    pub mode: Mode,
+    pub platform: Platform,
+    pub reference_id: u32,
+    pub director_activity_hash: u32,
+
+    pub member_id: String,
+    pub display_name: String,
+    pub member_platform: Platform,
+    pub character_id: String,
+    pub class_type: CharacterClass,
+    pub light_level: i32,
+
+    pub team: i32,
+    pub standing: Standing,
+    pub completed: bool,
+    pub start_seconds: u32,
+    pub time_played_seconds: u32,
+    pub activity_duration_seconds: u32,
+    pub player_count: u32,
+    pub team_score: u32,
+
+    pub score: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+    pub opponents_defeated: u32,
+    pub precision_kills: u32,
+    pub weapon_kills_ability: u32,
+    pub weapon_kills_grenade: u32,
+    pub weapon_kills_melee: u32,
+    pub weapon_kills_super: u32,
+    pub all_medals_earned: u32,
+}
+
+impl PlayerPerformanceRow {
+    pub const COLUMNS: &'static [&'static str] = &[
+        "activity_id",
+        "period",
+        "mode",
+        "platform",
+        "reference_id",
+        "director_activity_hash",
+        "member_id",
+        "display_name",
+        "member_platform",
+        "character_id",
+        "class",
+        "light_level",
+        "team",
+        "standing",
+        "completed",
+        "start_seconds",
+        "time_played_seconds",
+        "activity_duration_seconds",
+        "player_count",
+        "team_score",
+        "score",
+        "kills",
+        "deaths",
+        "assists",
+        "opponents_defeated",
+        "precision_kills",
+        "weapon_kills_ability",
+        "weapon_kills_grenade",
+        "weapon_kills_melee",
+        "weapon_kills_super",
+        "all_medals_earned",
+    ];
+
+    /// values for the row, in the same order as COLUMNS
+    pub fn values(&self) -> Vec<String> {
+        vec![
+            self.activity_id.to_string(),
+            self.period.to_string(),
+            format!("{:?}", self.mode),
+            format!("{}", self.platform),
+            self.reference_id.to_string(),
+            self.director_activity_hash.to_string(),
+            self.member_id.to_string(),
+            self.display_name.to_string(),
+            format!("{}", self.member_platform),
+            self.character_id.to_string(),
+            format!("{}", self.class_type),
+            self.light_level.to_string(),
+            self.team.to_string(),
+            format!("{}", self.standing),
+            self.completed.to_string(),
+            self.start_seconds.to_string(),
+            self.time_played_seconds.to_string(),
+            self.activity_duration_seconds.to_string(),
+            self.player_count.to_string(),
+            self.team_score.to_string(),
+            self.score.to_string(),
+            self.kills.to_string(),
+            self.deaths.to_string(),
+            self.assists.to_string(),
+            self.opponents_defeated.to_string(),
+            self.precision_kills.to_string(),
+            self.weapon_kills_ability.to_string(),
+            self.weapon_kills_grenade.to_string(),
+            self.weapon_kills_melee.to_string(),
+            self.weapon_kills_super.to_string(),
+            self.all_medals_earned.to_string(),
+        ]
+    }
+}
+
+/// replaces identifying player information in exported rows.
+///
+/// member and character ids are replaced with an HMAC-SHA256 of the id keyed
+/// with the salt, so the same player has the same id across the export (and
+/// across exports, and dcli releases, using the same salt), but can't be
+/// looked up. Activity and fireteam ids are remapped the same way. Display
+/// names are removed.
+pub struct Anonymizer {
+    salt: String,
+}
+
+impl Anonymizer {
+    pub fn new(salt: &str) -> Anonymizer {
+        Anonymizer {
+            salt: salt.to_string(),
+        }
+    }
+
+    fn digest(&self, kind: &str, value: &str) -> [u8; 32] {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.salt.as_bytes())
+            .expect("hmac accepts keys of any length");
+
+        //kind keeps ids of different types from mapping to the same value
+        mac.update(kind.as_bytes());
+        mac.update(b":");
+        mac.update(value.as_bytes());
+        mac.finalize().into_bytes().into()
+    }
+
+    fn hash(&self, kind: &str, value: &str) -> String {
+        self.digest(kind, value)[..16]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    //maps an id to a positive i64, so it can still be used as a key
+    fn remap_id(&self, kind: &str, id: i64) -> i64 {
+        let d = self.digest(kind, &id.to_string());
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&d[..8]);
+        (u64::from_be_bytes(bytes) >> 1) as i64
+    }
+
+    /// anonymizes the row. The activity id is remapped (so rows from the
+    /// same activity can still be grouped) and the period is reduced to the
+    /// date, so activities can't be looked up on the api.
+    pub fn anonymize(&self, row: &mut PlayerPerformanceRow) {
+        row.activity_id = self.remap_id("activity", row.activity_id);
+        row.period = row.period.chars().take(10).collect();
+        row.member_id = self.hash("member", &row.member_id);
+        row.character_id = self.hash("character", &row.character_id);
+        row.display_name = "".to_string();
+    }
+
+    /// anonymizes every player in the activity, along with the activity id
+    /// and period
+    pub fn anonymize_activity(&self, activity: &mut CrucibleActivity) {
+        let details = &mut activity.details;
+        details.id = self.remap_id("activity", details.id);
+        details.index_id = 0;
+        details.period = details.period.date().and_hms(0, 0, 0);
+
+        for t in activity.teams.values_mut() {
+            for p in t.player_performances.iter_mut() {
+                p.player.member_id = self.hash("member", &p.player.member_id);
+                p.player.character_id =
+                    self.hash("character", &p.player.character_id);
+                p.player.display_name = "".to_string();
+                p.stats.fireteam_id =
+                    p.stats.fireteam_id.map(|id| self.remap_id("fireteam", id));
+            }
+        }
+    }
+}
+
+/// writes tsv rows, splitting output across multiple files with at most
+/// rows_per_file rows each. Each file includes a header row.
+///
+/// files are named {prefix}_{index}.tsv, starting at index 0.
+pub struct ChunkedTsvWriter {
+    dir: PathBuf,
+    prefix: String,
+    rows_per_file: usize,
+    header: Vec<String>,
+
+    writer: Option<BufWriter<File>>,
+    file_index: usize,
+    rows_in_file: usize,
+    files: Vec<PathBuf>,
+}
+
+impl ChunkedTsvWriter {
+    pub fn new(
+        dir: &Path,
+        prefix: &str,
+        rows_per_file: usize,
+        header: &[&str],
+    ) -> Result<ChunkedTsvWriter, Error> {
+        std::fs::create_dir_all(dir)?;
+
+        Ok(ChunkedTsvWriter {
+            dir: dir.to_path_buf(),
+            prefix: prefix.to_string(),
+            rows_per_file: std::cmp::max(rows_per_file, 1),
+            header: header.iter().map(|h| h.to_string()).collect(),
+            writer: None,
+            file_index: 0,
+            rows_in_file: 0,
+            files: Vec::new(),
+        })
+    }
+
+    pub fn write_row(&mut self, values: &[String]) -> Result<(), Error> {
+        if self.writer.is_none() || self.rows_in_file >= self.rows_per_file {
+            self.start_file()?;
+        }
+
+        let line: Vec<String> = values.iter().map(|v| escape_tsv(v)).collect();
+
+        //writer is always set by start_file
+        let writer = self.writer.as_mut().unwrap();
+        writeln!(writer, "{}", line.join("\t"))?;
+        self.rows_in_file += 1;
+
+        Ok(())
+    }
+
+    fn start_file(&mut self) -> Result<(), Error> {
+        if let Some(mut w) = self.writer.take() {
+            w.flush()?;
+            self.file_index += 1;
+        }
+
+        let path = self
+            .dir
+            .join(format!("{}_{}.tsv", self.prefix, self.file_index));
+        let mut writer = BufWriter::new(File::create(&path)?);
+        writeln!(writer, "{}", self.header.join("\t"))?;
+
+        self.writer = Some(writer);
+        self.rows_in_file = 0;
+        self.files.push(path);
+
+        Ok(())
+    }
+
+    /// flushes any remaining data and returns the paths of the files written
+    pub fn finish(mut self) -> Result<Vec<PathBuf>, Error> {
+        if let Some(mut w) = self.writer.take() {
+            w.flush()?;
+        }
+
+        Ok(self.files)
+    }
+}
+
+//tabs and new lines would break the tsv format
+fn escape_tsv(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+/// columns for csv exports of a player's activity history. One row per
+/// activity, with all of the player's stats for the activity.
+pub const ACTIVITY_PERFORMANCE_COLUMNS: &[&str] = &[
+    "activity_id",
+    "period",
+    "mode",
+    "map_name",
+    "platform",
+    "reference_id",
+    "director_activity_hash",
+    "member_id",
+    "character_id",
+    "display_name",
+    "class",
+    "light_level",
+    "standing",
+    "team",
+    "completed",
+    "completion_reason",
+    "score",
+    "team_score",
+    "kills",
+    "deaths",
+    "assists",
+    "opponents_defeated",
+    "efficiency",
+    "kills_deaths_ratio",
+    "kills_deaths_assists",
+    "average_score_per_kill",
+    "average_score_per_life",
+    "activity_duration_seconds",
+    "start_seconds",
+    "time_played_seconds",
+    "player_count",
+    "precision_kills",
+    "weapon_kills_ability",
+    "weapon_kills_grenade",
+    "weapon_kills_melee",
+    "weapon_kills_super",
+    "all_medals_earned",
+];
+
+/// columns for csv exports of a player's activity history, including a
+/// column for each registered derived stat.
+pub fn activity_performance_columns() -> Vec<String> {
+    let mut out: Vec<String> = ACTIVITY_PERFORMANCE_COLUMNS
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+    out.extend(derivedstats::registered_names());
+
+    out
+}
+
+/// values for the activity, in the same order as
+/// activity_performance_columns(). Extended and derived stats are empty if
+/// they are not available.
+pub fn activity_performance_values(
+    data: &CruciblePlayerActivityPerformance,
+) -> Vec<String> {
+    let a = &data.activity_detail;
+    let p = &data.performance.player;
+    let s = &data.performance.stats;
+
+    let mut out = vec![
+        a.id.to_string(),
+        a.period.to_rfc3339(),
+        format!("{:?}", a.mode),
+        a.map_name.to_string(),
+        format!("{}", a.platform),
+        a.reference_id.to_string(),
+        a.director_activity_hash.to_string(),
+        p.member_id.to_string(),
+        p.character_id.to_string(),
+        p.display_name.to_string(),
+        format!("{}", p.class_type),
+        p.light_level.to_string(),
+        format!("{}", s.standing),
+        s.team.to_string(),
+        s.completed.to_string(),
+        format!("{}", s.completion_reason),
+        s.score.to_string(),
+        s.team_score.to_string(),
+        s.kills.to_string(),
+        s.deaths.to_string(),
+        s.assists.to_string(),
+        s.opponents_defeated.to_string(),
+        s.efficiency.to_string(),
+        s.kills_deaths_ratio.to_string(),
+        s.kills_deaths_assists.to_string(),
+        s.average_score_per_kill.to_string(),
+        s.average_score_per_life.to_string(),
+        s.activity_duration_seconds.to_string(),
+        s.start_seconds.to_string(),
+        s.time_played_seconds.to_string(),
+        s.player_count.to_string(),
+    ];
+
+    match &s.extended {
+        Some(e) => out.extend(vec![
+            e.precision_kills.to_string(),
+            e.weapon_kills_ability.to_string(),
+            e.weapon_kills_grenade.to_string(),
+            e.weapon_kills_melee.to_string(),
+            e.weapon_kills_super.to_string(),
+            e.all_medals_earned.to_string(),
+        ]),
+        None => out.extend(vec!["".to_string(); 6]),
+    }
+
+    for name in derivedstats::registered_names() {
+        let value = s
+            .derived
+            .iter()
+            .find(|d| d.name == name)
+            .map(|d| d.value.to_string())
+            .unwrap_or_default();
+        out.push(value);
+    }
+
+    out
+}
+
+/// writes rows in csv format (RFC 4180), which can be opened directly in
+/// spreadsheet apps
+pub struct CsvWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> CsvWriter<W> {
+    pub fn new(writer: W) -> CsvWriter<W> {
+        CsvWriter { writer }
+    }
+
+    pub fn write_header(&mut self, columns: &[&str]) -> Result<(), Error> {
+        let values: Vec<String> =
+            columns.iter().map(|c| c.to_string()).collect();
+        self.write_row(&values)
+    }
+
+    pub fn write_row(&mut self, values: &[String]) -> Result<(), Error> {
+        let line: Vec<String> = values.iter().map(|v| escape_csv(v)).collect();
+
+        //csv spec uses crlf line endings
+        write!(self.writer, "{}\r\n", line.join(","))?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), Error> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+//values containing delimiters, quotes or new lines are quoted, with quotes
+//doubled
+fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// a store table which can be exported as a parquet file
+#[derive(Debug)]
+pub struct ExportTable {
+    pub name: &'static str,
+    pub columns: &'static [ParquetColumn],
+
+    //sql expression for the activity row id a table row belongs to, used to
+    //filter exported rows by mode. None for tables not tied to an activity,
+    //which are always exported in full.
+    pub activity: Option<&'static str>,
+}
+
+const fn int64(name: &'static str) -> ParquetColumn {
+    ParquetColumn::new(name, ParquetType::Int64)
+}
+
+const fn double(name: &'static str) -> ParquetColumn {
+    ParquetColumn::new(name, ParquetType::Double)
+}
+
+const fn utf8(name: &'static str) -> ParquetColumn {
+    ParquetColumn::new(name, ParquetType::Utf8)
+}
+
+//columns added by schema migrations, which are null for existing rows
+const fn optional_int64(name: &'static str) -> ParquetColumn {
+    ParquetColumn::optional(name, ParquetType::Int64)
+}
+
+const fn optional_utf8(name: &'static str) -> ParquetColumn {
+    ParquetColumn::optional(name, ParquetType::Utf8)
+}
+
+/// tables exported by dcliex --parquet. Rows reference each other by the
+/// store row ids (the id columns), so the files can be joined the same way
+/// as the tables in the activity store.
+pub const PARQUET_EXPORT_TABLES: &[ExportTable] = &[
+    ExportTable {
+        name: "member",
+        columns: &[
+            int64("id"),
+            utf8("member_id"),
+            int64("platform_id"),
+            utf8("display_name"),
+        ],
+        activity: None,
+    },
+    ExportTable {
+        name: "character",
+        columns: &[
+            int64("id"),
+            utf8("character_id"),
+            int64("member"),
+            int64("class"),
+        ],
+        activity: None,
+    },
+    ExportTable {
+        name: "activity",
+        columns: &[
+            int64("id"),
+            int64("activity_id"),
+            ParquetColumn::new("period", ParquetType::TimestampMillis),
+            int64("mode"),
+            int64("platform"),
+            int64("director_activity_hash"),
+            int64("reference_id"),
+            optional_int64("season"),
+        ],
+        activity: Some("activity.id"),
+    },
+    ExportTable {
+        name: "team_result",
+        columns: &[
+            int64("id"),
+            int64("team_id"),
+            int64("activity"),
+            int64("score"),
+            int64("standing"),
+            optional_utf8("team_name"),
+        ],
+        activity: Some("team_result.activity"),
+    },
+    ExportTable {
+        name: "character_activity_stats",
+        columns: &[
+            int64("id"),
+            int64("character"),
+            int64("activity"),
+            int64("assists"),
+            int64("score"),
+            int64("kills"),
+            int64("deaths"),
+            double("average_score_per_kill"),
+            double("average_score_per_life"),
+            int64("completed"),
+            int64("opponents_defeated"),
+            int64("activity_duration_seconds"),
+            int64("standing"),
+            int64("team"),
+            int64("completion_reason"),
+            int64("start_seconds"),
+            int64("time_played_seconds"),
+            int64("player_count"),
+            int64("team_score"),
+            int64("precision_kills"),
+            int64("weapon_kills_ability"),
+            int64("weapon_kills_grenade"),
+            int64("weapon_kills_melee"),
+            int64("weapon_kills_super"),
+            int64("all_medals_earned"),
+            int64("light_level"),
+            optional_int64("fireteam_id"),
+        ],
+        activity: Some("character_activity_stats.activity"),
+    },
+    ExportTable {
+        name: "weapon_result",
+        columns: &[
+            int64("id"),
+            int64("reference_id"),
+            int64("kills"),
+            int64("precision_kills"),
+            double("kills_precision_kills_ratio"),
+            int64("character_activity_stats"),
+        ],
+        activity: Some(
+            "(select activity from character_activity_stats where \
+            character_activity_stats.id = \
+            weapon_result.character_activity_stats)",
+        ),
+    },
+];
@@ -0,0 +1,83 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use serde_derive::Serialize;
+
+use crate::crucible::MedalStat;
+
+//gambit specific values from the extended PGCR values. These are stored along
+//with the medals (in the medal_result table), keyed by these ids.
+const MOTES_DEPOSITED_ID: &str = "motesDeposited";
+const MOTES_LOST_ID: &str = "motesLost";
+const MOTES_DENIED_ID: &str = "motesDenied";
+const INVASION_KILLS_ID: &str = "invasionKills";
+const INVADER_KILLS_ID: &str = "invaderKills";
+const BLOCKERS_SENT_IDS: [&str; 3] = [
+    "smallBlockersSent",
+    "mediumBlockersSent",
+    "largeBlockersSent",
+];
+
+/// gambit stats for a player in an activity, or summed across activities
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct GambitStats {
+    pub motes_deposited: u32,
+    pub motes_lost: u32,
+    pub motes_denied: u32,
+
+    /// kills made while invading the other team
+    pub invasion_kills: u32,
+
+    /// kills of players invading the player's team
+    pub invader_kills: u32,
+    pub blockers_sent: u32,
+}
+
+impl GambitStats {
+    pub fn with_medals(medals: &[MedalStat]) -> GambitStats {
+        let count = |id: &str| -> u32 {
+            medals
+                .iter()
+                .filter(|m| m.medal.id == id)
+                .map(|m| m.count)
+                .sum()
+        };
+
+        GambitStats {
+            motes_deposited: count(MOTES_DEPOSITED_ID),
+            motes_lost: count(MOTES_LOST_ID),
+            motes_denied: count(MOTES_DENIED_ID),
+            invasion_kills: count(INVASION_KILLS_ID),
+            invader_kills: count(INVADER_KILLS_ID),
+            blockers_sent: BLOCKERS_SENT_IDS.iter().map(|e| count(e)).sum(),
+        }
+    }
+
+    pub fn add(&mut self, other: &GambitStats) {
+        self.motes_deposited += other.motes_deposited;
+        self.motes_lost += other.motes_lost;
+        self.motes_denied += other.motes_denied;
+        self.invasion_kills += other.invasion_kills;
+        self.invader_kills += other.invader_kills;
+        self.blockers_sent += other.blockers_sent;
+    }
+}
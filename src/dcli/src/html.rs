@@ -0,0 +1,77 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//styles are inlined so reports are a single file which can be shared
+pub const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif;
+    background: #1b1d22; color: #e4e4e4; margin: 0 auto; max-width: 1100px;
+    padding: 16px; }
+h1, h2, h3 { font-weight: 600; }
+.map { width: 100%; max-height: 240px; object-fit: cover; border-radius: 4px; }
+.summary { color: #b0b0b0; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 16px; }
+th, td { padding: 4px 8px; text-align: right; white-space: nowrap; }
+th:first-child, td:first-child { text-align: left; }
+th { border-bottom: 2px solid #555; }
+tr:nth-child(even) td { background: #23262d; }
+tr.total td { border-top: 1px solid #555; font-weight: 600; }
+td.text { text-align: left; }
+.medal { display: inline-block; margin: 4px 12px 4px 0; }
+.medal img { width: 24px; height: 24px; vertical-align: middle; }
+.note { color: #888; font-size: 0.9em; }
+a { color: #7fb2ff; }
+"#;
+
+pub fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+pub fn table_header(columns: &[&str]) -> String {
+    let cells: Vec<String> = columns
+        .iter()
+        .map(|c| format!("<th>{}</th>", escape(c)))
+        .collect();
+
+    format!("<thead><tr>{}</tr></thead>\n", cells.join(""))
+}
+
+//values are expected to already be escaped
+pub fn table_row(values: &[String], class: Option<&str>) -> String {
+    let cells: Vec<String> =
+        values.iter().map(|v| format!("<td>{}</td>", v)).collect();
+
+    match class {
+        Some(e) => format!("<tr class=\"{}\">{}</tr>\n", e, cells.join("")),
+        None => format!("<tr>{}</tr>\n", cells.join("")),
+    }
+}
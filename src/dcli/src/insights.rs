@@ -0,0 +1,182 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Derived insights calculated from aggregated activity data.
+
+use std::collections::HashMap;
+
+use crate::crucible::CruciblePlayerActivityPerformance;
+use crate::enums::itemtype::ItemSubType;
+use crate::utils::{calculate_kills_deaths_ratio, format_f32};
+
+/// minimum number of activities a weapon type must be used in before we
+/// generate insights for it
+pub const DEFAULT_MIN_INSIGHT_ACTIVITIES: u32 = 5;
+
+/// player performance in activities where they got kills with a weapon type,
+/// compared against their overall performance
+#[derive(Debug, Clone)]
+pub struct WeaponTypeInsight {
+    pub item_sub_type: ItemSubType,
+    pub activities: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    pub kills_deaths_ratio: f32,
+
+    /// percent difference between the K/D for this type and the overall K/D
+    pub diff_percent: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct WeaponTypeInsights {
+    pub overall_kills_deaths_ratio: f32,
+
+    /// sorted from best to worst performing
+    pub insights: Vec<WeaponTypeInsight>,
+}
+
+impl WeaponTypeInsights {
+    /// calculates K/D for activities where each weapon type was used (got at
+    /// least one kill), and compares it against the overall K/D. Types used
+    /// in less than min_activities activities are ignored.
+    pub fn with_performances(
+        performances: &[CruciblePlayerActivityPerformance],
+        min_activities: u32,
+    ) -> WeaponTypeInsights {
+        let mut total_kills = 0;
+        let mut total_deaths = 0;
+
+        //type -> (activities, kills, deaths)
+        let mut by_type: HashMap<ItemSubType, (u32, u32, u32)> = HashMap::new();
+
+        for p in performances {
+            let stats = &p.performance.stats;
+            total_kills += stats.kills;
+            total_deaths += stats.deaths;
+
+            let extended = match &stats.extended {
+                Some(e) => e,
+                None => continue,
+            };
+
+            let mut types: Vec<ItemSubType> = extended
+                .weapons
+                .iter()
+                .filter(|w| w.kills > 0)
+                .map(|w| w.weapon.item_sub_type)
+                .collect();
+            types.sort_by_key(|t| t.to_id());
+            types.dedup();
+
+            for t in types {
+                let e = by_type.entry(t).or_insert((0, 0, 0));
+                e.0 += 1;
+                e.1 += stats.kills;
+                e.2 += stats.deaths;
+            }
+        }
+
+        let overall_kills_deaths_ratio =
+            calculate_kills_deaths_ratio(total_kills, total_deaths);
+
+        let mut insights: Vec<WeaponTypeInsight> = by_type
+            .into_iter()
+            .filter(|(_, v)| v.0 >= min_activities)
+            .map(|(t, (activities, kills, deaths))| {
+                let kills_deaths_ratio =
+                    calculate_kills_deaths_ratio(kills, deaths);
+
+                let diff_percent = if overall_kills_deaths_ratio > 0.0 {
+                    ((kills_deaths_ratio - overall_kills_deaths_ratio)
+                        / overall_kills_deaths_ratio)
+                        * 100.0
+                } else {
+                    0.0
+                };
+
+                WeaponTypeInsight {
+                    item_sub_type: t,
+                    activities,
+                    kills,
+                    deaths,
+                    kills_deaths_ratio,
+                    diff_percent,
+                }
+            })
+            .collect();
+
+        insights.sort_by(|a, b| {
+            b.kills_deaths_ratio
+                .partial_cmp(&a.kills_deaths_ratio)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        WeaponTypeInsights {
+            overall_kills_deaths_ratio,
+            insights,
+        }
+    }
+
+    /// human readable hints comparing the best and worst performing types,
+    /// i.e. "You perform 18% better with Pulse Rifle than Hand Cannon".
+    pub fn hints(&self) -> Vec<String> {
+        let mut out = Vec::new();
+
+        if self.insights.len() < 2 {
+            return out;
+        }
+
+        let best = &self.insights[0];
+        let worst = &self.insights[self.insights.len() - 1];
+
+        if worst.kills_deaths_ratio > 0.0 {
+            let diff = ((best.kills_deaths_ratio - worst.kills_deaths_ratio)
+                / worst.kills_deaths_ratio)
+                * 100.0;
+
+            out.push(format!(
+                "You perform {}% better with {} than {}",
+                format_f32(diff, 0),
+                best.item_sub_type,
+                worst.item_sub_type
+            ));
+        }
+
+        for i in &self.insights {
+            if i.diff_percent >= 10.0 {
+                out.push(format!(
+                    "{} is over performing ({}% above your average K/D)",
+                    i.item_sub_type,
+                    format_f32(i.diff_percent, 0)
+                ));
+            } else if i.diff_percent <= -10.0 {
+                out.push(format!(
+                    "{} is under performing ({}% below your average K/D)",
+                    i.item_sub_type,
+                    format_f32(i.diff_percent.abs(), 0)
+                ));
+            }
+        }
+
+        out
+    }
+}
@@ -20,19 +20,47 @@
 * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 */
 
+pub mod accountcontext;
 pub mod activitystoreinterface;
 pub mod apiclient;
 pub mod apiinterface;
 pub mod apiutils;
+pub mod calendar;
+pub mod cancellation;
 pub mod character;
+pub mod competitive;
 pub mod crucible;
 pub mod cruciblestats;
+pub mod datadir;
+pub mod demo;
+pub mod derivedstats;
 pub mod emblem;
 pub mod enums;
 pub mod error;
+pub mod export;
+pub mod gambit;
+pub mod html;
+pub mod insights;
+pub mod links;
 pub mod manifest;
 pub mod manifestinterface;
+pub mod markdown;
 pub mod output;
+pub mod parquet;
+pub mod pve;
+pub mod ratelimit;
+pub mod render;
 pub mod response;
+pub mod session;
 pub mod statscontainer;
+#[cfg(feature = "encryption")]
+pub mod storeencryption;
+pub mod storeexport;
+pub mod storequery;
+pub mod storerows;
+pub mod storeschema;
+pub mod syncevent;
+pub mod synclock;
+pub mod template;
+pub mod timing;
 pub mod utils;
@@ -0,0 +1,170 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Builds links to activity and player pages on external sites, for inclusion
+//in tool output.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde_derive::Serialize;
+
+use crate::apiutils::RESOURCE_BASE_URL;
+use crate::enums::platform::Platform;
+
+#[derive(PartialEq, Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkSite {
+    Bungie,
+    CrucibleReport,
+    DestinyTracker,
+}
+
+impl LinkSite {
+    pub const ALL: &'static [LinkSite] = &[
+        LinkSite::Bungie,
+        LinkSite::CrucibleReport,
+        LinkSite::DestinyTracker,
+    ];
+
+    /// url for the post game carnage report of the specified activity
+    pub fn activity_url(&self, instance_id: i64) -> String {
+        match self {
+            LinkSite::Bungie => {
+                format!("{}/en/PGCR/{}", RESOURCE_BASE_URL, instance_id)
+            }
+            LinkSite::CrucibleReport => {
+                format!("https://crucible.report/pgcr/{}", instance_id)
+            }
+            LinkSite::DestinyTracker => format!(
+                "https://destinytracker.com/destiny-2/pgcr/{}",
+                instance_id
+            ),
+        }
+    }
+
+    /// url for the player's page on the site. None if the site doesn't
+    /// have player pages.
+    pub fn player_url(
+        &self,
+        member_id: &str,
+        platform: &Platform,
+    ) -> Option<String> {
+        match self {
+            LinkSite::Bungie => None,
+            LinkSite::CrucibleReport => Some(format!(
+                "https://crucible.report/report/{}/{}",
+                platform.to_id(),
+                member_id
+            )),
+            LinkSite::DestinyTracker => {
+                let platform_name = match platform {
+                    Platform::Xbox => "xbl",
+                    Platform::Playstation => "psn",
+                    Platform::Steam => "steam",
+                    Platform::Stadia => "stadia",
+                    _ => return None,
+                };
+
+                Some(format!(
+                    "https://destinytracker.com/destiny-2/profile/{}/{}/overview",
+                    platform_name, member_id
+                ))
+            }
+        }
+    }
+}
+
+impl FromStr for LinkSite {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.trim().to_lowercase()[..] {
+            "bungie" => Ok(LinkSite::Bungie),
+            "crucible_report" => Ok(LinkSite::CrucibleReport),
+            "destiny_tracker" => Ok(LinkSite::DestinyTracker),
+            _ => Err(format!("Unknown link site : {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for LinkSite {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let out = match self {
+            LinkSite::Bungie => "Bungie",
+            LinkSite::CrucibleReport => "Crucible Report",
+            LinkSite::DestinyTracker => "Destiny Tracker",
+        };
+
+        write!(f, "{}", out)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Link {
+    pub site: LinkSite,
+    pub url: String,
+}
+
+/// builds activity and player links for a set of preferred sites
+#[derive(Debug, Clone)]
+pub struct LinkBuilder {
+    sites: Vec<LinkSite>,
+}
+
+impl Default for LinkBuilder {
+    fn default() -> Self {
+        LinkBuilder::new(LinkSite::ALL)
+    }
+}
+
+impl LinkBuilder {
+    pub fn new(sites: &[LinkSite]) -> LinkBuilder {
+        LinkBuilder {
+            sites: sites.to_vec(),
+        }
+    }
+
+    pub fn activity_links(&self, instance_id: i64) -> Vec<Link> {
+        self.sites
+            .iter()
+            .map(|s| Link {
+                site: *s,
+                url: s.activity_url(instance_id),
+            })
+            .collect()
+    }
+
+    pub fn player_links(
+        &self,
+        member_id: &str,
+        platform: &Platform,
+    ) -> Vec<Link> {
+        self.sites
+            .iter()
+            .filter_map(|s| {
+                s.player_url(member_id, platform)
+                    .map(|url| Link { site: *s, url })
+            })
+            .collect()
+    }
+}
@@ -1,10 +1,13 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
 use serde_derive::{Deserialize, Serialize};
 
 use crate::enums::itemtype::{ItemSubType, ItemType};
 use crate::enums::medaltier::MedalTier;
 use crate::response::utils::prepend_base_url_option;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct DisplayPropertiesData {
     pub description: Option<String>,
     pub name: String,
@@ -113,3 +116,100 @@ pub struct ActivityTypeDefinitionData {
     #[serde(rename = "displayProperties")]
     pub display_properties: DisplayPropertiesData,
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ActivityModeDefinitionData {
+    #[serde(rename = "hash")]
+    pub id: u32,
+
+    #[serde(rename = "displayProperties")]
+    pub display_properties: DisplayPropertiesData,
+
+    //DestinyActivityModeType id. Not a Mode, since the manifest can contain
+    //modes we don't know about yet
+    #[serde(rename = "modeType")]
+    pub mode_type: u32,
+
+    #[serde(rename = "friendlyName", default)]
+    pub friendly_name: String,
+
+    #[serde(default)]
+    pub display: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RecordDefinitionData {
+    #[serde(rename = "hash")]
+    pub id: u32,
+
+    #[serde(rename = "displayProperties")]
+    pub display_properties: DisplayPropertiesData,
+
+    #[serde(rename = "titleInfo")]
+    pub title_info: Option<TitleInfoData>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TitleInfoData {
+    #[serde(rename = "hasTitle")]
+    pub has_title: bool,
+
+    //keyed by Male / Female
+    #[serde(rename = "titlesByGender", default)]
+    pub titles_by_gender: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SeasonDefinitionData {
+    #[serde(rename = "hash")]
+    pub id: u32,
+
+    #[serde(rename = "displayProperties")]
+    pub display_properties: DisplayPropertiesData,
+
+    #[serde(rename = "seasonNumber")]
+    pub season_number: u32,
+
+    //not set for seasons which have not been scheduled yet
+    #[serde(default, rename = "startDate")]
+    pub start_date: Option<DateTime<Utc>>,
+
+    #[serde(default, rename = "endDate")]
+    pub end_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProgressionStepData {
+    #[serde(rename = "stepName")]
+    pub step_name: String,
+
+    #[serde(rename = "progressTotal")]
+    pub progress_total: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProgressionDefinitionData {
+    #[serde(rename = "hash")]
+    pub id: u32,
+
+    pub steps: Vec<ProgressionStepData>,
+}
+
+impl ProgressionDefinitionData {
+    /// returns the step for the total progress. Each step's progress_total
+    /// is the progress needed to complete that step.
+    pub fn step_for_progress(
+        &self,
+        progress: u32,
+    ) -> Option<&ProgressionStepData> {
+        let mut total = 0;
+        for s in &self.steps {
+            total += s.progress_total;
+            if progress < total {
+                return Some(s);
+            }
+        }
+
+        self.steps.last()
+    }
+}
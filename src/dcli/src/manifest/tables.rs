@@ -0,0 +1,40 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Names of commonly used definition tables in the Destiny 2 manifest.
+//Definitions in these (and any other hash keyed tables in the manifest) can
+//be retrieved with ManifestInterface::get_definition, including definition
+//types which dcli doesnt have its own types and methods for.
+
+pub const ACTIVITY: &str = "DestinyActivityDefinition";
+pub const ACTIVITY_MODE: &str = "DestinyActivityModeDefinition";
+pub const ACTIVITY_TYPE: &str = "DestinyActivityTypeDefinition";
+pub const DESTINATION: &str = "DestinyDestinationDefinition";
+pub const INVENTORY_ITEM: &str = "DestinyInventoryItemDefinition";
+pub const PLACE: &str = "DestinyPlaceDefinition";
+pub const PROGRESSION: &str = "DestinyProgressionDefinition";
+pub const RECORD: &str = "DestinyRecordDefinition";
+pub const SEASON: &str = "DestinySeasonDefinition";
+
+//keyed by the stat id rather than a hash, so can't be retrieved with
+//ManifestInterface::get_definition
+pub const HISTORICAL_STATS: &str = "DestinyHistoricalStatsDefinition";
@@ -20,25 +20,101 @@
 * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 */
 
-use std::path::PathBuf;
+use std::path::Path;
 use std::str::FromStr;
 
 use futures::TryStreamExt;
+use regex::Regex;
 use serde_derive::{Deserialize, Serialize};
 use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode};
 use sqlx::Row;
 use sqlx::{ConnectOptions, Connection, SqliteConnection};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use crate::datadir;
+use crate::enums::character::CharacterGender;
 use crate::error::Error;
 use crate::manifest::definitions::{
-    ActivityDefinitionData, ActivityTypeDefinitionData,
-    DestinationDefinitionData, DisplayPropertiesData,
-    HistoricalStatsDefinition, InventoryItemDefinitionData,
-    PlaceDefinitionData,
+    ActivityDefinitionData, ActivityModeDefinitionData,
+    ActivityTypeDefinitionData, DestinationDefinitionData,
+    DisplayPropertiesData, HistoricalStatsDefinition,
+    InventoryItemDefinitionData, PlaceDefinitionData,
+    ProgressionDefinitionData, RecordDefinitionData, SeasonDefinitionData,
 };
+use crate::manifest::tables;
+use crate::timing::{self, TimedFutureExt, TimingCategory};
 
 pub const MANIFEST_FILE_NAME: &str = "manifest.sqlite3";
+pub const MANIFEST_INFO_FILE_NAME: &str = "manifest_info.json";
+
+//names which score lower than this against a search are not considered a
+//match. A single typo in a short name scores around 0.5
+const MIN_NAME_MATCH_SCORE: f32 = 0.35;
+
+//lower cases the name, and removes punctuation, so Felwinter's Lie and
+//felwinters lie match
+fn normalize_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_alphanumeric() {
+            out.extend(c.to_lowercase());
+        } else if c.is_whitespace() && !out.ends_with(' ') {
+            out.push(' ');
+        }
+    }
+
+    out.trim().to_string()
+}
+
+//three character sequences in each word of the name, padded so the start and
+//end of words count
+fn trigrams(name: &str) -> HashSet<[char; 3]> {
+    let mut out = HashSet::new();
+    for word in name.split(' ') {
+        let chars: Vec<char> = format!("  {} ", word).chars().collect();
+        for w in chars.windows(3) {
+            out.insert([w[0], w[1], w[2]]);
+        }
+    }
+
+    out
+}
+
+//scores how well the (normalized) name matches the query, from 0.0 to 1.0.
+//Exact matches score highest, followed by names which contain the query
+//(shorter names first), followed by the trigram similarity of the names
+fn name_match_score(
+    query: &str,
+    query_trigrams: &HashSet<[char; 3]>,
+    name: &str,
+) -> f32 {
+    if name == query {
+        return 1.0;
+    }
+
+    let query_len = query.chars().count() as f32;
+    let name_len = name.chars().count() as f32;
+
+    if name.contains(query) {
+        return 0.8 + 0.19 * (query_len / name_len);
+    }
+
+    let name_trigrams = trigrams(name);
+    let shared = query_trigrams.intersection(&name_trigrams).count() as f32;
+    let total = (query_trigrams.len() + name_trigrams.len()) as f32 - shared;
+
+    if total == 0.0 {
+        return 0.0;
+    }
+
+    //a partial name can only share a fraction of the trigrams of a longer
+    //name, so similarity is measured against the query where the name is
+    //longer
+    let jaccard = shared / total;
+    let coverage = shared / query_trigrams.len().max(1) as f32;
+
+    (0.8 * jaccard.max(coverage * 0.9)).min(0.79)
+}
 
 /// Takes a Destiny 2 API has and converts it to a Destiny 2 manifest db index value
 pub fn convert_hash_to_id(hash: u32) -> i64 {
@@ -57,14 +133,33 @@ pub struct ManifestInterface {
     inventory_item_definition_cache: HashMap<i64, InventoryItemDefinitionData>,
     historical_stats_definition_cache:
         HashMap<String, HistoricalStatsDefinition>,
+
+    //tables keyed by hash (with an id column), loaded the first time a
+    //definition is retrieved by table name
+    definition_tables: Option<Vec<String>>,
 }
 
 impl ManifestInterface {
     pub async fn new(
-        manifest_dir: &PathBuf,
+        data_dir: &Path,
+        cache: bool,
+    ) -> Result<ManifestInterface, Error> {
+        ManifestInterface::new_with_name(
+            data_dir,
+            datadir::LIVE_MANIFEST_NAME,
+            cache,
+        )
+        .await
+    }
+
+    /// opens the manifest with the specified name (i.e. live or previous)
+    pub async fn new_with_name(
+        data_dir: &Path,
+        name: &str,
         cache: bool,
     ) -> Result<ManifestInterface, Error> {
-        let manifest_path = manifest_dir.join(MANIFEST_FILE_NAME);
+        let manifest_path = datadir::named_manifest_dir(data_dir, name)
+            .join(MANIFEST_FILE_NAME);
 
         if !manifest_path.exists() {
             return Err(Error::IoFileDoesNotExist {
@@ -88,7 +183,7 @@ impl ManifestInterface {
         //as it can causes errors when opening a DB in readonly mode
         //We use Memory which should provide better performance
         //since we never write to the DB
-        let db = SqliteConnectOptions::from_str(&connection_string)?
+        let db = SqliteConnectOptions::from_str(connection_string)?
             .journal_mode(SqliteJournalMode::Memory)
             .read_only(read_only)
             .connect()
@@ -140,6 +235,7 @@ impl ManifestInterface {
             activity_definition_cache: HashMap::new(),
             inventory_item_definition_cache: HashMap::new(),
             historical_stats_definition_cache: HashMap::new(),
+            definition_tables: None,
         })
     }
 
@@ -165,6 +261,7 @@ impl ManifestInterface {
             //for some reason sqlx doesnt let you bind table names
             let q = format!("SELECT json FROM {} WHERE id=?", table);
 
+            let _timer = timing::start(TimingCategory::Database);
             let mut rows =
                 sqlx::query(&q).bind(id).fetch(&mut self.manifest_db);
 
@@ -174,6 +271,133 @@ impl ManifestInterface {
 
                 let mut v: FindResult = serde_json::from_str(json)?;
                 v.raw_json = json.to_string();
+                v.table = table.to_string();
+                out.push(v);
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Searches the names of definitions in the entire manifest, and returns
+    /// up to count of the closest matches, best match first. Names are
+    /// matched ignoring case and punctuation, and partial names and names with
+    /// small typos (i.e. Felwinters for Felwinter's Lie) are matched.
+    pub async fn find_by_name(
+        &mut self,
+        name: &str,
+        count: usize,
+    ) -> Result<Vec<FindResult>, Error> {
+        let query = normalize_name(name);
+        if query.is_empty() || count == 0 {
+            return Ok(Vec::new());
+        }
+        let query_trigrams = trigrams(&query);
+
+        let tables: Vec<String> = self.get_tables_with_id_column().await?;
+
+        //only the names are scored, and the json is retrieved for the best
+        //matches, so we dont parse every definition in the manifest
+        let mut matches: Vec<(f32, usize, i64)> = Vec::new();
+        for (index, table) in tables.iter().enumerate() {
+            let q = format!(
+                "SELECT id, json_extract(json, '$.displayProperties.name') as name FROM {}",
+                table
+            );
+
+            let _timer = timing::start(TimingCategory::Database);
+            let mut rows = sqlx::query(&q).fetch(&mut self.manifest_db);
+
+            while let Some(row) = rows.try_next().await? {
+                let n: Option<&str> = row.try_get("name")?;
+                let n = match n {
+                    Some(e) if !e.is_empty() => normalize_name(e),
+                    _ => continue,
+                };
+
+                let score = name_match_score(&query, &query_trigrams, &n);
+                if score >= MIN_NAME_MATCH_SCORE {
+                    matches.push((score, index, row.try_get("id")?));
+                }
+            }
+        }
+
+        matches.sort_by(|a, b| {
+            b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal)
+        });
+        matches.truncate(count);
+
+        let mut out: Vec<FindResult> = Vec::with_capacity(matches.len());
+        for (score, index, id) in matches {
+            let table = &tables[index];
+            let q = format!("SELECT json FROM {} WHERE id=?", table);
+
+            let row = sqlx::query(&q)
+                .bind(id)
+                .fetch_one(&mut self.manifest_db)
+                .timed(TimingCategory::Database)
+                .await?;
+            let json: &str = row.try_get("json")?;
+
+            let mut v: FindResult = serde_json::from_str(json)?;
+            v.raw_json = json.to_string();
+            v.table = table.to_string();
+            v.score = Some(score);
+            out.push(v);
+        }
+
+        Ok(out)
+    }
+
+    /// Searches the json of all definitions in the specified tables (or all
+    /// tables with an id column if no tables are specified), and returns the
+    /// definitions which match the regular expression. Returns
+    /// Error::ManifestItemNotFound if a table is not in the manifest.
+    pub async fn find_by_regex(
+        &mut self,
+        regex: &Regex,
+        tables: &[String],
+    ) -> Result<Vec<FindResult>, Error> {
+        let tables: Vec<String> = if tables.is_empty() {
+            self.get_tables_with_id_column().await?
+        } else {
+            //table names cant be bound, so only search tables which are
+            //in the manifest
+            let all_tables = self.get_tables().await?;
+
+            let mut out: Vec<String> = Vec::with_capacity(tables.len());
+            for t in tables {
+                match all_tables.iter().find(|e| e.eq_ignore_ascii_case(t)) {
+                    Some(e) => out.push(e.to_string()),
+                    None => {
+                        return Err(Error::ManifestItemNotFound {
+                            description: format!(
+                                "Table not found in manifest : {}",
+                                t
+                            ),
+                        })
+                    }
+                }
+            }
+            out
+        };
+
+        let mut out: Vec<FindResult> = Vec::new();
+        for table in tables.iter() {
+            let q = format!("SELECT json FROM {}", table);
+
+            let _timer = timing::start(TimingCategory::Database);
+            let mut rows = sqlx::query(&q).fetch(&mut self.manifest_db);
+
+            while let Some(row) = rows.try_next().await? {
+                let json: &str = row.try_get("json")?;
+                if !regex.is_match(json) {
+                    continue;
+                }
+
+                let mut v: FindResult = serde_json::from_str(json)?;
+                v.raw_json = json.to_string();
+                v.table = table.to_string();
                 out.push(v);
             }
         }
@@ -186,6 +410,7 @@ impl ManifestInterface {
     ) -> Result<Vec<String>, Error> {
         let mut tables: Vec<String> = Vec::new();
 
+        let _timer = timing::start(TimingCategory::Database);
         //select all of the tables which have an id column
         let mut rows = sqlx::query("SELECT m.name as name, p.name as id FROM sqlite_master AS m JOIN pragma_table_info(m.name) AS p WHERE p.name = 'id'")
             .fetch(&mut self.manifest_db);
@@ -200,6 +425,7 @@ impl ManifestInterface {
     pub async fn get_tables(&mut self) -> Result<Vec<String>, Error> {
         let mut tables: Vec<String> = Vec::new();
 
+        let _timer = timing::start(TimingCategory::Database);
         let mut rows =
             sqlx::query("SELECT name FROM sqlite_master WHERE type='table'")
                 .fetch(&mut self.manifest_db);
@@ -212,11 +438,62 @@ impl ManifestInterface {
         Ok(tables)
     }
 
+    /// returns the name of the definition table in the manifest (as it is in
+    /// the manifest) which matches name, ignoring case, or None if the
+    /// manifest doesnt have a hash keyed table with that name.
+    pub async fn find_definition_table(
+        &mut self,
+        name: &str,
+    ) -> Result<Option<String>, Error> {
+        if self.definition_tables.is_none() {
+            self.definition_tables =
+                Some(self.get_tables_with_id_column().await?);
+        }
+
+        Ok(self
+            .definition_tables
+            .iter()
+            .flatten()
+            .find(|e| e.eq_ignore_ascii_case(name))
+            .cloned())
+    }
+
+    /// Retrieves the definition with the specified hash from the specified
+    /// manifest table (any hash keyed table in the manifest, such as those in
+    /// manifest::tables), and deserializes it to T. Allows definitions which
+    /// dcli doesnt have a method for to be retrieved, either into a custom
+    /// type, or a serde_json::Value. Returns Error::ManifestItemNotFound if
+    /// the table is not a definition table in the manifest.
+    pub async fn get_definition<T: serde::de::DeserializeOwned>(
+        &mut self,
+        table: &str,
+        hash: u32,
+    ) -> Result<Option<T>, Error> {
+        //table names cant be bound, so we only use names which are in the
+        //manifest
+        let table = match self.find_definition_table(table).await? {
+            Some(e) => e,
+            None => {
+                return Err(Error::ManifestItemNotFound {
+                    description: format!(
+                        "Unknown manifest definition table : {}",
+                        table
+                    ),
+                })
+            }
+        };
+
+        let id = convert_hash_to_id(hash);
+        let query = &format!("SELECT json FROM {} WHERE id = {}", table, id);
+
+        self.query_definition(query).await
+    }
+
     pub async fn get_activity_definition(
         &mut self,
-        id: u32,
+        hash: u32,
     ) -> Result<Option<ActivityDefinitionData>, Error> {
-        let id = convert_hash_to_id(id);
+        let id = convert_hash_to_id(hash);
 
         if self.activity_definition_cache.contains_key(&id) {
             let out = self.activity_definition_cache.get(&id).unwrap();
@@ -224,27 +501,21 @@ impl ManifestInterface {
             return Ok(Some(out.clone()));
         }
 
-        let query = &format!(
-            "SELECT json FROM DestinyActivityDefinition WHERE id = {}",
-            id
-        );
         let data: Option<ActivityDefinitionData> =
-            self.get_definition(query).await?;
+            self.get_definition(tables::ACTIVITY, hash).await?;
 
-        if data.is_some() {
-            self.activity_definition_cache
-                .insert(id, data.as_ref().unwrap().clone());
+        if let Some(e) = &data {
+            self.activity_definition_cache.insert(id, e.clone());
         }
 
         Ok(data)
     }
 
-    //might be able to make this generic
     pub async fn get_iventory_item_definition(
         &mut self,
-        id: u32,
+        hash: u32,
     ) -> Result<Option<InventoryItemDefinitionData>, Error> {
-        let id = convert_hash_to_id(id);
+        let id = convert_hash_to_id(hash);
 
         if self.inventory_item_definition_cache.contains_key(&id) {
             let out = self.inventory_item_definition_cache.get(&id).unwrap();
@@ -252,17 +523,11 @@ impl ManifestInterface {
             return Ok(Some(out.clone()));
         }
 
-        let query = &format!(
-            "SELECT json FROM DestinyInventoryItemDefinition WHERE id = {}",
-            id
-        );
-
         let data: Option<InventoryItemDefinitionData> =
-            self.get_definition(query).await?;
+            self.get_definition(tables::INVENTORY_ITEM, hash).await?;
 
-        if data.is_some() {
-            self.inventory_item_definition_cache
-                .insert(id, data.as_ref().unwrap().clone());
+        if let Some(e) = &data {
+            self.inventory_item_definition_cache.insert(id, e.clone());
         }
 
         Ok(data)
@@ -280,16 +545,17 @@ impl ManifestInterface {
         }
 
         let query = &format!(
-            "SELECT json FROM DestinyHistoricalStatsDefinition WHERE key = '{}'",
+            "SELECT json FROM {} WHERE key = '{}'",
+            tables::HISTORICAL_STATS,
             key
         );
 
         let data: Option<HistoricalStatsDefinition> =
-            self.get_definition(query).await?;
+            self.query_definition(query).await?;
 
-        if data.is_some() {
+        if let Some(e) = &data {
             self.historical_stats_definition_cache
-                .insert(key.to_string(), data.as_ref().unwrap().clone());
+                .insert(key.to_string(), e.clone());
         }
 
         Ok(data)
@@ -299,55 +565,139 @@ impl ManifestInterface {
         &mut self,
         id: u32,
     ) -> Result<Option<DestinationDefinitionData>, Error> {
-        let id = convert_hash_to_id(id);
-
-        let query = &format!(
-            "SELECT json FROM DestinyDestinationDefinition WHERE id = {}",
-            id
-        );
-        let data: Option<DestinationDefinitionData> =
-            self.get_definition(query).await?;
-
-        Ok(data)
+        self.get_definition(tables::DESTINATION, id).await
     }
 
     pub async fn get_place_definition(
         &mut self,
         id: u32,
     ) -> Result<Option<PlaceDefinitionData>, Error> {
-        let id = convert_hash_to_id(id);
-
-        let query = &format!(
-            "SELECT json FROM DestinyPlaceDefinition WHERE id = {}",
-            id
-        );
-        let data: Option<PlaceDefinitionData> =
-            self.get_definition(query).await?;
-
-        Ok(data)
+        self.get_definition(tables::PLACE, id).await
     }
 
     pub async fn get_activity_type_definition(
         &mut self,
         id: u32,
     ) -> Result<Option<ActivityTypeDefinitionData>, Error> {
-        let id = convert_hash_to_id(id);
+        self.get_definition(tables::ACTIVITY_TYPE, id).await
+    }
 
-        let query = &format!(
-            "SELECT json FROM DestinyActivityTypeDefinition WHERE id = {}",
-            id
-        );
-        let data: Option<ActivityTypeDefinitionData> =
-            self.get_definition(query).await?;
+    pub async fn get_activity_mode_definitions(
+        &mut self,
+    ) -> Result<Vec<ActivityModeDefinitionData>, Error> {
+        let query = format!("SELECT json FROM {}", tables::ACTIVITY_MODE);
 
-        Ok(data)
+        let _timer = timing::start(TimingCategory::Database);
+        let mut rows = sqlx::query(&query).fetch(&mut self.manifest_db);
+
+        let mut out: Vec<ActivityModeDefinitionData> = Vec::new();
+        while let Some(row) = rows.try_next().await? {
+            let json: &str = row.try_get_unchecked("json")?;
+            out.push(serde_json::from_str(json)?);
+        }
+
+        Ok(out)
     }
 
-    async fn get_definition<T: serde::de::DeserializeOwned>(
+    /// returns all of the season definitions in the manifest, ordered by
+    /// season number
+    pub async fn get_season_definitions(
+        &mut self,
+    ) -> Result<Vec<SeasonDefinitionData>, Error> {
+        let query = format!("SELECT json FROM {}", tables::SEASON);
+
+        let _timer = timing::start(TimingCategory::Database);
+        let mut rows = sqlx::query(&query).fetch(&mut self.manifest_db);
+
+        let mut out: Vec<SeasonDefinitionData> = Vec::new();
+        while let Some(row) = rows.try_next().await? {
+            let json: &str = row.try_get_unchecked("json")?;
+            out.push(serde_json::from_str(json)?);
+        }
+
+        out.sort_by_key(|e| e.season_number);
+
+        Ok(out)
+    }
+
+    /// returns mode display names from the manifest, keyed by mode id, for
+    /// use with Mode::display_name. If there are multiple definitions for a
+    /// mode, ones that are displayed in game are preferred.
+    pub async fn get_mode_display_names(
+        &mut self,
+    ) -> Result<HashMap<u32, String>, Error> {
+        let definitions = self.get_activity_mode_definitions().await?;
+
+        let mut names: HashMap<u32, String> = HashMap::new();
+        let mut displayed: HashMap<u32, bool> = HashMap::new();
+        for d in definitions {
+            let name = d.display_properties.name.trim();
+            if name.is_empty() {
+                continue;
+            }
+
+            let replace = match displayed.get(&d.mode_type) {
+                Some(e) => !e && d.display,
+                None => true,
+            };
+
+            if replace {
+                names.insert(d.mode_type, name.to_string());
+                displayed.insert(d.mode_type, d.display);
+            }
+        }
+
+        Ok(names)
+    }
+
+    pub async fn get_record_definition(
+        &mut self,
+        id: u32,
+    ) -> Result<Option<RecordDefinitionData>, Error> {
+        self.get_definition(tables::RECORD, id).await
+    }
+
+    pub async fn get_progression_definition(
+        &mut self,
+        id: u32,
+    ) -> Result<Option<ProgressionDefinitionData>, Error> {
+        self.get_definition(tables::PROGRESSION, id).await
+    }
+
+    /// returns the title for the specified title record, for the specified
+    /// character gender
+    pub async fn get_title(
+        &mut self,
+        title_record_hash: u32,
+        gender: &CharacterGender,
+    ) -> Result<Option<String>, Error> {
+        let record = match self.get_record_definition(title_record_hash).await?
+        {
+            Some(e) => e,
+            None => return Ok(None),
+        };
+
+        let title_info = match record.title_info {
+            Some(e) if e.has_title => e,
+            _ => return Ok(None),
+        };
+
+        let key = match gender {
+            CharacterGender::Masculine => "Male",
+            CharacterGender::Feminine => "Female",
+        };
+
+        Ok(title_info.titles_by_gender.get(key).cloned())
+    }
+
+    async fn query_definition<T: serde::de::DeserializeOwned>(
         &mut self,
         query: &str,
     ) -> Result<Option<T>, Error> {
-        let rows = sqlx::query(query).fetch_all(&mut self.manifest_db).await?;
+        let rows = sqlx::query(query)
+            .fetch_all(&mut self.manifest_db)
+            .timed(TimingCategory::Database)
+            .await?;
 
         if rows.is_empty() {
             return Ok(None);
@@ -367,6 +717,20 @@ pub struct FindResult {
     #[serde(skip)]
     pub raw_json: String,
 
-    #[serde(rename = "displayProperties")]
+    //manifest table the result was found in
+    #[serde(skip)]
+    pub table: String,
+
+    #[serde(default)]
+    pub hash: u32,
+
+    //how closely the name matched the search, from 0.0 to 1.0. Only set
+    //for results from find_by_name
+    #[serde(skip)]
+    pub score: Option<f32>,
+
+    //not all definitions have display properties (i.e. when searching
+    //with find_by_regex)
+    #[serde(default, rename = "displayProperties")]
     pub display_properties: DisplayPropertiesData,
 }
@@ -20,12 +20,20 @@
 * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 */
 
+use std::fmt;
 use std::str::FromStr;
 
+use crate::error::Error;
+
 #[derive(PartialEq, Clone, Copy, Debug)]
 pub enum Output {
     Tsv,
     Default,
+    Jsonl,
+    Statusline,
+    Json,
+    Csv,
+    Markdown,
 }
 
 impl FromStr for Output {
@@ -39,7 +47,82 @@ impl FromStr for Output {
         match &s[..] {
             "tsv" => Ok(Output::Tsv),
             "default" => Ok(Output::Default),
+            "jsonl" => Ok(Output::Jsonl),
+            "statusline" => Ok(Output::Statusline),
+            "json" => Ok(Output::Json),
+            "csv" => Ok(Output::Csv),
+            "markdown" => Ok(Output::Markdown),
             _ => Err("Unknown Output type"),
         }
     }
 }
+
+impl Output {
+    /// returns an error if the output format is not one of the formats
+    /// supported by the calling tool
+    pub fn check_supported(&self, supported: &[Output]) -> Result<(), Error> {
+        if supported.contains(self) {
+            return Ok(());
+        }
+
+        let valid: Vec<String> =
+            supported.iter().map(|o| format!("{}", o)).collect();
+
+        Err(Error::UnsupportedOutputFormat {
+            description: format!(
+                "{} is not supported. Valid values are : {}",
+                self,
+                valid.join(", ")
+            ),
+        })
+    }
+
+    /// true if the format is intended to be parsed by other programs
+    pub fn is_machine_readable(&self) -> bool {
+        matches!(
+            self,
+            Output::Tsv | Output::Jsonl | Output::Json | Output::Csv
+        )
+    }
+
+    /// returns the format to use with --porcelain, where stdout only contains
+    /// structured data. The default format is replaced with the first machine
+    /// readable format supported by the tool (preferring tsv), and other
+    /// human readable formats are rejected.
+    pub fn porcelain(&self, supported: &[Output]) -> Result<Output, Error> {
+        if self.is_machine_readable() {
+            return Ok(*self);
+        }
+
+        if *self == Output::Default {
+            let preferred =
+                [Output::Tsv, Output::Csv, Output::Jsonl, Output::Json];
+            if let Some(e) = preferred.iter().find(|o| supported.contains(o)) {
+                return Ok(*e);
+            }
+        }
+
+        Err(Error::UnsupportedOutputFormat {
+            description: format!(
+                "{} is not machine readable, and cannot be used with --porcelain",
+                self
+            ),
+        })
+    }
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let out = match self {
+            Output::Tsv => "tsv",
+            Output::Default => "default",
+            Output::Jsonl => "jsonl",
+            Output::Statusline => "statusline",
+            Output::Json => "json",
+            Output::Csv => "csv",
+            Output::Markdown => "markdown",
+        };
+
+        write!(f, "{}", out)
+    }
+}
@@ -0,0 +1,743 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Minimal writer for Apache Parquet files, used to export store data for
+//analysis in tools such as DuckDB and pandas.
+//
+//Only supports what the exports need: flat schemas of required or optional
+//INT64, DOUBLE, UTF8 string and millisecond timestamp columns. Values are
+//PLAIN encoded into a single gzip compressed data page per column per row
+//group (along with RLE encoded definition levels for optional columns), and
+//file metadata is written using the thrift compact protocol.
+//
+//https://github.com/apache/parquet-format
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use crate::error::Error;
+
+const MAGIC: &[u8] = b"PAR1";
+
+//parquet physical types
+const TYPE_INT64: i32 = 2;
+const TYPE_DOUBLE: i32 = 5;
+const TYPE_BYTE_ARRAY: i32 = 6;
+
+//parquet converted types
+const CONVERTED_UTF8: i32 = 0;
+const CONVERTED_TIMESTAMP_MILLIS: i32 = 9;
+
+const REPETITION_REQUIRED: i32 = 0;
+const REPETITION_OPTIONAL: i32 = 1;
+const ENCODING_PLAIN: i32 = 0;
+const ENCODING_RLE: i32 = 3;
+const CODEC_GZIP: i32 = 2;
+const PAGE_TYPE_DATA: i32 = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParquetType {
+    Int64,
+    Double,
+    Utf8,
+
+    //milliseconds since the unix epoch, written as ParquetValue::Int64
+    TimestampMillis,
+}
+
+impl ParquetType {
+    fn physical_type(&self) -> i32 {
+        match self {
+            ParquetType::Int64 => TYPE_INT64,
+            ParquetType::Double => TYPE_DOUBLE,
+            ParquetType::Utf8 => TYPE_BYTE_ARRAY,
+            ParquetType::TimestampMillis => TYPE_INT64,
+        }
+    }
+
+    fn converted_type(&self) -> Option<i32> {
+        match self {
+            ParquetType::Utf8 => Some(CONVERTED_UTF8),
+            ParquetType::TimestampMillis => Some(CONVERTED_TIMESTAMP_MILLIS),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ParquetColumn {
+    pub name: &'static str,
+    pub kind: ParquetType,
+
+    //whether the column can contain ParquetValue::Null
+    pub optional: bool,
+}
+
+impl ParquetColumn {
+    pub const fn new(name: &'static str, kind: ParquetType) -> ParquetColumn {
+        ParquetColumn {
+            name,
+            kind,
+            optional: false,
+        }
+    }
+
+    pub const fn optional(
+        name: &'static str,
+        kind: ParquetType,
+    ) -> ParquetColumn {
+        ParquetColumn {
+            name,
+            kind,
+            optional: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParquetValue {
+    Int64(i64),
+    Double(f64),
+    Utf8(String),
+
+    //only valid for optional columns
+    Null,
+}
+
+struct ColumnChunkInfo {
+    offset: u64,
+    uncompressed_size: u64,
+    compressed_size: u64,
+}
+
+struct RowGroupInfo {
+    columns: Vec<ColumnChunkInfo>,
+    num_rows: usize,
+}
+
+/// Writes rows to a parquet file, buffering up to rows_per_group rows in
+/// memory before writing them out as a row group. finish must be called to
+/// write the file footer.
+pub struct ParquetWriter {
+    writer: BufWriter<File>,
+    offset: u64,
+    columns: Vec<ParquetColumn>,
+    rows_per_group: usize,
+
+    //plain encoded values for each column in the current row group
+    buffers: Vec<Vec<u8>>,
+
+    //definition level (1 if set, 0 if null) of each row in the current row
+    //group, for optional columns
+    levels: Vec<Vec<u8>>,
+    rows_in_group: usize,
+    row_groups: Vec<RowGroupInfo>,
+}
+
+impl ParquetWriter {
+    pub fn new(
+        path: &Path,
+        columns: &[ParquetColumn],
+        rows_per_group: usize,
+    ) -> Result<ParquetWriter, Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(MAGIC)?;
+
+        Ok(ParquetWriter {
+            writer,
+            offset: MAGIC.len() as u64,
+            columns: columns.to_vec(),
+            rows_per_group: std::cmp::max(rows_per_group, 1),
+            buffers: vec![Vec::new(); columns.len()],
+            levels: vec![Vec::new(); columns.len()],
+            rows_in_group: 0,
+            row_groups: Vec::new(),
+        })
+    }
+
+    pub fn write_row(&mut self, values: &[ParquetValue]) -> Result<(), Error> {
+        if values.len() != self.columns.len() {
+            return Err(Error::Unknown {
+                description: format!(
+                    "Parquet row has {} values, expected {}.",
+                    values.len(),
+                    self.columns.len()
+                ),
+            });
+        }
+
+        //check the whole row first, so a bad value doesn't leave the columns
+        //with different numbers of values
+        for (column, value) in self.columns.iter().zip(values.iter()) {
+            let valid = match (column.kind, value) {
+                (_, ParquetValue::Null) => column.optional,
+                (ParquetType::Int64, ParquetValue::Int64(_))
+                | (ParquetType::TimestampMillis, ParquetValue::Int64(_))
+                | (ParquetType::Double, ParquetValue::Double(_))
+                | (ParquetType::Utf8, ParquetValue::Utf8(_)) => true,
+                _ => false,
+            };
+
+            if !valid {
+                return Err(Error::Unknown {
+                    description: format!(
+                        "Invalid value for parquet column {} : {:?}",
+                        column.name, value
+                    ),
+                });
+            }
+        }
+
+        for (i, (column, value)) in
+            self.columns.iter().zip(values.iter()).enumerate()
+        {
+            if column.optional {
+                let level = if *value == ParquetValue::Null { 0 } else { 1 };
+                self.levels[i].push(level);
+            }
+
+            let buffer = &mut self.buffers[i];
+            match value {
+                ParquetValue::Int64(v) => {
+                    buffer.extend_from_slice(&v.to_le_bytes());
+                }
+                ParquetValue::Double(v) => {
+                    buffer.extend_from_slice(&v.to_le_bytes());
+                }
+                ParquetValue::Utf8(v) => {
+                    buffer.extend_from_slice(&(v.len() as u32).to_le_bytes());
+                    buffer.extend_from_slice(v.as_bytes());
+                }
+                //nulls only have a definition level
+                ParquetValue::Null => (),
+            }
+        }
+
+        self.rows_in_group += 1;
+        if self.rows_in_group >= self.rows_per_group {
+            self.write_row_group()?;
+        }
+
+        Ok(())
+    }
+
+    fn write_row_group(&mut self) -> Result<(), Error> {
+        if self.rows_in_group == 0 {
+            return Ok(());
+        }
+
+        let mut columns: Vec<ColumnChunkInfo> = Vec::new();
+        for i in 0..self.columns.len() {
+            let values = std::mem::take(&mut self.buffers[i]);
+            let levels = std::mem::take(&mut self.levels[i]);
+
+            //definition levels come before the values, prefixed with their
+            //length
+            let mut data = Vec::with_capacity(values.len());
+            if self.columns[i].optional {
+                let encoded = encode_levels(&levels);
+                data.extend_from_slice(&(encoded.len() as u32).to_le_bytes());
+                data.extend_from_slice(&encoded);
+            }
+            data.extend_from_slice(&values);
+
+            let mut encoder =
+                GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&data)?;
+            let compressed = encoder.finish()?;
+
+            let mut header = ThriftWriter::new();
+            header.struct_begin();
+            header.i32_field(1, PAGE_TYPE_DATA);
+            header.i32_field(2, data.len() as i32);
+            header.i32_field(3, compressed.len() as i32);
+            header.struct_field(5);
+            header.i32_field(1, self.rows_in_group as i32);
+            header.i32_field(2, ENCODING_PLAIN);
+            header.i32_field(3, ENCODING_RLE);
+            header.i32_field(4, ENCODING_RLE);
+            header.struct_end();
+            header.struct_end();
+            let header = header.into_bytes();
+
+            self.writer.write_all(&header)?;
+            self.writer.write_all(&compressed)?;
+
+            columns.push(ColumnChunkInfo {
+                offset: self.offset,
+                uncompressed_size: (header.len() + data.len()) as u64,
+                compressed_size: (header.len() + compressed.len()) as u64,
+            });
+            self.offset += (header.len() + compressed.len()) as u64;
+        }
+
+        self.row_groups.push(RowGroupInfo {
+            columns,
+            num_rows: self.rows_in_group,
+        });
+        self.rows_in_group = 0;
+
+        Ok(())
+    }
+
+    /// writes any buffered rows and the file footer, and returns the total
+    /// number of rows written
+    pub fn finish(mut self) -> Result<u64, Error> {
+        self.write_row_group()?;
+
+        let num_rows: usize = self.row_groups.iter().map(|r| r.num_rows).sum();
+
+        let mut meta = ThriftWriter::new();
+        meta.struct_begin();
+        meta.i32_field(1, 1);
+
+        //schema is a flattened tree, with a root element containing the
+        //columns
+        meta.list_field(2, THRIFT_STRUCT, self.columns.len() + 1);
+        meta.struct_begin();
+        meta.binary_field(4, b"schema");
+        meta.i32_field(5, self.columns.len() as i32);
+        meta.struct_end();
+        for c in self.columns.iter() {
+            meta.struct_begin();
+            meta.i32_field(1, c.kind.physical_type());
+            let repetition = if c.optional {
+                REPETITION_OPTIONAL
+            } else {
+                REPETITION_REQUIRED
+            };
+            meta.i32_field(3, repetition);
+            meta.binary_field(4, c.name.as_bytes());
+            if let Some(converted) = c.kind.converted_type() {
+                meta.i32_field(6, converted);
+            }
+            meta.struct_end();
+        }
+
+        meta.i64_field(3, num_rows as i64);
+
+        meta.list_field(4, THRIFT_STRUCT, self.row_groups.len());
+        for group in self.row_groups.iter() {
+            meta.struct_begin();
+            meta.list_field(1, THRIFT_STRUCT, group.columns.len());
+            for (column, chunk) in self.columns.iter().zip(group.columns.iter())
+            {
+                meta.struct_begin();
+                meta.i64_field(2, chunk.offset as i64);
+                meta.struct_field(3);
+                meta.i32_field(1, column.kind.physical_type());
+                meta.list_field(2, THRIFT_I32, 1);
+                meta.i32_value(ENCODING_PLAIN);
+                meta.list_field(3, THRIFT_BINARY, 1);
+                meta.binary_value(column.name.as_bytes());
+                meta.i32_field(4, CODEC_GZIP);
+                meta.i64_field(5, group.num_rows as i64);
+                meta.i64_field(6, chunk.uncompressed_size as i64);
+                meta.i64_field(7, chunk.compressed_size as i64);
+                meta.i64_field(9, chunk.offset as i64);
+                meta.struct_end();
+                meta.struct_end();
+            }
+
+            let total_byte_size: u64 =
+                group.columns.iter().map(|c| c.uncompressed_size).sum();
+            meta.i64_field(2, total_byte_size as i64);
+            meta.i64_field(3, group.num_rows as i64);
+            meta.struct_end();
+        }
+
+        meta.binary_field(
+            6,
+            format!("dcli version {}", env!("CARGO_PKG_VERSION")).as_bytes(),
+        );
+        meta.struct_end();
+
+        let meta = meta.into_bytes();
+        self.writer.write_all(&meta)?;
+        self.writer.write_all(&(meta.len() as u32).to_le_bytes())?;
+        self.writer.write_all(MAGIC)?;
+        self.writer.flush()?;
+
+        Ok(num_rows as u64)
+    }
+}
+
+//encodes definition levels (0 or 1) using the RLE / bit packed hybrid
+//encoding, as a run for each group of repeated levels
+fn encode_levels(levels: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < levels.len() {
+        let level = levels[i];
+        let run = levels[i..].iter().take_while(|l| **l == level).count();
+
+        //run header is the run length shifted left by one (the low bit
+        //marks bit packed runs), followed by the level in a single byte
+        let mut header = (run as u64) << 1;
+        while header >= 0x80 {
+            out.push((header as u8) | 0x80);
+            header >>= 7;
+        }
+        out.push(header as u8);
+        out.push(level);
+
+        i += run;
+    }
+    out
+}
+
+//thrift compact protocol types
+const THRIFT_I32: u8 = 5;
+const THRIFT_I64: u8 = 6;
+const THRIFT_BINARY: u8 = 8;
+const THRIFT_LIST: u8 = 9;
+const THRIFT_STRUCT: u8 = 12;
+
+//writes the subset of the thrift compact protocol used by parquet metadata.
+//fields must be written in increasing id order within a struct.
+struct ThriftWriter {
+    buf: Vec<u8>,
+    last_field_id: i16,
+    parent_field_ids: Vec<i16>,
+}
+
+impl ThriftWriter {
+    fn new() -> ThriftWriter {
+        ThriftWriter {
+            buf: Vec::new(),
+            last_field_id: 0,
+            parent_field_ids: Vec::new(),
+        }
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        self.buf
+    }
+
+    fn varint(&mut self, mut v: u64) {
+        while v >= 0x80 {
+            self.buf.push((v as u8) | 0x80);
+            v >>= 7;
+        }
+        self.buf.push(v as u8);
+    }
+
+    fn field_header(&mut self, id: i16, field_type: u8) {
+        let delta = id - self.last_field_id;
+        if delta > 0 && delta <= 15 {
+            self.buf.push(((delta as u8) << 4) | field_type);
+        } else {
+            self.buf.push(field_type);
+            self.varint((((id as i32) << 1) ^ ((id as i32) >> 31)) as u64);
+        }
+        self.last_field_id = id;
+    }
+
+    fn struct_begin(&mut self) {
+        self.parent_field_ids.push(self.last_field_id);
+        self.last_field_id = 0;
+    }
+
+    fn struct_end(&mut self) {
+        self.buf.push(0);
+        self.last_field_id = self.parent_field_ids.pop().unwrap_or(0);
+    }
+
+    fn struct_field(&mut self, id: i16) {
+        self.field_header(id, THRIFT_STRUCT);
+        self.struct_begin();
+    }
+
+    fn i32_value(&mut self, v: i32) {
+        self.varint(((v << 1) ^ (v >> 31)) as u32 as u64);
+    }
+
+    fn i32_field(&mut self, id: i16, v: i32) {
+        self.field_header(id, THRIFT_I32);
+        self.i32_value(v);
+    }
+
+    fn i64_field(&mut self, id: i16, v: i64) {
+        self.field_header(id, THRIFT_I64);
+        self.varint(((v << 1) ^ (v >> 63)) as u64);
+    }
+
+    fn binary_value(&mut self, v: &[u8]) {
+        self.varint(v.len() as u64);
+        self.buf.extend_from_slice(v);
+    }
+
+    fn binary_field(&mut self, id: i16, v: &[u8]) {
+        self.field_header(id, THRIFT_BINARY);
+        self.binary_value(v);
+    }
+
+    fn list_field(&mut self, id: i16, element_type: u8, size: usize) {
+        self.field_header(id, THRIFT_LIST);
+        if size < 15 {
+            self.buf.push(((size as u8) << 4) | element_type);
+        } else {
+            self.buf.push(0xf0 | element_type);
+            self.varint(size as u64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::PathBuf;
+
+    use parquet::basic::{
+        Compression, ConvertedType, Repetition, Type as PhysicalType,
+    };
+    use parquet::file::metadata::ParquetMetaData;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::Field;
+
+    //files are read back with the parquet crate, rather than a reader
+    //sharing code (and assumptions) with the writer
+    struct ReadFile {
+        meta: ParquetMetaData,
+        rows: Vec<Vec<ParquetValue>>,
+    }
+
+    fn read_file(path: &Path) -> ReadFile {
+        let reader = SerializedFileReader::new(File::open(path).unwrap())
+            .expect("could not read file");
+
+        let rows = reader
+            .get_row_iter(None)
+            .expect("could not read rows")
+            .map(|row| {
+                row.get_column_iter()
+                    .map(|(_name, field)| match field {
+                        Field::Null => ParquetValue::Null,
+                        Field::Long(v) => ParquetValue::Int64(*v),
+                        Field::TimestampMillis(v) => {
+                            ParquetValue::Int64(*v as i64)
+                        }
+                        Field::Double(v) => ParquetValue::Double(*v),
+                        Field::Str(v) => ParquetValue::Utf8(v.to_string()),
+                        e => panic!("unexpected field {:?}", e),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        ReadFile {
+            meta: reader.metadata().clone(),
+            rows,
+        }
+    }
+
+    fn expected_physical_type(kind: ParquetType) -> PhysicalType {
+        match kind {
+            ParquetType::Int64 | ParquetType::TimestampMillis => {
+                PhysicalType::INT64
+            }
+            ParquetType::Double => PhysicalType::DOUBLE,
+            ParquetType::Utf8 => PhysicalType::BYTE_ARRAY,
+        }
+    }
+
+    fn expected_converted_type(kind: ParquetType) -> ConvertedType {
+        match kind {
+            ParquetType::Utf8 => ConvertedType::UTF8,
+            ParquetType::TimestampMillis => ConvertedType::TIMESTAMP_MILLIS,
+            _ => ConvertedType::NONE,
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "dcli_parquet_{}_{}.parquet",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn write_file(
+        path: &Path,
+        columns: &[ParquetColumn],
+        rows: &[Vec<ParquetValue>],
+        rows_per_group: usize,
+    ) -> u64 {
+        let mut writer = ParquetWriter::new(path, columns, rows_per_group)
+            .expect("could not create file");
+        for row in rows {
+            writer.write_row(row).expect("could not write row");
+        }
+        writer.finish().expect("could not finish file")
+    }
+
+    const COLUMNS: &[ParquetColumn] = &[
+        ParquetColumn::new("id", ParquetType::Int64),
+        ParquetColumn::new("period", ParquetType::TimestampMillis),
+        ParquetColumn::new("ratio", ParquetType::Double),
+        ParquetColumn::new("name", ParquetType::Utf8),
+        ParquetColumn::optional("season", ParquetType::Int64),
+        ParquetColumn::optional("team_name", ParquetType::Utf8),
+    ];
+
+    fn row(
+        id: i64,
+        season: Option<i64>,
+        team_name: Option<&str>,
+    ) -> Vec<ParquetValue> {
+        vec![
+            ParquetValue::Int64(id),
+            ParquetValue::Int64(1_600_000_000_000 + id * 1000),
+            ParquetValue::Double(id as f64 / 3.0),
+            ParquetValue::Utf8(format!("Guardian \u{00e9}{}", id)),
+            season
+                .map(ParquetValue::Int64)
+                .unwrap_or(ParquetValue::Null),
+            team_name
+                .map(|e| ParquetValue::Utf8(e.to_string()))
+                .unwrap_or(ParquetValue::Null),
+        ]
+    }
+
+    #[test]
+    fn round_trip_schema() {
+        let path = temp_path("schema");
+        write_file(&path, COLUMNS, &[row(1, Some(14), Some("Alpha"))], 10);
+        let file = read_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let schema = file.meta.file_metadata().schema_descr();
+        assert_eq!(schema.root_schema().name(), "schema");
+        assert_eq!(schema.num_columns(), COLUMNS.len());
+
+        for (i, column) in COLUMNS.iter().enumerate() {
+            let element = schema.column(i);
+            assert_eq!(element.name(), column.name);
+            assert_eq!(
+                element.physical_type(),
+                expected_physical_type(column.kind)
+            );
+            assert_eq!(
+                element.converted_type(),
+                expected_converted_type(column.kind)
+            );
+
+            let repetition = if column.optional {
+                Repetition::OPTIONAL
+            } else {
+                Repetition::REQUIRED
+            };
+            assert_eq!(
+                element.self_type().get_basic_info().repetition(),
+                repetition
+            );
+        }
+
+        for chunk in file.meta.row_group(0).columns() {
+            assert_eq!(chunk.compression(), Compression::GZIP);
+        }
+        assert_eq!(file.rows, vec![row(1, Some(14), Some("Alpha"))]);
+    }
+
+    #[test]
+    fn round_trip_row_groups() {
+        let rows: Vec<Vec<ParquetValue>> =
+            (0..7).map(|i| row(i, Some(i % 3), Some("Bravo"))).collect();
+
+        let path = temp_path("row_groups");
+        let count = write_file(&path, COLUMNS, &rows, 3);
+        let file = read_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(count, 7);
+        let groups: Vec<i64> = file
+            .meta
+            .row_groups()
+            .iter()
+            .map(|g| g.num_rows())
+            .collect();
+        assert_eq!(groups, vec![3, 3, 1]);
+        assert_eq!(file.rows, rows);
+    }
+
+    #[test]
+    fn round_trip_nulls() {
+        let rows = vec![
+            row(1, None, None),
+            row(2, Some(12), None),
+            row(3, None, Some("Alpha")),
+            row(4, None, None),
+            row(5, Some(13), Some("")),
+        ];
+
+        let path = temp_path("nulls");
+        write_file(&path, COLUMNS, &rows, 100);
+        let file = read_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(file.rows, rows);
+    }
+
+    #[test]
+    fn empty_file() {
+        let path = temp_path("empty");
+        let count = write_file(&path, COLUMNS, &[], 10);
+        let file = read_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(count, 0);
+        assert_eq!(file.meta.num_row_groups(), 0);
+        assert!(file.rows.is_empty());
+    }
+
+    #[test]
+    fn invalid_rows() {
+        let path = temp_path("invalid");
+        let mut writer = ParquetWriter::new(&path, COLUMNS, 10).unwrap();
+
+        //wrong number of values
+        assert!(writer.write_row(&row(1, None, None)[..3]).is_err());
+
+        //null in a required column
+        let mut null_id = row(1, None, None);
+        null_id[0] = ParquetValue::Null;
+        assert!(writer.write_row(&null_id).is_err());
+
+        //wrong type, after values which would have been valid
+        let mut bad_season = row(1, None, None);
+        bad_season[4] = ParquetValue::Utf8("14".to_string());
+        assert!(writer.write_row(&bad_season).is_err());
+
+        //rejected rows don't leave partial values behind
+        writer.write_row(&row(2, Some(14), None)).unwrap();
+        writer.finish().unwrap();
+        let file = read_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(file.rows, vec![row(2, Some(14), None)]);
+    }
+}
@@ -0,0 +1,60 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use serde_derive::Serialize;
+
+use crate::utils::calculate_avg;
+
+/// a member's stats for a single pve activity (i.e. a specific strike, raid
+/// or nightfall difficulty), across all of the times it was played
+#[derive(Debug, Clone, Serialize)]
+pub struct PveActivityStats {
+    /// DestinyActivityDefinition hash for the activity
+    pub reference_id: u32,
+    pub activity_name: String,
+
+    pub activities: u32,
+
+    /// activities which were completed (objective complete)
+    pub clears: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    pub time_played_seconds: u32,
+
+    /// duration of the quickest clear. None if the activity has never been
+    /// cleared.
+    pub fastest_clear_seconds: Option<u32>,
+
+    /// total number of players (including the member) across all of the
+    /// activities
+    pub fireteam_members: u32,
+}
+
+impl PveActivityStats {
+    pub fn average_fireteam_size(&self) -> f32 {
+        calculate_avg(self.fireteam_members as f32, self.activities)
+    }
+
+    pub fn deaths_per_activity(&self) -> f32 {
+        calculate_avg(self.deaths as f32, self.activities)
+    }
+}
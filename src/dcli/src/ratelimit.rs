@@ -0,0 +1,114 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Optional cap on the rate of API requests (--max-rps), for users who share an
+//API key or IP rate budget with other tools. The cap is process wide, so it
+//applies across all ApiClient instances and concurrent requests. Requests are
+//not limited unless a cap has been set, either with
+//set_max_requests_per_second or the DCLI_MAX_RPS environment variable.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// environment variable used to set the maximum requests per second when
+/// it isn't set from the command line
+pub const MAX_RPS_ENV_VAR: &str = "DCLI_MAX_RPS";
+
+struct RateLimit {
+    interval: Option<Duration>,
+    next: Option<Instant>,
+    initialized: bool,
+}
+
+static RATE_LIMIT: Mutex<RateLimit> = Mutex::new(RateLimit {
+    interval: None,
+    next: None,
+    initialized: false,
+});
+
+/// smallest max requests per second value accepted (one request every 1000
+/// seconds). Smaller values overflow the interval between requests.
+pub const MIN_MAX_RPS: f64 = 0.001;
+
+/// parses a max requests per second value. Must be a number of at least
+/// MIN_MAX_RPS.
+pub fn parse_max_rps(src: &str) -> Result<f64, String> {
+    match src.trim().parse::<f64>() {
+        Ok(e) if e.is_finite() && e >= MIN_MAX_RPS => Ok(e),
+        _ => Err(format!(
+            "Max requests per second must be a number of at least {} : {}",
+            MIN_MAX_RPS, src
+        )),
+    }
+}
+
+/// caps api requests to max_rps requests per second. Overrides the
+/// DCLI_MAX_RPS environment variable. None removes the cap. Values below
+/// MIN_MAX_RPS are raised to it.
+pub fn set_max_requests_per_second(max_rps: Option<f64>) {
+    let mut limit = RATE_LIMIT.lock().unwrap();
+    limit.interval =
+        max_rps.map(|e| Duration::from_secs_f64(1.0 / e.max(MIN_MAX_RPS)));
+    limit.next = None;
+    limit.initialized = true;
+}
+
+//reserves the next available request slot, and returns how long the caller
+//needs to wait until it. The lock is never held across an await.
+fn reserve() -> Option<Duration> {
+    let mut limit = RATE_LIMIT.lock().unwrap();
+
+    if !limit.initialized {
+        limit.initialized = true;
+        limit.interval = match std::env::var(MAX_RPS_ENV_VAR) {
+            Ok(e) => match parse_max_rps(&e) {
+                Ok(e) => Some(Duration::from_secs_f64(1.0 / e)),
+                Err(e) => {
+                    eprintln!("Ignoring {}. {}", MAX_RPS_ENV_VAR, e);
+                    None
+                }
+            },
+            Err(_) => None,
+        };
+    }
+
+    let interval = limit.interval?;
+
+    let now = Instant::now();
+    let slot = match limit.next {
+        Some(e) if e > now => e,
+        _ => now,
+    };
+    limit.next = Some(slot + interval);
+
+    Some(slot - now)
+}
+
+/// waits until the next request can be made without exceeding the maximum
+/// requests per second. Returns immediately if no cap has been set.
+pub async fn wait() {
+    if let Some(e) = reserve() {
+        if e > Duration::from_millis(0) {
+            tokio::time::sleep(e).await;
+        }
+    }
+}
@@ -0,0 +1,423 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::fs;
+use std::path::Path;
+
+#[cfg(feature = "plotters")]
+use plotters::prelude::{
+    BitMapBackend, ChartBuilder, IntoDrawingArea, IntoFont, IntoSegmentedCoord,
+    RGBColor, Rectangle, SegmentValue, Text,
+};
+#[cfg(feature = "plotters")]
+use plotters::style::{register_font, Color as _, FontStyle, TRANSPARENT};
+use serde_derive::Deserialize;
+
+#[cfg(feature = "plotters")]
+use super::font::TRUETYPE_FONT;
+#[cfg(feature = "plotters")]
+use super::png::encode_png;
+#[cfg(feature = "plotters")]
+use super::ImageFormat;
+use super::{write_image, Color, Drawing};
+use crate::datadir;
+use crate::error::Error;
+
+const CONFIG_FILE_NAME: &str = "charts.json";
+
+const PADDING: u32 = 24;
+
+//gap between the axis labels and the chart area
+const LABEL_GAP: u32 = 8;
+
+const MIN_CHART_WIDTH: u32 = 200;
+const MIN_CHART_HEIGHT: u32 = 120;
+
+//larger sizes in charts.json are clamped to these
+const MAX_CHART_WIDTH: u32 = 4096;
+const MAX_CHART_HEIGHT: u32 = 4096;
+
+//name the embedded font is registered with in plotters
+#[cfg(feature = "plotters")]
+const PLOTTERS_FONT: &str = "DejaVu Sans Mono";
+
+/// colors used to draw charts
+#[derive(Debug, Clone, Copy)]
+pub struct ChartTheme {
+    pub background: Color,
+    pub rule: Color,
+    pub text: Color,
+    pub muted: Color,
+    pub bar: Color,
+}
+
+impl ChartTheme {
+    pub const DARK: ChartTheme = ChartTheme {
+        background: Color::rgb(0x1b, 0x1d, 0x22),
+        rule: Color::rgb(0x55, 0x55, 0x55),
+        text: Color::rgb(0xe4, 0xe4, 0xe4),
+        muted: Color::rgb(0x9a, 0x9a, 0x9a),
+        bar: Color::rgb(0xe3, 0xb3, 0x41),
+    };
+
+    pub const LIGHT: ChartTheme = ChartTheme {
+        background: Color::rgb(0xff, 0xff, 0xff),
+        rule: Color::rgb(0xcc, 0xcc, 0xcc),
+        text: Color::rgb(0x1b, 0x1d, 0x22),
+        muted: Color::rgb(0x66, 0x66, 0x66),
+        bar: Color::rgb(0x2f, 0x6f, 0xb5),
+    };
+
+    pub fn with_name(name: &str) -> Result<ChartTheme, Error> {
+        match name {
+            "dark" => Ok(ChartTheme::DARK),
+            "light" => Ok(ChartTheme::LIGHT),
+            _ => Err(Error::InvalidParameters),
+        }
+    }
+}
+
+/// size and theme of rendered charts. Read from charts.json in the config
+/// directory, i.e.
+///
+/// {"width" : 1024, "height" : 512, "theme" : "light"}
+///
+/// Any values not set use the defaults. Width and height are limited to
+/// 4096.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ChartConfig {
+    pub width: u32,
+    pub height: u32,
+
+    /// dark or light
+    pub theme: String,
+}
+
+impl Default for ChartConfig {
+    fn default() -> ChartConfig {
+        ChartConfig {
+            width: 800,
+            height: 400,
+            theme: "dark".to_string(),
+        }
+    }
+}
+
+impl ChartConfig {
+    /// loads the chart config from the data directory. If there is no config
+    /// file, the default config is returned.
+    pub fn load(data_dir: &Path) -> Result<ChartConfig, Error> {
+        let path = datadir::config_dir(data_dir).join(CONFIG_FILE_NAME);
+
+        if !path.exists() {
+            return Ok(ChartConfig::default());
+        }
+
+        let mut config: ChartConfig =
+            match serde_json::from_str(&fs::read_to_string(&path)?) {
+                Ok(e) => e,
+                Err(e) => {
+                    return Err(Error::IoError {
+                        description: format!(
+                            "Could not parse chart config {} : {}",
+                            path.display(),
+                            e
+                        ),
+                    })
+                }
+            };
+
+        //make sure the theme is valid up front, rather than when drawing
+        if ChartTheme::with_name(&config.theme).is_err() {
+            return Err(Error::IoError {
+                description: format!(
+                    "Unknown chart theme {} in {}. Valid values are dark and light.",
+                    config.theme,
+                    path.display()
+                ),
+            });
+        }
+
+        config.width = config.width.min(MAX_CHART_WIDTH);
+        config.height = config.height.min(MAX_CHART_HEIGHT);
+
+        Ok(config)
+    }
+
+    pub fn get_theme(&self) -> ChartTheme {
+        ChartTheme::with_name(&self.theme).unwrap_or(ChartTheme::DARK)
+    }
+}
+
+/// a single value in a trend chart, i.e. the average lobby rating for a day
+#[derive(Debug, Clone)]
+pub struct TrendPoint {
+    pub label: String,
+    pub value: f32,
+}
+
+/// draws a bar for each point, in order. Bars are scaled between the lowest
+/// and highest values so that changes are visible, the same as the text
+/// charts.
+pub fn draw_trend_chart(
+    title: &str,
+    points: &[TrendPoint],
+    config: &ChartConfig,
+) -> Drawing {
+    let theme = config.get_theme();
+    let width = config.width.clamp(MIN_CHART_WIDTH, MAX_CHART_WIDTH);
+    let height = config.height.clamp(MIN_CHART_HEIGHT, MAX_CHART_HEIGHT);
+    let line_height = Drawing::line_height(1);
+
+    let mut drawing = Drawing::new(width, height, theme.background);
+    drawing.text(PADDING, PADDING, title, theme.text, 1);
+
+    if points.is_empty() {
+        drawing.text(
+            PADDING,
+            PADDING + line_height * 2,
+            "No data",
+            theme.muted,
+            1,
+        );
+        return drawing;
+    }
+
+    let min = points.iter().map(|p| p.value).fold(f32::MAX, f32::min);
+    let max = points.iter().map(|p| p.value).fold(f32::MIN, f32::max);
+
+    let max_label = format!("{:.0}", max);
+    let min_label = format!("{:.0}", min);
+    let label_w = Drawing::text_width(&max_label, 1)
+        .max(Drawing::text_width(&min_label, 1));
+
+    let chart_x = PADDING + label_w + LABEL_GAP;
+    let chart_y = PADDING + line_height * 2;
+    let chart_w = width.saturating_sub(chart_x + PADDING).max(1);
+    let chart_h = height
+        .saturating_sub(chart_y + PADDING + line_height + LABEL_GAP)
+        .max(1);
+
+    //y axis labels, aligned with the top and bottom of the chart
+    drawing.text(
+        chart_x - LABEL_GAP - Drawing::text_width(&max_label, 1),
+        chart_y,
+        &max_label,
+        theme.muted,
+        1,
+    );
+    drawing.text(
+        chart_x - LABEL_GAP - Drawing::text_width(&min_label, 1),
+        chart_y + chart_h - line_height,
+        &min_label,
+        theme.muted,
+        1,
+    );
+
+    drawing.fill_rect(chart_x, chart_y, chart_w, 1, theme.rule);
+    drawing.fill_rect(chart_x, chart_y + chart_h, chart_w, 1, theme.rule);
+
+    let slot_w = (chart_w / points.len() as u32).max(1);
+    let gap = if slot_w > 4 { slot_w / 4 } else { 0 };
+
+    //the lowest value still gets a small bar, so it is visible
+    let min_bar_h = (chart_h / 20).max(1);
+
+    for (i, p) in points.iter().enumerate() {
+        let bar_h = if max - min > 0.0 {
+            min_bar_h
+                + (((p.value - min) / (max - min))
+                    * (chart_h - min_bar_h) as f32)
+                    .round() as u32
+        } else {
+            chart_h
+        };
+
+        drawing.fill_rect(
+            chart_x + i as u32 * slot_w + gap / 2,
+            chart_y + chart_h - bar_h,
+            (slot_w - gap).max(1),
+            bar_h,
+            theme.bar,
+        );
+    }
+
+    //labels for the first and last points
+    let label_y = chart_y + chart_h + LABEL_GAP;
+    let first = &points[0].label;
+    drawing.text(chart_x, label_y, first, theme.muted, 1);
+
+    if points.len() > 1 {
+        let last = &points[points.len() - 1].label;
+        let last_w = Drawing::text_width(last, 1);
+        if last_w + Drawing::text_width(first, 1) + LABEL_GAP < chart_w {
+            drawing.text(
+                chart_x + chart_w - last_w,
+                label_y,
+                last,
+                theme.muted,
+                1,
+            );
+        }
+    }
+
+    drawing
+}
+
+/// draws the trend chart and writes it to the path, using the file extension
+/// to determine the image format. When built with the plotters feature, png
+/// charts are drawn with plotters.
+pub fn write_trend_chart(
+    path: &Path,
+    title: &str,
+    points: &[TrendPoint],
+    config: &ChartConfig,
+) -> Result<(), Error> {
+    #[cfg(feature = "plotters")]
+    if ImageFormat::from_path(path)? == ImageFormat::Png {
+        let data = render_trend_chart_png(title, points, config)?;
+        fs::write(path, data)?;
+        return Ok(());
+    }
+
+    write_image(path, &draw_trend_chart(title, points, config))
+}
+
+/// draws the trend chart with plotters, and encodes it as a png
+#[cfg(feature = "plotters")]
+pub fn render_trend_chart_png(
+    title: &str,
+    points: &[TrendPoint],
+    config: &ChartConfig,
+) -> Result<Vec<u8>, Error> {
+    if register_font(PLOTTERS_FONT, FontStyle::Normal, TRUETYPE_FONT).is_err() {
+        return Err(Error::Unknown {
+            description: "Could not load chart font.".to_string(),
+        });
+    }
+
+    let width = config.width.clamp(MIN_CHART_WIDTH, MAX_CHART_WIDTH);
+    let height = config.height.clamp(MIN_CHART_HEIGHT, MAX_CHART_HEIGHT);
+
+    let mut pixels = vec![0; width as usize * height as usize * 3];
+    if let Err(e) = plot_trend_chart(
+        &mut pixels,
+        (width, height),
+        title,
+        points,
+        &config.get_theme(),
+    ) {
+        return Err(Error::Unknown {
+            description: format!("Could not draw chart : {}", e),
+        });
+    }
+
+    encode_png(width, height, &pixels)
+}
+
+#[cfg(feature = "plotters")]
+fn plot_trend_chart(
+    pixels: &mut [u8],
+    size: (u32, u32),
+    title: &str,
+    points: &[TrendPoint],
+    theme: &ChartTheme,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let rgb = |c: Color| RGBColor(c.r, c.g, c.b);
+    let line_height = Drawing::line_height(1);
+
+    let root = BitMapBackend::with_buffer(pixels, size).into_drawing_area();
+    root.fill(&rgb(theme.background))?;
+
+    if points.is_empty() {
+        root.draw(&Text::new(
+            title,
+            (PADDING as i32, PADDING as i32),
+            (PLOTTERS_FONT, 16).into_font().color(&rgb(theme.text)),
+        ))?;
+        root.draw(&Text::new(
+            "No data",
+            (PADDING as i32, (PADDING + line_height * 2) as i32),
+            (PLOTTERS_FONT, 13).into_font().color(&rgb(theme.muted)),
+        ))?;
+        root.present()?;
+        return Ok(());
+    }
+
+    let min = points.iter().map(|p| p.value).fold(f32::MAX, f32::min);
+    let max = points.iter().map(|p| p.value).fold(f32::MIN, f32::max);
+
+    //as with the text charts, bars are scaled between the lowest and highest
+    //values, and the lowest value still gets a small bar
+    let (low, high) = if max - min > 0.0 {
+        (min - (max - min) / 20.0, max)
+    } else {
+        (min - 1.0, max)
+    };
+
+    let label_w = Drawing::text_width(&format!("{:.0}", max), 1)
+        .max(Drawing::text_width(&format!("{:.0}", min), 1));
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(PADDING)
+        .caption(
+            title,
+            (PLOTTERS_FONT, 16).into_font().color(&rgb(theme.text)),
+        )
+        .x_label_area_size(line_height + LABEL_GAP)
+        .y_label_area_size(label_w + LABEL_GAP * 2)
+        .build_cartesian_2d((0..points.len()).into_segmented(), low..high)?;
+
+    let label_font = (PLOTTERS_FONT, 12).into_font().color(&rgb(theme.muted));
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .bold_line_style(rgb(theme.rule))
+        .light_line_style(TRANSPARENT)
+        .axis_style(rgb(theme.rule))
+        .label_style(label_font)
+        .x_label_formatter(&|x| match x {
+            SegmentValue::CenterOf(i) => {
+                points.get(*i).map(|p| p.label.clone()).unwrap_or_default()
+            }
+            _ => String::new(),
+        })
+        .y_label_formatter(&|y| format!("{:.0}", y))
+        .draw()?;
+
+    let bar = rgb(theme.bar);
+    chart.draw_series(points.iter().enumerate().map(|(i, p)| {
+        let mut rect = Rectangle::new(
+            [
+                (SegmentValue::Exact(i), low),
+                (SegmentValue::Exact(i + 1), p.value),
+            ],
+            bar.filled(),
+        );
+        rect.set_margin(0, 0, 2, 2);
+        rect
+    }))?;
+
+    root.present()?;
+    Ok(())
+}
@@ -0,0 +1,157 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//8x16 bitmap glyphs for printable ascii (0x20 - 0x7E), rasterized from
+//DejaVu Sans Mono Bold (Bitstream Vera / DejaVu fonts license). Each row is
+//one byte, with the most significant bit as the left most pixel.
+
+pub const GLYPH_WIDTH: u32 = 8;
+pub const GLYPH_HEIGHT: u32 = 16;
+
+//row of the baseline within a glyph, used to align svg text with the bitmap
+pub const GLYPH_BASELINE: u32 = 13;
+
+//font size at which a monospace font has the same advance as the bitmap font
+//(DejaVu Sans Mono advance is ~0.6 em)
+pub const FONT_SIZE: f32 = GLYPH_WIDTH as f32 / 0.6;
+
+//the font the bitmap glyphs were rasterized from, used to draw png text when
+//built with the truetype feature (see fonts/LICENSE)
+#[cfg(feature = "truetype")]
+pub const TRUETYPE_FONT: &[u8] =
+    include_bytes!("../../fonts/DejaVuSansMono-Bold.ttf");
+
+const FIRST_GLYPH: u32 = 0x20;
+const LAST_GLYPH: u32 = 0x7E;
+
+#[rustfmt::skip]
+const GLYPHS: [[u8; 16]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x00, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x00, 0x00, 0x00], // '!'
+    [0x00, 0x00, 0x00, 0x24, 0x66, 0x66, 0x66, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '"'
+    [0x00, 0x00, 0x00, 0x12, 0x12, 0x16, 0x7F, 0x34, 0x24, 0xFE, 0xFE, 0x68, 0x48, 0x00, 0x00, 0x00], // '#'
+    [0x00, 0x00, 0x00, 0x18, 0x18, 0x7E, 0x78, 0x78, 0x3C, 0x1E, 0x1E, 0x7E, 0x7C, 0x18, 0x18, 0x00], // '$'
+    [0x00, 0x00, 0x00, 0x00, 0x70, 0xD0, 0xD0, 0x66, 0x18, 0x4E, 0x09, 0x0B, 0x06, 0x00, 0x00, 0x00], // '%'
+    [0x00, 0x00, 0x00, 0x3C, 0x3C, 0x60, 0x30, 0x70, 0x79, 0xCF, 0xCF, 0x6E, 0x7F, 0x00, 0x00, 0x00], // '&'
+    [0x00, 0x00, 0x00, 0x18, 0x18, 0x18, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '\''
+    [0x00, 0x00, 0x00, 0x0C, 0x08, 0x18, 0x18, 0x10, 0x30, 0x30, 0x18, 0x18, 0x18, 0x08, 0x0C, 0x00], // '('
+    [0x00, 0x00, 0x00, 0x30, 0x10, 0x18, 0x18, 0x08, 0x0C, 0x0C, 0x18, 0x18, 0x18, 0x10, 0x30, 0x00], // ')'
+    [0x00, 0x00, 0x00, 0x00, 0x5A, 0x7E, 0x3C, 0x7E, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '*'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x18, 0x18, 0x18, 0xFF, 0x7E, 0x18, 0x18, 0x00, 0x00, 0x00, 0x00], // '+'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x18, 0x18, 0x18, 0x18, 0x10, 0x00], // ','
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3C, 0x3C, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '-'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x18, 0x18, 0x18, 0x00, 0x00, 0x00], // '.'
+    [0x00, 0x00, 0x00, 0x02, 0x06, 0x04, 0x0C, 0x08, 0x18, 0x10, 0x30, 0x20, 0x60, 0x60, 0x00, 0x00], // '/'
+    [0x00, 0x00, 0x00, 0x3C, 0x7E, 0x66, 0x66, 0x66, 0x7E, 0x66, 0x66, 0x7E, 0x3C, 0x00, 0x00, 0x00], // '0'
+    [0x00, 0x00, 0x00, 0x38, 0x78, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x7E, 0x7E, 0x00, 0x00, 0x00], // '1'
+    [0x00, 0x00, 0x00, 0x7C, 0x7E, 0x06, 0x06, 0x0C, 0x1C, 0x38, 0x30, 0x7E, 0x7E, 0x00, 0x00, 0x00], // '2'
+    [0x00, 0x00, 0x00, 0x7C, 0x7E, 0x06, 0x06, 0x3C, 0x1C, 0x06, 0x06, 0x7E, 0x7C, 0x00, 0x00, 0x00], // '3'
+    [0x00, 0x00, 0x00, 0x0C, 0x0C, 0x1C, 0x3C, 0x2C, 0x6C, 0x7E, 0x7F, 0x0C, 0x0C, 0x00, 0x00, 0x00], // '4'
+    [0x00, 0x00, 0x00, 0x7C, 0x7E, 0x60, 0x60, 0x7C, 0x0E, 0x06, 0x06, 0x7E, 0x7C, 0x00, 0x00, 0x00], // '5'
+    [0x00, 0x00, 0x00, 0x1C, 0x3E, 0x60, 0x60, 0x7E, 0x66, 0x66, 0x66, 0x76, 0x3C, 0x00, 0x00, 0x00], // '6'
+    [0x00, 0x00, 0x00, 0x7E, 0x7E, 0x06, 0x0C, 0x0C, 0x0C, 0x18, 0x18, 0x30, 0x30, 0x00, 0x00, 0x00], // '7'
+    [0x00, 0x00, 0x00, 0x3C, 0x7E, 0x66, 0x66, 0x3C, 0x3C, 0x66, 0x66, 0x6E, 0x3C, 0x00, 0x00, 0x00], // '8'
+    [0x00, 0x00, 0x00, 0x38, 0x7C, 0x66, 0x66, 0x66, 0x7E, 0x3E, 0x06, 0x4E, 0x7C, 0x00, 0x00, 0x00], // '9'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00, 0x00, 0x00], // ':'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x18, 0x10, 0x00], // ';'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0F, 0x3C, 0xE0, 0x70, 0x1E, 0x07, 0x00, 0x00, 0x00, 0x00], // '<'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7E, 0xFF, 0x00, 0x7E, 0xFF, 0x00, 0x00, 0x00, 0x00, 0x00], // '='
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xF0, 0x3C, 0x07, 0x0E, 0x78, 0xE0, 0x00, 0x00, 0x00, 0x00], // '>'
+    [0x00, 0x00, 0x00, 0x3C, 0x7E, 0x06, 0x06, 0x0C, 0x18, 0x18, 0x10, 0x10, 0x18, 0x00, 0x00, 0x00], // '?'
+    [0x00, 0x00, 0x00, 0x00, 0x3C, 0x66, 0x43, 0xDF, 0x93, 0xB3, 0x93, 0xDF, 0x40, 0x72, 0x3E, 0x00], // '@'
+    [0x00, 0x00, 0x00, 0x18, 0x3C, 0x3C, 0x3C, 0x24, 0x66, 0x7E, 0x7E, 0x66, 0xC3, 0x00, 0x00, 0x00], // 'A'
+    [0x00, 0x00, 0x00, 0x7C, 0x7E, 0x66, 0x66, 0x7C, 0x7E, 0x66, 0x67, 0x7E, 0x7E, 0x00, 0x00, 0x00], // 'B'
+    [0x00, 0x00, 0x00, 0x1E, 0x3E, 0x70, 0x60, 0x60, 0x60, 0x60, 0x60, 0x3E, 0x1E, 0x00, 0x00, 0x00], // 'C'
+    [0x00, 0x00, 0x00, 0x78, 0x7C, 0x66, 0x66, 0x66, 0x67, 0x66, 0x66, 0x7E, 0x7C, 0x00, 0x00, 0x00], // 'D'
+    [0x00, 0x00, 0x00, 0x7E, 0x7E, 0x60, 0x60, 0x7E, 0x7E, 0x60, 0x60, 0x7E, 0x7E, 0x00, 0x00, 0x00], // 'E'
+    [0x00, 0x00, 0x00, 0x7E, 0x7E, 0x60, 0x60, 0x7E, 0x7E, 0x60, 0x60, 0x60, 0x60, 0x00, 0x00, 0x00], // 'F'
+    [0x00, 0x00, 0x00, 0x1E, 0x3E, 0x60, 0x60, 0x60, 0x6E, 0x66, 0x62, 0x7E, 0x3E, 0x00, 0x00, 0x00], // 'G'
+    [0x00, 0x00, 0x00, 0x66, 0x66, 0x66, 0x66, 0x7E, 0x7E, 0x66, 0x66, 0x66, 0x66, 0x00, 0x00, 0x00], // 'H'
+    [0x00, 0x00, 0x00, 0x7E, 0x7E, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x7E, 0x7E, 0x00, 0x00, 0x00], // 'I'
+    [0x00, 0x00, 0x00, 0x3C, 0x3E, 0x06, 0x06, 0x06, 0x06, 0x06, 0x0E, 0x7C, 0x7C, 0x00, 0x00, 0x00], // 'J'
+    [0x00, 0x00, 0x00, 0x62, 0x66, 0x6C, 0x78, 0x78, 0x7C, 0x6C, 0x6E, 0x66, 0x63, 0x00, 0x00, 0x00], // 'K'
+    [0x00, 0x00, 0x00, 0x20, 0x70, 0x70, 0x70, 0x70, 0x70, 0x70, 0x70, 0x7E, 0x7F, 0x00, 0x00, 0x00], // 'L'
+    [0x00, 0x00, 0x00, 0x66, 0xE7, 0xE7, 0xFF, 0xFF, 0xDB, 0xC3, 0xC3, 0xC3, 0xC3, 0x00, 0x00, 0x00], // 'M'
+    [0x00, 0x00, 0x00, 0x62, 0x66, 0x76, 0x76, 0x76, 0x7E, 0x6E, 0x6E, 0x66, 0x66, 0x00, 0x00, 0x00], // 'N'
+    [0x00, 0x00, 0x00, 0x3C, 0x7E, 0x66, 0x66, 0x66, 0xE7, 0x66, 0x66, 0x7E, 0x3C, 0x00, 0x00, 0x00], // 'O'
+    [0x00, 0x00, 0x00, 0x78, 0x7E, 0x66, 0x67, 0x66, 0x7E, 0x60, 0x60, 0x60, 0x60, 0x00, 0x00, 0x00], // 'P'
+    [0x00, 0x00, 0x00, 0x3C, 0x7E, 0x66, 0x66, 0x66, 0xE7, 0x66, 0x66, 0x7E, 0x3C, 0x06, 0x04, 0x00], // 'Q'
+    [0x00, 0x00, 0x00, 0x78, 0x7E, 0x66, 0x66, 0x7E, 0x7C, 0x6C, 0x66, 0x66, 0x63, 0x00, 0x00, 0x00], // 'R'
+    [0x00, 0x00, 0x00, 0x3C, 0x7E, 0x60, 0x60, 0x78, 0x1E, 0x06, 0x06, 0x7E, 0x7C, 0x00, 0x00, 0x00], // 'S'
+    [0x00, 0x00, 0x00, 0x7E, 0xFF, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x00, 0x00, 0x00], // 'T'
+    [0x00, 0x00, 0x00, 0x42, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x7E, 0x3C, 0x00, 0x00, 0x00], // 'U'
+    [0x00, 0x00, 0x00, 0x42, 0x66, 0x66, 0x66, 0x66, 0x24, 0x3C, 0x3C, 0x3C, 0x18, 0x00, 0x00, 0x00], // 'V'
+    [0x00, 0x00, 0x00, 0xC3, 0xC3, 0xC3, 0xDB, 0xDB, 0x7A, 0x7E, 0x76, 0x66, 0x66, 0x00, 0x00, 0x00], // 'W'
+    [0x00, 0x00, 0x00, 0x42, 0x66, 0x7E, 0x3C, 0x18, 0x18, 0x3C, 0x3C, 0x66, 0xC3, 0x00, 0x00, 0x00], // 'X'
+    [0x00, 0x00, 0x00, 0xC3, 0x66, 0x66, 0x3C, 0x3C, 0x18, 0x18, 0x18, 0x18, 0x18, 0x00, 0x00, 0x00], // 'Y'
+    [0x00, 0x00, 0x00, 0x7E, 0x7F, 0x06, 0x0C, 0x1C, 0x18, 0x30, 0x70, 0x7E, 0x7F, 0x00, 0x00, 0x00], // 'Z'
+    [0x00, 0x00, 0x00, 0x1C, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x1C, 0x1C, 0x00], // '['
+    [0x00, 0x00, 0x00, 0x40, 0x60, 0x20, 0x30, 0x10, 0x18, 0x08, 0x0C, 0x0C, 0x06, 0x06, 0x00, 0x00], // '\\'
+    [0x00, 0x00, 0x00, 0x38, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x38, 0x38, 0x00], // ']'
+    [0x00, 0x00, 0x00, 0x18, 0x3C, 0x66, 0x42, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '^'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF], // '_'
+    [0x00, 0x00, 0x20, 0x30, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '`'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x18, 0x7E, 0x06, 0x3E, 0x7E, 0x66, 0x66, 0x7E, 0x00, 0x00, 0x00], // 'a'
+    [0x00, 0x00, 0x00, 0x60, 0x60, 0x60, 0x7E, 0x76, 0x66, 0x67, 0x66, 0x7E, 0x7C, 0x00, 0x00, 0x00], // 'b'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x3E, 0x72, 0x60, 0x60, 0x60, 0x36, 0x3E, 0x00, 0x00, 0x00], // 'c'
+    [0x00, 0x00, 0x00, 0x06, 0x06, 0x06, 0x7E, 0x6E, 0x66, 0xE6, 0x66, 0x6E, 0x3E, 0x00, 0x00, 0x00], // 'd'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x3E, 0x66, 0x7E, 0xFF, 0x60, 0x72, 0x3E, 0x00, 0x00, 0x00], // 'e'
+    [0x00, 0x00, 0x00, 0x1E, 0x18, 0x18, 0x7E, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x00, 0x00, 0x00], // 'f'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x7E, 0x66, 0x66, 0x66, 0x66, 0x7E, 0x3E, 0x06, 0x7E, 0x38], // 'g'
+    [0x00, 0x00, 0x00, 0x60, 0x60, 0x60, 0x7E, 0x76, 0x66, 0x66, 0x66, 0x66, 0x66, 0x00, 0x00, 0x00], // 'h'
+    [0x00, 0x00, 0x18, 0x18, 0x08, 0x00, 0x78, 0x18, 0x18, 0x18, 0x18, 0x7E, 0x7F, 0x00, 0x00, 0x00], // 'i'
+    [0x00, 0x00, 0x08, 0x1C, 0x08, 0x00, 0x3C, 0x1C, 0x1C, 0x1C, 0x1C, 0x1C, 0x1C, 0x1C, 0x78, 0x70], // 'j'
+    [0x00, 0x00, 0x00, 0x60, 0x60, 0x60, 0x66, 0x6C, 0x78, 0x7C, 0x6C, 0x66, 0x67, 0x00, 0x00, 0x00], // 'k'
+    [0x00, 0x00, 0x00, 0xF8, 0x38, 0x38, 0x38, 0x38, 0x38, 0x38, 0x38, 0x1E, 0x1E, 0x00, 0x00, 0x00], // 'l'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFE, 0xDB, 0xDB, 0xDB, 0xDB, 0xDB, 0xDB, 0x00, 0x00, 0x00], // 'm'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7E, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x00, 0x00, 0x00], // 'n'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3C, 0x66, 0x66, 0x66, 0x66, 0x7E, 0x3C, 0x00, 0x00, 0x00], // 'o'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7E, 0x76, 0x66, 0x67, 0x66, 0x7E, 0x7C, 0x60, 0x60, 0x60], // 'p'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7E, 0x6E, 0x66, 0xE6, 0x66, 0x7E, 0x3E, 0x06, 0x06, 0x06], // 'q'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3F, 0x38, 0x30, 0x30, 0x30, 0x30, 0x30, 0x00, 0x00, 0x00], // 'r'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x18, 0x3E, 0x60, 0x70, 0x3C, 0x06, 0x46, 0x7C, 0x00, 0x00, 0x00], // 's'
+    [0x00, 0x00, 0x00, 0x00, 0x38, 0x38, 0x7E, 0x38, 0x38, 0x38, 0x38, 0x1E, 0x1E, 0x00, 0x00, 0x00], // 't'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x66, 0x66, 0x66, 0x66, 0x66, 0x7E, 0x3E, 0x00, 0x00, 0x00], // 'u'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x66, 0x66, 0x66, 0x34, 0x3C, 0x3C, 0x18, 0x00, 0x00, 0x00], // 'v'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC3, 0xC3, 0xDB, 0x5A, 0x7E, 0x7E, 0x66, 0x00, 0x00, 0x00], // 'w'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x66, 0x3C, 0x1C, 0x18, 0x3C, 0x76, 0x66, 0x00, 0x00, 0x00], // 'x'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x66, 0x66, 0x66, 0x3C, 0x3C, 0x18, 0x18, 0x18, 0x70, 0x60], // 'y'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7E, 0x0E, 0x0C, 0x18, 0x30, 0x7E, 0x7E, 0x00, 0x00, 0x00], // 'z'
+    [0x00, 0x00, 0x00, 0x0E, 0x18, 0x18, 0x18, 0x18, 0x38, 0x70, 0x18, 0x18, 0x18, 0x18, 0x0E, 0x00], // '{'
+    [0x00, 0x00, 0x00, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18], // '|'
+    [0x00, 0x00, 0x00, 0x70, 0x18, 0x18, 0x18, 0x18, 0x1C, 0x0E, 0x18, 0x18, 0x18, 0x18, 0x70, 0x00], // '}'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7F, 0x0E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '~'
+];
+
+/// returns the bitmap glyph for the character. Characters outside of
+/// printable ascii are drawn as '?' (build with the truetype feature to draw
+/// them in png images).
+pub fn glyph(c: char) -> &'static [u8; 16] {
+    let code = c as u32;
+    let index = if (FIRST_GLYPH..=LAST_GLYPH).contains(&code) {
+        code - FIRST_GLYPH
+    } else {
+        '?' as u32 - FIRST_GLYPH
+    };
+
+    &GLYPHS[index as usize]
+}
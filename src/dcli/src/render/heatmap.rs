@@ -0,0 +1,196 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use chrono::Datelike;
+
+use super::chart::{ChartConfig, ChartTheme};
+use super::{Color, Drawing};
+use crate::calendar::{
+    group_weeks, heatmap_level, DayActivityCount, HEATMAP_LEVELS,
+};
+
+const PADDING: u32 = 24;
+
+//gap between the day labels and the cells
+const LABEL_GAP: u32 = 8;
+
+//space between cells, as a fraction of the cell size
+const CELL_GAP_RATIO: u32 = 6;
+
+const MIN_CELL_SIZE: u32 = 4;
+
+const DAY_LABELS: [&str; 7] = ["Mon", "", "Wed", "", "Fri", "", "Sun"];
+
+const MONTH_LABELS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct",
+    "Nov", "Dec",
+];
+
+//color for each level. Days without activities use the rule color, and the
+//busiest days the bar color.
+fn level_colors(theme: &ChartTheme) -> [Color; HEATMAP_LEVELS] {
+    let mut colors = [theme.rule; HEATMAP_LEVELS];
+    for (i, c) in colors.iter_mut().enumerate().skip(1) {
+        *c = theme
+            .background
+            .blend(theme.bar, i as f32 / (HEATMAP_LEVELS - 1) as f32);
+    }
+    colors
+}
+
+/// draws a github style calendar heatmap of activities per day, with a
+/// column for each week (starting on monday) and a row for each day of the
+/// week. Cells are sized to fit the configured chart width and height.
+pub fn draw_calendar_heatmap(
+    title: &str,
+    days: &[DayActivityCount],
+    config: &ChartConfig,
+) -> Drawing {
+    let theme = config.get_theme();
+    let line_height = Drawing::line_height(1);
+
+    let weeks = group_weeks(days);
+    let max = days.iter().map(|d| d.activities).max().unwrap_or(0);
+
+    let label_w = Drawing::text_width("Mon", 1);
+    let grid_x = PADDING + label_w + LABEL_GAP;
+
+    //title, then month labels above the grid
+    let grid_y = PADDING + line_height * 3;
+
+    //size cells to fit the configured size, but never smaller than the min
+    //(the image grows instead)
+    let cols = weeks.len().max(1) as u32;
+    let avail_w = config.width.saturating_sub(grid_x + PADDING);
+    let avail_h = config
+        .height
+        .saturating_sub(grid_y + PADDING + line_height * 2 + LABEL_GAP);
+    let pitch = (avail_w / cols).min(avail_h / 7).max(MIN_CELL_SIZE);
+    let gap = (pitch / CELL_GAP_RATIO).max(1);
+    let cell = pitch - gap;
+
+    let width = config.width.max(grid_x + cols * pitch + PADDING);
+    let legend_y = grid_y + 7 * pitch + LABEL_GAP;
+    let height = config.height.max(legend_y + line_height + PADDING);
+
+    let mut drawing = Drawing::new(width, height, theme.background);
+    drawing.text(PADDING, PADDING, title, theme.text, 1);
+
+    if weeks.is_empty() {
+        drawing.text(
+            PADDING,
+            PADDING + line_height * 2,
+            "No data",
+            theme.muted,
+            1,
+        );
+        return drawing;
+    }
+
+    for (i, label) in DAY_LABELS.iter().enumerate() {
+        if label.is_empty() {
+            continue;
+        }
+
+        //vertically center the label on the row
+        let y = grid_y + i as u32 * pitch + cell / 2;
+        drawing.text(
+            PADDING,
+            y.saturating_sub(line_height / 2),
+            label,
+            theme.muted,
+            1,
+        );
+    }
+
+    let colors = level_colors(&theme);
+
+    //x position after the last month label, so labels don't overlap
+    let mut label_end = 0;
+
+    for (col, week) in weeks.iter().enumerate() {
+        let x = grid_x + col as u32 * pitch;
+
+        //label the week with the first of the month (or the first week)
+        let month_start = week.iter().flatten().find(|d| d.day.day() == 1);
+        let label_day = match month_start {
+            Some(d) => Some(d.day),
+            None if col == 0 => week.iter().flatten().next().map(|d| d.day),
+            None => None,
+        };
+
+        if let Some(day) = label_day {
+            let label = MONTH_LABELS[day.month0() as usize];
+            if x >= label_end {
+                drawing.text(
+                    x,
+                    grid_y - line_height - LABEL_GAP,
+                    label,
+                    theme.muted,
+                    1,
+                );
+                label_end = x + Drawing::text_width(label, 1) + LABEL_GAP;
+            }
+        }
+
+        for (row, day) in week.iter().enumerate() {
+            if let Some(d) = day {
+                drawing.fill_rect(
+                    x,
+                    grid_y + row as u32 * pitch,
+                    cell,
+                    cell,
+                    colors[heatmap_level(d.activities, max)],
+                );
+            }
+        }
+    }
+
+    //legend in the bottom right, i.e. Less [] [] [] [] [] More
+    let less_w = Drawing::text_width("Less", 1);
+    let more_w = Drawing::text_width("More", 1);
+    let legend_w =
+        less_w + more_w + LABEL_GAP * 2 + HEATMAP_LEVELS as u32 * pitch;
+    let legend_x = (grid_x + cols * pitch).saturating_sub(legend_w).max(grid_x);
+    let mut x = legend_x;
+    let text_y = legend_y + cell / 2;
+    let text_y = text_y.saturating_sub(line_height / 2);
+
+    drawing.text(x, text_y, "Less", theme.muted, 1);
+    x += less_w + LABEL_GAP;
+
+    for c in &colors {
+        drawing.fill_rect(x, legend_y, cell, cell, *c);
+        x += pitch;
+    }
+
+    drawing.text(x + LABEL_GAP - gap, text_y, "More", theme.muted, 1);
+
+    //totals to the left of the legend, if there is room
+    let total: u32 = days.iter().map(|d| d.activities).sum();
+    let summary = format!("{} activities, max {} in a day", total, max);
+    if grid_x + Drawing::text_width(&summary, 1) + LABEL_GAP <= legend_x {
+        drawing.text(grid_x, text_y, &summary, theme.muted, 1);
+    }
+
+    drawing
+}
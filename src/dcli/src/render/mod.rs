@@ -0,0 +1,195 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+pub mod chart;
+pub mod font;
+pub mod heatmap;
+pub mod png;
+pub mod svg;
+
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+use crate::error::Error;
+
+use self::font::{GLYPH_HEIGHT, GLYPH_WIDTH};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b }
+    }
+
+    /// mixes the color with other. amount is from 0.0 (this color) to 1.0
+    /// (other)
+    pub fn blend(&self, other: Color, amount: f32) -> Color {
+        let amount = amount.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| -> u8 {
+            (a as f32 + (b as f32 - a as f32) * amount).round() as u8
+        };
+
+        Color::rgb(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+        )
+    }
+
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Shape {
+    Rect {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        color: Color,
+    },
+
+    //x and y are the top left of the text. scale is an integer multiple of
+    //the bitmap font size
+    Text {
+        x: u32,
+        y: u32,
+        text: String,
+        color: Color,
+        scale: u32,
+    },
+}
+
+/// A list of shapes drawn on a fixed size canvas, which can be rendered to
+/// either svg or png.
+///
+/// Text is laid out on a fixed width grid (see render::font), so that both
+/// formats produce the same layout.
+#[derive(Debug, Clone)]
+pub struct Drawing {
+    pub width: u32,
+    pub height: u32,
+    pub background: Color,
+    pub shapes: Vec<Shape>,
+}
+
+impl Drawing {
+    pub fn new(width: u32, height: u32, background: Color) -> Drawing {
+        Drawing {
+            width,
+            height,
+            background,
+            shapes: Vec::new(),
+        }
+    }
+
+    pub fn fill_rect(
+        &mut self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        color: Color,
+    ) {
+        self.shapes.push(Shape::Rect {
+            x,
+            y,
+            width,
+            height,
+            color,
+        });
+    }
+
+    pub fn text(
+        &mut self,
+        x: u32,
+        y: u32,
+        text: &str,
+        color: Color,
+        scale: u32,
+    ) {
+        self.shapes.push(Shape::Text {
+            x,
+            y,
+            text: text.to_string(),
+            color,
+            scale,
+        });
+    }
+
+    /// width in pixels of the text when drawn at the specified scale
+    pub fn text_width(text: &str, scale: u32) -> u32 {
+        text.chars().count() as u32 * GLYPH_WIDTH * scale
+    }
+
+    pub fn line_height(scale: u32) -> u32 {
+        GLYPH_HEIGHT * scale
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageFormat {
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    /// determines the image format from the file extension of the path
+    pub fn from_path(path: &Path) -> Result<ImageFormat, Error> {
+        let ext = path
+            .extension()
+            .and_then(OsStr::to_str)
+            .map(|e| e.to_lowercase());
+
+        match ext.as_deref() {
+            Some("png") => Ok(ImageFormat::Png),
+            Some("svg") => Ok(ImageFormat::Svg),
+            _ => Err(Error::UnsupportedOutputFormat {
+                description: format!(
+                    "Unsupported image type for {}. Use a .png or .svg \
+                    file extension.",
+                    path.display()
+                ),
+            }),
+        }
+    }
+}
+
+/// renders the drawing and writes it to the path, using the file extension
+/// to determine the image format.
+pub fn write_image(path: &Path, drawing: &Drawing) -> Result<(), Error> {
+    let data = match ImageFormat::from_path(path)? {
+        ImageFormat::Png => png::render_png(drawing)?,
+        ImageFormat::Svg => svg::render_svg(drawing).into_bytes(),
+    };
+
+    fs::write(path, data)?;
+    Ok(())
+}
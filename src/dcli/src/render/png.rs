@@ -0,0 +1,302 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::io::Write;
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+
+#[cfg(feature = "truetype")]
+use ab_glyph::{point, Font, FontRef};
+
+#[cfg(feature = "truetype")]
+use super::font::{FONT_SIZE, GLYPH_BASELINE, TRUETYPE_FONT};
+
+use super::font::{glyph, GLYPH_WIDTH};
+use super::{Color, Drawing, Shape};
+use crate::error::Error;
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// largest width and height of a rendered png, so the pixel buffer size
+/// can't overflow
+pub const MAX_IMAGE_SIZE: u32 = 16384;
+
+//rgb, 8 bits per channel
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+
+    #[cfg(feature = "truetype")]
+    font: FontRef<'static>,
+}
+
+impl Canvas {
+    fn new(
+        width: u32,
+        height: u32,
+        background: Color,
+    ) -> Result<Canvas, Error> {
+        check_size(width, height)?;
+
+        #[cfg(feature = "truetype")]
+        let font = match FontRef::try_from_slice(TRUETYPE_FONT) {
+            Ok(e) => e,
+            Err(e) => {
+                return Err(Error::Unknown {
+                    description: format!("Could not load font : {}", e),
+                })
+            }
+        };
+
+        let count = width as usize * height as usize;
+        let mut pixels = Vec::with_capacity(count * 3);
+        for _ in 0..count {
+            pixels.extend_from_slice(&[
+                background.r,
+                background.g,
+                background.b,
+            ]);
+        }
+
+        Ok(Canvas {
+            width,
+            height,
+            pixels,
+            #[cfg(feature = "truetype")]
+            font,
+        })
+    }
+
+    fn set_pixel(&mut self, x: u32, y: u32, color: Color) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+
+        let i = (y as usize * self.width as usize + x as usize) * 3;
+        self.pixels[i] = color.r;
+        self.pixels[i + 1] = color.g;
+        self.pixels[i + 2] = color.b;
+    }
+
+    //mixes the color into the pixel. amount is from 0.0 (unchanged) to 1.0
+    #[cfg(feature = "truetype")]
+    fn blend_pixel(&mut self, x: u32, y: u32, color: Color, amount: f32) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+
+        let i = (y as usize * self.width as usize + x as usize) * 3;
+        let current =
+            Color::rgb(self.pixels[i], self.pixels[i + 1], self.pixels[i + 2]);
+        self.set_pixel(x, y, current.blend(color, amount));
+    }
+
+    fn fill_rect(
+        &mut self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        color: Color,
+    ) {
+        for py in y..y.saturating_add(height).min(self.height) {
+            for px in x..x.saturating_add(width).min(self.width) {
+                self.set_pixel(px, py, color);
+            }
+        }
+    }
+
+    fn draw_text(
+        &mut self,
+        x: u32,
+        y: u32,
+        text: &str,
+        color: Color,
+        scale: u32,
+    ) {
+        let scale = scale.max(1);
+        for (i, c) in text.chars().enumerate() {
+            let gx = x + i as u32 * GLYPH_WIDTH * scale;
+            if gx >= self.width {
+                break;
+            }
+
+            #[cfg(feature = "truetype")]
+            if self.draw_truetype_glyph(gx, y, c, color, scale) {
+                continue;
+            }
+
+            self.draw_bitmap_glyph(gx, y, c, color, scale);
+        }
+    }
+
+    fn draw_bitmap_glyph(
+        &mut self,
+        x: u32,
+        y: u32,
+        c: char,
+        color: Color,
+        scale: u32,
+    ) {
+        for (row, bits) in glyph(c).iter().enumerate() {
+            for col in 0..GLYPH_WIDTH {
+                if bits & (0x80 >> col) == 0 {
+                    continue;
+                }
+
+                self.fill_rect(
+                    x + col * scale,
+                    y + row as u32 * scale,
+                    scale,
+                    scale,
+                    color,
+                );
+            }
+        }
+    }
+
+    //draws the character from the truetype font, on the same baseline as the
+    //bitmap font. Returns false if the font doesn't have the character.
+    #[cfg(feature = "truetype")]
+    fn draw_truetype_glyph(
+        &mut self,
+        x: u32,
+        y: u32,
+        c: char,
+        color: Color,
+        scale: u32,
+    ) -> bool {
+        let id = self.font.glyph_id(c);
+        if id.0 == 0 {
+            return false;
+        }
+
+        let glyph = id.with_scale_and_position(
+            FONT_SIZE * scale as f32,
+            point(x as f32, (y + GLYPH_BASELINE * scale) as f32),
+        );
+
+        //whitespace has no outline
+        let outline = match self.font.outline_glyph(glyph) {
+            Some(e) => e,
+            None => return true,
+        };
+
+        let bounds = outline.px_bounds();
+        outline.draw(|gx, gy, coverage| {
+            let px = bounds.min.x as i64 + gx as i64;
+            let py = bounds.min.y as i64 + gy as i64;
+            if px >= 0 && py >= 0 {
+                self.blend_pixel(px as u32, py as u32, color, coverage);
+            }
+        });
+
+        true
+    }
+}
+
+fn check_size(width: u32, height: u32) -> Result<(), Error> {
+    if width > MAX_IMAGE_SIZE || height > MAX_IMAGE_SIZE {
+        return Err(Error::UnsupportedOutputFormat {
+            description: format!(
+                "Image size {}x{} is larger than the maximum png size of \
+                {}x{}.",
+                width, height, MAX_IMAGE_SIZE, MAX_IMAGE_SIZE
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(kind);
+    hasher.update(data);
+    out.extend_from_slice(&hasher.finalize().to_be_bytes());
+}
+
+/// rasterizes the drawing and encodes it as a png
+pub fn render_png(drawing: &Drawing) -> Result<Vec<u8>, Error> {
+    let mut canvas =
+        Canvas::new(drawing.width, drawing.height, drawing.background)?;
+
+    for s in &drawing.shapes {
+        match s {
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                color,
+            } => canvas.fill_rect(*x, *y, *width, *height, *color),
+            Shape::Text {
+                x,
+                y,
+                text,
+                color,
+                scale,
+            } => canvas.draw_text(*x, *y, text, *color, *scale),
+        }
+    }
+
+    encode_png(canvas.width, canvas.height, &canvas.pixels)
+}
+
+/// encodes rgb pixels (8 bits per channel, one row after another) as a png
+pub fn encode_png(
+    width: u32,
+    height: u32,
+    pixels: &[u8],
+) -> Result<Vec<u8>, Error> {
+    check_size(width, height)?;
+
+    //each scanline is prefixed with its filter type (0, none)
+    let stride = width as usize * 3;
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    for row in pixels.chunks(stride) {
+        encoder.write_all(&[0])?;
+        encoder.write_all(row)?;
+    }
+    let compressed = encoder.finish()?;
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    //bit depth 8, color type 2 (rgb), default compression, filter and
+    //no interlacing
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    let mut out = Vec::new();
+    out.extend_from_slice(&PNG_SIGNATURE);
+    write_chunk(&mut out, b"IHDR", &header);
+    write_chunk(&mut out, b"IDAT", &compressed);
+    write_chunk(&mut out, b"IEND", &[]);
+
+    Ok(out)
+}
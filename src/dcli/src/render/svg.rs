@@ -0,0 +1,86 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use super::font::{FONT_SIZE, GLYPH_BASELINE};
+use super::{Drawing, Shape};
+use crate::html::escape;
+
+/// renders the drawing as a self-contained svg document
+pub fn render_svg(drawing: &Drawing) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" \
+        height=\"{h}\" viewBox=\"0 0 {w} {h}\" \
+        font-family=\"DejaVu Sans Mono, Menlo, Consolas, monospace\" \
+        font-weight=\"bold\">\n",
+        w = drawing.width,
+        h = drawing.height
+    ));
+
+    out.push_str(&format!(
+        "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+        drawing.background.to_hex()
+    ));
+
+    for s in &drawing.shapes {
+        match s {
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                color,
+            } => {
+                out.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" \
+                    fill=\"{}\"/>\n",
+                    x,
+                    y,
+                    width,
+                    height,
+                    color.to_hex()
+                ));
+            }
+            Shape::Text {
+                x,
+                y,
+                text,
+                color,
+                scale,
+            } => {
+                //xml:space preserves the padding used to align columns
+                out.push_str(&format!(
+                    "<text x=\"{}\" y=\"{}\" font-size=\"{:.1}\" \
+                    fill=\"{}\" xml:space=\"preserve\">{}</text>\n",
+                    x,
+                    y + GLYPH_BASELINE * scale,
+                    FONT_SIZE * *scale as f32,
+                    color.to_hex(),
+                    escape(text)
+                ));
+            }
+        }
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
@@ -128,11 +128,31 @@ pub struct ActivityHistoricalStatsValues {
     )]
     pub time_played_seconds: f32,
 
+    //player count and team score may be missing for pve activities
     #[serde(rename = "playerCount", deserialize_with = "property_to_value")]
+    #[serde(default)]
     pub player_count: f32,
 
     #[serde(rename = "teamScore", deserialize_with = "property_to_value")]
+    #[serde(default)]
     pub team_score: f32,
+
+    //only included in pgcr entries. f64, since ids dont fit in an f32
+    #[serde(rename = "fireteamId", deserialize_with = "property_to_value")]
+    #[serde(default)]
+    pub fireteam_id: f64,
+}
+
+impl ActivityHistoricalStatsValues {
+    /// id of the fireteam the player queued with, or None if it wasn't
+    /// included in the response
+    pub fn get_fireteam_id(&self) -> Option<i64> {
+        if self.fireteam_id == 0.0 {
+            None
+        } else {
+            Some(self.fireteam_id as i64)
+        }
+    }
 }
 
 //https://bungie-net.github.io/multi/schema_Destiny-HistoricalStats-DestinyHistoricalStatsActivity.html#schema_Destiny-HistoricalStats-DestinyHistoricalStatsActivity
@@ -60,6 +60,10 @@ pub struct CharacterData {
     #[serde(rename = "baseCharacterLevel")]
     pub base_character_level: u32,
 
+    //hash of the DestinyRecordDefinition for the currently equipped title
+    #[serde(rename = "titleRecordHash", default)]
+    pub title_record_hash: Option<u32>,
+
     #[serde(skip)]
     pub emblem: Option<Emblem>,
 
@@ -34,6 +34,13 @@ pub fn check_destiny_response_status(
         5 => Err(Error::ApiNotAvailableException),
         7 => Err(Error::ParameterParseFailure),
         18 => Err(Error::InvalidParameters),
+        36 | 37 | 38 | 51 | 52 | 53 | 54 => Err(Error::ApiThrottled {
+            description: format!(
+                "{}({}) : Throttle Seconds : {}",
+                status.error_status, status.error_code, status.throttle_seconds
+            ),
+            seconds: status.throttle_seconds,
+        }),
         1665 => Err(Error::PrivacyException),
         2102 => Err(Error::ApiKeyMissingFromRequest),
         _ => Err(Error::ApiStatus {
@@ -55,6 +55,9 @@ pub struct ProfileResponse {
     pub character_activities: Option<CharacterActivitiesFieldData>,
 
     pub profile: Option<ProfileData>,
+
+    #[serde(rename = "characterProgressions")]
+    pub character_progressions: Option<CharacterProgressionsFieldData>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -83,6 +86,31 @@ pub struct CharacterActivitiesFieldData {
     pub data: HashMap<String, CharacterActivitiesData>,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CharacterProgressionsFieldData {
+    pub data: HashMap<String, CharacterProgressionsData>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CharacterProgressionsData {
+    //keyed by progression hash
+    pub progressions: HashMap<String, ProgressionData>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProgressionData {
+    #[serde(rename = "progressionHash")]
+    pub progression_hash: u32,
+
+    #[serde(rename = "currentProgress")]
+    pub current_progress: u32,
+
+    pub level: u32,
+
+    #[serde(rename = "stepIndex")]
+    pub step_index: u32,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CharacterActivitiesData {
     #[serde(
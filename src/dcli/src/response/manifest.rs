@@ -20,8 +20,13 @@
 * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 */
 
+use std::collections::HashMap;
+
 use serde_derive::{Deserialize, Serialize};
 
+use crate::apiutils::RESOURCE_BASE_URL;
+use crate::enums::language::Language;
+
 use crate::response::drs::{DestinyResponseStatus, IsDestinyAPIResponse};
 use crate::response::utils::prepend_base_url;
 
@@ -52,4 +57,23 @@ pub struct ManifestData {
 pub struct MobileWorldContentPathsData {
     #[serde(deserialize_with = "prepend_base_url")]
     pub en: String,
+
+    //paths for the other languages, keyed by language code. Stored as
+    //returned by the api (without the base url)
+    #[serde(flatten)]
+    pub other: HashMap<String, String>,
+}
+
+impl MobileWorldContentPathsData {
+    /// url for the manifest in the specified language, or None if the api
+    /// did not return one for the language
+    pub fn url(&self, language: &Language) -> Option<String> {
+        match language {
+            Language::English => Some(self.en.clone()),
+            _ => self
+                .other
+                .get(language.code())
+                .map(|e| format!("{}{}", RESOURCE_BASE_URL, e)),
+        }
+    }
 }
@@ -27,4 +27,5 @@ pub mod gpr;
 pub mod manifest;
 pub mod pgcr;
 pub mod stats;
+pub mod user;
 pub mod utils;
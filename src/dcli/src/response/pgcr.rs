@@ -51,6 +51,23 @@ impl IsDestinyAPIResponse for PGCRResponse {
     }
 }
 
+/// PGCR response where the report is kept as untyped json, so fields which
+/// dcli does not parse are preserved
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RawPGCRResponse {
+    #[serde(rename = "Response")]
+    pub response: Option<serde_json::Value>,
+
+    #[serde(flatten)]
+    pub status: DestinyResponseStatus,
+}
+
+impl IsDestinyAPIResponse for RawPGCRResponse {
+    fn get_status(&self) -> &DestinyResponseStatus {
+        &self.status
+    }
+}
+
 //https://bungie-net.github.io/multi/schema_Destiny-HistoricalStats-DestinyPostGameCarnageReportData.html#schema_Destiny-HistoricalStats-DestinyPostGameCarnageReportData
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DestinyPostGameCarnageReportData {
@@ -73,8 +90,8 @@ pub struct DestinyPostGameCarnageReportTeamEntry {
     #[serde(rename = "teamId")]
     pub team: i32,
 
-    #[serde(rename = "teamName")]
-    pub team_name: String,
+    #[serde(rename = "teamName", default)]
+    pub team_name: Option<String>,
 
     #[serde(deserialize_with = "property_to_value")]
     pub score: f32,
@@ -207,21 +207,12 @@ impl ops::Add<PvpStatsData> for PvpStatsData {
         //so we dont really every need to aggregate stats.
         //but we will keep it here for completeness sake and in case the API is
         //ever updated
-        let best_single_game_kills: Option<f32>;
-        if _cs.best_single_game_kills.is_none()
-            || self.best_single_game_kills.is_none()
-        {
-            if _cs.best_single_game_kills.is_none() {
-                best_single_game_kills = self.best_single_game_kills;
-            } else {
-                best_single_game_kills = _cs.best_single_game_kills;
-            }
-        } else {
-            let a = _cs.best_single_game_kills.unwrap();
-            let b = self.best_single_game_kills.unwrap();
-            let c = if a > b { a } else { b };
-            best_single_game_kills = Some(c);
-        }
+        let best_single_game_kills: Option<f32> =
+            match (_cs.best_single_game_kills, self.best_single_game_kills) {
+                (Some(a), Some(b)) => Some(if a > b { a } else { b }),
+                (None, b) => b,
+                (a, None) => a,
+            };
 
         let kills = self.kills + _cs.kills;
         let total_kill_distance =
@@ -0,0 +1,55 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::response::drs::{DestinyResponseStatus, IsDestinyAPIResponse};
+
+//https://bungie-net.github.io/multi/operation_get_User-GetMembershipDataById.html#operation_get_User-GetMembershipDataById
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetMembershipsResponse {
+    #[serde(rename = "Response")]
+    pub response: Option<UserMembershipData>,
+
+    #[serde(flatten)]
+    pub status: DestinyResponseStatus,
+}
+
+impl IsDestinyAPIResponse for GetMembershipsResponse {
+    fn get_status(&self) -> &DestinyResponseStatus {
+        &self.status
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UserMembershipData {
+    //not all accounts have a linked bungie.net user
+    #[serde(rename = "bungieNetUser")]
+    pub bungie_net_user: Option<GeneralUser>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GeneralUser {
+    //rfc3339 date / time the bungie.net account was first accessed
+    #[serde(rename = "firstAccess")]
+    pub first_access: Option<String>,
+}
@@ -181,7 +181,7 @@ where
     let out = match s.parse::<i64>() {
         Ok(e) => e,
         Err(e) => {
-            return Err(serde::de::Error::custom(&format!(
+            return Err(serde::de::Error::custom(format!(
                 "Could not parse string to i64 : {}",
                 e
             )))
@@ -203,7 +203,7 @@ where
     let n = match NaiveDateTime::parse_from_str(&s, API_DATE_TIME_FORMAT) {
         Ok(e) => e,
         Err(e) => {
-            return Err(serde::de::Error::custom(&format!(
+            return Err(serde::de::Error::custom(format!(
                 "Could not parse date-time : {}",
                 e
             )))
@@ -0,0 +1,155 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Groups activities into play sessions, and estimates how the time in each
+//session was spent. Sessions are runs of activities where each one starts
+//within a set amount of time of the previous one.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::crucible::CruciblePlayerActivityPerformance;
+
+/// if there is more than this much time between the start of two activities,
+/// then they are in different sessions
+pub const DEFAULT_SESSION_GAP_MINUTES: i64 = 60;
+
+//gaps between activities longer than this are assumed to be breaks (i.e.
+//doing something else in game, or away from the game), rather than time in
+//orbit or matchmaking, and are not included in the queue time
+const MAX_QUEUE_GAP_MINUTES: i64 = 15;
+
+/// groups activities into play sessions. Expects activities sorted newest
+/// first (as returned from the activity store) and returns sessions oldest
+/// first, with the activities in each session oldest first.
+pub fn group_sessions(
+    data: &[CruciblePlayerActivityPerformance],
+    gap: Duration,
+) -> Vec<Vec<&CruciblePlayerActivityPerformance>> {
+    let mut sessions: Vec<Vec<&CruciblePlayerActivityPerformance>> = Vec::new();
+
+    for activity in data.iter().rev() {
+        let new_session = match sessions.last().and_then(|s| s.last()) {
+            Some(last) => {
+                activity.activity_detail.period - last.activity_detail.period
+                    > gap
+            }
+            None => true,
+        };
+
+        if new_session {
+            sessions.push(Vec::new());
+        }
+
+        sessions.last_mut().unwrap().push(activity);
+    }
+
+    sessions
+}
+
+/// estimate of the time spent in matches compared to time spent between
+/// them (in orbit, loading and matchmaking) for a session
+#[derive(Debug, Clone)]
+pub struct SessionQueueTime {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub activities: u32,
+
+    /// time the player was in matches
+    pub match_seconds: u32,
+
+    /// estimated time between matches
+    pub queue_seconds: u32,
+
+    /// gaps which were too long to be queue time, and were not counted
+    pub breaks: u32,
+}
+
+impl SessionQueueTime {
+    /// estimates queue time from the gaps between consecutive activities in
+    /// the session. Expects activities oldest first (as returned by
+    /// group_sessions).
+    ///
+    /// Gaps are measured from when the player left an activity to when they
+    /// joined the next, so joining in progress and leaving early are taken
+    /// into account. Overlapping activities (which can happen with bad data)
+    /// count as no gap, and gaps longer than MAX_QUEUE_GAP_MINUTES count as
+    /// breaks.
+    pub fn with_session(
+        session: &[&CruciblePlayerActivityPerformance],
+    ) -> Option<SessionQueueTime> {
+        let first = session.first()?;
+        let max_gap = Duration::minutes(MAX_QUEUE_GAP_MINUTES);
+
+        let mut out = SessionQueueTime {
+            start: first.activity_detail.period,
+            end: first.activity_detail.period,
+            activities: 0,
+            match_seconds: 0,
+            queue_seconds: 0,
+            breaks: 0,
+        };
+
+        let mut last_left: Option<DateTime<Utc>> = None;
+        for a in session {
+            let stats = &a.performance.stats;
+            let joined = a.activity_detail.period
+                + Duration::seconds(stats.start_seconds as i64);
+            let left =
+                joined + Duration::seconds(stats.time_played_seconds as i64);
+
+            if let Some(l) = last_left {
+                let gap = joined - l;
+                if gap > max_gap {
+                    out.breaks += 1;
+                } else if gap > Duration::zero() {
+                    out.queue_seconds += gap.num_seconds() as u32;
+                }
+            }
+
+            out.activities += 1;
+            out.match_seconds += stats.time_played_seconds;
+            out.end = out.end.max(left);
+            last_left = Some(last_left.map_or(left, |l| l.max(left)));
+        }
+
+        Some(out)
+    }
+
+    /// percent of the time in the session spent between matches
+    pub fn queue_percent(&self) -> f32 {
+        let total = self.match_seconds + self.queue_seconds;
+        if total == 0 {
+            return 0.0;
+        }
+
+        (self.queue_seconds as f32 / total as f32) * 100.0
+    }
+
+    /// estimated minutes between matches for each minute in a match
+    pub fn queue_ratio(&self) -> f32 {
+        if self.match_seconds == 0 {
+            return 0.0;
+        }
+
+        self.queue_seconds as f32 / self.match_seconds as f32
+    }
+}
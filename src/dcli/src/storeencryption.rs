@@ -0,0 +1,456 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Transparent file level encryption for the activity store.
+//
+//sqlx doesn't give us a way to key a SQLCipher database before it runs its
+//own connection pragmas, so instead we keep the store encrypted on disk
+//(dcli.sqlite3.enc) and decrypt it into a working copy while a tool is
+//running. Each process gets its own working copy, which is encrypted (if it
+//changed) and removed when the store is closed. If the encrypted store was
+//changed by another process while the working copy was open, the working
+//copy is not encrypted back to the store, so one process can't overwrite
+//the changes of another.
+//
+//Working copies are also removed if the process exits without closing the
+//store (although any changes are lost). If the process is killed or crashes,
+//the working copy is left on disk until the store is next opened.
+//
+//File format is:
+//[ MAGIC (8 bytes) | salt (16 bytes) | nonce (12 bytes) | ciphertext ]
+
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, Once};
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use fs2::FileExt;
+use pbkdf2::pbkdf2_hmac;
+use rand_core::RngCore;
+use sha2::{Digest, Sha256};
+
+use crate::error::Error;
+
+pub const PASSPHRASE_ENV_VAR: &str = "DCLI_STORE_PASSPHRASE";
+pub const ENCRYPTED_EXTENSION: &str = "enc";
+
+const MAGIC: &[u8; 8] = b"DCLIENC1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const KDF_ROUNDS: u32 = 100_000;
+const WORKING_EXTENSION: &str = "working";
+const LOCK_EXTENSION: &str = "lock";
+
+/// returns the passphrase to use for the store, if one has been set in the
+/// DCLI_STORE_PASSPHRASE environment variable
+pub fn passphrase_from_env() -> Option<String> {
+    match env::var(PASSPHRASE_ENV_VAR) {
+        Ok(e) if !e.is_empty() => Some(e),
+        _ => None,
+    }
+}
+
+/// path of the encrypted version of the specified file
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut p = path.as_os_str().to_owned();
+    p.push(".");
+    p.push(ENCRYPTED_EXTENSION);
+    PathBuf::from(p)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Key {
+    let mut key = [0u8; 32];
+    pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, KDF_ROUNDS, &mut key);
+    *Key::from_slice(&key)
+}
+
+pub fn encrypt_file(
+    src: &Path,
+    dest: &Path,
+    passphrase: &str,
+) -> Result<(), Error> {
+    let plaintext = fs::read(src)?;
+
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt));
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let ciphertext =
+        cipher.encrypt(&nonce, plaintext.as_ref()).map_err(|_| {
+            Error::Encryption {
+                description: format!("Could not encrypt {}", src.display()),
+            }
+        })?;
+
+    let mut out: Vec<u8> = Vec::with_capacity(
+        MAGIC.len() + SALT_LEN + NONCE_LEN + ciphertext.len(),
+    );
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+
+    //write to a temp file and then move it into place, so we never end up
+    //with a partially written encrypted store
+    let tmp = dest.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&tmp, out)?;
+    fs::rename(&tmp, dest)?;
+
+    Ok(())
+}
+
+pub fn decrypt_file(
+    src: &Path,
+    dest: &Path,
+    passphrase: &str,
+) -> Result<(), Error> {
+    let data = fs::read(src)?;
+
+    let header_len = MAGIC.len() + SALT_LEN + NONCE_LEN;
+    if data.len() < header_len || &data[..MAGIC.len()] != MAGIC {
+        return Err(Error::Encryption {
+            description: format!(
+                "{} is not an encrypted dcli file",
+                src.display()
+            ),
+        });
+    }
+
+    let salt = &data[MAGIC.len()..MAGIC.len() + SALT_LEN];
+    let nonce = Nonce::from_slice(&data[MAGIC.len() + SALT_LEN..header_len]);
+
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, salt));
+    let plaintext =
+        cipher.decrypt(nonce, &data[header_len..]).map_err(|_| {
+            Error::Encryption {
+                description: format!(
+                    "Could not decrypt {}. Check that {} is set correctly.",
+                    src.display(),
+                    PASSPHRASE_ENV_VAR
+                ),
+            }
+        })?;
+
+    fs::write(dest, plaintext)?;
+
+    Ok(())
+}
+
+//plaintext files (working copies and snapshots) which need to be removed if
+//the process exits without closing the store
+static PLAINTEXT_FILES: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+static REMOVE_AT_EXIT: Once = Once::new();
+
+fn register(path: &Path) {
+    //std::process::exit doesn't run destructors, so we also clean up from
+    //an exit handler. atexit only stores the function pointer.
+    REMOVE_AT_EXIT.call_once(|| unsafe {
+        libc::atexit(remove_registered);
+    });
+
+    if let Ok(mut files) = PLAINTEXT_FILES.lock() {
+        files.push(path.to_path_buf());
+    }
+}
+
+fn unregister(path: &Path) {
+    if let Ok(mut files) = PLAINTEXT_FILES.lock() {
+        files.retain(|e| e != path);
+    }
+}
+
+extern "C" fn remove_registered() {
+    //never block at exit
+    if let Ok(files) = PLAINTEXT_FILES.try_lock() {
+        for f in files.iter() {
+            let _ = remove_database_files(f);
+        }
+    }
+}
+
+//removes a sqlite database along with its wal and shm files
+fn remove_database_files(path: &Path) -> Result<(), Error> {
+    let mut paths = vec![path.to_path_buf()];
+    for suffix in ["-wal", "-shm"].iter() {
+        let mut p = path.as_os_str().to_owned();
+        p.push(suffix);
+        paths.push(PathBuf::from(p));
+    }
+
+    for p in paths {
+        if p.exists() {
+            fs::remove_file(p)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn digest_file(path: &Path) -> Result<Vec<u8>, Error> {
+    Ok(Sha256::digest(fs::read(path)?).to_vec())
+}
+
+//hash of the encrypted store, or None if it doesn't exist
+fn digest_encrypted(path: &Path) -> Result<Option<Vec<u8>>, Error> {
+    if path.exists() {
+        Ok(Some(digest_file(path)?))
+    } else {
+        Ok(None)
+    }
+}
+
+//lock held while the encrypted store is read or replaced, so checking that
+//it hasn't changed and replacing it can't be interleaved with another process
+fn lock_encrypted(enc: &Path) -> Result<File, Error> {
+    let mut p = enc.as_os_str().to_owned();
+    p.push(".");
+    p.push(LOCK_EXTENSION);
+
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(PathBuf::from(p))?;
+    file.lock_exclusive()?;
+
+    Ok(file)
+}
+
+//removes working copies left by processes which were killed or crashed
+//before they could remove them
+fn remove_stale_working_copies(path: &Path) {
+    let (dir, name) = match (path.parent(), path.file_name()) {
+        (Some(d), Some(n)) => (d, n.to_string_lossy().to_string()),
+        _ => return,
+    };
+
+    let entries = match fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(_e) => return,
+    };
+
+    let prefix = format!("{}.", name);
+    let suffix = format!(".{}", WORKING_EXTENSION);
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let pid = match file_name
+            .strip_prefix(&prefix)
+            .and_then(|e| e.strip_suffix(&suffix))
+            .and_then(|e| e.parse::<u32>().ok())
+        {
+            Some(e) => e,
+            None => continue,
+        };
+
+        if pid != std::process::id() && !is_process_running(pid) {
+            let _ = remove_database_files(&entry.path());
+        }
+    }
+}
+
+#[cfg(unix)]
+fn is_process_running(pid: u32) -> bool {
+    //signal 0 only checks whether the process exists
+    let result = unsafe { libc::kill(pid as libc::pid_t, 0) };
+    result == 0
+        || std::io::Error::last_os_error().raw_os_error() != Some(libc::ESRCH)
+}
+
+//we can't tell, so assume it is, and leave its working copy alone
+#[cfg(not(unix))]
+fn is_process_running(_pid: u32) -> bool {
+    true
+}
+
+/// an unencrypted copy of an encrypted store, private to this process. The
+/// copy is removed when it is closed or dropped, or if the process exits.
+#[derive(Debug)]
+pub struct WorkingCopy {
+    path: PathBuf,
+    store_path: PathBuf,
+    passphrase: String,
+
+    //hash of the data when it was decrypted, so unchanged copies are not
+    //encrypted again. None if the copy always needs to be encrypted.
+    digest: Option<Vec<u8>>,
+
+    //hash of the encrypted store when the copy was made, so we can tell if
+    //another process changed it while the copy was open
+    source_digest: Option<Vec<u8>>,
+
+    //whether the copy is encrypted back to the store when closed
+    persist: bool,
+
+    //set if the store was not encrypted yet, so the unencrypted store is
+    //removed once it has been
+    replaces_plaintext: bool,
+    closed: bool,
+}
+
+impl WorkingCopy {
+    /// decrypts the store at path into a working copy for this process. If
+    /// the store has not been encrypted yet, it is encrypted when the working
+    /// copy is closed.
+    pub fn open(path: &Path, passphrase: &str) -> Result<WorkingCopy, Error> {
+        remove_stale_working_copies(path);
+
+        let working = working_copy_path(path);
+        remove_database_files(&working)?;
+
+        let mut out = WorkingCopy {
+            path: working,
+            store_path: path.to_path_buf(),
+            passphrase: passphrase.to_string(),
+            digest: None,
+            source_digest: None,
+            persist: true,
+            replaces_plaintext: false,
+            closed: false,
+        };
+        register(&out.path);
+
+        let enc = encrypted_path(path);
+        let _lock = lock_encrypted(&enc)?;
+        out.source_digest = digest_encrypted(&enc)?;
+
+        if path.exists() {
+            //the store hasn't been encrypted yet, or a previous version of
+            //dcli didnt close the store, in which case the unencrypted
+            //store is the most recent version of the data
+            if enc.exists() {
+                eprintln!(
+                    "Found unencrypted copy of activity store. Using it."
+                );
+            }
+            fs::copy(path, &out.path)?;
+            out.replaces_plaintext = true;
+        } else if enc.exists() {
+            decrypt_file(&enc, &out.path, passphrase)?;
+            out.digest = Some(digest_file(&out.path)?);
+        }
+
+        Ok(out)
+    }
+
+    /// decrypts the store at path into dest, as a read only snapshot. The
+    /// snapshot is removed when closed or dropped, and never encrypted back
+    /// to the store.
+    pub fn snapshot(
+        path: &Path,
+        dest: &Path,
+        passphrase: &str,
+    ) -> Result<WorkingCopy, Error> {
+        let out = WorkingCopy::guard(dest);
+        decrypt_file(&encrypted_path(path), dest, passphrase)?;
+
+        Ok(out)
+    }
+
+    /// removes the existing unencrypted file at path when closed or dropped
+    pub fn guard(path: &Path) -> WorkingCopy {
+        register(path);
+
+        WorkingCopy {
+            path: path.to_path_buf(),
+            store_path: path.to_path_buf(),
+            passphrase: "".to_string(),
+            digest: None,
+            source_digest: None,
+            persist: false,
+            replaces_plaintext: false,
+            closed: false,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// encrypts the working copy back to the store if it has changed, and
+    /// removes all unencrypted files. The store connection must be closed
+    /// first. Returns an error, and doesn't save the changes, if another
+    /// process changed the store while the working copy was open.
+    pub fn close(mut self) -> Result<(), Error> {
+        if self.persist && self.path.exists() {
+            let changed = match &self.digest {
+                Some(e) => &digest_file(&self.path)? != e,
+                None => true,
+            };
+
+            if changed {
+                let enc = encrypted_path(&self.store_path);
+                let _lock = lock_encrypted(&enc)?;
+
+                if digest_encrypted(&enc)? != self.source_digest {
+                    remove_database_files(&self.path)?;
+                    unregister(&self.path);
+                    self.closed = true;
+
+                    return Err(Error::Encryption {
+                        description: format!(
+                            "{} was changed by another process while it was open. Changes made by this process were not saved.",
+                            enc.display()
+                        ),
+                    });
+                }
+
+                encrypt_file(&self.path, &enc, &self.passphrase)?;
+            }
+
+            if self.replaces_plaintext {
+                remove_database_files(&self.store_path)?;
+            }
+        }
+
+        remove_database_files(&self.path)?;
+        unregister(&self.path);
+        self.closed = true;
+
+        Ok(())
+    }
+}
+
+impl Drop for WorkingCopy {
+    fn drop(&mut self) {
+        if self.closed {
+            return;
+        }
+
+        //not closed (i.e. an error or panic), so changes may not have been
+        //written to the file. We dont persist them, but make sure the
+        //plaintext is removed.
+        let _ = remove_database_files(&self.path);
+        unregister(&self.path);
+    }
+}
+
+//path of the working copy of the store for this process
+fn working_copy_path(path: &Path) -> PathBuf {
+    let mut p = path.as_os_str().to_owned();
+    p.push(format!(".{}.{}", std::process::id(), WORKING_EXTENSION));
+    PathBuf::from(p)
+}
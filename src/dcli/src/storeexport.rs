@@ -0,0 +1,94 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Structure of the json file written by ActivityStoreInterface::export_json.
+//The export contains all of the members, characters and activities in the
+//store, using the stored values (hashes and enum ids are not resolved with
+//the manifest), so it can be archived or loaded into other databases.
+//
+//{
+//    "format" : "dcli_activity_store",
+//    "format_version" : 1,
+//    "schema_version" : 6,          //activity store schema version
+//    "exported_at" : "2021-03-01T17:00:00+00:00",
+//    "members" : [StoreExportMember],
+//    "activities" : [StoreExportActivity]
+//}
+//
+//Players in activities reference members and characters by their member and
+//character ids. Activities are written in the order they were stored.
+
+use serde_derive::Serialize;
+
+use crate::storerows::{
+    CharacterActivityStatsRow, MedalResultRow, TeamResultRow, WeaponResultRow,
+};
+
+pub const STORE_EXPORT_FORMAT: &str = "dcli_activity_store";
+
+/// incremented when the structure of the export changes
+pub const STORE_EXPORT_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize)]
+pub struct StoreExportMember {
+    pub member_id: String,
+    pub platform_id: u32,
+    pub display_name: String,
+    pub characters: Vec<StoreExportCharacter>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StoreExportCharacter {
+    pub character_id: String,
+
+    /// CharacterClass id (0 titan, 1 hunter, 2 warlock)
+    pub class: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StoreExportActivity {
+    /// instance id of the activity (PGCR id)
+    pub activity_id: i64,
+
+    /// RFC 3339 date / time the activity started
+    pub period: String,
+    pub mode: u32,
+
+    /// all of the modes the activity is included in
+    pub modes: Vec<u32>,
+    pub platform: u32,
+    pub director_activity_hash: i64,
+    pub reference_id: u32,
+    pub teams: Vec<TeamResultRow>,
+    pub players: Vec<StoreExportPlayer>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StoreExportPlayer {
+    pub member_id: String,
+    pub character_id: String,
+
+    /// the player's character_activity_stats for the activity
+    pub stats: CharacterActivityStatsRow,
+    pub weapons: Vec<WeaponResultRow>,
+    pub medals: Vec<MedalResultRow>,
+}
@@ -0,0 +1,244 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Support for running arbitrary, read only sql queries against the activity
+//store. Queries can use the store tables directly, or the views defined here,
+//which join the tables into shapes that are easier to query.
+//
+//Views are created as temporary views on the connection used for the query,
+//so they are never written to the store, and can be changed without a schema
+//update. Mode, class, platform and standing values are the ids stored in the
+//activity store (i.e. class 0 is Titan), and manifest hashes (reference_id)
+//are not resolved.
+
+use std::fmt;
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+#[derive(Debug)]
+pub struct QueryView {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub sql: &'static str,
+}
+
+pub const QUERY_VIEWS: &[QueryView] = &[
+    QueryView {
+        name: "player_activity",
+        description: "One row for each player in each stored activity, with \
+            the activity details and the player's stats for the activity.",
+        sql: r#"
+            CREATE TEMP VIEW IF NOT EXISTS "player_activity" AS
+            SELECT
+                activity.activity_id,
+                activity.period,
+                activity.mode,
+                activity.platform,
+                activity.reference_id,
+                activity.director_activity_hash,
+                member.member_id,
+                member.display_name,
+                member.platform_id,
+                character.character_id,
+                character.class,
+                stats.light_level,
+                stats.team,
+                stats.fireteam_id,
+                stats.standing,
+                stats.completed,
+                stats.completion_reason,
+                stats.score,
+                stats.team_score,
+                stats.kills,
+                stats.deaths,
+                stats.assists,
+                stats.opponents_defeated,
+                stats.precision_kills,
+                stats.weapon_kills_ability,
+                stats.weapon_kills_grenade,
+                stats.weapon_kills_melee,
+                stats.weapon_kills_super,
+                stats.all_medals_earned,
+                stats.activity_duration_seconds,
+                stats.start_seconds,
+                stats.time_played_seconds,
+                stats.player_count
+            FROM main.character_activity_stats AS stats
+            INNER JOIN main.activity ON stats.activity = activity.id
+            INNER JOIN main.character ON stats.character = character.id
+            INNER JOIN main.member ON character.member = member.id
+        "#,
+    },
+    QueryView {
+        name: "synced_player_activity",
+        description: "Same as player_activity, but only includes members \
+            who have been synced into the store.",
+        sql: r#"
+            CREATE TEMP VIEW IF NOT EXISTS "synced_player_activity" AS
+            SELECT * FROM "player_activity" WHERE member_id IN (
+                SELECT member_id FROM main.sync_history
+                UNION SELECT member_id FROM main.sync_member
+                UNION SELECT member_id FROM main.character_metadata
+            )
+        "#,
+    },
+    QueryView {
+        name: "weapon_activity",
+        description: "One row for each weapon used by each player in each \
+            activity. weapon_hash is the weapon's manifest hash.",
+        sql: r#"
+            CREATE TEMP VIEW IF NOT EXISTS "weapon_activity" AS
+            SELECT
+                activity.activity_id,
+                activity.period,
+                activity.mode,
+                member.member_id,
+                member.display_name,
+                character.character_id,
+                character.class,
+                weapon_result.reference_id AS weapon_hash,
+                weapon_result.kills,
+                weapon_result.precision_kills
+            FROM main.weapon_result
+            INNER JOIN main.character_activity_stats AS stats
+                ON weapon_result.character_activity_stats = stats.id
+            INNER JOIN main.activity ON stats.activity = activity.id
+            INNER JOIN main.character ON stats.character = character.id
+            INNER JOIN main.member ON character.member = member.id
+        "#,
+    },
+    QueryView {
+        name: "medal_activity",
+        description: "One row for each medal earned by each player in each \
+            activity. medal_id is the medal's historical stats id.",
+        sql: r#"
+            CREATE TEMP VIEW IF NOT EXISTS "medal_activity" AS
+            SELECT
+                activity.activity_id,
+                activity.period,
+                activity.mode,
+                member.member_id,
+                member.display_name,
+                character.character_id,
+                character.class,
+                medal_result.reference_id AS medal_id,
+                medal_result.count
+            FROM main.medal_result
+            INNER JOIN main.character_activity_stats AS stats
+                ON medal_result.character_activity_stats = stats.id
+            INNER JOIN main.activity ON stats.activity = activity.id
+            INNER JOIN main.character ON stats.character = character.id
+            INNER JOIN main.member ON character.member = member.id
+        "#,
+    },
+    QueryView {
+        name: "activity_modes",
+        description: "One row for each mode an activity is in. Activities \
+            are in multiple modes (i.e. control is also all_pvp).",
+        sql: r#"
+            CREATE TEMP VIEW IF NOT EXISTS "activity_modes" AS
+            SELECT
+                activity.activity_id,
+                activity.period,
+                modes.mode
+            FROM main.modes
+            INNER JOIN main.activity ON modes.activity = activity.id
+        "#,
+    },
+];
+
+/// a single value returned from a query, using sqlite's storage classes
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+impl fmt::Display for QueryValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QueryValue::Null => write!(f, ""),
+            QueryValue::Integer(e) => write!(f, "{}", e),
+            QueryValue::Real(e) => write!(f, "{}", e),
+            QueryValue::Text(e) => write!(f, "{}", e),
+            QueryValue::Blob(e) => write!(f, "<{} bytes>", e.len()),
+        }
+    }
+}
+
+impl Serialize for QueryValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            QueryValue::Null => serializer.serialize_none(),
+            QueryValue::Integer(e) => serializer.serialize_i64(*e),
+            QueryValue::Real(e) => serializer.serialize_f64(*e),
+            QueryValue::Text(e) => serializer.serialize_str(e),
+            QueryValue::Blob(e) => serializer.serialize_bytes(e),
+        }
+    }
+}
+
+/// the columns and rows returned from a query. Columns are empty if the
+/// query did not return any rows.
+#[derive(Debug, Clone, Default)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<QueryValue>>,
+}
+
+impl QueryResult {
+    /// rows as maps of column name to value, for json output
+    pub fn records(&self) -> Vec<QueryRecord<'_>> {
+        self.rows
+            .iter()
+            .map(|r| QueryRecord {
+                columns: &self.columns,
+                values: r,
+            })
+            .collect()
+    }
+}
+
+/// a single row of a QueryResult, serialized as an object keyed by column
+pub struct QueryRecord<'a> {
+    columns: &'a [String],
+    values: &'a [QueryValue],
+}
+
+impl<'a> Serialize for QueryRecord<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.columns.len()))?;
+        for (c, v) in self.columns.iter().zip(self.values.iter()) {
+            map.serialize_entry(c, v)?;
+        }
+        map.end()
+    }
+}
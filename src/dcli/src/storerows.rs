@@ -0,0 +1,131 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Typed rows for the activity store tables. Rows are decoded by column name
+//with sqlx::FromRow, which checks that each column is compatible with the
+//field type, so a renamed column or changed type is reported as an error
+//when the row is read, rather than being silently decoded.
+//
+//Queries which join tables (i.e. activity, character_activity_stats, member
+//and character) can decode several of these from the same row. Joined
+//queries must alias the row ids (activity_index_id and
+//character_activity_stats_index), since every table has an id column.
+//
+//Note, queries are not checked at compile time (with sqlx::query!), since
+//that requires a database (or saved query metadata) when building.
+
+use serde_derive::Serialize;
+use sqlx::FromRow;
+
+/// activity details, from the activity table
+#[derive(Debug, FromRow)]
+pub struct ActivityRow {
+    pub activity_index_id: i64,
+    pub activity_id: i64,
+    pub period: String,
+
+    //aliased, since mode is also stored in the modes table
+    pub activity_mode: u32,
+    pub platform: u32,
+    pub director_activity_hash: i64,
+    pub reference_id: u32,
+}
+
+/// a player's stats for an activity, from the character_activity_stats table
+#[derive(Debug, FromRow, Serialize)]
+pub struct CharacterActivityStatsRow {
+    #[serde(skip)]
+    pub character_activity_stats_index: i64,
+    pub assists: u32,
+    pub score: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    pub average_score_per_kill: f32,
+    pub average_score_per_life: f32,
+    pub completed: i32,
+    pub opponents_defeated: u32,
+    pub activity_duration_seconds: u32,
+    pub standing: u32,
+    pub team: i32,
+    pub completion_reason: u32,
+    pub start_seconds: u32,
+    pub time_played_seconds: u32,
+    pub player_count: u32,
+    pub team_score: u32,
+    pub precision_kills: u32,
+    pub weapon_kills_ability: u32,
+    pub weapon_kills_grenade: u32,
+    pub weapon_kills_melee: u32,
+    pub weapon_kills_super: u32,
+    pub all_medals_earned: u32,
+    pub light_level: i32,
+
+    //not set for activities synced before fireteams were stored
+    pub fireteam_id: Option<i64>,
+}
+
+/// the player a set of stats belongs to, from the member and character
+/// tables
+#[derive(Debug, FromRow)]
+pub struct PlayerRow {
+    pub member_id: String,
+    pub display_name: String,
+    pub platform_id: u32,
+    pub character_id: String,
+    pub class: u32,
+}
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct TeamResultRow {
+    pub team_id: i32,
+    pub standing: i32,
+    pub score: u32,
+    pub team_name: Option<String>,
+}
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct WeaponResultRow {
+    pub reference_id: u32,
+    pub kills: u32,
+    pub precision_kills: u32,
+    pub kills_precision_kills_ratio: f32,
+}
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct MedalResultRow {
+    pub reference_id: String,
+    pub count: u32,
+}
+
+/// character details refreshed on each sync, from the character_metadata
+/// table
+#[derive(Debug, FromRow)]
+pub struct CharacterMetadataRow {
+    pub character_id: String,
+    pub member_id: String,
+    pub class: u32,
+    pub emblem_hash: u32,
+    pub light: u32,
+    pub minutes_played_total: u32,
+    pub date_last_played: String,
+    pub updated_at: String,
+}
@@ -0,0 +1,452 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Metadata describing each version of the activity store schema. This is used
+//to tell the user what changed when their data store needs to be updated, and
+//to upgrade existing stores without losing their data.
+//
+//When DB_SCHEMA_VERSION is bumped, add an entry to SCHEMA_MIGRATIONS
+//describing the change, along with the sql to upgrade a store from the
+//previous version. Migrations should preserve existing data (i.e. ALTER TABLE
+//rather than DROP / CREATE). The base schema (actitvity_store_schema.sql) is
+//not changed, new stores are created from it and then migrated.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+use chrono::Utc;
+
+use crate::error::Error;
+
+const SCHEMA_LOG_FILE_NAME: &str = "schema_changes.log";
+
+#[derive(Debug)]
+pub struct SchemaMigration {
+    /// schema version this migration upgrades the store to
+    pub version: i32,
+
+    /// human readable descriptions of what changed
+    pub changes: &'static [&'static str],
+
+    /// description of the data that needs to be backfilled after the
+    /// migration, if any
+    pub backfill: Option<&'static str>,
+
+    /// sql run (in a transaction) to upgrade a store from the previous
+    /// version. None if the store can't be upgraded in place, and must be
+    /// rebuilt from the base schema.
+    pub sql: Option<&'static str>,
+}
+
+pub const SCHEMA_MIGRATIONS: &[SchemaMigration] = &[
+    SchemaMigration {
+        version: 6,
+        changes: &[
+            "Store rebuilt with current tables (member, character, activity, \
+             character_activity_stats, team_result, weapon_result, \
+             medal_result, modes, activity_queue)",
+        ],
+        backfill: Some(
+            "All existing activity data is removed and will be re-synced \
+             from the Destiny API on the next sync.",
+        ),
+        sql: None,
+    },
+    SchemaMigration {
+        version: 7,
+        changes: &[
+            "Added fireteam_id to character_activity_stats, so players who \
+             queued together can be identified",
+        ],
+        backfill: Some(
+            "Fireteams are not set for existing activities. Run dclias \
+             with --reprocess (and --refetch for activities without stored \
+             json) to set them.",
+        ),
+        sql: Some(
+            r#"ALTER TABLE "main"."character_activity_stats" ADD COLUMN "fireteam_id" INTEGER;"#,
+        ),
+    },
+    //modes (mode, activity) and activity (period) are already covered by
+    //the unique constraint on modes and activity_period_index
+    SchemaMigration {
+        version: 8,
+        changes: &[
+            "Added indexes on character_activity_stats (character, activity) \
+             and team_result (activity), so reports by character, mode and \
+             time period are faster for large stores",
+        ],
+        backfill: None,
+        sql: Some(
+            r#"
+            DROP INDEX IF EXISTS "character_activity_stats_char_index";
+            CREATE INDEX IF NOT EXISTS "character_activity_stats_character_activity_index"
+                ON "character_activity_stats" ("character", "activity");
+            CREATE INDEX IF NOT EXISTS "team_result_activity_index"
+                ON "team_result" ("activity");
+            "#,
+        ),
+    },
+    SchemaMigration {
+        version: 9,
+        changes: &[
+            "Added team_name to team_result, so the team names returned by \
+             the Destiny API are used instead of generated names",
+        ],
+        backfill: Some(
+            "Team names are not set for existing activities, which will \
+             continue to use generated names. Run dclias with --reprocess \
+             to set them.",
+        ),
+        sql: Some(
+            r#"ALTER TABLE "main"."team_result" ADD COLUMN "team_name" TEXT;"#,
+        ),
+    },
+    SchemaMigration {
+        version: 10,
+        changes: &[
+            "Added a season table, loaded from the season definitions in the \
+             manifest",
+            "Added season to activity, so activities can be retrieved by \
+             season",
+        ],
+        backfill: Some(
+            "Seasons are loaded and existing activities tagged with their \
+             season the next time dclias syncs with a manifest available \
+             (run dclim to download it).",
+        ),
+        sql: Some(
+            r#"
+            CREATE TABLE IF NOT EXISTS "season" (
+                "id"            INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT UNIQUE,
+                "season_number" INTEGER NOT NULL UNIQUE,
+                "season_hash"   INTEGER NOT NULL,
+                "name"          TEXT NOT NULL,
+                "start_date"    TEXT NOT NULL,
+                "end_date"      TEXT
+            );
+            ALTER TABLE "main"."activity" ADD COLUMN "season" INTEGER;
+            CREATE INDEX IF NOT EXISTS "activity_season_index"
+                ON "activity" ("season");
+            "#,
+        ),
+    },
+    //stores synced before this version may already have raw_pgcr, which was
+    //previously created outside of the schema versions
+    SchemaMigration {
+        version: 11,
+        changes: &[
+            "Added a raw_pgcr table, which stores the original PGCR json for \
+             activities synced with --store-raw-pgcr, so they can be \
+             re-parsed without downloading them again",
+        ],
+        backfill: None,
+        sql: Some(
+            r#"
+            CREATE TABLE IF NOT EXISTS "main"."raw_pgcr" (
+                "activity"  INTEGER NOT NULL PRIMARY KEY,
+                "data"      TEXT NOT NULL,
+                "stored_at" TEXT NOT NULL,
+                FOREIGN KEY ("activity")
+                    REFERENCES activity ("id")
+                    ON DELETE CASCADE
+            );
+            "#,
+        ),
+    },
+    //page is the next page of the walk to retrieve, and since_activity_id
+    //the activity it stops at (the most recent activity stored when the walk
+    //started). IF NOT EXISTS, since it was previously created when missing.
+    SchemaMigration {
+        version: 12,
+        changes: &[
+            "Added a history_cursor table, which records the position of \
+             activity history walks which have not finished, so they can be \
+             resumed on the next sync",
+        ],
+        backfill: None,
+        sql: Some(
+            r#"
+            CREATE TABLE IF NOT EXISTS "main"."history_cursor" (
+                "character"         INTEGER NOT NULL,
+                "mode"              INTEGER NOT NULL,
+                "page"              INTEGER NOT NULL,
+                "since_activity_id" INTEGER NOT NULL,
+                "updated_at"        TEXT NOT NULL,
+                PRIMARY KEY("character", "mode"),
+                FOREIGN KEY ("character")
+                    REFERENCES character ("id")
+                    ON DELETE CASCADE
+            );
+            "#,
+        ),
+    },
+    SchemaMigration {
+        version: 13,
+        changes: &[
+            "Added a prune_cutoff table, which records the date before which \
+             activities were pruned, so they are not synced again",
+        ],
+        backfill: None,
+        sql: Some(
+            r#"
+            CREATE TABLE IF NOT EXISTS "main"."prune_cutoff" (
+                "id"        INTEGER NOT NULL PRIMARY KEY CHECK ("id" = 1),
+                "cutoff"    TEXT NOT NULL,
+                "pruned_at" TEXT NOT NULL
+            );
+            "#,
+        ),
+    },
+    //sync_history and sync_log were previously created when missing, so may
+    //not exist yet. Existing key usage is attached to the run it was logged
+    //during. Usage logged before runs were recorded gets a run of its own,
+    //with no activity counts.
+    SchemaMigration {
+        version: 14,
+        changes: &[
+            "Added a sync_history table, with a row for each sync run \
+             (including failed and cancelled runs)",
+            "sync_log now records the api key usage for each run in \
+             sync_history, rather than duplicating the member and time of \
+             the run",
+        ],
+        backfill: None,
+        sql: Some(
+            r#"
+            CREATE TABLE IF NOT EXISTS "main"."sync_history" (
+                "id"                   INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT UNIQUE,
+                "member_id"            TEXT NOT NULL,
+                "started_at"           TEXT NOT NULL,
+                "finished_at"          TEXT NOT NULL,
+                "duration_ms"          INTEGER NOT NULL,
+                "activities_added"     INTEGER NOT NULL,
+                "activities_remaining" INTEGER NOT NULL,
+                "error"                TEXT
+            );
+            CREATE INDEX IF NOT EXISTS "sync_history_member_index"
+                ON "sync_history" ("member_id");
+
+            CREATE TABLE IF NOT EXISTS "main"."sync_log" (
+                "id"        INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT UNIQUE,
+                "synced_at" TEXT NOT NULL,
+                "member_id" TEXT NOT NULL,
+                "api_key"   TEXT NOT NULL,
+                "requests"  INTEGER NOT NULL,
+                "throttled" INTEGER NOT NULL
+            );
+            ALTER TABLE "main"."sync_log" RENAME TO "sync_log_old";
+
+            CREATE TABLE "main"."sync_log" (
+                "id"           INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT UNIQUE,
+                "sync_history" INTEGER NOT NULL,
+                "api_key"      TEXT NOT NULL,
+                "requests"     INTEGER NOT NULL,
+                "throttled"    INTEGER NOT NULL,
+                FOREIGN KEY ("sync_history")
+                    REFERENCES sync_history ("id")
+                    ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS "sync_log_sync_history_index"
+                ON "sync_log" ("sync_history");
+
+            INSERT INTO "main"."sync_history"("member_id", "started_at",
+                "finished_at", "duration_ms", "activities_added",
+                "activities_remaining", "error")
+            SELECT DISTINCT o.member_id, o.synced_at, o.synced_at, 0, 0, 0, NULL
+            FROM "sync_log_old" o
+            WHERE NOT EXISTS (
+                SELECT 1 FROM "sync_history" h
+                WHERE h.member_id = o.member_id
+                    AND h.started_at <= o.synced_at
+                    AND h.finished_at >= o.synced_at
+            );
+
+            INSERT INTO "main"."sync_log"("sync_history", "api_key",
+                "requests", "throttled")
+            SELECT
+                (
+                    SELECT min(h.id) FROM "sync_history" h
+                    WHERE h.member_id = o.member_id
+                        AND h.started_at <= o.synced_at
+                        AND h.finished_at >= o.synced_at
+                ),
+                o.api_key, o.requests, o.throttled
+            FROM "sync_log_old" o
+            ORDER BY o.id;
+
+            DROP TABLE "sync_log_old";
+            "#,
+        ),
+    },
+    //these tables were previously created when missing each time the store
+    //was opened, so may already exist
+    SchemaMigration {
+        version: 15,
+        changes: &[
+            "Added a combat_rating table, which records the combat ratings \
+             of players at the time an activity was viewed",
+            "Added a player_alias table, with local aliases for players",
+            "Added a competitive_progress table, which records competitive \
+             progress (i.e. glory) when syncing",
+            "Added a character_metadata table, with character details which \
+             are refreshed each time the member is synced",
+            "Added a sync_member table, with the members synced together \
+             when syncing all members",
+        ],
+        backfill: None,
+        sql: Some(
+            r#"
+            CREATE TABLE IF NOT EXISTS "main"."combat_rating" (
+                "id"           INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT UNIQUE,
+                "activity_id"  INTEGER NOT NULL,
+                "character_id" TEXT NOT NULL,
+                "rating"       REAL NOT NULL,
+                "recorded_at"  TEXT NOT NULL,
+                UNIQUE("activity_id", "character_id")
+            );
+
+            CREATE TABLE IF NOT EXISTS "main"."player_alias" (
+                "member_id"  TEXT NOT NULL PRIMARY KEY,
+                "alias"      TEXT NOT NULL COLLATE NOCASE UNIQUE,
+                "created_at" TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS "main"."competitive_progress" (
+                "id"               INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT UNIQUE,
+                "member_id"        TEXT NOT NULL,
+                "progression_hash" INTEGER NOT NULL,
+                "activity_id"      INTEGER NOT NULL,
+                "current_progress" INTEGER NOT NULL,
+                "step_index"       INTEGER NOT NULL,
+                "recorded_at"      TEXT NOT NULL,
+                UNIQUE("member_id", "progression_hash", "activity_id")
+            );
+
+            CREATE TABLE IF NOT EXISTS "main"."character_metadata" (
+                "character_id"         TEXT NOT NULL PRIMARY KEY,
+                "member_id"            TEXT NOT NULL,
+                "class"                INTEGER NOT NULL,
+                "emblem_hash"          INTEGER NOT NULL,
+                "light"                INTEGER NOT NULL,
+                "minutes_played_total" INTEGER NOT NULL,
+                "date_last_played"     TEXT NOT NULL,
+                "updated_at"           TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS "main"."sync_member" (
+                "member_id"   TEXT NOT NULL PRIMARY KEY,
+                "platform_id" INTEGER NOT NULL,
+                "created_at"  TEXT NOT NULL
+            );
+            "#,
+        ),
+    },
+];
+
+/// returns the migrations needed to move a store from from_version to
+/// to_version. from_version is None if the store has not been created yet.
+pub fn migrations_between(
+    from_version: Option<i32>,
+    to_version: i32,
+) -> Vec<&'static SchemaMigration> {
+    let from = from_version.unwrap_or(0);
+    SCHEMA_MIGRATIONS
+        .iter()
+        .filter(|m| m.version > from && m.version <= to_version)
+        .collect()
+}
+
+/// returns true if a store can be upgraded from from_version to to_version
+/// by running migrations, without having to be rebuilt
+pub fn can_migrate(from_version: i32, to_version: i32) -> bool {
+    from_version <= to_version
+        && migrations_between(Some(from_version), to_version)
+            .iter()
+            .all(|m| m.sql.is_some())
+}
+
+/// returns a human readable summary of the changes between the two versions,
+/// and what data needs to be backfilled.
+pub fn describe_upgrade(from_version: Option<i32>, to_version: i32) -> String {
+    let mut out = Vec::new();
+
+    match from_version {
+        Some(v) => out.push(format!(
+            "Data store needs to be updated from schema version {} to {}.",
+            v, to_version
+        )),
+        None => {
+            out.push(format!(
+                "Creating data store (schema version {}).",
+                to_version
+            ));
+            return out.join("\n");
+        }
+    };
+
+    let migrations = migrations_between(from_version, to_version);
+
+    if migrations.is_empty() {
+        out.push("No change details are available for this upgrade.".into());
+        return out.join("\n");
+    }
+
+    let mut backfill = Vec::new();
+    for m in migrations {
+        out.push(format!("Version {}:", m.version));
+        for c in m.changes {
+            out.push(format!("  - {}", c));
+        }
+
+        if let Some(b) = m.backfill {
+            backfill.push(b);
+        }
+    }
+
+    if backfill.is_empty() {
+        out.push("No data needs to be backfilled.".into());
+    } else {
+        out.push("Backfill required:".into());
+        for b in backfill {
+            out.push(format!("  - {}", b));
+        }
+    }
+
+    out.join("\n")
+}
+
+/// appends the upgrade summary to the schema change log in the specified
+/// directory
+pub fn log_upgrade(dir: &Path, summary: &str) -> Result<(), Error> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(SCHEMA_LOG_FILE_NAME))?;
+
+    writeln!(file, "[{}]", Utc::now().to_rfc3339())?;
+    writeln!(file, "{}", summary)?;
+    writeln!(file)?;
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_derive::Serialize;
+
+use crate::enums::{mode::Mode, platform::Platform};
+
+//events emitted while syncing. These are written out one JSON object per line
+//(JSON Lines) so they can be ingested by log pipelines (Vector, Fluentd, etc)
+#[derive(Serialize, Debug)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum SyncEvent {
+    SyncStarted {
+        timestamp: String,
+        member_id: String,
+        platform: String,
+    },
+    HistoryPage {
+        timestamp: String,
+        mode: String,
+        page: i32,
+        activities: u32,
+    },
+    ActivitySynced {
+        timestamp: String,
+        activity_id: i64,
+        mode: String,
+        period: String,
+    },
+    SyncFinished {
+        timestamp: String,
+        total_synced: u32,
+        total_available: u32,
+        path: String,
+    },
+    Error {
+        timestamp: String,
+        description: String,
+    },
+}
+
+fn timestamp() -> String {
+    format_datetime(&Utc::now())
+}
+
+fn format_datetime(dt: &DateTime<Utc>) -> String {
+    dt.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+impl SyncEvent {
+    pub fn sync_started(member_id: &str, platform: &Platform) -> SyncEvent {
+        SyncEvent::SyncStarted {
+            timestamp: timestamp(),
+            member_id: member_id.to_string(),
+            platform: format!("{}", platform).to_lowercase(),
+        }
+    }
+
+    /// a page of activity history was retrieved and queued. activities is
+    /// the number of new activities on the page
+    pub fn history_page(mode: &Mode, page: i32, activities: u32) -> SyncEvent {
+        SyncEvent::HistoryPage {
+            timestamp: timestamp(),
+            mode: format!("{}", mode),
+            page,
+            activities,
+        }
+    }
+
+    pub fn activity_synced(
+        activity_id: i64,
+        mode: &Mode,
+        period: &DateTime<Utc>,
+    ) -> SyncEvent {
+        SyncEvent::ActivitySynced {
+            timestamp: timestamp(),
+            activity_id,
+            mode: format!("{}", mode),
+            period: format_datetime(period),
+        }
+    }
+
+    pub fn sync_finished(
+        total_synced: u32,
+        total_available: u32,
+        path: &str,
+    ) -> SyncEvent {
+        SyncEvent::SyncFinished {
+            timestamp: timestamp(),
+            total_synced,
+            total_available,
+            path: path.to_string(),
+        }
+    }
+
+    pub fn error(description: &str) -> SyncEvent {
+        SyncEvent::Error {
+            timestamp: timestamp(),
+            description: description.to_string(),
+        }
+    }
+
+    pub fn to_json_line(&self) -> String {
+        //serialization of these types cant really fail, but we dont want
+        //to panic in the middle of a sync if it does
+        match serde_json::to_string(self) {
+            Ok(e) => e,
+            Err(e) => format!(
+                "{{\"event\":\"error\",\"description\":\"{}\"}}",
+                e.to_string().replace('"', "'")
+            ),
+        }
+    }
+
+    pub fn emit(&self) {
+        println!("{}", self.to_json_line());
+    }
+}
@@ -0,0 +1,103 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Advisory lock held by a process while it is syncing the activity store.
+//Report tools check the lock, and if a sync is in progress, query a snapshot
+//of the store instead of the live database. The lock is released by the OS
+//if the syncing process exits without cleaning up (i.e. ctrl-c).
+
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+
+use fs2::FileExt;
+
+use crate::datadir;
+use crate::error::Error;
+
+const SYNC_LOCK_FILE_NAME: &str = "sync.lock";
+
+pub struct SyncLock {
+    file: File,
+}
+
+impl SyncLock {
+    /// acquires the sync lock for the store in the specified data directory.
+    /// Returns an error if another process is currently syncing.
+    pub fn acquire(data_dir: &Path) -> Result<SyncLock, Error> {
+        let path = lock_path(data_dir);
+        let file = open_lock_file(&path)?;
+
+        if file.try_lock_exclusive().is_err() {
+            return Err(Error::SyncInProgress {
+                description: format!(
+                    "Another process is currently syncing the data store ({})",
+                    path.display()
+                ),
+            });
+        }
+
+        Ok(SyncLock { file })
+    }
+}
+
+impl Drop for SyncLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+/// returns true if a sync is currently in progress for the store in the
+/// specified data directory
+pub fn is_sync_in_progress(data_dir: &Path) -> bool {
+    let path = lock_path(data_dir);
+    if !path.exists() {
+        return false;
+    }
+
+    let file = match open_lock_file(&path) {
+        Ok(e) => e,
+        Err(_e) => return false,
+    };
+
+    match file.try_lock_shared() {
+        Ok(_) => {
+            let _ = file.unlock();
+            false
+        }
+        Err(_e) => true,
+    }
+}
+
+fn lock_path(data_dir: &Path) -> PathBuf {
+    datadir::store_dir(data_dir).join(SYNC_LOCK_FILE_NAME)
+}
+
+fn open_lock_file(path: &Path) -> Result<File, Error> {
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)?;
+
+    Ok(file)
+}
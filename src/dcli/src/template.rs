@@ -0,0 +1,67 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Renders the data behind a tool's output through a user provided handlebars
+//template (--template), so output can be formatted for overlays, bots and
+//scripts without needing a new built in output format.
+//
+//The data passed to the template is the same as the tool's json output.
+
+use std::path::Path;
+
+use handlebars::{handlebars_helper, Handlebars, Template};
+use serde::Serialize;
+
+use crate::error::Error;
+
+//formats a number with the specified number of decimal places
+//i.e. {{fixed kills_deaths_ratio 2}}
+handlebars_helper!(fixed: |v: f64, places: u64| {
+    format!("{:.*}", places as usize, v)
+});
+
+/// loads the template from the specified file, and checks that it is a
+/// valid template.
+pub fn load_template(path: &Path) -> Result<String, Error> {
+    let template = std::fs::read_to_string(path)?;
+
+    //compile here so syntax errors are reported before any data is retrieved
+    Template::compile(&template)?;
+
+    Ok(template)
+}
+
+/// renders the data through the template
+pub fn render_template<T: Serialize>(
+    template: &str,
+    data: &T,
+) -> Result<String, Error> {
+    let mut handlebars = Handlebars::new();
+
+    //output is going to the terminal, not html, so dont escape it
+    handlebars.register_escape_fn(handlebars::no_escape);
+    handlebars.register_helper("fixed", Box::new(fixed));
+
+    let out = handlebars.render_template(template, data)?;
+
+    Ok(out)
+}
@@ -0,0 +1,269 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Optional timing of where a command spends its time (--timing). Timers are
+//started around API requests, SQL queries and output formatting, and a
+//summary is printed to stderr when the command finishes.
+//
+//Times are wall-clock. Overlapping timers in the same category (i.e.
+//concurrent API requests) are only counted once, so the time for a category
+//is never more than the total run time. Timers are a no-op unless timing has
+//been enabled.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use crate::utils::repeat_str;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static TIMINGS: Mutex<Option<Timings>> = Mutex::new(None);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimingCategory {
+    Api,
+    Database,
+    Formatting,
+}
+
+impl TimingCategory {
+    fn index(&self) -> usize {
+        match self {
+            TimingCategory::Api => 0,
+            TimingCategory::Database => 1,
+            TimingCategory::Formatting => 2,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            TimingCategory::Api => "API",
+            TimingCategory::Database => "SQL",
+            TimingCategory::Formatting => "FORMATTING",
+        }
+    }
+}
+
+const CATEGORIES: [TimingCategory; 3] = [
+    TimingCategory::Api,
+    TimingCategory::Database,
+    TimingCategory::Formatting,
+];
+
+#[derive(Default, Clone, Copy)]
+struct CategoryTiming {
+    active: u32,
+    since: Option<Instant>,
+    total: Duration,
+    count: u32,
+}
+
+struct Timings {
+    started: Instant,
+    categories: [CategoryTiming; 3],
+}
+
+/// turns on timing for the process
+pub fn enable() {
+    let mut timings = TIMINGS.lock().unwrap();
+    *timings = Some(Timings {
+        started: Instant::now(),
+        categories: [CategoryTiming::default(); 3],
+    });
+
+    ENABLED.store(true, Ordering::SeqCst);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// starts a timer for the category, which stops when the returned guard
+/// is dropped
+pub fn start(category: TimingCategory) -> TimingGuard {
+    if !is_enabled() {
+        return TimingGuard { category: None };
+    }
+
+    if let Some(t) = TIMINGS.lock().unwrap().as_mut() {
+        let c = &mut t.categories[category.index()];
+        if c.active == 0 {
+            c.since = Some(Instant::now());
+        }
+        c.active += 1;
+        c.count += 1;
+    }
+
+    TimingGuard {
+        category: Some(category),
+    }
+}
+
+fn stop(category: TimingCategory) {
+    if let Some(t) = TIMINGS.lock().unwrap().as_mut() {
+        let c = &mut t.categories[category.index()];
+        c.active -= 1;
+        if c.active == 0 {
+            if let Some(since) = c.since.take() {
+                c.total += since.elapsed();
+            }
+        }
+    }
+}
+
+pub struct TimingGuard {
+    category: Option<TimingCategory>,
+}
+
+impl Drop for TimingGuard {
+    fn drop(&mut self) {
+        if let Some(c) = self.category {
+            stop(c);
+        }
+    }
+}
+
+/// prints a summary of the timings to stderr, if timing is enabled
+pub fn print_summary() {
+    let timings = TIMINGS.lock().unwrap();
+    let t = match timings.as_ref() {
+        Some(e) => e,
+        None => return,
+    };
+
+    let total = t.started.elapsed();
+
+    let name_col_w = 14;
+    let col_w = 12;
+
+    eprintln!();
+    eprintln!("TIMING");
+    eprintln!("{}", repeat_str("=", name_col_w + col_w * 3));
+    eprintln!(
+        "{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+        "",
+        "TIME",
+        "% TOTAL",
+        "COUNT",
+        name_col_w = name_col_w,
+        col_w = col_w,
+    );
+
+    for category in CATEGORIES.iter() {
+        let c = &t.categories[category.index()];
+        eprintln!(
+            "{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+            category.label(),
+            format_duration(c.total),
+            format!("{:.1}%", percent(c.total, total)),
+            c.count.to_string(),
+            name_col_w = name_col_w,
+            col_w = col_w,
+        );
+    }
+
+    eprintln!("{}", repeat_str("-", name_col_w + col_w * 3));
+    eprintln!(
+        "{:<0name_col_w$}{:>0col_w$}",
+        "TOTAL",
+        format_duration(total),
+        name_col_w = name_col_w,
+        col_w = col_w,
+    );
+    eprintln!();
+    eprintln!("Categories can overlap, and may not add up to the total.");
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{:.3}s", d.as_secs_f64())
+}
+
+fn percent(d: Duration, total: Duration) -> f64 {
+    if total.as_secs_f64() == 0.0 {
+        return 0.0;
+    }
+
+    d.as_secs_f64() / total.as_secs_f64() * 100.0
+}
+
+/// prints the timing summary when dropped. Create at the start of main so
+/// the summary is printed however main returns.
+pub struct TimingReport;
+
+impl Drop for TimingReport {
+    fn drop(&mut self) {
+        print_summary();
+    }
+}
+
+/// enables timing if enabled is true, and returns a guard which prints the
+/// summary when it is dropped
+pub fn report(enabled: bool) -> Option<TimingReport> {
+    if !enabled {
+        return None;
+    }
+
+    enable();
+    Some(TimingReport)
+}
+
+/// Future which times the inner future, from when it is first polled until
+/// it completes.
+pub struct Timed<F: Future> {
+    inner: Pin<Box<F>>,
+    category: TimingCategory,
+    guard: Option<TimingGuard>,
+}
+
+impl<F: Future> Future for Timed<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        if self.guard.is_none() {
+            self.guard = Some(start(self.category));
+        }
+
+        let out = self.inner.as_mut().poll(cx);
+
+        if out.is_ready() {
+            self.guard = None;
+        }
+
+        out
+    }
+}
+
+pub trait TimedFutureExt: Future + Sized {
+    /// times the future under the specified category
+    fn timed(self, category: TimingCategory) -> Timed<Self> {
+        Timed {
+            inner: Box::pin(self),
+            category,
+            guard: None,
+        }
+    }
+}
+
+impl<F: Future> TimedFutureExt for F {}
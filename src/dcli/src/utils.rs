@@ -27,8 +27,13 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use chrono::{DateTime, Datelike, Duration, Local, TimeZone, Timelike, Utc};
+use crossterm::style::{Colorize, Styler};
 use crossterm::{execute, terminal};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
+use crate::datadir;
+use crate::enums::standing::Standing;
 use crate::error::Error;
 
 //use chrono::prelude::*;
@@ -61,6 +66,16 @@ pub fn print_verbose(msg: &str, verbose: bool) {
     eprintln!("{}", msg);
 }
 
+/// prints a human readable status message. With --porcelain, stdout only
+/// contains structured data, so the message is printed to stderr instead.
+pub fn print_message(msg: &str, porcelain: bool) {
+    if porcelain {
+        eprintln!("{}", msg);
+    } else {
+        println!("{}", msg);
+    }
+}
+
 pub fn print_error(msg: &str, error: Error) {
     let app_name = env::current_exe()
         .ok()
@@ -69,7 +84,7 @@ pub fn print_error(msg: &str, error: Error) {
         .and_then(Path::file_name)
         .and_then(OsStr::to_str)
         .map(String::from)
-        .unwrap_or_else(|| "".to_string());
+        .unwrap_or_default();
 
     eprintln!("{} : v{}", app_name, VERSION);
 
@@ -143,7 +158,7 @@ pub fn format_f32(val: f32, precision: usize) -> String {
 }
 
 pub fn repeat_str(s: &str, count: usize) -> String {
-    std::iter::repeat(s).take(count).collect::<String>()
+    std::iter::repeat_n(s, count).collect::<String>()
 }
 
 /// Clears screen. Works across platforms
@@ -207,6 +222,24 @@ pub fn human_duration(seconds: u32) -> String {
     t.join(" ")
 }
 
+//i.e. 1.25 GB
+pub fn human_bytes(bytes: u64) -> String {
+    let units = ["bytes", "KB", "MB", "GB", "TB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        return format!("{} {}", bytes, units[unit]);
+    }
+
+    format!("{:.2} {}", value, units[unit])
+}
+
 pub fn build_time_str(t: i32, label: &str) -> String {
     let mut out: String = "".to_string();
     if t > 0 {
@@ -276,6 +309,8 @@ pub fn determine_data_dir(dir: Option<PathBuf>) -> Result<PathBuf, Error> {
         std::fs::create_dir_all(&path)?;
     }
 
+    datadir::init_layout(&path)?;
+
     Ok(path)
 }
 
@@ -303,10 +338,196 @@ pub fn calculate_percent(value: u32, total: u32) -> f32 {
     (value as f32 / total as f32) * 100.0
 }
 
-pub fn truncate_ascii_string(input: &str, max_len: usize) -> String {
-    if input.chars().count() <= max_len {
+/// returns the number of terminal columns the string takes up when displayed.
+/// Wide characters (such as CJK and most emoji) take up two columns.
+pub fn display_width(input: &str) -> usize {
+    UnicodeWidthStr::width(input)
+}
+
+/// truncates the string so it fits in max_width terminal columns, ending it
+/// with ... if it was truncated. The string is split on grapheme boundaries
+/// so accented characters and emoji are never broken apart. Columns narrower
+/// than the ellipsis only get as many dots as fit.
+pub fn truncate_string(input: &str, max_width: usize) -> String {
+    if display_width(input) <= max_width {
         return input.to_string();
     }
 
-    format!("{:.len$}...", input, len = max_len - 3)
+    let ellipsis = &"..."[..max_width.min(3)];
+    let available = max_width - ellipsis.len();
+
+    let mut out = String::with_capacity(input.len());
+    let mut width = 0;
+    for g in input.graphemes(true) {
+        let w = display_width(g);
+        if width + w > available {
+            break;
+        }
+
+        out.push_str(g);
+        width += w;
+    }
+
+    out.push_str(ellipsis);
+    out
+}
+
+/// left aligns the string in a column width terminal columns wide. Use this
+/// instead of format width specifiers for user and manifest provided names,
+/// since those count chars and not columns.
+pub fn pad_string(input: &str, width: usize) -> String {
+    let current = display_width(input);
+    if current >= width {
+        return input.to_string();
+    }
+
+    format!("{}{}", input, " ".repeat(width - current))
+}
+
+/// truncates the string to fit in the column, and then pads it to fill it
+pub fn fit_string(input: &str, width: usize) -> String {
+    pad_string(&truncate_string(input, width), width)
+}
+
+/// applies terminal colors to table output. Colors are only used when
+/// requested, and never when the NO_COLOR environment variable is set
+/// (https://no-color.org).
+///
+/// Cells should be padded to their column width before being colored, since
+/// the escape codes would otherwise be counted when aligning columns.
+#[derive(Debug, Clone, Copy)]
+pub struct TextColors {
+    enabled: bool,
+}
+
+impl TextColors {
+    pub fn new(requested: bool) -> TextColors {
+        let no_color = match env::var_os("NO_COLOR") {
+            Some(e) => !e.is_empty(),
+            None => false,
+        };
+
+        TextColors {
+            enabled: requested && !no_color,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// green for victories, red for defeats
+    pub fn standing(&self, text: &str, standing: Standing) -> String {
+        if !self.enabled {
+            return text.to_string();
+        }
+
+        match standing {
+            Standing::Victory => format!("{}", text.green()),
+            Standing::Defeat => format!("{}", text.red()),
+            Standing::Unknown => text.to_string(),
+        }
+    }
+
+    /// green above 1.0, red below
+    pub fn kills_deaths_ratio(&self, text: &str, kd: f32) -> String {
+        if !self.enabled || f32_are_equal(kd, 1.0) {
+            return text.to_string();
+        }
+
+        if kd > 1.0 {
+            format!("{}", text.green())
+        } else {
+            format!("{}", text.red())
+        }
+    }
+
+    /// used to call out the member the data was requested for
+    pub fn highlight(&self, text: &str) -> String {
+        if !self.enabled {
+            return text.to_string();
+        }
+
+        format!("{}", text.yellow().bold())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //names with wide characters, zero width joiners and combining accents
+    const CJK: &str = "\u{6F22}\u{5B57}\u{540D}\u{524D}"; //8 columns
+    const ZWJ: &str = "a\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}b";
+    const COMBINING: &str = "Ame\u{301}lie\u{300}s"; //7 columns
+
+    #[test]
+    fn truncate_fits_unchanged() {
+        assert_eq!(truncate_string("Player", 6), "Player");
+        assert_eq!(truncate_string(CJK, 8), CJK);
+        assert_eq!(truncate_string(COMBINING, 7), COMBINING);
+    }
+
+    #[test]
+    fn truncate_cjk() {
+        let out = truncate_string(CJK, 7);
+        assert_eq!(out, "\u{6F22}\u{5B57}...");
+        assert_eq!(display_width(&out), 7);
+
+        //a wide character which doesnt fit is dropped, not split
+        let out = truncate_string(CJK, 6);
+        assert_eq!(out, "\u{6F22}...");
+        assert!(display_width(&out) <= 6);
+    }
+
+    #[test]
+    fn truncate_zwj_emoji() {
+        let width = display_width(ZWJ);
+        let out = truncate_string(ZWJ, width - 1);
+        assert!(display_width(&out) < width);
+
+        //the emoji sequence is kept or dropped as a whole
+        assert_eq!(out, "a...");
+    }
+
+    #[test]
+    fn truncate_combining_accents() {
+        let out = truncate_string(COMBINING, 6);
+        assert_eq!(out, "Ame\u{301}...");
+        assert_eq!(display_width(&out), 6);
+    }
+
+    #[test]
+    fn truncate_narrow_columns() {
+        assert_eq!(truncate_string("Player", 3), "...");
+        assert_eq!(truncate_string("Player", 2), "..");
+        assert_eq!(truncate_string("Player", 1), ".");
+        assert_eq!(truncate_string("Player", 0), "");
+        assert_eq!(truncate_string(CJK, 2), "..");
+    }
+
+    #[test]
+    fn pad_wide_names() {
+        assert_eq!(pad_string("abc", 5), "abc  ");
+        assert_eq!(pad_string(CJK, 10), format!("{}  ", CJK));
+        assert_eq!(pad_string(COMBINING, 8), format!("{} ", COMBINING));
+        assert_eq!(pad_string(CJK, 4), CJK);
+    }
+
+    #[test]
+    fn fit_is_column_width() {
+        for name in &[CJK, ZWJ, COMBINING, "Player"] {
+            for width in 0..12 {
+                let out = fit_string(name, width);
+                assert_eq!(
+                    display_width(&out),
+                    width,
+                    "{:?} in {} columns : {:?}",
+                    name,
+                    width,
+                    out
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,280 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Publishes status to Discord Rich Presence over the local Discord client's
+//IPC socket (a unix domain socket, or a named pipe on windows). See:
+//https://discord.com/developers/docs/topics/rpc
+
+use std::io::{Read, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use dcli::error::Error;
+use serde_json::{json, Value};
+
+const OP_HANDSHAKE: u32 = 0;
+const OP_FRAME: u32 = 1;
+const OP_CLOSE: u32 = 2;
+
+const IPC_VERSION: u32 = 1;
+
+//discord tries ports / pipes 0 through 9
+const MAX_IPC_INDEX: u32 = 10;
+
+//discord rejects presence strings longer than this
+const MAX_PRESENCE_LENGTH: usize = 128;
+
+#[cfg(unix)]
+type IpcStream = std::os::unix::net::UnixStream;
+
+#[cfg(windows)]
+type IpcStream = std::fs::File;
+
+/// status displayed in Discord. details is the first line, and state (if
+/// set) the second.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Presence {
+    pub details: String,
+    pub state: Option<String>,
+}
+
+struct DiscordConnection {
+    stream: IpcStream,
+    nonce: u64,
+}
+
+impl DiscordConnection {
+    fn connect(app_id: &str) -> Result<DiscordConnection, Error> {
+        let mut connection = DiscordConnection {
+            stream: open_ipc_stream()?,
+            nonce: 0,
+        };
+
+        connection.send(
+            OP_HANDSHAKE,
+            &json!({ "v": IPC_VERSION, "client_id": app_id }),
+        )?;
+
+        //discord responds with a READY event, or closes the connection if
+        //the application id is not valid
+        connection.receive()?;
+
+        Ok(connection)
+    }
+
+    fn set_activity(
+        &mut self,
+        presence: Option<&Presence>,
+        start: u64,
+    ) -> Result<(), Error> {
+        let activity = match presence {
+            Some(p) => {
+                let mut activity = json!({
+                    "details": truncate(&p.details),
+                    "timestamps": { "start": start },
+                });
+
+                if let Some(state) = &p.state {
+                    activity["state"] = Value::from(truncate(state));
+                }
+
+                activity
+            }
+            //clears the presence
+            None => Value::Null,
+        };
+
+        self.nonce += 1;
+        self.send(
+            OP_FRAME,
+            &json!({
+                "cmd": "SET_ACTIVITY",
+                "args": {
+                    "pid": std::process::id(),
+                    "activity": activity,
+                },
+                "nonce": self.nonce.to_string(),
+            }),
+        )?;
+
+        self.receive()?;
+        Ok(())
+    }
+
+    //frames are an opcode and payload length (both little endian u32)
+    //followed by the json payload
+    fn send(&mut self, op: u32, payload: &Value) -> Result<(), Error> {
+        let data = payload.to_string();
+
+        let mut frame: Vec<u8> = Vec::with_capacity(data.len() + 8);
+        frame.extend_from_slice(&op.to_le_bytes());
+        frame.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        frame.extend_from_slice(data.as_bytes());
+
+        self.stream.write_all(&frame)?;
+        self.stream.flush()?;
+
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Result<Value, Error> {
+        let mut header = [0u8; 8];
+        self.stream.read_exact(&mut header)?;
+
+        let op =
+            u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+        let len =
+            u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+
+        let mut data = vec![0u8; len as usize];
+        self.stream.read_exact(&mut data)?;
+
+        let payload: Value = serde_json::from_slice(&data)?;
+
+        if op == OP_CLOSE {
+            return Err(Error::Discord {
+                description: format!(
+                    "Connection closed by Discord : {}",
+                    payload["message"].as_str().unwrap_or("Unknown reason")
+                ),
+            });
+        }
+
+        if payload["evt"] == "ERROR" {
+            return Err(Error::Discord {
+                description: payload["data"]["message"]
+                    .as_str()
+                    .unwrap_or("Unknown error")
+                    .to_string(),
+            });
+        }
+
+        Ok(payload)
+    }
+}
+
+/// publishes status to Discord Rich Presence for the specified Discord
+/// application. Connects on the first update, and reconnects if Discord is
+/// restarted. The presence is cleared by Discord when the process exits.
+pub struct PresencePublisher {
+    app_id: String,
+    connection: Option<DiscordConnection>,
+    current: Option<Presence>,
+    start: u64,
+}
+
+impl PresencePublisher {
+    pub fn new(app_id: &str) -> PresencePublisher {
+        PresencePublisher {
+            app_id: app_id.to_string(),
+            connection: None,
+            current: None,
+            start: 0,
+        }
+    }
+
+    /// sets the presence displayed in Discord, or clears it if None. The
+    /// elapsed time displayed is reset when the presence changes.
+    pub fn publish(&mut self, presence: Option<Presence>) -> Result<(), Error> {
+        if presence != self.current || self.start == 0 {
+            self.start = unix_now();
+        }
+
+        if self.connection.is_none() {
+            self.connection = Some(DiscordConnection::connect(&self.app_id)?);
+        }
+
+        //unwrap is safe since we just connected
+        let result = self
+            .connection
+            .as_mut()
+            .unwrap()
+            .set_activity(presence.as_ref(), self.start);
+
+        //drop the connection so we reconnect on the next update (i.e. if
+        //discord was restarted)
+        if result.is_err() {
+            self.connection = None;
+        }
+
+        self.current = presence;
+        result
+    }
+}
+
+fn truncate(value: &str) -> String {
+    value.chars().take(MAX_PRESENCE_LENGTH).collect()
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn discord_not_running() -> Error {
+    Error::Discord {
+        description: "Could not connect to Discord. Make sure the Discord desktop client is running.".to_string(),
+    }
+}
+
+#[cfg(unix)]
+fn open_ipc_stream() -> Result<IpcStream, Error> {
+    use std::path::PathBuf;
+
+    let mut dirs: Vec<PathBuf> = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .map(PathBuf::from)
+        .collect();
+    dirs.push(PathBuf::from("/tmp"));
+
+    for dir in &dirs {
+        //flatpak and snap installs of discord put the socket in a sub
+        //directory
+        for sub in &["", "app/com.discordapp.Discord", "snap.discord"] {
+            for i in 0..MAX_IPC_INDEX {
+                let path = dir.join(sub).join(format!("discord-ipc-{}", i));
+                if let Ok(e) = IpcStream::connect(&path) {
+                    return Ok(e);
+                }
+            }
+        }
+    }
+
+    Err(discord_not_running())
+}
+
+#[cfg(windows)]
+fn open_ipc_stream() -> Result<IpcStream, Error> {
+    for i in 0..MAX_IPC_INDEX {
+        if let Ok(e) = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(format!(r"\\.\pipe\discord-ipc-{}", i))
+        {
+            return Ok(e);
+        }
+    }
+
+    Err(discord_not_running())
+}
@@ -20,20 +20,33 @@
 * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 */
 
-use std::path::PathBuf;
+#[cfg(feature = "discord")]
+mod discord;
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{SecondsFormat, Utc};
 
 use dcli::apiinterface::ApiInterface;
+use dcli::datadir;
+use dcli::enums::language::Language;
+use dcli::enums::mode::Mode;
+use dcli::enums::platform::Platform;
+use dcli::error::Error;
 use dcli::manifest::definitions::{
     ActivityDefinitionData, DestinationDefinitionData, PlaceDefinitionData,
 };
-//use dcli::error::Error;
-use dcli::enums::mode::Mode;
-use dcli::enums::platform::Platform;
 use dcli::manifestinterface::ManifestInterface;
 use dcli::output::Output;
 use dcli::response::gpr::CharacterActivitiesData;
+use dcli::template;
+use dcli::timing::{self, TimingCategory};
 use dcli::utils::EXIT_FAILURE;
-use dcli::utils::{build_tsv, determine_data_dir, print_error, print_verbose};
+use dcli::utils::{
+    build_tsv, determine_data_dir, print_error, print_message, print_verbose,
+};
+use serde_derive::Serialize;
 use structopt::StructOpt;
 
 const ORBIT_PLACE_HASH: u32 = 2961497387;
@@ -71,6 +84,13 @@ struct Opt {
     #[structopt(short = "v", long = "verbose")]
     verbose: bool,
 
+    /// Print how long was spent in API calls, SQL queries and formatting
+    ///
+    /// Timings are printed to stderr when the command finishes, and can help
+    /// diagnose slow commands.
+    #[structopt(long = "timing")]
+    timing: bool,
+
     /// Directory where Destiny 2 manifest database file is stored. (optional)
     ///
     /// This will normally be downloaded using the dclim tool, and stored in a file
@@ -79,26 +99,147 @@ struct Opt {
     #[structopt(short = "D", long = "data-dir", parse(from_os_str))]
     data_dir: Option<PathBuf>,
 
+    /// Print the paths of the files and directories used by dcli and exit
+    ///
+    /// Includes the data directory, manifest, activity store and the cache,
+    /// config and backup directories.
+    #[structopt(long = "print-paths")]
+    print_paths: bool,
+
+    /// Name of the manifest to use (optional)
+    ///
+    /// Defaults to the current (live) manifest. dclim keeps the previously
+    /// downloaded manifest under the name previous, and can download manifests
+    /// into other named slots with its --manifest-name flag.
+    #[structopt(
+        long = "manifest-name",
+        default_value = "live",
+        parse(try_from_str = datadir::parse_manifest_name)
+    )]
+    manifest_name: String,
+
+    /// Language to display weapon, medal and map names in (optional)
+    ///
+    /// Defaults to en. Names are looked up in the manifest for the language,
+    /// which must first be downloaded with dclim --language. Stored activity
+    /// data does not need to be synced again.
+    ///
+    /// Valid values are en, fr, es, es-mx, de, it, ja, pt-br, ru, pl, ko,
+    /// zh-cht and zh-chs.
+    #[structopt(long = "language", default_value = "en")]
+    language: Language,
+
     /// Format for command output
     ///
-    /// Valid values are default (Default) and tsv.
+    /// Valid values are default (Default), tsv, json and jsonl.
     ///
     /// tsv outputs in a tab (\t) seperated format of name / value pairs with lines
     /// ending in a new line character (\n).
+    ///
+    /// json outputs the activity status as a single JSON object on one line.
+    ///
+    /// jsonl outputs one JSON object per line for each status check (including
+    /// when watching), with the event (status, unknown or error) and the time
+    /// it was checked.
     #[structopt(
         short = "O",
         long = "output-format",
         default_value = "default"
     )]
     output: Output,
+
+    /// Only write structured data to stdout
+    ///
+    /// All other messages (i.e. errors, or an activity missing from the
+    /// manifest) are written to stderr. Uses tsv output unless
+    /// --output-format is set to json.
+    #[structopt(long = "porcelain", conflicts_with = "template")]
+    porcelain: bool,
+
+    /// Handlebars template file used to format output (optional)
+    ///
+    /// The template is rendered with the same data as json output (i.e.
+    /// {{human_status}} or {{activity_name}} on {{place}}), and takes
+    /// precedence over --output-format.
+    #[structopt(long = "template", parse(from_os_str))]
+    template: Option<PathBuf>,
+
+    /// Keep running and output the status whenever it changes
+    ///
+    /// The status is checked every --interval seconds. Each change is output
+    /// on its own line (for json output, one json object per line). jsonl
+    /// output is written for every check, whether or not the status changed.
+    #[structopt(short = "w", long = "watch")]
+    watch: bool,
+
+    /// Number of seconds between status checks when watching
+    #[structopt(long = "interval", default_value = "30")]
+    interval: u64,
+
+    /// Publish status to Discord Rich Presence using the specified application id
+    ///
+    /// Shows the current activity (i.e. Playing Control on Midtown) on your
+    /// Discord profile, via the local Discord desktop client. Implies --watch,
+    /// and the status is cleared when dclia exits. Requires a Discord
+    /// application id from the Discord developer portal, and dclia to be
+    /// built with the discord feature.
+    #[structopt(long = "discord")]
+    discord: Option<String>,
 }
 
 #[tokio::main]
 async fn main() {
-    let opt = Opt::from_args();
+    let mut opt = Opt::from_args();
     print_verbose(&format!("{:#?}", opt), opt.verbose);
 
-    let data_dir = match determine_data_dir(opt.data_dir) {
+    let _timing = timing::report(opt.timing);
+
+    let supported_output =
+        [Output::Default, Output::Tsv, Output::Json, Output::Jsonl];
+    if let Err(e) = opt.output.check_supported(&supported_output) {
+        print_error("Invalid output format.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    if opt.porcelain {
+        opt.output = match opt.output.porcelain(&supported_output) {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Invalid output format.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+    }
+
+    #[cfg(not(feature = "discord"))]
+    if opt.discord.is_some() {
+        print_error(
+            "Discord Rich Presence is not available.",
+            Error::Discord {
+                description: "dclia was built without Discord support. Rebuild with --features discord.".to_string(),
+            },
+        );
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    #[cfg(feature = "discord")]
+    let mut publisher = opt
+        .discord
+        .as_ref()
+        .map(|e| discord::PresencePublisher::new(e));
+
+    let template = match &opt.template {
+        Some(e) => match template::load_template(e) {
+            Ok(e) => Some(e),
+            Err(e) => {
+                print_error("Could not load template.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        },
+        None => None,
+    };
+
+    let data_dir = match determine_data_dir(opt.data_dir.clone()) {
         Ok(e) => e,
         Err(e) => {
             print_error("Error initializing manifest directory.", e);
@@ -106,6 +247,11 @@ async fn main() {
         }
     };
 
+    if opt.print_paths {
+        datadir::print_paths(&data_dir);
+        return;
+    }
+
     let client = match ApiInterface::new(opt.verbose) {
         Ok(e) => e,
         Err(e) => {
@@ -114,40 +260,130 @@ async fn main() {
         }
     };
 
-    let activities_data: Option<CharacterActivitiesData> = match client
-        .retrieve_current_activity(opt.member_id, opt.platform)
-        .await
-    {
-        Ok(e) => e,
-        Err(e) => {
-            print_error("Error retrieving data from API", e);
-            std::process::exit(EXIT_FAILURE);
-        }
-    };
+    //the presence is cleared when we exit, so publishing to discord always
+    //runs until stopped
+    let watch = opt.watch || opt.discord.is_some();
 
-    let activity_data_a = match activities_data {
-        Some(e) => e,
-        None => {
-            match opt.output {
-                Output::Default => {
-                    println!("Not currently in an activity");
+    //jsonl is an event stream, so every check is output. Templates take
+    //precedence over the output format.
+    let emit_events = opt.output == Output::Jsonl && template.is_none();
+
+    //only loaded once the player is in an activity
+    let mut manifest: Option<ManifestInterface> = None;
+    let mut last_status: Option<CurrentStatus> = None;
+
+    loop {
+        match retrieve_status(&client, &mut manifest, &data_dir, &opt).await {
+            Ok(status) => {
+                let changed = last_status.as_ref() != Some(&status);
+
+                if emit_events {
+                    let _format_timer =
+                        timing::start(TimingCategory::Formatting);
+                    StatusEvent::from_status(&status, changed).emit();
                 }
-                Output::Tsv => {
-                    print_tsv_no_activity();
+
+                //in watch mode, status is only output when it changes
+                if changed {
+                    if !emit_events {
+                        let _format_timer =
+                            timing::start(TimingCategory::Formatting);
+                        print_status(
+                            &status,
+                            &opt.output,
+                            template.as_deref(),
+                            opt.porcelain,
+                        );
+                    }
+
+                    #[cfg(feature = "discord")]
+                    if let Some(p) = publisher.as_mut() {
+                        if let Err(e) = p.publish(build_presence(&status)) {
+                            print_error("Could not update Discord status.", e);
+                        }
+                    }
+
+                    last_status = Some(status);
                 }
-            };
-            return;
+            }
+            Err(e) => {
+                if emit_events {
+                    StatusEvent::error(&format!(
+                        "Error retrieving activity status. {}",
+                        e
+                    ))
+                    .emit();
+                }
+                print_error("Error retrieving activity status.", e);
+
+                //errors are usually transient (i.e. api maintenance) so we
+                //keep trying when watching
+                if !watch {
+                    std::process::exit(EXIT_FAILURE);
+                }
+            }
         }
-    };
 
-    let mut manifest = match ManifestInterface::new(&data_dir, false).await {
-        Ok(e) => e,
-        Err(e) => {
-            print_error("Manifest Error", e);
-            std::process::exit(EXIT_FAILURE);
+        if !watch {
+            break;
         }
+
+        tokio::time::sleep(Duration::from_secs(opt.interval)).await;
+    }
+}
+
+//activity names and hashes used to build the status for output
+#[derive(PartialEq, Debug)]
+struct ActivityInfo {
+    mode: Mode,
+    activity_type_name: String,
+    activity_name: String,
+    place_name: String,
+    destination_name: String,
+    description: String,
+    hashes: ActivityHashes,
+}
+
+#[derive(PartialEq, Debug)]
+enum CurrentStatus {
+    NoActivity,
+    Orbit(ActivityHashes),
+
+    //activity, place or destination is not in the manifest. Contains the
+    //message to display.
+    Unknown(String),
+    Activity(ActivityInfo),
+}
+
+async fn retrieve_status(
+    client: &ApiInterface,
+    manifest: &mut Option<ManifestInterface>,
+    data_dir: &Path,
+    opt: &Opt,
+) -> Result<CurrentStatus, Error> {
+    let activities_data: Option<CharacterActivitiesData> = client
+        .retrieve_current_activity(opt.member_id.clone(), opt.platform)
+        .await?;
+
+    let activity_data_a = match activities_data {
+        Some(e) => e,
+        None => return Ok(CurrentStatus::NoActivity),
     };
 
+    if manifest.is_none() {
+        *manifest = Some(
+            ManifestInterface::new_with_name(
+                data_dir,
+                &opt.language.manifest_name(&opt.manifest_name),
+                false,
+            )
+            .await?,
+        );
+    }
+
+    //unwrap is safe since we just loaded it
+    let manifest = manifest.as_mut().unwrap();
+
     print_verbose(
         &format!(
             "Getting activity definition data from manifest : {}",
@@ -155,35 +391,27 @@ async fn main() {
         ),
         opt.verbose,
     );
-    let activity_data_m: Option<ActivityDefinitionData> = match manifest
+    let activity_data_m: ActivityDefinitionData = match manifest
         .get_activity_definition(activity_data_a.current_activity_hash)
-        .await
+        .await?
     {
-        Ok(e) => e,
-        Err(e) => {
-            print_error("Error Retrieving Data from Manifest", e);
-            std::process::exit(EXIT_FAILURE);
+        Some(e) => e,
+        None => {
+            return Ok(CurrentStatus::Unknown("Unknown activity. Make sure you have synced the latest version of the manifest using dclim.".to_string()));
         }
     };
 
-    if activity_data_m.is_none() {
-        println!("Unknown activity. Make sure you have synced the latest version of the manifest using dclim.");
-        return;
-    }
-
-    let activity_data_m = activity_data_m.unwrap();
+    //raw hashes, so tools can do their own manifest lookups
+    let hashes = ActivityHashes {
+        current_activity_hash: activity_data_a.current_activity_hash,
+        current_activity_mode_hash: activity_data_a.current_activity_mode_hash,
+        activity_type_hash: activity_data_m.activity_type_hash,
+        destination_hash: activity_data_m.destination_hash,
+        place_hash: activity_data_m.place_hash,
+    };
 
     if activity_data_m.place_hash == ORBIT_PLACE_HASH {
-        match opt.output {
-            Output::Default => {
-                println!("{}", get_in_orbit_human());
-            }
-            Output::Tsv => {
-                print_tsv_orbit();
-            }
-        };
-
-        return;
+        return Ok(CurrentStatus::Orbit(hashes));
     }
 
     print_verbose(
@@ -193,23 +421,16 @@ async fn main() {
         ),
         opt.verbose,
     );
-    let place_data_m: Option<PlaceDefinitionData> = match manifest
+    let place_data_m: PlaceDefinitionData = match manifest
         .get_place_definition(activity_data_m.place_hash)
-        .await
+        .await?
     {
-        Ok(e) => e,
-        Err(e) => {
-            print_error("Error Retrieving Data from Manifest", e);
-            std::process::exit(EXIT_FAILURE);
+        Some(e) => e,
+        None => {
+            return Ok(CurrentStatus::Unknown("Unknown location. Make sure you have synced the latest version of the manifest using dclim.".to_string()));
         }
     };
 
-    if place_data_m.is_none() {
-        println!("Unknown location. Make sure you have synced the latest version of the manifest using dclim.");
-        return;
-    }
-    let place_data_m = place_data_m.unwrap();
-
     print_verbose(
         &format!(
             "Getting destination definition data from manifest : {}",
@@ -217,24 +438,16 @@ async fn main() {
         ),
         opt.verbose,
     );
-    let destination_data_m: Option<DestinationDefinitionData> = match manifest
+    let destination_data_m: DestinationDefinitionData = match manifest
         .get_destination_definition(activity_data_m.destination_hash)
-        .await
+        .await?
     {
-        Ok(e) => e,
-        Err(e) => {
-            print_error("Error Retrieving Data from Manifest", e);
-            std::process::exit(EXIT_FAILURE);
+        Some(e) => e,
+        None => {
+            return Ok(CurrentStatus::Unknown("Unknown destination. Make sure you have synced the latest version of the manifest using dclim.".to_string()));
         }
     };
 
-    if destination_data_m.is_none() {
-        println!("Unknown destination. Make sure you have synced the latest version of the manifest using dclim.");
-        return;
-    }
-
-    let destination_data_m = destination_data_m.unwrap();
-
     let mut mode = Mode::None;
 
     //lets find out the mode / activity type name
@@ -286,48 +499,346 @@ async fn main() {
     // note if for some reason correct activities are not displayed for some
     // crucible modes, then this may be false (i've only seen this as an issue
     // for raids thought (see link above for bug (raid)))
-    let description = activity_data_m
-        .display_properties
-        .description
-        .unwrap_or_else(|| "".to_string());
-    let activity_name = activity_data_m.display_properties.name;
-    let place_name = place_data_m.display_properties.name;
-    let destination_name = destination_data_m.display_properties.name;
-
-    match opt.output {
-        Output::Default => {
-            print_default(
-                mode,
-                &activity_type_name,
-                &activity_name,
-                &place_name,
-                &destination_name,
-                &description,
-            );
+    Ok(CurrentStatus::Activity(ActivityInfo {
+        mode,
+        activity_type_name,
+        description: activity_data_m
+            .display_properties
+            .description
+            .unwrap_or_default(),
+        activity_name: activity_data_m.display_properties.name,
+        place_name: place_data_m.display_properties.name,
+        destination_name: destination_data_m.display_properties.name,
+        hashes,
+    }))
+}
+
+fn print_status(
+    status: &CurrentStatus,
+    output: &Output,
+    template: Option<&str>,
+    porcelain: bool,
+) {
+    match status {
+        CurrentStatus::NoActivity => {
+            if let Some(e) = template {
+                print_template(e, &build_status_no_activity());
+                return;
+            }
+
+            match output {
+                Output::Default => {
+                    println!("Not currently in an activity");
+                }
+                Output::Tsv => {
+                    print_tsv(&build_status_no_activity());
+                }
+                Output::Json => {
+                    print_json(&build_status_no_activity());
+                }
+                //other formats are rejected at startup
+                _ => {}
+            };
         }
-        Output::Tsv => {
-            print_tsv(
-                mode,
-                &activity_type_name,
-                &activity_name,
-                &place_name,
-                &destination_name,
-                &description,
-                true,
-            );
+        CurrentStatus::Orbit(hashes) => {
+            if let Some(e) = template {
+                print_template(e, &build_status_orbit(*hashes));
+                return;
+            }
+
+            match output {
+                Output::Default => {
+                    println!("{}", get_in_orbit_human());
+                }
+                Output::Tsv => {
+                    print_tsv(&build_status_orbit(*hashes));
+                }
+                Output::Json => {
+                    print_json(&build_status_orbit(*hashes));
+                }
+                //other formats are rejected at startup
+                _ => {}
+            };
         }
-    };
+        CurrentStatus::Unknown(message) => {
+            print_message(message, porcelain);
+        }
+        CurrentStatus::Activity(a) => {
+            if let Some(e) = template {
+                print_template(e, &build_status_activity(a));
+                return;
+            }
+
+            match output {
+                Output::Default => {
+                    print_default(
+                        a.mode,
+                        &a.activity_type_name,
+                        &a.activity_name,
+                        &a.place_name,
+                        &a.destination_name,
+                        &a.description,
+                    );
+                }
+                Output::Tsv => {
+                    print_tsv(&build_status_activity(a));
+                }
+                Output::Json => {
+                    print_json(&build_status_activity(a));
+                }
+                //other formats are rejected at startup
+                _ => {}
+            };
+        }
+    }
+}
+
+//status shown in discord. Crucible and gambit leave out the activity
+//description, which is usually too long to display.
+#[cfg(feature = "discord")]
+fn build_presence(status: &CurrentStatus) -> Option<discord::Presence> {
+    match status {
+        CurrentStatus::NoActivity | CurrentStatus::Unknown(_) => None,
+        CurrentStatus::Orbit(_) => Some(discord::Presence {
+            details: "In Orbit".to_string(),
+            state: None,
+        }),
+        CurrentStatus::Activity(a) => {
+            let details = if a.mode.is_crucible() || a.mode.is_gambit() {
+                format!(
+                    "Playing {} on {}",
+                    a.activity_type_name, a.activity_name
+                )
+            } else {
+                build_human_status(
+                    a.mode,
+                    &a.activity_type_name,
+                    &a.activity_name,
+                    &a.place_name,
+                    &a.destination_name,
+                    &a.description,
+                )
+            };
+
+            let state = if a.destination_name.is_empty() {
+                None
+            } else {
+                Some(a.destination_name.clone())
+            };
+
+            Some(discord::Presence { details, state })
+        }
+    }
+}
+
+fn print_tsv(status: &ActivityStatusJson) {
+    let h = &status.hashes;
+    let name_values: Vec<(&str, String)> = vec![
+        ("in_activity", status.in_activity.to_string()),
+        ("activity_type_name", status.mode.to_string()),
+        ("activity_name", status.activity_name.to_string()),
+        ("place_name", status.place.to_string()),
+        ("destination_name", status.destination.to_string()),
+        ("description", status.description.to_string()),
+        ("human_status", status.human_status.to_string()),
+        ("is_crucible", status.is_crucible.to_string()),
+        ("current_activity_hash", h.current_activity_hash.to_string()),
+        (
+            "current_activity_mode_hash",
+            h.current_activity_mode_hash.to_string(),
+        ),
+        ("activity_type_hash", h.activity_type_hash.to_string()),
+        ("destination_hash", h.destination_hash.to_string()),
+        ("place_hash", h.place_hash.to_string()),
+    ];
+
+    print!("{}", build_tsv(name_values));
+}
+
+//manifest hashes for the current activity. All are 0 if the player is not
+//in an activity.
+#[derive(Serialize, Debug, Default, Clone, Copy, PartialEq)]
+struct ActivityHashes {
+    current_activity_hash: u32,
+    current_activity_mode_hash: u32,
+    activity_type_hash: u32,
+    destination_hash: u32,
+    place_hash: u32,
 }
 
-fn print_tsv_orbit() {
-    print_tsv(Mode::None, "", "", "Orbit", "", "", true);
+#[derive(Serialize, Debug)]
+struct ActivityStatusJson<'a> {
+    in_activity: bool,
+    mode: &'a str,
+    activity_name: &'a str,
+    place: &'a str,
+    destination: &'a str,
+    description: &'a str,
+    human_status: String,
+    is_crucible: bool,
+
+    #[serde(flatten)]
+    hashes: ActivityHashes,
+}
+
+fn build_status_orbit(hashes: ActivityHashes) -> ActivityStatusJson<'static> {
+    build_status(Mode::None, "", "", "Orbit", "", "", true, hashes)
+}
+
+fn build_status_activity(a: &ActivityInfo) -> ActivityStatusJson<'_> {
+    build_status(
+        a.mode,
+        &a.activity_type_name,
+        &a.activity_name,
+        &a.place_name,
+        &a.destination_name,
+        &a.description,
+        true,
+        a.hashes,
+    )
 }
 
-fn print_tsv_no_activity() {
-    print_tsv(Mode::None, "", "", "", "", "", false);
+fn build_status_no_activity() -> ActivityStatusJson<'static> {
+    build_status(
+        Mode::None,
+        "",
+        "",
+        "",
+        "",
+        "",
+        false,
+        ActivityHashes::default(),
+    )
 }
 
-fn print_tsv(
+//status data used for tsv, json and template output
+#[allow(clippy::too_many_arguments)]
+fn build_status<'a>(
+    mode: Mode,
+    activity_type_name: &'a str,
+    activity_name: &'a str,
+    place_name: &'a str,
+    destination_name: &'a str,
+    description: &'a str,
+    in_activity: bool,
+    hashes: ActivityHashes,
+) -> ActivityStatusJson<'a> {
+    ActivityStatusJson {
+        in_activity,
+        mode: activity_type_name,
+        activity_name,
+        place: place_name,
+        destination: destination_name,
+        description,
+        human_status: get_human_status(
+            mode,
+            activity_type_name,
+            activity_name,
+            place_name,
+            destination_name,
+            description,
+            in_activity,
+        ),
+        is_crucible: mode.is_crucible(),
+        hashes,
+    }
+}
+
+//events output for each status check with jsonl output. These are written out
+//one JSON object per line (JSON Lines), like the dclias sync events.
+#[derive(Serialize, Debug)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum StatusEvent<'a> {
+    Status {
+        timestamp: String,
+        changed: bool,
+
+        #[serde(flatten)]
+        status: ActivityStatusJson<'a>,
+    },
+    Unknown {
+        timestamp: String,
+        changed: bool,
+        description: String,
+    },
+    Error {
+        timestamp: String,
+        description: String,
+    },
+}
+
+fn timestamp() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+impl<'a> StatusEvent<'a> {
+    fn from_status(
+        status: &'a CurrentStatus,
+        changed: bool,
+    ) -> StatusEvent<'a> {
+        let status = match status {
+            CurrentStatus::NoActivity => build_status_no_activity(),
+            CurrentStatus::Orbit(hashes) => build_status_orbit(*hashes),
+            CurrentStatus::Activity(a) => build_status_activity(a),
+            CurrentStatus::Unknown(message) => {
+                return StatusEvent::Unknown {
+                    timestamp: timestamp(),
+                    changed,
+                    description: message.to_string(),
+                };
+            }
+        };
+
+        StatusEvent::Status {
+            timestamp: timestamp(),
+            changed,
+            status,
+        }
+    }
+
+    fn error(description: &str) -> StatusEvent<'a> {
+        StatusEvent::Error {
+            timestamp: timestamp(),
+            description: description.to_string(),
+        }
+    }
+
+    fn emit(&self) {
+        match serde_json::to_string(self) {
+            Ok(e) => println!("{}", e),
+            Err(e) => {
+                print_error(
+                    "Error serializing status to json.",
+                    Error::from(e),
+                );
+                std::process::exit(EXIT_FAILURE);
+            }
+        }
+    }
+}
+
+fn print_json(status: &ActivityStatusJson) {
+    match serde_json::to_string(status) {
+        Ok(e) => println!("{}", e),
+        Err(e) => {
+            print_error("Error serializing status to json.", Error::from(e));
+            std::process::exit(EXIT_FAILURE);
+        }
+    }
+}
+
+fn print_template(template: &str, status: &ActivityStatusJson) {
+    match template::render_template(template, status) {
+        Ok(e) => println!("{}", e),
+        Err(e) => {
+            print_error("Error rendering template.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    }
+}
+
+//human readable status shared by tsv and json output
+fn get_human_status(
     mode: Mode,
     activity_type_name: &str,
     activity_name: &str,
@@ -335,10 +846,10 @@ fn print_tsv(
     destination_name: &str,
     description: &str,
     in_activity: bool,
-) {
+) -> String {
     //figure out if they are in orbit since bungie doesnt give us
     //a mode for it
-    let human_status = if mode == Mode::None && in_activity {
+    if mode == Mode::None && in_activity {
         get_in_orbit_human()
     } else {
         build_human_status(
@@ -349,20 +860,7 @@ fn print_tsv(
             destination_name,
             description,
         )
-    };
-
-    let mut name_values: Vec<(&str, String)> = Vec::new();
-
-    name_values.push(("in_activity", in_activity.to_string()));
-    name_values.push(("activity_type_name", activity_type_name.to_string()));
-    name_values.push(("activity_name", activity_name.to_string()));
-    name_values.push(("place_name", place_name.to_string()));
-    name_values.push(("destination_name", destination_name.to_string()));
-    name_values.push(("description", description.to_string()));
-    name_values.push(("human_status", human_status));
-    name_values.push(("is_crucible", mode.is_crucible().to_string()));
-
-    print!("{}", build_tsv(name_values));
+    }
 }
 
 fn print_default(
@@ -0,0 +1,367 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::fmt;
+use std::str::FromStr;
+
+use dcli::crucible::{
+    AggregateCruciblePerformances, CrucibleActivity, CruciblePlayerPerformance,
+    ExtendedCrucibleStats,
+};
+use dcli::derivedstats;
+use dcli::enums::statprofile::StatProfile;
+use dcli::gambit::GambitStats;
+use dcli::utils::{calculate_avg, format_f32};
+
+//space between columns, since all columns are right aligned
+const COLUMN_GAP: usize = 3;
+
+const DEFAULT_COLUMNS: &str =
+    "kills,assists,kills_assists,deaths,kd,kda,eff,super,grenade,melee,medals,rating,status";
+
+const ROUNDS_COLUMNS: &str =
+    "kills,assists,kills_assists,deaths,kd,kda,eff,precision,super,medals,rating,status";
+
+const OBJECTIVE_COLUMNS: &str =
+    "kills,assists,kills_assists,deaths,kd,eff,score,super,grenade,melee,medals,rating,status";
+
+const FREE_FOR_ALL_COLUMNS: &str =
+    "placement,kills,deaths,kd,eff,score,precision,super,medals,rating,status";
+
+const GAMBIT_COLUMNS: &str =
+    "kills,assists,deaths,kd,eff,motes,motes_lost,invasion_kills,invader_kills,blockers,medals,status";
+
+/// default columns for the mode of an activity, used when columns are not
+/// specified
+pub fn profile_columns(profile: &StatProfile) -> Vec<PlayerColumn> {
+    let columns = match profile {
+        StatProfile::Standard => DEFAULT_COLUMNS,
+        StatProfile::Rounds => ROUNDS_COLUMNS,
+        StatProfile::Objective => OBJECTIVE_COLUMNS,
+        StatProfile::FreeForAll => FREE_FOR_ALL_COLUMNS,
+        StatProfile::Gambit => GAMBIT_COLUMNS,
+    };
+
+    //column lists are constants, so they will always parse
+    columns
+        .split(',')
+        .filter_map(|c| PlayerColumn::from_str(c).ok())
+        .collect()
+}
+
+/// a stat column which can be displayed in the player table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayerColumn {
+    Kills,
+    Assists,
+    OpponentsDefeated,
+    Deaths,
+    KillsDeathsRatio,
+    KillsDeathsAssists,
+    Efficiency,
+    Score,
+    PrecisionKills,
+    Supers,
+    Grenades,
+    Melees,
+    Abilities,
+    Medals,
+    Rating,
+    Status,
+    Placement,
+    Motes,
+    MotesLost,
+    InvasionKills,
+    InvaderKills,
+    Blockers,
+
+    /// a derived stat registered with derivedstats, by name
+    Derived(String),
+}
+
+impl PlayerColumn {
+    pub fn header(&self) -> String {
+        let header = match self {
+            PlayerColumn::Kills => "KILLS",
+            PlayerColumn::Assists => "ASTS",
+            PlayerColumn::OpponentsDefeated => "K+A",
+            PlayerColumn::Deaths => "DEATHS",
+            PlayerColumn::KillsDeathsRatio => "K/D",
+            PlayerColumn::KillsDeathsAssists => "KD/A",
+            PlayerColumn::Efficiency => "EFF",
+            PlayerColumn::Score => "SCORE",
+            PlayerColumn::PrecisionKills => "PREC",
+            PlayerColumn::Supers => "SUP",
+            PlayerColumn::Grenades => "GREN",
+            PlayerColumn::Melees => "MEL",
+            PlayerColumn::Abilities => "ABL",
+            PlayerColumn::Medals => "MED",
+            PlayerColumn::Rating => "RATING",
+            PlayerColumn::Status => "STATUS",
+            PlayerColumn::Placement => "PLACE",
+            PlayerColumn::Motes => "MOTES",
+            PlayerColumn::MotesLost => "LOST",
+            PlayerColumn::InvasionKills => "INV K",
+            PlayerColumn::InvaderKills => "INVDR K",
+            PlayerColumn::Blockers => "BLOCK",
+            PlayerColumn::Derived(name) => return name.to_uppercase(),
+        };
+
+        header.to_string()
+    }
+
+    /// value for a single player. rating is passed in already formatted, since
+    /// ratings are retrieved seperately from the activity
+    pub fn player_value(
+        &self,
+        activity: &CrucibleActivity,
+        p: &CruciblePlayerPerformance,
+        rating: &str,
+    ) -> String {
+        //players who leave right away (or bots) may not have extended stats
+        let no_extended = ExtendedCrucibleStats::default();
+        let extended = p.stats.extended.as_ref().unwrap_or(&no_extended);
+
+        match self {
+            PlayerColumn::Kills => p.stats.kills.to_string(),
+            PlayerColumn::Assists => p.stats.assists.to_string(),
+            PlayerColumn::OpponentsDefeated => {
+                p.stats.opponents_defeated.to_string()
+            }
+            PlayerColumn::Deaths => p.stats.deaths.to_string(),
+            PlayerColumn::KillsDeathsRatio => {
+                format_f32(p.stats.kills_deaths_ratio, 2)
+            }
+            PlayerColumn::KillsDeathsAssists => {
+                format_f32(p.stats.kills_deaths_assists, 2)
+            }
+            PlayerColumn::Efficiency => format_f32(p.stats.efficiency, 2),
+            PlayerColumn::Score => p.stats.score.to_string(),
+            PlayerColumn::PrecisionKills => {
+                extended.precision_kills.to_string()
+            }
+            PlayerColumn::Supers => extended.weapon_kills_super.to_string(),
+            PlayerColumn::Grenades => extended.weapon_kills_grenade.to_string(),
+            PlayerColumn::Melees => extended.weapon_kills_melee.to_string(),
+            PlayerColumn::Abilities => {
+                extended.weapon_kills_ability.to_string()
+            }
+            PlayerColumn::Medals => extended.all_medals_earned.to_string(),
+            PlayerColumn::Rating => rating.to_string(),
+            PlayerColumn::Status => p.stats.generate_status(),
+            PlayerColumn::Placement => activity.get_placement(p).to_string(),
+            PlayerColumn::Motes
+            | PlayerColumn::MotesLost
+            | PlayerColumn::InvasionKills
+            | PlayerColumn::InvaderKills
+            | PlayerColumn::Blockers => {
+                self.gambit_value(&extended.gambit_stats()).to_string()
+            }
+            PlayerColumn::Derived(name) => p
+                .stats
+                .derived
+                .iter()
+                .find(|d| &d.name == name)
+                .map(|d| format_f32(d.value, 2))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// total for a group of players
+    pub fn total_value(&self, a: &AggregateCruciblePerformances) -> String {
+        let no_extended = Default::default();
+        let extended = a.extended.as_ref().unwrap_or(&no_extended);
+
+        match self {
+            PlayerColumn::Kills => a.kills.to_string(),
+            PlayerColumn::Assists => a.assists.to_string(),
+            PlayerColumn::OpponentsDefeated => a.opponents_defeated.to_string(),
+            PlayerColumn::Deaths => a.deaths.to_string(),
+            PlayerColumn::KillsDeathsRatio => {
+                format_f32(a.kills_deaths_ratio, 2)
+            }
+            PlayerColumn::KillsDeathsAssists => {
+                format_f32(a.kills_deaths_assists, 2)
+            }
+            PlayerColumn::Efficiency => format_f32(a.efficiency, 2),
+            PlayerColumn::Score => a.score.to_string(),
+            PlayerColumn::PrecisionKills => {
+                extended.precision_kills.to_string()
+            }
+            PlayerColumn::Supers => extended.weapon_kills_super.to_string(),
+            PlayerColumn::Grenades => extended.weapon_kills_grenade.to_string(),
+            PlayerColumn::Melees => extended.weapon_kills_melee.to_string(),
+            PlayerColumn::Abilities => {
+                extended.weapon_kills_ability.to_string()
+            }
+            PlayerColumn::Medals => extended.all_medals_earned.to_string(),
+            PlayerColumn::Motes
+            | PlayerColumn::MotesLost
+            | PlayerColumn::InvasionKills
+            | PlayerColumn::InvaderKills
+            | PlayerColumn::Blockers => {
+                self.gambit_value(&extended.gambit).to_string()
+            }
+            PlayerColumn::Derived(name) => a
+                .derived
+                .iter()
+                .find(|d| &d.name == name)
+                .map(|d| format_f32(d.total, 2))
+                .unwrap_or_default(),
+            PlayerColumn::Rating
+            | PlayerColumn::Status
+            | PlayerColumn::Placement => "".to_string(),
+        }
+    }
+
+    /// per player average for a group of players. Ratios are not averaged,
+    /// since the totals already contain them.
+    pub fn average_value(
+        &self,
+        a: &AggregateCruciblePerformances,
+        count: u32,
+        rating: &str,
+    ) -> String {
+        let no_extended = Default::default();
+        let extended = a.extended.as_ref().unwrap_or(&no_extended);
+
+        let avg = |v: u32| format_f32(calculate_avg(v as f32, count), 2);
+
+        match self {
+            PlayerColumn::Kills => avg(a.kills),
+            PlayerColumn::Assists => avg(a.assists),
+            PlayerColumn::OpponentsDefeated => avg(a.opponents_defeated),
+            PlayerColumn::Deaths => avg(a.deaths),
+            PlayerColumn::Score => avg(a.score),
+            PlayerColumn::PrecisionKills => avg(extended.precision_kills),
+            PlayerColumn::Supers => avg(extended.weapon_kills_super),
+            PlayerColumn::Grenades => avg(extended.weapon_kills_grenade),
+            PlayerColumn::Melees => avg(extended.weapon_kills_melee),
+            PlayerColumn::Abilities => avg(extended.weapon_kills_ability),
+            PlayerColumn::Medals => avg(extended.all_medals_earned),
+            PlayerColumn::Motes
+            | PlayerColumn::MotesLost
+            | PlayerColumn::InvasionKills
+            | PlayerColumn::InvaderKills
+            | PlayerColumn::Blockers => {
+                avg(self.gambit_value(&extended.gambit))
+            }
+            PlayerColumn::Derived(name) => a
+                .derived
+                .iter()
+                .find(|d| &d.name == name)
+                .map(|d| format_f32(calculate_avg(d.total, count), 2))
+                .unwrap_or_default(),
+            PlayerColumn::Rating => rating.to_string(),
+            PlayerColumn::KillsDeathsRatio
+            | PlayerColumn::KillsDeathsAssists
+            | PlayerColumn::Efficiency
+            | PlayerColumn::Status
+            | PlayerColumn::Placement => "".to_string(),
+        }
+    }
+
+    //value of a gambit column. Other columns are 0.
+    fn gambit_value(&self, g: &GambitStats) -> u32 {
+        match self {
+            PlayerColumn::Motes => g.motes_deposited,
+            PlayerColumn::MotesLost => g.motes_lost,
+            PlayerColumn::InvasionKills => g.invasion_kills,
+            PlayerColumn::InvaderKills => g.invader_kills,
+            PlayerColumn::Blockers => g.blockers_sent,
+            _ => 0,
+        }
+    }
+}
+
+impl FromStr for PlayerColumn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.trim().to_lowercase()[..] {
+            "kills" => Ok(PlayerColumn::Kills),
+            "assists" => Ok(PlayerColumn::Assists),
+            "kills_assists" => Ok(PlayerColumn::OpponentsDefeated),
+            "deaths" => Ok(PlayerColumn::Deaths),
+            "kd" => Ok(PlayerColumn::KillsDeathsRatio),
+            "kda" => Ok(PlayerColumn::KillsDeathsAssists),
+            "eff" => Ok(PlayerColumn::Efficiency),
+            "score" => Ok(PlayerColumn::Score),
+            "precision" => Ok(PlayerColumn::PrecisionKills),
+            "super" => Ok(PlayerColumn::Supers),
+            "grenade" => Ok(PlayerColumn::Grenades),
+            "melee" => Ok(PlayerColumn::Melees),
+            "ability" => Ok(PlayerColumn::Abilities),
+            "medals" => Ok(PlayerColumn::Medals),
+            "rating" => Ok(PlayerColumn::Rating),
+            "status" => Ok(PlayerColumn::Status),
+            "placement" => Ok(PlayerColumn::Placement),
+            "motes" => Ok(PlayerColumn::Motes),
+            "motes_lost" => Ok(PlayerColumn::MotesLost),
+            "invasion_kills" => Ok(PlayerColumn::InvasionKills),
+            "invader_kills" => Ok(PlayerColumn::InvaderKills),
+            "blockers" => Ok(PlayerColumn::Blockers),
+            _ => derivedstats::registered_names()
+                .into_iter()
+                .find(|n| n.eq_ignore_ascii_case(s.trim()))
+                .map(PlayerColumn::Derived)
+                .ok_or_else(|| format!("Unknown column : {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for PlayerColumn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.header())
+    }
+}
+
+/// computes the width of each column so it fits its header and every value
+/// which will be displayed in it
+pub fn calculate_widths(
+    columns: &[PlayerColumn],
+    rows: &[Vec<String>],
+) -> Vec<usize> {
+    columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let values_w = rows
+                .iter()
+                .filter_map(|r| r.get(i))
+                .map(|v| v.chars().count())
+                .max()
+                .unwrap_or(0);
+
+            std::cmp::max(c.header().len(), values_w) + COLUMN_GAP
+        })
+        .collect()
+}
+
+/// right aligns each value in its column
+pub fn format_cells(values: &[String], widths: &[usize]) -> Vec<String> {
+    values
+        .iter()
+        .zip(widths.iter())
+        .map(|(v, w)| format!("{:>0w$}", v, w = w))
+        .collect()
+}
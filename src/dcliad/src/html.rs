@@ -0,0 +1,376 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use dcli::apiutils::RESOURCE_BASE_URL;
+use dcli::crucible::{
+    AggregateCruciblePerformances, CrucibleActivity, CruciblePlayerPerformance,
+    ExtendedCrucibleStats, MedalStat,
+};
+use dcli::enums::completionreason::CompletionReason;
+use dcli::error::Error;
+use dcli::html::{escape, table_header, table_row, STYLE};
+use dcli::links::Link;
+use dcli::utils::{
+    calculate_percent, f32_are_equal, format_f32, human_date_format,
+    human_duration,
+};
+
+use crate::{calculate_team_elo, generate_score, player_name, ELO_SCALE};
+
+//medal icon paths from the manifest are relative to bungie.net
+fn medal_icon_url(medal: &MedalStat) -> Option<String> {
+    medal.medal.icon_image_path.as_ref().map(|e| {
+        if e.starts_with('/') {
+            format!("{}{}", RESOURCE_BASE_URL, e)
+        } else {
+            e.to_string()
+        }
+    })
+}
+
+/// builds a self-contained html report for the activity, including team
+/// tables, per player weapon breakdowns, combined weapons and medals.
+pub fn generate_html(
+    data: &CrucibleActivity,
+    elo_hash: &HashMap<u64, f32>,
+    titles: &HashMap<u64, String>,
+    member_id: &str,
+    weapon_count: u32,
+    links: &[Link],
+    aliases: &HashMap<String, String>,
+) -> String {
+    let title = format!("{} on {}", data.details.mode, data.details.map_name);
+
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+    out.push_str("<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>{}</title>\n", escape(&title)));
+    out.push_str(&format!("<style>{}</style>\n", STYLE));
+    out.push_str("</head>\n<body>\n");
+
+    if let Some(e) = &data.details.map_image_url {
+        out.push_str(&format!(
+            "<img class=\"map\" src=\"{}\" alt=\"{}\">\n",
+            escape(e),
+            escape(&data.details.map_name)
+        ));
+    }
+
+    out.push_str(&format!("<h1>{}</h1>\n", escape(&title)));
+
+    let mut summary = vec![human_date_format(&data.details.period)];
+    if let Some(e) = data.get_member_performance(member_id) {
+        summary.push(format!(
+            "({})",
+            human_duration(e.stats.activity_duration_seconds)
+        ));
+        summary.push(format!("<strong>{}!</strong>", e.stats.standing));
+
+        if e.stats.completion_reason != CompletionReason::Unknown {
+            summary.push(format!("({})", e.stats.completion_reason));
+        }
+    }
+    summary.push(escape(&generate_score(data)));
+
+    out.push_str(&format!("<p class=\"summary\">{}</p>\n", summary.join(" ")));
+
+    let mut columns = vec![
+        "PLAYER", "KILLS", "ASTS", "K+A", "DEATHS", "K/D", "KD/A", "EFF",
+        "SUP", "GREN", "MEL", "MED", "RATING", "STATUS",
+    ];
+
+    if !titles.is_empty() {
+        columns.push("TITLE");
+    }
+
+    let no_extended = ExtendedCrucibleStats::default();
+    let mut all_performances: Vec<&CruciblePlayerPerformance> = Vec::new();
+    for v in data.teams.values() {
+        let team_elo = calculate_team_elo(&v.player_performances, elo_hash);
+        let team_elo_str = if f32_are_equal(team_elo, 0.0) {
+            "".to_string()
+        } else {
+            format!(" (avg rating {})", format_f32(team_elo, 0))
+        };
+
+        out.push_str(&format!(
+            "<h2>[{}] {} Team {}!{}</h2>\n",
+            v.score,
+            escape(&v.display_name),
+            v.standing,
+            team_elo_str
+        ));
+
+        if v.player_performances.is_empty() {
+            out.push_str("<p>No players</p>\n");
+            continue;
+        }
+
+        let mut player_performances: Vec<&CruciblePlayerPerformance> =
+            v.player_performances.iter().collect();
+        player_performances
+            .sort_by_key(|p| std::cmp::Reverse(p.stats.opponents_defeated));
+
+        out.push_str("<table>\n");
+        out.push_str(&table_header(&columns));
+        out.push_str("<tbody>\n");
+
+        for p in &player_performances {
+            let elo = *elo_hash.get(&p.player.calculate_hash()).unwrap_or(&0.0)
+                * ELO_SCALE;
+            let elo_str = if f32_are_equal(elo, 0.0) {
+                "".to_string()
+            } else {
+                format_f32(elo, 0)
+            };
+
+            let extended = p.stats.extended.as_ref().unwrap_or(&no_extended);
+
+            let mut row = vec![
+                escape(&player_name(&p.player, aliases)),
+                p.stats.kills.to_string(),
+                p.stats.assists.to_string(),
+                p.stats.opponents_defeated.to_string(),
+                p.stats.deaths.to_string(),
+                format_f32(p.stats.kills_deaths_ratio, 2),
+                format_f32(p.stats.kills_deaths_assists, 2),
+                format_f32(p.stats.efficiency, 2),
+                extended.weapon_kills_super.to_string(),
+                extended.weapon_kills_grenade.to_string(),
+                extended.weapon_kills_ability.to_string(),
+                extended.all_medals_earned.to_string(),
+                elo_str,
+                p.stats.generate_status(),
+            ];
+
+            if !titles.is_empty() {
+                row.push(match titles.get(&p.player.calculate_hash()) {
+                    Some(e) => escape(e),
+                    None => "".to_string(),
+                });
+            }
+
+            out.push_str(&table_row(&row, None));
+            all_performances.push(p);
+        }
+
+        let aggregate = AggregateCruciblePerformances::with_performances(
+            &player_performances,
+        );
+        let agg_extended = aggregate.extended.unwrap_or_default();
+
+        let mut row = vec![
+            "TOTAL".to_string(),
+            aggregate.kills.to_string(),
+            aggregate.assists.to_string(),
+            aggregate.opponents_defeated.to_string(),
+            aggregate.deaths.to_string(),
+            format_f32(aggregate.kills_deaths_ratio, 2),
+            format_f32(aggregate.kills_deaths_assists, 2),
+            format_f32(aggregate.efficiency, 2),
+            agg_extended.weapon_kills_super.to_string(),
+            agg_extended.weapon_kills_grenade.to_string(),
+            agg_extended.weapon_kills_melee.to_string(),
+            agg_extended.all_medals_earned.to_string(),
+            "".to_string(),
+            "".to_string(),
+        ];
+
+        if !titles.is_empty() {
+            row.push("".to_string());
+        }
+
+        out.push_str(&table_row(&row, Some("total")));
+        out.push_str("</tbody>\n</table>\n");
+
+        //weapon breakdown for each player on the team
+        out.push_str("<table>\n");
+        out.push_str(&table_header(&[
+            "PLAYER", "CLASS", "LIGHT", "WEAPON", "KILLS", "PREC", "% PREC",
+            "TYPE",
+        ]));
+        out.push_str("<tbody>\n");
+
+        for p in &player_performances {
+            let extended = p.stats.extended.as_ref().unwrap_or(&no_extended);
+
+            let mut weapons = extended.weapons.clone();
+            weapons.sort_by_key(|b| std::cmp::Reverse(b.kills));
+
+            let player = vec![
+                escape(&player_name(&p.player, aliases)),
+                format!("{}", p.player.class_type),
+                p.player.light_level.to_string(),
+            ];
+
+            if weapons.is_empty() {
+                let mut row = player.clone();
+                row.push("<span class=\"note\">No weapon kills</span>".into());
+                row.extend(vec!["".to_string(); 4]);
+                out.push_str(&table_row(&row, None));
+                continue;
+            }
+
+            for (i, w) in weapons.iter().enumerate() {
+                //only show the player details on their first weapon
+                let mut row = if i == 0 {
+                    player.clone()
+                } else {
+                    vec!["".to_string(); 3]
+                };
+
+                row.extend(vec![
+                    escape(&w.weapon.name),
+                    w.kills.to_string(),
+                    w.precision_kills.to_string(),
+                    //per player percent is stored as a fraction
+                    format!(
+                        "{}%",
+                        format_f32(w.precision_kills_percent * 100.0, 2)
+                    ),
+                    escape(&format!("{}", w.weapon.item_sub_type)),
+                ]);
+                out.push_str(&table_row(&row, None));
+            }
+        }
+        out.push_str("</tbody>\n</table>\n");
+    }
+
+    let aggregate =
+        AggregateCruciblePerformances::with_performances(&all_performances);
+    let agg_extended = aggregate.extended.unwrap_or_default();
+    let weapons = &agg_extended.weapons;
+    let max_weps = std::cmp::min(weapon_count as usize, weapons.len());
+
+    out.push_str("<h2>Weapons</h2>\n");
+
+    if weapons.is_empty() {
+        out.push_str("<p>No weapon kills</p>\n");
+    } else {
+        out.push_str("<table>\n");
+        out.push_str(&table_header(&[
+            "WEAPON", "KILLS", "% TOTAL", "PREC", "% PREC", "TYPE",
+        ]));
+        out.push_str("<tbody>\n");
+
+        for w in &weapons[..max_weps] {
+            out.push_str(&table_row(
+                &[
+                    escape(&w.weapon.name),
+                    w.kills.to_string(),
+                    format!(
+                        "{}%",
+                        format_f32(
+                            calculate_percent(w.kills, aggregate.kills),
+                            2
+                        )
+                    ),
+                    w.precision_kills.to_string(),
+                    format!("{}%", format_f32(w.precision_kills_percent, 2)),
+                    escape(&format!("{}", w.weapon.item_sub_type)),
+                ],
+                None,
+            ));
+        }
+        out.push_str("</tbody>\n</table>\n");
+    }
+
+    let mut medals = agg_extended.medals.clone();
+    medals.sort_by(|a, b| {
+        b.medal
+            .tier
+            .get_order()
+            .cmp(&a.medal.tier.get_order())
+            .then(b.count.cmp(&a.count))
+    });
+
+    out.push_str("<h2>Medals</h2>\n");
+
+    if medals.is_empty() {
+        out.push_str("<p>No medals</p>\n");
+    } else {
+        out.push_str("<div>\n");
+        for m in &medals {
+            let icon = match medal_icon_url(m) {
+                Some(e) => format!("<img src=\"{}\" alt=\"\"> ", escape(&e)),
+                None => "".to_string(),
+            };
+
+            out.push_str(&format!(
+                "<span class=\"medal\" title=\"{}\">{}{} x{}</span>\n",
+                escape(&m.medal.description),
+                icon,
+                escape(&m.medal.name),
+                m.count
+            ));
+        }
+        out.push_str("</div>\n");
+    }
+
+    if !links.is_empty() {
+        out.push_str("<h2>Links</h2>\n<ul>\n");
+        for l in links {
+            out.push_str(&format!(
+                "<li><a href=\"{}\">{}</a></li>\n",
+                escape(&l.url),
+                escape(&format!("{}", l.site))
+            ));
+        }
+        out.push_str("</ul>\n");
+    }
+
+    out.push_str(
+        "<p class=\"note\">STATUS : L - Joined late, E - Left early</p>\n",
+    );
+    out.push_str("</body>\n</html>\n");
+
+    out
+}
+
+/// writes the html report for the activity to the specified path
+#[allow(clippy::too_many_arguments)]
+pub fn write_html(
+    path: &Path,
+    data: &CrucibleActivity,
+    elo_hash: &HashMap<u64, f32>,
+    titles: &HashMap<u64, String>,
+    member_id: &str,
+    weapon_count: u32,
+    links: &[Link],
+    aliases: &HashMap<String, String>,
+) -> Result<(), Error> {
+    let html = generate_html(
+        data,
+        elo_hash,
+        titles,
+        member_id,
+        weapon_count,
+        links,
+        aliases,
+    );
+    std::fs::write(path, html)?;
+
+    Ok(())
+}
@@ -20,19 +20,40 @@
 * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 */
 
+mod columns;
+mod html;
+mod markdown;
+mod scorecard;
+mod table;
+mod tsv;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::{collections::HashMap, path::PathBuf};
 
+use columns::{profile_columns, PlayerColumn};
+use dcli::accountcontext::{AccountContext, AccountContextCache};
+use dcli::cancellation::CancellationToken;
+use dcli::datadir;
+use dcli::demo;
+use dcli::enums::language::Language;
+use dcli::enums::platform::Platform;
+use dcli::enums::statprofile::StatProfile;
+use dcli::links::{Link, LinkBuilder, LinkSite};
+use dcli::output::Output;
+use dcli::ratelimit::{self, parse_max_rps};
+use dcli::template;
+use dcli::timing::{self, TimingCategory};
 use dcli::{
     apiinterface::ApiInterface,
-    crucible::{
-        AggregateCruciblePerformances, CrucibleActivity,
-        CruciblePlayerPerformance, Player,
-    },
-    enums::completionreason::CompletionReason,
+    crucible::{CrucibleActivity, CruciblePlayerPerformance, Player},
     utils::{calculate_avg, f32_are_equal},
 };
-use dcli::{enums::platform::Platform, utils::truncate_ascii_string};
+use html::write_html;
+use markdown::print_markdown;
+use scorecard::write_scorecard;
+use table::write_default;
+use tsv::print_tsv;
 
 use dcli::enums::mode::Mode;
 use dcli::manifestinterface::ManifestInterface;
@@ -43,12 +64,12 @@ use dcli::error::Error;
 use dcli::activitystoreinterface::ActivityStoreInterface;
 
 use dcli::utils::{
-    determine_data_dir, format_f32, human_date_format, human_duration,
-    repeat_str,
+    determine_data_dir, fit_string, format_f32, repeat_str, TextColors,
 };
 
 use dcli::utils::EXIT_FAILURE;
-use dcli::utils::{print_error, print_verbose};
+use dcli::utils::{print_error, print_message, print_verbose};
+use serde_derive::Serialize;
 use structopt::StructOpt;
 
 const ELO_SCALE: f32 = 10.0;
@@ -56,7 +77,7 @@ const ELO_SCALE: f32 = 10.0;
 fn parse_and_validate_mode(src: &str) -> Result<Mode, String> {
     let mode = Mode::from_str(src)?;
 
-    if !mode.is_crucible() {
+    if !mode.is_crucible() && !mode.is_gambit() {
         return Err(format!("Unsupported mode specified : {}", src));
     }
 
@@ -97,382 +118,312 @@ async fn get_combat_ratings(
                 }
             }
 
-            match e
-                .retrieve_combat_ratings(&player_refs, &data.details.mode)
+            e.retrieve_combat_ratings(&player_refs, &data.details.mode)
                 .await
-            {
-                Ok(e) => e,
-                Err(_e) => HashMap::new(),
-            }
+                .unwrap_or_default()
         }
         Err(_e) => HashMap::new(),
     };
     elo_hash
 }
 
-fn print_default(
+//resolves the equipped title for each player in the activity
+async fn get_titles(
     data: &CrucibleActivity,
-    elo_hash: &HashMap<u64, f32>,
-    member_id: &str,
-    details: bool,
-    weapon_count: u32,
+    manifest: &mut ManifestInterface,
     verbose: bool,
-) {
-    let col_w = 8;
-    let name_col_w = 24;
+) -> HashMap<u64, String> {
+    let mut players: Vec<&Player> = Vec::new();
+    for t in data.teams.values() {
+        for p in &t.player_performances {
+            players.push(&p.player);
+        }
+    }
 
-    let mut activity_duration = "".to_string();
-    let mut completion_reason = "".to_string();
-    let mut standing_str = "".to_string();
-
-    if let Some(e) = data.get_member_performance(member_id) {
-        completion_reason =
-            if e.stats.completion_reason == CompletionReason::Unknown {
-                "".to_string()
-            } else {
-                format!("({})", e.stats.completion_reason)
-            };
-
-        activity_duration =
-            format!("({})", human_duration(e.stats.activity_duration_seconds));
-        standing_str = format!("{}!", e.stats.standing);
+    let records = match ApiInterface::new(verbose) {
+        Ok(e) => e.retrieve_title_records(&players).await,
+        Err(_e) => return HashMap::new(),
     };
 
-    let team_title_border = repeat_str("-", name_col_w + col_w);
-    let activity_title_border = repeat_str("=", name_col_w + col_w + col_w);
-
-    println!();
-    println!("ACTIVITY");
-    println!("{}", activity_title_border);
-
-    println!(
-        "{} on {} :: {} {}",
-        data.details.mode,
-        data.details.map_name,
-        human_date_format(&data.details.period),
-        activity_duration
-    );
-
-    if verbose {
-        println!("Activity ID : {}", data.details.id);
+    let mut titles: HashMap<u64, String> = HashMap::new();
+    for (player_hash, (record_hash, gender)) in records {
+        if let Ok(Some(e)) = manifest.get_title(record_hash, &gender).await {
+            titles.insert(player_hash, e);
+        }
     }
 
-    println!("{}", standing_str);
-    println!("{} {}", generate_score(data), completion_reason);
-
-    println!();
-
-    let header = format!("{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
-    "PLAYER",
-    "KILLS",
-    "ASTS",
-    "K+A",
-    "DEATHS",
-    "K/D",
-    "KD/A",
-    "EFF",
-    "SUP",
-    "GREN",
-    "MEL",
-    "MED",
-    "RATING",
-    "STATUS",
-    col_w=col_w,
-    name_col_w = name_col_w,
-    );
+    titles
+}
 
-    let table_width = header.chars().count();
-    let header_border = repeat_str("=", table_width);
-    let entry_border = repeat_str(".", table_width);
-    let footer_border = repeat_str("-", table_width);
+//saves ratings by character id for the activity. Errors are not fatal, since
+//the ratings are only used for historical stats
+async fn save_combat_ratings(
+    store: &mut ActivityStoreInterface,
+    data: &CrucibleActivity,
+    elo_hash: &HashMap<u64, f32>,
+    verbose: bool,
+) {
+    let mut ratings: HashMap<String, f32> = HashMap::new();
+    for t in data.teams.values() {
+        for p in &t.player_performances {
+            let elo = *elo_hash.get(&p.player.calculate_hash()).unwrap_or(&0.0);
+            if !f32_are_equal(elo, 0.0) {
+                ratings
+                    .insert(p.player.character_id.to_string(), elo * ELO_SCALE);
+            }
+        }
+    }
 
-    let mut all_performances: Vec<&CruciblePlayerPerformance> = Vec::new();
-    let mut elo_total_count = 0;
-    let mut elo_total_total = 0.0;
-    for v in data.teams.values() {
-        let mut elo_team_count = 0;
-        let mut elo_team_total = 0.0;
+    if let Err(e) = store.save_combat_ratings(data.details.id, &ratings).await {
+        print_verbose(
+            &format!("Could not save combat ratings : {}", e),
+            verbose,
+        );
+    }
+}
 
-        println!("[{}] {} Team {}!", v.score, v.display_name, v.standing);
-        println!("{}", team_title_border);
-        println!("{}", header);
-        println!("{}", header_border);
+//average rating for players on a team who have a rating. 0.0 if no players
+//have a rating
+fn calculate_team_elo(
+    performances: &[CruciblePlayerPerformance],
+    elo_hash: &HashMap<u64, f32>,
+) -> f32 {
+    let mut count = 0;
+    let mut total = 0.0;
+    for p in performances {
+        let elo = *elo_hash.get(&p.player.calculate_hash()).unwrap_or(&0.0)
+            * ELO_SCALE;
+
+        if !f32_are_equal(elo, 0.0) {
+            count += 1;
+            total += elo;
+        }
+    }
 
-        let mut first_performance = true;
+    calculate_avg(total, count)
+}
 
-        let mut player_performances = v.player_performances.clone();
-        player_performances.sort_by(|a, b| {
-            b.stats.opponents_defeated.cmp(&a.stats.opponents_defeated)
-        });
+//i.e. "Rating delta : Alpha 112 vs Bravo 98 (+14)". Only generated for two team
+//activities where both teams have ratings
+fn generate_team_elo_delta(team_elos: &[(String, f32)]) -> Option<String> {
+    if team_elos.len() != 2 {
+        return None;
+    }
 
-        for p in &player_performances {
-            let elo = *elo_hash.get(&p.player.calculate_hash()).unwrap_or(&0.0)
-                * ELO_SCALE;
+    let (a_name, a_elo) = &team_elos[0];
+    let (b_name, b_elo) = &team_elos[1];
 
-            let mut elo_str = "".to_string();
-            if !f32_are_equal(elo, 0.0) {
-                elo_team_count += 1;
-                elo_team_total += elo;
+    if f32_are_equal(*a_elo, 0.0) || f32_are_equal(*b_elo, 0.0) {
+        return None;
+    }
 
-                elo_total_count += 1;
-                elo_total_total += elo;
+    let delta = a_elo - b_elo;
+    let sign = if delta >= 0.0 { "+" } else { "" };
+
+    Some(format!(
+        "Rating delta : {} {} vs {} {} ({}{})",
+        a_name,
+        format_f32(*a_elo, 0),
+        b_name,
+        format_f32(*b_elo, 0),
+        sign,
+        format_f32(delta, 0)
+    ))
+}
 
-                elo_str = format_f32(elo, 0);
-            }
+//if the player has a local alias, it is displayed first, since aliases are
+//used to identify players whose gamertags change
+fn player_name(player: &Player, aliases: &HashMap<String, String>) -> String {
+    match aliases.get(&player.member_id) {
+        Some(e) => format!("{} ({})", e, player.display_name),
+        None => player.display_name.to_string(),
+    }
+}
 
-            let extended = p.stats.extended.as_ref().unwrap();
-            println!("{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
-                truncate_ascii_string(&p.player.display_name, name_col_w),
-                p.stats.kills.to_string(),
-                p.stats.assists.to_string(),
-                p.stats.opponents_defeated.to_string(),
-                p.stats.deaths.to_string(),
-                format_f32(p.stats.kills_deaths_ratio, 2),
-                format_f32(p.stats.kills_deaths_assists, 2),
-                format_f32(p.stats.efficiency, 2),
-                extended.weapon_kills_super.to_string(),
-                extended.weapon_kills_grenade.to_string(),
-                extended.weapon_kills_ability.to_string(),
-                extended.all_medals_earned.to_string(),
-                elo_str,
-                p.stats.generate_status(),
-                col_w=col_w,
-                name_col_w = name_col_w,
-            );
+//retrieves account context for all players in the activity, using cached
+//data where available
+async fn get_account_contexts(
+    data: &CrucibleActivity,
+    data_dir: &Path,
+    verbose: bool,
+) -> Vec<(Player, Option<AccountContext>)> {
+    let mut cache = AccountContextCache::load(data_dir);
 
-            //todo: what if they dont have weapon kills (test)
-            if details && !extended.weapons.is_empty() {
-                println!("{}", entry_border);
-
-                let mut weapons = extended.weapons.clone();
-                weapons.sort_by(|a, b| b.kills.cmp(&a.kills));
-
-                let mut min_index = 2;
-                if first_performance {
-                    println!(
-                        //"{:>0w_name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w2$}",
-                        "{:<0col_w$}{:>0w_name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w2$}",
-                        format!("{}", p.player.class_type),
-                        "NAME",
-                        "KILLS",
-                        "PREC",
-                        "%",
-                        "TYPE",
-                        w_name_col_w = col_w + col_w + name_col_w,
-                        col_w = col_w,
-                        col_w2 = col_w * 3,
-                    );
-                    first_performance = false;
-                    min_index = 1;
-                }
+    let mut players: Vec<Player> = Vec::new();
+    for t in data.teams.values() {
+        for p in &t.player_performances {
+            players.push(p.player.clone());
+        }
+    }
 
-                for i in 0..std::cmp::max(min_index, weapons.len()) {
-                    let modifier = 2 - min_index;
-                    let meta = match i + modifier {
-                        0 => format!("{}", p.player.class_type),
-                        1 => p.player.light_level.to_string(),
-                        _ => "".to_string(),
-                    };
-
-                    let mut weapon_name = "".to_string();
-                    let mut weapon_kills = "".to_string();
-                    let mut precision_kills = "".to_string();
-                    let mut precision_kills_percent = "".to_string();
-                    let mut weapon_type = "".to_string();
-
-                    if i < weapons.len() {
-                        let w = &weapons[i];
-                        weapon_name = w.weapon.name.to_string();
-                        weapon_kills = w.kills.to_string();
-                        precision_kills = w.precision_kills.to_string();
-                        precision_kills_percent =
-                            format_f32(w.precision_kills_percent * 100.0, 0)
-                                .to_string();
-                        weapon_type = format!("{}", w.weapon.item_sub_type);
-                    }
-
-                    println!(
-                        "{:<0col_w$}{:>0w_name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w2$}",
-                        meta,
-                        weapon_name,
-                        weapon_kills,
-                        precision_kills,
-                        precision_kills_percent,
-                        weapon_type,
-                        w_name_col_w = col_w + col_w + name_col_w,
-                        col_w = col_w,
-                        col_w2 = col_w * 3,
-                    );
-                }
-                println!();
+    let api = ApiInterface::new(verbose).ok();
+
+    let uncached: Vec<&Player> = players
+        .iter()
+        .filter(|p| cache.get(&p.member_id).is_none())
+        .collect();
+
+    if let Some(api) = &api {
+        for r in api.retrieve_account_contexts(&uncached).await {
+            match r {
+                Ok(e) => cache.insert(e),
+                Err(e) => print_verbose(
+                    &format!("Could not retrieve account context : {}", e),
+                    verbose,
+                ),
             }
         }
-        println!("{}", footer_border);
+    }
 
-        let mut cpp: Vec<&CruciblePlayerPerformance> = Vec::new();
+    if let Err(e) = cache.save() {
+        print_verbose(
+            &format!("Could not save account context cache : {}", e),
+            verbose,
+        );
+    }
 
-        for p in &v.player_performances {
-            cpp.push(p);
-            all_performances.push(p);
-        }
+    players
+        .into_iter()
+        .map(|p| {
+            let c = cache.get(&p.member_id).cloned();
+            (p, c)
+        })
+        .collect()
+}
 
-        let aggregate = AggregateCruciblePerformances::with_performances(&cpp);
+#[derive(Serialize)]
+struct ActivityJson<'a> {
+    activity: &'a CrucibleActivity,
 
-        let agg_extended = aggregate.extended.as_ref().unwrap();
-        let agg_supers = agg_extended.weapon_kills_super;
-        let agg_grenades = agg_extended.weapon_kills_grenade;
-        let agg_melees = agg_extended.weapon_kills_melee;
+    //keyed by member id
+    combat_ratings: HashMap<String, f32>,
+    titles: HashMap<String, String>,
+    links: &'a [Link],
+}
 
-        let team_elo = calculate_avg(elo_team_total, elo_team_count);
-        let team_elo_str = if f32_are_equal(team_elo, 0.0) {
-            "".to_string()
-        } else {
-            format_f32(team_elo, 0)
-        };
+//activity data used for json and template output
+fn build_activity_json<'a>(
+    data: &'a CrucibleActivity,
+    elo_hash: &HashMap<u64, f32>,
+    titles: &HashMap<u64, String>,
+    links: &'a [Link],
+) -> ActivityJson<'a> {
+    let mut combat_ratings: HashMap<String, f32> = HashMap::new();
+    let mut member_titles: HashMap<String, String> = HashMap::new();
 
-        println!("{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
-            "TOTAL",
-            aggregate.kills.to_string(),
-            aggregate.assists.to_string(),
-            aggregate.opponents_defeated.to_string(),
-            aggregate.deaths.to_string(),
-            format_f32(aggregate.kills_deaths_ratio, 2),
-            format_f32(aggregate.kills_deaths_assists, 2),
-            format_f32(aggregate.efficiency, 2),
-            agg_supers.to_string(),
-            agg_grenades.to_string(),
-            agg_melees.to_string(),
-            aggregate.extended.as_ref().unwrap().all_medals_earned.to_string(),
-            "",
-            "",
-            col_w=col_w,
-            name_col_w = name_col_w,
-        );
+    for t in data.teams.values() {
+        for p in &t.player_performances {
+            let hash = p.player.calculate_hash();
 
-        println!("{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
-            "AVG",
-            format_f32(aggregate.kills as f32 / player_performances.len() as f32, 2),
-            format_f32(aggregate.assists as f32 / player_performances.len() as f32,2),
-            format_f32(aggregate.opponents_defeated as f32 / player_performances.len() as f32,2),
-            format_f32(aggregate.deaths as f32 / player_performances.len() as f32,2),
-            "",
-            "",
-            "",
-            format_f32(agg_supers as f32 / player_performances.len() as f32,2),
-            format_f32(agg_grenades as f32 / player_performances.len() as f32,2),
-            format_f32(agg_melees as f32 / player_performances.len() as f32,2),
-            format_f32(aggregate.extended.as_ref().unwrap().all_medals_earned as f32 / player_performances.len() as f32,2),
-            team_elo_str,
-            "", //MAKE THIS REASON FOR COMPLETEION
-            col_w=col_w,
-            name_col_w = name_col_w,
-        );
+            if let Some(e) = elo_hash.get(&hash) {
+                combat_ratings
+                    .insert(p.player.member_id.to_string(), e * ELO_SCALE);
+            }
 
-        //println!("{}", header_border);
-        //println!("{}", header);
-        println!();
+            if let Some(e) = titles.get(&hash) {
+                member_titles
+                    .insert(p.player.member_id.to_string(), e.to_string());
+            }
+        }
     }
 
-    println!("Combined");
-    println!("{}", team_title_border);
-
-    let aggregate =
-        AggregateCruciblePerformances::with_performances(&all_performances);
+    ActivityJson {
+        activity: data,
+        combat_ratings,
+        titles: member_titles,
+        links,
+    }
+}
 
-    let agg_extended = aggregate.extended.as_ref().unwrap();
-    let agg_supers = agg_extended.weapon_kills_super;
-    let agg_grenades = agg_extended.weapon_kills_grenade;
-    let agg_melees = agg_extended.weapon_kills_melee;
+fn print_json(activity: &ActivityJson) {
+    match serde_json::to_string_pretty(activity) {
+        Ok(e) => println!("{}", e),
+        Err(e) => {
+            print_error("Error serializing activity to json.", Error::from(e));
+            std::process::exit(EXIT_FAILURE);
+        }
+    }
+}
 
-    println!("{}", header);
-    println!("{}", header_border);
-    println!("{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
-        "TOTAL",
-        aggregate.kills.to_string(),
-        aggregate.assists.to_string(),
-        aggregate.opponents_defeated.to_string(),
-        aggregate.deaths.to_string(),
-        format_f32(aggregate.kills_deaths_ratio, 2),
-        format_f32(aggregate.kills_deaths_assists, 2),
-        format_f32(aggregate.efficiency, 2),
-        agg_supers.to_string(),
-        agg_grenades.to_string(),
-        agg_melees.to_string(),
-        aggregate.extended.as_ref().unwrap().all_medals_earned.to_string(),
-        "",
-        "", //MAKE THIS REASON FOR COMPLETEION
-        col_w=col_w,
-        name_col_w = name_col_w,
-    );
+fn print_raw_pgcr(pgcr: &serde_json::Value) {
+    match serde_json::to_string_pretty(pgcr) {
+        Ok(e) => println!("{}", e),
+        Err(e) => {
+            print_error("Error serializing report to json.", Error::from(e));
+            std::process::exit(EXIT_FAILURE);
+        }
+    }
+}
 
-    let total_elo = calculate_avg(elo_total_total, elo_total_count);
-    let total_elo_str = if f32_are_equal(total_elo, 0.0) {
-        "".to_string()
-    } else {
-        format_f32(total_elo, 0)
-    };
+fn print_template(template: &str, activity: &ActivityJson) {
+    match template::render_template(template, activity) {
+        Ok(e) => println!("{}", e),
+        Err(e) => {
+            print_error("Error rendering template.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    }
+}
 
-    println!("{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
-    "AVG",
-    format_f32(aggregate.kills as f32 / all_performances.len() as f32, 2),
-    format_f32(aggregate.assists as f32 / all_performances.len() as f32,2),
-    format_f32(aggregate.opponents_defeated as f32 / all_performances.len() as f32,2),
-    format_f32(aggregate.deaths as f32 / all_performances.len() as f32,2),
-    "",
-    "",
-    "",
-    format_f32(agg_supers as f32 / all_performances.len() as f32,2),
-    format_f32(agg_grenades as f32 / all_performances.len() as f32,2),
-    format_f32(agg_melees as f32 / all_performances.len() as f32,2),
-    format_f32(aggregate.extended.as_ref().unwrap().all_medals_earned as f32 / all_performances.len() as f32,2),
-    total_elo_str,
-    "", //MAKE THIS REASON FOR COMPLETEION
-    col_w=col_w,
-    name_col_w = name_col_w,
-);
+fn print_links(links: &[Link]) {
+    let col_w = 18;
 
+    println!("LINKS");
+    println!("{}", repeat_str("=", col_w * 2));
+    for l in links {
+        println!("{:<0col_w$}{}", format!("{}", l.site), l.url, col_w = col_w);
+    }
     println!();
+}
 
-    let wep_col = name_col_w + col_w;
-    let wep_header_str = format!(
-        "{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0name_col_w$}",
-        "WEAPON",
-        "KILLS",
-        "% TOTAL",
-        "PREC",
-        "% PREC",
-        "TYPE",
-        col_w = col_w,
-        name_col_w = wep_col,
-    );
+fn print_account_contexts(
+    contexts: &[(Player, Option<AccountContext>)],
+    aliases: &HashMap<String, String>,
+) {
+    let name_col_w = 24;
+    let col_w = 16;
 
-    let wep_divider = repeat_str(&"=", wep_header_str.chars().count());
-    println!("{}", wep_header_str);
-    println!("{}", wep_divider);
+    println!("ACCOUNT CONTEXT");
+    println!("==================");
 
-    let weapons = &aggregate.extended.as_ref().unwrap().weapons;
-    let max_weps = std::cmp::min(weapon_count as usize, weapons.len());
+    let header = format!(
+        "{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+        "PLAYER",
+        "ACCOUNT SINCE",
+        "PVP MATCHES",
+        "PVP HOURS",
+        name_col_w = name_col_w,
+        col_w = col_w,
+    );
+    println!("{}", header);
+    println!("{}", repeat_str("=", header.chars().count()));
+
+    for (player, context) in contexts {
+        let (since, matches, hours) = match context {
+            Some(e) => (
+                e.get_first_access()
+                    .map(|d| format!("{}", d.format("%b %Y")))
+                    .unwrap_or_else(|| "Unknown".to_string()),
+                e.crucible_matches.to_string(),
+                (e.crucible_seconds_played / 3600).to_string(),
+            ),
+            None => ("".to_string(), "".to_string(), "".to_string()),
+        };
 
-    let wep_col = name_col_w + col_w;
-    for w in &weapons[..max_weps] {
         println!(
-            "{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0name_col_w$}",
-            w.weapon.name,
-            w.kills.to_string(),
-            format!(
-                "{}%",
-                format_f32((w.kills as f32 / aggregate.kills as f32) * 100.0, 2)
-            ),
-            w.precision_kills.to_string(),
-            format!("{}%", format_f32(w.precision_kills_percent, 2)),
-            format!("{}", w.weapon.item_sub_type),
+            "{}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+            fit_string(&player_name(player, aliases), name_col_w),
+            since,
+            matches,
+            hours,
             col_w = col_w,
-            name_col_w = wep_col,
         );
     }
 
     println!();
-    println!("STATUS : L - Joined late, E - Left early");
+    println!(
+        "ACCOUNT SINCE is when the Bungie.net account was first accessed."
+    );
     println!();
 }
 
@@ -499,14 +450,25 @@ struct Opt {
     /// Destiny 2 API member id
     ///
     /// This is not the user name, but the member id retrieved from the Destiny API.
-    #[structopt(short = "m", long = "member-id", required = true)]
-    member_id: String,
+    /// Can also be a player alias, or the display name of a player in synced
+    /// activities (add the platform, i.e. name:steam, if more than one player
+    /// has the name).
+    #[structopt(
+        short = "m",
+        long = "member-id",
+        required_unless_one = &["demo", "print-paths"]
+    )]
+    member_id: Option<String>,
 
     /// Platform for specified id
     ///
     /// Valid values are: xbox, playstation, stadia or steam.
-    #[structopt(short = "p", long = "platform", required = true)]
-    platform: Platform,
+    #[structopt(
+        short = "p",
+        long = "platform",
+        required_unless_one = &["demo", "print-paths"]
+    )]
+    platform: Option<Platform>,
 
     /// Activity mode from which to return last activity
     ///
@@ -518,6 +480,8 @@ struct Opt {
     /// countdown, all_doubles, doubles, private_clash, private_control,
     /// private_survival, private_rumble, showdown, lockdown,
     /// scorched, scorched_team, breakthrough, clash_quickplay, trials_of_the_nine
+    ///
+    /// Gambit values are all_gambit, gambit and gambit_prime.
     #[structopt(long = "mode", short = "M", 
         parse(try_from_str=parse_and_validate_mode), default_value = "all_pvp")]
     mode: Mode,
@@ -534,6 +498,30 @@ struct Opt {
     #[structopt(short = "v", long = "verbose")]
     verbose: bool,
 
+    /// Color output
+    ///
+    /// Teams and players are colored by team standing, the member is
+    /// highlighted, and K/D values above and below 1.0 are colored green and
+    /// red. Ignored if the NO_COLOR environment variable is set.
+    #[structopt(long = "color")]
+    color: bool,
+
+    /// Print how long was spent in API calls, SQL queries and formatting
+    ///
+    /// Timings are printed to stderr when the command finishes, and can help
+    /// diagnose slow commands.
+    #[structopt(long = "timing")]
+    timing: bool,
+
+    /// Maximum number of API requests to make per second
+    ///
+    /// Caps the rate of requests to the Destiny API when syncing and
+    /// retrieving combat ratings, for when the API key or IP rate budget is
+    /// shared with other tools. Can also be set with the DCLI_MAX_RPS
+    /// environment variable. By default requests are not capped.
+    #[structopt(long = "max-rps", parse(try_from_str = parse_max_rps))]
+    max_rps: Option<f64>,
+
     /// Don't sync activities
     ///
     /// If flag is set, activities will not be retrieved before displaying stats.
@@ -541,10 +529,30 @@ struct Opt {
     #[structopt(short = "N", long = "no-sync")]
     no_sync: bool,
 
+    /// Display details from bundled sample data
+    ///
+    /// Uses a small sample activity store and manifest included with dcli,
+    /// so you can see the output without an API key, member id or syncing
+    /// any activities. --member-id, --platform, --class and --data-dir are
+    /// ignored, activities are not synced, and combat ratings, titles and
+    /// account context (which require the API) are not displayed.
+    #[structopt(long = "demo")]
+    demo: bool,
+
+    /// Print the raw post game carnage report for the activity
+    ///
+    /// Retrieves the original PGCR json for the selected activity from the
+    /// Destiny 2 API and prints it, including fields which dcli does not
+    /// store or display. If the json was stored when the activity was synced
+    /// (dclias --store-raw-pgcr), the stored json is used instead. Useful for
+    /// debugging. Other output options are ignored.
+    #[structopt(long = "raw", conflicts_with = "demo")]
+    raw: bool,
+
     /// Display extended activity details
     ///
     /// If flag is set, additional information will be displayed, including per
-    /// user weapon stats.
+    /// user weapon stats and the top weapons used by each team.
     #[structopt(short = "d", long = "details")]
     details: bool,
 
@@ -552,6 +560,112 @@ struct Opt {
     #[structopt(long = "weapon-count", short = "w", default_value = "5")]
     weapon_count: u32,
 
+    /// Columns to display in the player table
+    ///
+    /// Comma seperated list. Valid values are kills, assists, kills_assists,
+    /// deaths, kd, kda, eff, score, precision, super, grenade, melee,
+    /// ability, medals, rating, status and placement. Gambit columns are
+    /// motes, motes_lost, invasion_kills, invader_kills and blockers. Derived
+    /// stats registered with dcli::derivedstats can also be used, by name.
+    /// Columns are sized to fit their values. Only applies to default output.
+    ///
+    /// If not specified, columns are chosen based on the mode of the
+    /// activity. Round based modes (such as Trials of Osiris and Elimination)
+    /// include precision kills, objective modes (such as Control) include
+    /// score, Rumble includes each player's placement and Gambit includes
+    /// motes, invasions and blockers. Other modes use
+    /// the default columns: kills, assists, kills_assists, deaths, kd, kda,
+    /// eff, super, grenade, melee, medals, rating and status.
+    #[structopt(long = "columns", use_delimiter = true)]
+    columns: Option<Vec<PlayerColumn>>,
+
+    /// Display the equipped title for each player
+    ///
+    /// Titles are retrieved from each player's profile, which requires an
+    /// additional API call per player.
+    #[structopt(long = "titles")]
+    titles: bool,
+
+    /// Display links to the activity on external sites
+    ///
+    /// Links are always included in json output.
+    #[structopt(long = "links")]
+    links: bool,
+
+    /// Sites to generate links for when --links is set
+    ///
+    /// Comma seperated list. Valid values are bungie, crucible_report and
+    /// destiny_tracker.
+    #[structopt(
+        long = "link-sites",
+        use_delimiter = true,
+        default_value = "bungie,crucible_report,destiny_tracker"
+    )]
+    link_sites: Vec<LinkSite>,
+
+    /// Format for command output
+    ///
+    /// Valid values are default (Default), json, markdown and tsv.
+    ///
+    /// json outputs the full activity details (teams, player performances,
+    /// weapons and medals) along with player combat ratings, titles and links.
+    ///
+    /// markdown outputs the team, player and weapon tables as GitHub flavored
+    /// markdown, which can be pasted into Discord or forums.
+    ///
+    /// tsv outputs the activity details as name / value pairs, followed by
+    /// an empty line and then a row for each player with the values : team,
+    /// team standing, member id, name, platform, character id, class, light
+    /// level, kills, assists, kills + assists, deaths, kd, kda, efficiency,
+    /// score, precision, super, grenade, melee and ability kills, medals,
+    /// rating, seconds played and completed.
+    #[structopt(
+        short = "O",
+        long = "output-format",
+        default_value = "default"
+    )]
+    output: Output,
+
+    /// Only write structured data to stdout
+    ///
+    /// All other messages (i.e. errors, or no activities found) are written
+    /// to stderr. Uses tsv output unless --output-format is set to json.
+    #[structopt(long = "porcelain", conflicts_with = "template")]
+    porcelain: bool,
+
+    /// Handlebars template file used to format output (optional)
+    ///
+    /// The template is rendered with the same data as json output (i.e.
+    /// {{activity.details.map_name}}), and takes precedence over
+    /// --output-format.
+    #[structopt(long = "template", parse(from_os_str))]
+    template: Option<PathBuf>,
+
+    /// Write a self-contained html report of the activity to the specified file
+    ///
+    /// The report includes team tables, per player weapon breakdowns, medals,
+    /// links and the map image, and can be shared as a single file. Output
+    /// is still printed to the console.
+    #[structopt(long = "html", parse(from_os_str))]
+    html: Option<PathBuf>,
+
+    /// Write a scorecard image of the activity to the specified file
+    ///
+    /// The format is determined by the file extension, and can be png or svg.
+    /// The scorecard includes the team tables (using the --columns
+    /// selection) and top weapons, and is meant to be shared where tables do
+    /// not display well, such as Discord. Output is still printed to the
+    /// console.
+    #[structopt(long = "image", parse(from_os_str))]
+    image: Option<PathBuf>,
+
+    /// Display account context for each player
+    ///
+    /// Includes when the account was created and total number of Crucible
+    /// matches and hours played. Data is cached for a day.
+    #[structopt(long = "account-context")]
+    account_context: bool,
+
     /// The index of the activity to display data about
     ///
     /// By default, the last activity will be displayed. The index can be retrieved
@@ -559,41 +673,152 @@ struct Opt {
     #[structopt(long = "activity-index", short = "a")]
     activity_index: Option<u32>,
 
+    /// The Bungie instance id of the activity to display data about
+    ///
+    /// The id used for the activity on bungie.net and third party sites, and
+    /// in their activity urls. The activity must have been synced to the
+    /// activity store.
+    #[structopt(long = "instance-id", conflicts_with = "activity-index")]
+    instance_id: Option<i64>,
+
     /// Directory where Destiny 2 manifest and activity database files are stored. (optional)
     ///
     /// This will normally be downloaded using the dclim and dclias tools, and uses
     /// a system appropriate directory by default.
     #[structopt(short = "D", long = "data-dir", parse(from_os_str))]
     data_dir: Option<PathBuf>,
+
+    /// Print the paths of the files and directories used by dcli and exit
+    ///
+    /// Includes the data directory, manifest, activity store and the cache,
+    /// config and backup directories.
+    #[structopt(long = "print-paths")]
+    print_paths: bool,
+
+    /// Name of the manifest to use (optional)
+    ///
+    /// Defaults to the current (live) manifest. dclim keeps the previously
+    /// downloaded manifest under the name previous, and can download manifests
+    /// into other named slots with its --manifest-name flag.
+    #[structopt(
+        long = "manifest-name",
+        default_value = "live",
+        parse(try_from_str = datadir::parse_manifest_name)
+    )]
+    manifest_name: String,
+
+    /// Language to display weapon, medal and map names in (optional)
+    ///
+    /// Defaults to en. Names are looked up in the manifest for the language,
+    /// which must first be downloaded with dclim --language. Stored activity
+    /// data does not need to be synced again.
+    ///
+    /// Valid values are en, fr, es, es-mx, de, it, ja, pt-br, ru, pl, ko,
+    /// zh-cht and zh-chs.
+    #[structopt(long = "language", default_value = "en")]
+    language: Language,
 }
 #[tokio::main]
 async fn main() {
-    let opt = Opt::from_args();
+    let mut opt = Opt::from_args();
     print_verbose(&format!("{:#?}", opt), opt.verbose);
 
-    let data_dir = match determine_data_dir(opt.data_dir) {
-        Ok(e) => e,
-        Err(e) => {
-            print_error("Error initializing manifest directory.", e);
-            std::process::exit(EXIT_FAILURE);
-        }
-    };
+    let _timing = timing::report(opt.timing);
 
-    let mut store =
-        match ActivityStoreInterface::init_with_path(&data_dir, opt.verbose)
-            .await
-        {
+    if opt.max_rps.is_some() {
+        ratelimit::set_max_requests_per_second(opt.max_rps);
+    }
+
+    let supported_output =
+        [Output::Default, Output::Json, Output::Markdown, Output::Tsv];
+    if let Err(e) = opt.output.check_supported(&supported_output) {
+        print_error("Invalid output format.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    if opt.porcelain {
+        opt.output = match opt.output.porcelain(&supported_output) {
             Ok(e) => e,
             Err(e) => {
-                print_error(
-                    "Could not initialize activity store. Have you run dclias?",
-                    e,
-                );
+                print_error("Invalid output format.", e);
                 std::process::exit(EXIT_FAILURE);
             }
         };
+    }
+
+    let template = match &opt.template {
+        Some(e) => match template::load_template(e) {
+            Ok(e) => Some(e),
+            Err(e) => {
+                print_error("Could not load template.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        },
+        None => None,
+    };
 
-    let mut manifest = match ManifestInterface::new(&data_dir, false).await {
+    let data_dir = if opt.demo {
+        match demo::create_demo_data_dir().await {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Error creating demo data.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        }
+    } else {
+        match determine_data_dir(opt.data_dir) {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Error initializing manifest directory.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        }
+    };
+
+    if opt.print_paths {
+        datadir::print_paths(&data_dir);
+        return;
+    }
+
+    //structopt ensures these are set when not running in demo mode. Class
+    //selections other than all require an api call, so demo always uses all
+    let (member_id, platform, character_class_selection) = if opt.demo {
+        (
+            demo::DEMO_MEMBER_ID.to_string(),
+            demo::DEMO_PLATFORM,
+            &CharacterClassSelection::All,
+        )
+    } else {
+        (
+            opt.member_id.clone().unwrap(),
+            opt.platform.unwrap(),
+            &opt.character_class_selection,
+        )
+    };
+
+    let mut store = match ActivityStoreInterface::init_read_only_with_path(
+        &data_dir,
+        opt.verbose,
+    )
+    .await
+    {
+        Ok(e) => e,
+        Err(e) => {
+            print_error(
+                "Could not initialize activity store. Have you run dclias?",
+                e,
+            );
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    let mut manifest = match ManifestInterface::new_with_name(
+        &data_dir,
+        &opt.language.manifest_name(&opt.manifest_name),
+        false,
+    )
+    .await
+    {
         Ok(e) => e,
         Err(e) => {
             print_error(
@@ -604,8 +829,27 @@ async fn main() {
         }
     };
 
-    if !opt.no_sync {
-        match store.sync(&opt.member_id, &opt.platform).await {
+    //names from the manifest for displaying modes in human readable output.
+    //The built in names are used if they can't be loaded.
+    let mode_names =
+        manifest.get_mode_display_names().await.unwrap_or_default();
+
+    //member id can also be a local player alias (see dclias --set-alias)
+    let member_id = match store.resolve_member_id(&member_id).await {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Could not resolve member id.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    //sync is skipped when reading a snapshot, since snapshot stores are read
+    //only copies of a store that another process is syncing
+    if !opt.no_sync && !opt.demo && !store.is_snapshot() {
+        match store
+            .sync(&member_id, &platform, &CancellationToken::new())
+            .await
+        {
             Ok(_e) => (),
             Err(e) => {
                 eprintln!("Could not sync activity store {}", e);
@@ -614,14 +858,21 @@ async fn main() {
         };
     }
 
-    let data_result = match opt.activity_index {
-        Some(e) => store.retrieve_activity_by_index(e, &mut manifest).await,
-        None => {
+    let data_result = match (opt.activity_index, opt.instance_id) {
+        (Some(e), _) => {
+            store.retrieve_activity_by_index(e, &mut manifest).await
+        }
+        (_, Some(e)) => {
+            store
+                .retrieve_activity_by_instance_id(e, &mut manifest)
+                .await
+        }
+        _ => {
             store
                 .retrieve_last_activity(
-                    &opt.member_id,
-                    &opt.platform,
-                    &opt.character_class_selection,
+                    &member_id,
+                    &platform,
+                    character_class_selection,
                     &opt.mode,
                     &mut manifest,
                 )
@@ -629,11 +880,70 @@ async fn main() {
         }
     };
 
+    //ratings are retrieved live, so we store them to track lobby ratings
+    //over time (see dcliah --lobby-rating)
+    let elo_hash = match &data_result {
+        Ok(e) if !opt.demo && !opt.raw => {
+            let elo_hash = get_combat_ratings(e, opt.verbose).await;
+            save_combat_ratings(&mut store, e, &elo_hash, opt.verbose).await;
+            elo_hash
+        }
+        _ => HashMap::new(),
+    };
+
+    //the raw report comes straight from the api (or the json stored with the
+    //activity), so none of the activity formatting applies
+    if opt.raw {
+        let result = match data_result {
+            Ok(e) => {
+                store
+                    .retrieve_raw_post_game_carnage_report(e.details.id)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = store.close().await {
+            print_error("Error closing activity store.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+
+        match result {
+            Ok(e) => print_raw_pgcr(&e),
+            Err(Error::ActivityNotFound) => {
+                print_message("No activities found", opt.porcelain)
+            }
+            Err(e) => {
+                print_error("Could not retrieve post game carnage report.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+        return;
+    }
+
+    //aliases are optional, so we dont fail if they cant be retrieved (i.e.
+    //reading from a snapshot of an older store)
+    let aliases = match store.retrieve_player_aliases().await {
+        Ok(e) => e,
+        Err(e) => {
+            print_verbose(
+                &format!("Could not retrieve player aliases : {}", e),
+                opt.verbose,
+            );
+            HashMap::new()
+        }
+    };
+
+    if let Err(e) = store.close().await {
+        print_error("Error closing activity store.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
     let data = match data_result {
         Ok(e) => e,
         Err(e) => {
             if e == Error::ActivityNotFound {
-                println!("No activities found");
+                print_message("No activities found", opt.porcelain);
                 return;
             }
 
@@ -642,14 +952,123 @@ async fn main() {
         }
     };
 
-    let elo_hash = get_combat_ratings(&data, opt.verbose).await;
+    let titles = if opt.titles && !opt.demo {
+        get_titles(&data, &mut manifest, opt.verbose).await
+    } else {
+        HashMap::new()
+    };
+
+    let format_timer = timing::start(TimingCategory::Formatting);
+
+    let columns = match &opt.columns {
+        Some(e) => e.clone(),
+        None => profile_columns(&StatProfile::from_mode(&data.details.mode)),
+    };
+
+    if let Some(e) = &opt.html {
+        //links are always included, since the report is meant to be shared
+        let links =
+            LinkBuilder::new(&opt.link_sites).activity_links(data.details.id);
+        if let Err(err) = write_html(
+            e,
+            &data,
+            &elo_hash,
+            &titles,
+            &member_id,
+            opt.weapon_count,
+            &links,
+            &aliases,
+        ) {
+            print_error("Could not write html report.", err);
+            std::process::exit(EXIT_FAILURE);
+        }
+    }
+
+    if let Some(e) = &opt.image {
+        if let Err(err) = write_scorecard(
+            e,
+            &data,
+            &elo_hash,
+            &member_id,
+            opt.weapon_count,
+            &columns,
+            &aliases,
+            &mode_names,
+        ) {
+            print_error("Could not write scorecard image.", err);
+            std::process::exit(EXIT_FAILURE);
+        }
+    }
+
+    if let Some(e) = &template {
+        let links =
+            LinkBuilder::new(&opt.link_sites).activity_links(data.details.id);
+        print_template(
+            e,
+            &build_activity_json(&data, &elo_hash, &titles, &links),
+        );
+        return;
+    }
+
+    if opt.output == Output::Json {
+        let links =
+            LinkBuilder::new(&opt.link_sites).activity_links(data.details.id);
+        print_json(&build_activity_json(&data, &elo_hash, &titles, &links));
+        return;
+    }
+
+    if opt.output == Output::Tsv {
+        print_tsv(&data, &elo_hash, &member_id);
+        return;
+    }
+
+    let links = if opt.links {
+        LinkBuilder::new(&opt.link_sites).activity_links(data.details.id)
+    } else {
+        Vec::new()
+    };
+
+    if opt.output == Output::Markdown {
+        print_markdown(
+            &data,
+            &elo_hash,
+            &titles,
+            &member_id,
+            opt.weapon_count,
+            &links,
+            &aliases,
+        );
+        return;
+    }
 
-    print_default(
+    if let Err(e) = write_default(
+        &mut std::io::stdout(),
         &data,
         &elo_hash,
-        &opt.member_id,
+        &titles,
+        &member_id,
         opt.details,
         opt.weapon_count,
         opt.verbose,
-    );
+        &TextColors::new(opt.color),
+        &columns,
+        &aliases,
+        &mode_names,
+    ) {
+        print_error("Could not write activity details.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    if !links.is_empty() {
+        print_links(&links);
+    }
+
+    //account context is retrieved from the api, so isnt included in formatting
+    drop(format_timer);
+
+    if opt.account_context && !opt.demo {
+        let contexts =
+            get_account_contexts(&data, &data_dir, opt.verbose).await;
+        print_account_contexts(&contexts, &aliases);
+    }
 }
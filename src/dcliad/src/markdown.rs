@@ -0,0 +1,235 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::collections::HashMap;
+
+use dcli::crucible::{
+    AggregateCruciblePerformances, CrucibleActivity, CruciblePlayerPerformance,
+    ExtendedCrucibleStats,
+};
+use dcli::enums::completionreason::CompletionReason;
+use dcli::links::Link;
+use dcli::markdown::{escape, table_header, table_row};
+use dcli::utils::{
+    calculate_percent, f32_are_equal, format_f32, human_date_format,
+    human_duration,
+};
+
+use crate::{calculate_team_elo, generate_score, player_name, ELO_SCALE};
+
+/// prints the activity as GitHub flavored markdown, which can be pasted into
+/// Discord, forums or GitHub.
+pub fn print_markdown(
+    data: &CrucibleActivity,
+    elo_hash: &HashMap<u64, f32>,
+    titles: &HashMap<u64, String>,
+    member_id: &str,
+    weapon_count: u32,
+    links: &[Link],
+    aliases: &HashMap<String, String>,
+) {
+    println!(
+        "## {} on {}",
+        escape(&format!("{}", data.details.mode)),
+        escape(&data.details.map_name)
+    );
+    println!();
+
+    let mut summary = vec![human_date_format(&data.details.period)];
+    if let Some(e) = data.get_member_performance(member_id) {
+        summary.push(format!(
+            "({})",
+            human_duration(e.stats.activity_duration_seconds)
+        ));
+        summary.push(format!("**{}!**", e.stats.standing));
+
+        if e.stats.completion_reason != CompletionReason::Unknown {
+            summary.push(format!("({})", e.stats.completion_reason));
+        }
+    }
+    summary.push(generate_score(data));
+
+    println!("{}", summary.join(" "));
+    println!();
+
+    let mut columns = vec![
+        "PLAYER", "KILLS", "ASTS", "K+A", "DEATHS", "K/D", "KD/A", "EFF",
+        "SUP", "GREN", "MEL", "MED", "RATING", "STATUS",
+    ];
+
+    if !titles.is_empty() {
+        columns.push("TITLE");
+    }
+
+    let no_extended = ExtendedCrucibleStats::default();
+    let mut all_performances: Vec<&CruciblePlayerPerformance> = Vec::new();
+    for v in data.teams.values() {
+        let team_elo = calculate_team_elo(&v.player_performances, elo_hash);
+        let team_elo_str = if f32_are_equal(team_elo, 0.0) {
+            "".to_string()
+        } else {
+            format!(" (avg rating {})", format_f32(team_elo, 0))
+        };
+
+        println!(
+            "### [{}] {} Team {}!{}",
+            v.score,
+            escape(&v.display_name),
+            v.standing,
+            team_elo_str
+        );
+        println!();
+
+        if v.player_performances.is_empty() {
+            println!("No players");
+            println!();
+            continue;
+        }
+
+        println!("{}", table_header(&columns));
+
+        let mut player_performances: Vec<&CruciblePlayerPerformance> =
+            v.player_performances.iter().collect();
+        player_performances
+            .sort_by_key(|p| std::cmp::Reverse(p.stats.opponents_defeated));
+
+        for p in &player_performances {
+            let elo = *elo_hash.get(&p.player.calculate_hash()).unwrap_or(&0.0)
+                * ELO_SCALE;
+            let elo_str = if f32_are_equal(elo, 0.0) {
+                "".to_string()
+            } else {
+                format_f32(elo, 0)
+            };
+
+            let extended = p.stats.extended.as_ref().unwrap_or(&no_extended);
+
+            let mut row = vec![
+                escape(&player_name(&p.player, aliases)),
+                p.stats.kills.to_string(),
+                p.stats.assists.to_string(),
+                p.stats.opponents_defeated.to_string(),
+                p.stats.deaths.to_string(),
+                format_f32(p.stats.kills_deaths_ratio, 2),
+                format_f32(p.stats.kills_deaths_assists, 2),
+                format_f32(p.stats.efficiency, 2),
+                extended.weapon_kills_super.to_string(),
+                extended.weapon_kills_grenade.to_string(),
+                extended.weapon_kills_ability.to_string(),
+                extended.all_medals_earned.to_string(),
+                elo_str,
+                p.stats.generate_status(),
+            ];
+
+            if !titles.is_empty() {
+                row.push(match titles.get(&p.player.calculate_hash()) {
+                    Some(e) => escape(e),
+                    None => "".to_string(),
+                });
+            }
+
+            println!("{}", table_row(&row));
+            all_performances.push(p);
+        }
+
+        let aggregate = AggregateCruciblePerformances::with_performances(
+            &player_performances,
+        );
+        let agg_extended = aggregate.extended.unwrap_or_default();
+
+        let mut row = vec![
+            "**TOTAL**".to_string(),
+            aggregate.kills.to_string(),
+            aggregate.assists.to_string(),
+            aggregate.opponents_defeated.to_string(),
+            aggregate.deaths.to_string(),
+            format_f32(aggregate.kills_deaths_ratio, 2),
+            format_f32(aggregate.kills_deaths_assists, 2),
+            format_f32(aggregate.efficiency, 2),
+            agg_extended.weapon_kills_super.to_string(),
+            agg_extended.weapon_kills_grenade.to_string(),
+            agg_extended.weapon_kills_melee.to_string(),
+            agg_extended.all_medals_earned.to_string(),
+            "".to_string(),
+            "".to_string(),
+        ];
+
+        if !titles.is_empty() {
+            row.push("".to_string());
+        }
+
+        println!("{}", table_row(&row));
+        println!();
+    }
+
+    let aggregate =
+        AggregateCruciblePerformances::with_performances(&all_performances);
+    let agg_extended = aggregate.extended.unwrap_or_default();
+    let weapons = &agg_extended.weapons;
+    let max_weps = std::cmp::min(weapon_count as usize, weapons.len());
+
+    println!("### Weapons");
+    println!();
+
+    if weapons.is_empty() {
+        println!("No weapon kills");
+    } else {
+        println!(
+            "{}",
+            table_header(&[
+                "WEAPON", "KILLS", "% TOTAL", "PREC", "% PREC", "TYPE"
+            ])
+        );
+
+        for w in &weapons[..max_weps] {
+            println!(
+                "{}",
+                table_row(&[
+                    escape(&w.weapon.name),
+                    w.kills.to_string(),
+                    format!(
+                        "{}%",
+                        format_f32(
+                            calculate_percent(w.kills, aggregate.kills),
+                            2
+                        )
+                    ),
+                    w.precision_kills.to_string(),
+                    format!("{}%", format_f32(w.precision_kills_percent, 2)),
+                    escape(&format!("{}", w.weapon.item_sub_type)),
+                ])
+            );
+        }
+    }
+    println!();
+
+    if !links.is_empty() {
+        println!("### Links");
+        println!();
+        for l in links {
+            println!("- [{}]({})", escape(&format!("{}", l.site)), l.url);
+        }
+        println!();
+    }
+
+    println!("*STATUS : L - Joined late, E - Left early*");
+}
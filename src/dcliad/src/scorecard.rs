@@ -0,0 +1,399 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use dcli::crucible::{
+    AggregateCruciblePerformances, CrucibleActivity, CruciblePlayerPerformance,
+};
+use dcli::enums::completionreason::CompletionReason;
+use dcli::enums::standing::Standing;
+use dcli::error::Error;
+use dcli::render::{write_image, Color, Drawing};
+use dcli::utils::{
+    calculate_percent, f32_are_equal, format_f32, human_date_format,
+    human_duration, truncate_string,
+};
+
+use crate::columns::PlayerColumn;
+use crate::{calculate_team_elo, generate_score, player_name, ELO_SCALE};
+
+const BACKGROUND: Color = Color::rgb(0x1b, 0x1d, 0x22);
+const ALT_ROW: Color = Color::rgb(0x23, 0x26, 0x2d);
+const RULE: Color = Color::rgb(0x55, 0x55, 0x55);
+const TEXT: Color = Color::rgb(0xe4, 0xe4, 0xe4);
+const MUTED: Color = Color::rgb(0x9a, 0x9a, 0x9a);
+const HIGHLIGHT: Color = Color::rgb(0xe3, 0xb3, 0x41);
+const VICTORY: Color = Color::rgb(0x3f, 0xb9, 0x50);
+const VICTORY_BAND: Color = Color::rgb(0x1f, 0x3a, 0x2a);
+const DEFEAT: Color = Color::rgb(0xf8, 0x51, 0x49);
+const DEFEAT_BAND: Color = Color::rgb(0x3d, 0x1f, 0x22);
+
+const PADDING: u32 = 24;
+const ROW_HEIGHT: u32 = 24;
+
+//vertical offset of text within a row
+const ROW_TEXT_OFFSET: u32 = 4;
+
+//gap between columns in characters
+const COLUMN_GAP: usize = 3;
+
+const MAX_NAME_WIDTH: usize = 32;
+
+#[derive(PartialEq)]
+enum RowStyle {
+    Normal,
+    Highlight,
+    Total,
+}
+
+//a table where the first column is left aligned, and the rest are right
+//aligned. Widths are in characters, since the font is fixed width.
+struct Table {
+    headers: Vec<String>,
+    rows: Vec<(Vec<String>, RowStyle)>,
+}
+
+impl Table {
+    fn widths(&self) -> Vec<usize> {
+        self.headers
+            .iter()
+            .enumerate()
+            .map(|(i, h)| {
+                self.rows
+                    .iter()
+                    .filter_map(|(r, _)| r.get(i))
+                    .map(|v| v.chars().count())
+                    .chain(std::iter::once(h.chars().count()))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+
+    fn draw(
+        &self,
+        drawing: &mut Drawing,
+        y: u32,
+        width: u32,
+        widths: &[usize],
+    ) -> u32 {
+        let char_w = Drawing::text_width(" ", 1) as usize;
+
+        let draw_row =
+            |drawing: &mut Drawing, y: u32, values: &[String], color| {
+                let mut offset = 0;
+                for (i, (v, w)) in values.iter().zip(widths.iter()).enumerate()
+                {
+                    let x = if i == 0 {
+                        offset
+                    } else {
+                        offset + (w - v.chars().count()) * char_w
+                    };
+                    drawing.text(
+                        PADDING + x as u32,
+                        y + ROW_TEXT_OFFSET,
+                        v,
+                        color,
+                        1,
+                    );
+                    offset += (w + COLUMN_GAP) * char_w;
+                }
+            };
+
+        let mut y = y;
+        draw_row(drawing, y, &self.headers, MUTED);
+        y += ROW_HEIGHT;
+        drawing.fill_rect(PADDING, y - 2, width, 2, RULE);
+
+        for (i, (values, style)) in self.rows.iter().enumerate() {
+            if *style == RowStyle::Total {
+                drawing.fill_rect(PADDING, y, width, 1, RULE);
+            } else if i % 2 == 1 {
+                drawing.fill_rect(PADDING, y, width, ROW_HEIGHT, ALT_ROW);
+            }
+
+            let color = if *style == RowStyle::Highlight {
+                HIGHLIGHT
+            } else {
+                TEXT
+            };
+
+            draw_row(drawing, y, values, color);
+            y += ROW_HEIGHT;
+        }
+
+        y
+    }
+}
+
+fn char_width(widths: &[usize]) -> usize {
+    widths.iter().sum::<usize>() + widths.len().saturating_sub(1) * COLUMN_GAP
+}
+
+fn team_table(
+    data: &CrucibleActivity,
+    players: &[&CruciblePlayerPerformance],
+    elo_hash: &HashMap<u64, f32>,
+    member_id: &str,
+    columns: &[PlayerColumn],
+    aliases: &HashMap<String, String>,
+) -> Table {
+    let mut headers = vec!["PLAYER".to_string()];
+    headers.extend(columns.iter().map(|c| c.header().to_string()));
+
+    let mut rows = Vec::new();
+    for p in players {
+        let elo = *elo_hash.get(&p.player.calculate_hash()).unwrap_or(&0.0)
+            * ELO_SCALE;
+        let rating = if f32_are_equal(elo, 0.0) {
+            "".to_string()
+        } else {
+            format_f32(elo, 0)
+        };
+
+        let mut row = vec![truncate_string(
+            &player_name(&p.player, aliases),
+            MAX_NAME_WIDTH,
+        )];
+        row.extend(columns.iter().map(|c| c.player_value(data, p, &rating)));
+
+        let style = if p.player.member_id == member_id {
+            RowStyle::Highlight
+        } else {
+            RowStyle::Normal
+        };
+        rows.push((row, style));
+    }
+
+    let aggregate = AggregateCruciblePerformances::with_performances(players);
+    let mut total = vec!["TOTAL".to_string()];
+    total.extend(columns.iter().map(|c| c.total_value(&aggregate)));
+    rows.push((total, RowStyle::Total));
+
+    Table { headers, rows }
+}
+
+fn weapon_table(
+    players: &[&CruciblePlayerPerformance],
+    weapon_count: u32,
+) -> Option<Table> {
+    let aggregate = AggregateCruciblePerformances::with_performances(players);
+    let extended = aggregate.extended.as_ref()?;
+    if extended.weapons.is_empty() {
+        return None;
+    }
+
+    let max_weps = std::cmp::min(weapon_count as usize, extended.weapons.len());
+    let rows = extended.weapons[..max_weps]
+        .iter()
+        .map(|w| {
+            (
+                vec![
+                    truncate_string(&w.weapon.name, MAX_NAME_WIDTH),
+                    w.kills.to_string(),
+                    format!(
+                        "{}%",
+                        format_f32(
+                            calculate_percent(w.kills, aggregate.kills),
+                            2
+                        )
+                    ),
+                    w.precision_kills.to_string(),
+                    format!("{}%", format_f32(w.precision_kills_percent, 2)),
+                    format!("{}", w.weapon.item_sub_type),
+                ],
+                RowStyle::Normal,
+            )
+        })
+        .collect();
+
+    Some(Table {
+        headers: ["WEAPON", "KILLS", "% TOTAL", "PREC", "% PREC", "TYPE"]
+            .iter()
+            .map(|e| e.to_string())
+            .collect(),
+        rows,
+    })
+}
+
+/// lays out a scorecard for the activity, with a summary, a table for each
+/// team using the selected columns, and the top weapons used in the match.
+pub fn generate_scorecard(
+    data: &CrucibleActivity,
+    elo_hash: &HashMap<u64, f32>,
+    member_id: &str,
+    weapon_count: u32,
+    columns: &[PlayerColumn],
+    aliases: &HashMap<String, String>,
+    mode_names: &HashMap<u32, String>,
+) -> Drawing {
+    let title = format!(
+        "{} on {}",
+        data.details.mode.display_name(mode_names),
+        data.details.map_name
+    );
+
+    let mut summary = vec![human_date_format(&data.details.period)];
+    let mut standing_color = TEXT;
+    if let Some(e) = data.get_member_performance(member_id) {
+        summary.push(format!(
+            "({})",
+            human_duration(e.stats.activity_duration_seconds)
+        ));
+        summary.push(format!("{}!", e.stats.standing));
+
+        if e.stats.completion_reason != CompletionReason::Unknown {
+            summary.push(format!("({})", e.stats.completion_reason));
+        }
+
+        standing_color = match e.stats.standing {
+            Standing::Victory => VICTORY,
+            Standing::Defeat => DEFEAT,
+            _ => TEXT,
+        };
+    }
+    summary.push(generate_score(data));
+    let summary = summary.join(" ");
+
+    let mut teams: Vec<_> = data.teams.values().collect();
+    teams.sort_by_key(|t| t.id);
+
+    let mut all_players: Vec<&CruciblePlayerPerformance> = Vec::new();
+    let mut team_tables = Vec::new();
+    for t in &teams {
+        let mut players: Vec<&CruciblePlayerPerformance> =
+            t.player_performances.iter().collect();
+        players.sort_by_key(|p| std::cmp::Reverse(p.stats.opponents_defeated));
+        all_players.extend(players.iter());
+
+        let team_elo = calculate_team_elo(&t.player_performances, elo_hash);
+        let mut heading =
+            format!("[{}] {} Team {}!", t.score, t.display_name, t.standing);
+        if !f32_are_equal(team_elo, 0.0) {
+            heading.push_str(&format!(
+                " (avg rating {})",
+                format_f32(team_elo, 0)
+            ));
+        }
+
+        let table =
+            team_table(data, &players, elo_hash, member_id, columns, aliases);
+        team_tables.push((t.standing, heading, table));
+    }
+
+    //team tables share column widths so they line up with each other
+    let mut team_widths: Vec<usize> = vec![0; columns.len() + 1];
+    for (_, _, table) in &team_tables {
+        for (a, b) in team_widths.iter_mut().zip(table.widths()) {
+            *a = (*a).max(b);
+        }
+    }
+
+    let weapons = weapon_table(&all_players, weapon_count);
+    let weapon_widths =
+        weapons.as_ref().map(|e| e.widths()).unwrap_or_default();
+
+    //size the image to fit the widest element
+    let mut max_chars = summary
+        .chars()
+        .count()
+        .max(char_width(&team_widths))
+        .max(char_width(&weapon_widths));
+    for (_, heading, _) in &team_tables {
+        max_chars = max_chars.max(heading.chars().count());
+    }
+
+    let content_width = Drawing::text_width(&title, 2)
+        .max(Drawing::text_width(" ", 1) * max_chars as u32);
+    let width = content_width + PADDING * 2;
+
+    let mut drawing = Drawing::new(width, 0, BACKGROUND);
+    let mut y = PADDING;
+
+    drawing.text(PADDING, y, &title, TEXT, 2);
+    y += Drawing::line_height(2) + 8;
+
+    drawing.text(PADDING, y, &summary, standing_color, 1);
+    y += ROW_HEIGHT + 8;
+
+    for (standing, heading, table) in &team_tables {
+        let (band, color) = match standing {
+            Standing::Victory => (VICTORY_BAND, VICTORY),
+            Standing::Defeat => (DEFEAT_BAND, DEFEAT),
+            _ => (ALT_ROW, TEXT),
+        };
+
+        drawing.fill_rect(PADDING, y, content_width, ROW_HEIGHT + 4, band);
+        drawing.text(PADDING + 8, y + ROW_TEXT_OFFSET + 2, heading, color, 1);
+        y += ROW_HEIGHT + 12;
+
+        y = table.draw(&mut drawing, y, content_width, &team_widths);
+        y += 16;
+    }
+
+    if let Some(e) = &weapons {
+        drawing.text(PADDING, y, "TOP WEAPONS", TEXT, 1);
+        y += ROW_HEIGHT;
+        y = e.draw(&mut drawing, y, content_width, &weapon_widths);
+        y += 16;
+    }
+
+    drawing.text(
+        PADDING,
+        y,
+        "STATUS : L - Joined late, E - Left early",
+        MUTED,
+        1,
+    );
+    y += ROW_HEIGHT;
+
+    drawing.height = y + PADDING;
+    drawing
+}
+
+/// writes a scorecard image of the activity. The format (png or svg) is
+/// determined by the file extension.
+#[allow(clippy::too_many_arguments)]
+pub fn write_scorecard(
+    path: &Path,
+    data: &CrucibleActivity,
+    elo_hash: &HashMap<u64, f32>,
+    member_id: &str,
+    weapon_count: u32,
+    columns: &[PlayerColumn],
+    aliases: &HashMap<String, String>,
+    mode_names: &HashMap<u32, String>,
+) -> Result<(), Error> {
+    let drawing = generate_scorecard(
+        data,
+        elo_hash,
+        member_id,
+        weapon_count,
+        columns,
+        aliases,
+        mode_names,
+    );
+
+    write_image(path, &drawing)
+}
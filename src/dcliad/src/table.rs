@@ -0,0 +1,786 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use dcli::crucible::{
+    AggregateCruciblePerformances, CrucibleActivity, CruciblePlayerPerformance,
+    ExtendedCrucibleStats, Team,
+};
+use dcli::enums::completionreason::CompletionReason;
+use dcli::error::Error;
+use dcli::utils::{
+    calculate_avg, calculate_percent, f32_are_equal, fit_string, format_f32,
+    human_date_format, human_duration, pad_string, repeat_str, TextColors,
+};
+
+use crate::columns::{calculate_widths, format_cells, PlayerColumn};
+use crate::{
+    calculate_team_elo, generate_score, generate_team_elo_delta, player_name,
+    ELO_SCALE,
+};
+
+//a team's players, sorted for display, along with their formatted values
+struct TeamTable<'a> {
+    team: &'a Team,
+    players: Vec<&'a CruciblePlayerPerformance>,
+    rows: Vec<Vec<String>>,
+    total: Vec<String>,
+    avg: Vec<String>,
+}
+
+/// writes the default (human readable) activity tables to out
+#[allow(clippy::too_many_arguments)]
+pub fn write_default<W: Write>(
+    out: &mut W,
+    data: &CrucibleActivity,
+    elo_hash: &HashMap<u64, f32>,
+    titles: &HashMap<u64, String>,
+    member_id: &str,
+    details: bool,
+    weapon_count: u32,
+    verbose: bool,
+    colors: &TextColors,
+    columns: &[PlayerColumn],
+    aliases: &HashMap<String, String>,
+    mode_names: &HashMap<u32, String>,
+) -> Result<(), Error> {
+    let col_w = 8;
+    let name_col_w = 24;
+
+    let mut activity_duration = "".to_string();
+    let mut completion_reason = "".to_string();
+    let mut standing_str = "".to_string();
+
+    if let Some(e) = data.get_member_performance(member_id) {
+        completion_reason =
+            if e.stats.completion_reason == CompletionReason::Unknown {
+                "".to_string()
+            } else {
+                format!("({})", e.stats.completion_reason)
+            };
+
+        activity_duration =
+            format!("({})", human_duration(e.stats.activity_duration_seconds));
+        standing_str = format!("{}!", e.stats.standing);
+    };
+
+    let team_title_border = repeat_str("-", name_col_w + col_w);
+    let activity_title_border = repeat_str("=", name_col_w + col_w + col_w);
+
+    writeln!(out)?;
+    writeln!(out, "ACTIVITY")?;
+    writeln!(out, "{}", activity_title_border)?;
+
+    writeln!(
+        out,
+        "{} on {} :: {} {}",
+        data.details.mode.display_name(mode_names),
+        data.details.map_name,
+        human_date_format(&data.details.period),
+        activity_duration
+    )?;
+
+    if verbose {
+        writeln!(out, "Activity ID : {}", data.details.id)?;
+    }
+
+    writeln!(out, "{}", standing_str)?;
+    writeln!(out, "{} {}", generate_score(data), completion_reason)?;
+
+    writeln!(out)?;
+
+    //ratings are displayed scaled, and only if we have one for the player
+    let player_rating = |p: &CruciblePlayerPerformance| -> f32 {
+        *elo_hash.get(&p.player.calculate_hash()).unwrap_or(&0.0) * ELO_SCALE
+    };
+    let rating_str = |elo: f32| -> String {
+        if f32_are_equal(elo, 0.0) {
+            "".to_string()
+        } else {
+            format_f32(elo, 0)
+        }
+    };
+
+    //build all of the rows up front, so the column widths can be calculated
+    //to fit every value in the activity
+    let mut tables: Vec<TeamTable> = Vec::new();
+    for v in data.teams.values() {
+        let mut players: Vec<&CruciblePlayerPerformance> =
+            v.player_performances.iter().collect();
+        players.sort_by_key(|p| std::cmp::Reverse(p.stats.opponents_defeated));
+
+        let rows = players
+            .iter()
+            .map(|p| {
+                let rating = rating_str(player_rating(p));
+                columns
+                    .iter()
+                    .map(|c| c.player_value(data, p, &rating))
+                    .collect()
+            })
+            .collect();
+
+        let aggregate =
+            AggregateCruciblePerformances::with_performances(&players);
+        let total = columns.iter().map(|c| c.total_value(&aggregate)).collect();
+        let avg = columns
+            .iter()
+            .map(|c| c.average_value(&aggregate, players.len() as u32, ""))
+            .collect();
+
+        tables.push(TeamTable {
+            team: v,
+            players,
+            rows,
+            total,
+            avg,
+        });
+    }
+
+    let all_performances: Vec<&CruciblePlayerPerformance> = tables
+        .iter()
+        .flat_map(|t| t.players.iter().copied())
+        .collect();
+
+    let aggregate =
+        AggregateCruciblePerformances::with_performances(&all_performances);
+
+    let mut elo_total_count = 0;
+    let mut elo_total_total = 0.0;
+    for p in &all_performances {
+        let elo = player_rating(p);
+        if !f32_are_equal(elo, 0.0) {
+            elo_total_count += 1;
+            elo_total_total += elo;
+        }
+    }
+    let total_elo_str =
+        rating_str(calculate_avg(elo_total_total, elo_total_count));
+
+    let combined_total: Vec<String> =
+        columns.iter().map(|c| c.total_value(&aggregate)).collect();
+    let combined_avg: Vec<String> = columns
+        .iter()
+        .map(|c| {
+            c.average_value(
+                &aggregate,
+                all_performances.len() as u32,
+                &total_elo_str,
+            )
+        })
+        .collect();
+
+    let mut all_rows: Vec<Vec<String>> = Vec::new();
+    for t in &tables {
+        all_rows.extend(t.rows.iter().cloned());
+        all_rows.push(t.total.clone());
+        all_rows.push(t.avg.clone());
+    }
+    all_rows.push(combined_total.clone());
+    all_rows.push(combined_avg.clone());
+
+    let widths = calculate_widths(columns, &all_rows);
+
+    let headers: Vec<String> =
+        columns.iter().map(|c| c.header().to_string()).collect();
+    let header = format!(
+        "{:<0name_col_w$}{}",
+        "PLAYER",
+        format_cells(&headers, &widths).join(""),
+        name_col_w = name_col_w,
+    );
+
+    //titles are displayed in an extra column at the end of the table
+    let header = if titles.is_empty() {
+        header
+    } else {
+        format!("{}  {}", header, "TITLE")
+    };
+
+    let table_width = header.chars().count();
+    let header_border = repeat_str("=", table_width);
+    let entry_border = repeat_str(".", table_width);
+    let footer_border = repeat_str("-", table_width);
+
+    let kd_index = columns
+        .iter()
+        .position(|c| c == &PlayerColumn::KillsDeathsRatio);
+
+    //average rating for each team, in the order the teams are displayed
+    let team_elos: Vec<(String, f32)> = tables
+        .iter()
+        .map(|t| {
+            (
+                t.team.display_name.to_string(),
+                calculate_team_elo(&t.team.player_performances, elo_hash),
+            )
+        })
+        .collect();
+
+    if let Some(delta) = generate_team_elo_delta(&team_elos) {
+        writeln!(out, "{}", delta)?;
+        writeln!(out)?;
+    }
+
+    let no_extended = ExtendedCrucibleStats::default();
+    for (t, (_, team_elo)) in tables.iter().zip(team_elos.iter()) {
+        let v = t.team;
+        let team_elo_str = if f32_are_equal(*team_elo, 0.0) {
+            "".to_string()
+        } else {
+            format!(" (avg rating {})", format_f32(*team_elo, 0))
+        };
+
+        writeln!(
+            out,
+            "{}",
+            colors.standing(
+                &format!(
+                    "[{}] {} Team {}!{}",
+                    v.score, v.display_name, v.standing, team_elo_str
+                ),
+                v.standing
+            )
+        )?;
+        writeln!(out, "{}", team_title_border)?;
+        writeln!(out, "{}", header)?;
+        writeln!(out, "{}", header_border)?;
+
+        let mut first_performance = true;
+
+        if t.players.is_empty() {
+            writeln!(out, "No players")?;
+        }
+
+        for (p, row) in t.players.iter().zip(t.rows.iter()) {
+            let title_str = match titles.get(&p.player.calculate_hash()) {
+                Some(e) => format!("  {}", e),
+                None => "".to_string(),
+            };
+
+            //the requested member is highlighted, everyone else is colored
+            //by their team's standing
+            let name = fit_string(&player_name(&p.player, aliases), name_col_w);
+            let name = if p.player.member_id == member_id {
+                colors.highlight(&name)
+            } else {
+                colors.standing(&name, v.standing)
+            };
+
+            let mut cells = format_cells(row, &widths);
+            if let Some(i) = kd_index {
+                cells[i] = colors
+                    .kills_deaths_ratio(&cells[i], p.stats.kills_deaths_ratio);
+            }
+
+            writeln!(out, "{}{}{}", name, cells.join(""), title_str)?;
+
+            //players with no weapon kills still display their class and
+            //light level
+            if details {
+                //players who leave right away (or bots) may not have extended
+                //stats
+                let extended =
+                    p.stats.extended.as_ref().unwrap_or(&no_extended);
+                writeln!(out, "{}", entry_border)?;
+
+                let mut weapons = extended.weapons.clone();
+                weapons.sort_by_key(|b| std::cmp::Reverse(b.kills));
+
+                let mut min_index = 2;
+                if first_performance {
+                    writeln!(
+                        out,
+                        //"{:>0w_name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w2$}",
+                        "{:<0col_w$}{:>0w_name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w2$}",
+                        format!("{}", p.player.class_type),
+                        "NAME",
+                        "KILLS",
+                        "PREC",
+                        "%",
+                        "TYPE",
+                        w_name_col_w = col_w + col_w + name_col_w,
+                        col_w = col_w,
+                        col_w2 = col_w * 3,
+                    )?;
+                    first_performance = false;
+                    min_index = 1;
+                }
+
+                for i in 0..std::cmp::max(min_index, weapons.len()) {
+                    let modifier = 2 - min_index;
+                    let meta = match i + modifier {
+                        0 => format!("{}", p.player.class_type),
+                        1 => p.player.light_level.to_string(),
+                        _ => "".to_string(),
+                    };
+
+                    let mut weapon_name = "".to_string();
+                    let mut weapon_kills = "".to_string();
+                    let mut precision_kills = "".to_string();
+                    let mut precision_kills_percent = "".to_string();
+                    let mut weapon_type = "".to_string();
+
+                    if weapons.is_empty() && i == 0 {
+                        weapon_name = "No weapon kills".to_string();
+                    } else if i < weapons.len() {
+                        let w = &weapons[i];
+                        weapon_name = w.weapon.name.to_string();
+                        weapon_kills = w.kills.to_string();
+                        precision_kills = w.precision_kills.to_string();
+                        precision_kills_percent =
+                            format_f32(w.precision_kills_percent * 100.0, 0)
+                                .to_string();
+                        weapon_type = format!("{}", w.weapon.item_sub_type);
+                    }
+
+                    writeln!(
+                        out,
+                        "{:<0col_w$}{:>0w_name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w2$}",
+                        meta,
+                        weapon_name,
+                        weapon_kills,
+                        precision_kills,
+                        precision_kills_percent,
+                        weapon_type,
+                        w_name_col_w = col_w + col_w + name_col_w,
+                        col_w = col_w,
+                        col_w2 = col_w * 3,
+                    )?;
+                }
+                writeln!(out)?;
+            }
+        }
+        writeln!(out, "{}", footer_border)?;
+
+        writeln!(
+            out,
+            "{:<0name_col_w$}{}",
+            "TOTAL",
+            format_cells(&t.total, &widths).join(""),
+            name_col_w = name_col_w,
+        )?;
+        writeln!(
+            out,
+            "{:<0name_col_w$}{}",
+            "AVG",
+            format_cells(&t.avg, &widths).join(""),
+            name_col_w = name_col_w,
+        )?;
+
+        writeln!(out)?;
+    }
+
+    writeln!(out, "Combined")?;
+    writeln!(out, "{}", team_title_border)?;
+
+    writeln!(out, "{}", header)?;
+    writeln!(out, "{}", header_border)?;
+    writeln!(
+        out,
+        "{:<0name_col_w$}{}",
+        "TOTAL",
+        format_cells(&combined_total, &widths).join(""),
+        name_col_w = name_col_w,
+    )?;
+    writeln!(
+        out,
+        "{:<0name_col_w$}{}",
+        "AVG",
+        format_cells(&combined_avg, &widths).join(""),
+        name_col_w = name_col_w,
+    )?;
+
+    let agg_extended = aggregate.extended.unwrap_or_default();
+    writeln!(out)?;
+
+    let wep_col = name_col_w + col_w;
+    let wep_header_str = format!(
+        "{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0name_col_w$}",
+        "WEAPON",
+        "KILLS",
+        "% TOTAL",
+        "PREC",
+        "% PREC",
+        "TYPE",
+        col_w = col_w,
+        name_col_w = wep_col,
+    );
+
+    let wep_divider = repeat_str("=", wep_header_str.chars().count());
+    writeln!(out, "{}", wep_header_str)?;
+    writeln!(out, "{}", wep_divider)?;
+
+    let weapons = &agg_extended.weapons;
+    let max_weps = std::cmp::min(weapon_count as usize, weapons.len());
+
+    if weapons.is_empty() {
+        writeln!(out, "No weapon kills")?;
+    }
+
+    let wep_col = name_col_w + col_w;
+    for w in &weapons[..max_weps] {
+        writeln!(
+            out,
+            "{}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0name_col_w$}",
+            pad_string(&w.weapon.name, wep_col),
+            w.kills.to_string(),
+            format!(
+                "{}%",
+                format_f32(calculate_percent(w.kills, aggregate.kills), 2)
+            ),
+            w.precision_kills.to_string(),
+            format!("{}%", format_f32(w.precision_kills_percent, 2)),
+            format!("{}", w.weapon.item_sub_type),
+            col_w = col_w,
+            name_col_w = wep_col,
+        )?;
+    }
+
+    if details {
+        write_team_weapons(out, data, member_id, weapon_count, colors)?;
+    }
+
+    writeln!(out)?;
+    writeln!(out, "STATUS : L - Joined late, E - Left early")?;
+    writeln!(out)?;
+
+    Ok(())
+}
+
+//top weapons for each team, so what the winning team used can be compared
+//against the member's team
+fn write_team_weapons<W: Write>(
+    out: &mut W,
+    data: &CrucibleActivity,
+    member_id: &str,
+    weapon_count: u32,
+    colors: &TextColors,
+) -> Result<(), Error> {
+    let col_w = 8;
+    let wep_col = 32;
+    let type_col = 24;
+
+    writeln!(out)?;
+    writeln!(out, "TEAM WEAPONS")?;
+    writeln!(
+        out,
+        "{}",
+        repeat_str("=", wep_col + col_w + col_w + type_col)
+    )?;
+
+    for (team, aggregate) in AggregateCruciblePerformances::by_team(data) {
+        let member_team = if team
+            .player_performances
+            .iter()
+            .any(|p| p.player.member_id == member_id)
+        {
+            " (your team)"
+        } else {
+            ""
+        };
+
+        writeln!(
+            out,
+            "{}",
+            colors.standing(
+                &format!(
+                    "[{}] {} Team {}!{}",
+                    team.score, team.display_name, team.standing, member_team
+                ),
+                team.standing
+            )
+        )?;
+
+        let header = format!(
+            "{:<0wep_col$}{:>0col_w$}{:>0col_w$}{:>0type_col$}",
+            "WEAPON",
+            "KILLS",
+            "% TEAM",
+            "TYPE",
+            wep_col = wep_col,
+            col_w = col_w,
+            type_col = type_col,
+        );
+        writeln!(out, "{}", header)?;
+        writeln!(out, "{}", repeat_str("-", header.chars().count()))?;
+
+        let weapons = match &aggregate.extended {
+            Some(e) => &e.weapons[..],
+            None => &[],
+        };
+
+        if weapons.is_empty() {
+            writeln!(out, "No weapon kills")?;
+        }
+
+        let max_weps = std::cmp::min(weapon_count as usize, weapons.len());
+        for w in &weapons[..max_weps] {
+            writeln!(
+                out,
+                "{}{:>0col_w$}{:>0col_w$}{:>0type_col$}",
+                pad_string(&w.weapon.name, wep_col),
+                w.kills.to_string(),
+                format!("{}%", format_f32(aggregate.weapon_kill_share(w), 2)),
+                format!("{}", w.weapon.item_sub_type),
+                col_w = col_w,
+                type_col = type_col,
+            )?;
+        }
+
+        writeln!(out)?;
+    }
+
+    writeln!(
+        out,
+        "% TEAM is the percent of the team's kills made with the weapon."
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+
+    use dcli::crucible::{
+        ActivityDetail, CrucibleStats, Item, Player, WeaponStat,
+    };
+    use dcli::derivedstats;
+    use dcli::enums::character::CharacterClass;
+    use dcli::enums::itemtype::{ItemSubType, ItemType};
+    use dcli::enums::mode::Mode;
+    use dcli::enums::platform::Platform;
+    use dcli::enums::standing::Standing;
+    use dcli::enums::statprofile::StatProfile;
+    use dcli::utils::get_destiny2_launch_date;
+
+    use crate::columns::profile_columns;
+
+    const MEMBER_ID: &str = "1";
+
+    fn performance(
+        id: u32,
+        kills: u32,
+        deaths: u32,
+        weapons: Option<Vec<WeaponStat>>,
+    ) -> CruciblePlayerPerformance {
+        let extended = weapons.map(|weapons| ExtendedCrucibleStats {
+            weapons,
+            ..Default::default()
+        });
+
+        CruciblePlayerPerformance {
+            player: Player {
+                member_id: id.to_string(),
+                character_id: format!("100{}", id),
+                platform: Platform::Xbox,
+                display_name: format!("Player {}", id),
+                light_level: 1350,
+                class_type: CharacterClass::Titan,
+            },
+            stats: CrucibleStats {
+                assists: 0,
+                score: 0,
+                kills,
+                deaths,
+                average_score_per_kill: 0.0,
+                average_score_per_life: 0.0,
+                completed: true,
+                opponents_defeated: kills,
+                efficiency: 0.0,
+                kills_deaths_ratio: 0.0,
+                kills_deaths_assists: 0.0,
+                activity_duration_seconds: 600,
+                standing: Standing::Victory,
+                team: 0,
+                fireteam_id: None,
+                completion_reason: CompletionReason::Unknown,
+                start_seconds: 0,
+                time_played_seconds: 0,
+                player_count: 0,
+                team_score: 0,
+                extended,
+                derived: Vec::new(),
+            },
+        }
+    }
+
+    fn weapon(name: &str, kills: u32) -> WeaponStat {
+        WeaponStat {
+            weapon: Item {
+                id: 1,
+                name: name.to_string(),
+                description: "".to_string(),
+                item_type: ItemType::Weapon,
+                item_sub_type: ItemSubType::AutoRifle,
+            },
+            kills,
+            precision_kills: 0,
+            precision_kills_percent: 0.0,
+            activity_count: 1,
+            activity_ids: std::iter::once(1).collect(),
+        }
+    }
+
+    fn activity(
+        teams: Vec<Vec<CruciblePlayerPerformance>>,
+    ) -> CrucibleActivity {
+        let teams = teams
+            .into_iter()
+            .enumerate()
+            .map(|(i, player_performances)| {
+                (
+                    i as i32,
+                    Team {
+                        id: i as i32,
+                        standing: Standing::Victory,
+                        score: 0,
+                        player_performances,
+                        display_name: format!("Team {}", i),
+                    },
+                )
+            })
+            .collect();
+
+        CrucibleActivity {
+            details: ActivityDetail {
+                index_id: 1,
+                id: 1,
+                period: get_destiny2_launch_date(),
+                map_name: "Javelin-4".to_string(),
+                map_image_url: None,
+                mode: Mode::Control,
+                platform: Platform::Xbox,
+                director_activity_hash: 0,
+                reference_id: 0,
+            },
+            teams,
+        }
+    }
+
+    fn render(data: &CrucibleActivity) -> String {
+        render_columns(data, &profile_columns(&StatProfile::Standard))
+    }
+
+    fn render_columns(
+        data: &CrucibleActivity,
+        columns: &[PlayerColumn],
+    ) -> String {
+        let mut out: Vec<u8> = Vec::new();
+        write_default(
+            &mut out,
+            data,
+            &HashMap::new(),
+            &HashMap::new(),
+            MEMBER_ID,
+            true,
+            5,
+            false,
+            &TextColors::new(false),
+            columns,
+            &HashMap::new(),
+            &HashMap::new(),
+        )
+        .unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(!out.contains("NaN"), "{}", out);
+
+        out
+    }
+
+    #[test]
+    fn player_without_weapon_kills() {
+        let data = activity(vec![vec![
+            performance(1, 5, 2, Some(vec![weapon("Ace of Spades", 5)])),
+            performance(2, 0, 4, Some(Vec::new())),
+        ]]);
+
+        let out = render(&data);
+
+        assert!(out.contains("Ace of Spades"));
+        assert!(out.contains("Player 2"));
+        assert!(out.contains("No weapon kills"));
+    }
+
+    #[test]
+    fn bot_which_left_immediately() {
+        //no kills, no deaths and no extended stats
+        let data = activity(vec![
+            vec![performance(1, 5, 2, Some(vec![weapon("Ace of Spades", 5)]))],
+            vec![performance(2, 0, 0, None)],
+        ]);
+
+        let out = render(&data);
+
+        assert!(out.contains("Player 2"));
+        assert!(out.contains("No weapon kills"));
+    }
+
+    #[test]
+    fn empty_team() {
+        let data = activity(vec![
+            vec![performance(1, 5, 2, Some(vec![weapon("Ace of Spades", 5)]))],
+            Vec::new(),
+        ]);
+
+        let out = render(&data);
+
+        assert!(out.contains("No players"));
+    }
+
+    #[test]
+    fn activity_without_kills() {
+        let data = activity(vec![
+            vec![performance(1, 0, 0, None)],
+            vec![performance(2, 0, 0, Some(Vec::new()))],
+        ]);
+
+        let out = render(&data);
+
+        assert!(out.contains("No weapon kills"));
+    }
+
+    #[test]
+    fn derived_stat_column() {
+        derivedstats::register("double_kills", |s| s.kills as f32 * 2.0)
+            .unwrap();
+
+        let column = PlayerColumn::from_str("DOUBLE_KILLS").unwrap();
+        assert_eq!(column, PlayerColumn::Derived("double_kills".to_string()));
+        assert!(PlayerColumn::from_str("triple_kills").is_err());
+
+        let mut p = performance(1, 5, 2, None);
+        p.stats.derived = derivedstats::calculate(&p.stats);
+        let data = activity(vec![vec![p]]);
+
+        let out = render_columns(&data, &[PlayerColumn::Kills, column]);
+
+        assert!(out.contains("DOUBLE_KILLS"));
+        assert!(out.contains("10.00"));
+    }
+}
@@ -0,0 +1,129 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::collections::HashMap;
+
+use dcli::crucible::{
+    CrucibleActivity, CruciblePlayerPerformance, ExtendedCrucibleStats, Team,
+};
+use dcli::utils::{build_tsv, f32_are_equal, format_f32, TSV_DELIM, TSV_EOL};
+
+use crate::{generate_score, ELO_SCALE};
+
+/// prints the activity as tsv. The activity details are printed first as
+/// name / value pairs, followed by an empty line and then one row per player
+/// performance.
+pub fn print_tsv(
+    data: &CrucibleActivity,
+    elo_hash: &HashMap<u64, f32>,
+    member_id: &str,
+) {
+    let (standing, completion_reason, duration) =
+        match data.get_member_performance(member_id) {
+            Some(e) => (
+                format!("{}", e.stats.standing),
+                format!("{}", e.stats.completion_reason),
+                e.stats.activity_duration_seconds.to_string(),
+            ),
+            None => ("".to_string(), "".to_string(), "".to_string()),
+        };
+
+    let name_values: Vec<(&str, String)> = vec![
+        ("activity_id", data.details.id.to_string()),
+        ("index", data.details.index_id.to_string()),
+        ("period", data.details.period.to_rfc3339()),
+        ("mode", format!("{}", data.details.mode)),
+        ("map_name", data.details.map_name.to_string()),
+        ("platform", format!("{}", data.details.platform)),
+        ("standing", standing),
+        ("score", generate_score(data)),
+        ("completion_reason", completion_reason),
+        ("activity_duration_seconds", duration),
+    ];
+
+    print!("{}", build_tsv(name_values));
+    print!("{}", TSV_EOL);
+
+    //teams are stored in a hash map, so sort them to keep output stable
+    let mut teams: Vec<&Team> = data.teams.values().collect();
+    teams.sort_by_key(|t| t.id);
+
+    for t in teams {
+        let mut players: Vec<&CruciblePlayerPerformance> =
+            t.player_performances.iter().collect();
+        players.sort_by_key(|p| std::cmp::Reverse(p.stats.opponents_defeated));
+
+        for p in players {
+            print!(
+                "{}{}",
+                player_values(t, p, elo_hash).join(TSV_DELIM),
+                TSV_EOL
+            );
+        }
+    }
+}
+
+fn player_values(
+    team: &Team,
+    p: &CruciblePlayerPerformance,
+    elo_hash: &HashMap<u64, f32>,
+) -> Vec<String> {
+    //players who leave right away (or bots) may not have extended stats
+    let no_extended = ExtendedCrucibleStats::default();
+    let extended = p.stats.extended.as_ref().unwrap_or(&no_extended);
+
+    let elo =
+        *elo_hash.get(&p.player.calculate_hash()).unwrap_or(&0.0) * ELO_SCALE;
+    let rating = if f32_are_equal(elo, 0.0) {
+        "".to_string()
+    } else {
+        format_f32(elo, 0)
+    };
+
+    vec![
+        team.display_name.to_string(),
+        format!("{}", team.standing),
+        p.player.member_id.to_string(),
+        p.player.display_name.to_string(),
+        format!("{}", p.player.platform),
+        p.player.character_id.to_string(),
+        format!("{}", p.player.class_type),
+        p.player.light_level.to_string(),
+        p.stats.kills.to_string(),
+        p.stats.assists.to_string(),
+        p.stats.opponents_defeated.to_string(),
+        p.stats.deaths.to_string(),
+        format_f32(p.stats.kills_deaths_ratio, 2),
+        format_f32(p.stats.kills_deaths_assists, 2),
+        format_f32(p.stats.efficiency, 2),
+        p.stats.score.to_string(),
+        extended.precision_kills.to_string(),
+        extended.weapon_kills_super.to_string(),
+        extended.weapon_kills_grenade.to_string(),
+        extended.weapon_kills_melee.to_string(),
+        extended.weapon_kills_ability.to_string(),
+        extended.all_medals_earned.to_string(),
+        rating,
+        p.stats.time_played_seconds.to_string(),
+        p.stats.completed.to_string(),
+    ]
+}
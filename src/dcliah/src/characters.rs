@@ -0,0 +1,83 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use dcli::crucible::AggregateCruciblePerformances;
+use dcli::enums::character::CharacterClass;
+use dcli::utils::{format_f32, repeat_str};
+use num_format::{Locale, ToFormattedString};
+
+/// prints the stats for each of the player's characters, along with the
+/// combined stats
+pub fn print_character_breakdown(
+    characters: &[((String, CharacterClass), AggregateCruciblePerformances)],
+    combined: &AggregateCruciblePerformances,
+) {
+    let name_col_w = 28;
+    let col_w = 10;
+
+    println!();
+    println!("CHARACTERS");
+    println!("==================");
+
+    let header = format!(
+        "{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+        "CHARACTER",
+        "GAMES",
+        "WIN %",
+        "KILLS",
+        "ASTS",
+        "DEATHS",
+        "K/D",
+        "KD/A",
+        "EFF",
+        "TIME",
+        name_col_w = name_col_w,
+        col_w = col_w,
+    );
+    println!("{}", header);
+    println!("{}", repeat_str("=", header.chars().count()));
+
+    let print_row = |name: &str, a: &AggregateCruciblePerformances| {
+        println!(
+            "{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+            name,
+            a.total_activities.to_formatted_string(&Locale::en),
+            format!("{}%", format_f32(a.win_rate, 2)),
+            a.kills.to_formatted_string(&Locale::en),
+            a.assists.to_formatted_string(&Locale::en),
+            a.deaths.to_formatted_string(&Locale::en),
+            format_f32(a.kills_deaths_ratio, 2),
+            format_f32(a.kills_deaths_assists, 2),
+            format_f32(a.efficiency, 2),
+            format!("{}h", format_f32(a.time_played_seconds as f32 / 3600.0, 1)),
+            name_col_w = name_col_w,
+            col_w = col_w,
+        );
+    };
+
+    for ((character_id, class_type), a) in characters {
+        print_row(&format!("{} {}", class_type, character_id), a);
+    }
+
+    println!("{}", repeat_str("-", header.chars().count()));
+    print_row("COMBINED", combined);
+}
@@ -0,0 +1,161 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use dcli::competitive::CompetitiveMatch;
+use dcli::render::chart::TrendPoint;
+use dcli::utils::{pad_string, repeat_str, truncate_string};
+
+/// prints the player's competitive games, along with their glory and rank
+/// changes
+pub fn print_competitive_history(matches: &[CompetitiveMatch]) {
+    let date_col_w = 18;
+    let map_col_w = 18;
+    let col_w = 9;
+    let rank_col_w = 14;
+    let chart_w = 24;
+
+    println!();
+    println!("COMPETITIVE");
+    println!("==================");
+
+    if matches.is_empty() {
+        println!("No competitive activities found");
+        return;
+    }
+
+    let header = format!(
+        "{:<0date_col_w$}{:<0map_col_w$}{:<0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}  {:<0rank_col_w$}",
+        "DATE",
+        "MAP",
+        "W/L",
+        "STREAK",
+        "GLORY",
+        "+/-",
+        "RANK",
+        date_col_w = date_col_w,
+        map_col_w = map_col_w,
+        col_w = col_w,
+        rank_col_w = rank_col_w,
+    );
+    println!("{}", header);
+    println!("{}", repeat_str("=", header.chars().count() + chart_w));
+
+    //chart is scaled between the lowest and highest glory so climbs and
+    //deranks are visible
+    let min = matches.iter().filter_map(|m| m.progress).min().unwrap_or(0);
+    let max = matches.iter().filter_map(|m| m.progress).max().unwrap_or(0);
+
+    let mut climbs = 0;
+    let mut deranks = 0;
+    let mut last_rank: Option<&String> = None;
+
+    for m in matches {
+        //inferred values are prefixed with ~
+        let prefix = if m.inferred { "~" } else { "" };
+
+        let progress = match m.progress {
+            Some(e) => format!("{}{}", prefix, e),
+            None => "".to_string(),
+        };
+
+        let change = match m.progress_change {
+            Some(e) if e > 0 => format!("{}+{}", prefix, e),
+            Some(e) => format!("{}{}", prefix, e),
+            None => "".to_string(),
+        };
+
+        let streak = match m.streak {
+            e if e > 0 => format!("W{}", e),
+            e if e < 0 => format!("L{}", -e),
+            _ => "".to_string(),
+        };
+
+        let bar = match m.progress {
+            Some(p) if max > min => repeat_str(
+                "#",
+                1 + (((p - min) as f32 / (max - min) as f32)
+                    * (chart_w - 1) as f32)
+                    .round() as usize,
+            ),
+            Some(_) => repeat_str("#", chart_w),
+            None => "".to_string(),
+        };
+
+        if let (Some(last), Some(rank)) = (last_rank, &m.rank) {
+            if last != rank {
+                if m.progress_change.unwrap_or(0) >= 0 {
+                    climbs += 1;
+                } else {
+                    deranks += 1;
+                }
+            }
+        }
+        if m.rank.is_some() {
+            last_rank = m.rank.as_ref();
+        }
+
+        println!(
+            "{:<0date_col_w$}{:<0map_col_w$}{:<0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}  {:<0rank_col_w$}{}",
+            m.activity_detail.period.format("%Y-%m-%d %H:%M").to_string(),
+            pad_string(
+                &truncate_string(&m.activity_detail.map_name, map_col_w - 2),
+                map_col_w
+            ),
+            format!("{}", m.standing),
+            streak,
+            progress,
+            change,
+            m.rank.as_deref().unwrap_or(""),
+            bar,
+            date_col_w = date_col_w,
+            map_col_w = map_col_w,
+            col_w = col_w,
+            rank_col_w = rank_col_w,
+        );
+    }
+
+    println!();
+    println!(
+        "Ranked up {} time{}, deranked {} time{}.",
+        climbs,
+        if climbs == 1 { "" } else { "s" },
+        deranks,
+        if deranks == 1 { "" } else { "s" },
+    );
+    println!("GLORY is recorded when syncing. Values starting with ~ are inferred from wins and losses.");
+}
+
+/// points for the competitive glory chart. Activities without recorded or
+/// inferred glory are skipped.
+pub fn competitive_history_points(
+    matches: &[CompetitiveMatch],
+) -> Vec<TrendPoint> {
+    matches
+        .iter()
+        .filter_map(|m| {
+            m.progress.map(|p| TrendPoint {
+                label: m.activity_detail.period.format("%Y-%m-%d").to_string(),
+                value: p as f32,
+            })
+        })
+        .collect()
+}
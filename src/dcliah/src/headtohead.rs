@@ -0,0 +1,102 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use dcli::crucible::{HeadToHeadStats, PlayerRecord, SharedActivity};
+use dcli::utils::{format_f32, human_date_format, repeat_str};
+
+/// prints the player's record in games with another player, split by
+/// whether they were teammates or opponents
+pub fn print_head_to_head(player: &str, activities: &[SharedActivity]) {
+    let name_col_w = 12;
+    let col_w = 10;
+
+    println!();
+    println!("HEAD TO HEAD : {}", player);
+    println!("==================");
+
+    let stats = HeadToHeadStats::with_activities(activities);
+
+    if stats.total_activities() == 0 {
+        println!("No games found with {}", player);
+        return;
+    }
+
+    let header = format!(
+        "{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+        "",
+        "GAMES",
+        "WINS",
+        "LOSSES",
+        "WIN %",
+        "K/D",
+        "THEIR K/D",
+        name_col_w = name_col_w,
+        col_w = col_w,
+    );
+    println!("{}", header);
+    println!("{}", repeat_str("=", header.chars().count()));
+
+    print_record("Teammate", &stats.teammate, name_col_w, col_w);
+    print_record("Opponent", &stats.opponent, name_col_w, col_w);
+
+    //activities are sorted oldest first
+    if let Some(last) = activities.last() {
+        println!();
+        println!(
+            "Last game together : {} ({}, {})",
+            human_date_format(&last.period),
+            last.mode,
+            last.relationship
+        );
+    }
+}
+
+fn print_record(
+    label: &str,
+    record: &PlayerRecord,
+    name_col_w: usize,
+    col_w: usize,
+) {
+    if record.activities == 0 {
+        println!(
+            "{:<0name_col_w$}{:>0col_w$}",
+            label,
+            "0",
+            name_col_w = name_col_w,
+            col_w = col_w,
+        );
+        return;
+    }
+
+    println!(
+        "{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+        label,
+        record.activities.to_string(),
+        record.wins.to_string(),
+        record.losses().to_string(),
+        format!("{}%", format_f32(record.win_rate(), 2)),
+        format_f32(record.kills_deaths_ratio(), 2),
+        format_f32(record.other_kills_deaths_ratio(), 2),
+        name_col_w = name_col_w,
+        col_w = col_w,
+    );
+}
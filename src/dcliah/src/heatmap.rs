@@ -0,0 +1,148 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::path::Path;
+
+use chrono::{Datelike, Duration, Local};
+use dcli::calendar::{
+    count_activities_by_day, group_weeks, heatmap_level, DayActivityCount,
+    HEATMAP_LEVELS, MAX_HEATMAP_DAYS,
+};
+use dcli::crucible::CruciblePlayerActivityPerformance;
+use dcli::enums::moment::DateTimePeriod;
+use dcli::error::Error;
+use dcli::render::chart::ChartConfig;
+use dcli::render::heatmap::draw_calendar_heatmap;
+use dcli::render::write_image;
+
+//character for each intensity level, from no activities to the most
+const LEVEL_CHARS: [char; HEATMAP_LEVELS] = ['·', '░', '▒', '▓', '█'];
+
+const DAY_LABELS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+//each week column is a level char followed by a space
+const CELL_W: usize = 2;
+
+/// prints a github style calendar heatmap of activities per day, with a
+/// column for each week and a row for each day of the week
+pub fn print_calendar_heatmap(days: &[DayActivityCount]) {
+    println!();
+    println!("CALENDAR");
+    println!("==================");
+
+    let weeks = group_weeks(days);
+    if weeks.is_empty() {
+        println!("No days in period.");
+        return;
+    }
+
+    let max = days.iter().map(|d| d.activities).max().unwrap_or(0);
+    let label_w = DAY_LABELS[0].len() + 2;
+
+    //month names above the first week of each month
+    let mut months = " ".repeat(label_w);
+    for (col, week) in weeks.iter().enumerate() {
+        let label_day = match week.iter().flatten().find(|d| d.day.day() == 1) {
+            Some(d) => Some(d.day),
+            None if col == 0 => week.iter().flatten().next().map(|d| d.day),
+            None => None,
+        };
+
+        let x = label_w + col * CELL_W;
+        if let Some(day) = label_day {
+            //skip labels which would overlap the previous one
+            if months.chars().count() <= x {
+                let pad = x - months.chars().count();
+                months.push_str(&" ".repeat(pad));
+                months.push_str(&day.format("%b").to_string());
+                months.push(' ');
+            }
+        }
+    }
+    println!("{}", months.trim_end());
+
+    for (row, label) in DAY_LABELS.iter().enumerate() {
+        let mut line = format!("{:<0label_w$}", label, label_w = label_w);
+        for week in &weeks {
+            match week[row] {
+                Some(d) => {
+                    line.push(LEVEL_CHARS[heatmap_level(d.activities, max)])
+                }
+                None => line.push(' '),
+            }
+            line.push(' ');
+        }
+        println!("{}", line.trim_end());
+    }
+
+    let total: u32 = days.iter().map(|d| d.activities).sum();
+    let legend: Vec<String> =
+        LEVEL_CHARS.iter().map(|c| c.to_string()).collect();
+
+    println!();
+    println!(
+        "{:<0label_w$}Less {} More",
+        "",
+        legend.join(" "),
+        label_w = label_w
+    );
+    println!(
+        "{:<0label_w$}{} activities over {} days. Most in a day : {}",
+        "",
+        total,
+        days.len(),
+        max,
+        label_w = label_w
+    );
+}
+
+/// days in the calendar heatmap, limited to the last year of the time
+/// period
+pub fn retrieve_calendar_days(
+    data: &[CruciblePlayerActivityPerformance],
+    time_period: &DateTimePeriod,
+) -> Vec<DayActivityCount> {
+    let end = time_period
+        .get_end()
+        .with_timezone(&Local)
+        .date()
+        .naive_local();
+    let start = time_period
+        .get_start()
+        .with_timezone(&Local)
+        .date()
+        .naive_local()
+        .max(end - Duration::days(MAX_HEATMAP_DAYS - 1));
+
+    count_activities_by_day(data, start, end)
+}
+
+/// writes the calendar heatmap to an image at path
+pub fn write_calendar_chart(
+    path: &Path,
+    data_dir: &Path,
+    days: &[DayActivityCount],
+) -> Result<(), Error> {
+    let config = ChartConfig::load(data_dir)?;
+    let drawing = draw_calendar_heatmap("GAMES PLAYED", days, &config);
+    write_image(path, &drawing)
+}
@@ -0,0 +1,103 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use dcli::crucible::LobbyRatingPeriod;
+use dcli::render::chart::TrendPoint;
+use dcli::utils::{format_f32, repeat_str};
+
+/// prints the average combat rating of the other players in the player's
+/// games, by day
+pub fn print_lobby_ratings(ratings: &[LobbyRatingPeriod]) {
+    let date_col_w = 14;
+    let col_w = 10;
+    let chart_w = 30;
+
+    println!();
+    println!("LOBBY RATING");
+    println!("==================");
+
+    if ratings.is_empty() {
+        println!("No ratings found. Ratings are recorded when viewing activities with dcliad.");
+        return;
+    }
+
+    let header = format!(
+        "{:<0date_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}  {}",
+        "DATE",
+        "GAMES",
+        "PLAYERS",
+        "RATING",
+        "",
+        date_col_w = date_col_w,
+        col_w = col_w,
+    );
+    println!("{}", header);
+    println!("{}", repeat_str("=", header.chars().count() + chart_w));
+
+    //chart is scaled between the lowest and highest ratings so changes are
+    //visible
+    let min = ratings
+        .iter()
+        .map(|r| r.average_rating)
+        .fold(f32::MAX, f32::min);
+    let max = ratings
+        .iter()
+        .map(|r| r.average_rating)
+        .fold(f32::MIN, f32::max);
+
+    for r in ratings {
+        let bar_w = if max - min > 0.0 {
+            1 + (((r.average_rating - min) / (max - min))
+                * (chart_w - 1) as f32)
+                .round() as usize
+        } else {
+            chart_w
+        };
+
+        println!(
+            "{:<0date_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}  {}",
+            r.day.format("%Y-%m-%d").to_string(),
+            r.activities.to_string(),
+            r.rated_players.to_string(),
+            format_f32(r.average_rating, 0),
+            repeat_str("#", bar_w),
+            date_col_w = date_col_w,
+            col_w = col_w,
+        );
+    }
+
+    println!();
+    println!(
+        "RATING is the average combat rating of other players in your games."
+    );
+}
+
+/// points for the lobby rating chart
+pub fn lobby_rating_points(ratings: &[LobbyRatingPeriod]) -> Vec<TrendPoint> {
+    ratings
+        .iter()
+        .map(|r| TrendPoint {
+            label: r.day.format("%Y-%m-%d").to_string(),
+            value: r.average_rating,
+        })
+        .collect()
+}
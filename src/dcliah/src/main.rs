@@ -20,34 +20,70 @@
 * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 */
 
+mod characters;
+mod comphistory;
+mod headtohead;
+mod heatmap;
+mod lobbyrating;
+mod mapmatrix;
+mod mapmodes;
+mod mapstats;
+mod modecolumns;
+mod openingduels;
+mod opponentclasses;
+mod opponentplatforms;
+mod queuetime;
+mod reports;
+mod summary;
+mod weaponinsights;
+
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::str::FromStr;
 
 use chrono::{DateTime, Utc};
+use dcli::cancellation::CancellationToken;
+use dcli::datadir;
+use dcli::demo;
+use dcli::enums::language::Language;
+use dcli::enums::moment::{DateTimePeriod, Moment};
+use dcli::enums::platform::Platform;
 use dcli::enums::standing::Standing;
-use dcli::enums::{
-    completionreason::CompletionReason,
-    moment::{DateTimePeriod, Moment},
+use dcli::enums::statprofile::StatProfile;
+use dcli::error::Error;
+use dcli::export::{
+    activity_performance_columns, activity_performance_values, CsvWriter,
 };
 use dcli::manifestinterface::ManifestInterface;
+use dcli::output::Output;
+use dcli::ratelimit::{self, parse_max_rps};
+use dcli::timing::{self, TimingCategory};
 use dcli::{
     crucible::{
-        AggregateCruciblePerformances, CruciblePlayerActivityPerformance,
-        CruciblePlayerPerformance,
+        AggregateCruciblePerformances, CruciblePerformanceAccumulator,
+        CruciblePlayerActivityPerformance, PartitionedPerformanceAccumulator,
+        WeaponStat,
     },
     enums::mode::Mode,
     utils::{calculate_ratio, human_duration},
 };
-use dcli::{enums::platform::Platform, utils::calculate_percent};
+use heatmap::retrieve_calendar_days;
+use modecolumns::{format_mode_cells, ModeColumn};
+use reports::{print_reports, write_chart, StoreReports};
+use summary::{previous_period, write_summary, Summary};
 
-use dcli::enums::character::CharacterClassSelection;
+use dcli::enums::character::{
+    CharacterClass, CharacterClassSelection, CharacterReport,
+};
 use dcli::enums::weaponsort::WeaponSort;
 
-use dcli::activitystoreinterface::ActivityStoreInterface;
+use dcli::activitystoreinterface::{
+    ActivityStoreInterface, Season, SyncHistoryEntry,
+};
 
 use dcli::utils::{
-    determine_data_dir, format_f32, human_date_format, repeat_str,
-    uppercase_first_char,
+    determine_data_dir, format_f32, human_date_format, pad_string, repeat_str,
+    truncate_string, uppercase_first_char, TextColors,
 };
 //use dcli::utils::EXIT_FAILURE;
 use dcli::utils::EXIT_FAILURE;
@@ -58,13 +94,36 @@ use structopt::StructOpt;
 fn parse_and_validate_mode(src: &str) -> Result<Mode, String> {
     let mode = Mode::from_str(src)?;
 
-    if !mode.is_crucible() {
+    if !mode.is_crucible() && !mode.is_gambit() {
         return Err(format!("Unsupported mode specified : {}", src));
     }
 
     Ok(mode)
 }
 
+fn print_last_sync(last_sync: &SyncHistoryEntry) {
+    let elapsed = (Utc::now() - last_sync.finished_at).num_seconds().max(0);
+
+    //seconds are just noise once it has been more than a minute
+    let elapsed = if elapsed >= 60 {
+        elapsed - elapsed % 60
+    } else {
+        elapsed
+    };
+
+    let age = if elapsed == 0 {
+        "just now".to_string()
+    } else {
+        format!("{} ago", human_duration(elapsed as u32))
+    };
+
+    println!();
+    match &last_sync.error {
+        None => println!("Data as of {}", age),
+        Some(e) => println!("Last sync failed {} : {}", age, e),
+    }
+}
+
 //TODO: we may not need custom validation here now
 fn parse_and_validate_moment(src: &str) -> Result<Moment, String> {
     let moment = Moment::from_str(src)?;
@@ -72,15 +131,20 @@ fn parse_and_validate_moment(src: &str) -> Result<Moment, String> {
     Ok(moment)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn print_default(
-    data: &[CruciblePlayerActivityPerformance],
-    activity_limit: &u32,
+    aggregate: &AggregateCruciblePerformances,
+    recent: &[CruciblePlayerActivityPerformance],
     mode: &Mode,
     time_period: &DateTimePeriod,
     moment: &Moment,
     end_moment: &Moment,
+    season: Option<&Season>,
     weapon_count: &u32,
     weapon_sort: &WeaponSort,
+    merge_weapon_variants: bool,
+    colors: &TextColors,
+    mode_names: &HashMap<u32, String>,
 ) {
     //todo: might want to look at buffering output
     //https://rust-cli.github.io/book/tutorial/output.html
@@ -88,15 +152,11 @@ fn print_default(
     let start_time = time_period.get_start();
     let end_time = time_period.get_end();
 
-    let performances = data;
-
-    let cpp: Vec<&CruciblePlayerPerformance> =
-        performances.iter().map(|x| &x.performance).collect();
-    let aggregate = AggregateCruciblePerformances::with_performances(&cpp);
+    //recent only contains the most recent activities we display details
+    //for, the totals come from the aggregate which covers all of them
+    let activity_count = aggregate.total_activities as usize;
 
-    let activity_count = performances.len();
-
-    let display_count = std::cmp::min(activity_count, *activity_limit as usize);
+    let display_count = recent.len();
     let is_limited = activity_count != display_count;
 
     let start_time_label = human_date_format(&start_time);
@@ -105,17 +165,26 @@ fn print_default(
     println!();
     println!();
 
-    let title = if end_moment == &Moment::Now {
+    let title = if let Some(season) = season {
+        format!(
+            "{mode} activities in Season {number} : {name} ({start_time} to {end_time})",
+            mode = uppercase_first_char(&mode.display_name(mode_names)),
+            number = season.season_number,
+            name = season.name,
+            start_time = start_time_label,
+            end_time = end_time_label,
+        )
+    } else if end_moment == &Moment::Now {
         format!(
             "{mode} activities since {start_time} ({moment})",
-            mode = uppercase_first_char(&format!("{}", mode)),
+            mode = uppercase_first_char(&mode.display_name(mode_names)),
             start_time = start_time_label,
             moment = moment,
         )
     } else {
         format!(
             "{mode} activities from {start_time} ({moment}) to {end_time} ({end_moment})",
-            mode = uppercase_first_char(&format!("{}", mode)),
+            mode = uppercase_first_char(&mode.display_name(mode_names)),
             start_time = start_time_label,
             moment = moment,
             end_time = end_time_label,
@@ -154,6 +223,13 @@ fn print_default(
     }
     println!();
 
+    //the last stat columns depend on the mode being displayed
+    let mode_columns = ModeColumn::for_profile(&StatProfile::from_mode(mode));
+    let mode_headers: Vec<String> = mode_columns
+        .iter()
+        .map(|c| c.header().to_string())
+        .collect();
+
     let col_w = 8;
     let wl_col_w = 14;
     let map_col_w = 18;
@@ -162,7 +238,7 @@ fn print_default(
 
     //TODO: maybe format this to yellow background
     let header = format!(
-        "{:<0map_col_w$}{:<0wl_col_w$}{:>0str_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0id_col_w$}",
+        "{:<0map_col_w$}{:<0wl_col_w$}{:>0str_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{}{:>0id_col_w$}",
         "MAP",
         "W/L",
         "STREAK",
@@ -173,10 +249,7 @@ fn print_default(
         "K/D",
         "KD/A",
         "EFF",
-        "SUP",
-        "GREN",
-        "MEL",
-        "MERCY",
+        format_mode_cells(&mode_headers, col_w),
         "INDEX",
         col_w = col_w,
         map_col_w = map_col_w,
@@ -185,34 +258,35 @@ fn print_default(
         id_col_w=id_col_w,
     );
     println!("{}", header);
-    let header_divider = repeat_str(&"=", header.chars().count());
+    let header_divider = repeat_str("=", header.chars().count());
     println!("{}", header_divider);
 
-    let slice: &[CruciblePlayerActivityPerformance] = if is_limited {
+    if is_limited {
         println!(
-            "{:<0map_col_w$}{:<0wl_col_w$}{:>0str_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0id_col_w$}",
-            "...", "...", "...", "...", "...", "...", "...","...","...","...","...","...", "...", "...","...",
+            "{:<0map_col_w$}{:<0wl_col_w$}{:>0str_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{}{:>0id_col_w$}",
+            "...", "...", "...", "...", "...", "...", "...","...","...","...",
+            format_mode_cells(&vec!["...".to_string(); mode_columns.len()], col_w),
+            "...",
             col_w = col_w,
             map_col_w = map_col_w,
             str_col_w=str_col_w,
             wl_col_w=wl_col_w,
             id_col_w = id_col_w,
         );
-
-        &performances[..*activity_limit as usize]
-    } else {
-        &performances[..]
-    };
+    }
 
     let mut last_mode = Mode::None;
     let mut streak: i32 = 0;
     let mut last_standing: Standing = Standing::Unknown;
 
-    for activity in slice.iter().rev() {
+    for activity in recent.iter().rev() {
         if activity.activity_detail.mode != last_mode {
             println!();
-            println!("{}", activity.activity_detail.mode);
-            println!("{}", repeat_str(&"-", col_w + map_col_w));
+            println!(
+                "{}",
+                activity.activity_detail.mode.display_name(mode_names)
+            );
+            println!("{}", repeat_str("-", col_w + map_col_w));
             last_mode = activity.activity_detail.mode;
         }
 
@@ -232,46 +306,42 @@ fn print_default(
             };
         }
 
-        let mut map_name = activity.activity_detail.map_name.clone();
-
-        //todo: move this into reusable util function
-        if map_name.chars().count() > map_col_w - 1 {
-            map_name = map_name[..(col_w - 3)].to_string();
-            map_name.push_str("..")
-        }
-
-        let extended = activity.performance.stats.extended.as_ref().unwrap();
-        let supers = extended.weapon_kills_super;
-        let grenades = extended.weapon_kills_grenade;
-        let melees = extended.weapon_kills_melee;
+        //leave a space between the map name and the next column
+        let map_name = pad_string(
+            &truncate_string(&activity.activity_detail.map_name, map_col_w - 1),
+            map_col_w,
+        );
 
-        let mercy_str = if activity.performance.stats.completion_reason
-            == CompletionReason::Mercy
-        {
-            "X"
-        } else {
-            ""
-        };
+        let mode_values: Vec<String> = mode_columns
+            .iter()
+            .map(|c| c.activity_value(activity))
+            .collect();
 
         println!(
-            "{:<0map_col_w$}{:<0wl_col_w$}{:>0str_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0id_col_w$}",
+            "{}{:<0wl_col_w$}{:>0str_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{}{:>0id_col_w$}",
             map_name,
-            activity.performance.stats.standing.to_string(),
+            colors.standing(
+                &format!("{:<0wl_col_w$}", standing, wl_col_w = wl_col_w),
+                standing
+            ),
             streak.to_string(),
             activity.performance.stats.kills.to_string(),
             activity.performance.stats.assists.to_string(),
             activity.performance.stats.opponents_defeated.to_string(),
             activity.performance.stats.deaths.to_string(),
-            format_f32(activity.performance.stats.kills_deaths_ratio, 2),
+            colors.kills_deaths_ratio(
+                &format!(
+                    "{:>0col_w$}",
+                    format_f32(activity.performance.stats.kills_deaths_ratio, 2),
+                    col_w = col_w
+                ),
+                activity.performance.stats.kills_deaths_ratio
+            ),
             format_f32(activity.performance.stats.kills_deaths_assists, 2),
             format_f32(activity.performance.stats.efficiency, 2),
-            supers.to_string(),
-            grenades.to_string(),
-            melees.to_string(),
-            mercy_str,
+            format_mode_cells(&mode_values, col_w),
             activity.activity_detail.index_id.to_string(),
             col_w = col_w,
-            map_col_w=map_col_w,
             str_col_w=str_col_w,
             wl_col_w=wl_col_w,
             id_col_w=id_col_w,
@@ -279,9 +349,9 @@ fn print_default(
     }
 
     let extended = aggregate.extended.as_ref().unwrap();
-    println!("{}", repeat_str(&"-", header.chars().count()));
+    println!("{}", repeat_str("-", header.chars().count()));
 
-    println!("{:<0map_col_w$}{:<0wl_col_w$}{:>0str_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0id_col_w$}",
+    println!("{:<0map_col_w$}{:<0wl_col_w$}{:>0str_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{}{:>0id_col_w$}",
     "TOTAL",
     aggregate.total_activities.to_formatted_string(&Locale::en),
     "",
@@ -292,10 +362,7 @@ fn print_default(
     "".to_string(),
     "".to_string(),
     "".to_string(),
-    extended.weapon_kills_super.to_formatted_string(&Locale::en),
-    extended.weapon_kills_grenade.to_formatted_string(&Locale::en),
-    extended.weapon_kills_melee.to_formatted_string(&Locale::en),
-    aggregate.total_mercy.to_string(),
+    format_mode_cells(&mode_columns.iter().map(|c| c.total_value(aggregate)).collect::<Vec<String>>(), col_w),
     "",
     col_w = col_w,
     map_col_w=map_col_w,
@@ -304,7 +371,7 @@ fn print_default(
     id_col_w=id_col_w,
     );
 
-    println!("{:<0map_col_w$}{:<0wl_col_w$}{:>0str_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0id_col_w$}",
+    println!("{:<0map_col_w$}{:<0wl_col_w$}{:>0str_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{}{:>0id_col_w$}",
     "HIGH",
     format!("{}-{}", aggregate.wins.to_formatted_string(&Locale::en), aggregate.losses.to_formatted_string(&Locale::en)),
     format!("{}W {}L", aggregate.longest_win_streak, aggregate.longest_loss_streak),
@@ -316,10 +383,7 @@ fn print_default(
     format_f32(aggregate.highest_kills_deaths_ratio, 2),
     format_f32(aggregate.highest_kills_deaths_assists, 2),
     format_f32(aggregate.highest_efficiency, 2),
-    format!("{}", extended.highest_weapon_kills_super),
-    format!("{}", extended.highest_weapon_kills_grenade),
-    format!("{}", extended.highest_weapon_kills_melee),
-    "",
+    format_mode_cells(&mode_columns.iter().map(|c| c.high_value(aggregate)).collect::<Vec<String>>(), col_w),
     "",
 
     col_w = col_w,
@@ -329,7 +393,7 @@ fn print_default(
     id_col_w=id_col_w,
     );
 
-    println!("{:<0map_col_w$}{:<0wl_col_w$}{:>0str_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0id_col_w$}",
+    println!("{:<0map_col_w$}{:<0wl_col_w$}{:>0str_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{}{:>0id_col_w$}",
     "PER GAME",
     format!("{}%", format_f32(aggregate.win_rate, 2)),
     "",
@@ -337,13 +401,10 @@ fn print_default(
     format_f32(aggregate.stat_per_game(aggregate.assists), 2),
     format_f32(aggregate.stat_per_game(aggregate.opponents_defeated), 2),
     format_f32(aggregate.stat_per_game(aggregate.deaths), 2),
-    format_f32(aggregate.kills_deaths_ratio, 2),
+    colors.kills_deaths_ratio(&format!("{:>0col_w$}", format_f32(aggregate.kills_deaths_ratio, 2), col_w = col_w), aggregate.kills_deaths_ratio),
     format_f32(aggregate.kills_deaths_assists, 2),
     format_f32(aggregate.efficiency, 2),
-    format_f32(aggregate.stat_per_game(extended.weapon_kills_super), 2),
-    format_f32(aggregate.stat_per_game(extended.weapon_kills_grenade), 2),
-    format_f32(aggregate.stat_per_game(extended.weapon_kills_melee), 2),
-    format!("{}%",format_f32(calculate_percent(aggregate.total_mercy, aggregate.total_activities), 2)),
+    format_mode_cells(&mode_columns.iter().map(|c| c.per_game_value(aggregate)).collect::<Vec<String>>(), col_w),
     "",
     col_w = col_w,
     map_col_w=map_col_w,
@@ -374,12 +435,17 @@ fn print_default(
         map_col_w = wep_col,
     );
 
-    let wep_divider = repeat_str(&"=", wep_header_str.chars().count());
+    let wep_divider = repeat_str("=", wep_header_str.chars().count());
 
     println!("{}", wep_header_str);
     println!("{}", wep_divider);
 
-    let mut weapons = extended.weapons.clone();
+    let mut weapons = if merge_weapon_variants {
+        WeaponStat::merge_variants(&extended.weapons)
+    } else {
+        extended.weapons.clone()
+    };
+
     match weapon_sort {
         WeaponSort::Name => {
             weapons.sort_by(|a, b| {
@@ -394,7 +460,7 @@ fn print_default(
             //weapons.sort_by(|a, b| b.kills.cmp(&a.kills));
         }
         WeaponSort::Games => {
-            weapons.sort_by(|a, b| b.activity_count.cmp(&a.activity_count));
+            weapons.sort_by_key(|b| std::cmp::Reverse(b.activity_count));
         }
         WeaponSort::KillsPerGameKills => {
             weapons.sort_by(|a, b| {
@@ -440,8 +506,8 @@ fn print_default(
 
     for w in &weapons[..max_weps] {
         println!(
-            "{:<0map_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0map_col_w$}",
-            w.weapon.name,
+            "{}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0map_col_w$}",
+            pad_string(&w.weapon.name, wep_col),
             w.activity_count.to_formatted_string(&Locale::en),
             w.kills.to_formatted_string(&Locale::en),
             format!("{}%", format_f32((w.kills as f32 / aggregate.kills as f32) * 100.0, 2)),
@@ -461,6 +527,25 @@ fn print_default(
     println!();
 }
 
+fn write_csv(data: &[CruciblePlayerActivityPerformance]) -> Result<(), Error> {
+    let stdout = std::io::stdout();
+    let mut writer = CsvWriter::new(std::io::BufWriter::new(stdout.lock()));
+
+    writer.write_row(&activity_performance_columns())?;
+    for d in data {
+        writer.write_row(&activity_performance_values(d))?;
+    }
+
+    writer.flush()
+}
+
+fn print_csv(data: &[CruciblePlayerActivityPerformance]) {
+    if let Err(e) = write_csv(data) {
+        print_error("Error writing csv.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+}
+
 fn parse_rfc3339(src: &str) -> Result<DateTime<Utc>, String> {
     let d =
         match DateTime::parse_from_rfc3339(src) {
@@ -500,14 +585,25 @@ struct Opt {
     /// Destiny 2 API member id
     ///
     /// This is not the user name, but the member id retrieved from the Destiny API.
-    #[structopt(short = "m", long = "member-id", required = true)]
-    member_id: String,
+    /// Can also be a player alias, or the display name of a player in synced
+    /// activities (add the platform, i.e. name:steam, if more than one player
+    /// has the name).
+    #[structopt(
+        short = "m",
+        long = "member-id",
+        required_unless_one = &["demo", "print-paths"]
+    )]
+    member_id: Option<String>,
 
     /// Platform for specified id
     ///
     /// Valid values are: xbox, playstation, stadia or steam.
-    #[structopt(short = "p", long = "platform", required = true)]
-    platform: Platform,
+    #[structopt(
+        short = "p",
+        long = "platform",
+        required_unless_one = &["demo", "print-paths"]
+    )]
+    platform: Option<Platform>,
 
     /// Custom start time in RFC 3339 date / time format
     ///
@@ -581,6 +677,17 @@ struct Opt {
         short = "E", default_value = "now")]
     end_moment: Moment,
 
+    /// Season to pull activities from
+    ///
+    /// Activities will be retrieved from the start to the end of the season
+    /// with the specified season number (i.e. 15 for Season of the Lost).
+    /// Overrides --moment and --end-moment.
+    ///
+    /// Season dates are loaded from the manifest when dclias syncs, so the
+    /// manifest must have been downloaded with dclim.
+    #[structopt(long = "season")]
+    season: Option<u32>,
+
     /// Activity mode to return stats for
     ///
     /// Supported values are all_pvp (default), control, clash, elimination,
@@ -591,6 +698,8 @@ struct Opt {
     /// countdown, all_doubles, doubles, private_clash, private_control,
     /// private_survival, private_rumble, showdown, lockdown,
     /// scorched, scorched_team, breakthrough, clash_quickplay, trials_of_the_nine
+    ///
+    /// Gambit values are all_gambit, gambit and gambit_prime.
     #[structopt(long = "mode", short = "M", 
         parse(try_from_str=parse_and_validate_mode), default_value = "all_pvp")]
     mode: Mode,
@@ -611,6 +720,15 @@ struct Opt {
     #[structopt(short = "C", long = "class", default_value = "last_active")]
     character_class_selection: CharacterClassSelection,
 
+    /// How stats for more than one character are reported
+    ///
+    /// Valid values are combined (default), which merges stats for all
+    /// selected characters, and per_character, which also displays a
+    /// breakdown of the stats for each character. Mostly useful with
+    /// --class all.
+    #[structopt(long = "character-report", default_value = "combined")]
+    character_report: CharacterReport,
+
     /// Specify weapon stats sort order
     ///
     /// Valid values include name, kills (default), games, kills_per_game_kills,
@@ -618,39 +736,317 @@ struct Opt {
     #[structopt(short = "W", long = "weapon-sort", default_value = "kills")]
     weapon_sort: WeaponSort,
 
+    /// Merge weapon variants into their base weapon
+    ///
+    /// Combines stats for (Adept), (Timelost) and (Harrowed) versions of
+    /// weapons with the base version of the weapon.
+    #[structopt(long = "merge-weapon-variants")]
+    merge_weapon_variants: bool,
+
+    /// Display a win / loss matrix by map for recent sessions
+    ///
+    /// Maps are displayed as rows and the most recent play sessions as columns,
+    /// with the result of each game displayed as W or L.
+    #[structopt(long = "map-matrix")]
+    map_matrix: bool,
+
+    /// Display stats grouped by map and mode pairing
+    ///
+    /// Displays a row for each map, with stats for each mode played on that
+    /// map beneath it (i.e. Control on Burnout vs Clash on Burnout).
+    #[structopt(long = "map-modes")]
+    map_modes: bool,
+
+    /// Display estimated queue time between matches for each session
+    ///
+    /// Uses the gaps between consecutive matches within a play session to
+    /// estimate time spent in orbit and matchmaking versus time spent in
+    /// matches. Gaps of more than 15 minutes are treated as breaks.
+    #[structopt(long = "queue-time")]
+    queue_time: bool,
+
+    /// Display a calendar heatmap of games played per day
+    ///
+    /// Days are displayed as a grid with a column for each week, and shaded
+    /// by the number of games played, relative to the day with the most
+    /// games. Displays up to the last year of the time period. Use with
+    /// --chart to save the heatmap as an image.
+    #[structopt(long = "calendar")]
+    calendar: bool,
+
+    /// Number of recent sessions to display in the map matrix
+    #[structopt(long = "matrix-sessions", default_value = "7")]
+    matrix_sessions: usize,
+
+    /// Display opening duel (first blood) stats per mode
+    #[structopt(long = "opening-duels")]
+    opening_duels: bool,
+
+    /// Display weapon type insights
+    ///
+    /// Compares your K/D in activities where you used each weapon type against
+    /// your overall K/D, and suggests over / under performing weapon types.
+    #[structopt(long = "weapon-insights")]
+    weapon_insights: bool,
+
+    /// Display the platforms of opponents faced
+    ///
+    /// Shows how many opponents were faced from each platform, and your win
+    /// rate in games against opponents from that platform. Includes all
+    /// characters.
+    #[structopt(long = "opponent-platforms")]
+    opponent_platforms: bool,
+
+    /// Display games, wins, losses, K/D and efficiency for each map
+    ///
+    /// Maps are sorted by the number of games played on them. Includes all
+    /// characters.
+    #[structopt(long = "maps")]
+    maps: bool,
+
+    /// Display the class composition of opposing teams
+    ///
+    /// Shows how many opponents of each class were faced, your win rate by
+    /// the number of opponents of each class on the other team (i.e. against
+    /// triple Titan teams), and your win rate against the most common team
+    /// compositions. Includes all characters.
+    #[structopt(long = "opponent-classes")]
+    opponent_classes: bool,
+
+    /// Display your record with and against the specified player
+    ///
+    /// Shows your wins and losses in games where the player was on your team,
+    /// and in games where they were an opponent, along with both of your
+    /// K/D. The player can be a member id, player alias or the display name
+    /// of a player in synced activities (i.e. name:steam). Includes all
+    /// characters.
+    #[structopt(long = "head-to-head")]
+    head_to_head: Option<String>,
+
+    /// Display the average lobby rating trend
+    ///
+    /// Charts the average combat rating of the other players in your games
+    /// for each day. Ratings are recorded when activities are viewed with
+    /// dcliad, so only those activities are included. Includes all characters.
+    #[structopt(long = "lobby-rating")]
+    lobby_rating: bool,
+
+    /// Display competitive glory and rank history
+    ///
+    /// Lists competitive and survival activities (for all characters) with
+    /// the result, streak, glory and rank after each one, and a chart of
+    /// climbs and deranks. Glory is recorded each time activities are
+    /// synced, and inferred from wins and losses for other activities.
+    /// Use a season --moment (i.e. season_of_the_splicer) to see a season.
+    #[structopt(long = "comp-ranks")]
+    comp_ranks: bool,
+
+    /// Write the --lobby-rating, --comp-ranks or --calendar chart to an image file
+    ///
+    /// The format is determined by the file extension, and can be png or svg.
+    /// Requires one of --lobby-rating, --comp-ranks or --calendar. The chart size and
+    /// theme (dark or light) can be set in a charts.json file in the config
+    /// directory (see --print-paths), i.e.
+    /// {"width" : 1024, "height" : 512, "theme" : "light"}
+    #[structopt(long = "chart", parse(from_os_str))]
+    chart: Option<PathBuf>,
+
+    /// Write a summary of the time period to the specified file
+    ///
+    /// The format is determined by the file extension, and can be html or md
+    /// (markdown). The summary includes games played, the best game (by
+    /// efficiency), the most used weapons and trends compared to the previous
+    /// period of the same length. It is meant to be generated on a schedule
+    /// (i.e. weekly with the default --moment) and emailed or posted by
+    /// other tools. Output is still printed to the console.
+    #[structopt(long = "summary", parse(from_os_str))]
+    summary: Option<PathBuf>,
+
+    /// Format for command output
+    ///
+    /// Valid values are default (Default) and csv.
+    ///
+    /// csv outputs a row for each activity in the time period, with all of the
+    /// stats for the activity, in a format which can be opened in spreadsheet
+    /// apps. Other display options are ignored.
+    #[structopt(
+        short = "O",
+        long = "output-format",
+        default_value = "default"
+    )]
+    output: Output,
+
+    /// Only write structured data to stdout
+    ///
+    /// All other messages (i.e. sync progress and errors) are written to
+    /// stderr. Uses csv output.
+    #[structopt(long = "porcelain")]
+    porcelain: bool,
+
     ///Print out additional information
     ///
     ///Output is printed to stderr.
     #[structopt(short = "v", long = "verbose")]
     verbose: bool,
 
+    /// Color output
+    ///
+    /// Wins and losses are colored green and red, as are K/D values above and
+    /// below 1.0. Ignored if the NO_COLOR environment variable is set.
+    #[structopt(long = "color")]
+    color: bool,
+
+    /// Print how long was spent in API calls, SQL queries and formatting
+    ///
+    /// Timings are printed to stderr when the command finishes, and can help
+    /// diagnose slow commands.
+    #[structopt(long = "timing")]
+    timing: bool,
+
+    /// Maximum number of API requests to make per second when syncing
+    ///
+    /// Caps the rate of requests to the Destiny API, for when the API key or
+    /// IP rate budget is shared with other tools. Can also be set with the
+    /// DCLI_MAX_RPS environment variable. By default requests are not capped.
+    #[structopt(long = "max-rps", parse(try_from_str = parse_max_rps))]
+    max_rps: Option<f64>,
+
     /// Don't sync activities
     ///
     /// If flag is set, activities will not be retrieved before displaying stats.
     /// This is useful in case you are syncing activities in a seperate process.
+    /// The time of the last sync is displayed with the stats.
     #[structopt(short = "N", long = "no-sync")]
     no_sync: bool,
 
+    /// Include recent activities which have not been synced yet
+    ///
+    /// Used with --no-sync when activities are synced by another process.
+    /// The most recent activities within the time period which are not in the
+    /// activity store are retrieved from the Destiny API and included in the
+    /// stats, but are not saved to the activity store.
+    #[structopt(long = "fetch-unsynced", requires = "no-sync")]
+    fetch_unsynced: bool,
+
+    /// Display stats from bundled sample data
+    ///
+    /// Uses a small sample activity store and manifest included with dcli,
+    /// so you can see the output without an API key, member id or syncing
+    /// any activities. --member-id, --platform, --class and --data-dir are
+    /// ignored, and activities are not synced.
+    #[structopt(long = "demo")]
+    demo: bool,
+
     /// Directory where Destiny 2 manifest and activity database files are stored. (optional)
     ///
     /// This will normally be downloaded using the dclim and dclias tools, and uses
     /// a system appropriate directory by default.
     #[structopt(short = "D", long = "data-dir", parse(from_os_str))]
     data_dir: Option<PathBuf>,
+
+    /// Print the paths of the files and directories used by dcli and exit
+    ///
+    /// Includes the data directory, manifest, activity store and the cache,
+    /// config and backup directories.
+    #[structopt(long = "print-paths")]
+    print_paths: bool,
+
+    /// Name of the manifest to use (optional)
+    ///
+    /// Defaults to the current (live) manifest. dclim keeps the previously
+    /// downloaded manifest under the name previous, and can download manifests
+    /// into other named slots with its --manifest-name flag.
+    #[structopt(
+        long = "manifest-name",
+        default_value = "live",
+        parse(try_from_str = datadir::parse_manifest_name)
+    )]
+    manifest_name: String,
+
+    /// Language to display weapon, medal and map names in (optional)
+    ///
+    /// Defaults to en. Names are looked up in the manifest for the language,
+    /// which must first be downloaded with dclim --language. Stored activity
+    /// data does not need to be synced again.
+    ///
+    /// Valid values are en, fr, es, es-mx, de, it, ja, pt-br, ru, pl, ko,
+    /// zh-cht and zh-chs.
+    #[structopt(long = "language", default_value = "en")]
+    language: Language,
 }
 #[tokio::main]
 async fn main() {
-    let opt = Opt::from_args();
+    let mut opt = Opt::from_args();
     print_verbose(&format!("{:#?}", opt), opt.verbose);
 
-    let data_dir = match determine_data_dir(opt.data_dir) {
-        Ok(e) => e,
-        Err(e) => {
-            print_error("Error initializing manifest directory.", e);
-            std::process::exit(EXIT_FAILURE);
+    let _timing = timing::report(opt.timing);
+
+    if opt.max_rps.is_some() {
+        ratelimit::set_max_requests_per_second(opt.max_rps);
+    }
+
+    let supported_output = [Output::Default, Output::Csv];
+    if let Err(e) = opt.output.check_supported(&supported_output) {
+        print_error("Invalid output format.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    if opt.porcelain {
+        opt.output = match opt.output.porcelain(&supported_output) {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Invalid output format.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+    }
+
+    let chart_sources = [opt.lobby_rating, opt.comp_ranks, opt.calendar];
+    if opt.chart.is_some() && chart_sources.iter().filter(|e| **e).count() != 1
+    {
+        eprintln!("--chart requires one of --lobby-rating, --comp-ranks or --calendar");
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    let data_dir = if opt.demo {
+        match demo::create_demo_data_dir().await {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Error creating demo data.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        }
+    } else {
+        match determine_data_dir(opt.data_dir.clone()) {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Error initializing manifest directory.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
         }
     };
 
+    if opt.print_paths {
+        datadir::print_paths(&data_dir);
+        return;
+    }
+
+    //structopt ensures these are set when not running in demo mode. Class
+    //selections other than all require an api call, so demo always uses all
+    let (member_id, platform, character_class_selection) = if opt.demo {
+        (
+            demo::DEMO_MEMBER_ID.to_string(),
+            demo::DEMO_PLATFORM,
+            &CharacterClassSelection::All,
+        )
+    } else {
+        (
+            opt.member_id.clone().unwrap(),
+            opt.platform.unwrap(),
+            &opt.character_class_selection,
+        )
+    };
+
     let start_time = match opt.moment {
         Moment::Custom => {
             opt.custom_time.unwrap() //note, this should be ok, because struct opt should ensure valid value
@@ -674,21 +1070,53 @@ async fn main() {
             }
         };
 
-    let mut store =
-        match ActivityStoreInterface::init_with_path(&data_dir, opt.verbose)
-            .await
+    //unsynced activities are added to a private copy of the store. Stores
+    //which are too large to copy are read without them.
+    let mut fetch_unsynced = opt.fetch_unsynced && !opt.demo;
+    let store = if fetch_unsynced {
+        match ActivityStoreInterface::init_overlay_with_path(
+            &data_dir,
+            opt.verbose,
+        )
+        .await
         {
-            Ok(e) => e,
-            Err(e) => {
-                print_error(
-                    "Could not initialize activity store. Have you run dclias?",
-                    e,
+            Err(Error::StoreTooLarge { description }) => {
+                eprintln!(
+                    "Activity store is too large to fetch unsynced activities. Only synced activities are included. {}",
+                    description
                 );
-                std::process::exit(EXIT_FAILURE);
+                fetch_unsynced = false;
+                ActivityStoreInterface::init_read_only_with_path(
+                    &data_dir,
+                    opt.verbose,
+                )
+                .await
             }
-        };
+            e => e,
+        }
+    } else {
+        ActivityStoreInterface::init_read_only_with_path(&data_dir, opt.verbose)
+            .await
+    };
 
-    let mut manifest = match ManifestInterface::new(&data_dir, false).await {
+    let store = match store {
+        Ok(e) => e,
+        Err(e) => {
+            print_error(
+                "Could not initialize activity store. Have you run dclias?",
+                e,
+            );
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    let mut manifest = match ManifestInterface::new_with_name(
+        &data_dir,
+        &opt.language.manifest_name(&opt.manifest_name),
+        false,
+    )
+    .await
+    {
         Ok(e) => e,
         Err(e) => {
             print_error(
@@ -699,8 +1127,70 @@ async fn main() {
         }
     };
 
-    if !opt.no_sync {
-        match store.sync(&opt.member_id, &opt.platform).await {
+    //names from the manifest for displaying modes, so new playlists display
+    //correctly. The built in names are used if they can't be loaded.
+    let mode_names =
+        manifest.get_mode_display_names().await.unwrap_or_default();
+
+    //seasons are stored in the activity store, so the time period is only
+    //replaced once the store is open
+    let season = match opt.season {
+        Some(number) => match store.retrieve_season(number).await {
+            Ok(Some(e)) => Some(e),
+            Ok(None) => {
+                eprintln!(
+                    "Season {} not found. Seasons are loaded from the manifest when dclias syncs (run dclim to download the manifest).",
+                    number
+                );
+                std::process::exit(EXIT_FAILURE);
+            }
+            Err(e) => {
+                print_error("Could not retrieve season.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        },
+        None => None,
+    };
+
+    let time_period = match &season {
+        Some(season) => {
+            //the current season ends in the future
+            let now = Utc::now();
+            let end = match season.end {
+                Some(e) if e < now => e,
+                _ => now,
+            };
+
+            match DateTimePeriod::with_start_end_time(season.start, end) {
+                Ok(e) => e,
+                Err(_e) => {
+                    eprintln!(
+                        "Season {} has not started",
+                        season.season_number
+                    );
+                    std::process::exit(EXIT_FAILURE);
+                }
+            }
+        }
+        None => time_period,
+    };
+
+    //member id can also be a local player alias (see dclias --set-alias)
+    let member_id = match store.resolve_member_id(&member_id).await {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Could not resolve member id.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    //sync is skipped when reading a snapshot, since snapshot stores are read
+    //only copies of a store that another process is syncing
+    if !opt.no_sync && !opt.demo && !store.is_snapshot() {
+        match store
+            .sync(&member_id, &platform, &CancellationToken::new())
+            .await
+        {
             Ok(_e) => (),
             Err(e) => {
                 eprintln!("Could not sync activity store {}", e);
@@ -709,44 +1199,246 @@ async fn main() {
         };
     }
 
-    let data = match store
-        .retrieve_activities_since(
-            &opt.member_id,
-            &opt.character_class_selection,
-            &opt.platform,
+    //when syncing is done by another process, let the user know how current
+    //the data is. Not fatal, since it is only informational.
+    let last_sync = if (opt.no_sync || store.is_snapshot()) && !opt.demo {
+        match store.last_sync(&member_id).await {
+            Ok(e) => e,
+            Err(e) => {
+                print_verbose(
+                    &format!("Could not retrieve last sync : {}", e),
+                    opt.verbose,
+                );
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    if fetch_unsynced {
+        match store
+            .add_unsynced_activities(
+                &member_id,
+                &platform,
+                character_class_selection,
+                &time_period,
+            )
+            .await
+        {
+            Ok(e) => print_verbose(
+                &format!("Retrieved {} unsynced activities", e),
+                opt.verbose,
+            ),
+            Err(e) => {
+                eprintln!("Could not retrieve unsynced activities {}", e);
+                eprintln!("Using existing data");
+            }
+        };
+    }
+
+    //the default report only needs the aggregate and the most recent
+    //activities, so we only hold on to every performance if an output
+    //option needs them. This keeps memory flat for large time periods.
+    let keep_all = opt.output == Output::Csv
+        || opt.map_matrix
+        || opt.map_modes
+        || opt.queue_time
+        || opt.calendar
+        || opt.opening_duels
+        || opt.weapon_insights;
+
+    let mut accumulator = CruciblePerformanceAccumulator::default();
+    let mut character_accumulator: PartitionedPerformanceAccumulator<(
+        String,
+        CharacterClass,
+    )> = PartitionedPerformanceAccumulator::new();
+    let per_character = opt.character_report == CharacterReport::PerCharacter;
+    let mut data: Vec<CruciblePlayerActivityPerformance> = Vec::new();
+    let activity_limit = opt.activity_limit as usize;
+
+    //best game (by efficiency) for the summary
+    let track_best_game = opt.summary.is_some();
+    let mut best_game: Option<CruciblePlayerActivityPerformance> = None;
+
+    if let Err(e) = store
+        .stream_activities_since(
+            &member_id,
+            character_class_selection,
+            &platform,
             &opt.mode,
             &time_period,
             &mut manifest,
+            |p| {
+                accumulator.add_performance(&p.performance);
+
+                if per_character {
+                    let player = &p.performance.player;
+                    character_accumulator.add_performance(
+                        (player.character_id.clone(), player.class_type),
+                        &p.performance,
+                    );
+                }
+
+                if track_best_game {
+                    let is_best = match &best_game {
+                        Some(b) => {
+                            p.performance.stats.efficiency
+                                > b.performance.stats.efficiency
+                        }
+                        None => true,
+                    };
+
+                    if is_best {
+                        best_game = Some(p.clone());
+                    }
+                }
+
+                if keep_all || data.len() < activity_limit {
+                    data.push(p);
+                }
+                Ok(())
+            },
         )
         .await
     {
-        Ok(e) => e,
-        Err(e) => {
+        print_error("Could not retrieve data from activity store.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    let aggregate = accumulator.finish();
+    let character_aggregates = character_accumulator.finish();
+
+    //the summary compares against the previous period of the same length, so
+    //we only need the aggregate for it
+    let previous_aggregate = if opt.summary.is_some() {
+        let previous_time_period = match previous_period(&time_period) {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Could not determine previous time period.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+
+        let mut previous = CruciblePerformanceAccumulator::default();
+        if let Err(e) = store
+            .stream_activities_since(
+                &member_id,
+                character_class_selection,
+                &platform,
+                &opt.mode,
+                &previous_time_period,
+                &mut manifest,
+                |p| {
+                    previous.add_performance(&p.performance);
+                    Ok(())
+                },
+            )
+            .await
+        {
             print_error("Could not retrieve data from activity store.", e);
             std::process::exit(EXIT_FAILURE);
         }
+
+        Some(previous.finish())
+    } else {
+        None
     };
 
-    if data.is_none() {
-        println!("No activities found");
-        return;
+    let reports = StoreReports::retrieve(
+        &store,
+        &mut manifest,
+        &opt,
+        &member_id,
+        &time_period,
+    )
+    .await;
+
+    if let Err(e) = store.close().await {
+        print_error("Error closing activity store.", e);
+        std::process::exit(EXIT_FAILURE);
     }
 
-    let data: Vec<CruciblePlayerActivityPerformance> = data.unwrap();
+    let _format_timer = timing::start(TimingCategory::Formatting);
+
+    if let (Some(path), Some(previous)) = (&opt.summary, &previous_aggregate) {
+        let weapons = match &aggregate.extended {
+            Some(e) if opt.merge_weapon_variants => {
+                WeaponStat::merge_variants(&e.weapons)
+            }
+            Some(e) => e.weapons.clone(),
+            None => Vec::new(),
+        };
+
+        let summary = Summary {
+            mode: &opt.mode,
+            time_period: &time_period,
+            aggregate: &aggregate,
+            previous,
+            best_game: best_game.as_ref(),
+            weapons: &weapons,
+            weapon_count: opt.weapon_count,
+        };
+
+        if let Err(e) = write_summary(path, &summary) {
+            print_error("Could not write summary.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    }
+
+    if opt.output == Output::Csv {
+        print_csv(&data);
+        return;
+    }
 
-    if data.is_empty() {
+    if aggregate.total_activities == 0 {
         println!("No activities found");
         return;
     }
 
+    let display_count = std::cmp::min(data.len(), activity_limit);
+
     print_default(
-        &data,
-        &opt.activity_limit,
+        &aggregate,
+        &data[..display_count],
         &opt.mode,
         &time_period,
         &opt.moment,
         &opt.end_moment,
+        season.as_ref(),
         &opt.weapon_count,
         &opt.weapon_sort,
+        opt.merge_weapon_variants,
+        &TextColors::new(opt.color),
+        &mode_names,
     );
+
+    if let Some(e) = &last_sync {
+        print_last_sync(e);
+    }
+
+    let calendar_days = if opt.calendar {
+        Some(retrieve_calendar_days(&data, &time_period))
+    } else {
+        None
+    };
+
+    print_reports(
+        &opt,
+        &data,
+        &aggregate,
+        &character_aggregates,
+        calendar_days.as_deref(),
+        &mode_names,
+        &reports,
+    );
+
+    if let Some(path) = &opt.chart {
+        if let Err(e) =
+            write_chart(path, &data_dir, calendar_days.as_deref(), &reports)
+        {
+            print_error("Could not write chart image.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    }
 }
@@ -0,0 +1,146 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Duration, Local};
+use dcli::crucible::CruciblePlayerActivityPerformance;
+use dcli::enums::standing::Standing;
+use dcli::session::{group_sessions, DEFAULT_SESSION_GAP_MINUTES};
+use dcli::utils::{pad_string, repeat_str, truncate_string};
+
+const MAX_MAP_NAME_LEN: usize = 18;
+
+fn standing_char(standing: &Standing) -> char {
+    match standing {
+        Standing::Victory => 'W',
+        Standing::Defeat => 'L',
+        Standing::Unknown => '-',
+    }
+}
+
+/// prints a matrix of wins / losses with maps as rows and the most recent
+/// play sessions as columns
+pub fn print_map_matrix(
+    data: &[CruciblePlayerActivityPerformance],
+    session_count: usize,
+) {
+    let sessions =
+        group_sessions(data, Duration::minutes(DEFAULT_SESSION_GAP_MINUTES));
+    let start = sessions.len().saturating_sub(session_count);
+    let sessions = &sessions[start..];
+
+    //map name -> (session index -> W/L string)
+    let mut rows: BTreeMap<String, HashMap<usize, String>> = BTreeMap::new();
+    let mut totals: HashMap<String, (u32, u32)> = HashMap::new();
+
+    for (i, session) in sessions.iter().enumerate() {
+        for activity in session {
+            let map_name = activity.activity_detail.map_name.clone();
+            let standing = activity.performance.stats.standing;
+
+            rows.entry(map_name.clone())
+                .or_default()
+                .entry(i)
+                .or_default()
+                .push(standing_char(&standing));
+
+            let t = totals.entry(map_name).or_insert((0, 0));
+            match standing {
+                Standing::Victory => t.0 += 1,
+                Standing::Defeat => t.1 += 1,
+                Standing::Unknown => {}
+            }
+        }
+    }
+
+    let labels: Vec<String> = sessions
+        .iter()
+        .map(|s| {
+            let period = s[0].activity_detail.period.with_timezone(&Local);
+            format!("{}", period.format("%m/%d %H:%M"))
+        })
+        .collect();
+
+    //each column is as wide as its widest cell
+    let col_widths: Vec<usize> = labels
+        .iter()
+        .enumerate()
+        .map(|(i, label)| {
+            rows.values()
+                .filter_map(|r| r.get(&i))
+                .map(|c| c.chars().count())
+                .chain(std::iter::once(label.chars().count()))
+                .max()
+                .unwrap_or(0)
+                + 2
+        })
+        .collect();
+
+    let map_col_w = MAX_MAP_NAME_LEN + 2;
+    let total_col_w = 8;
+
+    let mut header = format!("{:<0map_col_w$}", "MAP", map_col_w = map_col_w);
+    for (label, w) in labels.iter().zip(col_widths.iter()) {
+        header.push_str(&format!("{:<0w$}", label, w = w));
+    }
+    header.push_str(&format!("{:>0w$}", "W-L", w = total_col_w));
+
+    println!();
+    println!("MAP WIN / LOSS MATRIX");
+    println!("==================");
+    println!(
+        "Wins and losses by map for the last {} session{}",
+        sessions.len(),
+        if sessions.len() == 1 { "" } else { "s" }
+    );
+    println!();
+    println!("{}", header);
+    println!("{}", repeat_str("=", header.chars().count()));
+
+    for (map_name, cells) in &rows {
+        let mut row =
+            pad_string(&truncate_string(map_name, MAX_MAP_NAME_LEN), map_col_w);
+        for (i, w) in col_widths.iter().enumerate() {
+            let cell = match cells.get(&i) {
+                Some(e) => e.as_str(),
+                None => ".",
+            };
+            row.push_str(&format!("{:<0w$}", cell, w = w));
+        }
+
+        let (wins, losses) = totals.get(map_name).unwrap_or(&(0, 0));
+        row.push_str(&format!(
+            "{:>0w$}",
+            format!("{}-{}", wins, losses),
+            w = total_col_w
+        ));
+
+        println!("{}", row);
+    }
+
+    println!();
+    println!(
+        "Sessions are separated by more than {} minutes between activities.",
+        DEFAULT_SESSION_GAP_MINUTES
+    );
+}
@@ -0,0 +1,118 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::collections::HashMap;
+
+use dcli::crucible::{
+    AggregateCruciblePerformances, CruciblePlayerActivityPerformance,
+    MapModeKey,
+};
+use dcli::utils::{fit_string, format_f32, repeat_str};
+
+const MAP_COL_W: usize = 28;
+const COL_W: usize = 10;
+
+struct MapGroup {
+    map_name: String,
+    games: u32,
+    modes: Vec<(MapModeKey, AggregateCruciblePerformances)>,
+}
+
+//prints stats grouped by map, with a row for each mode played on the map.
+//Maps are sorted by games played, as are the modes for each map.
+pub fn print_map_modes(
+    data: &[CruciblePlayerActivityPerformance],
+    mode_names: &HashMap<u32, String>,
+) {
+    let groups = AggregateCruciblePerformances::group_by(
+        data,
+        MapModeKey::with_performance,
+    );
+
+    let mut maps: Vec<MapGroup> = Vec::new();
+    for (k, a) in groups {
+        let index = match maps.iter().position(|m| m.map_name == k.map_name) {
+            Some(e) => e,
+            None => {
+                maps.push(MapGroup {
+                    map_name: k.map_name.to_string(),
+                    games: 0,
+                    modes: Vec::new(),
+                });
+                maps.len() - 1
+            }
+        };
+
+        maps[index].games += a.total_activities;
+        maps[index].modes.push((k, a));
+    }
+
+    maps.sort_by(|a, b| {
+        b.games.cmp(&a.games).then(a.map_name.cmp(&b.map_name))
+    });
+
+    println!();
+    println!("MAP / MODE PAIRINGS");
+    println!("==================");
+
+    let header = format!(
+        "{:<0map_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+        "MAP / MODE",
+        "GAMES",
+        "WIN %",
+        "K/D",
+        "KD/A",
+        "EFF",
+        map_col_w = MAP_COL_W,
+        col_w = COL_W,
+    );
+    println!("{}", header);
+    println!("{}", repeat_str("=", header.chars().count()));
+
+    for mut m in maps {
+        println!(
+            "{} {:>0col_w$}",
+            fit_string(&m.map_name, MAP_COL_W - 1),
+            m.games.to_string(),
+            col_w = COL_W,
+        );
+
+        m.modes
+            .sort_by_key(|b| std::cmp::Reverse(b.1.total_activities));
+
+        for (k, a) in m.modes {
+            println!(
+                "{} {:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+                fit_string(
+                    &format!("  {}", k.mode.display_name(mode_names)),
+                    MAP_COL_W - 1,
+                ),
+                a.total_activities.to_string(),
+                format!("{}%", format_f32(a.win_rate, 2)),
+                format_f32(a.kills_deaths_ratio, 2),
+                format_f32(a.kills_deaths_assists, 2),
+                format_f32(a.efficiency, 2),
+                col_w = COL_W,
+            );
+        }
+    }
+}
@@ -0,0 +1,69 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use dcli::crucible::MapStats;
+use dcli::utils::{format_f32, repeat_str, truncate_string};
+
+/// prints the player's record and stats on each map
+pub fn print_map_stats(stats: &[MapStats]) {
+    let map_col_w = 24;
+    let col_w = 10;
+
+    println!();
+    println!("MAPS");
+    println!("==================");
+
+    if stats.is_empty() {
+        println!("No activities found");
+        return;
+    }
+
+    let header = format!(
+        "{:<0map_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+        "MAP",
+        "GAMES",
+        "WINS",
+        "LOSSES",
+        "WIN %",
+        "K/D",
+        "EFF",
+        map_col_w = map_col_w,
+        col_w = col_w,
+    );
+    println!("{}", header);
+    println!("{}", repeat_str("=", header.chars().count()));
+
+    for s in stats {
+        println!(
+            "{:<0map_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+            truncate_string(&s.map_name, map_col_w - 1),
+            s.activities.to_string(),
+            s.wins.to_string(),
+            s.losses().to_string(),
+            format!("{}%", format_f32(s.win_rate(), 2)),
+            format_f32(s.kills_deaths_ratio(), 2),
+            format_f32(s.efficiency(), 2),
+            map_col_w = map_col_w,
+            col_w = col_w,
+        );
+    }
+}
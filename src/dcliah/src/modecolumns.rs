@@ -0,0 +1,229 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use dcli::crucible::{
+    AggregateCruciblePerformances, CruciblePlayerActivityPerformance,
+    ExtendedCruciblePlayerActivityPerformances, ExtendedCrucibleStats,
+};
+use dcli::enums::completionreason::CompletionReason;
+use dcli::enums::statprofile::StatProfile;
+use dcli::utils::{calculate_percent, format_f32};
+use num_format::{Locale, ToFormattedString};
+
+/// stat columns at the end of the activity table, which change depending on
+/// the mode being displayed. The core columns (kills, deaths, kd, etc) are
+/// always displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModeColumn {
+    Supers,
+    Grenades,
+    Melees,
+    Precision,
+    Score,
+    Rounds,
+    Mercy,
+    Motes,
+    InvasionKills,
+}
+
+impl ModeColumn {
+    pub fn for_profile(profile: &StatProfile) -> &'static [ModeColumn] {
+        match profile {
+            StatProfile::Standard => &[
+                ModeColumn::Supers,
+                ModeColumn::Grenades,
+                ModeColumn::Melees,
+                ModeColumn::Mercy,
+            ],
+            StatProfile::Rounds => &[
+                ModeColumn::Rounds,
+                ModeColumn::Precision,
+                ModeColumn::Supers,
+                ModeColumn::Mercy,
+            ],
+            StatProfile::Objective => &[
+                ModeColumn::Score,
+                ModeColumn::Supers,
+                ModeColumn::Grenades,
+                ModeColumn::Melees,
+                ModeColumn::Mercy,
+            ],
+            StatProfile::FreeForAll => &[
+                ModeColumn::Score,
+                ModeColumn::Precision,
+                ModeColumn::Supers,
+                ModeColumn::Grenades,
+            ],
+            StatProfile::Gambit => &[
+                ModeColumn::Motes,
+                ModeColumn::InvasionKills,
+                ModeColumn::Supers,
+                ModeColumn::Grenades,
+            ],
+        }
+    }
+
+    pub fn header(&self) -> &'static str {
+        match self {
+            ModeColumn::Supers => "SUP",
+            ModeColumn::Grenades => "GREN",
+            ModeColumn::Melees => "MEL",
+            ModeColumn::Precision => "PREC",
+            ModeColumn::Score => "SCORE",
+            ModeColumn::Rounds => "ROUNDS",
+            ModeColumn::Mercy => "MERCY",
+            ModeColumn::Motes => "MOTES",
+            ModeColumn::InvasionKills => "INV K",
+        }
+    }
+
+    pub fn activity_value(
+        &self,
+        activity: &CruciblePlayerActivityPerformance,
+    ) -> String {
+        let stats = &activity.performance.stats;
+
+        let no_extended = ExtendedCrucibleStats::default();
+        let extended = stats.extended.as_ref().unwrap_or(&no_extended);
+
+        match self {
+            ModeColumn::Supers => extended.weapon_kills_super.to_string(),
+            ModeColumn::Grenades => extended.weapon_kills_grenade.to_string(),
+            ModeColumn::Melees => extended.weapon_kills_melee.to_string(),
+            ModeColumn::Precision => extended.precision_kills.to_string(),
+            ModeColumn::Score => stats.score.to_string(),
+
+            //in round based modes, the team score is the rounds won
+            ModeColumn::Rounds => stats.team_score.to_string(),
+            ModeColumn::Mercy => {
+                if stats.completion_reason == CompletionReason::Mercy {
+                    "X".to_string()
+                } else {
+                    "".to_string()
+                }
+            }
+            ModeColumn::Motes => {
+                extended.gambit_stats().motes_deposited.to_string()
+            }
+            ModeColumn::InvasionKills => {
+                extended.gambit_stats().invasion_kills.to_string()
+            }
+        }
+    }
+
+    pub fn total_value(&self, a: &AggregateCruciblePerformances) -> String {
+        let no_extended = ExtendedCruciblePlayerActivityPerformances::default();
+        let extended = a.extended.as_ref().unwrap_or(&no_extended);
+
+        match self {
+            ModeColumn::Supers => {
+                extended.weapon_kills_super.to_formatted_string(&Locale::en)
+            }
+            ModeColumn::Grenades => extended
+                .weapon_kills_grenade
+                .to_formatted_string(&Locale::en),
+            ModeColumn::Melees => {
+                extended.weapon_kills_melee.to_formatted_string(&Locale::en)
+            }
+            ModeColumn::Precision => {
+                extended.precision_kills.to_formatted_string(&Locale::en)
+            }
+            ModeColumn::Score => a.score.to_formatted_string(&Locale::en),
+
+            //rounds are not aggregated across activities
+            ModeColumn::Rounds => "".to_string(),
+            ModeColumn::Mercy => a.total_mercy.to_string(),
+            ModeColumn::Motes => extended
+                .gambit
+                .motes_deposited
+                .to_formatted_string(&Locale::en),
+            ModeColumn::InvasionKills => extended
+                .gambit
+                .invasion_kills
+                .to_formatted_string(&Locale::en),
+        }
+    }
+
+    pub fn high_value(&self, a: &AggregateCruciblePerformances) -> String {
+        let no_extended = ExtendedCruciblePlayerActivityPerformances::default();
+        let extended = a.extended.as_ref().unwrap_or(&no_extended);
+
+        match self {
+            ModeColumn::Supers => {
+                extended.highest_weapon_kills_super.to_string()
+            }
+            ModeColumn::Grenades => {
+                extended.highest_weapon_kills_grenade.to_string()
+            }
+            ModeColumn::Melees => {
+                extended.highest_weapon_kills_melee.to_string()
+            }
+            ModeColumn::Precision => {
+                extended.highest_precision_kills.to_string()
+            }
+            ModeColumn::Score => a.highest_score.to_string(),
+
+            //highs are not tracked for gambit stats
+            ModeColumn::Rounds
+            | ModeColumn::Mercy
+            | ModeColumn::Motes
+            | ModeColumn::InvasionKills => "".to_string(),
+        }
+    }
+
+    pub fn per_game_value(&self, a: &AggregateCruciblePerformances) -> String {
+        let no_extended = ExtendedCruciblePlayerActivityPerformances::default();
+        let extended = a.extended.as_ref().unwrap_or(&no_extended);
+
+        let per_game = |v: u32| format_f32(a.stat_per_game(v), 2);
+
+        match self {
+            ModeColumn::Supers => per_game(extended.weapon_kills_super),
+            ModeColumn::Grenades => per_game(extended.weapon_kills_grenade),
+            ModeColumn::Melees => per_game(extended.weapon_kills_melee),
+            ModeColumn::Precision => per_game(extended.precision_kills),
+            //scores are large, so decimals would overflow the column
+            ModeColumn::Score => format_f32(a.stat_per_game(a.score), 0),
+            ModeColumn::Rounds => "".to_string(),
+            ModeColumn::Motes => per_game(extended.gambit.motes_deposited),
+            ModeColumn::InvasionKills => {
+                per_game(extended.gambit.invasion_kills)
+            }
+            ModeColumn::Mercy => format!(
+                "{}%",
+                format_f32(
+                    calculate_percent(a.total_mercy, a.total_activities),
+                    2
+                )
+            ),
+        }
+    }
+}
+
+/// right aligns each value in a column of the given width
+pub fn format_mode_cells(values: &[String], col_w: usize) -> String {
+    values
+        .iter()
+        .map(|v| format!("{:>0col_w$}", v, col_w = col_w))
+        .collect::<Vec<String>>()
+        .join("")
+}
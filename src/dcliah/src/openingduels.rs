@@ -0,0 +1,68 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::collections::HashMap;
+
+use dcli::crucible::{CruciblePlayerActivityPerformance, OpeningDuelStats};
+use dcli::utils::{format_f32, repeat_str};
+
+/// prints how often the player got the first kill of a game, by mode
+pub fn print_opening_duels(
+    data: &[CruciblePlayerActivityPerformance],
+    mode_names: &HashMap<u32, String>,
+) {
+    let stats = OpeningDuelStats::with_performances(data);
+
+    let mode_col_w = 24;
+    let col_w = 14;
+
+    println!();
+    println!("OPENING DUELS");
+    println!("==================");
+
+    let header = format!(
+        "{:<0mode_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+        "MODE",
+        "GAMES",
+        "FIRST BLOODS",
+        "RATE",
+        mode_col_w = mode_col_w,
+        col_w = col_w,
+    );
+    println!("{}", header);
+    println!("{}", repeat_str("=", header.chars().count()));
+
+    for s in &stats {
+        println!(
+            "{:<0mode_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+            s.mode.display_name(mode_names),
+            s.activities.to_string(),
+            s.first_bloods.to_string(),
+            format!("{}%", format_f32(s.first_blood_rate(), 2)),
+            mode_col_w = mode_col_w,
+            col_w = col_w,
+        );
+    }
+
+    println!();
+    println!("Derived from first blood medals. Modes which do not award the medal will show 0.");
+}
@@ -0,0 +1,182 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use dcli::crucible::{ActivityOpponentComposition, OpponentCompositionStats};
+use dcli::enums::character::CharacterClass;
+use dcli::utils::{calculate_percent, format_f32, repeat_str, truncate_string};
+
+//number of opposing team compositions displayed with --opponent-classes
+pub const OPPONENT_COMPOSITION_COUNT: usize = 10;
+
+/// prints the classes the player played against, and the player's win rate
+/// against each opposing team composition
+pub fn print_opponent_compositions(
+    activities: &[ActivityOpponentComposition],
+    composition_count: usize,
+) {
+    let name_col_w = 32;
+    let col_w = 12;
+    let bucket_col_w = 16;
+
+    println!();
+    println!("OPPONENT COMPOSITION");
+    println!("====================");
+
+    if activities.is_empty() {
+        println!("No opponents found");
+        return;
+    }
+
+    let classes = [
+        CharacterClass::Titan,
+        CharacterClass::Hunter,
+        CharacterClass::Warlock,
+    ];
+
+    let total: u32 = activities.iter().map(|a| a.composition.total()).sum();
+
+    let header = format!(
+        "{:<0name_col_w$}{:>0col_w$}{:>0col_w$}",
+        "CLASS",
+        "OPPONENTS",
+        "%",
+        name_col_w = name_col_w,
+        col_w = col_w,
+    );
+    println!("{}", header);
+    println!("{}", repeat_str("=", header.chars().count()));
+
+    for c in &classes {
+        let opponents: u32 = activities
+            .iter()
+            .map(|a| a.composition.class_count(c))
+            .sum();
+
+        println!(
+            "{:<0name_col_w$}{:>0col_w$}{:>0col_w$}",
+            format!("{}", c),
+            opponents.to_string(),
+            format!("{}%", format_f32(calculate_percent(opponents, total), 2)),
+            name_col_w = name_col_w,
+            col_w = col_w,
+        );
+    }
+
+    //win rate grouped by how many opponents of each class were faced
+    //(i.e. vs triple titan teams)
+    let buckets = ["0", "1", "2", "3+"];
+
+    println!();
+    let header = format!(
+        "{:<0name_col_w$}{}",
+        "WIN % VS # OF CLASS",
+        buckets
+            .iter()
+            .map(|b| format!(
+                "{:>0bucket_col_w$}",
+                b,
+                bucket_col_w = bucket_col_w
+            ))
+            .collect::<Vec<String>>()
+            .join(""),
+        name_col_w = name_col_w,
+    );
+    println!("{}", header);
+    println!("{}", repeat_str("=", header.chars().count()));
+
+    for c in &classes {
+        let mut games = [0_u32; 4];
+        let mut wins = [0_u32; 4];
+
+        for a in activities {
+            let i = std::cmp::min(a.composition.class_count(c), 3) as usize;
+            games[i] += 1;
+            if a.won {
+                wins[i] += 1;
+            }
+        }
+
+        let cells: Vec<String> = games
+            .iter()
+            .zip(wins.iter())
+            .map(|(g, w)| {
+                let cell = if *g == 0 {
+                    "-".to_string()
+                } else {
+                    format!(
+                        "{}% ({})",
+                        format_f32(calculate_percent(*w, *g), 0),
+                        g
+                    )
+                };
+                format!("{:>0bucket_col_w$}", cell, bucket_col_w = bucket_col_w)
+            })
+            .collect();
+
+        println!(
+            "{:<0name_col_w$}{}",
+            format!("{}", c),
+            cells.join(""),
+            name_col_w = name_col_w,
+        );
+    }
+
+    let stats = OpponentCompositionStats::with_activities(activities);
+    let max_stats = std::cmp::min(composition_count, stats.len());
+
+    println!();
+    let header = format!(
+        "{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+        "OPPONENT TEAM",
+        "GAMES",
+        "WINS",
+        "WIN %",
+        name_col_w = name_col_w,
+        col_w = col_w,
+    );
+    println!("{}", header);
+    println!("{}", repeat_str("=", header.chars().count()));
+
+    for s in &stats[..max_stats] {
+        println!(
+            "{:<0name_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+            truncate_string(&s.composition.to_string(), name_col_w),
+            s.activities.to_string(),
+            s.wins.to_string(),
+            format!("{}%", format_f32(s.win_rate(), 2)),
+            name_col_w = name_col_w,
+            col_w = col_w,
+        );
+    }
+
+    println!();
+    println!(
+        "Includes all characters. Games are counted as (#) after the win %."
+    );
+    if stats.len() > max_stats {
+        println!(
+            "Showing the {} most played against team compositions (of {}).",
+            max_stats,
+            stats.len()
+        );
+    }
+}
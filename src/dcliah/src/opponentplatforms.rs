@@ -0,0 +1,74 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use dcli::crucible::OpponentPlatformStats;
+use dcli::utils::{calculate_percent, format_f32, repeat_str};
+
+/// prints the platforms of the player's opponents, and the player's win rate
+/// against each
+pub fn print_opponent_platforms(stats: &[OpponentPlatformStats]) {
+    let platform_col_w = 16;
+    let col_w = 12;
+
+    println!();
+    println!("OPPONENT PLATFORMS");
+    println!("==================");
+
+    if stats.is_empty() {
+        println!("No opponents found");
+        return;
+    }
+
+    let total: u32 = stats.iter().map(|s| s.opponents).sum();
+
+    let header = format!(
+        "{:<0platform_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+        "PLATFORM",
+        "OPPONENTS",
+        "%",
+        "GAMES",
+        "WIN %",
+        platform_col_w = platform_col_w,
+        col_w = col_w,
+    );
+    println!("{}", header);
+    println!("{}", repeat_str("=", header.chars().count()));
+
+    for s in stats {
+        println!(
+            "{:<0platform_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+            format!("{}", s.platform),
+            s.opponents.to_string(),
+            format!(
+                "{}%",
+                format_f32(calculate_percent(s.opponents, total), 2)
+            ),
+            s.activities.to_string(),
+            format!("{}%", format_f32(s.win_rate(), 2)),
+            platform_col_w = platform_col_w,
+            col_w = col_w,
+        );
+    }
+
+    println!();
+    println!("GAMES and WIN % are for games with at least one opponent from the platform.");
+}
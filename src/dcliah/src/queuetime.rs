@@ -0,0 +1,121 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use chrono::{Duration, Local};
+use dcli::crucible::CruciblePlayerActivityPerformance;
+use dcli::session::{
+    group_sessions, SessionQueueTime, DEFAULT_SESSION_GAP_MINUTES,
+};
+use dcli::utils::{format_f32, repeat_str};
+
+//i.e. 1h 05m
+fn format_hours_minutes(seconds: u32) -> String {
+    let minutes = seconds / 60;
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+/// prints the estimated time spent in matches and between matches (in orbit
+/// and matchmaking) for each play session, most recent first
+pub fn print_queue_time(data: &[CruciblePlayerActivityPerformance]) {
+    let sessions: Vec<SessionQueueTime> =
+        group_sessions(data, Duration::minutes(DEFAULT_SESSION_GAP_MINUTES))
+            .iter()
+            .filter_map(|s| SessionQueueTime::with_session(s))
+            .collect();
+
+    let date_col_w = 16;
+    let col_w = 10;
+
+    println!();
+    println!("QUEUE TIME");
+    println!("==================");
+
+    if sessions.is_empty() {
+        println!("No sessions found.");
+        return;
+    }
+
+    let header = format!(
+        "{:<0date_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+        "SESSION",
+        "GAMES",
+        "IN MATCH",
+        "QUEUE",
+        "QUEUE %",
+        "RATIO",
+        date_col_w = date_col_w,
+        col_w = col_w,
+    );
+    println!("{}", header);
+    println!("{}", repeat_str("=", header.chars().count()));
+
+    let mut total = SessionQueueTime {
+        start: sessions[0].start,
+        end: sessions[sessions.len() - 1].end,
+        activities: 0,
+        match_seconds: 0,
+        queue_seconds: 0,
+        breaks: 0,
+    };
+
+    let print_row = |label: &str, s: &SessionQueueTime| {
+        println!(
+            "{:<0date_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+            label,
+            s.activities.to_string(),
+            format_hours_minutes(s.match_seconds),
+            format_hours_minutes(s.queue_seconds),
+            format!("{}%", format_f32(s.queue_percent(), 0)),
+            format_f32(s.queue_ratio(), 2),
+            date_col_w = date_col_w,
+            col_w = col_w,
+        );
+    };
+
+    for s in sessions.iter().rev() {
+        let start = s.start.with_timezone(&Local);
+        print_row(&format!("{}", start.format("%Y-%m-%d %H:%M")), s);
+
+        total.activities += s.activities;
+        total.match_seconds += s.match_seconds;
+        total.queue_seconds += s.queue_seconds;
+        total.breaks += s.breaks;
+    }
+
+    println!("{}", repeat_str("-", header.chars().count()));
+    print_row("TOTAL", &total);
+
+    println!();
+    println!("QUEUE is the estimated time between matches (in orbit, loading and matchmaking).");
+    println!("RATIO is the minutes spent between matches for each minute in a match.");
+    if total.breaks > 0 {
+        println!(
+            "{} of more than 15 minutes between matches {} treated as breaks, and not counted.",
+            if total.breaks == 1 {
+                "1 gap".to_string()
+            } else {
+                format!("{} gaps", total.breaks)
+            },
+            if total.breaks == 1 { "was" } else { "were" }
+        );
+    }
+}
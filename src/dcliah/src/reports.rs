@@ -0,0 +1,308 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//Dispatch for the optional reports printed after the default report. Each
+//report is enabled by its own flag (i.e. --maps), and is printed by its own
+//module. Reports which need more than the activities streamed for the default
+//report retrieve their data from the store with StoreReports::retrieve, before
+//the store is closed.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use dcli::activitystoreinterface::ActivityStoreInterface;
+use dcli::calendar::DayActivityCount;
+use dcli::competitive::CompetitiveMatch;
+use dcli::crucible::{
+    ActivityOpponentComposition, AggregateCruciblePerformances,
+    CruciblePlayerActivityPerformance, LobbyRatingPeriod, MapStats,
+    OpponentPlatformStats, SharedActivity,
+};
+use dcli::enums::character::{CharacterClass, CharacterReport};
+use dcli::enums::moment::DateTimePeriod;
+use dcli::error::Error;
+use dcli::manifestinterface::ManifestInterface;
+use dcli::render::chart::{self, ChartConfig};
+use dcli::utils::{print_error, EXIT_FAILURE};
+
+use crate::characters::print_character_breakdown;
+use crate::comphistory::{
+    competitive_history_points, print_competitive_history,
+};
+use crate::headtohead::print_head_to_head;
+use crate::heatmap::{print_calendar_heatmap, write_calendar_chart};
+use crate::lobbyrating::{lobby_rating_points, print_lobby_ratings};
+use crate::mapmatrix::print_map_matrix;
+use crate::mapmodes::print_map_modes;
+use crate::mapstats::print_map_stats;
+use crate::openingduels::print_opening_duels;
+use crate::opponentclasses::{
+    print_opponent_compositions, OPPONENT_COMPOSITION_COUNT,
+};
+use crate::opponentplatforms::print_opponent_platforms;
+use crate::queuetime::print_queue_time;
+use crate::weaponinsights::print_weapon_insights;
+use crate::Opt;
+
+/// data retrieved from the activity store for the optional reports. Data for
+/// reports which were not requested is None.
+pub struct StoreReports {
+    pub opponent_compositions: Option<Vec<ActivityOpponentComposition>>,
+    pub opponent_platforms: Option<Vec<OpponentPlatformStats>>,
+    pub map_stats: Option<Vec<MapStats>>,
+
+    //the player as specified by the user, along with the games played with
+    //them
+    pub head_to_head: Option<(String, Vec<SharedActivity>)>,
+    pub lobby_ratings: Option<Vec<LobbyRatingPeriod>>,
+    pub competitive_history: Option<Vec<CompetitiveMatch>>,
+}
+
+impl StoreReports {
+    /// retrieves the data for the reports enabled in opt. Exits if the data
+    /// for a report can't be retrieved.
+    pub async fn retrieve(
+        store: &ActivityStoreInterface,
+        manifest: &mut ManifestInterface,
+        opt: &Opt,
+        member_id: &str,
+        time_period: &DateTimePeriod,
+    ) -> StoreReports {
+        StoreReports {
+            opponent_compositions: if opt.opponent_classes {
+                Some(exit_on_error(
+                    store
+                        .retrieve_opponent_compositions(
+                            member_id,
+                            &opt.mode,
+                            time_period,
+                        )
+                        .await,
+                    "Could not retrieve opponent classes from activity store.",
+                ))
+            } else {
+                None
+            },
+            opponent_platforms: if opt.opponent_platforms {
+                Some(exit_on_error(
+                    store
+                        .retrieve_opponent_platform_stats(
+                            member_id,
+                            &opt.mode,
+                            time_period,
+                        )
+                        .await,
+                    "Could not retrieve opponent platforms from activity store.",
+                ))
+            } else {
+                None
+            },
+            map_stats: if opt.maps {
+                Some(exit_on_error(
+                    store
+                        .retrieve_map_stats(
+                            member_id,
+                            &opt.mode,
+                            time_period,
+                            manifest,
+                        )
+                        .await,
+                    "Could not retrieve map stats from activity store.",
+                ))
+            } else {
+                None
+            },
+            head_to_head: match &opt.head_to_head {
+                Some(player) => Some(
+                    retrieve_head_to_head(
+                        store,
+                        opt,
+                        member_id,
+                        player,
+                        time_period,
+                    )
+                    .await,
+                ),
+                None => None,
+            },
+            lobby_ratings: if opt.lobby_rating {
+                Some(exit_on_error(
+                    store
+                        .retrieve_lobby_rating_trend(
+                            member_id,
+                            &opt.mode,
+                            time_period,
+                        )
+                        .await,
+                    "Could not retrieve lobby ratings from activity store.",
+                ))
+            } else {
+                None
+            },
+            competitive_history: if opt.comp_ranks {
+                Some(exit_on_error(
+                    store
+                        .retrieve_competitive_history(
+                            member_id,
+                            time_period,
+                            manifest,
+                        )
+                        .await,
+                    "Could not retrieve competitive history from activity store.",
+                ))
+            } else {
+                None
+            },
+        }
+    }
+}
+
+async fn retrieve_head_to_head(
+    store: &ActivityStoreInterface,
+    opt: &Opt,
+    member_id: &str,
+    player: &str,
+    time_period: &DateTimePeriod,
+) -> (String, Vec<SharedActivity>) {
+    let other_member_id = exit_on_error(
+        store.resolve_member_id(player).await,
+        "Could not resolve head to head player.",
+    );
+
+    let activities = exit_on_error(
+        store
+            .retrieve_games_with_player(
+                member_id,
+                &other_member_id,
+                &opt.mode,
+                time_period,
+            )
+            .await,
+        "Could not retrieve head to head games from activity store.",
+    );
+
+    (player.to_string(), activities)
+}
+
+fn exit_on_error<T>(result: Result<T, Error>, msg: &str) -> T {
+    match result {
+        Ok(e) => e,
+        Err(e) => {
+            print_error(msg, e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    }
+}
+
+/// prints the optional reports enabled in opt. data must contain all of the
+/// activities for the time period if any of the reports which use it are
+/// enabled.
+pub fn print_reports(
+    opt: &Opt,
+    data: &[CruciblePlayerActivityPerformance],
+    aggregate: &AggregateCruciblePerformances,
+    character_aggregates: &[(
+        (String, CharacterClass),
+        AggregateCruciblePerformances,
+    )],
+    calendar_days: Option<&[DayActivityCount]>,
+    mode_names: &HashMap<u32, String>,
+    reports: &StoreReports,
+) {
+    if opt.character_report == CharacterReport::PerCharacter {
+        print_character_breakdown(character_aggregates, aggregate);
+    }
+
+    if opt.map_matrix {
+        print_map_matrix(data, opt.matrix_sessions);
+    }
+
+    if opt.map_modes {
+        print_map_modes(data, mode_names);
+    }
+
+    if opt.queue_time {
+        print_queue_time(data);
+    }
+
+    if let Some(e) = calendar_days {
+        print_calendar_heatmap(e);
+    }
+
+    if opt.opening_duels {
+        print_opening_duels(data, mode_names);
+    }
+
+    if opt.weapon_insights {
+        print_weapon_insights(data);
+    }
+
+    if let Some(e) = &reports.opponent_platforms {
+        print_opponent_platforms(e);
+    }
+
+    if let Some(e) = &reports.map_stats {
+        print_map_stats(e);
+    }
+
+    if let Some((player, activities)) = &reports.head_to_head {
+        print_head_to_head(player, activities);
+    }
+
+    if let Some(e) = &reports.opponent_compositions {
+        print_opponent_compositions(e, OPPONENT_COMPOSITION_COUNT);
+    }
+
+    if let Some(e) = &reports.lobby_ratings {
+        print_lobby_ratings(e);
+    }
+
+    if let Some(e) = &reports.competitive_history {
+        print_competitive_history(e);
+    }
+}
+
+/// writes the chart for the report it was requested with (--calendar,
+/// --lobby-rating or --comp-ranks) to an image at path
+pub fn write_chart(
+    path: &Path,
+    data_dir: &Path,
+    calendar_days: Option<&[DayActivityCount]>,
+    reports: &StoreReports,
+) -> Result<(), Error> {
+    if let Some(days) = calendar_days {
+        return write_calendar_chart(path, data_dir, days);
+    }
+
+    let (title, points) = match &reports.lobby_ratings {
+        Some(e) => ("LOBBY RATING", lobby_rating_points(e)),
+        None => (
+            "COMPETITIVE GLORY",
+            competitive_history_points(
+                reports.competitive_history.as_deref().unwrap_or(&[]),
+            ),
+        ),
+    };
+
+    let config = ChartConfig::load(data_dir)?;
+    chart::write_trend_chart(path, title, &points, &config)
+}
@@ -0,0 +1,386 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+use dcli::crucible::{
+    AggregateCruciblePerformances, CruciblePlayerActivityPerformance,
+    WeaponStat,
+};
+use dcli::enums::mode::Mode;
+use dcli::enums::moment::DateTimePeriod;
+use dcli::error::Error;
+use dcli::utils::{
+    calculate_percent, calculate_ratio, f32_are_equal, format_f32,
+    human_date_format, human_duration, uppercase_first_char,
+};
+use dcli::{html, markdown};
+
+const DATE_FORMAT: &str = "%B %-d, %Y";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SummaryFormat {
+    Html,
+    Markdown,
+}
+
+impl SummaryFormat {
+    fn from_path(path: &Path) -> Result<SummaryFormat, Error> {
+        let ext = path
+            .extension()
+            .and_then(OsStr::to_str)
+            .map(|e| e.to_lowercase());
+
+        match ext.as_deref() {
+            Some("html") | Some("htm") => Ok(SummaryFormat::Html),
+            Some("md") | Some("markdown") => Ok(SummaryFormat::Markdown),
+            _ => Err(Error::UnsupportedOutputFormat {
+                description: format!(
+                    "Unsupported summary type for {}. Use a .html or .md \
+                    file extension.",
+                    path.display()
+                ),
+            }),
+        }
+    }
+}
+
+/// data for a summary of a time period (normally the last week), compared
+/// against the period of the same length before it.
+pub struct Summary<'a> {
+    pub mode: &'a Mode,
+    pub time_period: &'a DateTimePeriod,
+    pub aggregate: &'a AggregateCruciblePerformances,
+    pub previous: &'a AggregateCruciblePerformances,
+    pub best_game: Option<&'a CruciblePlayerActivityPerformance>,
+    pub weapons: &'a [WeaponStat],
+    pub weapon_count: u32,
+}
+
+//a titled table. Values are not escaped, so the same sections can be used
+//for each format
+struct Section {
+    title: String,
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+/// returns the period of the same length which ends when the period starts,
+/// used to compare stats against
+pub fn previous_period(
+    time_period: &DateTimePeriod,
+) -> Result<DateTimePeriod, Error> {
+    let start = time_period.get_start();
+    let length = time_period.get_end() - start;
+
+    DateTimePeriod::with_start_end_time(start - length, start)
+}
+
+//i.e. "+0.25". Empty if there is nothing to compare against
+fn format_change(
+    current: f32,
+    previous: f32,
+    games: u32,
+    decimals: usize,
+) -> String {
+    if games == 0 {
+        return "".to_string();
+    }
+
+    let delta = current - previous;
+    let sign = if delta > 0.0 && !f32_are_equal(delta, 0.0) {
+        "+"
+    } else {
+        ""
+    };
+    format!("{}{}", sign, format_f32(delta, decimals))
+}
+
+fn overview_section(a: &AggregateCruciblePerformances) -> Section {
+    let rows = vec![
+        vec!["Games".to_string(), a.total_activities.to_string()],
+        vec![
+            "Wins / Losses".to_string(),
+            format!("{} / {}", a.wins, a.losses),
+        ],
+        vec![
+            "Win rate".to_string(),
+            format!(
+                "{}%",
+                format_f32(calculate_percent(a.wins, a.total_activities), 2)
+            ),
+        ],
+        vec![
+            "Kills / Assists / Deaths".to_string(),
+            format!("{} / {} / {}", a.kills, a.assists, a.deaths),
+        ],
+        vec!["K/D".to_string(), format_f32(a.kills_deaths_ratio, 2)],
+        vec!["KD/A".to_string(), format_f32(a.kills_deaths_assists, 2)],
+        vec!["Efficiency".to_string(), format_f32(a.efficiency, 2)],
+        vec![
+            "Longest win streak".to_string(),
+            a.longest_win_streak.to_string(),
+        ],
+        vec![
+            "Time played".to_string(),
+            human_duration(a.time_played_seconds),
+        ],
+    ];
+
+    Section {
+        title: "Games".to_string(),
+        headers: vec!["STAT", "VALUE"],
+        rows,
+    }
+}
+
+fn trends_section(
+    current: &AggregateCruciblePerformances,
+    previous: &AggregateCruciblePerformances,
+) -> Section {
+    let games = previous.total_activities;
+    let win_rate = |a: &AggregateCruciblePerformances| {
+        calculate_percent(a.wins, a.total_activities)
+    };
+
+    //label, current, previous and the number of decimals to display
+    let values: Vec<(&str, f32, f32, usize)> = vec![
+        (
+            "Games",
+            current.total_activities as f32,
+            previous.total_activities as f32,
+            0,
+        ),
+        ("Win rate %", win_rate(current), win_rate(previous), 2),
+        (
+            "K/D",
+            current.kills_deaths_ratio,
+            previous.kills_deaths_ratio,
+            2,
+        ),
+        (
+            "KD/A",
+            current.kills_deaths_assists,
+            previous.kills_deaths_assists,
+            2,
+        ),
+        ("Efficiency", current.efficiency, previous.efficiency, 2),
+        (
+            "Kills / game",
+            calculate_ratio(current.kills, current.total_activities),
+            calculate_ratio(previous.kills, previous.total_activities),
+            2,
+        ),
+        (
+            "Deaths / game",
+            calculate_ratio(current.deaths, current.total_activities),
+            calculate_ratio(previous.deaths, previous.total_activities),
+            2,
+        ),
+    ];
+
+    let rows = values
+        .iter()
+        .map(|(label, c, p, decimals)| {
+            vec![
+                label.to_string(),
+                format_f32(*c, *decimals),
+                if games == 0 {
+                    "-".to_string()
+                } else {
+                    format_f32(*p, *decimals)
+                },
+                format_change(*c, *p, games, *decimals),
+            ]
+        })
+        .collect();
+
+    Section {
+        title: "Trends".to_string(),
+        headers: vec!["STAT", "THIS PERIOD", "PREVIOUS", "CHANGE"],
+        rows,
+    }
+}
+
+fn best_game_section(p: &CruciblePlayerActivityPerformance) -> Section {
+    let stats = &p.performance.stats;
+    let rows = vec![
+        vec![
+            "Map".to_string(),
+            format!(
+                "{} ({})",
+                p.activity_detail.map_name, p.activity_detail.mode
+            ),
+        ],
+        vec![
+            "Date".to_string(),
+            human_date_format(&p.activity_detail.period),
+        ],
+        vec!["Result".to_string(), format!("{}", stats.standing)],
+        vec![
+            "Kills / Assists / Deaths".to_string(),
+            format!("{} / {} / {}", stats.kills, stats.assists, stats.deaths),
+        ],
+        vec!["K/D".to_string(), format_f32(stats.kills_deaths_ratio, 2)],
+        vec!["Efficiency".to_string(), format_f32(stats.efficiency, 2)],
+        vec![
+            "Activity index".to_string(),
+            p.activity_detail.index_id.to_string(),
+        ],
+    ];
+
+    Section {
+        title: "Best Game".to_string(),
+        headers: vec!["STAT", "VALUE"],
+        rows,
+    }
+}
+
+fn weapons_section(
+    weapons: &[WeaponStat],
+    total_kills: u32,
+    weapon_count: u32,
+) -> Section {
+    let mut weapons = weapons.to_vec();
+    weapons.sort_by_key(|w| std::cmp::Reverse(w.kills));
+    weapons.truncate(weapon_count as usize);
+
+    let rows = weapons
+        .iter()
+        .map(|w| {
+            vec![
+                w.weapon.name.to_string(),
+                w.kills.to_string(),
+                w.activity_count.to_string(),
+                format!(
+                    "{}%",
+                    format_f32(calculate_percent(w.kills, total_kills), 2)
+                ),
+                format!("{}%", format_f32(w.precision_kills_percent, 2)),
+                format!("{}", w.weapon.item_sub_type),
+            ]
+        })
+        .collect();
+
+    Section {
+        title: "Weapon Meta".to_string(),
+        headers: vec!["WEAPON", "KILLS", "GAMES", "% TOTAL", "% PREC", "TYPE"],
+        rows,
+    }
+}
+
+fn build_sections(summary: &Summary) -> Vec<Section> {
+    let mut sections = vec![
+        overview_section(summary.aggregate),
+        trends_section(summary.aggregate, summary.previous),
+    ];
+
+    if let Some(e) = summary.best_game {
+        sections.push(best_game_section(e));
+    }
+
+    if !summary.weapons.is_empty() {
+        sections.push(weapons_section(
+            summary.weapons,
+            summary.aggregate.kills,
+            summary.weapon_count,
+        ));
+    }
+
+    sections
+}
+
+fn generate_title(summary: &Summary) -> String {
+    format!(
+        "{} Summary : {} - {}",
+        uppercase_first_char(&format!("{}", summary.mode)),
+        summary.time_period.get_start().format(DATE_FORMAT),
+        summary.time_period.get_end().format(DATE_FORMAT)
+    )
+}
+
+fn generate_html(summary: &Summary) -> String {
+    let title = generate_title(summary);
+
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+    out.push_str("<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>{}</title>\n", html::escape(&title)));
+    out.push_str(&format!("<style>{}</style>\n", html::STYLE));
+    out.push_str("</head>\n<body>\n");
+    out.push_str(&format!("<h1>{}</h1>\n", html::escape(&title)));
+
+    for s in build_sections(summary) {
+        out.push_str(&format!("<h2>{}</h2>\n", html::escape(&s.title)));
+        out.push_str("<table>\n");
+        out.push_str(&html::table_header(&s.headers));
+        out.push_str("<tbody>\n");
+        for r in &s.rows {
+            let row: Vec<String> = r.iter().map(|v| html::escape(v)).collect();
+            out.push_str(&html::table_row(&row, None));
+        }
+        out.push_str("</tbody>\n</table>\n");
+    }
+
+    out.push_str(
+        "<p class=\"note\">Trends compare against the previous period of \
+        the same length.</p>\n",
+    );
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn generate_markdown(summary: &Summary) -> String {
+    let mut out =
+        format!("## {}\n", markdown::escape(&generate_title(summary)));
+
+    for s in build_sections(summary) {
+        out.push_str(&format!("\n### {}\n\n", markdown::escape(&s.title)));
+        out.push_str(&markdown::table_header(&s.headers));
+        out.push('\n');
+        for r in &s.rows {
+            let row: Vec<String> =
+                r.iter().map(|v| markdown::escape(v)).collect();
+            out.push_str(&markdown::table_row(&row));
+            out.push('\n');
+        }
+    }
+
+    out.push_str(
+        "\nTrends compare against the previous period of the same length.\n",
+    );
+    out
+}
+
+/// writes the summary to the path as html or markdown, determined by the
+/// file extension
+pub fn write_summary(path: &Path, summary: &Summary) -> Result<(), Error> {
+    let content = match SummaryFormat::from_path(path)? {
+        SummaryFormat::Html => generate_html(summary),
+        SummaryFormat::Markdown => generate_markdown(summary),
+    };
+
+    fs::write(path, content)?;
+    Ok(())
+}
@@ -0,0 +1,87 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use dcli::crucible::CruciblePlayerActivityPerformance;
+use dcli::insights::{WeaponTypeInsights, DEFAULT_MIN_INSIGHT_ACTIVITIES};
+use dcli::utils::{format_f32, repeat_str};
+
+/// prints the player's k/d with each weapon type compared to their overall
+/// k/d, along with hints for the weapon types they do best and worst with
+pub fn print_weapon_insights(data: &[CruciblePlayerActivityPerformance]) {
+    let insights = WeaponTypeInsights::with_performances(
+        data,
+        DEFAULT_MIN_INSIGHT_ACTIVITIES,
+    );
+
+    let type_col_w = 24;
+    let col_w = 12;
+
+    println!();
+    println!("WEAPON INSIGHTS");
+    println!("==================");
+
+    if insights.insights.is_empty() {
+        println!(
+            "Not enough data. Weapon types must be used in at least {} activities.",
+            DEFAULT_MIN_INSIGHT_ACTIVITIES
+        );
+        return;
+    }
+
+    let header = format!(
+        "{:<0type_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+        "WEAPON TYPE",
+        "GAMES",
+        "K/D",
+        "VS AVG",
+        type_col_w = type_col_w,
+        col_w = col_w,
+    );
+    println!("{}", header);
+    println!("{}", repeat_str("=", header.chars().count()));
+
+    for i in &insights.insights {
+        let sign = if i.diff_percent >= 0.0 { "+" } else { "" };
+        println!(
+            "{:<0type_col_w$}{:>0col_w$}{:>0col_w$}{:>0col_w$}",
+            format!("{}", i.item_sub_type),
+            i.activities.to_string(),
+            format_f32(i.kills_deaths_ratio, 2),
+            format!("{}{}%", sign, format_f32(i.diff_percent, 0)),
+            type_col_w = type_col_w,
+            col_w = col_w,
+        );
+    }
+
+    println!();
+    println!(
+        "Overall K/D : {}",
+        format_f32(insights.overall_kills_deaths_ratio, 2)
+    );
+
+    for h in insights.hints() {
+        println!("* {}", h);
+    }
+
+    println!();
+    println!("K/D is calculated from activities where you got at least one kill with the weapon type.");
+}
@@ -20,17 +20,34 @@
 * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 */
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
 
 use dcli::activitystoreinterface::ActivityStoreInterface;
+use dcli::apiinterface::ApiInterface;
+use dcli::cancellation::CancellationToken;
+use dcli::datadir;
+use dcli::enums::character::{CharacterClass, CharacterClassSelection};
+use dcli::enums::moment::DateTimePeriod;
 use dcli::enums::platform::Platform;
+use dcli::error::Error;
+use dcli::manifestinterface::ManifestInterface;
 use dcli::output::Output;
+use dcli::ratelimit::{self, parse_max_rps};
+use dcli::syncevent::SyncEvent;
+use dcli::timing::{self, TimingCategory};
 use dcli::utils::{
-    build_tsv, determine_data_dir, print_error, print_verbose, EXIT_FAILURE,
+    build_tsv, determine_data_dir, human_bytes, print_error, print_message,
+    print_verbose, repeat_str, EXIT_FAILURE, TSV_EOL,
 };
 use structopt::StructOpt;
 
-use dcli::activitystoreinterface::SyncResult;
+use dcli::activitystoreinterface::{
+    parse_pgcr_concurrency, MemberSyncResult, ModeActivityCount, SyncResult,
+    DEFAULT_PGCR_CONCURRENCY,
+};
+use serde_derive::Serialize;
 
 #[derive(StructOpt, Debug)]
 #[structopt(verbatim_doc_comment)]
@@ -54,12 +71,26 @@ struct Opt {
     #[structopt(short = "v", long = "verbose")]
     verbose: bool,
 
+    /// Print how long was spent in API calls, SQL queries and formatting
+    ///
+    /// Timings are printed to stderr when the command finishes, and can help
+    /// diagnose slow commands.
+    #[structopt(long = "timing")]
+    timing: bool,
+
     /// Format for command output
     ///
-    /// Valid values are default (Default) and tsv.
+    /// Valid values are default (Default), tsv, json and jsonl.
     ///
     /// tsv outputs in a tab (\t) seperated format of name / value pairs with lines
     /// ending in a new line character (\n).
+    ///
+    /// json outputs the sync results, including results for each character,
+    /// as a JSON object once the sync completes.
+    ///
+    /// jsonl outputs a stream of sync events (sync_started, history_page,
+    /// activity_synced, sync_finished and error) as JSON objects, one per
+    /// line.
     #[structopt(
         short = "O",
         long = "output-format",
@@ -67,6 +98,15 @@ struct Opt {
     )]
     output: Output,
 
+    /// Only write structured data to stdout
+    ///
+    /// All other messages (i.e. sync progress, status messages and errors)
+    /// are written to stderr. Uses tsv output unless --output-format is set
+    /// to json or jsonl. Aliases and members are listed as tab seperated
+    /// values.
+    #[structopt(long = "porcelain")]
+    porcelain: bool,
+
     /// Directory where activity sqlite3 database will be stored. (optional)
     ///
     /// By default data will be loaded from and stored in the appropriate system
@@ -75,26 +115,805 @@ struct Opt {
     #[structopt(short = "D", long = "data-dir", parse(from_os_str))]
     data_dir: Option<PathBuf>,
 
+    /// Print the paths of the files and directories used by dcli and exit
+    ///
+    /// Includes the data directory, manifest, activity store and the cache,
+    /// config and backup directories.
+    #[structopt(long = "print-paths")]
+    print_paths: bool,
+
+    /// Maximum number of API requests to make per second
+    ///
+    /// Caps the rate of requests to the Destiny API, for when the API key or
+    /// IP rate budget is shared with other tools. Can also be set with the
+    /// DCLI_MAX_RPS environment variable. By default requests are not capped.
+    #[structopt(long = "max-rps", parse(try_from_str = parse_max_rps))]
+    max_rps: Option<f64>,
+
     /// Platform for specified id
     ///
     /// Valid values are: xbox, playstation, stadia or steam.
-    #[structopt(short = "p", long = "platform", required = true)]
-    platform: Platform,
+    #[structopt(
+        short = "p",
+        long = "platform",
+        required_unless_one = &["set-alias", "remove-alias", "list-aliases", "remove-member", "purge-member", "list-members", "all", "maintenance", "prune-before", "list-backups", "restore-backup", "verify", "reingest", "reingest-from", "reprocess", "print-paths"]
+    )]
+    platform: Option<Platform>,
 
     /// Destiny 2 API member id for the character to retrieve activities for.
     ///
     /// This is not the user name, but the member id
-    /// retrieved from the Destiny API.
-    #[structopt(short = "m", long = "member-id", required = true)]
-    member_id: String,
+    /// retrieved from the Destiny API. A player alias, or the display name of
+    /// a player in synced activities (i.e. name or name:steam), can also be
+    /// used.
+    #[structopt(
+        short = "m",
+        long = "member-id",
+        required_unless_one = &["set-alias", "remove-alias", "list-aliases", "add-member", "remove-member", "purge-member", "list-members", "all", "maintenance", "prune-before", "list-backups", "restore-backup", "verify", "reingest", "reingest-from", "reprocess", "print-paths"]
+    )]
+    member_id: Option<String>,
+
+    /// Set a local alias for a player, in the form member_id=alias
+    ///
+    /// Aliases are stored in the activity store, displayed next to the
+    /// player's name in dcliad, and can be used in place of a member id.
+    /// Activities are not synced.
+    #[structopt(long = "set-alias", parse(try_from_str = parse_alias))]
+    set_alias: Option<(String, String)>,
+
+    /// Remove the local alias for the specified member id
+    ///
+    /// Activities are not synced.
+    #[structopt(long = "remove-alias")]
+    remove_alias: Option<String>,
+
+    /// List all local player aliases
+    ///
+    /// Activities are not synced.
+    #[structopt(long = "list-aliases")]
+    list_aliases: bool,
+
+    /// Add a member to the members synced with --all
+    ///
+    /// Takes a member id or alias, and requires --platform. Lets a single
+    /// store keep the history for a whole fireteam. Activities are not synced.
+    #[structopt(long = "add-member", requires = "platform")]
+    add_member: Option<String>,
+
+    /// Remove a member from the members synced with --all
+    ///
+    /// Takes a member id or alias. Activities already synced for the member
+    /// are kept (use --purge-member to remove them). Activities are not
+    /// synced.
+    #[structopt(long = "remove-member")]
+    remove_member: Option<String>,
+
+    /// Remove a member and their synced activities from the activity store
+    ///
+    /// Takes a member id or alias. Use to remove a player that was synced by
+    /// mistake. Activities which another synced member played in are kept.
+    /// This cannot be undone. Activities are not synced.
+    #[structopt(long = "purge-member")]
+    purge_member: Option<String>,
+
+    /// List the members synced with --all
+    ///
+    /// Activities are not synced.
+    #[structopt(long = "list-members")]
+    list_members: bool,
+
+    /// Also sync PvE activity history
+    ///
+    /// Syncs strikes, nightfalls, raids and dungeons along with Crucible and
+    /// Gambit activities. PvE stats can be viewed with dclipve. The first
+    /// PvE sync can take a long time for players with a lot of PvE history.
+    #[structopt(long = "pve")]
+    pve: bool,
+
+    /// Store the original post game carnage report json for synced activities
+    ///
+    /// The json is stored in the activity store along with the parsed data,
+    /// so activities can be re-ingested (i.e. to backfill data added in new
+    /// versions of dcli) without downloading them again. Also applies to
+    /// --reingest. Stored json significantly increases the size of the store.
+    #[structopt(long = "store-raw-pgcr")]
+    store_raw_pgcr: bool,
+
+    /// Maximum number of activities to retrieve for each member synced
+    ///
+    /// Activities over the limit are left in the queue, and are synced the
+    /// next time dclias is run. Lets a long first sync be spread over several
+    /// runs. By default all activities in the queue are retrieved.
+    #[structopt(long = "max-activities")]
+    max_activities: Option<u32>,
+
+    /// Number of activities to retrieve from the Destiny API at the same time
+    ///
+    /// Lower values can help on slow or unreliable connections, while higher
+    /// values can speed up large syncs (i.e. the first sync of a long
+    /// history), but are more likely to be throttled by the API. Must be
+    /// between 1 and 64. Defaults to 24.
+    #[structopt(long = "concurrency", parse(try_from_str = parse_pgcr_concurrency))]
+    concurrency: Option<usize>,
+
+    /// Sync only the character with the specified id
+    ///
+    /// Can be used to import activity history for characters which have been
+    /// deleted (and so are no longer returned with the player's profile), as
+    /// long as the character id is known. Requires --character-class.
+    #[structopt(
+        long = "character-id",
+        requires = "character-class",
+        conflicts_with = "all"
+    )]
+    character_id: Option<String>,
+
+    /// Character to sync
+    ///
+    /// Valid values include hunter, titan, warlock, last_active and all.
+    /// Syncing a single character can be much faster for accounts with a
+    /// large activity history on other characters. Defaults to all.
+    #[structopt(
+        short = "C",
+        long = "class",
+        conflicts_with_all = &["all", "character-id"]
+    )]
+    character_class_selection: Option<CharacterClassSelection>,
+
+    /// Class of the character specified with --character-id
+    ///
+    /// Valid values are titan, hunter and warlock.
+    #[structopt(long = "character-class", requires = "character-id")]
+    character_class: Option<CharacterClass>,
+
+    /// Compact and check the activity store
+    ///
+    /// Runs an integrity check, and then ANALYZE and VACUUM to compact the
+    /// store and reclaim space, and prints the size of the store before and
+    /// after. Can take a while for large stores. Activities are not synced.
+    #[structopt(long = "maintenance")]
+    maintenance: bool,
+
+    /// Remove all activities older than the specified date from the store
+    ///
+    /// Date / time must be in RFC 3339 format (i.e.
+    /// 2020-12-08T17:00:00.774187+00:00). Keeps the store small when only
+    /// recent activities (i.e. the current season) are needed. Activities
+    /// older than the date are not synced again. This cannot be undone.
+    /// Activities are not synced.
+    #[structopt(long = "prune-before", parse(try_from_str = parse_rfc3339))]
+    prune_before: Option<DateTime<Utc>>,
+
+    /// List the activity store backups, most recent first
+    ///
+    /// A backup of the store is made before its schema is upgraded by a new
+    /// version of dcli. Backups can be restored with --restore-backup.
+    /// Activities are not synced.
+    #[structopt(long = "list-backups")]
+    list_backups: bool,
+
+    /// Replace the activity store with the specified backup
+    ///
+    /// Takes the path of a backup listed by --list-backups. The current store
+    /// is moved to the backup directory rather than deleted. The backup is
+    /// upgraded the next time the store is opened, if needed. Activities are
+    /// not synced.
+    #[structopt(
+        long = "restore-backup",
+        parse(from_os_str),
+        conflicts_with = "list-backups"
+    )]
+    restore_backup: Option<PathBuf>,
+
+    /// Check a random sample of stored activities against the Destiny API
+    ///
+    /// Re-downloads the specified number of randomly selected activities, and
+    /// compares their mode, team results and player stats with the values in
+    /// the store. Activities with values that do not match are listed, and
+    /// can be re-ingested. The store is not modified.
+    #[structopt(long = "verify")]
+    verify: Option<u32>,
+
+    /// Delete and re-download the specified activities from the Destiny API
+    ///
+    /// Takes one or more activity ids (seperated by commas). Use to repair
+    /// activities stored with bad data (i.e. those listed by --verify),
+    /// without rebuilding the whole store. Activities which can't be
+    /// downloaded are left as they are.
+    #[structopt(
+        long = "reingest",
+        use_delimiter = true,
+        conflicts_with = "reingest-from"
+    )]
+    reingest: Option<Vec<i64>>,
+
+    /// Delete and re-download all stored activities since the specified date
+    ///
+    /// Date / time must be in RFC 3339 format (i.e.
+    /// 2020-12-08T17:00:00.774187+00:00). Can be combined with --reingest-to
+    /// to re-ingest activities within a date range.
+    #[structopt(long = "reingest-from", parse(try_from_str = parse_rfc3339))]
+    reingest_from: Option<DateTime<Utc>>,
+
+    /// End date / time for --reingest-from (optional)
+    ///
+    /// Defaults to now. Date / time must be in RFC 3339 format.
+    #[structopt(
+        long = "reingest-to",
+        parse(try_from_str = parse_rfc3339),
+        requires = "reingest-from"
+    )]
+    reingest_to: Option<DateTime<Utc>>,
+
+    /// Re-parse all stored activities to backfill data added in new versions
+    ///
+    /// Activities are re-parsed from the post game carnage report json stored
+    /// with --store-raw-pgcr, so data added to the activity store in newer
+    /// versions of dcli is filled in for activities synced before it existed.
+    /// Activities without stored json are skipped unless --refetch is set.
+    #[structopt(long = "reprocess")]
+    reprocess: bool,
+
+    /// Download activities without stored json again when reprocessing
+    ///
+    /// Activities are downloaded from the Destiny API, which can take a
+    /// long time for large stores.
+    #[structopt(long = "refetch", requires = "reprocess")]
+    refetch: bool,
+
+    /// Sync all members added with --add-member
+    ///
+    /// Members are synced one after another into the same activity store.
+    /// An error syncing one member does not stop the others from syncing.
+    #[structopt(
+        long = "all",
+        conflicts_with_all = &["member-id", "platform"]
+    )]
+    all: bool,
+}
+
+fn parse_rfc3339(src: &str) -> Result<DateTime<Utc>, String> {
+    match DateTime::parse_from_rfc3339(src) {
+        Ok(e) => Ok(e.with_timezone(&Utc)),
+        Err(_e) => Err(
+            "Invalid RFC 3339 Date / Time String : Example : 2020-12-08T17:00:00.774187+00:00"
+                .to_string(),
+        ),
+    }
+}
+
+fn parse_alias(src: &str) -> Result<(String, String), String> {
+    let mut parts = src.splitn(2, '=');
+    let member_id = parts.next().unwrap_or("").trim();
+    let alias = parts.next().unwrap_or("").trim();
+
+    if member_id.is_empty() || alias.is_empty() {
+        return Err(format!(
+            "Alias must be in the form member_id=alias : {}",
+            src
+        ));
+    }
+
+    if !member_id.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("Invalid member id : {}", member_id));
+    }
+
+    Ok((member_id.to_string(), alias.to_string()))
+}
+
+//manages aliases and exits. Aliases are stored in the activity store.
+async fn manage_aliases(store: &mut ActivityStoreInterface, opt: &Opt) {
+    if let Some((member_id, alias)) = &opt.set_alias {
+        if let Err(e) = store.set_player_alias(member_id, alias).await {
+            print_error("Could not set alias.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+        print_message(
+            &format!("{} is now aliased as {}", member_id, alias),
+            opt.porcelain,
+        );
+    }
+
+    if let Some(member_id) = &opt.remove_alias {
+        match store.remove_player_alias(member_id).await {
+            Ok(true) => print_message(
+                &format!("Removed alias for {}", member_id),
+                opt.porcelain,
+            ),
+            Ok(false) => print_message(
+                &format!("{} does not have an alias", member_id),
+                opt.porcelain,
+            ),
+            Err(e) => {
+                print_error("Could not remove alias.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        }
+    }
+
+    if opt.list_aliases {
+        let aliases = match store.retrieve_player_aliases().await {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Could not retrieve aliases.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+
+        if aliases.is_empty() {
+            print_message("No aliases set", opt.porcelain);
+        }
+
+        let mut aliases: Vec<(String, String)> = aliases.into_iter().collect();
+        aliases.sort_by_key(|a| a.1.to_lowercase());
+
+        for (member_id, alias) in aliases {
+            if opt.porcelain {
+                println!("{}\t{}", alias, member_id);
+            } else {
+                println!("{:<0w$}{}", alias, member_id, w = 24);
+            }
+        }
+    }
+}
+
+//manages the members synced with --all and exits
+async fn manage_members(store: &mut ActivityStoreInterface, opt: &Opt) {
+    if let Some(member_id) = &opt.add_member {
+        let member_id = resolve_member_id(store, member_id).await;
+
+        //structopt ensures platform is set with --add-member
+        let platform = opt.platform.unwrap();
+        if let Err(e) = store.add_sync_member(&member_id, &platform).await {
+            print_error("Could not add member.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+        print_message(
+            &format!("{} ({}) will be synced with --all", member_id, platform),
+            opt.porcelain,
+        );
+    }
+
+    if let Some(member_id) = &opt.remove_member {
+        let member_id = resolve_member_id(store, member_id).await;
+        match store.remove_sync_member(&member_id).await {
+            Ok(true) => {
+                print_message(&format!("Removed {}", member_id), opt.porcelain)
+            }
+            Ok(false) => print_message(
+                &format!("{} is not synced with --all", member_id),
+                opt.porcelain,
+            ),
+            Err(e) => {
+                print_error("Could not remove member.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        }
+    }
+
+    if let Some(member_id) = &opt.purge_member {
+        let member_id = resolve_member_id(store, member_id).await;
+        match store.purge_member(&member_id).await {
+            Ok(Some(e)) => {
+                print_message(
+                    &format!(
+                        "Purged {} : {} activities removed",
+                        member_id, e.activities_removed
+                    ),
+                    opt.porcelain,
+                );
+                if !e.member_removed {
+                    print_message("Member kept as a player in activities with other synced members", opt.porcelain);
+                }
+            }
+            Ok(None) => print_message(
+                &format!(
+                    "{} has not been synced into the activity store",
+                    member_id
+                ),
+                opt.porcelain,
+            ),
+            Err(e) => {
+                print_error("Could not purge member.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        }
+    }
+
+    if opt.list_members {
+        let members = match store.retrieve_sync_members().await {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Could not retrieve members.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+
+        if members.is_empty() {
+            print_message(
+                "No members added. Add members with --add-member",
+                opt.porcelain,
+            );
+        }
+
+        for (member_id, platform) in members {
+            if opt.porcelain {
+                println!("{}\t{}", member_id, platform);
+            } else {
+                println!("{:<0w$}{}", member_id, platform, w = 24);
+            }
+        }
+    }
+}
+
+fn list_backups(data_dir: &Path, porcelain: bool) {
+    let backups = match ActivityStoreInterface::list_backups(data_dir) {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Error listing activity store backups.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    if backups.is_empty() {
+        print_message("No backups found", porcelain);
+        return;
+    }
+
+    for backup in backups {
+        println!("{}", backup.display());
+    }
+}
+
+async fn prune_activities(
+    store: &ActivityStoreInterface,
+    cutoff: &DateTime<Utc>,
+    porcelain: bool,
+) -> bool {
+    match store.prune_before(cutoff).await {
+        Ok(e) => {
+            print_message(
+                &format!(
+                    "Removed {} activities before {} ({} players no longer in any activities)",
+                    e.activities_removed,
+                    cutoff.to_rfc3339(),
+                    e.players_removed
+                ),
+                porcelain,
+            );
+            print_message(
+                "Run with --maintenance to reclaim the space used by the removed activities",
+                porcelain,
+            );
+            true
+        }
+        Err(e) => {
+            print_error("Error pruning activities.", e);
+            false
+        }
+    }
+}
+
+//loads seasons from the manifest, and tags activities which were stored
+//before their season was known. The manifest isnt required to sync, so this
+//is skipped if it hasnt been downloaded
+async fn update_seasons(
+    store: &ActivityStoreInterface,
+    data_dir: &Path,
+    verbose: bool,
+) {
+    let mut manifest = match ManifestInterface::new(data_dir, false).await {
+        Ok(e) => e,
+        Err(e) => {
+            print_verbose(
+                &format!(
+                    "Seasons not updated. Could not open manifest : {}",
+                    e
+                ),
+                verbose,
+            );
+            return;
+        }
+    };
+
+    match store.update_seasons(&mut manifest).await {
+        Ok(e) => print_verbose(
+            &format!("Tagged {} activities with their season", e),
+            verbose,
+        ),
+        Err(e) => {
+            print_verbose(&format!("Could not update seasons : {}", e), verbose)
+        }
+    }
+
+    if let Err(e) = manifest.close().await {
+        print_verbose(&format!("Error closing manifest : {}", e), verbose);
+    }
+}
+
+async fn run_maintenance(store: &mut ActivityStoreInterface, porcelain: bool) {
+    eprintln!("Running maintenance on {}", store.get_storage_path());
+
+    let result = match store.run_maintenance().await {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Could not run maintenance on activity store.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    if !result.integrity_errors.is_empty() {
+        print_message(
+            "Integrity check failed. The store was not compacted.",
+            porcelain,
+        );
+        for e in &result.integrity_errors {
+            print_message(e, porcelain);
+        }
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    print_message("Integrity check : ok", porcelain);
+    print_message(
+        &format!("Size before     : {}", human_bytes(result.size_before)),
+        porcelain,
+    );
+    print_message(
+        &format!("Size after      : {}", human_bytes(result.size_after)),
+        porcelain,
+    );
+}
+
+//returns false if any mismatches were found, or activities could not be
+//retrieved
+async fn verify_activities(
+    store: &mut ActivityStoreInterface,
+    sample_size: u32,
+    porcelain: bool,
+) -> bool {
+    eprintln!(
+        "Verifying {} activities against the Destiny API. This may take a while.",
+        sample_size
+    );
+
+    let result = match store.verify_activities(sample_size).await {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Could not verify activity store.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    for (activity_id, e) in &result.failed {
+        eprintln!("Could not retrieve activity {} : {}", activity_id, e);
+    }
+
+    let mut activity_ids: Vec<i64> =
+        result.mismatches.iter().map(|m| m.activity_id).collect();
+    activity_ids.dedup();
+
+    print_message(
+        &format!(
+            "{} activities checked. {} mismatches found in {} activities.",
+            result.activities_checked,
+            result.mismatches.len(),
+            activity_ids.len()
+        ),
+        porcelain,
+    );
+
+    if result.mismatches.is_empty() {
+        return result.failed.is_empty();
+    }
+
+    //one row per mismatch, with the same columns as the table
+    if porcelain {
+        for m in &result.mismatches {
+            println!(
+                "{}\t{}\t{}\t{}\t{}",
+                m.activity_id,
+                m.character_id.as_deref().unwrap_or(""),
+                m.field,
+                m.stored,
+                m.remote
+            );
+        }
+        return false;
+    }
+
+    println!();
+    println!(
+        "{:<22}{:<22}{:<24}{:>10}{:>10}",
+        "ACTIVITY", "CHARACTER", "VALUE", "STORED", "API"
+    );
+    println!("{}", repeat_str("=", 88));
+    for m in &result.mismatches {
+        println!(
+            "{:<22}{:<22}{:<24}{:>10}{:>10}",
+            m.activity_id,
+            m.character_id.as_deref().unwrap_or("-"),
+            m.field,
+            m.stored,
+            m.remote
+        );
+    }
+
+    println!();
+    println!("Activities with mismatches:");
+    for activity_id in activity_ids {
+        println!("{}", activity_id);
+    }
+
+    false
+}
+
+//returns false if any activities could not be re-ingested
+async fn reingest_activities(
+    store: &mut ActivityStoreInterface,
+    opt: &Opt,
+) -> bool {
+    let activity_ids = match (&opt.reingest, &opt.reingest_from) {
+        (Some(ids), _) => ids.clone(),
+        (None, Some(start)) => {
+            let end = opt.reingest_to.unwrap_or_else(Utc::now);
+            let time_period =
+                match DateTimePeriod::with_start_end_time(*start, end) {
+                    Ok(e) => e,
+                    Err(e) => {
+                        print_error("Invalid re-ingest date range.", e);
+                        std::process::exit(EXIT_FAILURE);
+                    }
+                };
+
+            match store.retrieve_activity_ids_in_period(&time_period).await {
+                Ok(e) => e,
+                Err(e) => {
+                    print_error("Could not retrieve activities.", e);
+                    std::process::exit(EXIT_FAILURE);
+                }
+            }
+        }
+        //only called if one of the options is set
+        (None, None) => Vec::new(),
+    };
+
+    eprintln!(
+        "Re-ingesting {} activities from the Destiny API. This may take a while.",
+        activity_ids.len()
+    );
+
+    let result = match store.reingest_activities(&activity_ids).await {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Could not re-ingest activities.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    for activity_id in &result.not_found {
+        eprintln!("Activity {} is not in the activity store", activity_id);
+    }
+
+    for (activity_id, e) in &result.failed {
+        eprintln!("Could not re-ingest activity {} : {}", activity_id, e);
+    }
+
+    print_message(
+        &format!("{} activities re-ingested.", result.reingested.len()),
+        opt.porcelain,
+    );
+
+    result.failed.is_empty()
+}
+
+//returns false if any activities could not be reprocessed
+async fn reprocess_activities(
+    store: &mut ActivityStoreInterface,
+    refetch: bool,
+    porcelain: bool,
+) -> bool {
+    if refetch {
+        eprintln!("Reprocessing activities. Activities without stored json will be downloaded from the Destiny API. This may take a while.");
+    } else {
+        eprintln!("Reprocessing activities with stored json.");
+    }
+
+    let result = match store.reprocess_activities(refetch).await {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Could not reprocess activities.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    for (activity_id, e) in &result.failed {
+        eprintln!("Could not reprocess activity {} : {}", activity_id, e);
+    }
+
+    print_message(
+        &format!("{} activities reprocessed.", result.reprocessed.len()),
+        porcelain,
+    );
+
+    if !result.skipped.is_empty() {
+        print_message(
+            &format!(
+                "{} activities without stored json were skipped. Use --refetch to download them again.",
+                result.skipped.len()
+            ),
+            porcelain,
+        );
+    }
+
+    result.failed.is_empty()
+}
+
+//ids of the member's characters for the selection
+async fn retrieve_character_ids(
+    member_id: &str,
+    platform: &Platform,
+    selection: &CharacterClassSelection,
+    verbose: bool,
+) -> Result<Vec<String>, Error> {
+    let api = ApiInterface::new(verbose)?;
+    let player_info = api.get_player_info(member_id, platform).await?;
+
+    let ids: Vec<String> = player_info
+        .characters
+        .get_by_selection_ref(selection)
+        .iter()
+        .map(|c| c.id.clone())
+        .collect();
+
+    if ids.is_empty() {
+        return Err(Error::CharacterDoesNotExist);
+    }
+
+    Ok(ids)
+}
+
+async fn resolve_member_id(
+    store: &mut ActivityStoreInterface,
+    member_id: &str,
+) -> String {
+    match store.resolve_member_id(member_id).await {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Error resolving member id.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    }
 }
 
 #[tokio::main]
 async fn main() {
-    let opt = Opt::from_args();
+    let mut opt = Opt::from_args();
     print_verbose(&format!("{:#?}", opt), opt.verbose);
 
-    let data_dir = match determine_data_dir(opt.data_dir) {
+    let _timing = timing::report(opt.timing);
+
+    if opt.max_rps.is_some() {
+        ratelimit::set_max_requests_per_second(opt.max_rps);
+    }
+
+    let supported_output =
+        [Output::Default, Output::Tsv, Output::Json, Output::Jsonl];
+    if let Err(e) = opt.output.check_supported(&supported_output) {
+        print_error("Invalid output format.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    if opt.porcelain {
+        opt.output = match opt.output.porcelain(&supported_output) {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Invalid output format.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+    }
+
+    let data_dir = match determine_data_dir(opt.data_dir.clone()) {
         Ok(e) => e,
         Err(e) => {
             print_error("Error initializing storage directory store.", e);
@@ -102,72 +921,518 @@ async fn main() {
         }
     };
 
+    if opt.print_paths {
+        datadir::print_paths(&data_dir);
+        return;
+    }
+
+    //backups are managed without opening the store, since it must be
+    //closed to be restored
+    if opt.list_backups {
+        list_backups(&data_dir, opt.porcelain);
+        return;
+    }
+
+    if let Some(backup) = &opt.restore_backup {
+        if let Err(e) =
+            ActivityStoreInterface::restore_backup(&data_dir, backup)
+        {
+            print_error("Error restoring activity store backup.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+
+        print_message(
+            &format!("Activity store restored from {}", backup.display()),
+            opt.porcelain,
+        );
+        return;
+    }
+
+    let emit_events = opt.output == Output::Jsonl;
+
     let mut store: ActivityStoreInterface =
         match ActivityStoreInterface::init_with_path(&data_dir, opt.verbose)
             .await
         {
             Ok(e) => e,
             Err(e) => {
+                if emit_events {
+                    SyncEvent::error(&format!(
+                        "Error initializing activity store. {}",
+                        e
+                    ))
+                    .emit();
+                }
                 print_error("Error initializing activity store.", e);
                 std::process::exit(EXIT_FAILURE);
             }
         };
 
-    let results = match store.sync(&opt.member_id, &opt.platform).await {
+    store.set_store_raw_pgcr(opt.store_raw_pgcr);
+
+    if opt.maintenance {
+        run_maintenance(&mut store, opt.porcelain).await;
+
+        if let Err(e) = store.close().await {
+            print_error("Error closing activity store.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+        return;
+    }
+
+    if let Some(cutoff) = &opt.prune_before {
+        let ok = prune_activities(&store, cutoff, opt.porcelain).await;
+
+        if let Err(e) = store.close().await {
+            print_error("Error closing activity store.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+
+        if !ok {
+            std::process::exit(EXIT_FAILURE);
+        }
+        return;
+    }
+
+    if opt.reingest.is_some() || opt.reingest_from.is_some() {
+        let ok = reingest_activities(&mut store, &opt).await;
+
+        if let Err(e) = store.close().await {
+            print_error("Error closing activity store.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+
+        if !ok {
+            std::process::exit(EXIT_FAILURE);
+        }
+        return;
+    }
+
+    if opt.reprocess {
+        let ok =
+            reprocess_activities(&mut store, opt.refetch, opt.porcelain).await;
+
+        if let Err(e) = store.close().await {
+            print_error("Error closing activity store.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+
+        if !ok {
+            std::process::exit(EXIT_FAILURE);
+        }
+        return;
+    }
+
+    if let Some(sample_size) = opt.verify {
+        let ok =
+            verify_activities(&mut store, sample_size, opt.porcelain).await;
+
+        if let Err(e) = store.close().await {
+            print_error("Error closing activity store.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+
+        if !ok {
+            std::process::exit(EXIT_FAILURE);
+        }
+        return;
+    }
+
+    if opt.set_alias.is_some() || opt.remove_alias.is_some() || opt.list_aliases
+    {
+        manage_aliases(&mut store, &opt).await;
+
+        if let Err(e) = store.close().await {
+            print_error("Error closing activity store.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+        return;
+    }
+
+    if opt.add_member.is_some()
+        || opt.remove_member.is_some()
+        || opt.purge_member.is_some()
+        || opt.list_members
+    {
+        manage_members(&mut store, &opt).await;
+
+        if let Err(e) = store.close().await {
+            print_error("Error closing activity store.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+        return;
+    }
+
+    store.set_emit_events(emit_events);
+    store.set_sync_pve(opt.pve);
+    store.set_max_activities(opt.max_activities);
+    store.set_pgcr_concurrency(
+        opt.concurrency.unwrap_or(DEFAULT_PGCR_CONCURRENCY),
+    );
+
+    //done before syncing so new activities are tagged with their season as
+    //they are stored
+    update_seasons(&store, &data_dir, opt.verbose).await;
+
+    //first ctrl-c cancels the sync once the current batch of activities has
+    //been stored. A second ctrl-c exits immediately.
+    let cancel = CancellationToken::new();
+    let handler_cancel = cancel.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_err() {
+            return;
+        }
+
+        eprintln!();
+        eprintln!("Cancelling sync. Press Ctrl-C again to exit immediately.");
+        handler_cancel.cancel();
+
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(EXIT_FAILURE);
+        }
+    });
+
+    let results: Vec<MemberSyncResult> = if opt.all {
+        match store.sync_all(&cancel).await {
+            Ok(e) => e,
+            Err(e) => handle_sync_error(store, e, emit_events).await,
+        }
+    } else {
+        //structopt ensures these are set if we are not managing aliases or
+        //members
+        let platform = opt.platform.unwrap();
+        let member_id =
+            resolve_member_id(&mut store, opt.member_id.as_ref().unwrap())
+                .await;
+
+        let result = match (
+            &opt.character_id,
+            &opt.character_class,
+            &opt.character_class_selection,
+        ) {
+            (Some(character_id), Some(class_type), _) => {
+                store
+                    .sync_character(
+                        &member_id,
+                        &platform,
+                        character_id,
+                        class_type,
+                        &cancel,
+                    )
+                    .await
+            }
+            (_, _, Some(selection))
+                if *selection != CharacterClassSelection::All =>
+            {
+                match retrieve_character_ids(
+                    &member_id,
+                    &platform,
+                    selection,
+                    opt.verbose,
+                )
+                .await
+                {
+                    Ok(ids) => {
+                        store
+                            .sync_characters(
+                                &member_id, &platform, &ids, &cancel,
+                            )
+                            .await
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            _ => store.sync(&member_id, &platform, &cancel).await,
+        };
+
+        match result {
+            Ok(e) => vec![MemberSyncResult {
+                member_id,
+                platform,
+                result: Ok(e),
+            }],
+            Err(e) => handle_sync_error(store, e, emit_events).await,
+        }
+    };
+
+    if results.is_empty() {
+        eprintln!("No members to sync. Add members with --add-member");
+    }
+
+    //errors for individual members when syncing with --all. These don't stop
+    //the other members from syncing, but we exit with an error once done
+    let mut had_errors = false;
+    let mut synced: Vec<(String, Platform, SyncResult)> = Vec::new();
+    for r in results {
+        match r.result {
+            Ok(e) => synced.push((r.member_id, r.platform, e)),
+            Err(e) => {
+                had_errors = true;
+                let msg =
+                    format!("Error syncing {} ({}).", r.member_id, r.platform);
+                if emit_events {
+                    SyncEvent::error(&format!("{} {}", msg, e)).emit();
+                }
+                print_error(&msg, e);
+            }
+        }
+    }
+
+    //modes added by bungie since this version was released. These are stored
+    //but wont display correctly
+    let unknown_modes = match store.retrieve_unknown_modes().await {
         Ok(e) => e,
         Err(e) => {
-            print_error("Error syncing ids.", e);
-            std::process::exit(EXIT_FAILURE);
+            print_verbose(
+                &format!("Could not retrieve unknown modes : {}", e),
+                opt.verbose,
+            );
+            Vec::new()
         }
     };
 
+    let format_timer = timing::start(TimingCategory::Formatting);
+
     match opt.output {
         Output::Default => {
-            print_default(&results, &store);
+            print_default(&synced, &unknown_modes, &store, opt.all);
         }
         Output::Tsv => {
-            print_tsv(&results, &store);
+            print_tsv(&synced, &unknown_modes, &store, opt.all);
         }
+        Output::Json => {
+            print_json(&synced, &unknown_modes, &store, opt.all);
+        }
+        Output::Jsonl => {
+            //sync_finished event has already been emitted by the store
+        }
+        //other formats are rejected at startup
+        _ => {}
     }
+
+    drop(format_timer);
+
+    if let Err(e) = store.close().await {
+        print_error("Error closing activity store.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    if had_errors {
+        std::process::exit(EXIT_FAILURE);
+    }
+}
+
+//prints the error for a failed sync, closing the store if the sync was
+//cancelled, and exits
+async fn handle_sync_error(
+    store: ActivityStoreInterface,
+    e: Error,
+    emit_events: bool,
+) -> ! {
+    if e == Error::Cancelled {
+        if emit_events {
+            SyncEvent::error("Sync cancelled.").emit();
+        }
+
+        if let Err(e) = store.close().await {
+            print_error("Error closing activity store.", e);
+        }
+
+        eprintln!("Sync cancelled. Remaining activities will be synced the next time app is run.");
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    if emit_events {
+        SyncEvent::error(&format!("Error syncing ids. {}", e)).emit();
+    }
+    print_error("Error syncing ids.", e);
+    std::process::exit(EXIT_FAILURE);
 }
 
-fn print_tsv(results: &SyncResult, store: &ActivityStoreInterface) {
-    let mut name_values: Vec<(&str, String)> = Vec::new();
+fn print_tsv(
+    results: &[(String, Platform, SyncResult)],
+    unknown_modes: &[ModeActivityCount],
+    store: &ActivityStoreInterface,
+    all: bool,
+) {
+    let unknown_mode_ids: Vec<String> = unknown_modes
+        .iter()
+        .map(|m| m.mode.to_id().to_string())
+        .collect();
 
-    name_values.push(("total_synced", results.total_synced.to_string()));
-    name_values.push(("total_available", results.total_available.to_string()));
-    name_values.push(("path", store.get_storage_path()));
+    //when syncing all members, each member gets its own block of name / value
+    //pairs, seperated by an empty line
+    let mut blocks: Vec<String> = Vec::new();
+    for (member_id, platform, result) in results {
+        let mut name_values: Vec<(&str, String)> = Vec::new();
 
-    print!("{}", build_tsv(name_values));
+        if all {
+            name_values.push(("member_id", member_id.to_string()));
+            name_values.push(("platform", format!("{}", platform)));
+        }
+
+        name_values.push(("total_synced", result.total_synced.to_string()));
+        name_values
+            .push(("total_available", result.total_available.to_string()));
+        name_values.push(("path", store.get_storage_path()));
+        name_values.push(("unknown_modes", unknown_mode_ids.join(",")));
+
+        blocks.push(build_tsv(name_values));
+    }
+
+    print!("{}", blocks.join(TSV_EOL));
+}
+
+#[derive(Serialize, Debug)]
+struct CharacterSyncJson {
+    character_id: String,
+    class_type: String,
+    total_synced: u32,
+    total_available: u32,
 }
 
-fn print_default(results: &SyncResult, store: &ActivityStoreInterface) {
+#[derive(Serialize, Debug)]
+struct UnknownModeJson {
+    mode_id: u32,
+    activities: u32,
+}
+
+#[derive(Serialize, Debug)]
+struct SyncResultJson {
+    //only set when syncing all members
+    #[serde(skip_serializing_if = "Option::is_none")]
+    member_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    platform: Option<String>,
+
+    total_synced: u32,
+    total_available: u32,
+    characters: Vec<CharacterSyncJson>,
+    unknown_modes: Vec<UnknownModeJson>,
+    path: String,
+}
+
+fn print_json(
+    results: &[(String, Platform, SyncResult)],
+    unknown_modes: &[ModeActivityCount],
+    store: &ActivityStoreInterface,
+    all: bool,
+) {
+    let out: Vec<SyncResultJson> = results
+        .iter()
+        .map(|(member_id, platform, result)| {
+            let characters: Vec<CharacterSyncJson> = result
+                .characters
+                .iter()
+                .map(|c| CharacterSyncJson {
+                    character_id: c.character_id.to_string(),
+                    class_type: format!("{}", c.class_type),
+                    total_synced: c.total_synced,
+                    total_available: c.total_available,
+                })
+                .collect();
+
+            SyncResultJson {
+                member_id: if all {
+                    Some(member_id.to_string())
+                } else {
+                    None
+                },
+                platform: if all {
+                    Some(format!("{}", platform))
+                } else {
+                    None
+                },
+                total_synced: result.total_synced,
+                total_available: result.total_available,
+                characters,
+                unknown_modes: unknown_modes
+                    .iter()
+                    .map(|m| UnknownModeJson {
+                        mode_id: m.mode.to_id(),
+                        activities: m.activities,
+                    })
+                    .collect(),
+                path: store.get_storage_path(),
+            }
+        })
+        .collect();
+
+    //a single sync outputs an object, and --all outputs an array with an
+    //object for each member
+    let json = if all {
+        serde_json::to_string_pretty(&out)
+    } else {
+        match out.first() {
+            Some(e) => serde_json::to_string_pretty(e),
+            None => return,
+        }
+    };
+
+    match json {
+        Ok(e) => println!("{}", e),
+        Err(e) => {
+            print_error(
+                "Error serializing sync results to json.",
+                Error::from(e),
+            );
+            std::process::exit(EXIT_FAILURE);
+        }
+    }
+}
+
+fn print_default(
+    results: &[(String, Platform, SyncResult)],
+    unknown_modes: &[ModeActivityCount],
+    store: &ActivityStoreInterface,
+    all: bool,
+) {
     println!();
     println!("{}", "Activity sync complete".to_string().to_uppercase());
     println!("------------------------------------------------");
 
-    let s = if results.total_synced == 1 {
-        "y"
-    } else {
-        "ies"
-    };
+    for (member_id, platform, result) in results {
+        if all {
+            println!("{} ({})", member_id, platform);
+        }
 
-    println!("{} activit{} synced", results.total_synced, s);
+        let s = if result.total_synced == 1 { "y" } else { "ies" };
 
-    let total_available = results.total_available;
-    let queue_str = if total_available == 1 {
-        "1 activity in queue. Activity will be synced the next time app is run."
-            .to_string()
-    } else if total_available == 0 {
-        "No activities in queue".to_string()
-    } else {
-        format!(
-            "{} activies in queue. Activities will be synced the next time app is run",
-            results.total_available
-        )
-    };
+        println!("{} activit{} synced", result.total_synced, s);
+
+        let total_available = result.total_available;
+        let queue_str = if total_available == 1 {
+            "1 activity in queue. Activity will be synced the next time app is run."
+                .to_string()
+        } else if total_available == 0 {
+            "No activities in queue".to_string()
+        } else {
+            format!(
+                "{} activies in queue. Activities will be synced the next time app is run",
+                result.total_available
+            )
+        };
 
-    println!("{}", queue_str);
+        println!("{}", queue_str);
+
+        if all {
+            println!();
+        }
+    }
 
     println!("Database stored at: {}", store.get_storage_path());
+
+    if !unknown_modes.is_empty() {
+        println!();
+        println!("UNKNOWN MODES");
+        println!("------------------------------------------------");
+        for m in unknown_modes {
+            println!(
+                "Mode id {} : {} activities",
+                m.mode.to_id(),
+                m.activities
+            );
+        }
+        println!("These modes have been added since this version of dcli was released, and have been stored, but may not display correctly. Check for an updated version of dcli.");
+    }
 }
@@ -20,13 +20,20 @@
 * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 */
 
+use std::path::PathBuf;
+
+use dcli::activitystoreinterface::ActivityStoreInterface;
 use dcli::apiinterface::ApiInterface;
-use dcli::character::Characters;
+use dcli::character::{CharacterMetadata, Characters};
 use dcli::enums::platform::Platform;
 use dcli::error::Error;
 use dcli::output::Output;
+use dcli::timing::{self, TimingCategory};
 use dcli::utils::EXIT_FAILURE;
-use dcli::utils::{print_error, print_verbose, repeat_str, TSV_DELIM, TSV_EOL};
+use dcli::utils::{
+    determine_data_dir, human_date_format, print_error, print_message,
+    print_verbose, repeat_str, TSV_DELIM, TSV_EOL,
+};
 use structopt::StructOpt;
 
 //todo: could move this to apiclient
@@ -80,6 +87,13 @@ struct Opt {
     #[structopt(short = "v", long = "verbose")]
     verbose: bool,
 
+    /// Print how long was spent in API calls, SQL queries and formatting
+    ///
+    /// Timings are printed to stderr when the command finishes, and can help
+    /// diagnose slow commands.
+    #[structopt(long = "timing")]
+    timing: bool,
+
     /// Format for command output
     ///
     /// Valid values are default (Default) and tsv.
@@ -92,13 +106,108 @@ struct Opt {
         default_value = "default"
     )]
     output: Output,
+
+    /// Only write structured data to stdout
+    ///
+    /// All other messages (i.e. errors, or no characters stored) are written to
+    /// stderr. Uses tsv output.
+    #[structopt(long = "porcelain")]
+    porcelain: bool,
+
+    /// Display the character details stored in the activity store
+    ///
+    /// Character details (light, emblem and when the character was last
+    /// played) are stored each time the member is synced with dclias. The
+    /// API is not called, and a player alias can be used for the member id.
+    #[structopt(long = "stored")]
+    stored: bool,
+
+    /// Directory where activity sqlite3 database is stored. (optional)
+    ///
+    /// Only used with --stored. By default data will be loaded from the
+    /// appropriate system local storage directory.
+    #[structopt(short = "D", long = "data-dir", parse(from_os_str))]
+    data_dir: Option<PathBuf>,
+}
+
+//retrieves the character details stored when the member was last synced
+async fn retrieve_stored_characters(
+    member_id: &str,
+    data_dir: Option<PathBuf>,
+    verbose: bool,
+) -> Result<Vec<CharacterMetadata>, Error> {
+    let data_dir = determine_data_dir(data_dir)?;
+    let store =
+        ActivityStoreInterface::init_read_only_with_path(&data_dir, verbose)
+            .await?;
+
+    let member_id = store.resolve_member_id(member_id).await?;
+    let characters = store.retrieve_character_metadata(&member_id).await?;
+
+    store.close().await?;
+
+    Ok(characters)
 }
 
 #[tokio::main]
 async fn main() {
-    let opt = Opt::from_args();
+    let mut opt = Opt::from_args();
     print_verbose(&format!("{:#?}", opt), opt.verbose);
 
+    let _timing = timing::report(opt.timing);
+
+    let supported_output = [Output::Default, Output::Tsv];
+    if let Err(e) = opt.output.check_supported(&supported_output) {
+        print_error("Invalid output format.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    if opt.porcelain {
+        opt.output = match opt.output.porcelain(&supported_output) {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Invalid output format.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+    }
+
+    if opt.stored {
+        let characters = match retrieve_stored_characters(
+            &opt.member_id,
+            opt.data_dir.clone(),
+            opt.verbose,
+        )
+        .await
+        {
+            Ok(e) => e,
+            Err(e) => {
+                print_error(
+                    "Error retrieving characters from activity store. Have you run dclias?",
+                    e,
+                );
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+
+        if characters.is_empty() {
+            print_message(
+                "No characters stored for member. Sync the member with dclias.",
+                opt.porcelain,
+            );
+            return;
+        }
+
+        let _format_timer = timing::start(TimingCategory::Formatting);
+        match opt.output {
+            Output::Default => print_stored_default(&characters),
+            Output::Tsv => print_stored_tsv(&characters),
+            //other formats are rejected at startup
+            _ => {}
+        }
+        return;
+    }
+
     let chars: Characters =
         match retrieve_characters(opt.member_id, opt.platform, opt.verbose)
             .await
@@ -116,6 +225,8 @@ async fn main() {
             }
         };
 
+    let _format_timer = timing::start(TimingCategory::Formatting);
+
     match opt.output {
         Output::Default => {
             print_default(&chars);
@@ -123,6 +234,8 @@ async fn main() {
         Output::Tsv => {
             print_tsv(&chars);
         }
+        //other formats are rejected at startup
+        _ => {}
     }
 }
 
@@ -178,3 +291,59 @@ fn print_tsv(characters: &Characters) {
         );
     }
 }
+
+//characters are sorted by when they were last played, so the first is the
+//last active character
+fn print_stored_default(characters: &[CharacterMetadata]) {
+    let col_w = 12;
+    let col_id = 24;
+    let col_date = 20;
+    println!(
+        "{:<0col_w$}{:<0col_id$}{:>col_w$}  {:<0col_date$}{:<0col_w$}",
+        "CLASS",
+        "ID",
+        "LIGHT",
+        "LAST PLAYED",
+        "STATUS",
+        col_w = col_w,
+        col_id = col_id,
+        col_date = col_date,
+    );
+
+    println!("{}", repeat_str("-", col_w * 3 + col_id + col_date + 2));
+
+    for (i, c) in characters.iter().enumerate() {
+        let label = if i == 0 { "LAST ACTIVE" } else { "" };
+
+        println!(
+            "{:<0col_w$}{:<0col_id$}{:>col_w$}  {:<0col_date$}{:<0col_w$}",
+            format!("{}", c.class_type),
+            c.character_id,
+            c.light,
+            human_date_format(&c.date_last_played),
+            label,
+            col_w = col_w,
+            col_id = col_id,
+            col_date = col_date,
+        );
+    }
+}
+
+fn print_stored_tsv(characters: &[CharacterMetadata]) {
+    for (i, c) in characters.iter().enumerate() {
+        let label = if i == 0 { "LAST ACTIVE" } else { "" };
+
+        print!(
+            "{c}{delim}{i}{delim}{s}{delim}{l}{delim}{e}{delim}{d}{delim}{m}{eol}",
+            c = c.class_type,
+            i = c.character_id,
+            s = label,
+            l = c.light,
+            e = c.emblem_hash,
+            d = c.date_last_played.to_rfc3339(),
+            m = c.minutes_played_total,
+            delim = TSV_DELIM,
+            eol = TSV_EOL
+        );
+    }
+}
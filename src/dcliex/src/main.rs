@@ -0,0 +1,495 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use dcli::activitystoreinterface::ActivityStoreInterface;
+use dcli::datadir;
+use dcli::enums::mode::Mode;
+use dcli::error::Error;
+use dcli::export::{
+    Anonymizer, ChunkedTsvWriter, PlayerPerformanceRow, PARQUET_EXPORT_TABLES,
+};
+use dcli::manifestinterface::ManifestInterface;
+use dcli::parquet::ParquetWriter;
+use dcli::timing::{self, TimingCategory};
+use dcli::utils::{
+    determine_data_dir, print_error, print_message, print_verbose, EXIT_FAILURE,
+};
+use structopt::StructOpt;
+
+const PLAYER_PERFORMANCES_PREFIX: &str = "player_performances";
+const STORE_JSON_FILE_NAME: &str = "store.json";
+
+fn parse_and_validate_mode(src: &str) -> Result<Mode, String> {
+    let mode = Mode::from_str(src)?;
+
+    if !mode.is_crucible() && !mode.is_gambit() {
+        return Err(format!("Unsupported mode specified : {}", src));
+    }
+
+    Ok(mode)
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(verbatim_doc_comment)]
+/// Command line tool for bulk exporting Destiny 2 Crucible data from the dcli
+/// activity store.
+///
+/// Exports include the performances of every player in every stored
+/// activity (not just synced players), and can be anonymized for sharing
+/// as part of community datasets. Output is split across multiple tsv files,
+/// or streamed to stdout as newline delimited json with --ndjson.
+///
+/// The activity, team_result, character_activity_stats and weapon_result
+/// tables (along with the member and character tables they reference) can
+/// also be exported
+/// as Apache Parquet files with --parquet, for analyzing large datasets in
+/// tools such as DuckDB and pandas.
+///
+/// The entire store can be exported to a single, documented json file with
+/// --store-json, for archiving data or migrating it off of sqlite.
+///
+/// Created by Mike Chambers.
+/// https://www.mikechambers.com
+///
+/// Get support,request features or just chat on the dcli Discord server:
+/// https://discord.gg/2Y8bV2Mq3p
+///
+/// Get the latest version, download the source and log issues at:
+/// https://github.com/mikechambers/dcli
+///
+/// Released under an MIT License.
+struct Opt {
+    /// Directory to write exported files to
+    ///
+    /// Will be created if it does not exist. Existing export files in the
+    /// directory will be overwritten.
+    #[structopt(
+        short = "o",
+        long = "output-dir",
+        parse(from_os_str),
+        required_unless = "ndjson"
+    )]
+    output_dir: Option<PathBuf>,
+
+    /// Stream every stored activity to stdout as newline delimited json
+    ///
+    /// Each line is a json object containing the full activity (details,
+    /// teams, player performances, weapons and medals). Activities are read
+    /// from the store a page at a time, so large stores can be piped into
+    /// tools such as jq without running out of memory.
+    #[structopt(long = "ndjson")]
+    ndjson: bool,
+
+    /// Export store tables as Apache Parquet files
+    ///
+    /// Writes member, character, activity, team_result,
+    /// character_activity_stats and weapon_result parquet files to the output
+    /// directory, one per table. Tables keep the activity store's row ids,
+    /// and can be joined on them in the same way as the store. Cannot be used
+    /// with --anonymize.
+    #[structopt(
+        long = "parquet",
+        conflicts_with_all = &["ndjson", "anonymize"]
+    )]
+    parquet: bool,
+
+    /// Export the entire activity store as a single json file
+    ///
+    /// Writes store.json to the output directory, containing every member,
+    /// character and activity (with teams, player stats, weapons and medals)
+    /// in the store. Useful for archiving data, or migrating it to another
+    /// database. All activities are exported, regardless of --mode. Cannot be
+    /// used with --anonymize.
+    #[structopt(
+        long = "store-json",
+        conflicts_with_all = &["ndjson", "parquet", "anonymize"]
+    )]
+    store_json: bool,
+
+    /// Activity mode to export
+    ///
+    /// Supported values are all_pvp (default), control, clash, elimination,
+    /// mayhem, iron_banner, all_private, rumble, pvp_competitive,
+    /// quickplay and trials_of_osiris.
+    ///
+    /// Gambit values are all_gambit, gambit and gambit_prime.
+    #[structopt(long = "mode", short = "M",
+        parse(try_from_str=parse_and_validate_mode), default_value = "all_pvp")]
+    mode: Mode,
+
+    /// Maximum number of rows to write to each file
+    ///
+    /// When exporting with --parquet, this is the maximum number of rows in
+    /// each row group of a file.
+    #[structopt(long = "rows-per-file", default_value = "100000")]
+    rows_per_file: usize,
+
+    /// Anonymize player data
+    ///
+    /// Member and character ids are replaced with salted hashes (consistent
+    /// within the export) and display names are removed.
+    #[structopt(short = "a", long = "anonymize")]
+    anonymize: bool,
+
+    /// Salt used when anonymizing player data (optional)
+    ///
+    /// Using the same salt across exports will generate the same anonymized
+    /// ids for the same players. If not specified, a new salt is generated
+    /// for each export.
+    #[structopt(long = "salt")]
+    salt: Option<String>,
+
+    /// Only write structured data to stdout
+    ///
+    /// The paths of the exported files are written to stdout, one per line,
+    /// and all other messages are written to stderr. With --ndjson, only
+    /// the exported activities are written to stdout.
+    #[structopt(long = "porcelain")]
+    porcelain: bool,
+
+    ///Print out additional information
+    ///
+    ///Output is printed to stderr.
+    #[structopt(short = "v", long = "verbose")]
+    verbose: bool,
+
+    /// Print how long was spent in API calls, SQL queries and formatting
+    ///
+    /// Timings are printed to stderr when the command finishes, and can help
+    /// diagnose slow commands.
+    #[structopt(long = "timing")]
+    timing: bool,
+
+    /// Directory where Destiny 2 manifest and activity database files are stored. (optional)
+    ///
+    /// This will normally be downloaded using the dclim and dclias tools, and uses
+    /// a system appropriate directory by default.
+    #[structopt(short = "D", long = "data-dir", parse(from_os_str))]
+    data_dir: Option<PathBuf>,
+
+    /// Print the paths of the files and directories used by dcli and exit
+    ///
+    /// Includes the data directory, manifest, activity store and the cache,
+    /// config and backup directories.
+    #[structopt(long = "print-paths")]
+    print_paths: bool,
+}
+
+//generates a random salt from the os rng. It must not be guessable, since
+//anyone who knows the salt can brute force ids back out of an export.
+fn generate_salt() -> Result<String, Error> {
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes).map_err(std::io::Error::from)?;
+
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+//streams all stored activities to stdout, one json object per line
+async fn export_ndjson(
+    store: ActivityStoreInterface,
+    data_dir: &Path,
+    mode: &Mode,
+    anonymizer: &Option<Anonymizer>,
+) {
+    let mut manifest = match ManifestInterface::new(data_dir, false).await {
+        Ok(e) => e,
+        Err(e) => {
+            print_error(
+                "Could not initialize manifest. Have you run dclim?",
+                e,
+            );
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    let stdout = std::io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    eprintln!("Exporting activities. This may take a while.");
+    let result = store
+        .stream_activities(mode, &mut manifest, |mut activity| {
+            let _format_timer = timing::start(TimingCategory::Formatting);
+            if let Some(a) = anonymizer {
+                a.anonymize_activity(&mut activity);
+            }
+
+            serde_json::to_writer(&mut out, &activity)?;
+            writeln!(out)?;
+            Ok(())
+        })
+        .await;
+
+    if let Err(e) = store.close().await {
+        print_error("Error closing activity store.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    let count = match result.and_then(|e| {
+        out.flush()?;
+        Ok(e)
+    }) {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Error exporting activities.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    eprintln!("{} activities exported.", count);
+}
+
+//writes each of the export tables to its own parquet file
+async fn export_parquet(
+    store: ActivityStoreInterface,
+    output_dir: &Path,
+    mode: &Mode,
+    rows_per_group: usize,
+    porcelain: bool,
+) {
+    if let Err(e) = std::fs::create_dir_all(output_dir) {
+        print_error("Could not create output directory.", e.into());
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    eprintln!("Exporting tables to parquet. This may take a while.");
+
+    let mut exported: Vec<(u64, PathBuf)> = Vec::new();
+    for table in PARQUET_EXPORT_TABLES {
+        let path = output_dir.join(format!("{}.parquet", table.name));
+
+        let mut writer =
+            match ParquetWriter::new(&path, table.columns, rows_per_group) {
+                Ok(e) => e,
+                Err(e) => {
+                    print_error("Could not create parquet file.", e);
+                    std::process::exit(EXIT_FAILURE);
+                }
+            };
+
+        let result = store
+            .export_table_rows(table, mode, |values| {
+                let _format_timer = timing::start(TimingCategory::Formatting);
+                writer.write_row(&values)
+            })
+            .await
+            .and_then(|_| writer.finish());
+
+        match result {
+            Ok(e) => exported.push((e, path)),
+            Err(e) => {
+                print_error(
+                    &format!("Error exporting {} table.", table.name),
+                    e,
+                );
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+    }
+
+    if let Err(e) = store.close().await {
+        print_error("Error closing activity store.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    for (count, path) in exported {
+        print_message(
+            &format!("{} rows exported to {}", count, path.display()),
+            porcelain,
+        );
+        if porcelain {
+            println!("{}", path.display());
+        }
+    }
+}
+
+//writes the entire activity store to a single json file
+async fn export_store_json(
+    store: ActivityStoreInterface,
+    output_dir: &Path,
+    porcelain: bool,
+) {
+    if let Err(e) = std::fs::create_dir_all(output_dir) {
+        print_error("Could not create output directory.", e.into());
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    let path = output_dir.join(STORE_JSON_FILE_NAME);
+
+    eprintln!("Exporting activity store to json. This may take a while.");
+    let result = store.export_json(&path).await;
+
+    if let Err(e) = store.close().await {
+        print_error("Error closing activity store.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    let count = match result {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Error exporting activity store.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    print_message(
+        &format!("{} activities exported to {}", count, path.display()),
+        porcelain,
+    );
+    if porcelain {
+        println!("{}", path.display());
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let opt = Opt::from_args();
+    print_verbose(&format!("{:#?}", opt), opt.verbose);
+
+    let _timing = timing::report(opt.timing);
+
+    let data_dir = match determine_data_dir(opt.data_dir) {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Error initializing storage directory store.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    if opt.print_paths {
+        datadir::print_paths(&data_dir);
+        return;
+    }
+
+    let store = match ActivityStoreInterface::init_read_only_with_path(
+        &data_dir,
+        opt.verbose,
+    )
+    .await
+    {
+        Ok(e) => e,
+        Err(e) => {
+            print_error(
+                "Could not initialize activity store. Have you run dclias?",
+                e,
+            );
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    let anonymizer = if opt.anonymize {
+        let salt = match opt.salt.clone() {
+            Some(e) => e,
+            None => match generate_salt() {
+                Ok(e) => e,
+                Err(e) => {
+                    print_error("Could not generate anonymization salt.", e);
+                    std::process::exit(EXIT_FAILURE);
+                }
+            },
+        };
+        Some(Anonymizer::new(&salt))
+    } else {
+        None
+    };
+
+    if opt.ndjson {
+        export_ndjson(store, &data_dir, &opt.mode, &anonymizer).await;
+        return;
+    }
+
+    //structopt ensures this is set if not exporting ndjson
+    let output_dir = opt.output_dir.unwrap();
+
+    if opt.store_json {
+        export_store_json(store, &output_dir, opt.porcelain).await;
+        return;
+    }
+
+    if opt.parquet {
+        export_parquet(
+            store,
+            &output_dir,
+            &opt.mode,
+            opt.rows_per_file,
+            opt.porcelain,
+        )
+        .await;
+        return;
+    }
+
+    let mut writer = match ChunkedTsvWriter::new(
+        &output_dir,
+        PLAYER_PERFORMANCES_PREFIX,
+        opt.rows_per_file,
+        PlayerPerformanceRow::COLUMNS,
+    ) {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Could not create output directory.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    eprintln!("Exporting player performances. This may take a while.");
+    let result = store
+        .export_player_performances(&opt.mode, |mut row| {
+            let _format_timer = timing::start(TimingCategory::Formatting);
+            if let Some(a) = &anonymizer {
+                a.anonymize(&mut row);
+            }
+            writer.write_row(&row.values())
+        })
+        .await;
+
+    if let Err(e) = store.close().await {
+        print_error("Error closing activity store.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    let count = match result {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Error exporting player performances.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    let files = match writer.finish() {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Error writing export files.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    print_message(
+        &format!("{} player performances exported to:", count),
+        opt.porcelain,
+    );
+    for f in files {
+        println!("{}", f.display());
+    }
+}
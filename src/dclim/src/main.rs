@@ -23,22 +23,24 @@
 mod manifest_info;
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use dcli::apiclient::ApiClient;
+use dcli::datadir;
+use dcli::enums::language::Language;
 use dcli::error::Error;
-use dcli::manifestinterface::MANIFEST_FILE_NAME;
+use dcli::manifestinterface::{MANIFEST_FILE_NAME, MANIFEST_INFO_FILE_NAME};
 use dcli::output::Output;
 use dcli::response::manifest::ManifestResponse;
+use dcli::timing::{self, TimingCategory};
 use dcli::utils::EXIT_FAILURE;
 use dcli::utils::{build_tsv, determine_data_dir, print_error, print_verbose};
 use manifest_info::ManifestInfo;
 use structopt::StructOpt;
 use tokio::io::AsyncWriteExt;
 
-pub const MANIFEST_INFO_FILE_NAME: &str = "manifest_info.json";
-
 async fn retrieve_manifest_info(
+    language: &Language,
     print_url: bool,
 ) -> Result<ManifestInfo, Error> {
     let client: ApiClient = ApiClient::new(print_url)?;
@@ -51,7 +53,7 @@ async fn retrieve_manifest_info(
         None => return Err(Error::ApiResponseMissing), //we should never get here as this will be caught earlier
     };
 
-    let m_info: ManifestInfo = ManifestInfo::from_manifest(&manifest);
+    let m_info: ManifestInfo = ManifestInfo::from_manifest(manifest, language)?;
 
     Ok(m_info)
 }
@@ -76,6 +78,33 @@ fn load_manifest_info(path: &PathBuf) -> Result<ManifestInfo, Error> {
     Ok(m)
 }
 
+//moves the current manifest and its info into the previous manifest slot
+//so it is still available after a new manifest is downloaded
+fn keep_previous_manifest(
+    data_dir: &Path,
+    language: &Language,
+    m_path: &Path,
+    m_info_path: &Path,
+) -> Result<(), Error> {
+    if !m_path.exists() {
+        return Ok(());
+    }
+
+    let previous_dir = datadir::named_manifest_dir(
+        data_dir,
+        &language.manifest_name(datadir::PREVIOUS_MANIFEST_NAME),
+    );
+    fs::create_dir_all(&previous_dir)?;
+
+    fs::rename(m_path, previous_dir.join(MANIFEST_FILE_NAME))?;
+
+    if m_info_path.exists() {
+        fs::rename(m_info_path, previous_dir.join(MANIFEST_INFO_FILE_NAME))?;
+    }
+
+    Ok(())
+}
+
 //should this move to ApiClient?
 async fn download_manifest(
     url: &str,
@@ -105,7 +134,7 @@ async fn download_manifest(
     let mut manifest = zip.by_index(0)?;
 
     //reference to file we are going to write the ucompressed manifest to
-    let mut outfile = fs::File::create(&path)?;
+    let mut outfile = fs::File::create(path)?;
 
     //save the uncompressed / unzipped manifest to the file system
     std::io::copy(&mut manifest, &mut outfile)?;
@@ -140,12 +169,51 @@ struct Opt {
     #[structopt(short = "D", long = "data-dir", parse(from_os_str))]
     data_dir: Option<PathBuf>,
 
+    /// Print the paths of the files and directories used by dcli and exit
+    ///
+    /// Includes the data directory, manifest, activity store and the cache,
+    /// config and backup directories.
+    #[structopt(long = "print-paths")]
+    print_paths: bool,
+
+    /// Name of the manifest to download and check (optional)
+    ///
+    /// Defaults to live, which is the manifest used by the other tools. When the
+    /// live manifest is updated, the existing manifest is kept under the name
+    /// previous. Other names (such as staging) can be used to keep additional
+    /// manifests, which can be selected with --manifest-name in the other tools.
+    /// Names may only contain letters, numbers, _ and -.
+    #[structopt(
+        long = "manifest-name",
+        default_value = "live",
+        parse(try_from_str = datadir::parse_manifest_name)
+    )]
+    manifest_name: String,
+
+    /// Language of the manifest to download and check (optional)
+    ///
+    /// Defaults to en. Manifests for other languages are kept alongside the
+    /// english manifest, and can be used to display weapon, medal and map
+    /// names in that language with the --language flag in the report tools.
+    ///
+    /// Valid values are en, fr, es, es-mx, de, it, ja, pt-br, ru, pl, ko,
+    /// zh-cht and zh-chs.
+    #[structopt(long = "language", default_value = "en")]
+    language: Language,
+
     ///Print out additional information
     ///
     ///Output is printed to stderr.
     #[structopt(short = "v", long = "verbose")]
     verbose: bool,
 
+    /// Print how long was spent in API calls, SQL queries and formatting
+    ///
+    /// Timings are printed to stderr when the command finishes, and can help
+    /// diagnose slow commands.
+    #[structopt(long = "timing")]
+    timing: bool,
+
     ///Force a download of manifest regardless of whether it has been updated.
     #[structopt(short = "F", long = "force", conflicts_with = "check")]
     force: bool,
@@ -166,12 +234,37 @@ struct Opt {
         default_value = "default"
     )]
     output: Output,
+
+    /// Only write structured data to stdout
+    ///
+    /// All other messages (i.e. manifest download progress and errors) are
+    /// written to stderr. Uses tsv output.
+    #[structopt(long = "porcelain")]
+    porcelain: bool,
 }
 #[tokio::main]
 async fn main() {
-    let opt = Opt::from_args();
+    let mut opt = Opt::from_args();
     print_verbose(&format!("{:#?}", opt), opt.verbose);
 
+    let _timing = timing::report(opt.timing);
+
+    let supported_output = [Output::Default, Output::Tsv];
+    if let Err(e) = opt.output.check_supported(&supported_output) {
+        print_error("Invalid output format.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    if opt.porcelain {
+        opt.output = match opt.output.porcelain(&supported_output) {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Invalid output format.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+    }
+
     let data_dir = match determine_data_dir(opt.data_dir) {
         Ok(e) => e,
         Err(e) => {
@@ -180,16 +273,33 @@ async fn main() {
         }
     };
 
-    let m_path = data_dir.join(MANIFEST_FILE_NAME);
-    let m_info_path = data_dir.join(MANIFEST_INFO_FILE_NAME);
+    if opt.print_paths {
+        datadir::print_paths(&data_dir);
+        return;
+    }
 
-    let remote_manifest_info = match retrieve_manifest_info(opt.verbose).await {
-        Ok(e) => e,
-        Err(e) => {
-            print_error("Could not retrieve manifest info from Bungie.", e);
+    let manifest_dir = datadir::named_manifest_dir(
+        &data_dir,
+        &opt.language.manifest_name(&opt.manifest_name),
+    );
+    if !manifest_dir.exists() {
+        if let Err(e) = fs::create_dir_all(&manifest_dir) {
+            print_error("Error creating manifest directory.", Error::from(e));
             std::process::exit(EXIT_FAILURE);
         }
-    };
+    }
+
+    let m_path = manifest_dir.join(MANIFEST_FILE_NAME);
+    let m_info_path = manifest_dir.join(MANIFEST_INFO_FILE_NAME);
+
+    let remote_manifest_info =
+        match retrieve_manifest_info(&opt.language, opt.verbose).await {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Could not retrieve manifest info from Bungie.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
 
     let col_w = 30;
     if opt.output == Output::Default {
@@ -252,10 +362,8 @@ async fn main() {
 
     if opt.check {
         match opt.output {
-            Output::Default => {
-                if !manifest_needs_updating {
-                    println!("No new manifest avaliable.");
-                }
+            Output::Default if !manifest_needs_updating => {
+                println!("No new manifest avaliable.");
             }
             Output::Tsv => {
                 let mut name_values: Vec<(&str, String)> = Vec::new();
@@ -269,6 +377,8 @@ async fn main() {
 
                 print!("{}", build_tsv(name_values));
             }
+            //other formats are rejected at startup
+            _ => {}
         }
         return;
     }
@@ -276,8 +386,16 @@ async fn main() {
     if opt.force || manifest_needs_updating {
         //print to stderr so user can redirect other output (such as tsv) to stdout
         eprintln!("Downloading manifest. This may take a bit of time.");
-        match download_manifest(&remote_manifest_info.url, &m_path, opt.verbose)
-            .await
+
+        //download to a temp file first, so we dont lose the existing manifest
+        //if the download fails
+        let download_path = m_path.with_extension("download");
+        match download_manifest(
+            &remote_manifest_info.url,
+            &download_path,
+            opt.verbose,
+        )
+        .await
         {
             Ok(e) => e,
             Err(e) => {
@@ -286,6 +404,23 @@ async fn main() {
             }
         };
 
+        if opt.manifest_name == datadir::LIVE_MANIFEST_NAME {
+            if let Err(e) = keep_previous_manifest(
+                &data_dir,
+                &opt.language,
+                &m_path,
+                &m_info_path,
+            ) {
+                print_error("Could not save previous manifest.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        }
+
+        if let Err(e) = fs::rename(&download_path, &m_path) {
+            print_error("Could not save manifest", Error::from(e));
+            std::process::exit(EXIT_FAILURE);
+        }
+
         print_verbose("Download and save complete.", opt.verbose);
         print_verbose("Saving manifest info.", opt.verbose);
 
@@ -304,6 +439,8 @@ async fn main() {
         println!("No new manifest available");
     }
 
+    let _format_timer = timing::start(TimingCategory::Formatting);
+
     match opt.output {
         Output::Default => {
             println!("{}", m_path.display());
@@ -318,5 +455,7 @@ async fn main() {
 
             print!("{}", build_tsv(name_values));
         }
+        //other formats are rejected at startup
+        _ => {}
     }
 }
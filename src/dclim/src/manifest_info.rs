@@ -20,6 +20,7 @@
 * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 */
 
+use dcli::enums::language::Language;
 use dcli::error::Error;
 use dcli::response::manifest::ManifestData;
 use serde_derive::{Deserialize, Serialize};
@@ -31,11 +32,26 @@ pub struct ManifestInfo {
 }
 
 impl ManifestInfo {
-    pub fn from_manifest(manifest: &ManifestData) -> ManifestInfo {
-        ManifestInfo {
+    pub fn from_manifest(
+        manifest: &ManifestData,
+        language: &Language,
+    ) -> Result<ManifestInfo, Error> {
+        let url = match manifest.mobile_world_content_paths.url(language) {
+            Some(e) => e,
+            None => {
+                return Err(Error::ApiParse {
+                    description: format!(
+                        "No manifest url returned for language : {}",
+                        language
+                    ),
+                })
+            }
+        };
+
+        Ok(ManifestInfo {
             version: String::from(&manifest.version),
-            url: String::from(&manifest.mobile_world_content_paths.en),
-        }
+            url,
+        })
     }
 
     pub fn from_json(json: &str) -> Result<ManifestInfo, Error> {
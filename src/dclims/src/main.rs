@@ -22,21 +22,28 @@
 
 use std::path::PathBuf;
 
+use dcli::datadir;
 use dcli::error::Error;
+use dcli::manifest::definitions::DisplayPropertiesData;
 use dcli::manifestinterface::{FindResult, ManifestInterface};
 use dcli::output::Output;
+use dcli::timing::{self, TimingCategory};
 use dcli::utils::{
     determine_data_dir, print_error, print_verbose, EXIT_FAILURE, TSV_DELIM,
     TSV_EOL,
 };
+use regex::Regex;
+use serde_derive::Serialize;
 use structopt::StructOpt;
 
 #[derive(StructOpt, Debug)]
 #[structopt(verbatim_doc_comment)]
-/// Command line tool for searching the Destiny 2 manifest by hash ids.
+/// Command line tool for searching the Destiny 2 manifest by hash ids or names.
 ///
 /// Takes a hash / id from the Destiny 2 API, and returns data from the
-/// item from the manifest. May return more than one result.
+/// item from the manifest. May return more than one result. Can also search
+/// by name, returning the closest matches along with their hashes, or search
+/// the manifest json with a regular expression.
 ///
 /// Created by Mike Chambers.
 /// https://www.mikechambers.com
@@ -57,18 +64,76 @@ struct Opt {
     #[structopt(short = "D", long = "data-dir", parse(from_os_str))]
     data_dir: Option<PathBuf>,
 
+    /// Print the paths of the files and directories used by dcli and exit
+    ///
+    /// Includes the data directory, manifest, activity store and the cache,
+    /// config and backup directories.
+    #[structopt(long = "print-paths")]
+    print_paths: bool,
+
+    /// Name of the manifest to use (optional)
+    ///
+    /// Defaults to the current (live) manifest. dclim keeps the previously
+    /// downloaded manifest under the name previous, and can download manifests
+    /// into other named slots with its --manifest-name flag.
+    #[structopt(
+        long = "manifest-name",
+        default_value = "live",
+        parse(try_from_str = datadir::parse_manifest_name)
+    )]
+    manifest_name: String,
+
     ///The hash id from the Destiny 2 API for the item to be searched for.
     ///
     ///Example : 326060471
-    #[structopt(long = "hash", short = "h", required = true)]
-    hash: u32,
+    #[structopt(
+        long = "hash",
+        short = "h",
+        required_unless_one = &["name", "regex"],
+        conflicts_with_all = &["name", "regex"]
+    )]
+    hash: Option<u32>,
+
+    /// Name of the item to be searched for
+    ///
+    /// Searches the names of all definitions in the manifest, and returns the
+    /// closest matches (best match first) along with their hashes. Case and
+    /// punctuation are ignored, and partial names and names with typos are
+    /// matched, i.e. "felwinters" finds Felwinter's Lie.
+    #[structopt(long = "name", short = "n", conflicts_with = "regex")]
+    name: Option<String>,
+
+    /// Regular expression to search the manifest json for
+    ///
+    /// Returns every definition whose json (as stored in the manifest)
+    /// matches the regular expression, i.e. to find all of the definitions
+    /// which reference a hash or contain a string. Searches all tables with
+    /// an id column, unless tables are specified with --table.
+    ///
+    /// Example : --regex "\b3260604718\b"
+    #[structopt(long = "regex", short = "r", parse(try_from_str = Regex::new))]
+    regex: Option<Regex>,
+
+    /// Manifest table to search with --regex
+    ///
+    /// Can be specified multiple times to search more than one table, i.e.
+    /// --table DestinyInventoryItemDefinition --table DestinyRecordDefinition
+    #[structopt(long = "table", short = "t", requires = "regex")]
+    tables: Vec<String>,
+
+    /// The maximum number of results to return when searching by --name
+    #[structopt(long = "result-count", short = "c", default_value = "10")]
+    result_count: usize,
 
     /// Format for command output
     ///
-    /// Valid values are default (Default) and tsv.
+    /// Valid values are default (Default), tsv and json.
     ///
     /// tsv outputs in a tab (\t) seperated format of columns with lines
     /// ending in a new line character (\n).
+    ///
+    /// json outputs an array of results, including the table each result was
+    /// found in and the complete manifest data for the result.
     #[structopt(
         short = "O",
         long = "output-format",
@@ -76,29 +141,106 @@ struct Opt {
     )]
     output: Output,
 
+    /// Only write structured data to stdout
+    ///
+    /// All other messages (i.e. errors) are written to stderr. Uses tsv output
+    /// unless --output-format is set to json.
+    #[structopt(long = "porcelain")]
+    porcelain: bool,
+
+    /// Print the raw manifest json for each result
+    ///
+    /// Prints the json stored in the manifest for each result, one result per
+    /// line. Useful for inspecting fields which are not displayed. Overrides
+    /// --output-format.
+    #[structopt(long = "raw")]
+    raw: bool,
+
+    /// Pretty print json output
+    ///
+    /// Applies to json output and --raw.
+    #[structopt(long = "pretty")]
+    pretty: bool,
+
     ///Print out additional information
     ///
     ///Output is printed to stderr.
     #[structopt(short = "v", long = "verbose")]
     verbose: bool,
+
+    /// Print how long was spent in API calls, SQL queries and formatting
+    ///
+    /// Timings are printed to stderr when the command finishes, and can help
+    /// diagnose slow commands.
+    #[structopt(long = "timing")]
+    timing: bool,
 }
 
 //TODO: can we make has and path reference?
 async fn search_manifest_by_hash(
     hash: u32,
-    manifest_dir: PathBuf,
+    data_dir: PathBuf,
+    manifest_name: &str,
 ) -> Result<Vec<FindResult>, Error> {
-    let mut manifest = ManifestInterface::new(&manifest_dir, false).await?;
+    let mut manifest =
+        ManifestInterface::new_with_name(&data_dir, manifest_name, false)
+            .await?;
     let out = manifest.find(hash).await?;
 
     Ok(out)
 }
 
+async fn search_manifest_by_name(
+    name: &str,
+    count: usize,
+    data_dir: PathBuf,
+    manifest_name: &str,
+) -> Result<Vec<FindResult>, Error> {
+    let mut manifest =
+        ManifestInterface::new_with_name(&data_dir, manifest_name, false)
+            .await?;
+    let out = manifest.find_by_name(name, count).await?;
+
+    Ok(out)
+}
+
+async fn search_manifest_by_regex(
+    regex: &Regex,
+    tables: &[String],
+    data_dir: PathBuf,
+    manifest_name: &str,
+) -> Result<Vec<FindResult>, Error> {
+    let mut manifest =
+        ManifestInterface::new_with_name(&data_dir, manifest_name, false)
+            .await?;
+    let out = manifest.find_by_regex(regex, tables).await?;
+
+    Ok(out)
+}
+
 #[tokio::main]
 async fn main() {
-    let opt = Opt::from_args();
+    let mut opt = Opt::from_args();
     print_verbose(&format!("{:#?}", opt), opt.verbose);
 
+    let _timing = timing::report(opt.timing);
+
+    let supported_output = [Output::Default, Output::Tsv, Output::Json];
+    if let Err(e) = opt.output.check_supported(&supported_output) {
+        print_error("Invalid output format.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    if opt.porcelain {
+        opt.output = match opt.output.porcelain(&supported_output) {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Invalid output format.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+    }
+
     let data_dir = match determine_data_dir(opt.data_dir) {
         Ok(e) => e,
         Err(e) => {
@@ -107,14 +249,51 @@ async fn main() {
         }
     };
 
-    let results: Vec<FindResult> =
-        match search_manifest_by_hash(opt.hash, data_dir).await {
-            Ok(e) => e,
-            Err(e) => {
-                print_error("Error searching manifest.", e);
-                std::process::exit(EXIT_FAILURE);
-            }
-        };
+    if opt.print_paths {
+        datadir::print_paths(&data_dir);
+        return;
+    }
+
+    //structopt ensures that one of hash, name or regex is set
+    let results = match (opt.hash, &opt.name, &opt.regex) {
+        (Some(hash), _, _) => {
+            search_manifest_by_hash(hash, data_dir, &opt.manifest_name).await
+        }
+        (None, Some(name), _) => {
+            search_manifest_by_name(
+                name,
+                opt.result_count,
+                data_dir,
+                &opt.manifest_name,
+            )
+            .await
+        }
+        (None, None, Some(regex)) => {
+            search_manifest_by_regex(
+                regex,
+                &opt.tables,
+                data_dir,
+                &opt.manifest_name,
+            )
+            .await
+        }
+        (None, None, None) => Ok(Vec::new()),
+    };
+
+    let results: Vec<FindResult> = match results {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Error searching manifest.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    let _format_timer = timing::start(TimingCategory::Formatting);
+
+    if opt.raw {
+        print_raw(results, opt.pretty);
+        return;
+    }
 
     match opt.output {
         Output::Default => {
@@ -123,9 +302,90 @@ async fn main() {
         Output::Tsv => {
             print_tsv(results);
         }
+        Output::Json => {
+            print_json(results, opt.pretty);
+        }
+        //other formats are rejected at startup
+        _ => {}
     };
 }
 
+#[derive(Serialize, Debug)]
+struct FindResultJson {
+    table: String,
+    hash: u32,
+
+    //only included when searching by name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<f32>,
+
+    display_properties: DisplayPropertiesData,
+
+    //complete json for the item from the manifest
+    data: serde_json::Value,
+}
+
+fn to_json_string<T: serde::Serialize>(
+    value: &T,
+    pretty: bool,
+) -> Result<String, serde_json::Error> {
+    if pretty {
+        serde_json::to_string_pretty(value)
+    } else {
+        serde_json::to_string(value)
+    }
+}
+
+fn print_json(results: Vec<FindResult>, pretty: bool) {
+    let mut out: Vec<FindResultJson> = Vec::new();
+    for r in results {
+        let data: serde_json::Value = match serde_json::from_str(&r.raw_json) {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Error parsing manifest json.", Error::from(e));
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+
+        out.push(FindResultJson {
+            table: r.table,
+            hash: r.hash,
+            score: r.score,
+            display_properties: r.display_properties,
+            data,
+        });
+    }
+
+    match to_json_string(&out, pretty) {
+        Ok(e) => println!("{}", e),
+        Err(e) => {
+            print_error("Error serializing results to json.", Error::from(e));
+            std::process::exit(EXIT_FAILURE);
+        }
+    }
+}
+
+fn print_raw(results: Vec<FindResult>, pretty: bool) {
+    for r in results {
+        if !pretty {
+            println!("{}", r.raw_json);
+            continue;
+        }
+
+        //round trip through a value so we can pretty print it
+        let out = serde_json::from_str::<serde_json::Value>(&r.raw_json)
+            .and_then(|v| serde_json::to_string_pretty(&v));
+
+        match out {
+            Ok(e) => println!("{}", e),
+            Err(e) => {
+                print_error("Error parsing manifest json.", Error::from(e));
+                std::process::exit(EXIT_FAILURE);
+            }
+        }
+    }
+}
+
 fn print_default(results: Vec<FindResult>) {
     if results.is_empty() {
         println!("No items found.");
@@ -164,6 +424,8 @@ fn print_default(results: Vec<FindResult>) {
             col_w = col_w
         );
         println!("{:<0col_w$}{}", "Icon Path", icon_path, col_w = col_w);
+        println!("{:<0col_w$}{}", "Hash", r.hash, col_w = col_w);
+        println!("{:<0col_w$}{}", "Table", r.table, col_w = col_w);
         println!();
     }
 }
@@ -185,12 +447,14 @@ fn print_tsv(results: Vec<FindResult>) {
             r.display_properties.icon_path.as_ref().unwrap_or(&default);
 
         print!(
-            "{i}{delim}{n}{delim}{d}{delim}{hi}{delim}{ip}{eol}",
+            "{i}{delim}{n}{delim}{d}{delim}{hi}{delim}{ip}{delim}{h}{delim}{t}{eol}",
             i = i,
             n = r.display_properties.name,
             d = description,
             hi = r.display_properties.has_icon,
             ip = icon_path,
+            h = r.hash,
+            t = r.table,
             delim = TSV_DELIM,
             eol = TSV_EOL,
         );
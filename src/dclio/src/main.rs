@@ -0,0 +1,630 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use dcli::activitystoreinterface::ActivityStoreInterface;
+use dcli::apiinterface::ApiInterface;
+use dcli::crucible::{
+    AggregateCruciblePerformances, CruciblePlayerPerformance,
+};
+use dcli::datadir;
+use dcli::enums::character::CharacterClassSelection;
+use dcli::enums::language::Language;
+use dcli::enums::mode::Mode;
+use dcli::enums::moment::{DateTimePeriod, Moment};
+use dcli::enums::platform::Platform;
+use dcli::enums::standing::Standing;
+use dcli::manifestinterface::ManifestInterface;
+use dcli::output::Output;
+use dcli::timing::{self, TimingCategory};
+use dcli::utils::{
+    build_tsv, determine_data_dir, display_width, format_f32, print_error,
+    print_verbose, truncate_string, EXIT_FAILURE,
+};
+use structopt::StructOpt;
+
+const ORBIT_PLACE_HASH: u32 = 2961497387;
+
+//shortest output we will truncate to (truncation adds "...")
+const MIN_MAX_LENGTH: usize = 10;
+
+//segments which can be displayed in the statusline output
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum Segment {
+    Activity,
+    Session,
+    Kd,
+}
+
+impl FromStr for Segment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.trim().to_lowercase()[..] {
+            "activity" => Ok(Segment::Activity),
+            "session" => Ok(Segment::Session),
+            "kd" => Ok(Segment::Kd),
+            _ => Err(format!("Unknown segment : {}", s)),
+        }
+    }
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(verbatim_doc_comment)]
+/// Command line tool for displaying a compact, one line overview of a
+/// player's current Destiny 2 activity, last Crucible match and Crucible
+/// stats since the daily reset.
+///
+/// Intended for use in status bars such as polybar or i3status. Activity
+/// data is read from the activity store and is not synced. Use dclias to
+/// sync activities.
+///
+/// Created by Mike Chambers.
+/// https://www.mikechambers.com
+///
+/// Get support,request features or just chat on the dcli Discord server:
+/// https://discord.gg/2Y8bV2Mq3p
+///
+/// Get the latest version, download the source and log issues at:
+/// https://github.com/mikechambers/dcli
+///
+/// Released under an MIT License.
+struct Opt {
+    /// Platform for specified id
+    ///
+    /// Valid values are: xbox, playstation, stadia or steam.
+    #[structopt(short = "p", long = "platform", required = true)]
+    platform: Platform,
+
+    /// Destiny 2 API member id
+    ///
+    /// This is not the user name, but the member id retrieved from the Destiny API.
+    #[structopt(short = "m", long = "member-id", required = true)]
+    member_id: String,
+
+    /// Maximum width of output, in terminal columns
+    ///
+    /// Default and statusline output is truncated to this length. Set to 0
+    /// to not truncate the output. Ignored for tsv output.
+    #[structopt(long = "max-length", default_value = "80")]
+    max_length: usize,
+
+    /// Segments to display for statusline output
+    ///
+    /// Comma seperated list of segments, displayed in the order specified.
+    /// Valid values are activity, session (wins - losses since the daily
+    /// reset) and kd (K/D since the daily reset).
+    #[structopt(
+        long = "segments",
+        use_delimiter = true,
+        default_value = "activity,session,kd"
+    )]
+    segments: Vec<Segment>,
+
+    /// Prefix statusline segments with emoji icons
+    #[structopt(long = "icons")]
+    icons: bool,
+
+    ///Print out additional information
+    ///
+    ///Output is printed to stderr.
+    #[structopt(short = "v", long = "verbose")]
+    verbose: bool,
+
+    /// Print how long was spent in API calls, SQL queries and formatting
+    ///
+    /// Timings are printed to stderr when the command finishes, and can help
+    /// diagnose slow commands.
+    #[structopt(long = "timing")]
+    timing: bool,
+
+    /// Directory where Destiny 2 manifest and activity database files are stored. (optional)
+    ///
+    /// This will normally be downloaded using the dclim and dclias tools, and uses
+    /// a system appropriate directory by default.
+    #[structopt(short = "D", long = "data-dir", parse(from_os_str))]
+    data_dir: Option<PathBuf>,
+
+    /// Print the paths of the files and directories used by dcli and exit
+    ///
+    /// Includes the data directory, manifest, activity store and the cache,
+    /// config and backup directories.
+    #[structopt(long = "print-paths")]
+    print_paths: bool,
+
+    /// Name of the manifest to use (optional)
+    ///
+    /// Defaults to the current (live) manifest. dclim keeps the previously
+    /// downloaded manifest under the name previous, and can download manifests
+    /// into other named slots with its --manifest-name flag.
+    #[structopt(
+        long = "manifest-name",
+        default_value = "live",
+        parse(try_from_str = datadir::parse_manifest_name)
+    )]
+    manifest_name: String,
+
+    /// Language to display weapon, medal and map names in (optional)
+    ///
+    /// Defaults to en. Names are looked up in the manifest for the language,
+    /// which must first be downloaded with dclim --language. Stored activity
+    /// data does not need to be synced again.
+    ///
+    /// Valid values are en, fr, es, es-mx, de, it, ja, pt-br, ru, pl, ko,
+    /// zh-cht and zh-chs.
+    #[structopt(long = "language", default_value = "en")]
+    language: Language,
+
+    /// Format for command output
+    ///
+    /// Valid values are default (Default), tsv and statusline.
+    ///
+    /// tsv outputs in a tab (\t) seperated format of name / value pairs with lines
+    /// ending in a new line character (\n).
+    ///
+    /// statusline outputs a single short line containing the segments specified
+    /// by --segments, for use in tmux / polybar.
+    #[structopt(
+        short = "O",
+        long = "output-format",
+        default_value = "default"
+    )]
+    output: Output,
+
+    /// Only write structured data to stdout
+    ///
+    /// All other messages (i.e. errors) are written to stderr. Uses tsv output.
+    #[structopt(long = "porcelain")]
+    porcelain: bool,
+}
+
+#[derive(Debug, Default)]
+struct LastMatch {
+    standing: Standing,
+    mode: String,
+    map_name: String,
+    kills_deaths_ratio: f32,
+}
+
+#[derive(Debug, Default)]
+struct TodaySummary {
+    activities: u32,
+    wins: u32,
+    losses: u32,
+    kills_deaths_ratio: f32,
+}
+
+//returns None if the player is not in an activity
+async fn get_current_activity(
+    member_id: &str,
+    platform: &Platform,
+    manifest: &mut ManifestInterface,
+    verbose: bool,
+) -> Option<String> {
+    let api = match ApiInterface::new(verbose) {
+        Ok(e) => e,
+        Err(e) => {
+            print_verbose(&format!("Error initializing API : {}", e), verbose);
+            return None;
+        }
+    };
+
+    let activity_data_a = match api
+        .retrieve_current_activity(member_id.to_string(), *platform)
+        .await
+    {
+        Ok(e) => e?,
+        Err(e) => {
+            print_verbose(
+                &format!("Error retrieving current activity : {}", e),
+                verbose,
+            );
+            return None;
+        }
+    };
+
+    let activity_data_m = match manifest
+        .get_activity_definition(activity_data_a.current_activity_hash)
+        .await
+    {
+        Ok(Some(e)) => e,
+        _ => return Some("Unknown activity".to_string()),
+    };
+
+    if activity_data_m.place_hash == ORBIT_PLACE_HASH {
+        return Some("Orbit".to_string());
+    }
+
+    let name = activity_data_m.display_properties.name;
+    match activity_data_a.current_activity_mode_type {
+        Some(e) if e.is_crucible() => Some(format!("{} on {}", e, name)),
+        _ => Some(name),
+    }
+}
+
+async fn get_last_match(
+    store: &mut ActivityStoreInterface,
+    manifest: &mut ManifestInterface,
+    member_id: &str,
+    platform: &Platform,
+    verbose: bool,
+) -> Option<LastMatch> {
+    let activity = match store
+        .retrieve_last_activity(
+            member_id,
+            platform,
+            &CharacterClassSelection::All,
+            &Mode::AllPvP,
+            manifest,
+        )
+        .await
+    {
+        Ok(e) => e,
+        Err(e) => {
+            print_verbose(
+                &format!("Error retrieving last activity : {}", e),
+                verbose,
+            );
+            return None;
+        }
+    };
+
+    let performance = activity.get_member_performance(member_id)?;
+
+    Some(LastMatch {
+        standing: performance.stats.standing,
+        mode: format!("{}", activity.details.mode),
+        map_name: activity.details.map_name.to_string(),
+        kills_deaths_ratio: performance.stats.kills_deaths_ratio,
+    })
+}
+
+async fn get_today_summary(
+    store: &mut ActivityStoreInterface,
+    manifest: &mut ManifestInterface,
+    member_id: &str,
+    platform: &Platform,
+    verbose: bool,
+) -> Option<TodaySummary> {
+    let time_period =
+        DateTimePeriod::with_start_time(Moment::Daily.get_date_time()).ok()?;
+
+    let data = match store
+        .retrieve_activities_since(
+            member_id,
+            &CharacterClassSelection::All,
+            platform,
+            &Mode::AllPvP,
+            &time_period,
+            manifest,
+        )
+        .await
+    {
+        Ok(e) => e,
+        Err(e) => {
+            print_verbose(
+                &format!("Error retrieving activities : {}", e),
+                verbose,
+            );
+            return None;
+        }
+    };
+
+    let data = match data {
+        Some(e) => e,
+        None => return Some(TodaySummary::default()),
+    };
+
+    let cpp: Vec<&CruciblePlayerPerformance> =
+        data.iter().map(|x| &x.performance).collect();
+    let aggregate = AggregateCruciblePerformances::with_performances(&cpp);
+
+    Some(TodaySummary {
+        activities: aggregate.total_activities,
+        wins: aggregate.wins,
+        losses: aggregate.losses,
+        kills_deaths_ratio: aggregate.kills_deaths_ratio,
+    })
+}
+
+async fn open_store(
+    data_dir: &Path,
+    verbose: bool,
+) -> Option<ActivityStoreInterface> {
+    match ActivityStoreInterface::init_read_only_with_path(data_dir, verbose)
+        .await
+    {
+        Ok(e) => Some(e),
+        Err(e) => {
+            print_verbose(
+                &format!("Could not initialize activity store : {}", e),
+                verbose,
+            );
+            None
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let mut opt = Opt::from_args();
+    print_verbose(&format!("{:#?}", opt), opt.verbose);
+
+    let _timing = timing::report(opt.timing);
+
+    let supported_output = [Output::Default, Output::Tsv, Output::Statusline];
+    if let Err(e) = opt.output.check_supported(&supported_output) {
+        print_error("Invalid output format.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    if opt.porcelain {
+        opt.output = match opt.output.porcelain(&supported_output) {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Invalid output format.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+    }
+
+    let data_dir = match determine_data_dir(opt.data_dir) {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Error initializing storage directory store.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    if opt.print_paths {
+        datadir::print_paths(&data_dir);
+        return;
+    }
+
+    let mut manifest = match ManifestInterface::new_with_name(
+        &data_dir,
+        &opt.language.manifest_name(&opt.manifest_name),
+        false,
+    )
+    .await
+    {
+        Ok(e) => e,
+        Err(e) => {
+            print_error(
+                "Could not initialize manifest. Have you run dclim?",
+                e,
+            );
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    //each section is optional, so we still output what we can if part of the
+    //data can't be retrieved (i.e. api is down, or store hasnt been synced)
+    let current = get_current_activity(
+        &opt.member_id,
+        &opt.platform,
+        &mut manifest,
+        opt.verbose,
+    )
+    .await;
+
+    let mut last_match = None;
+    let mut today = None;
+    if let Some(mut store) = open_store(&data_dir, opt.verbose).await {
+        last_match = get_last_match(
+            &mut store,
+            &mut manifest,
+            &opt.member_id,
+            &opt.platform,
+            opt.verbose,
+        )
+        .await;
+
+        today = get_today_summary(
+            &mut store,
+            &mut manifest,
+            &opt.member_id,
+            &opt.platform,
+            opt.verbose,
+        )
+        .await;
+
+        if let Err(e) = store.close().await {
+            print_error("Error closing activity store.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    }
+
+    let _format_timer = timing::start(TimingCategory::Formatting);
+
+    match opt.output {
+        Output::Default => {
+            print_default(&current, &last_match, &today, opt.max_length);
+        }
+        Output::Tsv => {
+            print_tsv(&current, &last_match, &today);
+        }
+        Output::Statusline => {
+            print_statusline(
+                &current,
+                &today,
+                &opt.segments,
+                opt.icons,
+                opt.max_length,
+            );
+        }
+        //other formats are rejected at startup
+        _ => {}
+    };
+}
+
+fn print_default(
+    current: &Option<String>,
+    last_match: &Option<LastMatch>,
+    today: &Option<TodaySummary>,
+    max_length: usize,
+) {
+    let mut segments: Vec<String> = Vec::new();
+
+    segments.push(match current {
+        Some(e) => e.to_string(),
+        None => "Not in activity".to_string(),
+    });
+
+    if let Some(e) = last_match {
+        segments.push(format!(
+            "Last: {} {} K/D ({})",
+            e.standing,
+            format_f32(e.kills_deaths_ratio, 2),
+            e.map_name
+        ));
+    }
+
+    if let Some(e) = today {
+        if e.activities > 0 {
+            segments.push(format!(
+                "Today: {}W {}L {} K/D",
+                e.wins,
+                e.losses,
+                format_f32(e.kills_deaths_ratio, 2)
+            ));
+        }
+    }
+
+    let out = segments.join(" | ");
+
+    let out = if max_length == 0 {
+        out
+    } else {
+        truncate_string(&out, std::cmp::max(max_length, MIN_MAX_LENGTH))
+    };
+
+    println!("{}", out);
+}
+
+fn print_statusline(
+    current: &Option<String>,
+    today: &Option<TodaySummary>,
+    segments: &[Segment],
+    icons: bool,
+    max_length: usize,
+) {
+    let mut out: Vec<String> = Vec::new();
+
+    for s in segments {
+        let (icon, value) = match s {
+            Segment::Activity => match current {
+                Some(e) => ("\u{1F3AE}", e.to_string()),
+                None => continue,
+            },
+            Segment::Session => match today {
+                Some(e) if e.activities > 0 => {
+                    ("\u{1F3C6}", format!("{}-{}", e.wins, e.losses))
+                }
+                _ => continue,
+            },
+            Segment::Kd => match today {
+                Some(e) if e.activities > 0 => (
+                    "\u{1F3AF}",
+                    format!("{} K/D", format_f32(e.kills_deaths_ratio, 2)),
+                ),
+                _ => continue,
+            },
+        };
+
+        let segment = if icons {
+            format!("{} {}", icon, value)
+        } else {
+            value
+        };
+
+        out.push(segment);
+    }
+
+    println!("{}", cap_statusline(&out, max_length));
+}
+
+//joins segments, dropping trailing segments that don't fit in max_length. If
+//the first segment doesnt fit, it is truncated. Length is measured in
+//terminal columns.
+fn cap_statusline(segments: &[String], max_length: usize) -> String {
+    const SEPARATOR: &str = " | ";
+
+    if max_length == 0 {
+        return segments.join(SEPARATOR);
+    }
+
+    let max_length = std::cmp::max(max_length, MIN_MAX_LENGTH);
+
+    let mut out = String::new();
+    for s in segments {
+        let candidate = if out.is_empty() {
+            s.to_string()
+        } else {
+            format!("{}{}{}", out, SEPARATOR, s)
+        };
+
+        if display_width(&candidate) > max_length {
+            if out.is_empty() {
+                out = truncate_string(s, max_length);
+            }
+            break;
+        }
+
+        out = candidate;
+    }
+
+    out
+}
+
+fn print_tsv(
+    current: &Option<String>,
+    last_match: &Option<LastMatch>,
+    today: &Option<TodaySummary>,
+) {
+    let default_last = LastMatch::default();
+    let last = last_match.as_ref().unwrap_or(&default_last);
+
+    let default_today = TodaySummary::default();
+    let t = today.as_ref().unwrap_or(&default_today);
+
+    let name_values: Vec<(&str, String)> = vec![
+        ("in_activity", current.is_some().to_string()),
+        ("current_activity", current.clone().unwrap_or_default()),
+        ("has_last_match", last_match.is_some().to_string()),
+        ("last_match_standing", format!("{}", last.standing)),
+        ("last_match_mode", last.mode.to_string()),
+        ("last_match_map_name", last.map_name.to_string()),
+        (
+            "last_match_kills_deaths_ratio",
+            last.kills_deaths_ratio.to_string(),
+        ),
+        ("today_activities", t.activities.to_string()),
+        ("today_wins", t.wins.to_string()),
+        ("today_losses", t.losses.to_string()),
+        ("today_kills_deaths_ratio", t.kills_deaths_ratio.to_string()),
+    ];
+
+    print!("{}", build_tsv(name_values));
+}
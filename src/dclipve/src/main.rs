@@ -0,0 +1,510 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use dcli::activitystoreinterface::ActivityStoreInterface;
+use dcli::datadir;
+use dcli::enums::character::CharacterClassSelection;
+use dcli::enums::language::Language;
+use dcli::enums::mode::Mode;
+use dcli::enums::moment::{DateTimePeriod, Moment};
+use dcli::enums::platform::Platform;
+use dcli::manifestinterface::ManifestInterface;
+use dcli::output::Output;
+use dcli::pve::PveActivityStats;
+use dcli::timing;
+use dcli::utils::{
+    calculate_avg, determine_data_dir, format_f32, human_duration, print_error,
+    print_message, print_verbose, repeat_str, truncate_string, EXIT_FAILURE,
+    TSV_DELIM, TSV_EOL,
+};
+use structopt::StructOpt;
+
+fn parse_and_validate_mode(src: &str) -> Result<Mode, String> {
+    let mode = Mode::from_str(src)?;
+
+    if !mode.is_pve() {
+        return Err(format!("Unsupported mode specified : {}", src));
+    }
+
+    Ok(mode)
+}
+
+fn parse_and_validate_moment(src: &str) -> Result<Moment, String> {
+    let moment = Moment::from_str(src)?;
+
+    Ok(moment)
+}
+
+fn parse_rfc3339(src: &str) -> Result<DateTime<Utc>, String> {
+    let d =
+        match DateTime::parse_from_rfc3339(src) {
+            Ok(e) => e,
+            Err(_e) => return Err(
+                "Invalid RFC 3339 Date / Time String : Example : 2020-12-08T17:00:00.774187+00:00"
+                    .to_string(),
+            ),
+        };
+
+    let d = d.with_timezone(&Utc);
+
+    if d > Utc::now() {
+        return Err("start-date must be in the past.".to_string());
+    }
+
+    Ok(d)
+}
+
+//clear times are compared against each other, so we use a fixed format
+//(h:mm:ss) rather than human_duration
+fn format_clear_time(seconds: u32) -> String {
+    let h = seconds / 3600;
+    let m = (seconds % 3600) / 60;
+    let s = seconds % 60;
+
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
+
+fn print_default(
+    stats: &[PveActivityStats],
+    mode: &Mode,
+    mode_names: &HashMap<u32, String>,
+) {
+    let name_col_w = 40;
+    let col_w = 10;
+    let fastest_col_w = 10;
+    let fireteam_col_w = 14;
+
+    println!();
+    let title = mode.display_name(mode_names).to_uppercase();
+    println!("{}", title);
+    println!("{}", repeat_str("=", title.chars().count()));
+
+    let header = format!(
+        "{:<name_col_w$}{:>col_w$}{:>col_w$}{:>fastest_col_w$}{:>col_w$}{:>col_w$}{:>fireteam_col_w$}",
+        "ACTIVITY",
+        "RUNS",
+        "CLEARS",
+        "FASTEST",
+        "DEATHS",
+        "D/RUN",
+        "AVG FIRETEAM",
+        name_col_w = name_col_w,
+        col_w = col_w,
+        fastest_col_w = fastest_col_w,
+        fireteam_col_w = fireteam_col_w,
+    );
+
+    println!("{}", header);
+    println!("{}", repeat_str("-", header.chars().count()));
+
+    let mut activities = 0;
+    let mut clears = 0;
+    let mut deaths = 0;
+    let mut fireteam_members = 0;
+    let mut time_played_seconds = 0;
+
+    for s in stats {
+        activities += s.activities;
+        clears += s.clears;
+        deaths += s.deaths;
+        fireteam_members += s.fireteam_members;
+        time_played_seconds += s.time_played_seconds;
+
+        let fastest = match s.fastest_clear_seconds {
+            Some(e) => format_clear_time(e),
+            None => "-".to_string(),
+        };
+
+        println!(
+            "{:<name_col_w$}{:>col_w$}{:>col_w$}{:>fastest_col_w$}{:>col_w$}{:>col_w$}{:>fireteam_col_w$}",
+            truncate_string(&s.activity_name, name_col_w - 2),
+            s.activities,
+            s.clears,
+            fastest,
+            s.deaths,
+            format_f32(s.deaths_per_activity(), 2),
+            format_f32(s.average_fireteam_size(), 1),
+            name_col_w = name_col_w,
+            col_w = col_w,
+            fastest_col_w = fastest_col_w,
+            fireteam_col_w = fireteam_col_w,
+        );
+    }
+
+    println!("{}", repeat_str("-", header.chars().count()));
+    println!(
+        "{:<name_col_w$}{:>col_w$}{:>col_w$}{:>fastest_col_w$}{:>col_w$}{:>col_w$}{:>fireteam_col_w$}",
+        "TOTAL",
+        activities,
+        clears,
+        "",
+        deaths,
+        format_f32(calculate_avg(deaths as f32, activities), 2),
+        format_f32(calculate_avg(fireteam_members as f32, activities), 1),
+        name_col_w = name_col_w,
+        col_w = col_w,
+        fastest_col_w = fastest_col_w,
+        fireteam_col_w = fireteam_col_w,
+    );
+
+    println!();
+    println!("Time played : {}", human_duration(time_played_seconds));
+    println!("Clears are activities completed with the objective complete.");
+}
+
+fn print_tsv(stats: &[PveActivityStats]) {
+    for s in stats {
+        let fastest = match s.fastest_clear_seconds {
+            Some(e) => e.to_string(),
+            None => "".to_string(),
+        };
+
+        print!(
+            "{h}{d}{n}{d}{a}{d}{c}{d}{f}{d}{k}{d}{de}{d}{t}{d}{fm}{eol}",
+            h = s.reference_id,
+            n = s.activity_name,
+            a = s.activities,
+            c = s.clears,
+            f = fastest,
+            k = s.kills,
+            de = s.deaths,
+            t = s.time_played_seconds,
+            fm = s.fireteam_members,
+            d = TSV_DELIM,
+            eol = TSV_EOL,
+        );
+    }
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(verbatim_doc_comment)]
+/// Command line tool for viewing Destiny 2 PvE activity stats.
+///
+/// Displays runs, clears, fastest clears, deaths and fireteam size for each
+/// strike, nightfall, raid and dungeon stored in the activity store. PvE
+/// activities are synced by running dclias with the --pve flag.
+///
+/// Created by Mike Chambers.
+/// https://www.mikechambers.com
+///
+/// Get support, request features or just chat on the dcli Discord server:
+/// https://discord.gg/2Y8bV2Mq3p
+///
+/// Get the latest version, download the source and log issues at:
+/// https://github.com/mikechambers/dcli
+///
+/// Released under an MIT License.
+struct Opt {
+    /// Destiny 2 API member id
+    ///
+    /// This is not the user name, but the member id retrieved from the Destiny
+    /// API. Can also be a player alias set with dclias --set-alias, or the
+    /// display name of a player in synced activities (add the platform, i.e.
+    /// name:steam, if more than one player has the name).
+    #[structopt(short = "m", long = "member-id", required = true)]
+    member_id: String,
+
+    /// Platform for specified id
+    ///
+    /// Valid values are: xbox, playstation, stadia or steam.
+    #[structopt(short = "p", long = "platform", required = true)]
+    platform: Platform,
+
+    /// Character to retrieve data for
+    ///
+    /// Valid values include hunter, titan, warlock, last_active and all.
+    #[structopt(short = "C", long = "class", default_value = "all")]
+    character_class_selection: CharacterClassSelection,
+
+    /// Activity mode to return stats for
+    ///
+    /// Supported values are all_pve (default), all_strikes, scored_nightfall,
+    /// raid, dungeon and nightmare_hunt.
+    #[structopt(long = "mode", short = "M", 
+        parse(try_from_str=parse_and_validate_mode), default_value = "all_pve")]
+    mode: Mode,
+
+    /// Start moment from which to pull activities from
+    ///
+    /// Activities will be retrieved from moment to end-moment.
+    ///
+    /// For example, Specifying: --moment weekly
+    /// will return all activities since the last weekly reset on Tuesday.
+    ///
+    /// Valid values include daily (last daily reset), weekend
+    /// (last weekend reset on Friday), weekly (last weekly reset on Tuesday),
+    /// day (last day), week (last week), month (last month), all_time and custom
+    /// as well as the following season moments launch, curse_of_osiris, warmind,
+    /// season_of_the_outlaw, season_of_the_forge, season_of_the_drifter,
+    /// season_of_opulence, season_of_the_undying, season_of_dawn,
+    /// season_of_the_worthy, season_of_arrivals, season_of_the_hunt,
+    /// season_of_the_chosen.
+    ///
+    /// When custom is specified, the custom start date in RFC3339 format must
+    /// be specified with the --custom-time argument.
+    #[structopt(long = "moment", parse(try_from_str=parse_and_validate_moment), 
+        short = "T", default_value = "week")]
+    moment: Moment,
+
+    /// End moment from which to pull activities from
+    ///
+    /// Activities will be retrieved from moment to end-moment. End moment
+    /// must be greater than moment. Accepts the same values as --moment.
+    ///
+    /// When custom is specified, the custom end date in RFC3339 format must
+    /// be specified with the --end-custom-time argument.
+    #[structopt(long = "end-moment", parse(try_from_str=parse_and_validate_moment), 
+        short = "E", default_value = "now")]
+    end_moment: Moment,
+
+    /// Custom start time in RFC 3339 date / time format
+    ///
+    /// Must be a valid date in the past.
+    ///
+    /// Example RFC 3339 format: 2020-12-08T17:00:00.774187+00:00
+    ///
+    /// Required when --moment is set to custom, but otherwise not applicable.
+    #[structopt(short = "t", long = "custom-time", parse(try_from_str = parse_rfc3339), required_if("moment", "custom"))]
+    custom_time: Option<DateTime<Utc>>,
+
+    /// Custom end time in RFC 3339 date / time format
+    ///
+    /// Must be a valid date in the past.
+    ///
+    /// Example RFC 3339 format: 2020-12-08T17:00:00.774187+00:00
+    ///
+    /// Required when --end-moment is set to custom, but otherwise not applicable.
+    #[structopt(short = "e", long = "end-custom-time", parse(try_from_str = parse_rfc3339), required_if("end-moment", "custom"))]
+    end_custom_time: Option<DateTime<Utc>>,
+
+    /// Format for command output
+    ///
+    /// Valid values are default (Default) and tsv.
+    ///
+    /// tsv outputs a row for each activity, with the activity hash, name,
+    /// runs, clears, fastest clear (seconds), kills, deaths, time played
+    /// (seconds) and total fireteam members.
+    #[structopt(
+        short = "O",
+        long = "output-format",
+        default_value = "default"
+    )]
+    output: Output,
+
+    /// Only write structured data to stdout
+    ///
+    /// All other messages (i.e. errors, or no activities found) are written to
+    /// stderr. Uses tsv output.
+    #[structopt(long = "porcelain")]
+    porcelain: bool,
+
+    /// Directory where Destiny 2 manifest and activity database files are stored. (optional)
+    ///
+    /// This will normally be downloaded using the dclim and dclias tools, and uses
+    /// a system appropriate directory by default.
+    #[structopt(short = "D", long = "data-dir", parse(from_os_str))]
+    data_dir: Option<PathBuf>,
+
+    /// Name of the manifest to use (optional)
+    ///
+    /// Defaults to the current (live) manifest.
+    #[structopt(
+        long = "manifest-name",
+        default_value = "live",
+        parse(try_from_str = datadir::parse_manifest_name)
+    )]
+    manifest_name: String,
+
+    /// Language to display weapon, medal and map names in (optional)
+    ///
+    /// Defaults to en. Names are looked up in the manifest for the language,
+    /// which must first be downloaded with dclim --language. Stored activity
+    /// data does not need to be synced again.
+    ///
+    /// Valid values are en, fr, es, es-mx, de, it, ja, pt-br, ru, pl, ko,
+    /// zh-cht and zh-chs.
+    #[structopt(long = "language", default_value = "en")]
+    language: Language,
+
+    /// Print how long was spent in API calls, SQL queries and formatting
+    ///
+    /// Timings are printed to stderr when the command finishes.
+    #[structopt(long = "timing")]
+    timing: bool,
+
+    ///Print out additional information
+    ///
+    ///Output is printed to stderr.
+    #[structopt(short = "v", long = "verbose")]
+    verbose: bool,
+}
+
+#[tokio::main]
+async fn main() {
+    let mut opt = Opt::from_args();
+    print_verbose(&format!("{:#?}", opt), opt.verbose);
+
+    let _timing = timing::report(opt.timing);
+
+    let supported_output = [Output::Default, Output::Tsv];
+    if let Err(e) = opt.output.check_supported(&supported_output) {
+        print_error("Invalid output format.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    if opt.porcelain {
+        opt.output = match opt.output.porcelain(&supported_output) {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Invalid output format.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+    }
+
+    let data_dir = match determine_data_dir(opt.data_dir) {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Error initializing manifest directory.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    let start_time = match opt.moment {
+        Moment::Custom => {
+            opt.custom_time.unwrap() //note, this should be ok, because struct opt should ensure valid value
+        }
+        _ => opt.moment.get_date_time(),
+    };
+
+    let end_time = match opt.end_moment {
+        Moment::Custom => {
+            opt.end_custom_time.unwrap() //note, this should be ok, because struct opt should ensure valid value
+        }
+        _ => opt.end_moment.get_date_time(),
+    };
+
+    let time_period =
+        match DateTimePeriod::with_start_end_time(start_time, end_time) {
+            Ok(e) => e,
+            Err(_e) => {
+                eprintln!("--end-moment must be greater than --moment");
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+
+    let store = match ActivityStoreInterface::init_read_only_with_path(
+        &data_dir,
+        opt.verbose,
+    )
+    .await
+    {
+        Ok(e) => e,
+        Err(e) => {
+            print_error(
+                "Could not initialize activity store. Have you run dclias?",
+                e,
+            );
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    let mut manifest = match ManifestInterface::new_with_name(
+        &data_dir,
+        &opt.language.manifest_name(&opt.manifest_name),
+        false,
+    )
+    .await
+    {
+        Ok(e) => e,
+        Err(e) => {
+            print_error(
+                "Could not initialize manifest. Have you run dclim?",
+                e,
+            );
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    //names from the manifest for displaying modes in human readable output.
+    //The built in names are used if they can't be loaded.
+    let mode_names =
+        manifest.get_mode_display_names().await.unwrap_or_default();
+
+    //member id can also be a local player alias (see dclias --set-alias)
+    let member_id = match store.resolve_member_id(&opt.member_id).await {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Could not resolve member id.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    let stats = match store
+        .retrieve_pve_activity_stats(
+            &member_id,
+            &opt.character_class_selection,
+            &opt.platform,
+            &opt.mode,
+            &time_period,
+            &mut manifest,
+        )
+        .await
+    {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Could not retrieve PvE activities.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    if let Err(e) = store.close().await {
+        print_error("Error closing activity store.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    if stats.is_empty() {
+        print_message(
+            "No activities found for the specified mode and time period.",
+            opt.porcelain,
+        );
+        print_message(
+            "PvE activities are only synced when dclias is run with --pve.",
+            opt.porcelain,
+        );
+        return;
+    }
+
+    match opt.output {
+        Output::Tsv => print_tsv(&stats),
+        _ => print_default(&stats, &opt.mode, &mode_names),
+    }
+}
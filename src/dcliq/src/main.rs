@@ -0,0 +1,374 @@
+/*
+* Copyright 2021 Mike Chambers
+* https://github.com/mikechambers/dcli
+*
+* Permission is hereby granted, free of charge, to any person obtaining a copy of
+* this software and associated documentation files (the "Software"), to deal in
+* the Software without restriction, including without limitation the rights to
+* use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+* of the Software, and to permit persons to whom the Software is furnished to do
+* so, subject to the following conditions:
+*
+* The above copyright notice and this permission notice shall be included in all
+* copies or substantial portions of the Software.
+*
+* THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+* IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+* FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+* COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+* IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+* CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+use std::path::PathBuf;
+
+use dcli::activitystoreinterface::ActivityStoreInterface;
+use dcli::datadir;
+use dcli::error::Error;
+use dcli::export::CsvWriter;
+use dcli::output::Output;
+use dcli::storequery::{QueryResult, QUERY_VIEWS};
+use dcli::timing::{self, TimingCategory};
+use dcli::utils::{
+    determine_data_dir, display_width, fit_string, print_error, print_verbose,
+    repeat_str, EXIT_FAILURE, TSV_DELIM, TSV_EOL,
+};
+use structopt::StructOpt;
+
+//maximum width of a column in the default output. Longer values are
+//truncated.
+const MAX_COLUMN_WIDTH: usize = 40;
+
+//space between columns in the default output
+const COLUMN_GAP: usize = 2;
+
+#[derive(StructOpt, Debug)]
+#[structopt(verbatim_doc_comment)]
+/// Command line tool for running read only SQL queries against the dcli
+/// activity store.
+///
+/// Useful for answering one off questions about your activity history
+/// without needing a seperate sqlite client. Queries can use the store
+/// tables, as well as a set of views which join them into shapes that are
+/// easier to query. Use --list-views to see the available views.
+///
+/// Queries cannot modify the store.
+///
+/// Created by Mike Chambers.
+/// https://www.mikechambers.com
+///
+/// Get support,request features or just chat on the dcli Discord server:
+/// https://discord.gg/2Y8bV2Mq3p
+///
+/// Get the latest version, download the source and log issues at:
+/// https://github.com/mikechambers/dcli
+///
+/// Released under an MIT License.
+struct Opt {
+    /// SQL query to run against the activity store
+    #[structopt(
+        required_unless_one = &["file", "list-views", "print-paths"],
+        conflicts_with = "file"
+    )]
+    query: Option<String>,
+
+    /// Path to a file containing the SQL query to run
+    #[structopt(short = "f", long = "file", parse(from_os_str))]
+    file: Option<PathBuf>,
+
+    /// List the views which can be used in queries, along with their columns
+    #[structopt(long = "list-views")]
+    list_views: bool,
+
+    /// Format for command output
+    ///
+    /// Valid values are default (Default), tsv, csv and json.
+    ///
+    /// default outputs a table, with long values truncated to fit.
+    ///
+    /// tsv outputs in a tab (\t) seperated format, with a header row and lines
+    /// ending in a new line character (\n).
+    ///
+    /// csv outputs in a comma seperated format (RFC 4180), with a header row.
+    ///
+    /// json outputs an array of objects, one for each row, keyed by column
+    /// name.
+    #[structopt(
+        short = "O",
+        long = "output-format",
+        default_value = "default"
+    )]
+    output: Output,
+
+    /// Only write structured data to stdout
+    ///
+    /// All other messages (i.e. errors) are written to stderr. Uses tsv output
+    /// unless --output-format is set to csv or json.
+    #[structopt(long = "porcelain")]
+    porcelain: bool,
+
+    ///Print out additional information
+    ///
+    ///Output is printed to stderr.
+    #[structopt(short = "v", long = "verbose")]
+    verbose: bool,
+
+    /// Print how long was spent in API calls, SQL queries and formatting
+    ///
+    /// Timings are printed to stderr when the command finishes, and can help
+    /// diagnose slow commands.
+    #[structopt(long = "timing")]
+    timing: bool,
+
+    /// Directory where Destiny 2 manifest and activity database files are stored. (optional)
+    ///
+    /// This will normally be downloaded using the dclim and dclias tools, and uses
+    /// a system appropriate directory by default.
+    #[structopt(short = "D", long = "data-dir", parse(from_os_str))]
+    data_dir: Option<PathBuf>,
+
+    /// Print the paths of the files and directories used by dcli and exit
+    ///
+    /// Includes the data directory, manifest, activity store and the cache,
+    /// config and backup directories.
+    #[structopt(long = "print-paths")]
+    print_paths: bool,
+}
+
+//prints each view, along with its columns, which are retrieved from the store
+async fn print_views(
+    store: &ActivityStoreInterface,
+    porcelain: bool,
+) -> Result<(), Error> {
+    for v in QUERY_VIEWS {
+        let columns = store
+            .query(&format!("SELECT name FROM pragma_table_info('{}')", v.name))
+            .await?;
+
+        let columns: Vec<String> = columns
+            .rows
+            .iter()
+            .filter_map(|r| r.first().map(|c| format!("{}", c)))
+            .collect();
+
+        //one line per view : name, description and comma seperated columns
+        if porcelain {
+            println!("{}\t{}\t{}", v.name, v.description, columns.join(","));
+            continue;
+        }
+
+        println!("{}", v.name);
+        println!("{}", repeat_str("-", v.name.len()));
+        println!("{}", v.description);
+        println!();
+        println!("Columns : {}", columns.join(", "));
+        println!();
+    }
+
+    Ok(())
+}
+
+fn print_default(result: &QueryResult) {
+    if result.rows.is_empty() {
+        println!("No results found.");
+        return;
+    }
+
+    let values: Vec<Vec<String>> = result
+        .rows
+        .iter()
+        .map(|r| r.iter().map(|v| format!("{}", v)).collect())
+        .collect();
+
+    let widths: Vec<usize> = result
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let w = values
+                .iter()
+                .map(|r| display_width(&r[i]))
+                .fold(display_width(c), usize::max);
+            w.min(MAX_COLUMN_WIDTH)
+        })
+        .collect();
+
+    let gap = " ".repeat(COLUMN_GAP);
+    let format_row = |row: &[String]| -> String {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(v, w)| fit_string(v, *w))
+            .collect();
+        cells.join(&gap).trim_end().to_string()
+    };
+
+    let header: Vec<String> =
+        result.columns.iter().map(|c| c.to_uppercase()).collect();
+    let header = format_row(&header);
+    println!("{}", header);
+    println!("{}", repeat_str("=", display_width(&header)));
+
+    for r in &values {
+        println!("{}", format_row(r));
+    }
+
+    println!();
+    println!("{} rows", result.rows.len());
+}
+
+//tabs and new lines would break the tsv format
+fn escape_tsv(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+fn print_tsv(result: &QueryResult) {
+    let mut out = String::new();
+
+    let header: Vec<String> =
+        result.columns.iter().map(|c| escape_tsv(c)).collect();
+    out.push_str(&header.join(TSV_DELIM));
+    out.push_str(TSV_EOL);
+
+    for r in &result.rows {
+        let row: Vec<String> =
+            r.iter().map(|v| escape_tsv(&format!("{}", v))).collect();
+        out.push_str(&row.join(TSV_DELIM));
+        out.push_str(TSV_EOL);
+    }
+
+    print!("{}", out);
+}
+
+fn write_csv(result: &QueryResult) -> Result<(), Error> {
+    let stdout = std::io::stdout();
+    let mut writer = CsvWriter::new(std::io::BufWriter::new(stdout.lock()));
+
+    writer.write_row(&result.columns)?;
+    for r in &result.rows {
+        let row: Vec<String> = r.iter().map(|v| format!("{}", v)).collect();
+        writer.write_row(&row)?;
+    }
+
+    writer.flush()
+}
+
+fn print_json(result: &QueryResult) {
+    match serde_json::to_string_pretty(&result.records()) {
+        Ok(e) => println!("{}", e),
+        Err(e) => {
+            print_error("Error serializing results to json.", Error::from(e));
+            std::process::exit(EXIT_FAILURE);
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let mut opt = Opt::from_args();
+    print_verbose(&format!("{:#?}", opt), opt.verbose);
+
+    let _timing = timing::report(opt.timing);
+
+    let supported_output =
+        [Output::Default, Output::Tsv, Output::Csv, Output::Json];
+    if let Err(e) = opt.output.check_supported(&supported_output) {
+        print_error("Invalid output format.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    if opt.porcelain {
+        opt.output = match opt.output.porcelain(&supported_output) {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Invalid output format.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+    }
+
+    let data_dir = match determine_data_dir(opt.data_dir) {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Error initializing storage directory store.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    if opt.print_paths {
+        datadir::print_paths(&data_dir);
+        return;
+    }
+
+    let sql = match (&opt.query, &opt.file) {
+        (Some(e), _) => e.to_string(),
+        (None, Some(f)) => match std::fs::read_to_string(f) {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Could not read query file.", e.into());
+                std::process::exit(EXIT_FAILURE);
+            }
+        },
+        //only listing views
+        (None, None) => String::new(),
+    };
+
+    let store = match ActivityStoreInterface::init_read_only_with_path(
+        &data_dir,
+        opt.verbose,
+    )
+    .await
+    {
+        Ok(e) => e,
+        Err(e) => {
+            print_error(
+                "Could not initialize activity store. Have you run dclias?",
+                e,
+            );
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    if opt.list_views {
+        let result = print_views(&store, opt.porcelain).await;
+
+        if let Err(e) = store.close().await {
+            print_error("Error closing activity store.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+
+        if let Err(e) = result {
+            print_error("Error retrieving views.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+        return;
+    }
+
+    let result = store.query(&sql).await;
+
+    if let Err(e) = store.close().await {
+        print_error("Error closing activity store.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    let result = match result {
+        Ok(e) => e,
+        Err(e) => {
+            print_error("Error running query.", e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    let _format_timer = timing::start(TimingCategory::Formatting);
+    match opt.output {
+        Output::Tsv => print_tsv(&result),
+        Output::Json => print_json(&result),
+        Output::Csv => {
+            if let Err(e) = write_csv(&result) {
+                print_error("Error writing csv.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        }
+        _ => print_default(&result),
+    }
+}
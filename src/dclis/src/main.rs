@@ -24,8 +24,9 @@ mod memberidsearch;
 
 use dcli::enums::platform::Platform;
 use dcli::output::Output;
+use dcli::timing::{self, TimingCategory};
 use dcli::utils::{
-    print_error, print_verbose, EXIT_FAILURE, TSV_DELIM, TSV_EOL,
+    print_error, print_message, print_verbose, EXIT_FAILURE, TSV_DELIM, TSV_EOL,
 };
 use memberidsearch::MemberIdSearch;
 use memberidsearch::Membership;
@@ -79,6 +80,13 @@ struct Opt {
     #[structopt(short = "v", long = "verbose")]
     verbose: bool,
 
+    /// Print how long was spent in API calls, SQL queries and formatting
+    ///
+    /// Timings are printed to stderr when the command finishes, and can help
+    /// diagnose slow commands.
+    #[structopt(long = "timing")]
+    timing: bool,
+
     /// Format for command output
     ///
     /// Valid values are default (Default) and tsv.
@@ -91,15 +99,43 @@ struct Opt {
         default_value = "default"
     )]
     output: Output,
+
+    /// Only write structured data to stdout
+    ///
+    /// All other messages (i.e. errors, or an invalid steam id) are written to
+    /// stderr. Uses tsv output.
+    #[structopt(long = "porcelain")]
+    porcelain: bool,
 }
 
 #[tokio::main]
 async fn main() {
-    let opt = Opt::from_args();
+    let mut opt = Opt::from_args();
     print_verbose(&format!("{:#?}", opt), opt.verbose);
 
+    let _timing = timing::report(opt.timing);
+
+    let supported_output = [Output::Default, Output::Tsv];
+    if let Err(e) = opt.output.check_supported(&supported_output) {
+        print_error("Invalid output format.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    if opt.porcelain {
+        opt.output = match opt.output.porcelain(&supported_output) {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Invalid output format.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+    }
+
     if opt.platform == Platform::Steam && !is_valid_steam_id(&opt.name) {
-        println!("Invalid steam 64 id. Must be a 17 digit Steam 64 ID.");
+        print_message(
+            "Invalid steam 64 id. Must be a 17 digit Steam 64 ID.",
+            opt.porcelain,
+        );
         return;
     }
 
@@ -152,6 +188,8 @@ async fn main() {
         };
     }
 
+    let _format_timer = timing::start(TimingCategory::Formatting);
+
     match opt.output {
         Output::Default => {
             print_default(&membership);
@@ -159,6 +197,8 @@ async fn main() {
         Output::Tsv => {
             print_tsv(&membership);
         }
+        //other formats are rejected at startup
+        _ => {}
     }
 }
 
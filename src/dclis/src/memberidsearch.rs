@@ -44,7 +44,7 @@ impl MemberIdSearch {
     ) -> Result<Option<Membership>, Error> {
         let url = format!(
             "https://www.bungie.net/Platform/User/GetMembershipFromHardLinkedCredential/12/{steam_id}/",
-            steam_id = utf8_percent_encode(&steam_id, NON_ALPHANUMERIC),
+            steam_id = utf8_percent_encode(steam_id, NON_ALPHANUMERIC),
         );
 
         let member = match self
@@ -72,13 +72,13 @@ impl MemberIdSearch {
         platform: Platform,
     ) -> Result<Option<Membership>, Error> {
         if platform == Platform::Steam {
-            return self.retrieve_member_id_from_steam(&id).await;
+            return self.retrieve_member_id_from_steam(id).await;
         }
 
         let url = format!(
             "https://www.bungie.net/Platform/Destiny2/SearchDestinyPlayer/{platform_id}/{id}/",
             platform_id = platform.to_id(),
-            id = utf8_percent_encode(&id, NON_ALPHANUMERIC),
+            id = utf8_percent_encode(id, NON_ALPHANUMERIC),
         );
 
         let mut results: Vec<DestinyResponseMember> = match self
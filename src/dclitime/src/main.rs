@@ -27,7 +27,7 @@ use std::str::FromStr;
 use datetimeformat::DateTimeFormat;
 use dcli::enums::moment::Moment;
 use dcli::output::Output;
-use dcli::utils::{build_tsv, print_verbose};
+use dcli::utils::{build_tsv, print_error, print_verbose, EXIT_FAILURE};
 use structopt::StructOpt;
 
 //we do a custom parse / validation here so we can reuse Moment enum
@@ -110,13 +110,35 @@ struct Opt {
         default_value = "default"
     )]
     output: Output,
+
+    /// Only write structured data to stdout
+    ///
+    /// All other messages (i.e. errors) are written to stderr. Uses tsv output.
+    #[structopt(long = "porcelain")]
+    porcelain: bool,
 }
 
 #[tokio::main]
 async fn main() {
-    let opt = Opt::from_args();
+    let mut opt = Opt::from_args();
     print_verbose(&format!("{:#?}", opt), opt.verbose);
 
+    let supported_output = [Output::Default, Output::Tsv];
+    if let Err(e) = opt.output.check_supported(&supported_output) {
+        print_error("Invalid output format.", e);
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    if opt.porcelain {
+        opt.output = match opt.output.porcelain(&supported_output) {
+            Ok(e) => e,
+            Err(e) => {
+                print_error("Invalid output format.", e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+    }
+
     let dt = opt.moment.get_date_time();
     let date_time_str = match opt.time_format {
         DateTimeFormat::RFC3339 => dt.to_rfc3339(),
@@ -136,5 +158,7 @@ async fn main() {
 
             print!("{}", build_tsv(name_values));
         }
+        //other formats are rejected at startup
+        _ => {}
     }
 }